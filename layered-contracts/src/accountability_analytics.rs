@@ -5,7 +5,7 @@
 //! surface verification queues for unresolved beneficiaries.
 
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use serde::Serialize;
 use serde_json::{self, Value};
@@ -14,7 +14,7 @@ use crate::accountability_graph::{BeneficiaryLink, ConditionLink, ObligationNode
 use crate::clause_aggregate::ClauseAggregateEntry;
 use crate::contract_clause::{ClauseCondition, ClauseDuty, ClauseParty};
 use crate::contract_keyword::ContractKeyword;
-use crate::obligation::ObligationType;
+use crate::obligation::{ObligationNature, ObligationType};
 use crate::utils::normalize_party_name;
 use crate::verification::{VerificationNote, VerificationTarget};
 use crate::Scored;
@@ -81,6 +81,17 @@ impl<'a> ObligationGraph<'a> {
         self.with_condition(|condition| condition.condition_type == keyword)
     }
 
+    /// Filter nodes with a clause of the given nature, e.g. to separate
+    /// performance duties from representations and warranties.
+    pub fn with_nature(&self, nature: ObligationNature) -> Vec<&'a Scored<ObligationNode>> {
+        self.filter_nodes(|node| {
+            node.value
+                .clauses
+                .iter()
+                .any(|clause| clause.duty.obligation_nature == nature)
+        })
+    }
+
     /// Filter nodes that reference a specific section label within conditions.
     pub fn referencing_section(&self, section_label: &str) -> Vec<&'a Scored<ObligationNode>> {
         let needle = section_label.to_lowercase();
@@ -370,6 +381,59 @@ pub struct PartyAnalytics<'a> {
     pub unassigned_nodes: Vec<&'a Scored<ObligationNode>>,
 }
 
+impl<'a> PartyAnalytics<'a> {
+    /// The party's clauses ordered from least to most conditional.
+    ///
+    /// Unconditional duties come first: they carry the most exposure for the
+    /// party. Ties keep clause order.
+    pub fn clauses_by_conditionality(&self) -> Vec<ClauseConditionality<'a>> {
+        let mut seen = HashSet::new();
+        let nodes = self
+            .beneficiary_groups
+            .iter()
+            .flat_map(|group| group.nodes.iter())
+            .chain(self.unassigned_nodes.iter())
+            .copied()
+            .filter(|node| seen.insert(node.value.node_id));
+
+        let mut clauses: Vec<ClauseConditionality<'a>> = nodes
+            .flat_map(|node| {
+                node.value.clauses.iter().map(move |clause| ClauseConditionality {
+                    node,
+                    clause,
+                    score: clause.conditionality_score(),
+                })
+            })
+            .collect();
+        clauses.sort_by(|a, b| {
+            a.score
+                .total_cmp(&b.score)
+                .then_with(|| a.clause.clause_id.cmp(&b.clause.clause_id))
+        });
+        clauses
+    }
+
+    /// Mean conditionality over the party's clauses, `None` without clauses.
+    pub fn mean_conditionality(&self) -> Option<f64> {
+        let clauses = self.clauses_by_conditionality();
+        if clauses.is_empty() {
+            return None;
+        }
+        Some(clauses.iter().map(|c| c.score).sum::<f64>() / clauses.len() as f64)
+    }
+}
+
+/// A party's clause with its conditionality score.
+#[derive(Debug, Clone, Copy)]
+pub struct ClauseConditionality<'a> {
+    /// Node the clause belongs to.
+    pub node: &'a Scored<ObligationNode>,
+    /// The clause itself.
+    pub clause: &'a ClauseAggregateEntry,
+    /// See [`ClauseAggregateEntry::conditionality_score`].
+    pub score: f64,
+}
+
 /// Group of obligations linked to a beneficiary.
 #[derive(Debug)]
 pub struct BeneficiaryGroup<'a> {
@@ -531,6 +595,7 @@ pub struct ConditionPayload {
 pub struct ClausePayload {
     pub clause_id: u32,
     pub obligation_type: String,
+    pub obligation_nature: String,
     pub action: String,
     pub conditions: Vec<ConditionPayload>,
     pub clause_confidence: f64,
@@ -605,6 +670,7 @@ impl From<&ClauseAggregateEntry> for ClausePayload {
         Self {
             clause_id: entry.clause_id,
             obligation_type: format_obligation_type(&entry.duty),
+            obligation_nature: format_obligation_nature(&entry.duty),
             action: entry.duty.action.clone(),
             conditions: entry
                 .conditions
@@ -636,6 +702,17 @@ fn format_obligation_type(duty: &ClauseDuty) -> String {
     .to_string()
 }
 
+fn format_obligation_nature(duty: &ClauseDuty) -> String {
+    match duty.obligation_nature {
+        ObligationNature::Performance => "Performance",
+        ObligationNature::Representation => "Representation",
+        ObligationNature::Warranty => "Warranty",
+        ObligationNature::Acknowledgment => "Acknowledgment",
+        ObligationNature::Covenant => "Covenant",
+    }
+    .to_string()
+}
+
 fn format_contract_keyword(keyword: &ContractKeyword) -> String {
     match keyword {
         ContractKeyword::Shall => "Shall",
@@ -648,6 +725,7 @@ fn format_contract_keyword(keyword: &ContractKeyword) -> String {
         ContractKeyword::Cannot => "Cannot",
         ContractKeyword::MayNot => "MayNot",
         ContractKeyword::WillNot => "WillNot",
+        ContractKeyword::PermissionIdiom => "PermissionIdiom",
         ContractKeyword::Means => "Means",
         ContractKeyword::Includes => "Includes",
         ContractKeyword::Hereinafter => "Hereinafter",
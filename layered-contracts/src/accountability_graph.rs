@@ -222,13 +222,18 @@ impl AccountabilityGraphResolver {
             .collect()
     }
 
+    /// Adjust the aggregate confidence for beneficiary linkage.
+    ///
+    /// The node starts from the aggregate's confidence, which already combines
+    /// its clauses (see [`crate::ConfidencePropagation`]), so weak clauses
+    /// carry through to the node. The aggregate's breakdown leads the node's.
     fn calculate_confidence(
         &self,
-        aggregate_confidence: f64,
+        aggregate: &Scored<ClauseAggregate>,
         beneficiaries: &[BeneficiaryLink],
     ) -> (f64, Vec<String>) {
-        let mut confidence = aggregate_confidence;
-        let mut breakdown = vec![format!("Layer8 aggregate: {:.2}", aggregate_confidence)];
+        let mut confidence = aggregate.confidence;
+        let mut breakdown = aggregate.value.confidence_breakdown.clone();
         if beneficiaries.iter().any(|b| b.needs_verification) {
             confidence -= self.unresolved_beneficiary_penalty;
             breakdown.push(format!(
@@ -398,8 +403,7 @@ impl AccountabilityGraphResolver {
                 seen.insert(key, true).is_none()
             });
 
-            let (confidence, breakdown) =
-                self.calculate_confidence(aggregate.confidence, &beneficiaries);
+            let (confidence, breakdown) = self.calculate_confidence(aggregate, &beneficiaries);
 
             let node = ObligationNode {
                 node_id: aggregate.value.aggregate_id,
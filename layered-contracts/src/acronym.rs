@@ -0,0 +1,282 @@
+//! Acronym definitions and their later uses.
+//!
+//! ```text
+//! The Provider shall meet the Service Level Agreement ("SLA"). Each SLA
+//! breach is reported monthly.
+//! ```
+//!
+//! [`AcronymResolver`] finds an acronym in parentheses directly after a phrase
+//! whose initials spell it, registers the acronym as an alias of that phrase,
+//! and links every later use of the acronym to the definition.
+//!
+//! The quoted form is also found by `DefinedTermResolver` as a parenthetical
+//! definition. Matching the letters against the phrase is stronger evidence
+//! than the quotes alone, so acronym definitions score higher; an unquoted
+//! `Service Level Agreement (SLA)` is only found here.
+
+use layered_nlp_document::{DocumentResolver, LayeredDocument};
+
+use crate::utils::content_tokens;
+use crate::{DocSpan, Scored};
+
+/// Words an acronym may skip ("Statement of Work" is "SOW").
+const CONNECTORS: &[&str] = &["a", "an", "and", "for", "in", "of", "on", "the", "to"];
+
+/// Whether an [`Acronym`] is the definition or a later use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AcronymKind {
+    /// `Service Level Agreement ("SLA")`
+    Definition,
+    /// A later "SLA"
+    Reference,
+}
+
+/// An acronym defined as, or linked to, the phrase it abbreviates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Acronym {
+    /// The acronym as defined ("SLA")
+    pub acronym: String,
+    /// The phrase it abbreviates ("Service Level Agreement")
+    pub expansion: String,
+    pub kind: AcronymKind,
+    /// This occurrence of the acronym
+    pub span: DocSpan,
+    /// The acronym in the parentheses of its definition
+    pub definition_span: DocSpan,
+    /// The abbreviated phrase at the definition
+    pub expansion_span: DocSpan,
+}
+
+/// Document-level resolver for acronym definitions and references.
+///
+/// A definition is a parenthesized acronym, optionally quoted, after a phrase
+/// whose words' initial letters spell it, ignoring case and skipping
+/// connectors such as "of". A parenthesis whose letters do not match is not an
+/// acronym definition. References are later exact uses of the acronym, or its
+/// plural ("SLAs"); a redefined acronym links to its latest definition.
+///
+/// Needs no other resolver to have run.
+#[derive(Debug, Clone)]
+pub struct AcronymResolver {
+    /// Confidence of a quoted acronym definition
+    quoted_confidence: f64,
+    /// Confidence of an unquoted acronym definition
+    unquoted_confidence: f64,
+    /// Confidence of a later use
+    reference_confidence: f64,
+}
+
+impl Default for AcronymResolver {
+    fn default() -> Self {
+        Self {
+            quoted_confidence: 0.95,
+            unquoted_confidence: 0.85,
+            reference_confidence: 0.85,
+        }
+    }
+}
+
+/// Whether `text` looks like an acronym: 2 to 10 letters or digits, starting
+/// with a capital and with at least two capitals ("SLA", "SaaS", "B2B").
+fn is_acronym_like(text: &str) -> bool {
+    let len = text.chars().count();
+    (2..=10).contains(&len)
+        && text.chars().all(|c| c.is_ascii_alphanumeric())
+        && text.starts_with(|c: char| c.is_ascii_uppercase())
+        && text.chars().filter(|c| c.is_ascii_uppercase()).count() >= 2
+}
+
+impl AcronymResolver {
+    /// Create a new resolver with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the confidence of quoted and unquoted acronym definitions.
+    pub fn with_definition_confidence(mut self, quoted: f64, unquoted: f64) -> Self {
+        self.quoted_confidence = quoted;
+        self.unquoted_confidence = unquoted;
+        self
+    }
+
+    /// Set the confidence of later uses of an acronym.
+    pub fn with_reference_confidence(mut self, confidence: f64) -> Self {
+        self.reference_confidence = confidence;
+        self
+    }
+
+    /// The first word of the phrase before `tokens[..paren]` spelling
+    /// `acronym`, if its initials do.
+    ///
+    /// Letters are matched from the end of the acronym against the words
+    /// before the parenthesis; a connector that does not supply the next
+    /// letter is skipped, and any other mismatch rejects the acronym.
+    fn expansion_start(
+        tokens: &[(usize, &str, bool)],
+        paren: usize,
+        acronym: &str,
+    ) -> Option<usize> {
+        let letters: Vec<char> = acronym
+            .chars()
+            .filter(|c| c.is_ascii_alphabetic())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        let mut remaining = letters.len();
+        let mut start = None;
+        let mut i = paren;
+        while remaining > 0 && i > 0 {
+            i -= 1;
+            let (_, word, is_word) = tokens[i];
+            if !is_word {
+                return None;
+            }
+            let initial = word.chars().next().map(|c| c.to_ascii_lowercase());
+            if initial == Some(letters[remaining - 1]) {
+                remaining -= 1;
+                start = Some(i);
+            } else if !CONNECTORS.contains(&word.to_lowercase().as_str()) {
+                return None;
+            }
+        }
+        start.filter(|_| remaining == 0 && letters.len() >= 2)
+    }
+
+    /// The definitions on one line: (acronym index, expansion start, quoted).
+    fn line_definitions(tokens: &[(usize, &str, bool)]) -> Vec<(usize, usize, bool)> {
+        let mut definitions = Vec::new();
+        for paren in (0..tokens.len()).filter(|&i| tokens[i].1 == "(") {
+            let quoted = tokens.get(paren + 1).is_some_and(|token| token.1 == "\"");
+            let acronym = paren + 1 + usize::from(quoted);
+            let closing = acronym + 1 + usize::from(quoted);
+            let well_formed = tokens.get(acronym).is_some_and(|token| is_acronym_like(token.1))
+                && (!quoted || tokens.get(acronym + 1).is_some_and(|token| token.1 == "\""))
+                && tokens.get(closing).is_some_and(|token| token.1 == ")");
+            if !well_formed {
+                continue;
+            }
+            if let Some(start) = Self::expansion_start(tokens, paren, tokens[acronym].1) {
+                definitions.push((acronym, start, quoted));
+            }
+        }
+        definitions
+    }
+}
+
+impl DocumentResolver for AcronymResolver {
+    type Attr = Scored<Acronym>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        // Acronym -> (expansion, definition span, expansion span), latest definition wins
+        let mut defined: Vec<(String, String, DocSpan, DocSpan)> = Vec::new();
+        let mut results = Vec::new();
+
+        for (line_index, line) in doc.lines_enumerated() {
+            let tokens = content_tokens(line);
+            let definitions = Self::line_definitions(&tokens);
+
+            for (i, &(token, text, _)) in tokens.iter().enumerate() {
+                let span = DocSpan::single_line(line_index, token, token);
+
+                if let Some(&(_, start, quoted)) =
+                    definitions.iter().find(|(acronym, _, _)| *acronym == i)
+                {
+                    let paren = i - 1 - usize::from(quoted);
+                    let words: Vec<&str> = tokens[start..paren].iter().map(|t| t.1).collect();
+                    let expansion_span =
+                        DocSpan::single_line(line_index, tokens[start].0, tokens[paren - 1].0);
+                    let acronym = Acronym {
+                        acronym: text.to_string(),
+                        expansion: words.join(" "),
+                        kind: AcronymKind::Definition,
+                        span,
+                        definition_span: span,
+                        expansion_span,
+                    };
+                    defined.retain(|(name, ..)| name != text);
+                    let expansion = acronym.expansion.clone();
+                    defined.push((text.to_string(), expansion, span, expansion_span));
+                    let confidence =
+                        if quoted { self.quoted_confidence } else { self.unquoted_confidence };
+                    results.push(Scored::rule_based(acronym, confidence, "acronym_definition"));
+                    continue;
+                }
+
+                let definition = defined.iter().find(|(name, ..)| {
+                    text == name || text.strip_suffix('s') == Some(name.as_str())
+                });
+                if let Some((name, expansion, definition_span, expansion_span)) = definition {
+                    results.push(Scored::rule_based(
+                        Acronym {
+                            acronym: name.clone(),
+                            expansion: expansion.clone(),
+                            kind: AcronymKind::Reference,
+                            span,
+                            definition_span: *definition_span,
+                            expansion_span: *expansion_span,
+                        },
+                        self.reference_confidence,
+                        "acronym_reference",
+                    ));
+                }
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn acronyms(text: &str) -> Vec<(String, String, AcronymKind, usize)> {
+        let doc = LayeredDocument::from_text(text);
+        AcronymResolver::new()
+            .resolve(&doc)
+            .into_iter()
+            .map(|scored| {
+                let a = scored.value;
+                (a.acronym, a.expansion, a.kind, a.span.start.line)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_definition_links_later_uses() {
+        let found = acronyms(
+            "The Provider shall meet the Service Level Agreement (\"SLA\").\n\
+             Each SLA breach and all SLAs are reported under the Statement of Work (SOW).",
+        );
+        let sla = |kind, line| {
+            ("SLA".to_string(), "Service Level Agreement".to_string(), kind, line)
+        };
+        assert_eq!(
+            found,
+            vec![
+                sla(AcronymKind::Definition, 0),
+                sla(AcronymKind::Reference, 1),
+                sla(AcronymKind::Reference, 1),
+                ("SOW".to_string(), "Statement of Work".to_string(), AcronymKind::Definition, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mismatched_initials_are_not_acronyms() {
+        assert!(acronyms("The Company (\"ABC\") shall pay the Fees (USD).").is_empty());
+        // Letters must match every word back to the phrase's first initial
+        assert!(acronyms("The Master Service Agreement (MSX) applies.").is_empty());
+        assert!(acronyms("An SLA is defined later.").is_empty());
+    }
+
+    #[test]
+    fn test_quoted_definitions_score_higher() {
+        let doc = LayeredDocument::from_text(
+            "The Service Level Agreement (\"SLA\") and the Key Performance Indicators (KPI).",
+        );
+        let found = AcronymResolver::new().resolve(&doc);
+        assert_eq!(found.len(), 2);
+        assert!(found[0].confidence > found[1].confidence);
+        assert_eq!(found[1].value.expansion, "Key Performance Indicators");
+    }
+}
@@ -0,0 +1,285 @@
+//! Agreement between two pipeline configurations.
+//!
+//! When a resolver changes, running the old and new configurations over the
+//! same corpus and comparing their annotations shows how much the change
+//! perturbed the output, without needing gold labels. [`agreement`] treats
+//! the two runs as two annotators: annotations are aligned by type and exact
+//! span, and each type gets Cohen's kappa over token coverage.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::snapshot::{Snapshot, SnapshotDocSpan, SpanData};
+use crate::ContractDocument;
+
+/// Agreement between two runs for one annotation type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAgreement {
+    /// Snapshot type name ("ObligationPhrase", "TermReference")
+    pub type_name: String,
+    /// Annotations found by both runs at the same span
+    pub matched: usize,
+    /// Matched annotations whose values differ
+    pub value_changed: usize,
+    /// Annotations only the first run produced
+    pub only_a: usize,
+    /// Annotations only the second run produced
+    pub only_b: usize,
+    /// Cohen's kappa over tokens: each token is labelled by whether an
+    /// annotation of this type covers it in each run
+    pub kappa: f64,
+    /// Token coverage table: (covered in both, only in a, only in b, in neither)
+    coverage: [usize; 4],
+}
+
+impl TypeAgreement {
+    /// Share of annotations found at the same span by both runs, in `0.0..=1.0`.
+    ///
+    /// The F1 of one run scored against the other; 1.0 when neither run
+    /// produced any.
+    pub fn span_agreement(&self) -> f64 {
+        let total = 2 * self.matched + self.only_a + self.only_b;
+        if total == 0 {
+            1.0
+        } else {
+            (2 * self.matched) as f64 / total as f64
+        }
+    }
+
+    /// Whether both runs produced exactly the same annotations.
+    pub fn is_identical(&self) -> bool {
+        self.only_a == 0 && self.only_b == 0 && self.value_changed == 0
+    }
+}
+
+/// Per-type agreement between two runs over a corpus.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgreementReport {
+    /// Every type either run produced, by type name
+    pub types: Vec<TypeAgreement>,
+    /// Document pairs compared
+    pub documents: usize,
+}
+
+impl AgreementReport {
+    /// Agreement for one type, if either run produced it.
+    pub fn get(&self, type_name: &str) -> Option<&TypeAgreement> {
+        self.types.iter().find(|agreement| agreement.type_name == type_name)
+    }
+
+    /// Cohen's kappa with the coverage of all types pooled: a single number
+    /// for how much a change perturbed the output. 1.0 when the runs agree.
+    pub fn kappa(&self) -> f64 {
+        let mut pooled = [0; 4];
+        for agreement in &self.types {
+            for (total, cell) in pooled.iter_mut().zip(agreement.coverage) {
+                *total += cell;
+            }
+        }
+        cohens_kappa(pooled)
+    }
+
+    /// Types whose annotations differ between the runs.
+    pub fn changed(&self) -> impl Iterator<Item = &TypeAgreement> {
+        self.types.iter().filter(|agreement| !agreement.is_identical())
+    }
+}
+
+/// Compare the standard snapshot annotations of two runs over the same corpus.
+///
+/// `docs_a[i]` is compared with `docs_b[i]`; the pairs should be the same
+/// text analyzed by the two configurations. Documents without a partner in
+/// the other slice are ignored.
+pub fn agreement(docs_a: &[ContractDocument], docs_b: &[ContractDocument]) -> AgreementReport {
+    let mut counts: BTreeMap<String, TypeAgreement> = BTreeMap::new();
+    let mut documents = 0;
+
+    for (doc_a, doc_b) in docs_a.iter().zip(docs_b) {
+        documents += 1;
+        let line_lengths: Vec<usize> =
+            doc_a.lines().iter().map(|line| line.ll_tokens().len()).collect();
+        let tokens: usize = line_lengths.iter().sum();
+        let snapshot_a = Snapshot::from_document(doc_a);
+        let snapshot_b = Snapshot::from_document(doc_b);
+
+        let mut type_names: Vec<&String> =
+            snapshot_a.spans.keys().chain(snapshot_b.spans.keys()).collect();
+        type_names.sort();
+        type_names.dedup();
+        for type_name in type_names {
+            let spans_a = snapshot_a.spans.get(type_name).map_or(&[][..], Vec::as_slice);
+            let spans_b = snapshot_b.spans.get(type_name).map_or(&[][..], Vec::as_slice);
+            let entry = counts.entry(type_name.clone()).or_insert_with(|| TypeAgreement {
+                type_name: type_name.clone(),
+                matched: 0,
+                value_changed: 0,
+                only_a: 0,
+                only_b: 0,
+                kappa: 1.0,
+                coverage: [0; 4],
+            });
+            align(spans_a, spans_b, entry);
+
+            let covered_a = coverage(spans_a, &line_lengths);
+            let covered_b = coverage(spans_b, &line_lengths);
+            let both = covered_a.intersection(&covered_b).count();
+            let only_a = covered_a.len() - both;
+            let only_b = covered_b.len() - both;
+            entry.coverage[0] += both;
+            entry.coverage[1] += only_a;
+            entry.coverage[2] += only_b;
+            entry.coverage[3] += tokens.saturating_sub(both + only_a + only_b);
+        }
+    }
+
+    let types = counts
+        .into_values()
+        .map(|mut agreement| {
+            agreement.kappa = cohens_kappa(agreement.coverage);
+            agreement
+        })
+        .collect();
+    AgreementReport { types, documents }
+}
+
+/// Pair annotations at the same span, preferring pairs with equal values.
+fn align(spans_a: &[SpanData], spans_b: &[SpanData], agreement: &mut TypeAgreement) {
+    let mut by_span: HashMap<SnapshotDocSpan, (Vec<&SpanData>, Vec<&SpanData>)> = HashMap::new();
+    for span in spans_a {
+        by_span.entry(span.position).or_default().0.push(span);
+    }
+    for span in spans_b {
+        by_span.entry(span.position).or_default().1.push(span);
+    }
+
+    for (at_a, at_b) in by_span.into_values() {
+        let matched = at_a.len().min(at_b.len());
+        let mut unpaired_b = at_b.clone();
+        let mut equal = 0;
+        for span in &at_a {
+            if let Some(idx) = unpaired_b.iter().position(|other| other.value == span.value) {
+                unpaired_b.swap_remove(idx);
+                equal += 1;
+            }
+        }
+        agreement.matched += matched;
+        agreement.value_changed += matched - equal;
+        agreement.only_a += at_a.len() - matched;
+        agreement.only_b += at_b.len() - matched;
+    }
+}
+
+/// Tokens covered by any of the spans, keyed by (line, token).
+fn coverage(spans: &[SpanData], line_lengths: &[usize]) -> HashSet<(usize, usize)> {
+    let mut covered = HashSet::new();
+    for span in spans {
+        let (start, end) = (span.position.start, span.position.end);
+        for line in start.line as usize..=end.line as usize {
+            let first = if line == start.line as usize { start.token as usize } else { 0 };
+            let last = if line == end.line as usize {
+                end.token as usize
+            } else {
+                line_lengths.get(line).map_or(0, |len| len.saturating_sub(1))
+            };
+            for token in first..=last {
+                covered.insert((line, token));
+            }
+        }
+    }
+    covered
+}
+
+/// Cohen's kappa from a 2x2 table (both, only first, only second, neither).
+///
+/// When chance agreement is total (both raters label every item the same
+/// way) kappa is undefined; it is reported as 1.0 if the raters agree.
+fn cohens_kappa([both, only_a, only_b, neither]: [usize; 4]) -> f64 {
+    let total = (both + only_a + only_b + neither) as f64;
+    if total == 0.0 {
+        return 1.0;
+    }
+    let observed = (both + neither) as f64 / total;
+    let yes_a = (both + only_a) as f64 / total;
+    let yes_b = (both + only_b) as f64 / total;
+    let chance = yes_a * yes_b + (1.0 - yes_a) * (1.0 - yes_b);
+    if (1.0 - chance).abs() < f64::EPSILON {
+        if observed >= 1.0 { 1.0 } else { 0.0 }
+    } else {
+        (observed - chance) / (1.0 - chance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver, ProhibitionResolver,
+        PronounResolver, TermReferenceResolver,
+    };
+    use layered_part_of_speech::POSTagResolver;
+
+    const CONTRACT: &str = "ABC Corp (the \"Company\") shall deliver the goods.\n\
+        The relevant authority shall approve the plan.";
+
+    fn analyze(text: &str, obligations: Option<ObligationPhraseResolver>) -> ContractDocument {
+        let doc = ContractDocument::from_text(text)
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ProhibitionResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+            .run_resolver(&TermReferenceResolver::default())
+            .run_resolver(&PronounResolver::default());
+        match obligations {
+            Some(resolver) => doc.run_resolver(&resolver),
+            None => doc,
+        }
+    }
+
+    #[test]
+    fn test_identical_runs_agree() {
+        let docs = [analyze(CONTRACT, Some(ObligationPhraseResolver::default()))];
+        let report = agreement(&docs, &docs);
+
+        assert_eq!(report.documents, 1);
+        assert!(report.changed().next().is_none());
+        assert!((report.kappa() - 1.0).abs() < 1e-9);
+        let obligations = report.get("ObligationPhrase").unwrap();
+        assert_eq!(obligations.matched, 2);
+        assert!((obligations.span_agreement() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_missing_and_changed_annotations() {
+        let baseline = [analyze(CONTRACT, Some(ObligationPhraseResolver::default()))];
+        let removed = [analyze(CONTRACT, None)];
+        let report = agreement(&baseline, &removed);
+
+        let obligations = report.get("ObligationPhrase").unwrap();
+        assert_eq!((obligations.matched, obligations.only_a, obligations.only_b), (0, 2, 0));
+        assert_eq!(obligations.span_agreement(), 0.0);
+        assert!(obligations.kappa.abs() < 1e-9, "no overlap beyond chance: {}", obligations.kappa);
+        let changed: Vec<_> = report.changed().map(|t| t.type_name.as_str()).collect();
+        assert_eq!(changed, ["ObligationPhrase"]);
+        assert!(report.kappa() < 1.0);
+
+        // Same spans; the vague obligor's confidence breakdown differs
+        let unpenalized = [analyze(
+            CONTRACT,
+            Some(ObligationPhraseResolver::default().with_unresolved_obligor_penalty(0.0)),
+        )];
+        let report = agreement(&baseline, &unpenalized);
+        let obligations = report.get("ObligationPhrase").unwrap();
+        assert_eq!((obligations.matched, obligations.value_changed), (2, 1));
+        assert_eq!((obligations.only_a, obligations.only_b), (0, 0));
+        assert!(!obligations.is_identical());
+        assert!((obligations.kappa - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cohens_kappa() {
+        // 15 of 20 items agreed on; chance agreement is 0.5
+        let kappa = cohens_kappa([8, 2, 3, 7]);
+        assert!((kappa - 0.5).abs() < 0.01, "{}", kappa);
+        assert_eq!(cohens_kappa([0, 0, 0, 10]), 1.0);
+        assert_eq!(cohens_kappa([0, 10, 0, 0]), 0.0);
+    }
+}
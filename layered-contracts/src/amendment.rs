@@ -0,0 +1,635 @@
+//! Amendment instructions.
+//!
+//! ```text
+//! 1. Section 3.1 of the Agreement is hereby deleted in its entirety.
+//! 2. Section 4 is hereby amended and restated to read as follows:
+//! "4. Term. This Agreement shall continue for two (2) years."
+//! 3. In Section 5.2, "thirty (30) days" is replaced with "sixty (60) days".
+//! ```
+//!
+//! An amendment names sections of a base contract and says how to change
+//! them. [`AmendmentResolver`] reads the instructions from the amendment;
+//! [`AmendmentInstruction::resolve_target`] finds the named section in the
+//! base contract, and [`apply_amendments`] produces the amended contract.
+
+use std::collections::HashSet;
+
+use layered_nlp::{x, LLLine, LToken};
+use layered_nlp_document::{DocumentResolver, LayeredDocument};
+
+use crate::document_structure::DocumentStructureExt;
+use crate::section_reference::{ReferenceType, SectionReference};
+use crate::section_reference_linker::{ReferenceResolution, SectionReferenceLinker};
+use crate::utils::line_text;
+use crate::{ContractDocument, DocSpan, Scored};
+
+/// How an instruction changes its target section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmendmentOperation {
+    /// "is hereby deleted" - the section is removed
+    Delete,
+    /// "replace 'X' with 'Y'" - text within the section is substituted
+    Replace,
+    /// "is amended by adding the following" - text is inserted
+    Add,
+    /// "is amended and restated to read as follows" - the section is rewritten
+    AmendAndRestate,
+}
+
+impl AmendmentOperation {
+    /// Whether the instruction supplies text ("as follows: ...").
+    pub fn expects_new_text(self) -> bool {
+        !matches!(self, AmendmentOperation::Delete)
+    }
+}
+
+/// An instruction modifying a section of the base contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmendmentInstruction {
+    /// The section being amended
+    pub target: SectionReference,
+    /// Where the target reference appears in the amendment
+    pub target_span: DocSpan,
+    /// How the section changes
+    pub operation: AmendmentOperation,
+    /// For [`AmendmentOperation::Replace`], the text being replaced
+    pub replaced_text: Option<String>,
+    /// The text to replace, insert or restate with; `None` for deletions and
+    /// when the amendment does not give it
+    pub new_text: Option<String>,
+}
+
+impl AmendmentInstruction {
+    /// Resolve the target section in the base contract.
+    ///
+    /// The base must have been processed with `SectionHeaderResolver`.
+    pub fn resolve_target(&self, base: &ContractDocument) -> ReferenceResolution {
+        SectionReferenceLinker::resolve(&self.target, base.structure())
+    }
+}
+
+/// Confidence of an instruction with all its parts.
+const INSTRUCTION_CONFIDENCE: f64 = 0.85;
+
+/// Confidence multiplier for an instruction missing its new text.
+const MISSING_TEXT_FACTOR: f64 = 0.6;
+
+/// Document-level resolver for amendment instructions.
+///
+/// A line is an instruction when it has a section reference naming a section
+/// ("Section 3.1", "Section 3.1 of the Agreement") and an operation keyword
+/// outside quotation marks. The new text is the quoted text on the line, the
+/// text after a colon, or the lines that follow: a quotation spanning lines,
+/// or the paragraph up to the next instruction.
+///
+/// Requires `SectionHeaderResolver` and `SectionReferenceResolver` to have run
+/// on the amendment's lines.
+#[derive(Debug, Clone, Default)]
+pub struct AmendmentResolver;
+
+/// A line token for instruction matching.
+struct Word {
+    idx: usize,
+    text: String,
+    quoted: bool,
+}
+
+/// A line recognized as an instruction, before its new text is collected.
+struct Candidate {
+    line: usize,
+    target: SectionReference,
+    target_span: DocSpan,
+    operation: AmendmentOperation,
+    /// Token index of the operation keyword
+    keyword: usize,
+}
+
+impl AmendmentResolver {
+    /// Create a new resolver.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// The line's tokens, lowercased, marked when inside quotation marks.
+    fn words(line: &LLLine) -> Vec<Word> {
+        let mut words = Vec::new();
+        let mut quoted = false;
+        for (idx, token) in line.ll_tokens().iter().enumerate() {
+            let text = match token.get_token() {
+                LToken::Text(text, _) => text,
+                LToken::Value => continue,
+            };
+            match text.as_str() {
+                "\"" => quoted = !quoted,
+                "“" => quoted = true,
+                "”" => quoted = false,
+                _ => words.push(Word { idx, text: text.to_lowercase(), quoted }),
+            }
+        }
+        words
+    }
+
+    /// The operation named on a line, with the token index of its keyword.
+    fn operation(words: &[Word]) -> Option<(AmendmentOperation, usize)> {
+        let unquoted: Vec<&Word> = words.iter().filter(|word| !word.quoted).collect();
+        let find = |keywords: &[&str]| {
+            unquoted.iter().find(|word| keywords.contains(&word.text.as_str())).map(|w| w.idx)
+        };
+
+        if let Some(idx) = find(&["restated", "restate"]) {
+            return Some((AmendmentOperation::AmendAndRestate, idx));
+        }
+        // "deleting 'X' and replacing it with 'Y'" is a replacement
+        if let Some(idx) = find(&["replace", "replaced", "replacing", "substitute", "substituting"])
+        {
+            return Some((AmendmentOperation::Replace, idx));
+        }
+        if let Some(idx) = find(&["add", "added", "adding", "insert", "inserted", "inserting"]) {
+            return Some((AmendmentOperation::Add, idx));
+        }
+        // "is hereby amended to read as follows"
+        let to_read = unquoted
+            .windows(3)
+            .find(|w| w[0].text == "to" && w[1].text.trim().is_empty() && w[2].text == "read");
+        if let (Some(_), Some(idx)) = (to_read, find(&["amended"])) {
+            return Some((AmendmentOperation::AmendAndRestate, idx));
+        }
+        find(&["delete", "deleted", "deleting", "strike", "struck", "striking"])
+            .map(|idx| (AmendmentOperation::Delete, idx))
+    }
+
+    /// Quoted passages on a line as (first token index, text).
+    fn quotations(line: &LLLine) -> Vec<(usize, String)> {
+        let mut quotations = Vec::new();
+        let mut current: Option<(usize, String)> = None;
+        for (idx, token) in line.ll_tokens().iter().enumerate() {
+            let text = match token.get_token() {
+                LToken::Text(text, _) => text.as_str(),
+                LToken::Value => continue,
+            };
+            let opens = text == "“" || (text == "\"" && current.is_none());
+            let closes = text == "”" || (text == "\"" && current.is_some());
+            if opens {
+                current = Some((idx, String::new()));
+            } else if closes {
+                if let Some((start, quoted)) = current.take() {
+                    quotations.push((start, quoted));
+                }
+            } else if let Some((_, quoted)) = current.as_mut() {
+                quoted.push_str(text);
+            }
+        }
+        quotations
+    }
+
+    /// Text after the first colon outside quotation marks, if any.
+    fn after_colon(line: &LLLine, words: &[Word]) -> Option<String> {
+        let colon = words.iter().find(|word| word.text == ":" && !word.quoted)?;
+        let rest: String = line.ll_tokens()[colon.idx + 1..]
+            .iter()
+            .filter_map(|token| match token.get_token() {
+                LToken::Text(text, _) => Some(text.as_str()),
+                LToken::Value => None,
+            })
+            .collect();
+        let rest = trim_quotes(&rest);
+        if rest.is_empty() {
+            None
+        } else {
+            Some(rest.to_string())
+        }
+    }
+
+    /// New text given on the lines after an instruction: a quotation up to
+    /// its closing mark, otherwise the lines up to the next instruction or
+    /// paragraph break.
+    fn following_text(
+        doc: &LayeredDocument,
+        line_idx: usize,
+        instruction_lines: &HashSet<usize>,
+    ) -> Option<String> {
+        let first = doc.lines().get(line_idx + 1).map(line_text)?;
+        let quoted = first.trim_start().starts_with(['"', '“']);
+
+        let mut lines: Vec<String> = Vec::new();
+        for (idx, line) in doc.lines_enumerated().skip(line_idx + 1) {
+            if instruction_lines.contains(&idx) {
+                break;
+            }
+            let text = line_text(line);
+            let text = text.trim();
+            if !quoted && !lines.is_empty() && Self::paragraph_break(doc, idx) {
+                break;
+            }
+            lines.push(text.to_string());
+            let closed = text.ends_with(['"', '”'])
+                || text.ends_with("\".")
+                || text.ends_with("”.");
+            if quoted && closed && (lines.len() > 1 || text.len() > 1) {
+                break;
+            }
+        }
+
+        let joined = lines.join("\n");
+        let joined = trim_quotes(joined.trim_end_matches('.'));
+        if joined.is_empty() {
+            None
+        } else {
+            Some(joined.to_string())
+        }
+    }
+
+    /// Whether a blank source line precedes line `idx`.
+    fn paragraph_break(doc: &LayeredDocument, idx: usize) -> bool {
+        match (doc.source_line_number(idx - 1), doc.source_line_number(idx)) {
+            (Some(previous), Some(current)) => current > previous + 1,
+            _ => false,
+        }
+    }
+}
+
+impl DocumentResolver for AmendmentResolver {
+    type Attr = Scored<AmendmentInstruction>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        let mut candidates = Vec::new();
+        for (line_idx, line) in doc.lines_enumerated() {
+            let words = Self::words(line);
+            let (operation, keyword) = match Self::operation(&words) {
+                Some(found) => found,
+                None => continue,
+            };
+            // Not the linker's references: it filters out references at the start
+            // of a line as section headers, and "Section 3 is hereby deleted" is
+            // not a header
+            let target = line
+                .find(&x::attr::<SectionReference>())
+                .into_iter()
+                .filter(|found| {
+                    found.attr().target.is_some()
+                        && !matches!(found.attr().reference_type, ReferenceType::Relative(_))
+                })
+                .min_by_key(|found| found.token_range().0);
+            if let Some(found) = target {
+                candidates.push(Candidate {
+                    line: line_idx,
+                    target: (*found.attr()).clone(),
+                    target_span: DocSpan::from_find(line_idx, &found),
+                    operation,
+                    keyword,
+                });
+            }
+        }
+        let instruction_lines: HashSet<usize> = candidates.iter().map(|c| c.line).collect();
+
+        candidates
+            .into_iter()
+            .map(|candidate| {
+                let line = &doc.lines()[candidate.line];
+                let words = Self::words(line);
+                let quotations: Vec<String> = Self::quotations(line)
+                    .into_iter()
+                    .filter(|(start, _)| *start > candidate.keyword)
+                    .map(|(_, text)| text)
+                    .collect();
+
+                let (replaced_text, new_text) = match candidate.operation {
+                    AmendmentOperation::Delete => (None, None),
+                    // "replace 'X' with 'Y'" or "'X' is replaced with 'Y'"
+                    AmendmentOperation::Replace => {
+                        let all: Vec<String> =
+                            Self::quotations(line).into_iter().map(|(_, text)| text).collect();
+                        match all.as_slice() {
+                            [old, new, ..] => (Some(old.clone()), Some(new.clone())),
+                            [new] => (None, Some(new.clone())),
+                            [] => (None, None),
+                        }
+                    }
+                    AmendmentOperation::Add | AmendmentOperation::AmendAndRestate => {
+                        let new_text = quotations
+                            .into_iter()
+                            .next()
+                            .or_else(|| Self::after_colon(line, &words))
+                            .or_else(|| {
+                                Self::following_text(doc, candidate.line, &instruction_lines)
+                            });
+                        (None, new_text)
+                    }
+                };
+
+                let mut confidence = INSTRUCTION_CONFIDENCE;
+                if candidate.operation.expects_new_text() && new_text.is_none() {
+                    confidence *= MISSING_TEXT_FACTOR;
+                }
+                Scored::rule_based(
+                    AmendmentInstruction {
+                        target: candidate.target,
+                        target_span: candidate.target_span,
+                        operation: candidate.operation,
+                        replaced_text,
+                        new_text,
+                    },
+                    confidence,
+                    "amendment_instruction",
+                )
+            })
+            .collect()
+    }
+}
+
+/// Why an amendment could not be applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AmendmentError {
+    /// The target section is not in the base contract
+    UnresolvedTarget {
+        reference: String,
+        location: DocSpan,
+        reason: String,
+    },
+    /// Two instructions change the same section (or one changes a section
+    /// containing the other's)
+    Conflict {
+        section: String,
+        first: DocSpan,
+        second: DocSpan,
+    },
+    /// The instruction does not give the text it needs (the new text, or the
+    /// text being replaced)
+    MissingText {
+        section: String,
+        location: DocSpan,
+        operation: AmendmentOperation,
+    },
+    /// The text to replace does not occur in the target section
+    TextNotFound { section: String, text: String },
+}
+
+impl std::fmt::Display for AmendmentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AmendmentError::UnresolvedTarget { reference, location, reason } => write!(
+                f,
+                "Line {}: cannot find '{}' in the base contract: {}",
+                location.start.line, reference, reason
+            ),
+            AmendmentError::Conflict { section, first, second } => write!(
+                f,
+                "Lines {} and {} both amend section '{}'",
+                first.start.line, second.start.line, section
+            ),
+            AmendmentError::MissingText { section, location, operation } => write!(
+                f,
+                "Line {}: {:?} of section '{}' does not give its text",
+                location.start.line, operation, section
+            ),
+            AmendmentError::TextNotFound { section, text } => {
+                write!(f, "Section '{}' does not contain '{}'", section, text)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AmendmentError {}
+
+/// An instruction located in the base contract's source lines.
+struct Edit<'a> {
+    instruction: &'a AmendmentInstruction,
+    section: String,
+    /// First and last source line (0-based) of the section and its subsections
+    lines: (usize, usize),
+}
+
+/// Apply the instructions of `amendment` to `base`, giving the consolidated
+/// contract.
+///
+/// Deletions remove the target section with its subsections, restatements
+/// replace it, additions insert the new text after it, and replacements
+/// substitute text within it. The result is built from text, so it has not
+/// been processed by any resolver.
+///
+/// `base` must have been processed with `SectionHeaderResolver`, and
+/// `amendment` with `SectionHeaderResolver` and `SectionReferenceResolver`.
+/// Nothing is applied if any instruction fails.
+pub fn apply_amendments(
+    base: &ContractDocument,
+    amendment: &ContractDocument,
+) -> Result<ContractDocument, AmendmentError> {
+    let instructions = AmendmentResolver::new().resolve(amendment);
+    let structure = base.structure();
+
+    let mut edits = Vec::new();
+    for scored in &instructions {
+        let instruction = &scored.value;
+        let section = match instruction.resolve_target(base) {
+            ReferenceResolution::Resolved { canonical, .. } => canonical,
+            ReferenceResolution::Unresolved { reason, .. } => {
+                return Err(AmendmentError::UnresolvedTarget {
+                    reference: instruction.target.reference_text.clone(),
+                    location: instruction.target_span,
+                    reason,
+                })
+            }
+            ReferenceResolution::Ambiguous { candidates, .. } => {
+                return Err(AmendmentError::UnresolvedTarget {
+                    reference: instruction.target.reference_text.clone(),
+                    location: instruction.target_span,
+                    reason: format!("could be any of {:?}", candidates),
+                })
+            }
+            ReferenceResolution::FilteredAsHeader => continue,
+        };
+        let span = match structure.find_by_canonical(&section) {
+            Some(node) => node.full_span(),
+            None => continue,
+        };
+        // Source line numbers are 1-based
+        let source = |line: usize| base.source_line_number(line).map_or(line, |n| n - 1);
+        edits.push(Edit {
+            instruction,
+            section,
+            lines: (source(span.start.line), source(span.end.line)),
+        });
+    }
+
+    edits.sort_by_key(|edit| edit.lines.0);
+    for pair in edits.windows(2) {
+        if pair[1].lines.0 <= pair[0].lines.1 {
+            return Err(AmendmentError::Conflict {
+                section: pair[0].section.clone(),
+                first: pair[0].instruction.target_span,
+                second: pair[1].instruction.target_span,
+            });
+        }
+    }
+
+    let mut lines: Vec<String> = base.original_text().lines().map(str::to_string).collect();
+    // Bottom-up, so earlier sections keep their line numbers
+    for edit in edits.iter().rev() {
+        let instruction = edit.instruction;
+        let (first, last) = edit.lines;
+        let missing = || AmendmentError::MissingText {
+            section: edit.section.clone(),
+            location: instruction.target_span,
+            operation: instruction.operation,
+        };
+        let new_lines = || -> Result<Vec<String>, AmendmentError> {
+            let text = instruction.new_text.as_ref().ok_or_else(missing)?;
+            Ok(text.lines().map(str::to_string).collect())
+        };
+
+        match instruction.operation {
+            AmendmentOperation::Delete => {
+                lines.drain(first..=last);
+            }
+            AmendmentOperation::AmendAndRestate => {
+                lines.splice(first..=last, new_lines()?);
+            }
+            AmendmentOperation::Add => {
+                lines.splice(last + 1..last + 1, new_lines()?);
+            }
+            AmendmentOperation::Replace => {
+                let old = instruction.replaced_text.as_ref().ok_or_else(missing)?;
+                let new = instruction.new_text.as_ref().ok_or_else(missing)?;
+                let mut found = false;
+                for line in &mut lines[first..=last] {
+                    if line.contains(old.as_str()) {
+                        *line = line.replace(old.as_str(), new);
+                        found = true;
+                    }
+                }
+                if !found {
+                    return Err(AmendmentError::TextNotFound {
+                        section: edit.section.clone(),
+                        text: old.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(ContractDocument::from_text(&lines.join("\n")))
+}
+
+fn trim_quotes(text: &str) -> &str {
+    text.trim().trim_matches(|c| matches!(c, '"' | '“' | '”')).trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SectionHeaderResolver, SectionReferenceResolver};
+
+    fn document(text: &str) -> ContractDocument {
+        ContractDocument::from_text(text)
+            .run_resolver(&SectionHeaderResolver::new())
+            .run_resolver(&SectionReferenceResolver::new())
+    }
+
+    fn instructions(text: &str) -> Vec<Scored<AmendmentInstruction>> {
+        AmendmentResolver::new().resolve(&document(text))
+    }
+
+    #[test]
+    fn test_operations_and_new_text() {
+        let found = instructions(
+            "1. Section 3.1 of the Agreement is hereby deleted in its entirety.\n\
+             2. Section 4 is hereby amended and restated to read as follows:\n\
+             \"4. Term. This Agreement shall continue for two (2) years.\"\n\
+             3. In Section 5.2, \"thirty (30) days\" is replaced with \"sixty (60) days\".\n\
+             4. Section 6 is amended by adding the following: The Supplier shall keep records.",
+        );
+        assert_eq!(found.len(), 4);
+
+        let delete = &found[0].value;
+        assert_eq!(delete.operation, AmendmentOperation::Delete);
+        // "1", ".", " ", then "Section 3.1"
+        assert_eq!(delete.target_span, DocSpan::single_line(0, 3, 7));
+        assert_eq!(delete.target.target.as_ref().unwrap().canonical(), "SECTION:3.1");
+        assert_eq!(delete.new_text, None);
+
+        let restate = &found[1].value;
+        assert_eq!(restate.operation, AmendmentOperation::AmendAndRestate);
+        assert_eq!(
+            restate.new_text.as_deref(),
+            Some("4. Term. This Agreement shall continue for two (2) years.")
+        );
+
+        let replace = &found[2].value;
+        assert_eq!(replace.operation, AmendmentOperation::Replace);
+        assert_eq!(replace.replaced_text.as_deref(), Some("thirty (30) days"));
+        assert_eq!(replace.new_text.as_deref(), Some("sixty (60) days"));
+
+        let add = &found[3].value;
+        assert_eq!(add.operation, AmendmentOperation::Add);
+        assert_eq!(add.new_text.as_deref(), Some("The Supplier shall keep records."));
+        assert!(found.iter().all(|f| f.confidence == INSTRUCTION_CONFIDENCE));
+    }
+
+    #[test]
+    fn test_missing_new_text_and_target_resolution() {
+        let found = instructions(
+            "Section 2 is hereby amended and restated.\n\
+             Section 9 is hereby deleted.",
+        );
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].value.new_text, None);
+        assert!(found[0].confidence < INSTRUCTION_CONFIDENCE);
+
+        let base = document(
+            "Section 1. Services\n\
+             The Supplier shall provide the Services.\n\
+             Section 2. Fees\n\
+             The Customer shall pay the Fees.",
+        );
+        assert!(found[0].value.resolve_target(&base).is_resolved());
+        assert!(!found[1].value.resolve_target(&base).is_resolved());
+    }
+
+    const BASE: &str = "Section 1. Services\n\
+                        The Supplier shall provide the Services.\n\
+                        Section 2. Fees\n\
+                        The Customer shall pay the Fees within thirty (30) days.\n\
+                        Section 3. Audit\n\
+                        The Customer may audit the Supplier once a year.";
+
+    fn consolidated(amendment: &str) -> Result<String, AmendmentError> {
+        let base = document(BASE);
+        apply_amendments(&base, &document(amendment)).map(|doc| doc.original_text().to_string())
+    }
+
+    #[test]
+    fn test_apply_amendments() {
+        let text = consolidated(
+            "1. Section 3 of the Agreement is hereby deleted in its entirety.\n\
+             2. In Section 2, \"thirty (30) days\" is replaced with \"sixty (60) days\".\n\
+             3. Section 1 is amended by adding the following: The Supplier shall keep records.",
+        )
+        .unwrap();
+        assert_eq!(
+            text,
+            "Section 1. Services\n\
+             The Supplier shall provide the Services.\n\
+             The Supplier shall keep records.\n\
+             Section 2. Fees\n\
+             The Customer shall pay the Fees within sixty (60) days."
+        );
+    }
+
+    #[test]
+    fn test_apply_amendments_errors() {
+        let conflict = consolidated(
+            "Section 2 is hereby deleted.\n\
+             Section 2 is amended and restated to read as follows: The Fees are waived.",
+        );
+        assert!(matches!(
+            conflict,
+            Err(AmendmentError::Conflict { ref section, .. }) if section == "SECTION:2"
+        ));
+
+        let missing = consolidated("Section 9 is hereby deleted.");
+        assert!(matches!(missing, Err(AmendmentError::UnresolvedTarget { .. })));
+
+        let not_found = consolidated("In Section 1, \"goods\" is replaced with \"works\".");
+        assert!(matches!(not_found, Err(AmendmentError::TextNotFound { .. })));
+    }
+}
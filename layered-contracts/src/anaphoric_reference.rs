@@ -0,0 +1,250 @@
+//! Anaphoric "such"/"said" noun phrases.
+//!
+//! ```text
+//! The Supplier shall deliver the Goods. Said Goods must be inspected.
+//!                                       ╰────────╯DeicticReference { DiscourseAnaphoric, "Said Goods" -> "Goods" }
+//! ```
+//!
+//! Legal drafting points back to an earlier mention with "such party",
+//! "said agreement" or "the aforesaid obligations". [`AnaphoricReferenceResolver`]
+//! finds these phrases and links each to the nearest earlier mention of its
+//! noun on the same line.
+
+use layered_deixis::{
+    DeicticCategory, DeicticReference, DeicticSource, DeicticSubcategory, ResolvedReferent,
+};
+use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// Words that point back to an earlier mention of the noun after them.
+const ANAPHORIC_DETERMINERS: &[&str] = &["such", "said", "aforesaid", "foregoing"];
+
+/// Words after a determiner that show it is not used anaphorically
+/// ("such as", "such that", "he said the").
+const NON_NOUNS: &[&str] = &[
+    "a", "an", "and", "as", "at", "by", "for", "in", "is", "of", "on", "or", "that", "the",
+    "to", "was", "which",
+];
+
+/// Capitalized words that start a sentence rather than a name.
+const DETERMINERS: &[&str] = &["a", "an", "any", "each", "every", "no", "the", "this", "that"];
+
+/// Resolver for anaphoric noun phrases ("such Party", "said Agreement").
+///
+/// A phrase is an anaphoric determiner followed directly by a word. Its
+/// antecedent is the nearest earlier mention of the same noun on the line,
+/// ignoring case and plural "s", extended back over a capitalized name
+/// ("Supply Agreement" for "said Agreement"). Mentions that are themselves
+/// anaphoric phrases are skipped.
+///
+/// When no earlier mention exists, or earlier mentions name different things
+/// (both "Supply Agreement" and "License Agreement" before "said Agreement"),
+/// the reference is emitted without a `resolved_referent` and at a lower
+/// confidence, instead of guessing.
+///
+/// Needs no other resolver to have run.
+#[derive(Debug, Clone)]
+pub struct AnaphoricReferenceResolver {
+    /// Confidence of a reference with a single clear antecedent
+    resolved_confidence: f64,
+    /// Confidence of a reference left unresolved
+    unresolved_confidence: f64,
+}
+
+impl Default for AnaphoricReferenceResolver {
+    fn default() -> Self {
+        Self {
+            resolved_confidence: 0.85,
+            unresolved_confidence: 0.5,
+        }
+    }
+}
+
+/// Lowercased singular form of a noun, for matching "obligations" to "obligation".
+fn noun_key(word: &str) -> String {
+    let lower = word.to_lowercase();
+    if let Some(stem) = lower.strip_suffix("ies") {
+        format!("{stem}y")
+    } else if lower.ends_with('s') && !lower.ends_with("ss") {
+        lower[..lower.len() - 1].to_string()
+    } else {
+        lower
+    }
+}
+
+fn is_determiner(word: &str) -> bool {
+    ANAPHORIC_DETERMINERS.contains(&word.to_lowercase().as_str())
+}
+
+impl AnaphoricReferenceResolver {
+    /// Create a new resolver with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the confidence of resolved and unresolved references.
+    pub fn with_confidence(mut self, resolved: f64, unresolved: f64) -> Self {
+        self.resolved_confidence = resolved;
+        self.unresolved_confidence = unresolved;
+        self
+    }
+
+    /// The antecedent of the noun at `words[noun]`: its text and token span.
+    ///
+    /// `None` when no earlier word matches, or when matches disagree on the
+    /// name they belong to.
+    fn antecedent(words: &[(usize, usize, &str)], noun: usize) -> Option<(String, usize, usize)> {
+        let key = noun_key(words[noun].2);
+        // The determiner sits at `noun - 1`
+        let mentions: Vec<(String, usize, usize)> = (0..noun - 1)
+            .filter(|&i| noun_key(words[i].2) == key)
+            .filter(|&i| i == 0 || !is_determiner(words[i - 1].2))
+            .map(|i| {
+                let mut start = i;
+                let capitalized = |w: &str| w.starts_with(|c: char| c.is_uppercase());
+                while start > 0
+                    && capitalized(words[i].2)
+                    && capitalized(words[start - 1].2)
+                    && words[start - 1].1 + 2 >= words[start].0
+                    && !DETERMINERS.contains(&words[start - 1].2.to_lowercase().as_str())
+                    && !is_determiner(words[start - 1].2)
+                {
+                    start -= 1;
+                }
+                let text: Vec<&str> = words[start..=i].iter().map(|w| w.2).collect();
+                (text.join(" "), words[start].0, words[i].1)
+            })
+            .collect();
+
+        let nearest = mentions.last()?;
+        let agree = mentions
+            .iter()
+            .all(|(text, ..)| text.eq_ignore_ascii_case(&nearest.0));
+        agree.then(|| nearest.clone())
+    }
+}
+
+impl Resolver for AnaphoricReferenceResolver {
+    type Attr = DeicticReference;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let found = selection.find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())));
+        // (start token, end token, text), in line order
+        let mut words: Vec<(usize, usize, &str)> = found
+            .iter()
+            .map(|(sel, (_, text))| {
+                let span = sel.span_ref();
+                (span.start_idx, span.end_idx, *text)
+            })
+            .collect();
+        words.sort_by_key(|w| w.0);
+
+        let mut results = Vec::new();
+        for (determiner_sel, (_, determiner)) in &found {
+            if !is_determiner(determiner) {
+                continue;
+            }
+            let Some((ws_sel, _)) = determiner_sel.match_first_forwards(&x::whitespace()) else {
+                continue;
+            };
+            let Some((phrase_sel, (_, noun))) = ws_sel
+                .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            else {
+                continue;
+            };
+            if NON_NOUNS.contains(&noun.to_lowercase().as_str()) {
+                continue;
+            }
+
+            let noun_start = phrase_sel.span_ref().end_idx;
+            let Some(noun_idx) = words.iter().position(|w| w.0 == noun_start) else {
+                continue;
+            };
+
+            let deictic = DeicticReference::new(
+                DeicticCategory::Discourse,
+                DeicticSubcategory::DiscourseAnaphoric,
+                format!("{determiner} {noun}"),
+                DeicticSource::AnaphoricReferenceResolver,
+            );
+            let deictic = match Self::antecedent(&words, noun_idx) {
+                Some((text, start, end)) => deictic
+                    .with_confidence(self.resolved_confidence)
+                    .with_referent(
+                        ResolvedReferent::new(text, self.resolved_confidence)
+                            .with_span(start, end),
+                    ),
+                None => deictic.with_confidence(self.unresolved_confidence),
+            };
+            results.push(phrase_sel.finish_with_attr(deictic));
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use layered_nlp::create_line_from_string;
+
+    fn references(text: &str) -> Vec<(String, Option<String>)> {
+        let line = create_line_from_string(text).run(&AnaphoricReferenceResolver::new());
+        line.find(&x::attr::<DeicticReference>())
+            .into_iter()
+            .map(|found| {
+                let deictic = *found.attr();
+                (
+                    deictic.surface_text.clone(),
+                    deictic.resolved_referent.as_ref().map(|r| r.text.clone()),
+                )
+            })
+            .collect()
+    }
+
+    fn resolved(surface: &str, referent: &str) -> (String, Option<String>) {
+        (surface.to_string(), Some(referent.to_string()))
+    }
+
+    #[test]
+    fn test_links_to_nearest_prior_mention() {
+        assert_eq!(
+            references(
+                "Either party may terminate the Supply Agreement. Such party shall \
+                 return all copies of said Agreement and perform the aforesaid obligations \
+                 and its obligations."
+            ),
+            vec![
+                resolved("Such party", "party"),
+                resolved("said Agreement", "Supply Agreement"),
+                ("aforesaid obligations".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plural_and_chained_references() {
+        assert_eq!(
+            references("The Buyer shall pay the Fee. Such Fees are due when such Fees are invoiced."),
+            vec![resolved("Such Fees", "Fee"), resolved("such Fees", "Fee")]
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_antecedents_are_unresolved() {
+        let line = create_line_from_string(
+            "The Supply Agreement and the License Agreement are assigned. Said Agreement survives.",
+        )
+        .run(&AnaphoricReferenceResolver::new());
+        let found = line.find(&x::attr::<DeicticReference>());
+        assert_eq!(found.len(), 1);
+        let deictic = *found[0].attr();
+        assert_eq!(deictic.subcategory, DeicticSubcategory::DiscourseAnaphoric);
+        assert!(deictic.resolved_referent.is_none());
+        assert_eq!(deictic.confidence, 0.5);
+    }
+
+    #[test]
+    fn test_non_anaphoric_uses_are_ignored() {
+        assert!(references("Fees such as taxes are excluded, as he said that.").is_empty());
+    }
+}
@@ -7,6 +7,7 @@
 use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver};
 
 use crate::contract_clause::{ClauseCondition, ClauseDuty, ClauseParty, ContractClause};
+use crate::obligation::conditionality_score;
 use crate::utils::normalize_party_name;
 use crate::Scored;
 
@@ -25,6 +26,8 @@ pub struct ClauseAggregate {
     pub source_start: usize,
     /// End offset of the last clause.
     pub source_end: usize,
+    /// Explanation of how the aggregate confidence was derived from its clauses.
+    pub confidence_breakdown: Vec<String>,
 }
 
 impl ClauseAggregate {
@@ -69,6 +72,69 @@ pub struct ClauseAggregateEntry {
     pub clause_confidence: f64,
 }
 
+impl ClauseAggregateEntry {
+    /// How strongly the clause's conditions gate its duty, in `0.0..1.0`.
+    ///
+    /// See [`crate::ObligationPhrase::conditionality_score`].
+    pub fn conditionality_score(&self) -> f64 {
+        conditionality_score(self.conditions.iter().map(|c| (&c.condition_type, c.text.as_str())))
+    }
+}
+
+/// How a rollup's confidence is derived from its constituents' confidences.
+///
+/// With constituent confidences `c1..cn`:
+/// - `Min`: `min(c1..cn)`, an aggregate is only as certain as its weakest clause
+/// - `Mean`: `(c1 + .. + cn) / n`, weak clauses pull the aggregate down
+///   proportionally
+/// - `Product`: `c1 * .. * cn`, every clause must hold (treated as independent),
+///   so certainty shrinks as clauses are added
+///
+/// An empty set of constituents combines to 1.0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConfidencePropagation {
+    /// Weakest constituent
+    #[default]
+    Min,
+    /// Arithmetic mean
+    Mean,
+    /// Product of all constituents
+    Product,
+}
+
+impl ConfidencePropagation {
+    /// Combine constituent confidences into one.
+    pub fn combine(self, confidences: impl IntoIterator<Item = f64>) -> f64 {
+        let mut count = 0usize;
+        let mut combined = match self {
+            ConfidencePropagation::Min | ConfidencePropagation::Product => 1.0,
+            ConfidencePropagation::Mean => 0.0,
+        };
+        for confidence in confidences {
+            count += 1;
+            combined = match self {
+                ConfidencePropagation::Min => f64::min(combined, confidence),
+                ConfidencePropagation::Mean => combined + confidence,
+                ConfidencePropagation::Product => combined * confidence,
+            };
+        }
+        match self {
+            ConfidencePropagation::Mean if count == 0 => 1.0,
+            ConfidencePropagation::Mean => combined / count as f64,
+            _ => combined,
+        }
+    }
+
+    /// Short name used in confidence breakdowns.
+    pub fn label(self) -> &'static str {
+        match self {
+            ConfidencePropagation::Min => "min",
+            ConfidencePropagation::Mean => "mean",
+            ConfidencePropagation::Product => "product",
+        }
+    }
+}
+
 /// Resolver settings controlling how aggregates are formed and scored.
 pub struct ClauseAggregationResolver {
     /// Maximum token gap (based on clause offsets) allowed between clauses to stay in the same aggregate.
@@ -79,6 +145,8 @@ pub struct ClauseAggregationResolver {
     missing_chain_penalty: f64,
     /// Penalty when an aggregate spans more than `max_span_without_penalty`.
     cross_section_penalty: f64,
+    /// How clause confidences combine into the aggregate confidence.
+    propagation: ConfidencePropagation,
 }
 
 impl Default for ClauseAggregationResolver {
@@ -88,6 +156,7 @@ impl Default for ClauseAggregationResolver {
             max_span_without_penalty: 40,
             missing_chain_penalty: 0.05,
             cross_section_penalty: 0.10,
+            propagation: ConfidencePropagation::Min,
         }
     }
 }
@@ -110,9 +179,19 @@ impl ClauseAggregationResolver {
             max_span_without_penalty,
             missing_chain_penalty,
             cross_section_penalty,
+            ..Self::default()
         }
     }
 
+    /// Set how clause confidences combine into the aggregate confidence.
+    ///
+    /// Penalties are applied after combining. Defaults to
+    /// [`ConfidencePropagation::Min`].
+    pub fn with_propagation(mut self, propagation: ConfidencePropagation) -> Self {
+        self.propagation = propagation;
+        self
+    }
+
     fn party_key(clause: &ContractClause) -> ClausePartyKey {
         ClausePartyKey {
             chain_id: clause.obligor.chain_id,
@@ -214,20 +293,33 @@ impl ClauseAggregateBuilder {
     }
 
     fn build(self, aggregate_id: u32, settings: &ClauseAggregationResolver) -> (ClauseAggregate, f64) {
-        let min_clause_conf = self
-            .entries
-            .iter()
-            .map(|entry| entry.clause_confidence)
-            .fold(1.0, f64::min);
-
-        let mut confidence = min_clause_conf;
+        let clause_confidence = settings
+            .propagation
+            .combine(self.entries.iter().map(|entry| entry.clause_confidence));
+
+        let mut confidence = clause_confidence;
+        let mut breakdown = vec![format!(
+            "Clauses ({} of {}): {:.2}",
+            settings.propagation.label(),
+            self.entries.len(),
+            clause_confidence
+        )];
         if self.obligor.chain_id.is_none() {
             confidence -= settings.missing_chain_penalty;
+            breakdown.push(format!(
+                "Missing chain penalty: -{:.2}",
+                settings.missing_chain_penalty
+            ));
         }
         if self.entries.len() > 1 && self.span() > settings.max_span_without_penalty {
             confidence -= settings.cross_section_penalty;
+            breakdown.push(format!(
+                "Cross-section penalty: -{:.2}",
+                settings.cross_section_penalty
+            ));
         }
         confidence = confidence.clamp(0.0, 1.0);
+        breakdown.push(format!("Layer8 result: {:.2}", confidence));
 
         (
             ClauseAggregate {
@@ -237,6 +329,7 @@ impl ClauseAggregateBuilder {
                 clauses: self.entries,
                 source_start: self.source_start,
                 source_end: self.source_end,
+                confidence_breakdown: breakdown,
             },
             confidence,
         )
@@ -0,0 +1,260 @@
+//! Similarity-based clustering of clauses.
+//!
+//! Boilerplate is often copied between sections (or between contracts) and
+//! edited slightly along the way. [`ClauseClusterer`] groups clauses whose
+//! token similarity ([`TokenAlignment::similarity`]) reaches a threshold, so
+//! drifted copies of "the same" clause end up in one [`ClauseCluster`] with a
+//! representative medoid.
+//!
+//! Clustering is single-linkage: two clauses share a cluster when a chain of
+//! pairwise matches above the threshold connects them. Results depend only on
+//! the input order, never on hashing.
+
+use crate::token_diff::{TokenAligner, TokenAlignmentConfig, TokenRef, WhitespaceMode};
+use crate::{ContractDocument, DocSpan};
+
+/// A group of near-duplicate clauses.
+///
+/// Members are indices into the slice passed to the clusterer, in input order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClauseCluster {
+    /// Indices of the clauses in this cluster, ascending
+    pub members: Vec<usize>,
+    /// Index of the member with the highest total similarity to the others
+    pub medoid: usize,
+    /// Similarity of each member to the medoid, parallel to `members`
+    pub similarity_to_medoid: Vec<f64>,
+}
+
+impl ClauseCluster {
+    /// Whether the clause has no near-duplicates.
+    pub fn is_singleton(&self) -> bool {
+        self.members.len() == 1
+    }
+
+    /// Members other than the medoid, with their similarity to it.
+    pub fn variants(&self) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.members
+            .iter()
+            .copied()
+            .zip(self.similarity_to_medoid.iter().copied())
+            .filter(move |(member, _)| *member != self.medoid)
+    }
+}
+
+/// Groups clauses by token similarity.
+///
+/// # Example
+///
+/// ```
+/// use layered_contracts::ClauseClusterer;
+///
+/// let clusters = ClauseClusterer::new(0.8).cluster_texts(&[
+///     "The Supplier shall keep all Confidential Information secret.",
+///     "Payment is due within thirty days of invoice.",
+///     "The Supplier shall keep all Confidential Information strictly secret.",
+/// ]);
+///
+/// assert_eq!(clusters[0].members, vec![0, 2]);
+/// assert!(clusters[1].is_singleton());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClauseClusterer {
+    /// Minimum similarity (0.0-1.0) for two clauses to be linked
+    threshold: f64,
+    /// Alignment settings used for every pairwise comparison
+    config: TokenAlignmentConfig,
+}
+
+impl Default for ClauseClusterer {
+    fn default() -> Self {
+        Self::new(0.8)
+    }
+}
+
+impl ClauseClusterer {
+    /// Create a clusterer linking clauses with similarity of at least `threshold`.
+    ///
+    /// Whitespace is ignored when comparing clauses.
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            config: TokenAlignmentConfig {
+                whitespace_mode: WhitespaceMode::Ignore,
+                ..TokenAlignmentConfig::default()
+            },
+        }
+    }
+
+    /// Use a custom alignment configuration for pairwise comparisons.
+    pub fn with_config(mut self, config: TokenAlignmentConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Cluster spans of a document, such as sections or sentences.
+    ///
+    /// Spans may cross lines; the tokens of each covered line are concatenated.
+    pub fn cluster(&self, doc: &ContractDocument, spans: &[DocSpan]) -> Vec<ClauseCluster> {
+        let clauses: Vec<Vec<TokenRef>> =
+            spans.iter().map(|span| span_tokens(doc, span)).collect();
+        self.cluster_tokens(&clauses)
+    }
+
+    /// Cluster free-standing clause texts, such as clauses from a corpus.
+    pub fn cluster_texts(&self, texts: &[&str]) -> Vec<ClauseCluster> {
+        let clauses: Vec<Vec<TokenRef>> = texts
+            .iter()
+            .map(|text| TokenAligner::extract_tokens_from_text(text))
+            .collect();
+        self.cluster_tokens(&clauses)
+    }
+
+    /// Cluster pre-extracted token sequences.
+    ///
+    /// Clusters are ordered by their first member; every clause belongs to
+    /// exactly one cluster, so clauses without a match form singletons.
+    pub fn cluster_tokens(&self, clauses: &[Vec<TokenRef>]) -> Vec<ClauseCluster> {
+        let count = clauses.len();
+        let mut similarity = vec![vec![1.0; count]; count];
+        let mut parent: Vec<usize> = (0..count).collect();
+
+        for left in 0..count {
+            for right in (left + 1)..count {
+                let score = TokenAligner::align(&clauses[left], &clauses[right], &self.config)
+                    .similarity();
+                similarity[left][right] = score;
+                similarity[right][left] = score;
+                if score >= self.threshold {
+                    union(&mut parent, left, right);
+                }
+            }
+        }
+
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut group_of_root: Vec<Option<usize>> = vec![None; count];
+        for clause in 0..count {
+            let root = find(&mut parent, clause);
+            match group_of_root[root] {
+                Some(group) => groups[group].push(clause),
+                None => {
+                    group_of_root[root] = Some(groups.len());
+                    groups.push(vec![clause]);
+                }
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|members| {
+                let total = |member: usize| -> f64 {
+                    members.iter().map(|&other| similarity[member][other]).sum()
+                };
+                // Strictly greater keeps the earliest member on ties.
+                let mut medoid = members[0];
+                let mut best = total(medoid);
+                for &member in &members[1..] {
+                    let score = total(member);
+                    if score > best {
+                        medoid = member;
+                        best = score;
+                    }
+                }
+                let similarity_to_medoid =
+                    members.iter().map(|&member| similarity[medoid][member]).collect();
+                ClauseCluster {
+                    members,
+                    medoid,
+                    similarity_to_medoid,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Tokens covered by a span, concatenated across lines.
+fn span_tokens(doc: &ContractDocument, span: &DocSpan) -> Vec<TokenRef> {
+    let lines = doc.lines();
+    let last_line = span.end.line.min(lines.len().saturating_sub(1));
+    (span.start.line..=last_line)
+        .filter_map(|line_idx| lines.get(line_idx).map(|line| (line_idx, line)))
+        .flat_map(|(line_idx, line)| {
+            TokenAligner::extract_tokens(line).into_iter().filter(move |token| {
+                (line_idx > span.start.line || token.index >= span.start.token)
+                    && (line_idx < span.end.line || token.index <= span.end.token)
+            })
+        })
+        .collect()
+}
+
+fn find(parent: &mut [usize], mut node: usize) -> usize {
+    while parent[node] != node {
+        parent[node] = parent[parent[node]];
+        node = parent[node];
+    }
+    node
+}
+
+/// Link two sets, keeping the smaller index as root so results are order-stable.
+fn union(parent: &mut [usize], left: usize, right: usize) {
+    let left_root = find(parent, left);
+    let right_root = find(parent, right);
+    if left_root != right_root {
+        let (root, child) = if left_root < right_root {
+            (left_root, right_root)
+        } else {
+            (right_root, left_root)
+        };
+        parent[child] = root;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DocPosition;
+
+    #[test]
+    fn test_cluster_document_spans_with_medoid() {
+        let doc = ContractDocument::from_text(
+            "The Supplier shall indemnify the Buyer against all claims.\n\
+             Either party may terminate this Agreement on notice.\n\
+             The Supplier shall indemnify the Buyer against all third party claims.\n\
+             The Supplier shall indemnify the Buyer against all losses.",
+        );
+        let spans: Vec<DocSpan> = (0..doc.line_count())
+            .map(|line| {
+                let last = doc.lines()[line].ll_tokens().len() - 1;
+                DocSpan::single_line(line, 0, last)
+            })
+            .collect();
+
+        let clusters = ClauseClusterer::new(0.7).cluster(&doc, &spans);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].members, vec![0, 2, 3]);
+        assert_eq!(clusters[0].medoid, 0);
+        let variants: Vec<usize> = clusters[0].variants().map(|(member, _)| member).collect();
+        assert_eq!(variants, vec![2, 3]);
+        assert!(clusters[0].similarity_to_medoid.iter().all(|s| *s >= 0.7));
+        assert!(clusters[1].is_singleton());
+    }
+
+    #[test]
+    fn test_multi_line_span_and_threshold() {
+        let doc = ContractDocument::from_text(
+            "Fees are payable\nwithin thirty days.\nFees are payable within thirty days.",
+        );
+        let spans = vec![
+            DocSpan::new(DocPosition { line: 0, token: 0 }, DocPosition { line: 1, token: 6 }),
+            DocSpan::single_line(2, 0, 12),
+        ];
+
+        let clusters = ClauseClusterer::default().cluster(&doc, &spans);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].similarity_to_medoid, vec![1.0, 1.0]);
+
+        let strict = ClauseClusterer::new(1.01).cluster(&doc, &spans);
+        assert_eq!(strict.len(), 2);
+    }
+}
@@ -0,0 +1,216 @@
+//! Obligations hidden in conditional permissions.
+//!
+//! ```text
+//! The Tenant may terminate this Lease only upon 30 days written notice.
+//! ```
+//!
+//! The clause grants a permission, but "only upon" makes the notice a
+//! precondition: a Tenant who terminates must give it. [`ConditionalPermissionResolver`]
+//! finds "may X only if/upon Y" and emits the implied obligation (Y when X),
+//! linked to the permission with [`ClauseRole::GatedPermission`]. The
+//! permission itself is still reported by `ObligationPhraseResolver`.
+
+use layered_nlp::{x, LToken, TextTag};
+use layered_nlp_document::{ClauseRole, DocSpanLink, DocumentResolver, LayeredDocument};
+
+use crate::contract_keyword::ContractKeyword;
+use crate::obligation::{ObligationPhrase, ObligationType, ObligorReference};
+use crate::{DocSpan, Scored};
+
+/// Share of the permission's confidence carried by the implied obligation.
+const IMPLIED_CONFIDENCE_FACTOR: f64 = 0.9;
+
+/// An obligation implied by a permission that may only be exercised under a condition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImpliedObligation {
+    /// The permitted party, who must satisfy the precondition
+    pub obligor: ObligorReference,
+    /// What must happen first ("30 days written notice to the Landlord")
+    pub precondition: String,
+    /// The permitted action the duty attaches to ("terminate this Lease")
+    pub gated_action: String,
+    /// The condition keyword after "only" (`If` for "if"/"upon", or `Provided`)
+    pub condition_type: ContractKeyword,
+    /// The gating phrase, from "only" through the precondition
+    pub anchor: DocSpan,
+    /// Link to the permission, with role `GatedPermission`
+    pub link: DocSpanLink<ClauseRole>,
+}
+
+/// Document-level resolver for permissions gated by "only if"/"only upon".
+///
+/// A gate applies to the nearest preceding permission in the same sentence,
+/// up to the next obligation on the line. The precondition runs from the
+/// condition keyword to the next punctuation mark or obligation.
+///
+/// Requires `ObligationPhraseResolver` (and its prerequisites) to have run on
+/// the document's lines.
+#[derive(Debug, Clone, Default)]
+pub struct ConditionalPermissionResolver;
+
+impl ConditionalPermissionResolver {
+    /// Create a new resolver.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl DocumentResolver for ConditionalPermissionResolver {
+    type Attr = Scored<ImpliedObligation>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        let mut results = Vec::new();
+
+        for (line_idx, line) in doc.lines_enumerated() {
+            let mut obligations: Vec<(DocSpan, Scored<ObligationPhrase>)> = line
+                .find(&x::attr::<Scored<ObligationPhrase>>())
+                .iter()
+                .map(|found| (DocSpan::from_find(line_idx, found), (*found.attr()).clone()))
+                .collect();
+            if obligations.is_empty() {
+                continue;
+            }
+            obligations.sort_by_key(|(span, _)| span.start.token);
+
+            let conditions: Vec<(usize, usize, ContractKeyword)> = line
+                .find(&x::attr::<ContractKeyword>())
+                .iter()
+                .filter(|found| {
+                    matches!(*found.attr(), ContractKeyword::If | ContractKeyword::Provided)
+                })
+                .map(|found| {
+                    let (start, end) = found.token_range();
+                    (start, end, **found.attr())
+                })
+                .collect();
+            let tokens = line.ll_tokens();
+            let text_at = |idx: usize| match tokens[idx].get_token() {
+                LToken::Text(text, tag) => Some((text.as_str(), tag)),
+                LToken::Value => None,
+            };
+
+            for (i, (span, permission)) in obligations.iter().enumerate() {
+                if permission.value.obligation_type != ObligationType::Permission {
+                    continue;
+                }
+                let clause_end = obligations
+                    .get(i + 1)
+                    .map_or(tokens.len(), |(next, _)| next.start.token);
+
+                let gate = conditions.iter().find(|(start, _, _)| {
+                    *start > span.end.token
+                        && *start < clause_end
+                        && !(span.end.token..*start).any(|idx| {
+                            matches!(text_at(idx), Some(("." | ";" | "!" | "?", _)))
+                        })
+                });
+                let (keyword_start, keyword_end, condition_type) = match gate {
+                    Some(gate) => gate,
+                    None => continue,
+                };
+
+                // "only" must immediately precede the keyword
+                let only = (0..*keyword_start)
+                    .rev()
+                    .find(|&idx| !matches!(text_at(idx), Some((_, TextTag::SPACE))));
+                let only = match only {
+                    Some(idx)
+                        if text_at(idx).is_some_and(|(w, _)| w.eq_ignore_ascii_case("only")) =>
+                    {
+                        idx
+                    }
+                    _ => continue,
+                };
+
+                let mut precondition = Vec::new();
+                let mut last = *keyword_end;
+                for idx in keyword_end + 1..clause_end {
+                    match text_at(idx) {
+                        Some((_, TextTag::SPACE)) => {}
+                        Some((text, TextTag::WORD | TextTag::NATN)) => {
+                            precondition.push(text);
+                            last = idx;
+                        }
+                        Some((text @ ("-" | "§" | "(" | ")"), _)) => {
+                            precondition.push(text);
+                            last = idx;
+                        }
+                        _ => break,
+                    }
+                }
+                if precondition.is_empty() {
+                    continue;
+                }
+
+                results.push(Scored::rule_based(
+                    ImpliedObligation {
+                        obligor: permission.value.obligor.clone(),
+                        precondition: precondition.join(" "),
+                        gated_action: permission.value.action.clone(),
+                        condition_type: *condition_type,
+                        anchor: DocSpan::single_line(line_idx, only, last),
+                        link: DocSpanLink::new(ClauseRole::GatedPermission, *span),
+                    },
+                    permission.confidence * IMPLIED_CONFIDENCE_FACTOR,
+                    "only_if_permission",
+                ));
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractDocument, ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver,
+        ProhibitionResolver, PronounResolver, TermReferenceResolver,
+    };
+    use layered_part_of_speech::POSTagResolver;
+
+    fn document(text: &str) -> ContractDocument {
+        ContractDocument::from_text(text)
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ProhibitionResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+            .run_resolver(&TermReferenceResolver::default())
+            .run_resolver(&PronounResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default())
+    }
+
+    #[test]
+    fn test_only_upon_implies_obligation() {
+        let doc = document(
+            "The Tenant may terminate this Lease only upon 30 days written notice to the Landlord.",
+        );
+
+        let found = ConditionalPermissionResolver::new().resolve(&doc);
+        assert_eq!(found.len(), 1);
+        let implied = &found[0].value;
+        assert_eq!(implied.precondition, "30 days written notice to the Landlord");
+        assert_eq!(implied.gated_action, "terminate this Lease");
+        assert_eq!(implied.condition_type, ContractKeyword::If);
+        assert_eq!(implied.link.role, ClauseRole::GatedPermission);
+        assert_eq!(implied.link.target.start.token, 4, "points at the permission's modal");
+        assert!(implied.anchor.start.token > implied.link.target.end.token);
+        assert!(matches!(&implied.obligor, ObligorReference::NounPhrase { text } if text == "Tenant"));
+    }
+
+    #[test]
+    fn test_gate_stops_at_clause_and_needs_only() {
+        let doc = document(
+            "The Licensee may sublicense the Software only if the Licensor consents, and the Licensee shall pay the fees.\n\
+             The Licensee may assign this Agreement if the Licensor consents.\n\
+             The Licensee shall pay the fees only if invoiced.",
+        );
+
+        let found = ConditionalPermissionResolver::new().resolve(&doc);
+        assert_eq!(found.len(), 1, "plain conditions and gated duties imply nothing");
+        assert_eq!(found[0].value.precondition, "the Licensor consents");
+        assert_eq!(found[0].value.gated_action, "sublicense the Software");
+        assert_eq!(found[0].value.anchor.start.line, 0);
+    }
+}
@@ -0,0 +1,207 @@
+//! Audit of how much resolvers vary their confidence scores.
+//!
+//! A resolver that gives every match the same hardcoded confidence makes the
+//! verification queue meaningless: nothing sorts above anything else.
+//! [`confidence_audit`] collects the confidences of each annotation type in a
+//! document and flags types whose scores barely vary as needing calibration.
+
+use std::collections::BTreeMap;
+
+use layered_nlp::x;
+
+use crate::snapshot::{Snapshot, SnapshotKind};
+use crate::temporal::TemporalExpression;
+use crate::ContractDocument;
+
+/// Confidence statistics for one annotation type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfidenceStats {
+    /// Snapshot type name ("ObligationPhrase", "TemporalExpression")
+    pub type_name: String,
+    /// Number of annotations scored
+    pub count: usize,
+    pub mean: f64,
+    /// Population standard deviation
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Enough annotations were scored, and their spread is below the
+    /// audit's threshold
+    pub needs_calibration: bool,
+}
+
+/// Confidence statistics of every scored annotation type, by type name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditReport {
+    pub types: Vec<ConfidenceStats>,
+}
+
+impl AuditReport {
+    /// The types flagged as needing calibration.
+    pub fn needs_calibration(&self) -> impl Iterator<Item = &ConfidenceStats> {
+        self.types.iter().filter(|stats| stats.needs_calibration)
+    }
+
+    /// Statistics for one type, if the document had any of it.
+    pub fn get(&self, type_name: &str) -> Option<&ConfidenceStats> {
+        self.types.iter().find(|stats| stats.type_name == type_name)
+    }
+}
+
+/// Flags annotation types whose confidences have near-zero variance.
+///
+/// Types with fewer than `min_samples` annotations are reported but never
+/// flagged: two matches with the same score say little about a resolver.
+#[derive(Debug, Clone)]
+pub struct ConfidenceAudit {
+    /// Fewest annotations of a type before it can be flagged
+    min_samples: usize,
+    /// Standard deviation at or below which a type is flagged
+    max_std_dev: f64,
+}
+
+impl Default for ConfidenceAudit {
+    fn default() -> Self {
+        Self {
+            min_samples: 3,
+            max_std_dev: 0.01,
+        }
+    }
+}
+
+impl ConfidenceAudit {
+    /// Create an audit with default thresholds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the fewest annotations of a type before it can be flagged.
+    pub fn with_min_samples(mut self, min_samples: usize) -> Self {
+        self.min_samples = min_samples;
+        self
+    }
+
+    /// Set the standard deviation at or below which a type is flagged.
+    pub fn with_max_std_dev(mut self, max_std_dev: f64) -> Self {
+        self.max_std_dev = max_std_dev;
+        self
+    }
+
+    /// Audit the standard snapshot types of a document.
+    ///
+    /// `TemporalExpression` keeps its confidence in the value rather than in
+    /// a `Scored` wrapper, so it is read from the document directly.
+    pub fn audit(&self, doc: &ContractDocument) -> AuditReport {
+        let mut samples = Self::snapshot_samples(&Snapshot::from_document(doc));
+        let temporal: Vec<f64> = doc
+            .lines()
+            .iter()
+            .flat_map(|line| line.find(&x::attr::<TemporalExpression>()))
+            .map(|found| found.attr().confidence)
+            .collect();
+        if !temporal.is_empty() {
+            samples.insert(TemporalExpression::SNAPSHOT_TYPE_NAME.to_string(), temporal);
+        }
+        self.report(samples)
+    }
+
+    /// Audit the confidences recorded in a snapshot.
+    pub fn audit_snapshot(&self, snapshot: &Snapshot) -> AuditReport {
+        self.report(Self::snapshot_samples(snapshot))
+    }
+
+    fn snapshot_samples(snapshot: &Snapshot) -> BTreeMap<String, Vec<f64>> {
+        snapshot
+            .spans
+            .iter()
+            .map(|(type_name, spans)| {
+                let confidences = spans.iter().filter_map(|span| span.confidence).collect();
+                (type_name.clone(), confidences)
+            })
+            .filter(|(_, confidences): &(String, Vec<f64>)| !confidences.is_empty())
+            .collect()
+    }
+
+    fn report(&self, samples: BTreeMap<String, Vec<f64>>) -> AuditReport {
+        let types = samples
+            .into_iter()
+            .map(|(type_name, confidences)| {
+                let count = confidences.len();
+                let mean = confidences.iter().sum::<f64>() / count as f64;
+                let variance =
+                    confidences.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / count as f64;
+                let std_dev = variance.sqrt();
+                ConfidenceStats {
+                    type_name,
+                    count,
+                    mean,
+                    std_dev,
+                    min: confidences.iter().copied().fold(f64::INFINITY, f64::min),
+                    max: confidences.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                    needs_calibration: count >= self.min_samples && std_dev <= self.max_std_dev,
+                }
+            })
+            .collect();
+        AuditReport { types }
+    }
+}
+
+/// Audit a document with the default thresholds; see [`ConfidenceAudit`].
+pub fn confidence_audit(doc: &ContractDocument) -> AuditReport {
+    ConfidenceAudit::new().audit(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver,
+        ProhibitionResolver, PronounResolver, TemporalExpressionResolver, TermReferenceResolver,
+    };
+    use layered_part_of_speech::POSTagResolver;
+
+    fn analyze(text: &str) -> ContractDocument {
+        ContractDocument::from_text(text)
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ProhibitionResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+            .run_resolver(&TermReferenceResolver::default())
+            .run_resolver(&PronounResolver::default())
+            .run_resolver(&TemporalExpressionResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default())
+    }
+
+    const CONTRACT: &str = "ABC Corp (the \"Company\") shall deliver the goods by March 1, 2024.\n\
+        The fees shall be paid by June 30, 2024.\n\
+        The relevant authority shall approve the filing by December 31, 2024.";
+
+    #[test]
+    fn test_uniform_scores_need_calibration() {
+        let report = confidence_audit(&analyze(CONTRACT));
+
+        let dates = report.get("TemporalExpression").unwrap();
+        assert_eq!(dates.count, 3);
+        assert!(dates.std_dev < 1e-9);
+        assert!(dates.needs_calibration);
+
+        let obligations = report.get("ObligationPhrase").unwrap();
+        assert_eq!(obligations.count, 3);
+        assert!(obligations.max - obligations.min > 0.1);
+        assert!(!obligations.needs_calibration);
+
+        let flagged: Vec<_> = report.needs_calibration().map(|s| s.type_name.as_str()).collect();
+        assert!(flagged.contains(&"TemporalExpression"));
+        assert!(!flagged.contains(&"ObligationPhrase"));
+    }
+
+    #[test]
+    fn test_too_few_samples_are_not_flagged() {
+        let doc = analyze("The Company shall deliver the goods by March 1, 2024.");
+        let report = confidence_audit(&doc);
+        assert!(!report.get("TemporalExpression").unwrap().needs_calibration);
+
+        let strict = ConfidenceAudit::new().with_min_samples(1).audit(&doc);
+        assert!(strict.get("TemporalExpression").unwrap().needs_calibration);
+    }
+}
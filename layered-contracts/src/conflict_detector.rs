@@ -7,6 +7,7 @@
 //! - **Temporal conflicts**: Same obligation with incompatible timing requirements
 //! - **Contradictory parties**: Same action assigned to different parties
 //! - **Scope overlap**: Obligations that partially overlap with conflicting requirements
+//! - **Governing-law conflicts**: Two governing-law clauses naming different jurisdictions
 //!
 //! # Example
 //!
@@ -18,12 +19,22 @@
 //! let conflicts = detector.detect_in_document(&doc);
 //! ```
 
-use std::collections::HashMap;
-
-use crate::obligation::{ObligorReference, ObligationType};
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::degradation::{DegradationFallback, DegradationReport};
+use crate::document_structure::{DocumentStructure, DocumentStructureExt, SectionNode};
+use crate::governing_law::GoverningLaw;
+use crate::obligation::{ActionSpan, ObligationNature, ObligorReference, ObligationType, UNKNOWN_OBLIGOR};
+use crate::number::NumberParser;
+use crate::sentence_boundary::SentenceBoundary;
 use crate::{DocPosition, DocSpan, Scored};
 use crate::temporal::{NormalizedTiming, TimeUnit};
-use layered_nlp_document::DocumentResolver;
+use layered_nlp::{x, LLLine, SpanRef};
+use layered_nlp_document::{DocumentResolver, StageSpan};
+use layered_part_of_speech::Tag;
 
 // ============================================================================
 // Gate 0: Core Types
@@ -100,7 +111,7 @@ impl Conflict {
 // Note: SnapshotKind impl is in snapshot/types.rs when snapshot module is enabled
 
 /// The type of conflict between two obligations.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum ConflictType {
     /// Same party, same action, but different modal (shall vs may).
     ModalConflict,
@@ -110,6 +121,8 @@ pub enum ConflictType {
     ContradictoryParties,
     /// Obligations that partially overlap with conflicting requirements.
     ScopeOverlap,
+    /// Governing-law clauses naming different jurisdictions.
+    GoverningLawConflict,
 }
 
 impl ConflictType {
@@ -120,12 +133,15 @@ impl ConflictType {
             ConflictType::TemporalConflict => "Temporal conflict (incompatible timing)",
             ConflictType::ContradictoryParties => "Contradictory parties (same action, different obligors)",
             ConflictType::ScopeOverlap => "Scope overlap (conflicting requirements)",
+            ConflictType::GoverningLawConflict => {
+                "Governing-law conflict (different jurisdictions)"
+            }
         }
     }
 }
 
 /// Topic classification for grouping related obligations.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ObligationTopic {
     /// Payment-related obligations (pay, remit, compensate).
     Payment,
@@ -139,6 +155,8 @@ pub enum ObligationTopic {
     Indemnification,
     /// Notice obligations (notify, inform, give notice).
     Notice,
+    /// A domain-specific topic registered with [`TopicClassifier::add_topic`].
+    Custom(String),
     /// Other obligations not fitting the above categories.
     Other,
 }
@@ -160,6 +178,12 @@ pub struct NormalizedObligation {
     pub line_index: usize,
     /// The classified topic of this obligation.
     pub topic: ObligationTopic,
+    /// Whether this is a performance duty or a representation, warranty,
+    /// acknowledgment or covenant.
+    pub obligation_nature: ObligationNature,
+    /// Negative verb folded into `obligation_type` and `action`: "fail" for
+    /// "shall not fail to deliver", normalized as a duty to "deliver".
+    pub negative_verb: Option<String>,
 }
 
 impl NormalizedObligation {
@@ -182,24 +206,95 @@ impl NormalizedObligation {
             original_span,
             line_index,
             topic,
+            obligation_nature: ObligationNature::Performance,
+            negative_verb: None,
         }
     }
+
+    /// Set the nature of the obligation (a performance duty by default).
+    pub fn with_nature(mut self, nature: ObligationNature) -> Self {
+        self.obligation_nature = nature;
+        self
+    }
+
+    /// A stable key identifying what this obligation requires, for grouping,
+    /// counting and matching obligations within and across documents.
+    ///
+    /// The key is `obligor|action|TYPE|timing`, e.g.
+    /// `"company|deliver goods|DUTY|30d"`. Case, whitespace, punctuation
+    /// around words and articles in the action are ignored, and timing is
+    /// reduced to days (calendar or business) or months, so "4 weeks" and
+    /// "28 days" share a key. Location (span, line) and the derived topic are
+    /// not part of the key. A literal `|` or `\` inside a field is escaped, so
+    /// distinct fields never produce the same key.
+    pub fn canonical_key(&self) -> String {
+        let obligor = canonical_words(&self.obligor, false);
+        let action = canonical_words(&self.action, true);
+        let obligation_type = format!("{:?}", self.obligation_type).to_uppercase();
+        let timing = self.timing.as_ref().map_or("-".to_string(), canonical_timing);
+        format!(
+            "{}|{}|{}|{}",
+            escape_key_part(&obligor),
+            escape_key_part(&action),
+            obligation_type,
+            timing
+        )
+    }
+}
+
+/// Lowercased words with surrounding punctuation trimmed, optionally dropping
+/// articles.
+fn canonical_words(text: &str, drop_articles: bool) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| c.is_ascii_punctuation() && c != '|' && c != '\\')
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .filter(|word| !(drop_articles && matches!(word.as_str(), "the" | "a" | "an")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Timing in the coarsest exact unit: `30d`, `10bd` or `6mo`, with a `~`
+/// suffix when approximate.
+fn canonical_timing(timing: &NormalizedTiming) -> String {
+    let (value, unit) = match timing.unit {
+        TimeUnit::Days => (timing.value, "d"),
+        TimeUnit::Weeks => (timing.value * 7.0, "d"),
+        TimeUnit::BusinessDays => (timing.value, "bd"),
+        TimeUnit::Months => (timing.value, "mo"),
+        TimeUnit::Years => (timing.value * 12.0, "mo"),
+    };
+    // f64 Display prints 30.0 as "30" and keeps fractions ("1.5")
+    let approximate = if timing.is_approximate { "~" } else { "" };
+    format!("{}{}{}", value, unit, approximate)
+}
+
+fn escape_key_part(part: &str) -> String {
+    part.replace('\\', "\\\\").replace('|', "\\|")
 }
 
 // ============================================================================
 // Gate 1: Obligation Normalization
 // ============================================================================
 
+/// Verbs that negate the action they introduce ("fail to deliver").
+const NEGATIVE_VERBS: &[&str] = &["fail", "neglect", "refuse", "omit"];
+
 /// Normalizes obligation phrases for comparison.
 ///
 /// Handles:
 /// - Party name normalization (strips articles, lowercases)
 /// - Action verb lemmatization
+/// - Negative verbs ("fail to deliver"), folded into the obligation type
 /// - Timing expression parsing
 #[derive(Debug, Clone)]
 pub struct ObligationNormalizer {
     /// Mapping from inflected verb forms to base lemma
     lemma_table: HashMap<String, String>,
+    /// Parser for the numbers of timing expressions
+    number_parser: NumberParser,
 }
 
 impl Default for ObligationNormalizer {
@@ -226,6 +321,8 @@ impl ObligationNormalizer {
             ("warrant", &["warrants", "warranted", "warranting", "warranty"]),
             ("represent", &["represents", "represented", "representing", "representation"]),
             ("agree", &["agrees", "agreed", "agreeing", "agreement"]),
+            ("acknowledge", &["acknowledges", "acknowledged", "acknowledging", "acknowledgment"]),
+            ("covenant", &["covenants", "covenanted", "covenanting"]),
             ("comply", &["complies", "complied", "complying", "compliance"]),
             ("submit", &["submits", "submitted", "submitting", "submission"]),
             ("maintain", &["maintains", "maintained", "maintaining", "maintenance"]),
@@ -238,6 +335,10 @@ impl ObligationNormalizer {
             ("reimburse", &["reimburses", "reimbursed", "reimbursing", "reimbursement"]),
             ("remit", &["remits", "remitted", "remitting", "remittance"]),
             ("transfer", &["transfers", "transferred", "transferring"]),
+            ("fail", &["fails", "failed", "failing"]),
+            ("neglect", &["neglects", "neglected", "neglecting"]),
+            ("refuse", &["refuses", "refused", "refusing"]),
+            ("omit", &["omits", "omitted", "omitting"]),
         ];
 
         for (base, inflections) in verbs {
@@ -249,7 +350,17 @@ impl ObligationNormalizer {
             }
         }
 
-        Self { lemma_table }
+        Self {
+            lemma_table,
+            number_parser: NumberParser::default(),
+        }
+    }
+
+    /// Use `parser` for the numbers of timing expressions ("within thirty
+    /// (30) days").
+    pub fn with_number_parser(mut self, parser: NumberParser) -> Self {
+        self.number_parser = parser;
+        self
     }
 
     /// Extracts the obligor name from an ObligorReference.
@@ -258,6 +369,7 @@ impl ObligationNormalizer {
             ObligorReference::TermRef { term_name, .. } => self.normalize_party(term_name),
             ObligorReference::PronounRef { resolved_to, .. } => self.normalize_party(resolved_to),
             ObligorReference::NounPhrase { text } => self.normalize_party(text),
+            ObligorReference::Unknown => UNKNOWN_OBLIGOR.to_string(),
         }
     }
 
@@ -281,8 +393,12 @@ impl ObligationNormalizer {
     }
 
     /// Normalizes an action string by lemmatizing verbs.
+    ///
+    /// Parenthetical qualifiers such as "(subject to availability)" are dropped
+    /// so they don't add noise to topic classification or action similarity.
     pub fn normalize_action(&self, action: &str) -> String {
-        let words: Vec<&str> = action.split_whitespace().collect();
+        let core = Self::strip_parentheticals(action);
+        let words: Vec<&str> = core.split_whitespace().collect();
         let normalized_words: Vec<String> = words
             .iter()
             .map(|word| {
@@ -299,107 +415,173 @@ impl ObligationNormalizer {
         normalized_words.join(" ")
     }
 
-    /// Parses timing information from an action string.
-    ///
-    /// Handles patterns like:
-    /// - "within 30 days"
-    /// - "within thirty (30) days"
-    /// - "15 business days"
-    pub fn normalize_timing(&self, text: &str) -> Option<NormalizedTiming> {
-        let lower = text.to_lowercase();
+    /// Splits a normalized action opening with a negative verb ("fail to
+    /// deliver the goods") into the verb and the underlying action ("deliver
+    /// the goods").
+    pub fn split_negative_verb(action: &str) -> Option<(&str, &str)> {
+        let (verb, rest) = action.split_once(' ')?;
+        let underlying = rest.strip_prefix("to ")?.trim_start();
+        (NEGATIVE_VERBS.contains(&verb) && !underlying.is_empty()).then_some((verb, underlying))
+    }
 
-        // Try to find numeric patterns first (prefer digits over written)
-        if let Some(timing) = self.parse_numeric_timing(&lower) {
-            return Some(timing);
-        }
+    /// Normalizes an action string using the POS tags of the line it came from.
+    ///
+    /// Verbs in the lemma table are normalized from the table. Other words whose
+    /// own token in `action_span` was tagged as a verb by `POSTagResolver` are
+    /// lemmatized by stripping regular inflections (-s, -ed, -ing). Action words
+    /// are matched to the tokens of `action_span` in order; all other words are
+    /// only lowercased, exactly as in [`normalize_action`](Self::normalize_action).
+    pub fn normalize_action_with_pos(
+        &self,
+        action: &str,
+        line: &LLLine,
+        action_span: SpanRef,
+    ) -> String {
+        let in_span = |idx: usize| (action_span.start_idx..=action_span.end_idx).contains(&idx);
+        let verbs: HashSet<usize> = line
+            .find(&x::all((x::attr_eq(&Tag::Verb), x::token_text())))
+            .into_iter()
+            .map(|found| found.token_range().0)
+            .filter(|&idx| in_span(idx))
+            .collect();
+        let tokens: Vec<(usize, String)> = line
+            .find(&x::token_text())
+            .into_iter()
+            .filter(|found| in_span(found.token_range().0))
+            .map(|found| (found.token_range().0, found.attr().to_lowercase()))
+            .collect();
 
-        // Try written numbers if no numeric match
-        if let Some(timing) = self.parse_written_timing(&lower) {
-            return Some(timing);
-        }
+        let core = Self::strip_parentheticals(action);
+        let mut next_token = 0;
+        let normalized_words: Vec<String> = core
+            .split_whitespace()
+            .map(|word| {
+                let lower = word.to_lowercase();
+                let clean = lower.trim_end_matches(|c: char| c.is_ascii_punctuation());
+                // Key the word to its own token so a verb elsewhere on the
+                // line doesn't lemmatize a noun of the same spelling
+                let token = tokens[next_token..]
+                    .iter()
+                    .position(|(_, text)| text == clean)
+                    .map(|offset| {
+                        next_token += offset + 1;
+                        tokens[next_token - 1].0
+                    });
+                if let Some(lemma) = self.lemma_table.get(clean) {
+                    lemma.clone()
+                } else if token.is_some_and(|idx| verbs.contains(&idx)) {
+                    Self::strip_inflection(clean)
+                } else {
+                    clean.to_string()
+                }
+            })
+            .collect();
 
-        None
+        normalized_words.join(" ")
     }
 
-    /// Parses numeric timing expressions like "30 days" or "within 15 business days".
-    fn parse_numeric_timing(&self, text: &str) -> Option<NormalizedTiming> {
-        // Find a number in the text
-        let mut num_start = None;
-        let mut num_end = 0;
+    /// Strips a regular verb inflection from a lowercased word.
+    ///
+    /// This is a rule-based approximation: irregular verbs are left as-is, and
+    /// a stem is never shortened below three characters.
+    fn strip_inflection(word: &str) -> String {
+        let stem_of = |suffix: &str| -> Option<&str> {
+            word.strip_suffix(suffix).filter(|stem| stem.chars().count() >= 3)
+        };
 
-        for (i, c) in text.char_indices() {
-            if c.is_ascii_digit() {
-                if num_start.is_none() {
-                    num_start = Some(i);
+        if let Some(stem) = stem_of("ies").or_else(|| stem_of("ied")) {
+            return format!("{}y", stem);
+        }
+        if let Some(stem) = stem_of("ing").or_else(|| stem_of("ed")) {
+            return Self::restore_stem(stem);
+        }
+        if ["sses", "xes", "zes", "ches", "shes"].iter().any(|s| word.ends_with(s)) {
+            if let Some(stem) = stem_of("es") {
+                return stem.to_string();
+            }
+        }
+        if !word.ends_with("ss") {
+            if let Some(stem) = stem_of("s") {
+                return stem.to_string();
+            }
+        }
+        word.to_string()
+    }
+
+    /// Undoes consonant doubling ("submitt" -> "submit") or restores a dropped
+    /// final "e" ("approv" -> "approve") after removing -ed/-ing.
+    fn restore_stem(stem: &str) -> String {
+        let chars: Vec<char> = stem.chars().collect();
+        let n = chars.len();
+        if n >= 2 && chars[n - 1] == chars[n - 2] && !"lsfz".contains(chars[n - 1]) {
+            return chars[..n - 1].iter().collect();
+        }
+        const SILENT_E_ENDINGS: &[&str] = &["v", "c", "z", "iz", "ur", "bl", "us", "os", "ag"];
+        // "-at" only after a consonant: "validat" -> "validate", but "repeat" stays
+        let consonant_at = stem.ends_with("at") && n >= 3 && !"aeiou".contains(chars[n - 3]);
+        if consonant_at || SILENT_E_ENDINGS.iter().any(|ending| stem.ends_with(ending)) {
+            return format!("{}e", stem);
+        }
+        stem.to_string()
+    }
+
+    /// Removes parenthesized text (including nested groups) from an action string.
+    fn strip_parentheticals(text: &str) -> String {
+        let mut depth = 0usize;
+        let mut result = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '(' => {
+                    depth += 1;
+                    result.push(' ');
                 }
-                num_end = i + 1;
-            } else if num_start.is_some() {
-                break;
+                ')' if depth > 0 => depth -= 1,
+                _ if depth == 0 => result.push(c),
+                _ => {}
             }
         }
+        result
+    }
 
-        let num_start = num_start?;
-        let value: f64 = text[num_start..num_end].parse().ok()?;
+    /// Parses timing information from an action string.
+    ///
+    /// Handles patterns like:
+    /// - "within 30 days"
+    /// - "within thirty (30) days"
+    /// - "15 business days"
+    pub fn normalize_timing(&self, text: &str) -> Option<NormalizedTiming> {
+        let lower = text.to_lowercase();
+        let numbers: Vec<_> = self
+            .number_parser
+            .find_all(&lower)
+            .into_iter()
+            .filter(|number| !number.ordinal)
+            .collect();
 
-        // Look for unit after the number
-        let after_num = &text[num_end..];
+        // Prefer digits over written numbers
+        let (numeric, written): (Vec<_>, Vec<_>) =
+            numbers.into_iter().partition(|number| number.written_form.is_none());
+        numeric.into_iter().chain(written).find_map(|number| {
+            let unit = Self::timing_unit(&lower[number.span.end..])?;
+            Some(NormalizedTiming::new(number.value as f64, unit, false))
+        })
+    }
 
-        let unit = if after_num.contains("business day") || after_num.contains("working day") {
-            TimeUnit::BusinessDays
+    /// The time unit mentioned after a number ("business days", "months").
+    fn timing_unit(after_num: &str) -> Option<TimeUnit> {
+        if after_num.contains("business day") || after_num.contains("working day") {
+            Some(TimeUnit::BusinessDays)
         } else if after_num.contains("day") {
-            TimeUnit::Days
+            Some(TimeUnit::Days)
         } else if after_num.contains("week") {
-            TimeUnit::Weeks
+            Some(TimeUnit::Weeks)
         } else if after_num.contains("month") {
-            TimeUnit::Months
+            Some(TimeUnit::Months)
         } else if after_num.contains("year") {
-            TimeUnit::Years
+            Some(TimeUnit::Years)
         } else {
-            return None;
-        };
-
-        Some(NormalizedTiming::new(value, unit, false))
-    }
-
-    /// Parses written number timing expressions.
-    fn parse_written_timing(&self, text: &str) -> Option<NormalizedTiming> {
-        // Written number patterns - order matters (longer matches first)
-        let written_numbers = [
-            ("twenty-five", 25), ("twenty-four", 24), ("twenty-three", 23),
-            ("twenty-two", 22), ("twenty-one", 21), ("twenty", 20),
-            ("nineteen", 19), ("eighteen", 18), ("seventeen", 17),
-            ("sixteen", 16), ("fifteen", 15), ("fourteen", 14),
-            ("thirteen", 13), ("twelve", 12), ("eleven", 11),
-            ("ten", 10), ("nine", 9), ("eight", 8), ("seven", 7),
-            ("six", 6), ("five", 5), ("four", 4), ("three", 3),
-            ("two", 2), ("one", 1),
-            ("thirty", 30), ("forty", 40), ("fifty", 50),
-            ("sixty", 60), ("ninety", 90),
-        ];
-
-        for (word, value) in written_numbers {
-            if text.contains(word) {
-                // Check what unit follows
-                let after_num = text.split(word).nth(1)?;
-                let unit = if after_num.contains("business day") || after_num.contains("working day") {
-                    TimeUnit::BusinessDays
-                } else if after_num.contains("day") {
-                    TimeUnit::Days
-                } else if after_num.contains("week") {
-                    TimeUnit::Weeks
-                } else if after_num.contains("month") {
-                    TimeUnit::Months
-                } else if after_num.contains("year") {
-                    TimeUnit::Years
-                } else {
-                    continue;
-                };
-
-                return Some(NormalizedTiming::new(value as f64, unit, false));
-            }
+            None
         }
-
-        None
     }
 
     /// Normalizes a scored obligation phrase into a NormalizedObligation.
@@ -409,11 +591,54 @@ impl ObligationNormalizer {
         line_index: usize,
         start_token: usize,
         end_token: usize,
+    ) -> NormalizedObligation {
+        let action = self.normalize_action(&scored.value.action);
+        self.normalize_with_action(scored, action, line_index, start_token, end_token)
+    }
+
+    /// Like [`normalize`](Self::normalize), but lemmatizes the action with the
+    /// POS tags of its tokens `action_span` in `line` (see
+    /// [`normalize_action_with_pos`](Self::normalize_action_with_pos)).
+    pub fn normalize_with_pos(
+        &self,
+        scored: &Scored<crate::obligation::ObligationPhrase>,
+        line: &LLLine,
+        action_span: SpanRef,
+        line_index: usize,
+        start_token: usize,
+        end_token: usize,
+    ) -> NormalizedObligation {
+        let action = self.normalize_action_with_pos(&scored.value.action, line, action_span);
+        self.normalize_with_action(scored, action, line_index, start_token, end_token)
+    }
+
+    fn normalize_with_action(
+        &self,
+        scored: &Scored<crate::obligation::ObligationPhrase>,
+        action: String,
+        line_index: usize,
+        start_token: usize,
+        end_token: usize,
     ) -> NormalizedObligation {
         let phrase = &scored.value;
 
+        // "shall not fail to deliver" is a duty to deliver, "shall fail to
+        // deliver" a prohibition. A permission not to act ("may fail to") has
+        // no type of its own and keeps its wording.
+        let inverted = Self::split_negative_verb(&action).and_then(|(verb, underlying)| {
+            let obligation_type = match phrase.obligation_type {
+                ObligationType::Duty => ObligationType::Prohibition,
+                ObligationType::Prohibition => ObligationType::Duty,
+                ObligationType::Permission => return None,
+            };
+            Some((obligation_type, underlying.to_string(), verb.to_string()))
+        });
+        let (obligation_type, action, negative_verb) = match inverted {
+            Some((obligation_type, underlying, verb)) => (obligation_type, underlying, Some(verb)),
+            None => (phrase.obligation_type, action, None),
+        };
+
         let obligor = self.extract_obligor_name(&phrase.obligor);
-        let action = self.normalize_action(&phrase.action);
         let timing = self.normalize_timing(&phrase.action);
         let original_span = DocSpan::new(
             DocPosition::new(line_index, start_token),
@@ -423,15 +648,18 @@ impl ObligationNormalizer {
         // Topic classification will be done in Gate 2
         let topic = ObligationTopic::Other;
 
-        NormalizedObligation::new(
+        let mut normalized = NormalizedObligation::new(
             obligor,
-            phrase.obligation_type,
+            obligation_type,
             action,
             timing,
             original_span,
             line_index,
             topic,
         )
+        .with_nature(phrase.obligation_nature);
+        normalized.negative_verb = negative_verb;
+        normalized
     }
 }
 
@@ -457,6 +685,8 @@ pub struct TopicClassifier {
     indemnification_keywords: Vec<&'static str>,
     /// Keywords for Notice topic
     notice_keywords: Vec<&'static str>,
+    /// Registered custom topics and their keywords, checked in order
+    custom_topics: Vec<(String, Vec<String>)>,
 }
 
 impl Default for TopicClassifier {
@@ -513,16 +743,60 @@ impl TopicClassifier {
                 "advise", "advises", "advised", "advising",
                 "written notice", "prior notice", "advance notice",
             ],
+            custom_topics: Vec::new(),
         }
     }
 
+    /// Registers a domain-specific topic.
+    ///
+    /// Custom topics are checked after the built-in topics and before
+    /// `Other`, in the order they were added. Keywords are matched like the
+    /// built-in ones: case-insensitively, on word boundaries, and may span
+    /// several words ("intellectual property").
+    ///
+    /// ```
+    /// use layered_contracts::{ObligationTopic, TopicClassifier};
+    ///
+    /// let mut classifier = TopicClassifier::new();
+    /// classifier.add_topic("Audit", ["audit", "audits", "inspect", "books and records"]);
+    /// assert_eq!(
+    ///     classifier.classify_action("permit an audit of its books"),
+    ///     ObligationTopic::Custom("Audit".to_string())
+    /// );
+    /// ```
+    pub fn add_topic<I, K>(&mut self, name: impl Into<String>, keywords: I) -> &mut Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        let keywords = keywords
+            .into_iter()
+            .map(|keyword| keyword.into().to_lowercase())
+            .collect();
+        self.custom_topics.push((name.into(), keywords));
+        self
+    }
+
+    /// Builder form of [`add_topic`](Self::add_topic).
+    pub fn with_topic<I, K>(mut self, name: impl Into<String>, keywords: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        self.add_topic(name, keywords);
+        self
+    }
+
     /// Classifies an obligation into a topic category.
     ///
     /// Searches the normalized action text for topic keywords with word
     /// boundary detection to avoid false positives.
     pub fn classify(&self, obligation: &NormalizedObligation) -> ObligationTopic {
-        let action = &obligation.action;
+        self.classify_action(&obligation.action)
+    }
 
+    /// Classifies an action text into a topic category.
+    pub fn classify_action(&self, action: &str) -> ObligationTopic {
         // Check topics in order of specificity (more specific first)
         if self.matches_any(action, &self.indemnification_keywords) {
             ObligationTopic::Indemnification
@@ -537,7 +811,15 @@ impl TopicClassifier {
         } else if self.matches_any(action, &self.delivery_keywords) {
             ObligationTopic::Delivery
         } else {
-            ObligationTopic::Other
+            let lower = action.to_lowercase();
+            self.custom_topics
+                .iter()
+                .find(|(_, keywords)| {
+                    keywords.iter().any(|kw| self.has_word_boundary_match(&lower, kw))
+                })
+                .map_or(ObligationTopic::Other, |(name, _)| {
+                    ObligationTopic::Custom(name.clone())
+                })
         }
     }
 
@@ -607,7 +889,7 @@ pub fn group_by_topic(
 
     for mut obligation in obligations {
         let topic = classifier.classify(&obligation);
-        obligation.topic = topic;
+        obligation.topic = topic.clone();
         groups.entry(topic).or_default().push(obligation);
     }
 
@@ -618,6 +900,118 @@ pub fn group_by_topic(
 // Gate 3: Conflict Detection Logic
 // ============================================================================
 
+/// Which obligations are compared against each other during conflict detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictScope {
+    /// Every obligation is compared with every other obligation
+    #[default]
+    WholeDocument,
+    /// Only obligations within the same innermost `SectionNode` are compared
+    SameSection,
+    /// Only obligations within the same sentence are compared
+    SameSentence,
+}
+
+/// Articles and prepositions ignored by [`SimilarityMetric::JaccardWithoutStopwords`].
+const SIMILARITY_STOPWORDS: &[&str] = &[
+    "a", "an", "the", "at", "by", "for", "from", "in", "into", "of", "on", "to", "upon", "with",
+];
+
+/// A caller-supplied action similarity for [`SimilarityMetric::Custom`].
+pub type SimilarityFn = Arc<dyn Fn(&str, &str) -> f64 + Send + Sync>;
+
+/// How [`ConflictDetector::action_similarity`] compares two actions.
+#[derive(Clone, Default)]
+pub enum SimilarityMetric {
+    /// Jaccard coefficient of the sets of words in each action
+    #[default]
+    Jaccard,
+    /// Jaccard coefficient after dropping articles and prepositions, so
+    /// "deliver the goods" and "deliver goods" are the same action
+    JaccardWithoutStopwords,
+    /// One minus the word-level edit distance over the longer action's word
+    /// count; unlike Jaccard, word order matters
+    Levenshtein,
+    /// A caller-supplied similarity in `0.0..=1.0`
+    Custom(SimilarityFn),
+}
+
+impl std::fmt::Debug for SimilarityMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SimilarityMetric::Jaccard => write!(f, "Jaccard"),
+            SimilarityMetric::JaccardWithoutStopwords => write!(f, "JaccardWithoutStopwords"),
+            SimilarityMetric::Levenshtein => write!(f, "Levenshtein"),
+            SimilarityMetric::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+impl SimilarityMetric {
+    /// Wraps a closure as a [`SimilarityMetric::Custom`] metric.
+    pub fn custom(metric: impl Fn(&str, &str) -> f64 + Send + Sync + 'static) -> Self {
+        SimilarityMetric::Custom(Arc::new(metric))
+    }
+
+    /// Computes the similarity of two actions, 1.0 for identical actions.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        match self {
+            SimilarityMetric::Jaccard => {
+                Self::jaccard(a.split_whitespace().collect(), b.split_whitespace().collect())
+            }
+            SimilarityMetric::JaccardWithoutStopwords => {
+                let content = |text: &'_ str| -> Vec<String> {
+                    text.split_whitespace()
+                        .map(str::to_lowercase)
+                        .filter(|word| !SIMILARITY_STOPWORDS.contains(&word.as_str()))
+                        .collect()
+                };
+                let (words_a, words_b) = (content(a), content(b));
+                Self::jaccard(
+                    words_a.iter().map(String::as_str).collect(),
+                    words_b.iter().map(String::as_str).collect(),
+                )
+            }
+            SimilarityMetric::Levenshtein => {
+                let words_a: Vec<&str> = a.split_whitespace().collect();
+                let words_b: Vec<&str> = b.split_whitespace().collect();
+                let longest = words_a.len().max(words_b.len());
+                if longest == 0 {
+                    return 1.0;
+                }
+                1.0 - Self::edit_distance(&words_a, &words_b) as f64 / longest as f64
+            }
+            SimilarityMetric::Custom(metric) => metric(a, b),
+        }
+    }
+
+    /// |A ∩ B| / |A ∪ B|, or 1.0 when both sets are empty.
+    fn jaccard(words_a: HashSet<&str>, words_b: HashSet<&str>) -> f64 {
+        if words_a.is_empty() && words_b.is_empty() {
+            return 1.0;
+        }
+
+        let intersection = words_a.intersection(&words_b).count();
+        let union = words_a.union(&words_b).count();
+
+        intersection as f64 / union as f64
+    }
+
+    /// Number of word insertions, deletions and substitutions turning `a` into `b`.
+    fn edit_distance(a: &[&str], b: &[&str]) -> usize {
+        let mut previous: Vec<usize> = (0..=b.len()).collect();
+        for (i, word_a) in a.iter().enumerate() {
+            let mut current = vec![i + 1];
+            for (j, word_b) in b.iter().enumerate() {
+                let substitution = previous[j] + usize::from(word_a != word_b);
+                current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+            }
+            previous = current;
+        }
+        previous[b.len()]
+    }
+}
+
 /// Detects conflicts between obligations in a contract document.
 ///
 /// Conflicts include:
@@ -626,16 +1020,25 @@ pub fn group_by_topic(
 /// - **Contradictory parties**: Same action assigned to different parties
 #[derive(Debug, Clone)]
 pub struct ConflictDetector {
-    /// Threshold for action similarity (Jaccard) to consider them "same action"
+    /// Threshold for action similarity to consider them "same action"
     pub similarity_threshold: f64,
+    /// How action similarity is computed
+    pub similarity_metric: SimilarityMetric,
     /// Minimum confidence for obligations to be considered for conflict detection
     pub confidence_threshold: f64,
     /// Tolerance ratio for temporal conflicts (e.g., 0.5 means 50% difference is a conflict)
     pub temporal_tolerance: f64,
+    /// Which obligation pairs are compared
+    pub scope: ConflictScope,
+    /// Maximum number of obligation pairs to compare; see [`with_max_pairs`](Self::with_max_pairs)
+    pub max_pairs: Option<usize>,
     /// Topic classifier
     classifier: TopicClassifier,
     /// Obligation normalizer
     normalizer: ObligationNormalizer,
+    /// Whether actions are lemmatized with their POS tags; see
+    /// [`with_pos_lemmatization`](Self::with_pos_lemmatization)
+    pos_lemmatization: bool,
 }
 
 impl Default for ConflictDetector {
@@ -649,10 +1052,14 @@ impl ConflictDetector {
     pub fn new() -> Self {
         Self {
             similarity_threshold: 0.7,
+            similarity_metric: SimilarityMetric::Jaccard,
             confidence_threshold: 0.5,
             temporal_tolerance: 0.5,
+            scope: ConflictScope::WholeDocument,
+            max_pairs: None,
             classifier: TopicClassifier::new(),
             normalizer: ObligationNormalizer::new(),
+            pos_lemmatization: false,
         }
     }
 
@@ -664,13 +1071,58 @@ impl ConflictDetector {
     ) -> Self {
         Self {
             similarity_threshold,
+            similarity_metric: SimilarityMetric::Jaccard,
             confidence_threshold,
             temporal_tolerance,
+            scope: ConflictScope::WholeDocument,
+            max_pairs: None,
             classifier: TopicClassifier::new(),
             normalizer: ObligationNormalizer::new(),
+            pos_lemmatization: false,
         }
     }
 
+    /// Restricts conflict detection to obligations within the given scope.
+    pub fn with_scope(mut self, scope: ConflictScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Compares actions with `metric` instead of plain Jaccard.
+    pub fn with_similarity_metric(mut self, metric: SimilarityMetric) -> Self {
+        self.similarity_metric = metric;
+        self
+    }
+
+    /// Caps the number of obligation pairs compared per document.
+    ///
+    /// When a document needs more pairs than `max_pairs`, a whole-document
+    /// comparison first falls back to comparing obligations within the same
+    /// section. If that is still over budget, each obligation is compared only
+    /// with the next few obligations in document order, keeping the number of
+    /// pairs near `max_pairs` (at least one per obligation).
+    /// [`detect_with_report`](Self::detect_with_report) says what was skipped.
+    pub fn with_max_pairs(mut self, max_pairs: usize) -> Self {
+        self.max_pairs = Some(max_pairs);
+        self
+    }
+
+    /// Lemmatizes obligation actions with the POS tags of their own tokens
+    /// (see [`ObligationNormalizer::normalize_action_with_pos`]), so verbs
+    /// missing from the lemma table still match across inflections. The
+    /// document must have been processed with `POSTagResolver`.
+    pub fn with_pos_lemmatization(mut self, enabled: bool) -> Self {
+        self.pos_lemmatization = enabled;
+        self
+    }
+
+    /// Classifies obligation topics with `classifier`, e.g. one extended with
+    /// [`TopicClassifier::add_topic`].
+    pub fn with_classifier(mut self, classifier: TopicClassifier) -> Self {
+        self.classifier = classifier;
+        self
+    }
+
     /// Returns a reference to the topic classifier.
     pub fn classifier(&self) -> &TopicClassifier {
         &self.classifier
@@ -681,28 +1133,12 @@ impl ConflictDetector {
         &self.normalizer
     }
 
-    /// Computes Jaccard similarity between two action strings.
-    ///
-    /// The Jaccard coefficient is defined as |A ∩ B| / |A ∪ B| where
-    /// A and B are the sets of words in each action.
+    /// Computes the similarity between two action strings using the
+    /// configured [`SimilarityMetric`] (Jaccard by default).
     ///
     /// Returns 1.0 for identical actions, 0.0 for completely different actions.
     pub fn action_similarity(&self, a: &str, b: &str) -> f64 {
-        let words_a: std::collections::HashSet<&str> = a.split_whitespace().collect();
-        let words_b: std::collections::HashSet<&str> = b.split_whitespace().collect();
-
-        if words_a.is_empty() && words_b.is_empty() {
-            return 1.0;
-        }
-
-        let intersection = words_a.intersection(&words_b).count();
-        let union = words_a.union(&words_b).count();
-
-        if union == 0 {
-            return 0.0;
-        }
-
-        intersection as f64 / union as f64
+        self.similarity_metric.similarity(a, b)
     }
 
     /// Detects a modal conflict between two obligations.
@@ -711,6 +1147,13 @@ impl ConflictDetector {
     /// - Same obligor (party)
     /// - Same or similar action
     /// - Different obligation types (e.g., Duty vs Permission)
+    /// - Same nature: a representation and a performance duty with similar
+    ///   wording say different things
+    ///
+    /// A permission against a prohibition ("may disclose" vs "shall not
+    /// disclose") is the plainest contradiction and gets its own explanation;
+    /// [`detect_party_conflict`](Self::detect_party_conflict) never sees it,
+    /// since it compares obligations of the same type.
     pub fn detect_modal_conflict(
         &self,
         a: &NormalizedObligation,
@@ -721,6 +1164,10 @@ impl ConflictDetector {
             return None;
         }
 
+        if a.obligation_nature != b.obligation_nature {
+            return None;
+        }
+
         // Different obligation type required
         if a.obligation_type == b.obligation_type {
             return None;
@@ -732,10 +1179,22 @@ impl ConflictDetector {
             return None;
         }
 
-        let explanation = format!(
-            "{} has conflicting obligations: '{}' ({:?}) vs '{}' ({:?})",
-            a.obligor, a.action, a.obligation_type, b.action, b.obligation_type
+        let permits_and_prohibits = matches!(
+            (a.obligation_type, b.obligation_type),
+            (ObligationType::Permission, ObligationType::Prohibition)
+                | (ObligationType::Prohibition, ObligationType::Permission)
         );
+        let explanation = if permits_and_prohibits {
+            format!(
+                "{} is both permitted and prohibited: '{}' ({:?}) vs '{}' ({:?})",
+                a.obligor, a.action, a.obligation_type, b.action, b.obligation_type
+            )
+        } else {
+            format!(
+                "{} has conflicting obligations: '{}' ({:?}) vs '{}' ({:?})",
+                a.obligor, a.action, a.obligation_type, b.action, b.obligation_type
+            )
+        };
 
         let conflict = Conflict::new(
             a.original_span,
@@ -754,6 +1213,8 @@ impl ConflictDetector {
     /// - Same obligor
     /// - Same or similar action
     /// - Incompatible timing (e.g., "within 15 days" vs "within 30 days")
+    /// - Both are performance duties; timing in a representation describes
+    ///   the facts stated, not a deadline
     ///
     /// The temporal_tolerance controls how much difference is considered a conflict.
     /// For example, 0.5 means a 50% difference triggers a conflict.
@@ -767,6 +1228,10 @@ impl ConflictDetector {
             return None;
         }
 
+        if !a.obligation_nature.is_performance() || !b.obligation_nature.is_performance() {
+            return None;
+        }
+
         // Both must have timing information
         let timing_a = a.timing.as_ref()?;
         let timing_b = b.timing.as_ref()?;
@@ -822,6 +1287,8 @@ impl ConflictDetector {
     /// - Different obligors (parties)
     /// - Same or similar action
     /// - Same obligation type (both duties or both prohibitions)
+    /// - Both are performance duties; parties routinely make the same
+    ///   representation or acknowledgment
     ///
     /// This indicates contradictory assignments of the same responsibility.
     pub fn detect_party_conflict(
@@ -844,6 +1311,10 @@ impl ConflictDetector {
             return None;
         }
 
+        if !a.obligation_nature.is_performance() || !b.obligation_nature.is_performance() {
+            return None;
+        }
+
         // Similar action required (high threshold for party conflicts)
         let similarity = self.action_similarity(&a.action, &b.action);
         if similarity < self.similarity_threshold {
@@ -873,17 +1344,54 @@ impl ConflictDetector {
         &self,
         obligations: &[NormalizedObligation],
     ) -> Vec<Scored<Conflict>> {
+        let scopes = vec![None; obligations.len()];
+        self.detect_conflicts_within(obligations, &scopes, None, None).0
+    }
+
+    /// Compares only pairs of obligations whose scope keys are equal.
+    ///
+    /// `scopes[i]` is the scope key of `obligations[i]`; `None` is a scope of its own
+    /// (e.g. text before the first section) and compares equal to other `None`s.
+    /// With a `focus` range, only pairs with at least one obligation on a line in
+    /// that range are compared. With a `window`, each obligation is compared only
+    /// with the next `window` obligations in document order.
+    ///
+    /// Returns the conflicts and the number of pairs compared.
+    fn detect_conflicts_within(
+        &self,
+        obligations: &[NormalizedObligation],
+        scopes: &[Option<usize>],
+        focus: Option<&Range<usize>>,
+        window: Option<usize>,
+    ) -> (Vec<Scored<Conflict>>, usize) {
         let mut conflicts = Vec::new();
+        let mut compared = 0;
 
         // Sort by position for deterministic ordering
-        let mut sorted: Vec<_> = obligations.iter().collect();
-        sorted.sort_by_key(|o| (o.line_index, o.original_span.start.token));
+        let mut sorted: Vec<_> = obligations.iter().zip(scopes.iter()).collect();
+        sorted.sort_by_key(|(o, _)| (o.line_index, o.original_span.start.token));
 
         // Compare all pairs
         for i in 0..sorted.len() {
-            for j in (i + 1)..sorted.len() {
-                let a = sorted[i];
-                let b = sorted[j];
+            let end = match window {
+                Some(window) => sorted.len().min(i + 1 + window),
+                None => sorted.len(),
+            };
+            for j in (i + 1)..end {
+                let (a, scope_a) = sorted[i];
+                let (b, scope_b) = sorted[j];
+
+                if scope_a != scope_b {
+                    continue;
+                }
+
+                if let Some(focus) = focus {
+                    if !focus.contains(&a.line_index) && !focus.contains(&b.line_index) {
+                        continue;
+                    }
+                }
+
+                compared += 1;
 
                 // Try each conflict type
                 if let Some(conflict) = self.detect_modal_conflict(a, b) {
@@ -900,7 +1408,7 @@ impl ConflictDetector {
             }
         }
 
-        conflicts
+        (conflicts, compared)
     }
 
     /// Normalizes an obligation phrase and classifies its topic.
@@ -940,44 +1448,350 @@ impl ConflictDetector {
     /// let detector = ConflictDetector::new();
     /// let conflicts = detector.detect_in_document(&doc);
     /// ```
+    ///
+    /// With [`ConflictScope::SameSection`] the document's cached structure is
+    /// used (built from `SectionHeader` attributes on first access); use [`detect_in_document_with_structure`] to
+    /// reuse an already-built structure.
+    ///
+    /// [`detect_in_document_with_structure`]: Self::detect_in_document_with_structure
     pub fn detect_in_document(
         &self,
         doc: &crate::ContractDocument,
     ) -> Vec<Scored<Conflict>> {
-        use crate::obligation::ObligationPhrase;
-        use layered_nlp::x;
+        self.detect_with_report(doc).conflicts
+    }
 
-        let mut obligations = Vec::new();
+    /// Detects conflicts in a contract document, reporting any comparisons
+    /// skipped to stay within [`max_pairs`](Self::with_max_pairs).
+    pub fn detect_with_report(&self, doc: &crate::ContractDocument) -> ConflictReport {
+        let empty = DocumentStructure::empty();
+        // An over-budget document falls back to section scoping
+        let structure = match (self.scope, self.max_pairs) {
+            (ConflictScope::SameSection, _) | (_, Some(_)) => doc.structure(),
+            _ => &empty,
+        };
+        self.report_with_structure(doc, structure)
+    }
 
+    /// Detects conflicts in a contract document using a pre-built document structure.
+    ///
+    /// The structure is only consulted for [`ConflictScope::SameSection`]. For
+    /// [`ConflictScope::SameSentence`] the document must have been processed with
+    /// `SentenceBoundaryResolver`; without any boundaries each line is treated as
+    /// one sentence.
+    pub fn detect_in_document_with_structure(
+        &self,
+        doc: &crate::ContractDocument,
+        structure: &DocumentStructure,
+    ) -> Vec<Scored<Conflict>> {
+        self.report_with_structure(doc, structure).conflicts
+    }
+
+    /// Runs [`compare_pairs`](Self::compare_pairs) as a traced stage.
+    fn report_with_structure(
+        &self,
+        doc: &crate::ContractDocument,
+        structure: &DocumentStructure,
+    ) -> ConflictReport {
+        let stage = StageSpan::start("analysis", "detect_in_document");
+        let report = self.compare_pairs(doc, structure);
+        stage.finish(|| report.conflicts.len());
+        report
+    }
+
+    /// Compares obligation pairs within the configured scope and budget.
+    fn compare_pairs(
+        &self,
+        doc: &crate::ContractDocument,
+        structure: &DocumentStructure,
+    ) -> ConflictReport {
+        let obligations = self.collect_obligations(doc);
+        let mut scopes = self.scope_keys(doc, structure, &obligations);
+        let required = pair_count(&scopes);
+        let budget = match self.max_pairs {
+            Some(budget) if required > budget => budget,
+            _ => {
+                let (mut conflicts, _) =
+                    self.detect_conflicts_within(&obligations, &scopes, None, None);
+                conflicts.extend(self.detect_governing_law_conflicts(doc));
+                return ConflictReport { conflicts, degradation: None };
+            }
+        };
+
+        let mut window = None;
+        let mut fallback = DegradationFallback::SectionScoped;
+        let sections = structure.flatten();
+        let section_scopes: Vec<Option<usize>> = obligations
+            .iter()
+            .map(|o| innermost_section(&sections, o.line_index))
+            .collect();
+        if self.scope == ConflictScope::WholeDocument && pair_count(&section_scopes) <= budget {
+            scopes = section_scopes;
+        } else {
+            let size = (budget / obligations.len()).max(1);
+            window = Some(size);
+            fallback = DegradationFallback::Windowed { window: size };
+        }
+
+        let (mut conflicts, compared) =
+            self.detect_conflicts_within(&obligations, &scopes, None, window);
+        conflicts.extend(self.detect_governing_law_conflicts(doc));
+        ConflictReport {
+            conflicts,
+            degradation: Some(DegradationReport {
+                unit: "obligation pairs".to_string(),
+                budget,
+                required,
+                compared,
+                fallback,
+                skipped_stages: Vec::new(),
+            }),
+        }
+    }
+
+    /// Re-detects conflicts after an edit to `changed_lines`.
+    ///
+    /// Only pairs involving an obligation on a changed line, or on the line just
+    /// before or after the edit, are re-compared. Conflicts in `previous` between
+    /// two obligations outside that region are carried over unchanged. The edit
+    /// is assumed not to have shifted line indices (lines replaced in place).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let before = detector.detect_in_document(&doc);
+    /// // ... user edits line 12, document is re-processed ...
+    /// let delta = detector.detect_incremental(&edited, &before, 12..13);
+    /// for conflict in &delta.added { /* show new warning */ }
+    /// ```
+    pub fn detect_incremental(
+        &self,
+        doc: &crate::ContractDocument,
+        previous: &[Scored<Conflict>],
+        changed_lines: Range<usize>,
+    ) -> ConflictDelta {
+        let affected = changed_lines.start.saturating_sub(1)..changed_lines.end.saturating_add(1);
+        let touches_affected = |conflict: &Scored<Conflict>| {
+            let span_lines = |span: &DocSpan| span.start.line..=span.end.line;
+            span_lines(&conflict.value.span_a)
+                .chain(span_lines(&conflict.value.span_b))
+                .any(|line| affected.contains(&line))
+        };
+
+        let empty = DocumentStructure::empty();
+        let structure = match self.scope {
+            ConflictScope::SameSection => doc.structure(),
+            _ => &empty,
+        };
+        let obligations = self.collect_obligations(doc);
+        let scopes = self.scope_keys(doc, structure, &obligations);
+        let (mut recomputed, _) =
+            self.detect_conflicts_within(&obligations, &scopes, Some(&affected), None);
+        recomputed.extend(
+            self.detect_governing_law_conflicts(doc)
+                .into_iter()
+                .filter(|c| touches_affected(c)),
+        );
+
+        let (stale, mut conflicts): (Vec<_>, Vec<_>) =
+            previous.iter().cloned().partition(|c| touches_affected(c));
+
+        let removed = stale
+            .iter()
+            .filter(|old| !recomputed.iter().any(|new| new.value == old.value))
+            .cloned()
+            .collect();
+        let added = recomputed
+            .iter()
+            .filter(|new| !stale.iter().any(|old| old.value == new.value))
+            .cloned()
+            .collect();
+        conflicts.extend(recomputed);
+
+        ConflictDelta { added, removed, conflicts }
+    }
+
+    /// Detects governing-law clauses that name different jurisdictions.
+    ///
+    /// A contract should have exactly one governing law, so this always
+    /// compares every pair of `Scored<GoverningLaw>` annotations in the
+    /// document, regardless of [`ConflictScope`] or the pair budget. Clauses
+    /// naming the same jurisdiction ("the State of New York" and "New York")
+    /// do not conflict. Clearly different jurisdictions are reported with
+    /// confidence 0.95; when one name contains the other ("England" and
+    /// "England and Wales") the conflict may be a drafting variation and is
+    /// reported with 0.6.
+    ///
+    /// Requires `GoverningLawResolver` to have run on the document's lines.
+    pub fn detect_governing_law_conflicts(
+        &self,
+        doc: &crate::ContractDocument,
+    ) -> Vec<Scored<Conflict>> {
+        let mut clauses: Vec<(DocSpan, GoverningLaw)> = Vec::new();
         for (line_index, line) in doc.lines().iter().enumerate() {
-            // Find all ObligationPhrase attributes in this line
-            for found in line.find(&x::attr::<Scored<ObligationPhrase>>()) {
-                let scored = found.attr();
+            for found in line.find(&x::attr::<Scored<GoverningLaw>>()) {
+                let span = DocSpan::from_find(line_index, &found);
+                clauses.push((span, found.attr().value.clone()));
+            }
+        }
 
-                // Only process obligations above confidence threshold
-                if scored.confidence < self.confidence_threshold {
+        let mut conflicts = Vec::new();
+        for (i, (span_a, law_a)) in clauses.iter().enumerate() {
+            for (span_b, law_b) in &clauses[i + 1..] {
+                let a = law_a.normalized_jurisdiction();
+                let b = law_b.normalized_jurisdiction();
+                if a == b {
                     continue;
                 }
+                let confidence = if a.contains(b.as_str()) || b.contains(a.as_str()) {
+                    0.6
+                } else {
+                    0.95
+                };
+                let explanation = format!(
+                    "Conflicting governing law: the laws of {} vs the laws of {}",
+                    law_a.jurisdiction, law_b.jurisdiction
+                );
+                let conflict_type = ConflictType::GoverningLawConflict;
+                let conflict = Conflict::new(*span_a, *span_b, conflict_type, explanation);
+                conflicts.push(Scored::rule_based(
+                    conflict,
+                    confidence,
+                    "governing_law_conflict",
+                ));
+            }
+        }
+        conflicts
+    }
 
-                // Get the span of the obligation within the line
-                let (start_token, end_token) = found.range();
+    /// Collects and normalizes every obligation above the confidence threshold.
+    fn collect_obligations(&self, doc: &crate::ContractDocument) -> Vec<NormalizedObligation> {
+        use crate::obligation::ObligationPhrase;
 
-                // Normalize and classify
-                let normalized = self.normalize_and_classify(
-                    scored,
-                    line_index,
-                    start_token,
-                    end_token,
-                );
+        let mut obligations = Vec::new();
 
-                obligations.push(normalized);
+        for (line_index, line) in doc.lines().iter().enumerate() {
+            // Find all ObligationPhrase attributes in this line
+            for ((start_token, end_token), _, values) in
+                line.query_with_associations::<Scored<ObligationPhrase>>()
+            {
+                for (scored, associations) in values {
+                    // Only process obligations above confidence threshold
+                    if scored.confidence < self.confidence_threshold {
+                        continue;
+                    }
+
+                    let action_span = associations
+                        .iter()
+                        .find(|assoc| assoc.association_type_id() == TypeId::of::<ActionSpan>())
+                        .map(|assoc| assoc.span);
+
+                    // Normalize (with the action's POS tags if enabled), then classify
+                    let mut normalized = match action_span {
+                        Some(action_span) if self.pos_lemmatization => {
+                            self.normalizer.normalize_with_pos(
+                                scored,
+                                line,
+                                action_span,
+                                line_index,
+                                start_token,
+                                end_token,
+                            )
+                        }
+                        _ => self.normalizer.normalize(scored, line_index, start_token, end_token),
+                    };
+                    normalized.topic = self.classifier.classify(&normalized);
+
+                    obligations.push(normalized);
+                }
             }
         }
 
-        self.detect_conflicts(&obligations)
+        obligations
+    }
+
+    /// Computes the scope key of each obligation for the configured [`ConflictScope`].
+    fn scope_keys(
+        &self,
+        doc: &crate::ContractDocument,
+        structure: &DocumentStructure,
+        obligations: &[NormalizedObligation],
+    ) -> Vec<Option<usize>> {
+        match self.scope {
+            ConflictScope::WholeDocument => vec![None; obligations.len()],
+            ConflictScope::SameSection => {
+                let sections = structure.flatten();
+                obligations
+                    .iter()
+                    .map(|o| innermost_section(&sections, o.line_index))
+                    .collect()
+            }
+            ConflictScope::SameSentence => {
+                let boundaries = sentence_boundaries(doc);
+                obligations
+                    .iter()
+                    .map(|o| {
+                        if boundaries.is_empty() {
+                            return Some(o.line_index);
+                        }
+                        let start = (o.original_span.start.line, o.original_span.start.token);
+                        Some(boundaries.iter().filter(|&&b| b < start).count())
+                    })
+                    .collect()
+            }
+        }
     }
 }
 
+/// Result of [`ConflictDetector::detect_incremental`].
+#[derive(Debug, Clone, Default)]
+pub struct ConflictDelta {
+    /// Conflicts that did not exist before the edit
+    pub added: Vec<Scored<Conflict>>,
+    /// Previous conflicts that no longer hold after the edit
+    pub removed: Vec<Scored<Conflict>>,
+    /// The full, updated conflict list (carried-over plus re-detected)
+    pub conflicts: Vec<Scored<Conflict>>,
+}
+
+/// Result of [`ConflictDetector::detect_with_report`].
+#[derive(Debug, Clone, Default)]
+pub struct ConflictReport {
+    /// Detected conflicts
+    pub conflicts: Vec<Scored<Conflict>>,
+    /// What was skipped when the document exceeded the pair budget
+    pub degradation: Option<DegradationReport>,
+}
+
+/// Number of pairs whose scope keys are equal.
+fn pair_count(scopes: &[Option<usize>]) -> usize {
+    let mut sizes: HashMap<Option<usize>, usize> = HashMap::new();
+    for scope in scopes {
+        *sizes.entry(*scope).or_default() += 1;
+    }
+    sizes.values().map(|&size| size * size.saturating_sub(1) / 2).sum()
+}
+
+/// Returns the index (into `sections`) of the innermost section containing `line`.
+fn innermost_section(sections: &[&SectionNode], line: usize) -> Option<usize> {
+    sections
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.start_line <= line && line < s.end_line.unwrap_or(usize::MAX))
+        .max_by_key(|(_, s)| s.start_line)
+        .map(|(idx, _)| idx)
+}
+
+/// Collects the (line, token) positions of all sentence boundaries in document order.
+fn sentence_boundaries(doc: &crate::ContractDocument) -> Vec<(usize, usize)> {
+    let mut boundaries = Vec::new();
+    for (line_index, line) in doc.lines().iter().enumerate() {
+        for found in line.find(&x::attr::<SentenceBoundary>()) {
+            boundaries.push((line_index, found.token_range().0));
+        }
+    }
+    boundaries
+}
+
 // ============================================================================
 // DocumentResolver Implementation
 // ============================================================================
@@ -1146,6 +1960,42 @@ mod tests {
         assert_eq!(obligation.topic, ObligationTopic::Delivery);
     }
 
+    #[test]
+    fn test_canonical_key_merges_trivial_phrasings() {
+        let obligation = |obligor: &str, action: &str, timing: Option<NormalizedTiming>| {
+            NormalizedObligation::new(
+                obligor.to_string(),
+                ObligationType::Duty,
+                action.to_string(),
+                timing,
+                make_span(0, 0, 0, 10),
+                0,
+                ObligationTopic::Delivery,
+            )
+        };
+        let weeks = Some(NormalizedTiming::new(4.0, TimeUnit::Weeks, false));
+        let days = Some(NormalizedTiming::new(28.0, TimeUnit::Days, false));
+
+        let key = obligation("company", "deliver the goods", days.clone()).canonical_key();
+        assert_eq!(key, "company|deliver goods|DUTY|28d");
+        assert_eq!(obligation("Company", "deliver  goods,", weeks).canonical_key(), key);
+
+        // Genuinely different obligations keep different keys
+        let business = Some(NormalizedTiming::new(28.0, TimeUnit::BusinessDays, false));
+        assert_ne!(obligation("company", "deliver goods", business).canonical_key(), key);
+        assert_ne!(obligation("company", "deliver goods", None).canonical_key(), key);
+        assert_ne!(
+            obligation("a|b", "c", None).canonical_key(),
+            obligation("a", "b|c", None).canonical_key()
+        );
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for action in ["deliver goods", "deliver the goods", "pay fees"] {
+            *counts.entry(obligation("company", action, None).canonical_key()).or_default() += 1;
+        }
+        assert_eq!(counts["company|deliver goods|DUTY|-"], 2);
+    }
+
     // ========================================================================
     // Gate 1: ObligationNormalizer Tests
     // ========================================================================
@@ -1197,6 +2047,91 @@ mod tests {
         assert_eq!(normalizer.normalize_action("delivers xyz"), "deliver xyz");
     }
 
+    #[test]
+    fn test_normalize_action_with_pos_lemmatizes_untabled_verbs() {
+        use layered_part_of_speech::POSTagResolver;
+
+        let normalizer = ObligationNormalizer::new();
+        let line = layered_nlp::create_line_from_string(
+            "The Vendor approved, assigned and certified the invoices after submitting them.",
+        )
+        .run(&POSTagResolver::default());
+        let whole_line = SpanRef::new(0, line.ll_tokens().len() - 1);
+        let normalize = |action| normalizer.normalize_action_with_pos(action, &line, whole_line);
+
+        assert_eq!(normalize("approved the invoices"), "approve the invoices");
+        assert_eq!(normalize("assigned"), "assign");
+        assert_eq!(normalize("certified"), "certify");
+        // Table entries still take precedence
+        assert_eq!(normalize("submitting"), "submit");
+        // Without POS tags, out-of-table verbs are left inflected
+        assert_eq!(normalizer.normalize_action("approved"), "approved");
+    }
+
+    #[test]
+    fn test_normalize_action_with_pos_keys_verbs_by_token() {
+        use layered_nlp::{LLCursorAssignment, LLSelection, Resolver};
+
+        /// Tags only the first "certified" as a verb.
+        struct FirstCertifiedIsVerb;
+
+        impl Resolver for FirstCertifiedIsVerb {
+            type Attr = Tag;
+
+            fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+                selection
+                    .find_by(&x::token_text())
+                    .into_iter()
+                    .filter(|(_, text)| *text == "certified")
+                    .take(1)
+                    .map(|(sel, _)| sel.finish_with_attr(Tag::Verb))
+                    .collect()
+            }
+        }
+
+        let normalizer = ObligationNormalizer::new();
+        let line = layered_nlp::create_line_from_string(
+            "The Buyer certified the copies and the Vendor shall keep certified copies.",
+        )
+        .run(&FirstCertifiedIsVerb);
+
+        // "certified" at token 4 is a verb, the one at token 20 is not
+        assert_eq!(
+            normalizer.normalize_action_with_pos("certified the copies", &line, SpanRef::new(4, 8)),
+            "certify the copies"
+        );
+        assert_eq!(
+            normalizer.normalize_action_with_pos("keep certified copies", &line, SpanRef::new(18, 22)),
+            "keep certified copies"
+        );
+    }
+
+    #[test]
+    fn test_strip_inflection_rules() {
+        assert_eq!(ObligationNormalizer::strip_inflection("renews"), "renew");
+        assert_eq!(ObligationNormalizer::strip_inflection("processes"), "process");
+        assert_eq!(ObligationNormalizer::strip_inflection("access"), "access");
+        assert_eq!(ObligationNormalizer::strip_inflection("invoiced"), "invoice");
+        assert_eq!(ObligationNormalizer::strip_inflection("validating"), "validate");
+        assert_eq!(ObligationNormalizer::strip_inflection("permitted"), "permit");
+        assert_eq!(ObligationNormalizer::strip_inflection("installing"), "install");
+        assert_eq!(ObligationNormalizer::strip_inflection("is"), "is");
+    }
+
+    #[test]
+    fn test_normalize_action_excludes_parentheticals() {
+        let normalizer = ObligationNormalizer::new();
+
+        assert_eq!(
+            normalizer.normalize_action("deliver (subject to availability)"),
+            "deliver"
+        );
+        assert_eq!(
+            normalizer.normalize_action("delivers (as (defined) below) the goods"),
+            "deliver the goods"
+        );
+    }
+
     #[test]
     fn test_normalize_timing_numeric_days() {
         let normalizer = ObligationNormalizer::new();
@@ -1463,6 +2398,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_custom_topics_checked_before_other() {
+        let classifier = TopicClassifier::new()
+            .with_topic("Audit", ["audit", "Books and Records"])
+            .with_topic("Insurance", ["insurance", "insure", "audit"]);
+
+        assert_eq!(
+            classifier.classify(&make_obligation("maintain accurate books and records")),
+            ObligationTopic::Custom("Audit".to_string())
+        );
+        // First registered topic wins
+        assert_eq!(
+            classifier.classify(&make_obligation("submit to an audit")),
+            ObligationTopic::Custom("Audit".to_string())
+        );
+        assert_eq!(
+            classifier.classify(&make_obligation("carry general insurance")),
+            ObligationTopic::Custom("Insurance".to_string())
+        );
+        // Built-in topics still take precedence
+        assert_eq!(
+            classifier.classify(&make_obligation("pay the audit fees")),
+            ObligationTopic::Payment
+        );
+        assert_eq!(
+            classifier.classify(&make_obligation("comply with applicable laws")),
+            ObligationTopic::Other
+        );
+    }
+
     #[test]
     fn test_word_boundary_prevents_false_positives() {
         let classifier = TopicClassifier::new();
@@ -1603,6 +2568,51 @@ mod tests {
         assert_eq!(detector.action_similarity("", ""), 1.0);
     }
 
+    #[test]
+    fn test_stopword_metric_ignores_articles() {
+        let jaccard = ConflictDetector::new();
+        let stopwords = ConflictDetector::new()
+            .with_similarity_metric(SimilarityMetric::JaccardWithoutStopwords);
+
+        let plain = jaccard.action_similarity("deliver the goods", "deliver goods");
+        assert!(plain < jaccard.similarity_threshold);
+        assert_eq!(stopwords.action_similarity("deliver the goods", "deliver goods"), 1.0);
+        assert_eq!(
+            stopwords.action_similarity("deliver goods to the Buyer", "deliver goods for Buyer"),
+            1.0
+        );
+        assert_eq!(stopwords.action_similarity("pay invoice", "ship products"), 0.0);
+
+        // An article-only difference now reaches the threshold and conflicts
+        let shall = make_obligation_full("company", ObligationType::Duty, "deliver the goods", None, 0);
+        let may = make_obligation_full("company", ObligationType::Permission, "deliver goods", None, 1);
+        assert!(jaccard.detect_modal_conflict(&shall, &may).is_none());
+        assert!(stopwords.detect_modal_conflict(&shall, &may).is_some());
+    }
+
+    #[test]
+    fn test_levenshtein_metric_respects_word_order() {
+        let detector = ConflictDetector::new().with_similarity_metric(SimilarityMetric::Levenshtein);
+
+        assert_eq!(detector.action_similarity("deliver goods", "deliver goods"), 1.0);
+        let sim = detector.action_similarity("deliver the goods", "deliver goods");
+        assert!((sim - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(detector.action_similarity("goods deliver", "deliver goods"), 0.0);
+        assert_eq!(detector.action_similarity("", ""), 1.0);
+    }
+
+    #[test]
+    fn test_custom_similarity_metric() {
+        let detector = ConflictDetector::new()
+            .with_similarity_metric(SimilarityMetric::custom(|a, b| {
+                if a.split_whitespace().next() == b.split_whitespace().next() { 1.0 } else { 0.0 }
+            }));
+
+        assert_eq!(detector.action_similarity("deliver goods", "deliver services"), 1.0);
+        assert_eq!(detector.action_similarity("pay fees", "deliver goods"), 0.0);
+        assert_eq!(format!("{:?}", detector.similarity_metric), "Custom(..)");
+    }
+
     #[test]
     fn test_detect_modal_conflict() {
         let detector = ConflictDetector::new();
@@ -1630,6 +2640,39 @@ mod tests {
         assert!(scored.confidence >= 0.7); // High similarity
     }
 
+    #[test]
+    fn test_permission_prohibition_conflict() {
+        let detector = ConflictDetector::new();
+
+        let may_disclose = make_obligation_full(
+            "company",
+            ObligationType::Permission,
+            "disclose the confidential information",
+            None,
+            0,
+        );
+        let shall_not_disclose = make_obligation_full(
+            "company",
+            ObligationType::Prohibition,
+            "disclose the confidential information",
+            None,
+            1,
+        );
+
+        let conflict = detector.detect_modal_conflict(&may_disclose, &shall_not_disclose).unwrap();
+        assert_eq!(conflict.value.conflict_type, ConflictType::ModalConflict);
+        assert!(conflict.value.explanation.contains("both permitted and prohibited"));
+        // Same type is required for a party conflict, so it cannot catch this pair
+        assert!(detector.detect_party_conflict(&may_disclose, &shall_not_disclose).is_none());
+
+        let shall_deliver =
+            make_obligation_full("company", ObligationType::Duty, "deliver goods", None, 0);
+        let may_deliver =
+            make_obligation_full("company", ObligationType::Permission, "deliver goods", None, 1);
+        let conflict = detector.detect_modal_conflict(&shall_deliver, &may_deliver).unwrap();
+        assert!(!conflict.value.explanation.contains("both permitted and prohibited"));
+    }
+
     #[test]
     fn test_no_modal_conflict_different_obligor() {
         let detector = ConflictDetector::new();
@@ -1777,6 +2820,22 @@ mod tests {
         assert!(conflict.is_none());
     }
 
+    #[test]
+    fn test_no_party_conflict_for_representations() {
+        let detector = ConflictDetector::new();
+
+        // Each party making the same representation is not a contradiction
+        let company = make_obligation_full("company", ObligationType::Duty, "represent", None, 0)
+            .with_nature(ObligationNature::Representation);
+        let vendor = make_obligation_full("vendor", ObligationType::Duty, "represent", None, 1)
+            .with_nature(ObligationNature::Representation);
+        assert!(detector.detect_party_conflict(&company, &vendor).is_none());
+
+        // Nor does a representation conflict with a duty worded alike
+        let may = make_obligation_full("company", ObligationType::Permission, "represent", None, 1);
+        assert!(detector.detect_modal_conflict(&company, &may).is_none());
+    }
+
     #[test]
     fn test_detect_conflicts_multiple() {
         let detector = ConflictDetector::new();
@@ -1882,6 +2941,116 @@ ABC Corp (the "Company") may deliver goods."#;
         );
     }
 
+    #[test]
+    fn test_pos_lemmatization_is_opt_in() {
+        let doc = run_full_pipeline("The Company shall keep the renewed permits.");
+
+        let actions = |detector: ConflictDetector| -> Vec<String> {
+            detector.collect_obligations(&doc).into_iter().map(|o| o.action).collect()
+        };
+        assert_eq!(actions(ConflictDetector::new()), vec!["keep the renewed permits"]);
+        assert_eq!(
+            actions(ConflictDetector::new().with_pos_lemmatization(true)),
+            vec!["keep the renew permit"]
+        );
+    }
+
+    #[test]
+    fn test_detect_in_document_permission_prohibition_conflict() {
+        let text = r#"ABC Corp (the "Company") may disclose the Confidential Information.
+The Company shall not disclose the Confidential Information."#;
+
+        let doc = run_full_pipeline(text);
+        let conflicts = ConflictDetector::new().detect_in_document(&doc);
+
+        assert_eq!(conflicts.len(), 1, "{:?}", conflicts);
+        let conflict = &conflicts[0].value;
+        assert_eq!(conflict.conflict_type, ConflictType::ModalConflict);
+        assert!(conflict.explanation.contains("both permitted and prohibited"));
+        assert_eq!((conflict.span_a.start.line, conflict.span_b.start.line), (0, 1));
+    }
+
+    #[test]
+    fn test_negative_verb_folds_into_obligation_type() {
+        assert_eq!(
+            ObligationNormalizer::split_negative_verb("fail to deliver the goods"),
+            Some(("fail", "deliver the goods"))
+        );
+        assert_eq!(ObligationNormalizer::split_negative_verb("deliver the goods"), None);
+        assert_eq!(ObligationNormalizer::split_negative_verb("fail to"), None);
+
+        let text = r#"ABC Corp (the "Company") shall deliver the goods.
+The Company shall not fail to deliver the goods.
+The Company shall not refuse to deliver the goods.
+The Company shall fail to deliver the goods."#;
+        let doc = run_full_pipeline(text);
+        let detector = ConflictDetector::new();
+        let normalized: Vec<NormalizedObligation> = doc
+            .lines()
+            .iter()
+            .enumerate()
+            .flat_map(|(line_index, line)| {
+                line.find(&x::attr::<Scored<crate::ObligationPhrase>>())
+                    .into_iter()
+                    .map(|found| {
+                        let (start, end) = found.token_range();
+                        detector.normalize_and_classify(found.attr(), line_index, start, end)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        assert_eq!(normalized.len(), 4);
+        // Double negatives are the plain duty, and dedupe with it
+        for negated in &normalized[1..3] {
+            assert_eq!(negated.obligation_type, ObligationType::Duty);
+            assert_eq!(negated.canonical_key(), normalized[0].canonical_key());
+        }
+        assert_eq!(normalized[0].negative_verb, None);
+        assert_eq!(normalized[1].negative_verb.as_deref(), Some("fail"));
+        assert_eq!(normalized[2].negative_verb.as_deref(), Some("refuse"));
+        assert_eq!(normalized[3].obligation_type, ObligationType::Prohibition);
+        assert_eq!(normalized[3].action, "deliver the goods");
+
+        // Only the plain prohibition on the last line contradicts the duties
+        let conflicts = detector.detect_in_document(&doc);
+        assert_eq!(conflicts.len(), 3, "{:?}", conflicts);
+        assert!(conflicts.iter().all(|c| c.value.conflict_type == ConflictType::ModalConflict
+            && c.value.span_b.start.line == 3));
+    }
+
+    #[test]
+    fn test_detect_in_document_negated_duty_conflict() {
+        use layered_part_of_speech::POSTagResolver;
+        use crate::{
+            ContractKeywordResolver, DefinedTermResolver, ObligationPhrase,
+            ObligationPhraseResolver, PronounResolver, TermReferenceResolver,
+        };
+
+        // No ProhibitionResolver: "not" is only seen as a negation scope over the action
+        let text = r#"ABC Corp (the "Company") shall pay the fees.
+The Company shall not pay the fees."#;
+        let doc = crate::ContractDocument::from_text(text)
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::new())
+            .run_resolver(&DefinedTermResolver::new())
+            .run_resolver(&TermReferenceResolver::new())
+            .run_resolver(&PronounResolver::new())
+            .run_resolver(&ObligationPhraseResolver::new());
+
+        let negated = doc.lines()[1].find(&x::attr::<Scored<ObligationPhrase>>());
+        assert_eq!(negated.len(), 1);
+        assert_eq!(negated[0].attr().value.obligation_type, ObligationType::Prohibition);
+        assert_eq!(negated[0].attr().value.action, "pay the fees");
+
+        let conflicts = ConflictDetector::new().detect_in_document(&doc);
+        assert!(
+            conflicts.iter().any(|c| c.value.conflict_type == ConflictType::ModalConflict),
+            "Expected shall vs shall not conflict, found {:?}",
+            conflicts.iter().map(|c| &c.value.explanation).collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn test_detect_in_document_party_conflict() {
         // Contract text with party conflict: Both Company and Vendor shall deliver
@@ -1940,6 +3109,199 @@ ABC Corp (the "Company") may deliver goods."#;
         );
     }
 
+    #[test]
+    fn test_same_section_scope_ignores_cross_section_conflicts() {
+        let text = r#"
+Section 1. Schedule A
+ABC Corp (the "Company") shall deliver goods.
+
+Section 2. Schedule B
+ABC Corp (the "Company") may deliver goods.
+"#;
+
+        let doc = run_full_pipeline(text);
+
+        let whole = ConflictDetector::new().detect_in_document(&doc);
+        assert!(whole.iter().any(|c| c.value.conflict_type == ConflictType::ModalConflict));
+
+        let scoped = ConflictDetector::new()
+            .with_scope(ConflictScope::SameSection)
+            .detect_in_document(&doc);
+        assert!(
+            scoped.is_empty(),
+            "Obligations in different sections should not be compared. Found: {:?}",
+            scoped.iter().map(|c| &c.value.explanation).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_same_section_scope_keeps_in_section_conflicts() {
+        let text = r#"
+Section 1. Delivery
+ABC Corp (the "Company") shall deliver goods.
+ABC Corp (the "Company") may deliver goods.
+"#;
+
+        let doc = run_full_pipeline(text);
+        let conflicts = ConflictDetector::new()
+            .with_scope(ConflictScope::SameSection)
+            .detect_in_document(&doc);
+
+        assert!(conflicts.iter().any(|c| c.value.conflict_type == ConflictType::ModalConflict));
+    }
+
+    #[test]
+    fn test_same_sentence_scope() {
+        use crate::SentenceBoundaryResolver;
+
+        let text = "ABC Corp (the \"Company\") shall deliver goods. \
+                    The Company may deliver goods.";
+        let doc = run_full_pipeline(text).run_resolver(&SentenceBoundaryResolver::new());
+
+        let whole = ConflictDetector::new().detect_in_document(&doc);
+        assert!(whole.iter().any(|c| c.value.conflict_type == ConflictType::ModalConflict));
+
+        let scoped = ConflictDetector::new()
+            .with_scope(ConflictScope::SameSentence)
+            .detect_in_document(&doc);
+        assert!(scoped.is_empty(), "Separate sentences should not be compared");
+    }
+
+    #[test]
+    fn test_same_sentence_scope_compares_token_positions() {
+        use crate::SentenceBoundaryResolver;
+
+        let text = "ABC Corp (the \"Company\") shall deliver goods and the Company may deliver \
+                    goods. The Company shall pay the fees.";
+        let doc = run_full_pipeline(text).run_resolver(&SentenceBoundaryResolver::new());
+
+        let scoped = ConflictDetector::new()
+            .with_scope(ConflictScope::SameSentence)
+            .detect_in_document(&doc);
+        let modal = scoped
+            .iter()
+            .find(|c| c.value.conflict_type == ConflictType::ModalConflict)
+            .expect("shall and may in one sentence conflict");
+        // "shall" is token 12, "may" token 24
+        assert_eq!(modal.value.span_a, DocSpan::single_line(0, 12, 12));
+        assert_eq!(modal.value.span_b, DocSpan::single_line(0, 24, 24));
+    }
+
+    #[test]
+    fn test_max_pairs_degrades_to_sections_then_windows() {
+        let text = r#"
+Section 1. Delivery
+ABC Corp (the "Company") shall deliver goods.
+ABC Corp (the "Company") may deliver goods.
+Section 2. Payment
+ABC Corp (the "Company") shall pay the fees.
+ABC Corp (the "Company") may pay the fees.
+"#;
+        let doc = run_full_pipeline(text);
+
+        let full = ConflictDetector::new().with_max_pairs(6).detect_with_report(&doc);
+        assert!(full.degradation.is_none());
+
+        let sectioned = ConflictDetector::new().with_max_pairs(2).detect_with_report(&doc);
+        let report = sectioned.degradation.expect("six pairs exceed a budget of two");
+        assert_eq!(report.fallback, DegradationFallback::SectionScoped);
+        assert_eq!((report.required, report.compared, report.skipped()), (6, 2, 4));
+        let modal = |c: &&Scored<Conflict>| c.value.conflict_type == ConflictType::ModalConflict;
+        assert_eq!(sectioned.conflicts.iter().filter(modal).count(), 2);
+
+        let windowed = ConflictDetector::new().with_max_pairs(1).detect_with_report(&doc);
+        let report = windowed.degradation.unwrap();
+        assert_eq!(report.fallback, DegradationFallback::Windowed { window: 1 });
+        assert_eq!(report.compared, 3);
+        assert!(report.warning().contains("6 obligation pairs exceed the budget of 1"));
+    }
+
+    #[test]
+    fn test_governing_law_conflict_is_document_level() {
+        let text = r#"Section 1. Law
+This Agreement shall be governed by the laws of the State of New York.
+Section 2. Disputes
+This Agreement is governed by the laws of Delaware.
+Section 3. Courts
+This Agreement is governed by the laws of New York."#;
+        let doc = run_full_pipeline(text).run_resolver(&crate::GoverningLawResolver::new());
+
+        // Section scoping does not hide conflicts between sections
+        let detector = ConflictDetector::new().with_scope(ConflictScope::SameSection);
+        let conflicts: Vec<_> = detector
+            .detect_in_document(&doc)
+            .into_iter()
+            .filter(|c| c.value.conflict_type == ConflictType::GoverningLawConflict)
+            .collect();
+
+        // "the State of New York" and "New York" agree; both disagree with Delaware
+        assert_eq!(conflicts.len(), 2);
+        assert!(conflicts.iter().all(|c| c.confidence >= 0.9));
+        let lines = |c: &Scored<Conflict>| (c.value.span_a.start.line, c.value.span_b.start.line);
+        assert_eq!(lines(&conflicts[0]), (1, 3));
+        assert!(conflicts[0].value.explanation.contains("Delaware"));
+        assert_eq!(lines(&conflicts[1]), (3, 5));
+        // "governed by the laws of Delaware" is tokens 6-16 of its line
+        assert_eq!(conflicts[1].value.span_a, DocSpan::single_line(3, 6, 16));
+    }
+
+    fn conflict_values(conflicts: &[Scored<Conflict>]) -> Vec<Conflict> {
+        let mut values: Vec<Conflict> = conflicts.iter().map(|c| c.value.clone()).collect();
+        values.sort_by_key(|c| (c.span_a.start.line, c.span_b.start.line, c.explanation.clone()));
+        values
+    }
+
+    #[test]
+    fn test_detect_incremental_adds_conflicts_in_edited_region() {
+        let before = run_full_pipeline(
+            r#"ABC Corp (the "Company") shall deliver goods.
+ABC Corp (the "Company") may deliver goods.
+XYZ Inc (the "Vendor") shall pay the invoice.
+XYZ Inc (the "Vendor") shall audit the records."#,
+        );
+        let after = run_full_pipeline(
+            r#"ABC Corp (the "Company") shall deliver goods.
+ABC Corp (the "Company") may deliver goods.
+XYZ Inc (the "Vendor") shall pay the invoice.
+XYZ Inc (the "Vendor") may pay the invoice."#,
+        );
+
+        let detector = ConflictDetector::new();
+        let previous = detector.detect_in_document(&before);
+        assert!(!previous.is_empty());
+
+        let delta = detector.detect_incremental(&after, &previous, 3..4);
+
+        assert!(delta.removed.is_empty());
+        assert!(!delta.added.is_empty());
+        assert!(delta.added.iter().all(|c| c.value.span_b.start.line == 3));
+        // The untouched conflict between lines 0 and 1 is carried over
+        assert_eq!(
+            conflict_values(&delta.conflicts),
+            conflict_values(&detector.detect_in_document(&after))
+        );
+    }
+
+    #[test]
+    fn test_detect_incremental_removes_resolved_conflicts() {
+        let before = run_full_pipeline(
+            r#"ABC Corp (the "Company") shall deliver goods.
+ABC Corp (the "Company") may deliver goods."#,
+        );
+        let after = run_full_pipeline(
+            r#"ABC Corp (the "Company") shall deliver goods.
+ABC Corp (the "Company") shall submit reports."#,
+        );
+
+        let detector = ConflictDetector::new();
+        let previous = detector.detect_in_document(&before);
+        let delta = detector.detect_incremental(&after, &previous, 1..2);
+
+        assert!(delta.added.is_empty());
+        assert_eq!(conflict_values(&delta.removed), conflict_values(&previous));
+        assert!(delta.conflicts.is_empty());
+    }
+
     // ========================================================================
     // DocumentResolver Integration Tests
     // ========================================================================
@@ -7,12 +7,17 @@
 use std::cmp::Ordering;
 use std::collections::HashSet;
 
+use layered_deixis::{DeicticCategory, DeicticReference, DeicticSource};
 use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver};
 
 use crate::contract_keyword::ContractKeyword;
-use crate::obligation::{ConditionRef, ObligationPhrase, ObligationType, ObligorReference};
+use crate::obligation::{
+    is_temporal_condition, ConditionRef, ObligationNature, ObligationPhrase, ObligationType,
+    ObligorReference, UNKNOWN_OBLIGOR,
+};
+use crate::pronoun::PronounReference;
 use crate::pronoun_chain::PronounChain;
-use crate::Scored;
+use crate::{AmbiguityConfig, AmbiguityFlag, Ambiguous, Scored};
 use crate::utils::normalize_party_name;
 
 const COMMON_CAPITALIZED_ALLOWLIST: &[&str] = &[
@@ -58,6 +63,9 @@ pub struct ClauseDuty {
     pub obligation_type: ObligationType,
     /// Plain-text action captured from the obligation phrase.
     pub action: String,
+    /// Whether this is a performance duty or a representation, warranty,
+    /// acknowledgment or covenant.
+    pub obligation_nature: ObligationNature,
 }
 
 /// Normalized representation of a clause condition.
@@ -71,6 +79,90 @@ pub struct ClauseCondition {
     pub mentions_unknown_entity: bool,
 }
 
+impl ClauseCondition {
+    /// Whether the condition only concerns timing rather than an event or state.
+    pub fn is_temporal(&self) -> bool {
+        is_temporal_condition(&self.condition_type, &self.text)
+    }
+}
+
+/// The kind of annotation an ambiguity inside a clause comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClauseAmbiguityKind {
+    /// Competing modal readings: "shall and may"
+    Modality,
+    /// A pronoun with close or missing antecedents
+    PronounReference,
+    /// A low-confidence deictic expression other than a pronoun
+    /// ("such party", "herein", "thereafter")
+    Deixis(DeicticCategory),
+}
+
+impl ClauseAmbiguityKind {
+    /// How much an ambiguity of this kind can change what the clause means.
+    ///
+    /// Not knowing whether a duty is mandatory or permitted changes the
+    /// clause outright, and an unclear obligor changes who owes it; unclear
+    /// place or time deixis usually only blurs a detail.
+    pub fn weight(self) -> f64 {
+        match self {
+            ClauseAmbiguityKind::Modality => 1.0,
+            ClauseAmbiguityKind::PronounReference => 0.8,
+            ClauseAmbiguityKind::Deixis(DeicticCategory::Person) => 0.6,
+            ClauseAmbiguityKind::Deixis(DeicticCategory::Discourse) => 0.5,
+            ClauseAmbiguityKind::Deixis(DeicticCategory::Time) => 0.4,
+            ClauseAmbiguityKind::Deixis(DeicticCategory::Place) => 0.3,
+            ClauseAmbiguityKind::Deixis(DeicticCategory::Social) => 0.2,
+        }
+    }
+}
+
+/// An ambiguous annotation inside a clause.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClauseAmbiguity {
+    pub kind: ClauseAmbiguityKind,
+    /// Never [`AmbiguityFlag::None`]
+    pub flag: AmbiguityFlag,
+    /// What is ambiguous: the pronoun or deictic phrase, or the competing
+    /// obligation types ("Duty or Permission")
+    pub text: String,
+}
+
+impl ClauseAmbiguity {
+    /// Weight of this ambiguity in [`ClauseAmbiguitySummary::score`]: the
+    /// kind's weight, halved for a low-confidence reading without a rival.
+    pub fn weight(&self) -> f64 {
+        let severity = match self.flag {
+            AmbiguityFlag::CompetingAlternatives => 1.0,
+            AmbiguityFlag::LowConfidence => 0.5,
+            AmbiguityFlag::None => 0.0,
+        };
+        self.kind.weight() * severity
+    }
+}
+
+/// Clause-level view of the ambiguities inside a clause; see
+/// [`ContractClause::ambiguity_summary`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClauseAmbiguitySummary {
+    /// `CompetingAlternatives` if any ambiguity has rival readings, else
+    /// `LowConfidence` if any is uncertain, else `None`
+    pub flag: AmbiguityFlag,
+    /// Number of ambiguous annotations
+    pub count: usize,
+    /// Distinct kinds of ambiguity, heaviest first
+    pub kinds: Vec<ClauseAmbiguityKind>,
+    /// Sum of the ambiguities' weights, for sorting clauses most ambiguous first
+    pub score: f64,
+}
+
+impl ClauseAmbiguitySummary {
+    /// Whether the clause holds any ambiguity.
+    pub fn is_ambiguous(&self) -> bool {
+        self.flag != AmbiguityFlag::None
+    }
+}
+
 /// Clause-level aggregation over a single [`ObligationPhrase`].
 #[derive(Debug, Clone, PartialEq)]
 pub struct ContractClause {
@@ -84,6 +176,41 @@ pub struct ContractClause {
     pub duty: ClauseDuty,
     /// Conditions attached to this clause.
     pub conditions: Vec<ClauseCondition>,
+    /// Ambiguous annotations in the clause's part of the line, which ends at
+    /// the last sentence break before the next obligation's modal.
+    pub ambiguities: Vec<ClauseAmbiguity>,
+}
+
+impl ContractClause {
+    /// Aggregate the clause's ambiguous annotations into one clause-level flag.
+    pub fn ambiguity_summary(&self) -> ClauseAmbiguitySummary {
+        let flag = if self
+            .ambiguities
+            .iter()
+            .any(|a| a.flag == AmbiguityFlag::CompetingAlternatives)
+        {
+            AmbiguityFlag::CompetingAlternatives
+        } else if self.ambiguities.is_empty() {
+            AmbiguityFlag::None
+        } else {
+            AmbiguityFlag::LowConfidence
+        };
+
+        let mut kinds: Vec<ClauseAmbiguityKind> = Vec::new();
+        for ambiguity in &self.ambiguities {
+            if !kinds.contains(&ambiguity.kind) {
+                kinds.push(ambiguity.kind);
+            }
+        }
+        kinds.sort_by(|a, b| b.weight().partial_cmp(&a.weight()).unwrap_or(Ordering::Equal));
+
+        ClauseAmbiguitySummary {
+            flag,
+            count: self.ambiguities.len(),
+            kinds,
+            score: self.ambiguities.iter().fold(0.0, |score, a| score + a.weight()),
+        }
+    }
 }
 
 /// Aggregates [`ObligationPhrase`] outputs into [`ContractClause`] structs.
@@ -102,6 +229,10 @@ pub struct ContractClauseResolver {
     missing_action_penalty: f64,
     /// Penalty when conditions reference unknown/capitalized entities.
     undefined_condition_penalty: f64,
+    /// Collapse clauses that re-detect the same obligation over overlapping spans.
+    deduplicate_overlapping: bool,
+    /// Thresholds for flagging pronoun and deixis ambiguity
+    ambiguity_config: AmbiguityConfig,
 }
 
 impl Default for ContractClauseResolver {
@@ -110,6 +241,8 @@ impl Default for ContractClauseResolver {
             verified_party_bonus: 0.05,
             missing_action_penalty: 0.10,
             undefined_condition_penalty: 0.15,
+            deduplicate_overlapping: false,
+            ambiguity_config: AmbiguityConfig::default(),
         }
     }
 }
@@ -130,7 +263,93 @@ impl ContractClauseResolver {
             verified_party_bonus,
             missing_action_penalty,
             undefined_condition_penalty,
+            ..Self::default()
+        }
+    }
+
+    /// Collapse clauses that are the same obligation detected more than once.
+    ///
+    /// Two clauses are collapsed only when their obligation spans overlap and
+    /// they agree on the resolved obligor (chain ID, or normalized name when
+    /// either side has no chain), the obligation type and the normalized
+    /// action. The higher-confidence clause is kept. Separate sentences with
+    /// identical duties never overlap, so they stay distinct.
+    pub fn with_deduplication(mut self, enabled: bool) -> Self {
+        self.deduplicate_overlapping = enabled;
+        self
+    }
+
+    /// Set the thresholds used to flag ambiguous pronouns and deixis.
+    pub fn with_ambiguity_config(mut self, config: AmbiguityConfig) -> Self {
+        self.ambiguity_config = config;
+        self
+    }
+
+    /// Ambiguous annotations on the line as (start token, ambiguity), in
+    /// token order.
+    ///
+    /// Pronouns are read from `PronounReference` and flagged from their
+    /// antecedent candidates, so the `DeicticReference` mapped from the same
+    /// pronoun is skipped.
+    fn line_ambiguities(&self, selection: &LLSelection) -> Vec<(usize, ClauseAmbiguity)> {
+        let mut found = Vec::new();
+
+        for (sel, modality) in selection.find_by(&x::attr::<Ambiguous<ObligationType>>()) {
+            if modality.is_ambiguous() {
+                let readings: Vec<String> = modality
+                    .alternatives_with_margins()
+                    .into_iter()
+                    .map(|(obligation_type, _, _)| format!("{:?}", obligation_type))
+                    .collect();
+                found.push((
+                    sel.span_ref().start_idx,
+                    ClauseAmbiguity {
+                        kind: ClauseAmbiguityKind::Modality,
+                        flag: modality.flag,
+                        text: readings.join(" or "),
+                    },
+                ));
+            }
         }
+
+        for (sel, pronoun) in selection.find_by(&x::attr::<Scored<PronounReference>>()) {
+            let candidates = pronoun
+                .value
+                .candidates
+                .iter()
+                .map(|c| Scored::rule_based(c.text.clone(), c.confidence, "antecedent"))
+                .collect();
+            let flag = Ambiguous::from_candidates(candidates, &self.ambiguity_config)
+                .map_or(AmbiguityFlag::LowConfidence, |resolved| resolved.flag);
+            if flag != AmbiguityFlag::None {
+                found.push((
+                    sel.span_ref().start_idx,
+                    ClauseAmbiguity {
+                        kind: ClauseAmbiguityKind::PronounReference,
+                        flag,
+                        text: pronoun.value.pronoun.clone(),
+                    },
+                ));
+            }
+        }
+
+        for (sel, deictic) in selection.find_by(&x::attr::<DeicticReference>()) {
+            if deictic.source != DeicticSource::PronounResolver
+                && deictic.confidence < self.ambiguity_config.low_confidence
+            {
+                found.push((
+                    sel.span_ref().start_idx,
+                    ClauseAmbiguity {
+                        kind: ClauseAmbiguityKind::Deixis(deictic.category),
+                        flag: AmbiguityFlag::LowConfidence,
+                        text: deictic.surface_text.clone(),
+                    },
+                ));
+            }
+        }
+
+        found.sort_by_key(|(start, _)| *start);
+        found
     }
 
     /// Estimate a deterministic offset for a selection by counting tokens.
@@ -210,6 +429,14 @@ impl ContractClauseResolver {
                     review_reason: Some("Not linked to defined term".to_string()),
                 }
             }
+            ObligorReference::Unknown => ClauseParty {
+                display_text: UNKNOWN_OBLIGOR.to_string(),
+                chain_id: None,
+                has_verified_chain: false,
+                confidence: 0.2,
+                needs_review: true,
+                review_reason: Some("Passive voice without an agent".to_string()),
+            },
         }
     }
 
@@ -291,6 +518,29 @@ impl ContractClauseResolver {
 
         confidence.clamp(0.0, 1.0)
     }
+
+    /// Whether two clauses over overlapping spans state the same obligation.
+    fn is_same_obligation(a: &ContractClause, b: &ContractClause) -> bool {
+        let same_party = match (a.obligor.chain_id, b.obligor.chain_id) {
+            (Some(a), Some(b)) => a == b,
+            _ => {
+                normalize_party_name(&a.obligor.display_text)
+                    == normalize_party_name(&b.obligor.display_text)
+            }
+        };
+        same_party
+            && a.duty.obligation_type == b.duty.obligation_type
+            && normalize_action(&a.duty.action) == normalize_action(&b.duty.action)
+    }
+}
+
+/// Lowercased action with whitespace collapsed.
+fn normalize_action(action: &str) -> String {
+    action
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 impl Resolver for ContractClauseResolver {
@@ -318,11 +568,48 @@ impl Resolver for ContractClauseResolver {
 
         obligations.sort_by_key(|(_, _, offset)| *offset);
 
-        for (sel, scored_obligation, offset) in obligations {
+        let ambiguities = self.line_ambiguities(&selection);
+        let modal_spans: Vec<(usize, usize)> = obligations
+            .iter()
+            .map(|(sel, _, _)| {
+                let span = sel.span_ref();
+                (span.start_idx, span.end_idx)
+            })
+            .collect();
+
+        // Clauses split the line after the last "." or ";" between two modals,
+        // or at the later modal when there is none
+        let sentence_ends: Vec<usize> = selection
+            .find_by(&x::token_text())
+            .into_iter()
+            .filter(|(_, text)| *text == "." || *text == ";")
+            .map(|(sel, _)| sel.span_ref().start_idx)
+            .collect();
+        let mut boundaries = vec![0];
+        for pair in modal_spans.windows(2) {
+            let split = sentence_ends
+                .iter()
+                .filter(|&&end| end > pair[0].1 && end < pair[1].0)
+                .max()
+                .map_or(pair[1].0, |end| end + 1);
+            boundaries.push(split);
+        }
+        boundaries.push(usize::MAX);
+
+        let mut clauses: Vec<(LLSelection, ContractClause, f64)> = Vec::new();
+        for (idx, (sel, scored_obligation, offset)) in obligations.into_iter().enumerate() {
+            let (window_start, window_end) = (boundaries[idx], boundaries[idx + 1]);
+            let clause_ambiguities = ambiguities
+                .iter()
+                .filter(|(start, _)| *start >= window_start && *start < window_end)
+                .map(|(_, ambiguity)| ambiguity.clone())
+                .collect();
+
             let party = self.build_clause_party(&scored_obligation.value.obligor, &chains);
             let duty = ClauseDuty {
                 obligation_type: scored_obligation.value.obligation_type,
                 action: scored_obligation.value.action.clone(),
+                obligation_nature: scored_obligation.value.obligation_nature,
             };
             let conditions = self.convert_conditions(
                 &scored_obligation.value.conditions,
@@ -335,6 +622,7 @@ impl Resolver for ContractClauseResolver {
                 obligor: party,
                 duty,
                 conditions,
+                ambiguities: clause_ambiguities,
             };
 
             let confidence = self.calculate_confidence(
@@ -344,6 +632,25 @@ impl Resolver for ContractClauseResolver {
                 &clause.conditions,
             );
 
+            if self.deduplicate_overlapping {
+                let span = sel.span_ref();
+                let duplicate = clauses.iter().position(|(kept_sel, kept, _)| {
+                    let kept_span = kept_sel.span_ref();
+                    kept_span.start_idx <= span.end_idx
+                        && span.start_idx <= kept_span.end_idx
+                        && Self::is_same_obligation(kept, &clause)
+                });
+                if let Some(idx) = duplicate {
+                    if confidence > clauses[idx].2 {
+                        clauses[idx] = (sel, clause, confidence);
+                    }
+                    continue;
+                }
+            }
+            clauses.push((sel, clause, confidence));
+        }
+
+        for (sel, clause, confidence) in clauses {
             assignments.push(sel.finish_with_attr(Scored::derived(clause, confidence)));
         }
 
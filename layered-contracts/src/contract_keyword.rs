@@ -6,7 +6,7 @@
 //! - Conditionals (if, unless, provided)
 //! - Party references (party, parties)
 
-use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver};
+use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, ResolverTrace};
 
 /// Contract-specific keywords that carry semantic meaning.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -32,6 +32,9 @@ pub enum ContractKeyword {
     MayNot,
     /// "will not" - indicates prohibition
     WillNot,
+    /// "reserves the right to", "is permitted to", "is entitled to" - a
+    /// permission granted without a modal
+    PermissionIdiom,
 
     // Definition signals
     /// "means", "refers to", "is defined as"
@@ -56,6 +59,37 @@ pub enum ContractKeyword {
     Party,
 }
 
+/// Phrasings that grant a permission without a modal verb, with how much
+/// keyword strength each gives up against "may" for being less explicit.
+///
+/// "is entitled to" often grants a right to receive rather than to act.
+pub(crate) const PERMISSION_IDIOMS: &[(&str, f64)] = &[
+    ("reserves the right to", 0.0),
+    ("reserve the right to", 0.0),
+    ("is permitted to", 0.0),
+    ("are permitted to", 0.0),
+    ("has the right to", 0.05),
+    ("is entitled to", 0.10),
+    ("are entitled to", 0.10),
+];
+
+/// Negated permission idioms, read as "may not".
+const NEGATED_PERMISSION_IDIOMS: &[&str] = &["is not permitted to", "are not permitted to"];
+
+/// Words that make a following idiom part of a modal phrase ("shall reserve
+/// the right to") or negate it ("does not reserve the right to").
+const IDIOM_BLOCKERS: &[&str] =
+    &["shall", "must", "may", "will", "can", "would", "should", "not", "never", "to"];
+
+/// How much keyword strength a permission idiom gives up; 0.0 for text that
+/// is not a known idiom.
+pub(crate) fn permission_idiom_penalty(text: &str) -> f64 {
+    PERMISSION_IDIOMS
+        .iter()
+        .find(|(idiom, _)| idiom.eq_ignore_ascii_case(text))
+        .map_or(0.0, |(_, penalty)| *penalty)
+}
+
 /// Configuration for contract keyword detection.
 pub struct ContractKeywordResolver {
     /// Keywords that map to Shall (obligation)
@@ -152,12 +186,45 @@ impl ContractKeywordResolver {
             None
         }
     }
+
+    /// Extend a selection on the first word of `idiom` through its remaining
+    /// words, if they follow separated by whitespace.
+    fn match_idiom(sel: &LLSelection, idiom: &str) -> Option<LLSelection> {
+        let mut current = sel.clone();
+        for word in idiom.split(' ').skip(1) {
+            let (ws_sel, _) = current.match_first_forwards(&x::whitespace())?;
+            let (word_sel, text) = ws_sel.match_first_forwards(&x::token_text())?;
+            if !text.eq_ignore_ascii_case(word) {
+                return None;
+            }
+            current = word_sel;
+        }
+        Some(current)
+    }
+
+    /// Whether the word before the selection is a modal or negation that the
+    /// idiom starting there belongs to.
+    fn idiom_is_blocked(sel: &LLSelection) -> bool {
+        sel.match_first_backwards(&x::whitespace()).is_some_and(|(ws_sel, _)| {
+            ws_sel
+                .match_first_backwards(&x::token_text())
+                .is_some_and(|(_, text)| IDIOM_BLOCKERS.contains(&text.to_lowercase().as_str()))
+        })
+    }
 }
 
 impl Resolver for ContractKeywordResolver {
     type Attr = ContractKeyword;
 
     fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        self.go_traced(selection, &mut ResolverTrace::disabled())
+    }
+
+    fn go_traced(
+        &self,
+        selection: LLSelection,
+        trace: &mut ResolverTrace,
+    ) -> Vec<LLCursorAssignment<Self::Attr>> {
         let mut results = Vec::new();
 
         // Single token matching
@@ -171,6 +238,7 @@ impl Resolver for ContractKeywordResolver {
                             ws_sel.match_first_forwards(&x::token_text())
                         {
                             if next_text.to_lowercase() == "to" {
+                                trace.accept(&extended_sel, "keyword matched: SubjectTo");
                                 results
                                     .push(extended_sel.finish_with_attr(ContractKeyword::SubjectTo));
                                 continue;
@@ -178,13 +246,42 @@ impl Resolver for ContractKeywordResolver {
                         }
                     }
                     // "subject" alone is not a contract keyword
+                    trace.reject(&sel, "\"subject\" not followed by \"to\"");
                     continue;
                 }
 
+                if trace.is_enabled() {
+                    trace.accept(&sel, format!("keyword matched: {:?}", keyword));
+                }
                 results.push(sel.finish_with_attr(keyword));
             }
         }
 
+        // Multi-word permission idioms, assigned over the whole phrase
+        for (sel, text) in selection.find_by(&x::token_text()) {
+            let lower = text.to_lowercase();
+            let idioms = PERMISSION_IDIOMS
+                .iter()
+                .map(|(idiom, _)| (*idiom, ContractKeyword::PermissionIdiom))
+                .chain(NEGATED_PERMISSION_IDIOMS.iter().map(|idiom| (*idiom, ContractKeyword::MayNot)));
+            for (idiom, keyword) in idioms {
+                if !idiom.starts_with(&format!("{} ", lower)) {
+                    continue;
+                }
+                if let Some(idiom_sel) = Self::match_idiom(&sel, idiom) {
+                    if Self::idiom_is_blocked(&sel) {
+                        trace.reject(&idiom_sel, "idiom follows a modal or negation");
+                        break;
+                    }
+                    if trace.is_enabled() {
+                        trace.accept(&idiom_sel, format!("permission idiom matched: {:?}", idiom));
+                    }
+                    results.push(idiom_sel.finish_with_attr(keyword));
+                    break;
+                }
+            }
+        }
+
         results
     }
 }
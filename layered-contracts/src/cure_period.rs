@@ -0,0 +1,382 @@
+//! Cure periods: time granted to remedy a breach before remedies apply.
+//!
+//! ```text
+//! The Company shall deliver the Goods by March 1, 2025.
+//! If the Company fails to perform, it shall have 30 days to cure such breach.
+//! ```
+//!
+//! [`CurePeriodResolver`] finds cure language ("30 days to cure", "a cure
+//! period of ten (10) business days", "the opportunity to cure within 15
+//! days"), takes the duration from the `TemporalExpression` nearest the cue
+//! and links the cure right to the breach that triggers it and, where the
+//! party's earlier obligation can be found, to that obligation.
+//!
+//! Requires `ObligationPhraseResolver` and `TemporalExpressionResolver` to
+//! have run on the document's lines.
+
+use layered_nlp::{x, LLLine, LToken, TextTag};
+use layered_nlp_document::{DocumentResolver, LayeredDocument};
+
+use crate::conflict_detector::ObligationNormalizer;
+use crate::obligation::{ObligationPhrase, ObligationType, ObligorReference};
+use crate::temporal::{NormalizedTiming, TemporalConverter, TemporalExpression};
+use crate::{DocSpan, Scored};
+
+/// Words opening the breach that triggers a cure right ("fails to perform").
+const BREACH_WORDS: &[&str] = &[
+    "breach", "breaches", "default", "defaults", "fail", "fails", "failure",
+];
+
+/// Confidence of a cure period whose duration was found.
+const CURE_CONFIDENCE: f64 = 0.85;
+
+/// Confidence of a cure right without a stated duration ("an opportunity to cure").
+const OPEN_CURE_CONFIDENCE: f64 = 0.7;
+
+/// A right to cure a breach within a period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurePeriod {
+    /// The cure cue: "to cure" or "cure period"
+    pub span: DocSpan,
+    /// The breach language triggering the cure right ("fails to perform"),
+    /// when stated in the same sentence
+    pub triggering_breach_span: Option<DocSpan>,
+    /// The time allowed to cure; `None` when no duration is stated
+    pub duration: Option<NormalizedTiming>,
+    /// The temporal expression the duration was taken from
+    pub duration_span: Option<DocSpan>,
+    /// The party entitled to cure, by name ("Company")
+    pub party: Option<String>,
+    /// The party's earlier obligation whose breach triggers the cure right
+    pub breached_obligation: Option<DocSpan>,
+}
+
+/// Document-level resolver for cure-period language.
+///
+/// One cure period is reported per sentence. The party is the resolved
+/// obligor of the clause granting the cure ("it shall have 30 days"), the
+/// subject of a passive grant ("The Licensee shall be given"), or the party
+/// named in the breach ("the Company fails").
+#[derive(Debug, Clone, Default)]
+pub struct CurePeriodResolver;
+
+/// An obligation phrase with its span, in document order.
+struct LocatedObligation {
+    span: DocSpan,
+    phrase: ObligationPhrase,
+}
+
+impl CurePeriodResolver {
+    /// Create a new resolver.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Cure cues on a line as token ranges, at most one per sentence.
+    fn cues(
+        words: &[(usize, String)],
+        sentence_of: impl Fn(usize) -> usize,
+    ) -> Vec<(usize, usize)> {
+        let mut cues: Vec<(usize, usize)> = Vec::new();
+        for (i, (idx, word)) in words.iter().enumerate() {
+            if word != "cure" {
+                continue;
+            }
+            let cue = if i > 0 && words[i - 1].1 == "to" {
+                (words[i - 1].0, *idx)
+            } else if words.get(i + 1).is_some_and(|(_, next)| next == "period") {
+                (*idx, words[i + 1].0)
+            } else {
+                continue;
+            };
+            if cues
+                .last()
+                .is_some_and(|last| sentence_of(last.0) == sentence_of(cue.0))
+            {
+                continue;
+            }
+            cues.push(cue);
+        }
+        cues
+    }
+
+    /// The name of a party bound by an obligation, if the obligor is resolved.
+    fn party_of(phrase: &ObligationPhrase) -> Option<String> {
+        match &phrase.obligor {
+            ObligorReference::TermRef { term_name, .. } => Some(term_name.clone()),
+            ObligorReference::PronounRef {
+                resolved_to,
+                is_defined_term: true,
+                ..
+            } => Some(resolved_to.clone()),
+            ObligorReference::NounPhrase { text } if !phrase.obligor.is_unresolved() => {
+                Some(text.clone())
+            }
+            ObligorReference::Unknown => phrase.object.as_deref().map(strip_determiner),
+            _ => None,
+        }
+    }
+
+    /// The capitalized party named at a breach: "the Company fails",
+    /// "a breach by the Buyer".
+    fn breach_subject(line: &LLLine, breach: usize, breach_end: usize) -> Option<String> {
+        let tokens = line.ll_tokens();
+        let word_at = |idx: usize| match tokens[idx].get_token() {
+            LToken::Text(text, TextTag::WORD) => Some(text.as_str()),
+            _ => None,
+        };
+        let before = (0..breach)
+            .rev()
+            .filter_map(word_at)
+            .find(|w| !w.eq_ignore_ascii_case("the"));
+        if let Some(word) = before.filter(|w| w.starts_with(char::is_uppercase)) {
+            return Some(word.to_string());
+        }
+        let after: Vec<&str> = (breach..=breach_end).filter_map(word_at).collect();
+        after
+            .iter()
+            .position(|w| *w == "by")
+            .and_then(|by| {
+                after[by + 1..]
+                    .iter()
+                    .find(|w| !w.eq_ignore_ascii_case("the"))
+            })
+            .filter(|w| w.starts_with(char::is_uppercase))
+            .map(|w| w.to_string())
+    }
+}
+
+/// "The Licensee" -> "Licensee"
+fn strip_determiner(text: &str) -> String {
+    let lower = text.to_lowercase();
+    for determiner in ["the ", "a ", "an "] {
+        if lower.starts_with(determiner) {
+            return text[determiner.len()..].trim().to_string();
+        }
+    }
+    text.trim().to_string()
+}
+
+impl DocumentResolver for CurePeriodResolver {
+    type Attr = Scored<CurePeriod>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        let converter = TemporalConverter::new();
+        let normalizer = ObligationNormalizer::new();
+        let mut earlier: Vec<LocatedObligation> = Vec::new();
+        let mut results = Vec::new();
+
+        for (line_idx, line) in doc.lines_enumerated() {
+            let tokens = line.ll_tokens();
+            let mut words: Vec<(usize, String)> = Vec::new();
+            // Sentence index of each token, split at ".", ";", "!" and "?"
+            let mut sentences = Vec::with_capacity(tokens.len());
+            let mut sentence = 0;
+            for (idx, token) in tokens.iter().enumerate() {
+                sentences.push(sentence);
+                match token.get_token() {
+                    LToken::Text(text, TextTag::WORD) => words.push((idx, text.to_lowercase())),
+                    LToken::Text(text, _) if matches!(text.as_str(), "." | ";" | "!" | "?") => {
+                        sentence += 1
+                    }
+                    _ => {}
+                }
+            }
+            let sentence_of = |idx: usize| sentences[idx];
+
+            let mut obligations: Vec<LocatedObligation> = line
+                .find(&x::attr::<Scored<ObligationPhrase>>())
+                .iter()
+                .map(|found| LocatedObligation {
+                    span: DocSpan::from_find(line_idx, found),
+                    phrase: found.attr().value.clone(),
+                })
+                .collect();
+            obligations.sort_by_key(|o| o.span.start.token);
+            let temporals: Vec<((usize, usize), TemporalExpression)> = line
+                .find(&x::attr::<TemporalExpression>())
+                .iter()
+                .map(|found| (found.token_range(), (*found.attr()).clone()))
+                .collect();
+
+            let cues = Self::cues(&words, sentence_of);
+            for &(cue_start, cue_end) in &cues {
+                let in_sentence = |idx: usize| sentence_of(idx) == sentence_of(cue_start);
+
+                // Nearest convertible duration in the sentence, outermost on ties
+                let duration = temporals
+                    .iter()
+                    .filter(|((start, _), _)| in_sentence(*start))
+                    .filter_map(|(range, expr)| {
+                        converter.convert(expr).map(|timing| (*range, timing))
+                    })
+                    .min_by_key(|((start, end), _)| {
+                        let distance = if *end < cue_start {
+                            cue_start - end
+                        } else {
+                            start.saturating_sub(cue_end)
+                        };
+                        (distance, usize::MAX - (end - start))
+                    });
+
+                let breach = words
+                    .iter()
+                    .find(|(idx, word)| {
+                        *idx < cue_start
+                            && in_sentence(*idx)
+                            && BREACH_WORDS.contains(&word.as_str())
+                    })
+                    .map(|(idx, _)| *idx);
+                let grant = obligations
+                    .iter()
+                    .rfind(|o| in_sentence(o.span.start.token) && o.span.start.token < cue_start);
+                let breach_span = breach.map(|start| {
+                    let limit = grant.map_or(cue_start, |g| g.span.start.token.min(cue_start));
+                    let end = (start..limit)
+                        .take_while(|&idx| {
+                            !matches!(tokens[idx].get_token(), LToken::Text(_, TextTag::PUNC))
+                        })
+                        .filter(|&idx| {
+                            matches!(tokens[idx].get_token(), LToken::Text(_, TextTag::WORD))
+                        })
+                        .last()
+                        .unwrap_or(start);
+                    DocSpan::single_line(line_idx, start, end)
+                });
+
+                let party = grant.and_then(|g| Self::party_of(&g.phrase)).or_else(|| {
+                    breach_span.and_then(|span| {
+                        Self::breach_subject(line, span.start.token, span.end.token)
+                    })
+                });
+
+                let breached_obligation = match (&breach_span, &party) {
+                    (Some(span), Some(party)) => {
+                        let party = normalizer.normalize_party(party);
+                        let same_line = obligations
+                            .iter()
+                            .filter(|o| o.span.start.token < span.start.token);
+                        earlier
+                            .iter()
+                            .chain(same_line)
+                            .filter(|o| o.phrase.obligation_type != ObligationType::Permission)
+                            .rfind(|o| normalizer.extract_obligor_name(&o.phrase.obligor) == party)
+                            .map(|o| o.span)
+                    }
+                    _ => None,
+                };
+
+                results.push(Scored::rule_based(
+                    CurePeriod {
+                        span: DocSpan::single_line(line_idx, cue_start, cue_end),
+                        triggering_breach_span: breach_span,
+                        duration: duration.as_ref().map(|(_, timing)| timing.clone()),
+                        duration_span: duration
+                            .as_ref()
+                            .map(|((start, end), _)| DocSpan::single_line(line_idx, *start, *end)),
+                        party,
+                        breached_obligation,
+                    },
+                    if duration.is_some() {
+                        CURE_CONFIDENCE
+                    } else {
+                        OPEN_CURE_CONFIDENCE
+                    },
+                    "cure_period",
+                ));
+            }
+
+            // Cure grants are not obligations a later breach could refer to
+            obligations.retain(|o| {
+                !cues.iter().any(|&(cue_start, _)| {
+                    sentence_of(o.span.start.token) == sentence_of(cue_start)
+                })
+            });
+            earlier.extend(obligations);
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temporal::TimeUnit;
+    use crate::{
+        ContractDocument, ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver,
+        ProhibitionResolver, PronounResolver, TemporalExpressionResolver, TermReferenceResolver,
+    };
+    use layered_part_of_speech::POSTagResolver;
+
+    fn cure_periods(text: &str) -> Vec<Scored<CurePeriod>> {
+        let doc = ContractDocument::from_text(text)
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ProhibitionResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+            .run_resolver(&TermReferenceResolver::default())
+            .run_resolver(&PronounResolver::default())
+            .run_resolver(&TemporalExpressionResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default());
+        CurePeriodResolver::new().resolve(&doc)
+    }
+
+    #[test]
+    fn test_days_to_cure_links_breached_obligation() {
+        let found = cure_periods(
+            "The Company shall deliver the Goods by March 1, 2025.\n\
+             The Supplier shall pay the invoice.\n\
+             If the Company fails to perform, it shall have 30 days to cure such breach.",
+        );
+
+        assert_eq!(found.len(), 1);
+        let cure = &found[0].value;
+        assert_eq!(cure.party.as_deref(), Some("Company"));
+        assert_eq!(
+            cure.duration,
+            Some(NormalizedTiming::new(30.0, TimeUnit::Days, false))
+        );
+        // "fails to perform"
+        assert_eq!(
+            cure.triggering_breach_span,
+            Some(DocSpan::single_line(2, 6, 10))
+        );
+        assert_eq!(
+            cure.breached_obligation.map(|span| span.start.line),
+            Some(0)
+        );
+        assert_eq!(found[0].confidence, CURE_CONFIDENCE);
+    }
+
+    #[test]
+    fn test_cure_period_and_opportunity_to_cure() {
+        let found = cure_periods(
+            "The Licensee shall be given a cure period of ten (10) business days.\n\
+             In the event of a material breach by the Buyer, the Buyer shall have the \
+             opportunity to cure within fifteen (15) days after notice.\n\
+             The Seller shall have an opportunity to cure.",
+        );
+
+        assert_eq!(found.len(), 3);
+        let licensee = &found[0].value;
+        assert_eq!(licensee.party.as_deref(), Some("Licensee"));
+        assert_eq!(
+            licensee.duration,
+            Some(NormalizedTiming::new(10.0, TimeUnit::BusinessDays, false))
+        );
+        assert_eq!(licensee.triggering_breach_span, None);
+
+        let buyer = &found[1].value;
+        assert_eq!(buyer.party.as_deref(), Some("Buyer"));
+        assert_eq!(
+            buyer.duration.as_ref().map(|d| d.to_approx_days()),
+            Some(15.0)
+        );
+        assert!(buyer.triggering_breach_span.is_some());
+        assert_eq!(buyer.breached_obligation, None);
+
+        assert_eq!(found[2].value.duration, None);
+        assert_eq!(found[2].confidence, OPEN_CURE_CONFIDENCE);
+    }
+}
@@ -0,0 +1,554 @@
+//! Absolute deadline resolution and overdue tracking for obligations.
+//!
+//! Contracts mostly express deadlines relative to an anchor ("within thirty (30)
+//! days after the Effective Date"). [`DeadlineTracker`] detects the Effective Date
+//! in the document, resolves each obligation's deadline to a calendar date where
+//! possible, and keeps the ones it cannot resolve in a separate list so callers
+//! can route them to review instead of silently losing them.
+//! [`infer_missing_deadlines`] fills in payment and delivery obligations that
+//! state no timing from a general clause ("all invoices are due within ...").
+//!
+//! Requires that `ObligationPhraseResolver` and `TemporalExpressionResolver`
+//! have been run on the document.
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use layered_nlp::{x, LLLine};
+
+use crate::obligation::ObligationPhrase;
+use crate::temporal::{
+    DeadlineType, DurationUnit, TemporalConverter, TemporalExpression, TemporalType,
+};
+use crate::utils::line_text;
+use crate::{ContractDocument, DocSpan, Scored};
+
+/// An obligation whose deadline resolved to a calendar date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObligationDeadline {
+    /// Span of the obligation phrase
+    pub span: DocSpan,
+    /// Resolved due date
+    pub due: NaiveDate,
+    /// Text of the temporal expression the date was derived from
+    pub text: String,
+}
+
+/// Why an obligation's deadline could not be resolved to a date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndeterminedReason {
+    /// Vague timing: "promptly", "within a reasonable time"
+    Vague,
+    /// Relative to an event or date that isn't known ("upon termination",
+    /// "within 10 days of receipt", or the Effective Date was not found)
+    Unanchored,
+    /// A date missing its year or day
+    IncompleteDate,
+}
+
+/// An obligation with timing that could not be resolved to a date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndeterminedDeadline {
+    /// Span of the obligation phrase
+    pub span: DocSpan,
+    /// Text of the timing expression (or the action, for vague wording)
+    pub text: String,
+    /// Why the deadline could not be resolved
+    pub reason: UndeterminedReason,
+}
+
+/// Resolves obligation deadlines to dates and answers "what is overdue?".
+#[derive(Debug, Clone)]
+pub struct DeadlineTracker {
+    effective_date: Option<NaiveDate>,
+    deadlines: Vec<ObligationDeadline>,
+    undetermined: Vec<UndeterminedDeadline>,
+}
+
+impl DeadlineTracker {
+    /// Build a tracker, anchoring relative deadlines to the Effective Date
+    /// detected in the document.
+    pub fn from_document(doc: &ContractDocument) -> Self {
+        let effective_date = Self::detect_effective_date(doc);
+        Self::build(doc, effective_date)
+    }
+
+    /// Build a tracker with an explicit Effective Date, ignoring any date
+    /// stated in the document.
+    pub fn with_effective_date(doc: &ContractDocument, effective_date: NaiveDate) -> Self {
+        Self::build(doc, Some(effective_date))
+    }
+
+    /// The Effective Date used to anchor relative deadlines, if known.
+    pub fn effective_date(&self) -> Option<NaiveDate> {
+        self.effective_date
+    }
+
+    /// All obligations whose deadline resolved to a date.
+    pub fn deadlines(&self) -> &[ObligationDeadline] {
+        &self.deadlines
+    }
+
+    /// Obligations whose deadline could not be determined.
+    pub fn undetermined_deadlines(&self) -> &[UndeterminedDeadline] {
+        &self.undetermined
+    }
+
+    /// Obligations whose resolved deadline falls before `as_of`.
+    pub fn overdue_obligations(&self, as_of: NaiveDate) -> Vec<(DocSpan, NaiveDate)> {
+        self.deadlines
+            .iter()
+            .filter(|deadline| deadline.due < as_of)
+            .map(|deadline| (deadline.span, deadline.due))
+            .collect()
+    }
+
+    fn build(doc: &ContractDocument, effective_date: Option<NaiveDate>) -> Self {
+        let converter = TemporalConverter::new();
+        let mut deadlines = Vec::new();
+        let mut undetermined = Vec::new();
+
+        for (line_idx, line) in doc.lines_enumerated() {
+            for ((start, end), scored, in_window) in obligations_with_timing(line) {
+                let span = DocSpan::single_line(line_idx, start, end);
+                match Self::resolve_deadline(&converter, &in_window, &scored.value, effective_date) {
+                    Some(Ok((due, text))) => deadlines.push(ObligationDeadline { span, due, text }),
+                    Some(Err((reason, text))) => {
+                        undetermined.push(UndeterminedDeadline { span, text, reason })
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        Self {
+            effective_date,
+            deadlines,
+            undetermined,
+        }
+    }
+
+    /// Resolve one obligation's timing.
+    ///
+    /// Returns `None` when the obligation carries no timing at all.
+    fn resolve_deadline(
+        converter: &TemporalConverter,
+        temporals: &[((usize, usize), TemporalExpression)],
+        phrase: &ObligationPhrase,
+        effective_date: Option<NaiveDate>,
+    ) -> Option<Result<(NaiveDate, String), (UndeterminedReason, String)>> {
+        let anchored_to_effective = temporals.iter().any(|(_, expr)| {
+            matches!(&expr.temporal_type, TemporalType::DefinedDate { term }
+                if term.to_lowercase().contains("effective date"))
+        });
+
+        if let Some((_, expr)) = temporals
+            .iter()
+            .find(|(_, expr)| matches!(expr.temporal_type, TemporalType::Deadline { .. }))
+        {
+            let text = expr.text.clone();
+            if converter.is_vague(&expr.text) {
+                return Some(Err((UndeterminedReason::Vague, text)));
+            }
+            if let TemporalType::Deadline { deadline_type, reference } = &expr.temporal_type {
+                if *deadline_type == DeadlineType::PromptlyFollowing {
+                    return Some(Err((UndeterminedReason::Vague, text)));
+                }
+                return Some(match reference.as_ref() {
+                    TemporalType::Duration { value, unit, .. } => {
+                        match effective_date.filter(|_| anchored_to_effective) {
+                            Some(anchor) => add_duration(anchor, *value, *unit)
+                                .map(|due| (due, text.clone()))
+                                .ok_or((UndeterminedReason::Unanchored, text)),
+                            None => Err((UndeterminedReason::Unanchored, text)),
+                        }
+                    }
+                    TemporalType::Date { year, month, day } => to_date(*year, *month, *day)
+                        .map(|due| (due, text.clone()))
+                        .ok_or((UndeterminedReason::IncompleteDate, text)),
+                    _ => Err((UndeterminedReason::Unanchored, text)),
+                });
+            }
+        }
+
+        if let Some((_, expr)) = temporals
+            .iter()
+            .find(|(_, expr)| matches!(expr.temporal_type, TemporalType::Date { .. }))
+        {
+            if let TemporalType::Date { year, month, day } = &expr.temporal_type {
+                let text = expr.text.clone();
+                return Some(
+                    to_date(*year, *month, *day)
+                        .map(|due| (due, text.clone()))
+                        .ok_or((UndeterminedReason::IncompleteDate, text)),
+                );
+            }
+        }
+
+        if let Some((_, expr)) = temporals
+            .iter()
+            .find(|(_, expr)| matches!(expr.temporal_type, TemporalType::RelativeTime { .. }))
+        {
+            return Some(Err((UndeterminedReason::Unanchored, expr.text.clone())));
+        }
+
+        if converter.is_vague(&phrase.action) {
+            return Some(Err((UndeterminedReason::Vague, phrase.action.clone())));
+        }
+
+        None
+    }
+
+    /// Find a complete date on a line that mentions the Effective Date.
+    pub(crate) fn detect_effective_date(doc: &ContractDocument) -> Option<NaiveDate> {
+        doc.lines().iter().find_map(|line| {
+            if !line_text(line).to_lowercase().contains("effective date") {
+                return None;
+            }
+            line.find(&x::attr::<TemporalExpression>())
+                .into_iter()
+                .find_map(|found| match &found.attr().temporal_type {
+                    TemporalType::Date { year, month, day } => to_date(*year, *month, *day),
+                    _ => None,
+                })
+        })
+    }
+}
+
+/// Deadline queries on a contract document.
+pub trait DeadlineExt {
+    /// Obligations whose resolved deadline falls before `as_of`.
+    ///
+    /// Relative deadlines are anchored to the Effective Date detected in the
+    /// document; use [`DeadlineTracker::with_effective_date`] to supply one.
+    fn overdue_obligations(&self, as_of: NaiveDate) -> Vec<(DocSpan, NaiveDate)>;
+}
+
+impl DeadlineExt for ContractDocument {
+    fn overdue_obligations(&self, as_of: NaiveDate) -> Vec<(DocSpan, NaiveDate)> {
+        DeadlineTracker::from_document(self).overdue_obligations(as_of)
+    }
+}
+
+/// Factor applied to the governing clause's confidence for an inferred
+/// deadline: the general rule might not be meant to reach this obligation.
+const INFERRED_DEADLINE_FACTOR: f64 = 0.6;
+
+/// Words that quantify over every payment or delivery ("all invoices").
+const GENERAL_QUANTIFIERS: &[&str] = &["all", "any", "each", "every"];
+
+/// What kind of performance an obligation or a general timing clause is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimingTopic {
+    Payment,
+    Delivery,
+}
+
+impl TimingTopic {
+    /// The topic of an obligation's action, from its verb ("pay", "be delivered").
+    fn of_action(action: &str) -> Option<Self> {
+        words(action).find_map(|word| match word.as_str() {
+            "pay" | "pays" | "paid" | "remit" | "remitted" | "reimburse" | "reimbursed" => {
+                Some(TimingTopic::Payment)
+            }
+            "deliver" | "delivered" | "ship" | "shipped" => Some(TimingTopic::Delivery),
+            _ => None,
+        })
+    }
+
+    /// The topic of a noun a general clause quantifies over ("invoices").
+    fn of_noun(noun: &str) -> Option<Self> {
+        match noun {
+            "payment" | "payments" | "invoice" | "invoices" | "amount" | "amounts" | "fee"
+            | "fees" | "sum" | "sums" => Some(TimingTopic::Payment),
+            "delivery" | "deliveries" | "shipment" | "shipments" | "order" | "orders" => {
+                Some(TimingTopic::Delivery)
+            }
+            _ => None,
+        }
+    }
+
+    /// The topic of a line that quantifies over payments or deliveries
+    /// ("all invoices", "each undisputed payment"), allowing one word between
+    /// the quantifier and the noun.
+    fn of_general_clause(text: &str) -> Option<Self> {
+        let words: Vec<String> = words(text).collect();
+        words.iter().enumerate().find_map(|(i, word)| {
+            if !GENERAL_QUANTIFIERS.contains(&word.as_str()) {
+                return None;
+            }
+            words[i + 1..].iter().take(2).find_map(|noun| Self::of_noun(noun))
+        })
+    }
+}
+
+/// Lowercased words of `text`.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Infer deadlines for payment and delivery obligations that state none.
+///
+/// "The Buyer shall pay the invoice" says nothing about when, but is governed
+/// by a general clause elsewhere such as "All invoices shall be paid within
+/// thirty (30) days". A general clause is a line that quantifies over payments
+/// or deliveries ("all invoices", "each delivery") and carries a deadline; an
+/// obligation without any timing takes the deadline of the nearest general
+/// clause on its topic.
+///
+/// Returns the obligation span, the governing deadline with its confidence
+/// scaled down, and the span of that deadline. Obligations with their own
+/// timing, and those with no governing clause, are left out.
+///
+/// Requires that `ObligationPhraseResolver` and `TemporalExpressionResolver`
+/// have been run on the document.
+pub fn infer_missing_deadlines(doc: &ContractDocument) -> Vec<(DocSpan, TemporalExpression, DocSpan)> {
+    let mut general: Vec<(TimingTopic, TemporalExpression, DocSpan)> = Vec::new();
+    for (line_idx, line) in doc.lines_enumerated() {
+        let Some(topic) = TimingTopic::of_general_clause(&line_text(line)) else {
+            continue;
+        };
+        let deadline = line
+            .find(&x::attr::<TemporalExpression>())
+            .into_iter()
+            .filter(|found| matches!(found.attr().temporal_type, TemporalType::Deadline { .. }))
+            .min_by_key(|found| found.token_range().0)
+            .map(|found| (found.token_range(), (*found.attr()).clone()));
+        if let Some(((start, end), expr)) = deadline {
+            general.push((topic, expr, DocSpan::single_line(line_idx, start, end)));
+        }
+    }
+
+    let mut inferred = Vec::new();
+    for (line_idx, line) in doc.lines_enumerated() {
+        for ((start, end), scored, in_window) in obligations_with_timing(line) {
+            if !in_window.is_empty() {
+                continue;
+            }
+            let Some(topic) = TimingTopic::of_action(&scored.value.action) else {
+                continue;
+            };
+            let nearest = general
+                .iter()
+                .filter(|(clause_topic, _, _)| *clause_topic == topic)
+                .min_by_key(|(_, _, source)| source.start.line.abs_diff(line_idx));
+            if let Some((_, expr, source)) = nearest {
+                let mut timing = expr.clone();
+                timing.confidence *= INFERRED_DEADLINE_FACTOR;
+                inferred.push((DocSpan::single_line(line_idx, start, end), timing, *source));
+            }
+        }
+    }
+    inferred
+}
+
+/// An obligation's token range, the obligation, and the temporal expressions
+/// in its window with their token ranges.
+type TimedObligation =
+    ((usize, usize), Scored<ObligationPhrase>, Vec<((usize, usize), TemporalExpression)>);
+
+/// The obligations on a line in token order, each with the temporal
+/// expressions in its window, also in token order. Timing belongs to an
+/// obligation from its start until the next obligation starts.
+fn obligations_with_timing(line: &LLLine) -> Vec<TimedObligation> {
+    let mut obligations: Vec<((usize, usize), Scored<ObligationPhrase>)> = line
+        .find(&x::attr::<Scored<ObligationPhrase>>())
+        .into_iter()
+        .map(|found| (found.token_range(), (*found.attr()).clone()))
+        .collect();
+    obligations.sort_by_key(|((start, _), _)| *start);
+
+    let mut temporals: Vec<((usize, usize), TemporalExpression)> = line
+        .find(&x::attr::<TemporalExpression>())
+        .into_iter()
+        .map(|found| (found.token_range(), (*found.attr()).clone()))
+        .collect();
+    temporals.sort_by_key(|((start, _), _)| *start);
+
+    let window_ends: Vec<usize> = obligations
+        .iter()
+        .skip(1)
+        .map(|((next_start, _), _)| *next_start)
+        .chain(std::iter::once(usize::MAX))
+        .collect();
+    obligations
+        .into_iter()
+        .zip(window_ends)
+        .map(|((range, scored), window_end)| {
+            let in_window = temporals
+                .iter()
+                .filter(|((t_start, _), _)| *t_start >= range.0 && *t_start < window_end)
+                .cloned()
+                .collect();
+            (range, scored, in_window)
+        })
+        .collect()
+}
+
+pub(crate) fn to_date(year: Option<u32>, month: Option<u8>, day: Option<u8>) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year? as i32, month? as u32, day? as u32)
+}
+
+/// Add a contract duration to a date. Business days skip Saturdays and Sundays.
+pub(crate) fn add_duration(date: NaiveDate, value: u32, unit: DurationUnit) -> Option<NaiveDate> {
+    match unit {
+        DurationUnit::Days => date.checked_add_days(Days::new(value as u64)),
+        DurationUnit::Weeks => date.checked_add_days(Days::new(value as u64 * 7)),
+        DurationUnit::Months => date.checked_add_months(Months::new(value)),
+        DurationUnit::Years => date.checked_add_months(Months::new(value.checked_mul(12)?)),
+        DurationUnit::BusinessDays => {
+            let mut current = date;
+            let mut remaining = value;
+            while remaining > 0 {
+                current = current.succ_opt()?;
+                if !matches!(current.weekday(), Weekday::Sat | Weekday::Sun) {
+                    remaining -= 1;
+                }
+            }
+            Some(current)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver,
+        ProhibitionResolver, PronounResolver, TemporalExpressionResolver, TermReferenceResolver,
+    };
+    use layered_part_of_speech::POSTagResolver;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn analyze(text: &str) -> ContractDocument {
+        ContractDocument::from_text(text)
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ProhibitionResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+            .run_resolver(&TermReferenceResolver::default())
+            .run_resolver(&PronounResolver::default())
+            .run_resolver(&TemporalExpressionResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default())
+    }
+
+    fn tracker(text: &str) -> DeadlineTracker {
+        DeadlineTracker::from_document(&analyze(text))
+    }
+
+    const CONTRACT: &str = "This Agreement is effective as of January 1, 2024 (the \"Effective Date\").\n\
+        The Vendor shall deliver the goods within thirty (30) days after the Effective Date.\n\
+        The Buyer shall pay the invoice by December 31, 2024.\n\
+        The Vendor shall provide support upon termination.\n\
+        The Buyer shall promptly notify the Vendor of defects.";
+
+    #[test]
+    fn test_detects_effective_date() {
+        assert_eq!(tracker(CONTRACT).effective_date(), Some(date(2024, 1, 1)));
+    }
+
+    #[test]
+    fn test_resolves_relative_and_absolute_deadlines() {
+        let tracker = tracker(CONTRACT);
+        let dues: Vec<_> = tracker.deadlines().iter().map(|d| (d.span.start.line, d.due)).collect();
+        assert_eq!(dues, vec![(1, date(2024, 1, 31)), (2, date(2024, 12, 31))]);
+    }
+
+    #[test]
+    fn test_deadline_spans_are_token_indices() {
+        let tracker = tracker(CONTRACT);
+        // "shall" is token 4 of "The Vendor shall deliver ..."
+        assert_eq!(tracker.deadlines()[0].span, DocSpan::single_line(1, 4, 4));
+    }
+
+    #[test]
+    fn test_overdue_obligations() {
+        let tracker = tracker(CONTRACT);
+
+        let overdue = tracker.overdue_obligations(date(2024, 6, 1));
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].1, date(2024, 1, 31));
+
+        assert_eq!(tracker.overdue_obligations(date(2025, 1, 1)).len(), 2);
+        assert!(tracker.overdue_obligations(date(2024, 1, 31)).is_empty());
+
+        let doc = analyze(CONTRACT);
+        assert_eq!(doc.overdue_obligations(date(2024, 6, 1)), overdue);
+    }
+
+    #[test]
+    fn test_unresolvable_deadlines_are_reported() {
+        let tracker = tracker(CONTRACT);
+        let reasons: Vec<_> = tracker
+            .undetermined_deadlines()
+            .iter()
+            .map(|u| (u.span.start.line, u.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![(3, UndeterminedReason::Unanchored), (4, UndeterminedReason::Vague)]
+        );
+    }
+
+    #[test]
+    fn test_missing_effective_date_leaves_relative_deadlines_unanchored() {
+        let tracker = tracker(
+            "The Vendor shall deliver the goods within thirty (30) days after the Effective Date.",
+        );
+        assert!(tracker.deadlines().is_empty());
+        assert_eq!(tracker.undetermined_deadlines()[0].reason, UndeterminedReason::Unanchored);
+
+        let doc = analyze(
+            "The Vendor shall deliver the goods within thirty (30) days after the Effective Date.",
+        );
+        let explicit = DeadlineTracker::with_effective_date(&doc, date(2024, 3, 1));
+        assert_eq!(explicit.deadlines()[0].due, date(2024, 3, 31));
+    }
+
+    #[test]
+    fn test_infers_deadline_from_general_clause() {
+        let doc = analyze(
+            "All invoices shall be paid within thirty (30) days of receipt.\n\
+             The Buyer shall pay the service fees.\n\
+             The Vendor shall deliver the goods.\n\
+             The Buyer shall pay the license fee by December 31, 2024.",
+        );
+        let inferred = infer_missing_deadlines(&doc);
+        assert_eq!(inferred.len(), 1, "{:?}", inferred);
+
+        let (obligation, timing, source) = &inferred[0];
+        assert_eq!(obligation.start.line, 1);
+        assert_eq!(timing.text, "within thirty (30) days");
+        assert_eq!(source.start.line, 0);
+        assert!((timing.confidence - 0.85 * INFERRED_DEADLINE_FACTOR).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_inferred_deadline_follows_topic() {
+        let doc = analyze(
+            "Payment of any invoice is due no later than ten (10) days after receipt.\n\
+             Each delivery shall be made within five (5) business days after the order.\n\
+             The Vendor shall ship the parts.",
+        );
+        let inferred = infer_missing_deadlines(&doc);
+        assert_eq!(inferred.len(), 1, "{:?}", inferred);
+        assert_eq!(inferred[0].0.start.line, 2);
+        assert_eq!(inferred[0].1.text, "within five (5) business days");
+        assert_eq!(inferred[0].2.start.line, 1);
+
+        assert!(infer_missing_deadlines(&analyze("The Buyer shall pay the fees.")).is_empty());
+    }
+
+    #[test]
+    fn test_add_business_days_skips_weekends() {
+        // Friday + 1 business day = Monday
+        assert_eq!(
+            add_duration(date(2024, 1, 5), 1, DurationUnit::BusinessDays),
+            Some(date(2024, 1, 8))
+        );
+        assert_eq!(add_duration(date(2024, 1, 31), 1, DurationUnit::Months), Some(date(2024, 2, 29)));
+    }
+}
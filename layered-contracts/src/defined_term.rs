@@ -2,13 +2,15 @@
 //!
 //! This resolver identifies formally defined terms in contract text using patterns:
 //! - `"Term" means ...` - Quoted term followed by "means" keyword
+//! - `"Buyer" and "Purchaser" mean ...` - Several quoted terms sharing one "means"
 //! - `ABC Corp (the "Term")` - Parenthetical definition
+//! - `the services (the "Services")` - Alias of a preceding noun phrase
 //! - `ABC Corp, hereinafter "Term"` - Hereinafter pattern
 
-use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+use layered_nlp::{x, Association, LLCursorAssignment, LLSelection, Resolver, SpanRef, TextTag};
 
 use crate::contract_keyword::ContractKeyword;
-use crate::Scored;
+use crate::{DocSpan, Scored};
 
 /// Represents a formally defined term in a contract.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -17,6 +19,128 @@ pub struct DefinedTerm {
     pub term_name: String,
     /// How this term was defined
     pub definition_type: DefinitionType,
+    /// Where the definition applies
+    pub scope: DefinitionScope,
+    /// Forms a reference may take: the term, its plural and their possessives
+    /// ("Party", "Parties", "Party's", "Parties'")
+    pub forms: Vec<String>,
+    /// Other terms defined together with this one, in text order
+    /// (`"Purchaser"` for "Buyer" in `"Buyer" and "Purchaser" mean ...`)
+    pub synonyms: Vec<String>,
+}
+
+impl DefinedTerm {
+    /// A globally scoped term with its inflected [`forms`](Self::forms).
+    pub fn new(term_name: String, definition_type: DefinitionType) -> Self {
+        Self {
+            forms: term_forms(&term_name),
+            term_name,
+            definition_type,
+            scope: DefinitionScope::Global,
+            synonyms: Vec::new(),
+        }
+    }
+
+    /// Set the terms defined together with this one.
+    pub fn with_synonyms(mut self, synonyms: Vec<String>) -> Self {
+        self.synonyms = synonyms;
+        self
+    }
+}
+
+/// Plurals the suffix rules get wrong, lowercase. Uncountable nouns are their
+/// own plural; "Persons", not "People", is the contract usage.
+const IRREGULAR_PLURALS: &[(&str, &str)] = &[
+    ("analysis", "analyses"),
+    ("appendix", "appendices"),
+    ("basis", "bases"),
+    ("child", "children"),
+    ("criterion", "criteria"),
+    ("equipment", "equipment"),
+    ("information", "information"),
+    ("person", "persons"),
+    ("premises", "premises"),
+    ("series", "series"),
+    ("software", "software"),
+];
+
+/// The plural of a term, inflecting its last word ("Business Days").
+///
+/// Words already ending in a plural "s" ("Services") are left as they are.
+pub fn plural_form(term: &str) -> String {
+    let (head, last) = match term.rfind(' ') {
+        Some(idx) => term.split_at(idx + 1),
+        None => ("", term),
+    };
+    let lower = last.to_lowercase();
+    let upper = last.len() > 1 && last.chars().all(|c| !c.is_lowercase());
+
+    let plural = if let Some((_, plural)) = IRREGULAR_PLURALS.iter().find(|(s, _)| *s == lower) {
+        // Keep the word's capitalization
+        let mut chars = plural.chars();
+        match (upper, last.chars().next()) {
+            (true, _) => plural.to_uppercase(),
+            (false, Some(first)) if first.is_uppercase() => {
+                chars.next().map_or(String::new(), |c| c.to_uppercase().chain(chars).collect())
+            }
+            _ => plural.to_string(),
+        }
+    } else {
+        let suffix_of = |suffix: &str| {
+            if upper {
+                suffix.to_uppercase()
+            } else {
+                suffix.to_string()
+            }
+        };
+        let consonant_y = lower.ends_with('y')
+            && !lower[..lower.len() - 1].ends_with(['a', 'e', 'i', 'o', 'u']);
+        if lower.ends_with('s') && !lower.ends_with("ss") && !lower.ends_with("us") {
+            last.to_string()
+        } else if consonant_y {
+            format!("{}{}", &last[..last.len() - 1], suffix_of("ies"))
+        } else if ["s", "x", "z", "ch", "sh"].iter().any(|end| lower.ends_with(end)) {
+            format!("{}{}", last, suffix_of("es"))
+        } else {
+            format!("{}{}", last, suffix_of("s"))
+        }
+    };
+    format!("{}{}", head, plural)
+}
+
+/// A term with its plural and their possessives, without duplicates.
+fn term_forms(term: &str) -> Vec<String> {
+    let plural = plural_form(term);
+    let possessive = |form: &str| {
+        if form.ends_with(['s', 'S']) {
+            format!("{}'", form)
+        } else {
+            format!("{}'s", form)
+        }
+    };
+    let mut forms = vec![term.to_string(), plural.clone(), possessive(term), possessive(&plural)];
+    let mut seen = Vec::new();
+    forms.retain(|form| {
+        let new = !seen.contains(form);
+        seen.push(form.clone());
+        new
+    });
+    forms
+}
+
+/// The part of a document in which a defined term applies.
+///
+/// `DefinedTermResolver` only sees one line, so it always reports `Global`;
+/// `DocumentTermReferenceResolver` infers narrower scopes from the document
+/// structure.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum DefinitionScope {
+    /// The term applies throughout the document
+    #[default]
+    Global,
+    /// The term was defined inside an exhibit, schedule, annex or appendix and
+    /// applies only within that section's span
+    Section(DocSpan),
 }
 
 /// The type of formal definition pattern detected.
@@ -24,17 +148,66 @@ pub struct DefinedTerm {
 pub enum DefinitionType {
     /// Pattern: "Term" means ... (quoted term followed by means keyword)
     /// Example: "Company" means ABC Corporation
+    ///
+    /// Coordinated terms (`"Buyer" and "Purchaser" mean`) each get a
+    /// definition listing the others as [`synonyms`](DefinedTerm::synonyms).
     QuotedMeans,
 
     /// Pattern: ABC Corp (the "Term") - parenthetical definition
     /// Example: ABC Corporation (the "Company")
     Parenthetical,
 
+    /// Pattern: the services (the "Term") - quoted alias of a common noun phrase
+    /// Example: the consulting services ("Services")
+    ///
+    /// The aliased noun phrase is linked through a [`DefinitionBody`] association.
+    Alias,
+
     /// Pattern: ABC Corp, hereinafter "Term" - hereinafter pattern
     /// Example: ABC Corporation, hereinafter referred to as the "Contractor"
     Hereinafter,
 }
 
+/// Association linking a parenthetical or alias definition to the phrase it names
+/// ("ABC Corp" in `ABC Corp (the "Company")`).
+#[derive(Debug, Clone)]
+pub struct DefinitionBody;
+
+impl Association for DefinitionBody {
+    fn label(&self) -> &'static str {
+        "definition_body"
+    }
+    fn glyph(&self) -> Option<&'static str> {
+        Some("=")
+    }
+}
+
+/// Words that open a noun phrase and bound an alias antecedent on the left.
+const DETERMINERS: &[&str] = &[
+    "a", "all", "an", "any", "each", "its", "such", "that", "the", "their", "these", "this",
+    "those",
+];
+
+/// Words that cannot belong to an alias antecedent ("provision of services").
+const ANTECEDENT_BREAKS: &[&str] = &[
+    "and", "as", "at", "between", "by", "for", "from", "in", "of", "on", "or", "to", "under",
+    "with",
+];
+
+/// Maximum number of words considered before the opening parenthesis.
+const MAX_ANTECEDENT_WORDS: usize = 6;
+
+/// The phrase a parenthetical definition names.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Antecedent {
+    /// Capitalized name: `ABC Corp (the "Company")`
+    ProperName(SpanRef),
+    /// Common noun phrase; `bounded` when a determiner marks where it starts
+    NounPhrase { span: SpanRef, bounded: bool },
+    /// Nothing usable directly before the parenthesis
+    Missing,
+}
+
 /// Resolver for detecting formally defined terms in contract text.
 pub struct DefinedTermResolver {
     /// Base confidence for QuotedMeans pattern (highest - most explicit)
@@ -43,6 +216,8 @@ pub struct DefinedTermResolver {
     parenthetical_confidence: f64,
     /// Base confidence for Hereinafter pattern
     hereinafter_confidence: f64,
+    /// Confidence for Alias pattern with a determiner-bounded antecedent
+    alias_confidence: f64,
 }
 
 impl Default for DefinedTermResolver {
@@ -51,6 +226,7 @@ impl Default for DefinedTermResolver {
             quoted_means_confidence: 0.95,
             parenthetical_confidence: 0.90,
             hereinafter_confidence: 0.90,
+            alias_confidence: 0.85,
         }
     }
 }
@@ -71,6 +247,80 @@ impl DefinedTermResolver {
             quoted_means_confidence: quoted_means,
             parenthetical_confidence: parenthetical,
             hereinafter_confidence: hereinafter,
+            ..Self::default()
+        }
+    }
+
+    /// Set the confidence for aliases of a clearly bounded noun phrase.
+    ///
+    /// Aliases whose antecedent has no determiner get 0.1 less; aliases with
+    /// no antecedent at all get 0.25 less.
+    pub fn with_alias_confidence(mut self, confidence: f64) -> Self {
+        self.alias_confidence = confidence;
+        self
+    }
+
+    /// Find the phrase directly before an opening parenthesis.
+    fn antecedent(&self, paren_sel: &LLSelection) -> Antecedent {
+        // (token index, text), nearest word first
+        let mut words: Vec<(usize, String)> = Vec::new();
+        let mut current = paren_sel.clone();
+        // Abbreviated names end in a period: "Acme Inc. (the "Buyer")"
+        if let Some((ws_sel, _)) = current.match_first_backwards(&x::whitespace()) {
+            current = ws_sel;
+        }
+        if let Some((period_sel, _)) = current.match_first_backwards(&x::attr_eq(&'.')) {
+            current = period_sel;
+        }
+        while words.len() < MAX_ANTECEDENT_WORDS {
+            if let Some((ws_sel, _)) = current.match_first_backwards(&x::whitespace()) {
+                current = ws_sel;
+            }
+            match current
+                .match_first_backwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            {
+                Some((word_sel, (_, text))) => {
+                    words.push((word_sel.span_ref().start_idx, text.to_string()));
+                    current = word_sel;
+                }
+                None => break,
+            }
+        }
+
+        let is_capitalized = |text: &str| text.chars().next().is_some_and(char::is_uppercase);
+        let span_of = |taken: &[(usize, String)]| SpanRef {
+            start_idx: taken[taken.len() - 1].0,
+            end_idx: taken[0].0,
+        };
+
+        match words.first() {
+            None => Antecedent::Missing,
+            Some((_, nearest)) if is_capitalized(nearest) => {
+                let name_len = words.iter().take_while(|(_, text)| is_capitalized(text)).count();
+                Antecedent::ProperName(span_of(&words[..name_len]))
+            }
+            Some(_) => {
+                let mut bounded = false;
+                let mut phrase_len = 0;
+                for (_, text) in &words {
+                    let lower = text.to_lowercase();
+                    if DETERMINERS.contains(&lower.as_str()) {
+                        bounded = true;
+                        break;
+                    }
+                    if ANTECEDENT_BREAKS.contains(&lower.as_str()) {
+                        break;
+                    }
+                    phrase_len += 1;
+                }
+                if phrase_len == 0 {
+                    return Antecedent::Missing;
+                }
+                Antecedent::NounPhrase {
+                    span: span_of(&words[..phrase_len]),
+                    bounded,
+                }
+            }
         }
     }
 
@@ -161,39 +411,104 @@ impl DefinedTermResolver {
     }
 
     /// Pattern 1: "Term" means ...
-    /// Find ContractKeyword::Means, then look backwards for a quoted term.
+    /// Find ContractKeyword::Means, then look backwards for a quoted term, and
+    /// for further quoted terms coordinated with it (`"A", "B" and "C" mean`).
     fn find_quoted_means_patterns(
         &self,
         selection: &LLSelection,
     ) -> Vec<LLCursorAssignment<Scored<DefinedTerm>>> {
-        selection
-            .find_by(&x::attr_eq(&ContractKeyword::Means))
-            .into_iter()
-            .filter_map(|(means_sel, _)| {
-                // Match backwards: whitespace -> closing quote
-                means_sel
-                    .match_first_backwards(&x::whitespace())
-                    .and_then(|(ws_sel, _)| ws_sel.match_first_backwards(&x::attr_eq(&'"')))
-                    .and_then(|(close_quote_sel, _)| {
-                        // Extract term backwards from closing quote
-                        self.extract_quoted_term_backwards(&close_quote_sel)
-                    })
-                    .map(|(full_sel, term_name)| {
-                        full_sel.finish_with_attr(Scored::rule_based(
-                            DefinedTerm {
-                                term_name,
-                                definition_type: DefinitionType::QuotedMeans,
-                            },
-                            self.quoted_means_confidence,
-                            "quoted_means",
-                        ))
-                    })
-            })
-            .collect()
+        let mut results = Vec::new();
+
+        for (means_sel, _) in selection.find_by(&x::attr_eq(&ContractKeyword::Means)) {
+            // Match backwards: whitespace -> closing quote
+            let Some(last) = means_sel
+                .match_first_backwards(&x::whitespace())
+                .and_then(|(ws_sel, _)| ws_sel.match_first_backwards(&x::attr_eq(&'"')))
+                .and_then(|(close_quote_sel, _)| {
+                    // Extract term backwards from closing quote
+                    self.extract_quoted_term_backwards(&close_quote_sel)
+                })
+            else {
+                continue;
+            };
+
+            // The last term's span runs to the keyword; earlier ones cover their quotes
+            let mut terms = vec![last.clone()];
+            let mut current = last.0;
+            while let Some((term_sel, quoted_sel, term_name)) =
+                self.extract_coordinated_term_backwards(&current)
+            {
+                terms.push((quoted_sel, term_name));
+                current = term_sel;
+            }
+            terms.reverse();
+
+            let names: Vec<&String> = terms.iter().map(|(_, name)| name).collect();
+            for (sel, term_name) in &terms {
+                let synonyms = names
+                    .iter()
+                    .filter(|name| **name != term_name)
+                    .map(|name| name.to_string())
+                    .collect();
+                results.push(sel.finish_with_attr(Scored::rule_based(
+                    DefinedTerm::new(term_name.clone(), DefinitionType::QuotedMeans)
+                        .with_synonyms(synonyms),
+                    self.quoted_means_confidence,
+                    "quoted_means",
+                )));
+            }
+        }
+
+        results
     }
 
-    /// Pattern 2: ABC Corp (the "Term")
+    /// Find a quoted term joined to the one `current` starts with by a comma,
+    /// "and" or "or" (`"Buyer" and ` before `"Purchaser" mean`).
+    ///
+    /// Returns the selection extended back to the term's opening quote, the
+    /// selection of just the quoted term, and the term name.
+    fn extract_coordinated_term_backwards(
+        &self,
+        current: &LLSelection,
+    ) -> Option<(LLSelection, LLSelection, String)> {
+        let skip_whitespace = |sel: LLSelection| match sel.match_first_backwards(&x::whitespace()) {
+            Some((ws_sel, _)) => ws_sel,
+            None => sel,
+        };
+        let conjunction = |sel: &LLSelection| {
+            sel.match_first_backwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+                .filter(|(_, (_, text))| matches!(text.to_lowercase().as_str(), "and" | "or"))
+                .map(|(word_sel, _)| word_sel)
+        };
+
+        let mut sep = skip_whitespace(current.clone());
+        let mut found_separator = false;
+        if let Some((comma_sel, _)) = sep.match_first_backwards(&x::attr_eq(&',')) {
+            sep = comma_sel;
+            found_separator = true;
+        } else if let Some(word_sel) = conjunction(&sep) {
+            sep = skip_whitespace(word_sel);
+            found_separator = true;
+            // Serial comma: `"A", "B", and "C"`
+            if let Some((comma_sel, _)) = sep.match_first_backwards(&x::attr_eq(&',')) {
+                sep = comma_sel;
+            }
+        }
+        if !found_separator {
+            return None;
+        }
+
+        let (close_quote_sel, _) = sep.match_first_backwards(&x::attr_eq(&'"'))?;
+        let (term_sel, term_name) = self.extract_quoted_term_backwards(&close_quote_sel)?;
+        // Cut off everything from the separator on
+        let [quoted_sel, _] = term_sel.split_with(&sep);
+        Some((term_sel, quoted_sel?, term_name))
+    }
+
+    /// Pattern 2: ABC Corp (the "Term") / the services ("Term")
     /// Find opening paren, then look for optional "the" + quoted term + closing paren.
+    /// A capitalized name before the paren makes a Parenthetical definition; a common
+    /// noun phrase (or nothing) makes an Alias.
     fn find_parenthetical_patterns(
         &self,
         selection: &LLSelection,
@@ -239,14 +554,38 @@ impl DefinedTermResolver {
                         check_sel
                             .match_first_forwards(&x::attr_eq(&')'))
                             .map(|(close_paren_sel, _)| {
-                                close_paren_sel.finish_with_attr(Scored::rule_based(
-                                    DefinedTerm {
-                                        term_name,
-                                        definition_type: DefinitionType::Parenthetical,
-                                    },
-                                    self.parenthetical_confidence,
-                                    "parenthetical",
-                                ))
+                                let (definition_type, confidence, rule, body) =
+                                    match self.antecedent(&paren_sel) {
+                                        Antecedent::ProperName(span) => (
+                                            DefinitionType::Parenthetical,
+                                            self.parenthetical_confidence,
+                                            "parenthetical",
+                                            Some(span),
+                                        ),
+                                        Antecedent::NounPhrase { span, bounded } => (
+                                            DefinitionType::Alias,
+                                            if bounded {
+                                                self.alias_confidence
+                                            } else {
+                                                self.alias_confidence - 0.1
+                                            },
+                                            "alias",
+                                            Some(span),
+                                        ),
+                                        Antecedent::Missing => (
+                                            DefinitionType::Alias,
+                                            self.alias_confidence - 0.25,
+                                            "alias",
+                                            None,
+                                        ),
+                                    };
+                                let term = DefinedTerm::new(term_name, definition_type);
+                                let mut builder = close_paren_sel
+                                    .assign(Scored::rule_based(term, confidence, rule));
+                                if let Some(span) = body {
+                                    builder = builder.with_association(DefinitionBody, span);
+                                }
+                                builder.build()
                             })
                     })
             })
@@ -325,10 +664,7 @@ impl DefinedTermResolver {
                     })
                     .map(|(full_sel, term_name)| {
                         full_sel.finish_with_attr(Scored::rule_based(
-                            DefinedTerm {
-                                term_name,
-                                definition_type: DefinitionType::Hereinafter,
-                            },
+                            DefinedTerm::new(term_name, DefinitionType::Hereinafter),
                             self.hereinafter_confidence,
                             "hereinafter",
                         ))
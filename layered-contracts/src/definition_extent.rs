@@ -0,0 +1,569 @@
+//! Full extents of definitions that wrap onto following lines.
+//!
+//! ```text
+//! "Confidential Information" means all non-public information disclosed
+//! by either party, including trade secrets and know-how. The Recipient
+//! shall protect it.
+//! ```
+//!
+//! `DefinedTermResolver` runs per line, so the definition above is bounded to
+//! its first line. [`DefinitionExtentResolver`] follows the definition onto
+//! the next lines while no sentence has ended, and stops at the first sentence
+//! terminator ("know-how.") so the following sentence is not swallowed.
+//!
+//! [`find_definition_cycles`] uses these extents as definition bodies to find
+//! terms defined, directly or through other terms, in terms of themselves.
+//! [`find_inconsistent_definitions`] compares them to find a term defined
+//! twice, differently.
+
+use layered_nlp::{x, Association};
+use layered_nlp_document::{DocumentResolver, LayeredDocument};
+
+use crate::defined_term::{DefinedTerm, DefinitionBody, DefinitionScope, DefinitionType};
+use crate::section_header::SectionHeader;
+use crate::sentence_boundary::SentenceBoundaryResolver;
+use crate::term_reference::DocumentTermReferenceResolver;
+use crate::utils::content_tokens;
+use crate::{DocPosition, DocSpan, Scored};
+
+/// A defined term together with the full span of its definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefinitionExtent {
+    /// The defined term
+    pub term: DefinedTerm,
+    /// The quoted term, as found by `DefinedTermResolver`
+    pub term_span: DocSpan,
+    /// From the quoted term to the end of the defining sentence, possibly
+    /// across lines
+    pub span: DocSpan,
+}
+
+impl DefinitionExtent {
+    /// Whether the definition continues past the line of the term.
+    pub fn is_multi_line(&self) -> bool {
+        self.span.end.line > self.span.start.line
+    }
+}
+
+/// Document-level resolver assembling definitions across lines.
+///
+/// `"Term" means ...` definitions are extended to the first sentence
+/// terminator, continuing onto following lines while the current one ends
+/// without one. Extension stops early at a section header, a blank line,
+/// a line opening with another definition, the end of a section-scoped
+/// definition's section, or after `max_lines` lines. Other definition types
+/// name a phrase on the term's own line and keep the term's span.
+///
+/// Requires `DefinedTermResolver` (and `SectionHeaderResolver`, for scoping
+/// and stopping at headers) to have run on the document's lines.
+#[derive(Debug, Clone)]
+pub struct DefinitionExtentResolver {
+    /// Maximum number of lines a definition may cover
+    max_lines: usize,
+}
+
+impl Default for DefinitionExtentResolver {
+    fn default() -> Self {
+        Self { max_lines: 6 }
+    }
+}
+
+impl DefinitionExtentResolver {
+    /// Create a new resolver with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of lines a definition may cover (at least 1).
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = max_lines.max(1);
+        self
+    }
+
+    /// Whether `line_idx` cannot continue a definition from the line above.
+    fn breaks_definition(doc: &LayeredDocument, line_idx: usize) -> bool {
+        let line = &doc.lines()[line_idx];
+        let tokens = content_tokens(line);
+        let opens_with = |range: (usize, usize)| {
+            tokens.first().is_some_and(|(first, _, _)| range.0 <= *first)
+        };
+        tokens.is_empty()
+            || line
+                .find(&x::attr::<SectionHeader>())
+                .iter()
+                .any(|found| opens_with(found.token_range()))
+            || line
+                .find(&x::attr::<Scored<DefinedTerm>>())
+                .iter()
+                .any(|found| opens_with(found.token_range()))
+    }
+
+    /// End of the sentence starting at `start`, bounded by `last_line`.
+    fn sentence_end(
+        &self,
+        doc: &LayeredDocument,
+        start: DocPosition,
+        last_line: usize,
+    ) -> DocPosition {
+        let sentences = SentenceBoundaryResolver::new();
+        let mut end = start;
+        let mut previous: Option<&str> = None;
+
+        for line_idx in start.line..=last_line {
+            if line_idx > start.line && Self::breaks_definition(doc, line_idx) {
+                break;
+            }
+            let tokens = content_tokens(&doc.lines()[line_idx]);
+            for (token, text, _) in tokens {
+                if line_idx == start.line && token < start.token {
+                    continue;
+                }
+                end = DocPosition::new(line_idx, token);
+                if sentences.ends_sentence(previous, text) {
+                    return end;
+                }
+                previous = Some(text);
+            }
+        }
+        end
+    }
+}
+
+impl DocumentResolver for DefinitionExtentResolver {
+    type Attr = Scored<DefinitionExtent>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        let definitions = DocumentTermReferenceResolver::new().scoped_definitions(doc);
+
+        definitions
+            .into_iter()
+            .map(|(term_span, scored)| {
+                let span = match scored.value.definition_type {
+                    DefinitionType::QuotedMeans => {
+                        let mut last_line = (term_span.start.line + self.max_lines - 1)
+                            .min(doc.line_count() - 1);
+                        // A scoped definition cannot run past its section
+                        if let DefinitionScope::Section(section) = &scored.value.scope {
+                            last_line = last_line.min(section.end.line);
+                        }
+                        let end = self.sentence_end(doc, term_span.start, last_line);
+                        DocSpan::new(term_span.start, end)
+                    }
+                    _ => term_span,
+                };
+                Scored::rule_based(
+                    DefinitionExtent {
+                        term: scored.value,
+                        term_span,
+                        span,
+                    },
+                    scored.confidence,
+                    "definition_extent",
+                )
+            })
+            .collect()
+    }
+}
+
+/// Find defined terms whose definitions depend on each other in a cycle.
+///
+/// ```text
+/// "Services" means the work described in each Statement of Work.
+/// "Statement of Work" means a document describing Services.
+/// ```
+///
+/// The body of each `"Term" means ...` definition (its [`DefinitionExtent`]
+/// after the quoted term) refers to the defined terms it mentions with their
+/// defined capitalization, giving a directed graph of term to referenced
+/// terms. Every cycle closed by a depth-first search of that graph is reported
+/// as its member terms in dependency order, starting from the one defined
+/// first: `["Services", "Statement of Work"]` above. A term mentioned in its
+/// own definition is a cycle of one.
+///
+/// Requires `DefinedTermResolver` (and `SectionHeaderResolver`, for scoping)
+/// to have run on the document's lines.
+pub fn find_definition_cycles(doc: &LayeredDocument) -> Vec<Vec<String>> {
+    let extents: Vec<DefinitionExtent> = DefinitionExtentResolver::new()
+        .resolve(doc)
+        .into_iter()
+        .map(|scored| scored.value)
+        .collect();
+
+    // One node per term, in order of first definition
+    let mut terms: Vec<&str> = Vec::new();
+    for extent in &extents {
+        if !terms.contains(&extent.term.term_name.as_str()) {
+            terms.push(&extent.term.term_name);
+        }
+    }
+    // Longest term first, so "Statement of Work" wins over "Work"
+    let mut by_length: Vec<(usize, Vec<&str>)> = terms
+        .iter()
+        .enumerate()
+        .map(|(idx, term)| (idx, term.split_whitespace().collect()))
+        .collect();
+    by_length.sort_by_key(|(_, words)| std::cmp::Reverse(words.len()));
+
+    let mut edges: Vec<Vec<usize>> = vec![Vec::new(); terms.len()];
+    for extent in extents
+        .iter()
+        .filter(|extent| extent.term.definition_type == DefinitionType::QuotedMeans)
+    {
+        let from = terms
+            .iter()
+            .position(|term| *term == extent.term.term_name)
+            .expect("every extent has a node");
+        let body = definition_body(doc, extent);
+        let mut i = 0;
+        while i < body.len() {
+            let matched = by_length.iter().find(|(_, words)| {
+                words.len() <= body.len() - i
+                    && words.iter().zip(&body[i..]).all(|(word, text)| word == text)
+            });
+            match matched {
+                Some((to, words)) => {
+                    if !edges[from].contains(to) {
+                        edges[from].push(*to);
+                    }
+                    i += words.len();
+                }
+                None => i += 1,
+            }
+        }
+    }
+
+    let mut cycles = Vec::new();
+    let mut finished = vec![false; terms.len()];
+    let mut stack = Vec::new();
+    for start in 0..terms.len() {
+        collect_cycles(start, &edges, &mut stack, &mut finished, &mut cycles);
+    }
+    cycles
+        .into_iter()
+        .map(|cycle| cycle.into_iter().map(|idx| terms[idx].to_string()).collect())
+        .collect()
+}
+
+/// Token texts of a definition after its quoted term.
+fn definition_body<'a>(doc: &'a LayeredDocument, extent: &DefinitionExtent) -> Vec<&'a str> {
+    let mut body = Vec::new();
+    let mut quotes = 0;
+    for line_idx in extent.span.start.line..=extent.span.end.line {
+        for (token, text, _) in content_tokens(&doc.lines()[line_idx]) {
+            if !extent.span.contains_position(&DocPosition::new(line_idx, token)) {
+                continue;
+            }
+            if quotes < 2 {
+                quotes += usize::from(matches!(text, "\"" | "\u{201c}" | "\u{201d}"));
+                continue;
+            }
+            body.push(text);
+        }
+    }
+    body
+}
+
+/// Two global definitions of the same term with different bodies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InconsistentDefinition {
+    /// The term defined twice
+    pub term_name: String,
+    /// The first global definition of the term
+    pub first: DocSpan,
+    /// What the first definition defines the term as ("ABC Corp")
+    pub first_body: String,
+    /// A later global definition disagreeing with the first
+    pub conflicting: DocSpan,
+    /// What the later definition defines the term as ("XYZ Inc")
+    pub conflicting_body: String,
+}
+
+/// Share of differing body words below which two definitions are taken to
+/// say the same thing.
+const MATERIAL_DIFFERENCE: f64 = 0.5;
+
+/// Words left out when comparing definition bodies.
+const BODY_STOP_WORDS: &[&str] = &["a", "an", "mean", "means", "shall", "the"];
+
+/// Find terms defined more than once at the global scope with materially
+/// different bodies.
+///
+/// ```text
+/// ABC Corp (the "Company") shall deliver the goods.
+/// ...
+/// XYZ Inc (the "Company") shall pay the invoice.
+/// ```
+///
+/// A body is the phrase a parenthetical, alias or hereinafter definition
+/// names ("ABC Corp"), or the text after a `"Term" means` definition's quoted
+/// term. Every later global definition of a term is compared with the first;
+/// section-scoped redefinitions, such as in an exhibit, are legitimate and
+/// never reported. Body words are compared ignoring case, articles and
+/// abbreviations ("Corp" matches "Corporation"), and a pair is reported when
+/// at least half of them differ. Confidence is that share of differing words,
+/// capped at 0.95.
+///
+/// Requires `DefinedTermResolver` (and `SectionHeaderResolver`, for scoping)
+/// to have run on the document's lines.
+pub fn find_inconsistent_definitions(doc: &LayeredDocument) -> Vec<Scored<InconsistentDefinition>> {
+    let definitions: Vec<(DefinitionExtent, Vec<String>)> = DefinitionExtentResolver::new()
+        .resolve(doc)
+        .into_iter()
+        .map(|scored| scored.value)
+        .filter(|extent| extent.term.scope == DefinitionScope::Global)
+        .map(|extent| {
+            let body = named_body(doc, &extent);
+            (extent, body)
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for (idx, (first, first_body)) in definitions.iter().enumerate() {
+        let is_first = !definitions[..idx]
+            .iter()
+            .any(|(earlier, _)| earlier.term.term_name == first.term.term_name);
+        if !is_first {
+            continue;
+        }
+        for (later, later_body) in &definitions[idx + 1..] {
+            if later.term.term_name != first.term.term_name {
+                continue;
+            }
+            let difference = body_difference(first_body, later_body);
+            if difference < MATERIAL_DIFFERENCE {
+                continue;
+            }
+            results.push(Scored::rule_based(
+                InconsistentDefinition {
+                    term_name: first.term.term_name.clone(),
+                    first: first.span,
+                    first_body: first_body.join(" "),
+                    conflicting: later.span,
+                    conflicting_body: later_body.join(" "),
+                },
+                difference.min(0.95),
+                "inconsistent_definition",
+            ));
+        }
+    }
+    results
+}
+
+/// Words of what a definition defines its term as.
+fn named_body(doc: &LayeredDocument, extent: &DefinitionExtent) -> Vec<String> {
+    let line = &doc.lines()[extent.term_span.start.line];
+    let tokens = content_tokens(line);
+    let words: Vec<&str> = match extent.term.definition_type {
+        DefinitionType::QuotedMeans => definition_body(doc, extent),
+        DefinitionType::Parenthetical | DefinitionType::Alias => {
+            let range = (extent.term_span.start.token, extent.term_span.end.token);
+            let body = line
+                .query_with_associations::<Scored<DefinedTerm>>()
+                .into_iter()
+                .filter(|(found, _, _)| *found == range)
+                .flat_map(|(_, _, values)| values)
+                .flat_map(|(_, associations)| associations.iter())
+                .find(|association| association.label() == DefinitionBody.label())
+                .map(|association| association.span);
+            tokens
+                .iter()
+                .filter(|(idx, _, _)| {
+                    body.is_some_and(|span| span.start_idx <= *idx && *idx <= span.end_idx)
+                })
+                .map(|(_, text, _)| *text)
+                .collect()
+        }
+        // The words before ", hereinafter"
+        DefinitionType::Hereinafter => {
+            let start = tokens
+                .iter()
+                .position(|(idx, _, _)| *idx >= extent.term_span.start.token)
+                .unwrap_or(tokens.len());
+            let mut before: Vec<&str> = tokens[..start]
+                .iter()
+                .rev()
+                .skip_while(|(_, text, _)| *text == ",")
+                .take_while(|(_, text, _)| text.chars().all(char::is_alphanumeric))
+                .map(|(_, text, _)| *text)
+                .collect();
+            before.reverse();
+            before
+        }
+    };
+    words
+        .into_iter()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .map(str::to_lowercase)
+        .filter(|word| !BODY_STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Share of the words of two bodies without a match in the other, where a
+/// word matches another it abbreviates ("corp", "corporation").
+fn body_difference(a: &[String], b: &[String]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let matches = |word: &String, other: &String| {
+        let (short, long) = if word.len() <= other.len() { (word, other) } else { (other, word) };
+        short == long || (short.len() >= 3 && long.starts_with(short.as_str()))
+    };
+    let matched = |words: &[String], others: &[String]| {
+        words.iter().filter(|word| others.iter().any(|other| matches(word, other))).count()
+    };
+    let matched = matched(a, b) + matched(b, a);
+    1.0 - matched as f64 / (a.len() + b.len()) as f64
+}
+
+/// Depth-first search from `node`, recording the stack from the target of
+/// each edge back into the stack as a cycle.
+fn collect_cycles(
+    node: usize,
+    edges: &[Vec<usize>],
+    stack: &mut Vec<usize>,
+    finished: &mut [bool],
+    cycles: &mut Vec<Vec<usize>>,
+) {
+    if finished[node] || stack.contains(&node) {
+        return;
+    }
+    stack.push(node);
+    for &next in &edges[node] {
+        match stack.iter().position(|&on_stack| on_stack == next) {
+            Some(pos) => cycles.push(stack[pos..].to_vec()),
+            None => collect_cycles(next, edges, stack, finished, cycles),
+        }
+    }
+    stack.pop();
+    finished[node] = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractDocument, ContractKeywordResolver, DefinedTermResolver, SectionHeaderResolver,
+    };
+
+    fn document(text: &str) -> ContractDocument {
+        ContractDocument::from_text(text)
+            .run_resolver(&SectionHeaderResolver::new())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+    }
+
+    fn extents(doc: &ContractDocument) -> Vec<DefinitionExtent> {
+        DefinitionExtentResolver::new()
+            .resolve(doc)
+            .into_iter()
+            .map(|scored| scored.value)
+            .collect()
+    }
+
+    fn text_before(doc: &ContractDocument, position: DocPosition) -> Vec<&str> {
+        let tokens = content_tokens(&doc.lines()[position.line]);
+        let end = tokens.iter().position(|(idx, _, _)| *idx == position.token).unwrap();
+        tokens[end - 1..=end].iter().map(|(_, text, _)| *text).collect()
+    }
+
+    #[test]
+    fn test_definition_continues_to_first_terminator() {
+        let doc = document(
+            "\"Confidential Information\" means all non-public information disclosed\n\
+             by either party, including trade secrets of ABC Inc. and know-how. The\n\
+             Recipient shall protect it.\n\
+             \"Services\" means the consulting services. The Supplier shall perform them.",
+        );
+        let found = extents(&doc);
+
+        assert_eq!(found.len(), 2);
+        let confidential = &found[0];
+        assert_eq!(confidential.term.term_name, "Confidential Information");
+        assert!(confidential.is_multi_line());
+        assert_eq!(confidential.span.start, confidential.term_span.start);
+        // "Inc." is an abbreviation; the sentence ends at "know-how."
+        assert_eq!(confidential.span.end.line, 1);
+        assert_eq!(text_before(&doc, confidential.span.end), vec!["how", "."]);
+
+        let services = &found[1];
+        assert!(!services.is_multi_line());
+        assert_eq!(services.span.start.line, 3);
+    }
+
+    #[test]
+    fn test_definition_stops_at_headers_and_new_definitions() {
+        let found = extents(&document(
+            "\"Affiliate\" means any entity controlling a party\n\
+             \"Business Day\" means a day on which banks are open\n\
+             Section 2. Payment\n\
+             Fees are due monthly.",
+        ));
+
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|extent| !extent.is_multi_line()));
+
+        let capped = document("\"Term\" means the period\nof one\nyear.");
+        let resolver = DefinitionExtentResolver::new().with_max_lines(2);
+        assert_eq!(resolver.resolve(&capped)[0].value.span.end.line, 1);
+        assert_eq!(DefinitionExtentResolver::new().resolve(&capped)[0].value.span.end.line, 2);
+    }
+
+    #[test]
+    fn test_definition_stops_at_indented_headers_and_definitions() {
+        let found = extents(&document(concat!(
+            "\"Affiliate\" means any entity controlling a party\n",
+            "    \"Business Day\" means a day on which banks are open\n",
+            "    Section 2. Payment\n",
+            "Fees are due monthly.",
+        )));
+
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|extent| !extent.is_multi_line()));
+    }
+
+    #[test]
+    fn test_find_inconsistent_definitions() {
+        let doc = document(
+            "ABC Corp (the \"Company\") shall deliver the goods.\n\
+             ABC Corporation (the \"Company\") shall keep records.\n\
+             \"Fee\" means the annual fee payable by the Company.\n\
+             XYZ Inc (the \"Company\") shall pay the invoice.\n\
+             SCHEDULE 1\n\
+             \"Fee\" means the setup fee.",
+        );
+        let found = find_inconsistent_definitions(&doc);
+
+        // "ABC Corporation" agrees with "ABC Corp"; the scheduled "Fee" is scoped
+        assert_eq!(found.len(), 1);
+        let conflict = &found[0].value;
+        assert_eq!(conflict.term_name, "Company");
+        assert_eq!((conflict.first.start.line, conflict.conflicting.start.line), (0, 3));
+        assert_eq!(conflict.first_body, "abc corp");
+        assert_eq!(conflict.conflicting_body, "xyz inc");
+        assert!(found[0].confidence > 0.9);
+    }
+
+    #[test]
+    fn test_find_definition_cycles() {
+        let doc = document(
+            "\"Services\" means the work described in each Statement of Work.\n\
+             \"Statement of Work\" means a document describing Services.\n\
+             \"Fees\" means the amounts payable for the Services.\n\
+             \"Deliverables\" means the deliverables listed in the Deliverables Schedule.",
+        );
+        assert_eq!(
+            find_definition_cycles(&doc),
+            vec![
+                vec!["Services".to_string(), "Statement of Work".to_string()],
+                vec!["Deliverables".to_string()],
+            ]
+        );
+
+        // Lowercase mentions are not references to the term
+        let acyclic = document(
+            "\"Services\" means the consulting services.\n\
+             \"Fees\" means the amounts payable for the Services.",
+        );
+        assert!(find_definition_cycles(&acyclic).is_empty());
+    }
+}
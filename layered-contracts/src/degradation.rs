@@ -0,0 +1,66 @@
+//! Reporting for analyses that ran with a reduced comparison budget.
+//!
+//! Pairwise analyses grow quadratically with document size. [`ConflictDetector`]
+//! and [`DocumentAligner`] accept a budget and, when a document exceeds it,
+//! narrow what they compare instead of running to completion. The narrowing is
+//! recorded in a [`DegradationReport`] so callers can tell a clean result from
+//! a partial one.
+//!
+//! [`ConflictDetector`]: crate::ConflictDetector
+//! [`DocumentAligner`]: crate::DocumentAligner
+
+use serde::{Deserialize, Serialize};
+
+/// How an over-budget comparison was narrowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DegradationFallback {
+    /// Only items within the same section were compared
+    SectionScoped,
+    /// Each item was compared only with the next `window` items in document order
+    Windowed { window: usize },
+}
+
+/// What an analysis skipped because its input exceeded the configured budget.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DegradationReport {
+    /// What the budget limits ("obligation pairs", "sections")
+    pub unit: String,
+    /// The configured budget
+    pub budget: usize,
+    /// How many units a full run would have needed
+    pub required: usize,
+    /// How many comparisons were actually made
+    pub compared: usize,
+    /// How the comparison was narrowed
+    pub fallback: DegradationFallback,
+    /// Stages that were not run at all
+    pub skipped_stages: Vec<String>,
+}
+
+impl DegradationReport {
+    /// Comparisons a full run would have made but this one did not.
+    ///
+    /// Only meaningful when `required` counts comparisons; for other units
+    /// use [`warning`](Self::warning).
+    pub fn skipped(&self) -> usize {
+        self.required.saturating_sub(self.compared)
+    }
+
+    /// A one-line description suitable for result warnings.
+    pub fn warning(&self) -> String {
+        let fallback = match self.fallback {
+            DegradationFallback::SectionScoped => "section-scoped comparison".to_string(),
+            DegradationFallback::Windowed { window } => {
+                format!("windowed comparison (window {})", window)
+            }
+        };
+        let mut warning = format!(
+            "{} {} exceed the budget of {}; fell back to {}",
+            self.required, self.unit, self.budget, fallback
+        );
+        if !self.skipped_stages.is_empty() {
+            warning.push_str(&format!(", skipped {}", self.skipped_stages.join(", ")));
+        }
+        warning
+    }
+}
@@ -6,14 +6,19 @@
 //!
 //! This respects Rust's orphan rule: the domain crate does the mapping since
 //! it owns neither the trait nor the type from layered_deixis.
+//!
+//! [`aggregate_deixis`] profiles a whole document, merging the output of every
+//! deixis resolver into per-category counts and spans.
+
+use std::cmp::Ordering;
 
 use layered_deixis::{
     DeicticCategory, DeicticReference, DeicticSource, DeicticSubcategory, ResolvedReferent,
 };
-use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver};
+use layered_nlp::{x, LLCursorAssignment, LLLine, LLSelection, Resolver};
 
 use crate::pronoun::{PronounReference, PronounType};
-use crate::Scored;
+use crate::{ContractDocument, DocSpan, Scored};
 use crate::section_reference::{ReferenceType, RelativeReference, SectionReference};
 use crate::temporal::{TemporalExpression, TemporalType};
 
@@ -150,6 +155,153 @@ impl Resolver for DeicticResolver {
     }
 }
 
+/// Order in which categories are reported by [`DeicticSummary`].
+const CATEGORY_ORDER: [DeicticCategory; 5] = [
+    DeicticCategory::Person,
+    DeicticCategory::Place,
+    DeicticCategory::Time,
+    DeicticCategory::Discourse,
+    DeicticCategory::Social,
+];
+
+/// Deictic references found in a document, grouped by category and subcategory.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DeicticSummary {
+    /// Number of references after overlapping spans are merged
+    pub total: usize,
+    /// Number of references with a resolved referent
+    pub resolved: usize,
+    /// Categories present in the document, in `DeicticCategory` declaration order
+    pub categories: Vec<DeicticCategorySummary>,
+}
+
+/// References of one deictic category.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DeicticCategorySummary {
+    pub category: DeicticCategory,
+    pub count: usize,
+    /// Subcategories in order of first appearance
+    pub subcategories: Vec<DeicticSubcategorySummary>,
+}
+
+/// References of one deictic subcategory.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DeicticSubcategorySummary {
+    pub subcategory: DeicticSubcategory,
+    /// Spans of each reference, in document order
+    pub spans: Vec<DocSpan>,
+}
+
+impl DeicticSummary {
+    /// Share of references that were resolved to a referent (0.0 when there are none).
+    pub fn resolved_ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.resolved as f64 / self.total as f64
+        }
+    }
+
+    /// Number of references in a category.
+    pub fn count(&self, category: DeicticCategory) -> usize {
+        self.category(category).map_or(0, |c| c.count)
+    }
+
+    /// The summary for a category, if the document has any references in it.
+    pub fn category(&self, category: DeicticCategory) -> Option<&DeicticCategorySummary> {
+        self.categories.iter().find(|c| c.category == category)
+    }
+
+    fn add(&mut self, span: DocSpan, deictic: &DeicticReference) {
+        self.total += 1;
+        if deictic.resolved_referent.is_some() {
+            self.resolved += 1;
+        }
+
+        let idx = match self.categories.iter().position(|c| c.category == deictic.category) {
+            Some(idx) => idx,
+            None => {
+                let rank = |category: DeicticCategory| {
+                    CATEGORY_ORDER.iter().position(|c| *c == category)
+                };
+                let idx = self
+                    .categories
+                    .iter()
+                    .position(|c| rank(c.category) > rank(deictic.category))
+                    .unwrap_or(self.categories.len());
+                self.categories.insert(
+                    idx,
+                    DeicticCategorySummary {
+                        category: deictic.category,
+                        count: 0,
+                        subcategories: Vec::new(),
+                    },
+                );
+                idx
+            }
+        };
+        let category = &mut self.categories[idx];
+        category.count += 1;
+
+        match category
+            .subcategories
+            .iter_mut()
+            .find(|s| s.subcategory == deictic.subcategory)
+        {
+            Some(sub) => sub.spans.push(span),
+            None => category.subcategories.push(DeicticSubcategorySummary {
+                subcategory: deictic.subcategory.clone(),
+                spans: vec![span],
+            }),
+        }
+    }
+}
+
+/// Count and locate the `DeicticReference`s in a document.
+///
+/// Expects the deixis resolvers (`DeicticResolver` and the word-list resolvers
+/// from `layered_deixis`) to have run on the document's lines. Where their
+/// results overlap, only one reference is counted: a resolved one if any,
+/// otherwise the most confident, preferring the longer span on ties.
+pub fn aggregate_deixis(doc: &ContractDocument) -> DeicticSummary {
+    let mut summary = DeicticSummary::default();
+    for (line_idx, line) in doc.lines_enumerated() {
+        for (start, end, deictic) in merged_line_deixis(line) {
+            summary.add(doc.span_from_line_range(line_idx, start, end), deictic);
+        }
+    }
+    summary
+}
+
+/// The non-overlapping deictic references of a line, in token order.
+fn merged_line_deixis(line: &LLLine) -> Vec<(usize, usize, &DeicticReference)> {
+    let mut found: Vec<_> = line
+        .find(&x::attr::<DeicticReference>())
+        .into_iter()
+        .map(|found| {
+            let (start, end) = found.token_range();
+            (start, end, *found.attr())
+        })
+        .collect();
+    found.sort_by(|a, b| {
+        let resolved = |d: &DeicticReference| d.resolved_referent.is_some();
+        resolved(b.2)
+            .cmp(&resolved(a.2))
+            .then_with(|| b.2.confidence.partial_cmp(&a.2.confidence).unwrap_or(Ordering::Equal))
+            .then_with(|| (b.1 - b.0).cmp(&(a.1 - a.0)))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+
+    let mut kept: Vec<(usize, usize, &DeicticReference)> = Vec::new();
+    for candidate in found {
+        if kept.iter().all(|(start, end, _)| candidate.1 < *start || candidate.0 > *end) {
+            kept.push(candidate);
+        }
+    }
+    kept.sort_by_key(|(start, _, _)| *start);
+    kept
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,4 +458,65 @@ mod tests {
                            ╰────╯DeicticReference { category: Discourse, subcategory: DiscourseThisDocument, surface_text: "herein", resolved_referent: None, confidence: 0.7, source: SectionReferenceResolver }
         "###);
     }
+
+    #[test]
+    fn test_aggregate_deixis_merges_resolvers() {
+        use crate::{ContractKeywordResolver, DefinedTermResolver, PronounResolver};
+        use crate::{TermReferenceResolver, TemporalExpressionResolver};
+        use layered_deixis::{DiscourseMarkerResolver, PersonPronounResolver, PlaceDeicticResolver};
+
+        let doc = ContractDocument::from_text(
+            "ABC Corp (the \"Company\") shall deliver within 30 days and it must deliver there.\n\
+             However, we agree.",
+        )
+        .run_resolver(&ContractKeywordResolver::default())
+        .run_resolver(&DefinedTermResolver::default())
+        .run_resolver(&TermReferenceResolver::default())
+        .run_resolver(&PronounResolver::default())
+        .run_resolver(&TemporalExpressionResolver::default())
+        .run_resolver(&DeicticResolver)
+        .run_resolver(&PersonPronounResolver::new())
+        .run_resolver(&PlaceDeicticResolver::new())
+        .run_resolver(&DiscourseMarkerResolver::new());
+
+        let summary = aggregate_deixis(&doc);
+
+        // "it" is produced by both PronounResolver mapping and the word list: counted once
+        let person = summary.category(DeicticCategory::Person).unwrap();
+        assert_eq!(person.count, 2, "{:?}", person);
+        assert_eq!(summary.count(DeicticCategory::Place), 1);
+        // "there" is token 30 of the first line
+        let place = summary.category(DeicticCategory::Place).unwrap();
+        assert_eq!(place.subcategories[0].spans, vec![DocSpan::single_line(0, 30, 30)]);
+        assert_eq!(summary.count(DeicticCategory::Discourse), 1);
+        assert_eq!(summary.count(DeicticCategory::Time), 1);
+        assert_eq!(
+            summary.total,
+            summary.categories.iter().map(|c| c.count).sum::<usize>()
+        );
+
+        // Only "it" resolves to an antecedent
+        assert_eq!(summary.resolved, 1);
+        assert!((summary.resolved_ratio() - 1.0 / summary.total as f64).abs() < 1e-9);
+
+        let order: Vec<_> = summary.categories.iter().map(|c| c.category).collect();
+        assert_eq!(
+            order,
+            vec![
+                DeicticCategory::Person,
+                DeicticCategory::Place,
+                DeicticCategory::Time,
+                DeicticCategory::Discourse
+            ]
+        );
+        assert!(serde_json::to_string(&summary).is_ok());
+    }
+
+    #[test]
+    fn test_aggregate_deixis_empty_document() {
+        let summary = aggregate_deixis(&ContractDocument::from_text("Contract terms apply."));
+        assert_eq!(summary.total, 0);
+        assert_eq!(summary.resolved_ratio(), 0.0);
+        assert!(summary.categories.is_empty());
+    }
 }
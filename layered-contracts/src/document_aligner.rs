@@ -75,6 +75,7 @@ use pathfinding::kuhn_munkres::{kuhn_munkres_min, Weights};
 use serde::{Deserialize, Serialize};
 
 use crate::ContractDocument;
+use crate::degradation::{DegradationFallback, DegradationReport};
 use crate::document_structure::{DocumentStructure, SectionNode};
 use crate::section_header::SectionIdentifier;
 
@@ -203,6 +204,9 @@ pub struct AlignmentResult {
     pub stats: AlignmentStats,
     /// Warnings generated during alignment
     pub warnings: Vec<String>,
+    /// What was skipped when the documents exceeded the section budget
+    #[serde(default)]
+    pub degradation: Option<DegradationReport>,
 }
 
 impl AlignmentResult {
@@ -286,6 +290,9 @@ pub struct AlignmentCandidates {
     pub original_section_count: usize,
     /// Total sections in revised
     pub revised_section_count: usize,
+    /// What was skipped when the documents exceeded the section budget
+    #[serde(default)]
+    pub degradation: Option<DegradationReport>,
 }
 
 impl AlignmentCandidates {
@@ -433,6 +440,8 @@ macro_rules! token_text {
 /// Aligns sections between two versions of a contract document.
 pub struct DocumentAligner {
     config: SimilarityConfig,
+    /// Largest section count aligned with all-pairs comparison
+    max_sections: Option<usize>,
 }
 
 impl Default for DocumentAligner {
@@ -446,12 +455,27 @@ impl DocumentAligner {
     pub fn new() -> Self {
         Self {
             config: SimilarityConfig::default(),
+            max_sections: None,
         }
     }
 
     /// Create an aligner with custom configuration.
     pub fn with_config(config: SimilarityConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            max_sections: None,
+        }
+    }
+
+    /// Cap the number of sections compared all-pairs.
+    ///
+    /// When either document has more than `max_sections` sections, content
+    /// matching (after ID and title matching) only compares sections within
+    /// position-aligned windows of `max_sections`, and split/merge detection
+    /// is skipped. The result's `degradation` records this.
+    pub fn with_max_sections(mut self, max_sections: usize) -> Self {
+        self.max_sections = Some(max_sections.max(1));
+        self
     }
 
     /// Phase 1: Compute initial alignment with deterministic signals.
@@ -473,6 +497,10 @@ impl DocumentAligner {
         let mut candidates: Vec<AlignmentCandidate> = Vec::new();
         let mut candidate_id = 0;
 
+        let required = original_sections.len().max(revised_sections.len());
+        let window = self.max_sections.filter(|&budget| required > budget);
+        let mut compared = 0;
+
         // Extract semantics for all sections
         let original_semantics: Vec<_> = original_sections
             .iter()
@@ -628,7 +656,13 @@ impl DocumentAligner {
             .filter(|i| !matched_revised.contains(i))
             .collect();
 
-        if !unmatched_orig.is_empty() && !unmatched_rev.is_empty() {
+        // Over budget, only sections in the same position window are compared
+        let chunk = window.unwrap_or(usize::MAX);
+        for (unmatched_orig, unmatched_rev) in
+            unmatched_orig.chunks(chunk).zip(unmatched_rev.chunks(chunk))
+        {
+            compared += unmatched_orig.len() * unmatched_rev.len();
+
             // Build similarity matrix
             let n = unmatched_orig.len().max(unmatched_rev.len());
             let mut weights = vec![vec![0i64; n]; n];
@@ -717,7 +751,7 @@ impl DocumentAligner {
         // Pass 4: Split/Merge detection
         // Check unmatched original sections for potential splits
         for orig_idx in 0..original_sections.len() {
-            if matched_original.contains(&orig_idx) {
+            if window.is_some() || matched_original.contains(&orig_idx) {
                 continue;
             }
 
@@ -776,7 +810,7 @@ impl DocumentAligner {
 
         // Check unmatched revised sections for potential merges
         for rev_idx in 0..revised_sections.len() {
-            if matched_revised.contains(&rev_idx) {
+            if window.is_some() || matched_revised.contains(&rev_idx) {
                 continue;
             }
 
@@ -889,6 +923,14 @@ impl DocumentAligner {
             unmatched_revised,
             original_section_count: original_sections.len(),
             revised_section_count: revised_sections.len(),
+            degradation: window.map(|window| DegradationReport {
+                unit: "sections".to_string(),
+                budget: window,
+                required,
+                compared,
+                fallback: DegradationFallback::Windowed { window },
+                skipped_stages: vec!["split/merge detection".to_string()],
+            }),
         }
     }
 
@@ -974,6 +1016,9 @@ impl DocumentAligner {
             ..Default::default()
         };
         let mut warnings: Vec<String> = Vec::new();
+        if let Some(degradation) = &candidates.degradation {
+            warnings.push(degradation.warning());
+        }
 
         for candidate in candidates.candidates {
             // Skip rejected candidates
@@ -1031,6 +1076,7 @@ impl DocumentAligner {
             alignments,
             stats,
             warnings,
+            degradation: candidates.degradation,
         }
     }
 
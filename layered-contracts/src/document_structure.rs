@@ -4,9 +4,17 @@
 //! with `SectionHeader` attributes on individual lines and builds a hierarchical
 //! `DocumentStructure` representing the document's outline.
 
-use layered_nlp::x;
+use std::collections::HashMap;
 
-use crate::{ContractDocument, DocPosition, DocSpan, ProcessResult};
+use layered_nlp::{x, LToken, TextTag};
+
+use crate::{
+    ContractDocument, DocPosition, DocSpan, DocumentWarning, ProcessResult, Scored, TypedQuery,
+    WarningSeverity,
+};
+use crate::document_aligner::SectionRef;
+use crate::obligation::ObligationPhrase;
+use crate::precedence::SectionClassifier;
 use crate::section_header::SectionHeader;
 
 /// Hierarchical representation of document structure.
@@ -45,10 +53,77 @@ impl DocumentStructure {
             .find(|node| node.header.identifier.canonical() == canonical)
     }
 
+    /// Find a section by label, e.g. "3" for "Section 3" or "ARTICLE:R2".
+    ///
+    /// An exact canonical match wins; otherwise the first section whose
+    /// identifier after the keyword ("Section", "Article") matches is used.
+    pub fn find_by_label(&self, label: &str) -> Option<&SectionNode> {
+        let label = label.trim().to_uppercase();
+        let sections = self.flatten();
+        let canonical = |node: &SectionNode| node.header.identifier.canonical().to_uppercase();
+        sections
+            .iter()
+            .find(|node| canonical(node) == label)
+            .or_else(|| {
+                sections.iter().find(|node| {
+                    canonical(node).split_once(':').is_some_and(|(_, sub)| sub == label)
+                })
+            })
+            .copied()
+    }
+
+    /// The section at `path`, given as child indices from the root.
+    fn node_at(&self, path: &[usize]) -> Option<&SectionNode> {
+        let (first, rest) = path.split_first()?;
+        rest.iter()
+            .try_fold(self.sections.get(*first)?, |node, idx| node.children.get(*idx))
+    }
+
     /// Get total number of sections (including nested).
     pub fn total_sections(&self) -> usize {
         self.flatten().len()
     }
+
+    /// Obligations per section, for triaging where review effort should go.
+    ///
+    /// Returns one entry per section in document order: the section, the
+    /// number of `Scored<ObligationPhrase>` annotations on its own lines
+    /// (subsections are counted separately), and that count divided by the
+    /// section's non-whitespace token count. Sort by the density to find
+    /// hotspots:
+    ///
+    /// ```ignore
+    /// let mut density = doc.structure().obligation_density(&doc);
+    /// density.sort_by(|a, b| b.2.total_cmp(&a.2));
+    /// ```
+    ///
+    /// Requires `ObligationPhraseResolver` to have run on the document's lines.
+    pub fn obligation_density(&self, doc: &ContractDocument) -> Vec<(SectionRef, usize, f64)> {
+        self.flatten()
+            .into_iter()
+            .map(|node| {
+                let lines = node.content_span.start.line..node.content_span.end.line + 1;
+                let obligations = doc
+                    .query()
+                    .of_type::<Scored<ObligationPhrase>>()
+                    .on_lines(lines.clone())
+                    .count();
+                let tokens: usize = doc.lines()[lines.start..lines.end.min(doc.line_count())]
+                    .iter()
+                    .flat_map(|line| line.ll_tokens())
+                    .filter(|token| {
+                        !matches!(token.get_token(), LToken::Text(_, TextTag::SPACE))
+                    })
+                    .count();
+                let density = if tokens == 0 {
+                    0.0
+                } else {
+                    obligations as f64 / tokens as f64
+                };
+                (SectionRef::from_node(node), obligations, density)
+            })
+            .collect()
+    }
 }
 
 /// A node in the document structure tree.
@@ -71,6 +146,15 @@ impl SectionNode {
     pub fn depth(&self) -> u8 {
         self.header.identifier.depth()
     }
+
+    /// Span covering the section together with all of its subsections.
+    pub fn full_span(&self) -> DocSpan {
+        let end = self
+            .children
+            .last()
+            .map_or(self.content_span.end, |child| child.full_span().end);
+        DocSpan::new(self.content_span.start, end)
+    }
 }
 
 /// Builds document structure from per-line section headers.
@@ -104,7 +188,10 @@ impl DocumentStructureBuilder {
         }
 
         if headers.is_empty() {
-            warnings.push("No section headers found in document".to_string());
+            warnings.push(DocumentWarning::new(
+                WarningSeverity::Info,
+                "No section headers found in document",
+            ));
             return ProcessResult {
                 value: DocumentStructure::empty(),
                 errors,
@@ -187,10 +274,14 @@ impl DocumentStructureBuilder {
             // Validate depth consistency
             if let Some((_, parent_depth)) = stack.last() {
                 if depth <= *parent_depth {
-                    warnings.push(format!(
-                        "Line {}: section '{}' (depth {}) appears after deeper section (depth {})",
-                        line_idx, header.raw_text, depth, parent_depth
-                    ));
+                    let message = format!(
+                        "section '{}' (depth {}) appears after deeper section (depth {})",
+                        header.raw_text, depth, parent_depth
+                    );
+                    warnings.push(
+                        DocumentWarning::new(WarningSeverity::Warning, message)
+                            .at(DocSpan::single_line(*line_idx, 0, 0)),
+                    );
                 }
             }
 
@@ -237,6 +328,111 @@ impl DocumentProcessor for DocumentStructureBuilder {
     }
 }
 
+/// Cached access to a document's section structure.
+///
+/// The structure is built on first access and shared by every later caller,
+/// so all consumers see the same outline. It is rebuilt after the document's
+/// lines change (e.g. another `run_resolver`).
+pub trait DocumentStructureExt {
+    /// Get the document structure, building it on first use.
+    fn structure(&self) -> &DocumentStructure;
+
+    /// The innermost section containing `span`.
+    ///
+    /// The sections of all `Scored<ObligationPhrase>` spans are indexed on
+    /// first use, so looking up an obligation is a hash lookup; other spans
+    /// are classified on demand.
+    fn section_of(&self, span: &DocSpan) -> Option<&SectionNode>;
+}
+
+impl DocumentStructureExt for ContractDocument {
+    fn structure(&self) -> &DocumentStructure {
+        self.memoized(|doc| DocumentStructureBuilder::build(doc).value)
+    }
+
+    fn section_of(&self, span: &DocSpan) -> Option<&SectionNode> {
+        let structure = self.structure();
+        let index = self.memoized(ObligationSectionIndex::build);
+        match index.paths.get(span) {
+            Some(path) => structure.node_at(path),
+            None => SectionClassifier::new(structure).find_containing_section(span),
+        }
+    }
+}
+
+/// Section of every obligation, as child indices from the root of the
+/// document structure.
+struct ObligationSectionIndex {
+    paths: HashMap<DocSpan, Vec<usize>>,
+}
+
+impl ObligationSectionIndex {
+    fn build(doc: &ContractDocument) -> Self {
+        let structure = doc.structure();
+        let classifier = SectionClassifier::new(structure);
+        let mut nodes = Vec::new();
+        for (idx, section) in structure.sections.iter().enumerate() {
+            Self::collect_paths(section, vec![idx], &mut nodes);
+        }
+
+        let mut paths = HashMap::new();
+        for (line_idx, line) in doc.lines_enumerated() {
+            for found in line.find(&x::attr::<Scored<ObligationPhrase>>()) {
+                let span = DocSpan::from_find(line_idx, &found);
+                let path = classifier.find_containing_section(&span).and_then(|section| {
+                    nodes
+                        .iter()
+                        .find(|(node, _)| std::ptr::eq(*node, section))
+                        .map(|(_, path)| path.clone())
+                });
+                if let Some(path) = path {
+                    paths.insert(span, path);
+                }
+            }
+        }
+        Self { paths }
+    }
+
+    fn collect_paths<'a>(
+        node: &'a SectionNode,
+        path: Vec<usize>,
+        nodes: &mut Vec<(&'a SectionNode, Vec<usize>)>,
+    ) {
+        for (idx, child) in node.children.iter().enumerate() {
+            let mut child_path = path.clone();
+            child_path.push(idx);
+            Self::collect_paths(child, child_path, nodes);
+        }
+        nodes.push((node, path));
+    }
+}
+
+/// Section filter for annotation queries.
+///
+/// ```ignore
+/// doc.query()
+///     .of_type::<Scored<ObligationPhrase>>()
+///     .in_section("3")
+///     .above_confidence(0.8)
+///     .collect();
+/// ```
+pub trait SectionQueryExt: Sized {
+    /// Keep annotations inside the labelled section or its subsections.
+    ///
+    /// The label is looked up with [`DocumentStructure::find_by_label`]; an
+    /// unknown label matches nothing.
+    fn in_section(self, label: &str) -> Self;
+}
+
+impl<T: 'static> SectionQueryExt for TypedQuery<'_, T> {
+    fn in_section(self, label: &str) -> Self {
+        match self.document().structure().find_by_label(label) {
+            Some(section) => self.within(section.full_span()),
+            None => self.filter(|_| false),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -366,7 +562,7 @@ ARTICLE II - SERVICES
     #[test]
     fn test_empty_document() {
         let result = build_structure("Just some text without any section headers.");
-        assert!(result.warnings.iter().any(|w| w.contains("No section headers")));
+        assert!(result.warnings.iter().any(|w| w.message.contains("No section headers")));
         assert_eq!(result.value.total_sections(), 0);
     }
 
@@ -548,4 +744,126 @@ Confidential Information shall not include publicly available data.
         assert_eq!(structure.sections[0].depth(), 2); // ARTICLE I = Named + Roman = 2
         assert_eq!(section_1_1.depth(), 3); // Section 1.1 = Named + Numeric(2 parts) = 1 + 2 = 3
     }
+
+    #[test]
+    fn test_structure_is_cached() {
+        let text = "ARTICLE I - DEFINITIONS\nSection 1.1 Terms\nARTICLE II - SERVICES";
+        let doc = ContractDocument::from_text(text).run_resolver(&SectionHeaderResolver::new());
+
+        let first = doc.structure();
+        assert!(std::ptr::eq(first, doc.structure()));
+        let rebuilt = DocumentStructureBuilder::build(&doc).value;
+        assert_eq!(first.total_sections(), rebuilt.total_sections());
+        assert_eq!(first.sections.len(), 2);
+    }
+
+    #[test]
+    fn test_query_in_section() {
+        use crate::{
+            ContractKeywordResolver, ObligationPhrase, ObligationPhraseResolver, Scored,
+        };
+        use layered_part_of_speech::POSTagResolver;
+
+        let text = "Section 1. Delivery\n\
+                    The Supplier shall deliver the Goods.\n\
+                    Section 3. Payment\n\
+                    The Buyer shall pay the fees.\n\
+                    Section 3.1 Late Payment\n\
+                    The Buyer shall pay interest.\n\
+                    Section 4. Audit\n\
+                    The Buyer may audit the Supplier.";
+        let doc = ContractDocument::from_text(text)
+            .run_resolver(&SectionHeaderResolver::new())
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default());
+
+        let actions: Vec<&str> = doc
+            .query()
+            .of_type::<Scored<ObligationPhrase>>()
+            .in_section("3")
+            .collect()
+            .into_iter()
+            .map(|found| found.value.value.action.as_str())
+            .collect();
+        assert_eq!(actions, vec!["pay the fees", "pay interest"]);
+
+        let query = doc.query().of_type::<Scored<ObligationPhrase>>();
+        assert_eq!(query.in_section("SECTION:3.1").count(), 1);
+        let query = doc.query().of_type::<Scored<ObligationPhrase>>();
+        assert_eq!(query.in_section("9").count(), 0);
+    }
+
+    #[test]
+    fn test_obligation_density() {
+        use crate::{ContractKeywordResolver, ObligationPhraseResolver};
+        use layered_part_of_speech::POSTagResolver;
+
+        let text = "Section 1. Delivery\n\
+                    The Supplier shall deliver the Goods.\n\
+                    Section 2. Payment\n\
+                    The Buyer shall pay the fees. The Buyer shall pay interest.\n\
+                    The Buyer may audit the Supplier.\n\
+                    Section 2.1 Notices\n\
+                    Notices are sent by email.";
+        let doc = ContractDocument::from_text(text)
+            .run_resolver(&SectionHeaderResolver::new())
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default());
+
+        let mut density = doc.structure().obligation_density(&doc);
+        let counts: Vec<(&str, usize)> = density
+            .iter()
+            .map(|(section, count, _)| (section.canonical_id.as_str(), *count))
+            .collect();
+        assert_eq!(counts, vec![("SECTION:1", 1), ("SECTION:2", 3), ("SECTION:2.1", 0)]);
+        // "Section 1. Delivery" + "The Supplier shall deliver the Goods." = 4 + 7 tokens
+        assert!((density[0].2 - 1.0 / 11.0).abs() < 1e-9);
+        assert_eq!(density[2].2, 0.0);
+
+        density.sort_by(|a, b| b.2.total_cmp(&a.2));
+        assert_eq!(density[0].0.canonical_id, "SECTION:2");
+    }
+
+    #[test]
+    fn test_section_of_obligation() {
+        use crate::{ContractKeywordResolver, ObligationPhraseResolver};
+        use layered_part_of_speech::POSTagResolver;
+
+        let text = "Section 3. Payment\n\
+                    The Buyer shall pay the fees.\n\
+                    Section 3.1 Late Payment\n\
+                    The Buyer shall pay interest.\n\
+                    Section 4. Audit\n\
+                    The Buyer may audit the Supplier.";
+        let doc = ContractDocument::from_text(text)
+            .run_resolver(&SectionHeaderResolver::new())
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default());
+
+        let sections: Vec<(usize, String)> = doc
+            .query()
+            .of_type::<Scored<ObligationPhrase>>()
+            .collect()
+            .into_iter()
+            .map(|found| {
+                let section = doc.section_of(&found.span).expect("obligation is in a section");
+                (found.span.start.line, section.header.identifier.canonical())
+            })
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                (1, "SECTION:3".to_string()),
+                (3, "SECTION:3.1".to_string()),
+                (5, "SECTION:4".to_string()),
+            ]
+        );
+
+        // Spans outside the index are classified on demand
+        let heading = DocSpan::single_line(4, 0, 2);
+        assert_eq!(doc.section_of(&heading).map(|s| s.start_line), Some(4));
+    }
 }
@@ -0,0 +1,213 @@
+//! One punch list of the soft problems found in a document.
+//!
+//! Each analysis pass reports its problems its own way: the structure builder
+//! and section reference linker return them in a `ProcessResult`, the
+//! definition checks return spans and names, the confidence audit flags
+//! annotation types. [`DocumentWarningsExt::warnings`] runs them all and
+//! collects their findings as [`DocumentWarning`]s, so a reviewer reads a
+//! single list.
+
+use std::cmp::Reverse;
+
+use layered_nlp::x;
+
+use crate::definition_extent::{find_definition_cycles, find_inconsistent_definitions};
+use crate::document_structure::DocumentStructureBuilder;
+use crate::obligation::ObligationPhrase;
+use crate::section_reference_linker::SectionReferenceLinker;
+use crate::term_reference::{
+    find_article_inconsistencies, find_capitalization_inconsistencies, TermReferenceResolver,
+};
+use crate::{
+    confidence_audit, ContractDocument, DocSpan, DocumentWarning, ProcessResult, Scored,
+    WarningSeverity,
+};
+
+/// Confidence below which an obligation is listed for review.
+const LOW_OBLIGATION_CONFIDENCE: f64 = 0.5;
+
+/// Document-wide collection of warnings.
+pub trait DocumentWarningsExt {
+    /// Every warning the document's analysis passes raise.
+    ///
+    /// Collects section structure and reference problems (dangling
+    /// references are surfaced as [`WarningSeverity::Error`]), definition
+    /// cycles and conflicting redefinitions, common-word definitions,
+    /// capitalization and article inconsistencies, obligations without a
+    /// responsible party or with low confidence, and annotation types whose
+    /// confidences need calibration. Passes whose resolvers have not run find
+    /// nothing.
+    ///
+    /// Document-wide warnings come first, then located ones in document
+    /// order, most severe first at the same position.
+    fn warnings(&self) -> Vec<DocumentWarning>;
+}
+
+impl DocumentWarningsExt for ContractDocument {
+    fn warnings(&self) -> Vec<DocumentWarning> {
+        let mut collected = ProcessResult::ok(());
+        let structure = collected.merge(DocumentStructureBuilder::build(self));
+        collected.merge(SectionReferenceLinker::link(self, &structure));
+        let ProcessResult { errors, mut warnings, .. } = collected;
+        // A dangling reference is also reported as unresolved; keep the error
+        let errors: Vec<DocumentWarning> = errors.iter().map(DocumentWarning::from_error).collect();
+        warnings.retain(|warning| {
+            warning.span.is_none() || !errors.iter().any(|error| error.span == warning.span)
+        });
+        warnings.extend(errors);
+
+        for cycle in find_definition_cycles(self) {
+            let mut path = cycle.clone();
+            path.push(cycle[0].clone());
+            warnings.push(DocumentWarning::new(
+                WarningSeverity::Warning,
+                format!("definitions refer to each other in a cycle: {}", path.join(" -> ")),
+            ));
+        }
+        for scored in find_inconsistent_definitions(self) {
+            let conflict = scored.value;
+            let message = format!(
+                "'{}' is defined as '{}' here but as '{}' at line {}",
+                conflict.term_name,
+                conflict.conflicting_body,
+                conflict.first_body,
+                conflict.first.start.line
+            );
+            warnings.push(
+                DocumentWarning::new(WarningSeverity::Warning, message).at(conflict.conflicting),
+            );
+        }
+
+        let located = |severity, spans: Vec<(DocSpan, String)>, describe: fn(&str) -> String| {
+            spans
+                .into_iter()
+                .map(move |(span, term)| DocumentWarning::new(severity, describe(&term)).at(span))
+        };
+        warnings.extend(located(
+            WarningSeverity::Info,
+            TermReferenceResolver::new().risky_definitions(self),
+            |term| format!("'{}' is a common word; defining it shadows its ordinary use", term),
+        ));
+        warnings.extend(located(
+            WarningSeverity::Info,
+            find_capitalization_inconsistencies(self),
+            |term| format!("mention of '{}' is not capitalized as defined", term),
+        ));
+        warnings.extend(located(
+            WarningSeverity::Info,
+            find_article_inconsistencies(self),
+            |term| format!("'{}' names a specific party but follows an indefinite article", term),
+        ));
+
+        for (line_idx, line) in self.lines_enumerated() {
+            for found in line.find(&x::attr::<Scored<ObligationPhrase>>()) {
+                let span = DocSpan::from_find(line_idx, &found);
+                let scored = found.attr();
+                if scored.value.has_unknown_obligor() {
+                    warnings.push(
+                        DocumentWarning::new(
+                            WarningSeverity::Warning,
+                            format!("no party is responsible for '{}'", scored.value.action),
+                        )
+                        .at(span),
+                    );
+                } else if scored.confidence < LOW_OBLIGATION_CONFIDENCE {
+                    let message = format!(
+                        "low-confidence obligation ({:.2}): '{}'",
+                        scored.confidence, scored.value.action
+                    );
+                    warnings.push(DocumentWarning::new(WarningSeverity::Info, message).at(span));
+                }
+            }
+        }
+
+        for stats in confidence_audit(self).needs_calibration() {
+            let message = format!(
+                "{} confidences barely vary (std dev {:.3} over {}); scores may be hardcoded",
+                stats.type_name, stats.std_dev, stats.count
+            );
+            warnings.push(DocumentWarning::new(WarningSeverity::Info, message));
+        }
+
+        warnings.sort_by_key(|warning| {
+            let position = warning.span.map(|span| (span.start.line, span.start.token));
+            (position, Reverse(warning.severity))
+        });
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver, ProhibitionResolver,
+        PronounResolver, SectionHeaderResolver, SectionReferenceResolver,
+    };
+    use layered_part_of_speech::POSTagResolver;
+
+    fn analyze(text: &str) -> ContractDocument {
+        ContractDocument::from_text(text)
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&SectionHeaderResolver::new())
+            .run_resolver(&SectionReferenceResolver::new())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ProhibitionResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+            .run_resolver(&TermReferenceResolver::default())
+            .run_resolver(&PronounResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default())
+    }
+
+    #[test]
+    fn test_warnings_collect_every_pass() {
+        let doc = analyze(
+            "Section 1. Delivery\n\
+             ABC Corp (the \"Company\") shall deliver the goods under Section 9.\n\
+             The fees shall be paid.\n\
+             The company shall notify the Buyer.",
+        );
+        let warnings = doc.warnings();
+        let messages: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+
+        let dangling = warnings
+            .iter()
+            .find(|w| w.severity == WarningSeverity::Error)
+            .unwrap_or_else(|| panic!("no dangling reference in {:#?}", messages));
+        assert!(dangling.message.contains("Section 9"), "{}", dangling);
+        assert_eq!(dangling.span.map(|span| span.start.line), Some(1));
+        let at_reference = warnings.iter().filter(|w| w.span == dangling.span).count();
+        assert_eq!(at_reference, 1, "{:#?}", messages);
+
+        assert!(
+            warnings.iter().any(|w| w.message.starts_with("no party is responsible")
+                && w.span.map(|span| span.start.line) == Some(2)),
+            "{:#?}",
+            messages
+        );
+        assert!(
+            warnings.iter().any(|w| w.message.contains("not capitalized as defined")
+                && w.span.map(|span| span.start.line) == Some(3)),
+            "{:#?}",
+            messages
+        );
+
+        let positions: Vec<_> = warnings
+            .iter()
+            .map(|w| w.span.map(|span| (span.start.line, span.start.token)))
+            .collect();
+        let mut sorted = positions.clone();
+        sorted.sort();
+        assert_eq!(positions, sorted);
+    }
+
+    #[test]
+    fn test_plain_document_warns_only_about_structure() {
+        let doc = analyze("ABC Corp (the \"Company\") shall deliver the goods.");
+        let warnings = doc.warnings();
+        assert_eq!(warnings.len(), 1, "{:#?}", warnings);
+        assert_eq!(warnings[0].severity, WarningSeverity::Info);
+        assert!(warnings[0].message.contains("No section headers"));
+        assert_eq!(warnings[0].span, None);
+    }
+}
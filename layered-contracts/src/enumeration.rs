@@ -0,0 +1,671 @@
+//! Detection of "including without limitation" enumerations.
+//!
+//! Definitions and obligations frequently enumerate their members:
+//!
+//! ```text
+//! "Confidential Information" includes, without limitation, (a) trade secrets,
+//! (b) customer lists, including (i) names and (ii) addresses, and (c) pricing.
+//! ```
+//!
+//! [`EnumerationResolver`] finds the head phrase ("includes, without limitation"),
+//! then collects the marked list items that follow it. Items may be lettered,
+//! roman, numbered or bulleted, inline or one per line. Markers of a different
+//! style inside an item are recorded as that item's sub-items.
+//!
+//! Which markers count is set by a [`ListMarkerConfig`], shared with the
+//! list-obligation distribution of [`crate::ListObligationResolver`]. It tells
+//! ordered markers ("(a)", "1.", "iv.") from unordered bullets ("•", "—"), so
+//! ordered lists can be checked for numbering with [`ListMarker::follows`].
+
+use layered_nlp::{LLLine, LToken, TextTag};
+use layered_nlp_document::{DocumentResolver, LayeredDocument};
+
+use crate::{DocPosition, DocSpan};
+
+/// An enumeration introduced by a head phrase such as "including".
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enumeration {
+    /// The head phrase ("including, without limitation")
+    pub head_span: DocSpan,
+    /// Top-level items without their own marker and trailing separators; an
+    /// item's span covers its nested sub-items
+    pub items: Vec<DocSpan>,
+    /// Nested items: `sub_items[i]` are the sub-items found inside `items[i]`
+    pub sub_items: Vec<Vec<DocSpan>>,
+    /// False when the head disclaims exhaustiveness ("without limitation",
+    /// "but not limited to")
+    pub is_exhaustive: bool,
+}
+
+/// Marker styles recognized as list items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkerStyle {
+    /// (a), (b), (c)
+    Letter,
+    /// (i), (ii), (iii)
+    Roman,
+    /// (1), (2), (3)
+    Digit,
+    /// 1., 2., 3. at the start of a line
+    Numbered,
+    /// a., b., c. at the start of a line
+    DottedLetter,
+    /// i., ii., iii. at the start of a line
+    DottedRoman,
+    /// One of the configured bullet symbols at the start of a line
+    Bullet,
+}
+
+impl MarkerStyle {
+    /// Whether items of this style are counted rather than bulleted.
+    pub fn is_ordered(self) -> bool {
+        self != MarkerStyle::Bullet
+    }
+}
+
+/// A list marker found at the start of an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListMarker {
+    pub style: MarkerStyle,
+    /// Position in its list ("(c)" = 3, "iv." = 4); 0 for bullets
+    pub ordinal: u32,
+    /// Number of (non-space) tokens the marker occupies
+    len: usize,
+}
+
+impl ListMarker {
+    /// Whether this marker continues a list whose previous item had
+    /// `previous`: same style and, for ordered styles, the next ordinal.
+    pub fn follows(&self, previous: &ListMarker) -> bool {
+        self.style == previous.style
+            && (!self.style.is_ordered() || self.ordinal == previous.ordinal + 1)
+    }
+}
+
+/// The list markers that open an item.
+///
+/// By default: parenthesized letters, roman numerals and digits anywhere,
+/// and "1." or one of "•", "-", "*", "·" at the start of a line. Dotted
+/// letters and roman numerals ("a.", "iv.") are off by default, since a line
+/// may well start with a word followed by a period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListMarkerConfig {
+    /// Ordered styles recognized
+    ordered: Vec<MarkerStyle>,
+    /// Symbols, each a single token, that open an unordered item
+    bullets: Vec<String>,
+}
+
+impl Default for ListMarkerConfig {
+    fn default() -> Self {
+        Self {
+            ordered: vec![
+                MarkerStyle::Letter,
+                MarkerStyle::Roman,
+                MarkerStyle::Digit,
+                MarkerStyle::Numbered,
+            ],
+            bullets: ["•", "-", "*", "·"].iter().map(|b| b.to_string()).collect(),
+        }
+    }
+}
+
+impl ListMarkerConfig {
+    /// Create a config recognizing the default markers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also recognize an ordered style.
+    pub fn with_ordered(mut self, style: MarkerStyle) -> Self {
+        if style.is_ordered() && !self.ordered.contains(&style) {
+            self.ordered.push(style);
+        }
+        self
+    }
+
+    /// Stop recognizing an ordered style.
+    pub fn without_ordered(mut self, style: MarkerStyle) -> Self {
+        self.ordered.retain(|&s| s != style);
+        self
+    }
+
+    /// Also recognize a bullet symbol, such as "—" or "–".
+    pub fn with_bullet(mut self, symbol: &str) -> Self {
+        if !self.bullets.iter().any(|b| b == symbol) {
+            self.bullets.push(symbol.to_string());
+        }
+        self
+    }
+
+    /// Replace the recognized bullet symbols.
+    pub fn with_bullets(mut self, symbols: &[&str]) -> Self {
+        self.bullets = symbols.iter().map(|b| b.to_string()).collect();
+        self
+    }
+
+    /// Whether markers of `style` are recognized.
+    pub fn recognizes(&self, style: MarkerStyle) -> bool {
+        match style {
+            MarkerStyle::Bullet => !self.bullets.is_empty(),
+            _ => self.ordered.contains(&style),
+        }
+    }
+
+    /// If `line` starts with a list marker, returns it with the index of the
+    /// first token after it.
+    pub fn item_start(&self, line: &LLLine) -> Option<(ListMarker, usize)> {
+        let mut line_start = true;
+        let mut tokens = Vec::new();
+        for (token_index, token) in line.ll_tokens().iter().enumerate() {
+            if let LToken::Text(text, tag) = token.get_token() {
+                if *tag == TextTag::SPACE {
+                    continue;
+                }
+                tokens.push(Tok { line: 0, token: token_index, text, tag: tag.clone(), line_start });
+                line_start = false;
+                if tokens.len() == 3 {
+                    break;
+                }
+            }
+        }
+        let marker = self.parse(&tokens, 0, None)?;
+        let last = tokens.get(marker.len - 1)?;
+        Some((marker, last.token + 1))
+    }
+
+    /// Parses a list marker starting at `k`.
+    ///
+    /// `expected_letter` resolves "(i)"/"(v)"/"(x)": they are letters when they
+    /// continue a lettered list, roman numerals otherwise.
+    fn parse(&self, tokens: &[Tok], k: usize, expected_letter: Option<u32>) -> Option<ListMarker> {
+        let tok = tokens.get(k)?;
+
+        if tok.text == "(" {
+            let content = tokens.get(k + 1)?;
+            if tokens.get(k + 2)?.text != ")" {
+                return None;
+            }
+            let lower = content.text.to_lowercase();
+            let (style, ordinal) = match self.letter_or_roman(
+                &lower,
+                expected_letter,
+                MarkerStyle::Letter,
+                MarkerStyle::Roman,
+            ) {
+                Some(found) => found,
+                None => (MarkerStyle::Digit, content.text.parse().ok()?),
+            };
+            return self.marker(style, ordinal, 3);
+        }
+
+        if !tok.line_start {
+            return None;
+        }
+        let dotted = tokens.get(k + 1).map(|t| t.text) == Some(".");
+        if dotted && tok.tag == TextTag::NATN {
+            return self.marker(MarkerStyle::Numbered, tok.text.parse().ok()?, 2);
+        }
+        if dotted && tok.tag == TextTag::WORD {
+            let (style, ordinal) = self.letter_or_roman(
+                &tok.text.to_lowercase(),
+                expected_letter,
+                MarkerStyle::DottedLetter,
+                MarkerStyle::DottedRoman,
+            )?;
+            return self.marker(style, ordinal, 2);
+        }
+        if self.bullets.iter().any(|b| b == tok.text) {
+            return self.marker(MarkerStyle::Bullet, 0, 1);
+        }
+        None
+    }
+
+    /// Reads `text` as a letter or roman numeral of the recognized styles.
+    fn letter_or_roman(
+        &self,
+        text: &str,
+        expected_letter: Option<u32>,
+        letter_style: MarkerStyle,
+        roman_style: MarkerStyle,
+    ) -> Option<(MarkerStyle, u32)> {
+        let letter = letter_ordinal(text).filter(|_| self.recognizes(letter_style));
+        let roman = roman_value(text).filter(|_| self.recognizes(roman_style));
+        match (letter, roman) {
+            (Some(ord), _) if Some(ord) == expected_letter => Some((letter_style, ord)),
+            (_, Some(value)) => Some((roman_style, value)),
+            (Some(ord), None) => Some((letter_style, ord)),
+            (None, None) => None,
+        }
+    }
+
+    fn marker(&self, style: MarkerStyle, ordinal: u32, len: usize) -> Option<ListMarker> {
+        self.recognizes(style).then_some(ListMarker { style, ordinal, len })
+    }
+}
+
+/// A non-whitespace token with its document position.
+struct Tok<'a> {
+    line: usize,
+    token: usize,
+    text: &'a str,
+    tag: TextTag,
+    line_start: bool,
+}
+
+/// Separators and conjunctions trimmed from the end of an item.
+pub(crate) const TRAILING_SEPARATORS: &[&str] = &[",", ";", ":", ".", "and", "or"];
+
+/// Detects enumerations following "including", "includes", "include" and
+/// "consisting of".
+///
+/// Produces document-level [`Enumeration`] attributes. Enumerations without
+/// list markers ("including X, Y and Z") are not reported.
+#[derive(Debug, Default)]
+pub struct EnumerationResolver {
+    markers: ListMarkerConfig,
+}
+
+impl EnumerationResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the list markers that open an item.
+    pub fn with_markers(mut self, markers: ListMarkerConfig) -> Self {
+        self.markers = markers;
+        self
+    }
+
+    /// Matches a head phrase at `i`, returning (index of last head token,
+    /// is_exhaustive).
+    fn match_head(tokens: &[Tok], i: usize) -> Option<(usize, bool)> {
+        let word = |idx: usize| tokens.get(idx).map(|t| t.text.to_lowercase());
+        let matches_seq = |start: usize, seq: &[&str]| {
+            seq.iter()
+                .enumerate()
+                .all(|(offset, expected)| word(start + offset).as_deref() == Some(*expected))
+        };
+
+        match word(i)?.as_str() {
+            "including" | "includes" | "include" => {
+                let mut next = i + 1;
+                if word(next).as_deref() == Some(",") {
+                    next += 1;
+                }
+                if matches_seq(next, &["without", "limitation"]) {
+                    Some((next + 1, false))
+                } else if matches_seq(next, &["but", "not", "limited", "to"]) {
+                    Some((next + 3, false))
+                } else {
+                    Some((i, true))
+                }
+            }
+            "consisting" if matches_seq(i + 1, &["of"]) => Some((i + 1, true)),
+            _ => None,
+        }
+    }
+
+    /// Builds an item span from `start..=end`, trimming trailing separators.
+    fn item_span(tokens: &[Tok], start: usize, end: usize) -> Option<DocSpan> {
+        let mut end = end;
+        while end >= start
+            && TRAILING_SEPARATORS.contains(&tokens[end].text.to_lowercase().as_str())
+        {
+            if end == 0 {
+                return None;
+            }
+            end -= 1;
+        }
+        if end < start {
+            return None;
+        }
+        Some(DocSpan::new(
+            DocPosition::new(tokens[start].line, tokens[start].token),
+            DocPosition::new(tokens[end].line, tokens[end].token),
+        ))
+    }
+
+    /// Parses the items following a head ending at `head_end`.
+    ///
+    /// Returns the enumeration's items, sub-items and the index of its last token.
+    #[allow(clippy::type_complexity)]
+    fn parse_items(
+        &self,
+        tokens: &[Tok],
+        head_end: usize,
+    ) -> Option<(Vec<DocSpan>, Vec<Vec<DocSpan>>, usize)> {
+        // Skip separators and "the following" between the head and the first marker
+        let mut k = head_end + 1;
+        while let Some(tok) = tokens.get(k) {
+            match tok.text.to_lowercase().as_str() {
+                "," | ":" | "the" | "following" => k += 1,
+                _ => break,
+            }
+        }
+
+        let first = self.markers.parse(tokens, k, Some(1))?;
+        let top_style = first.style;
+        let line_layout = tokens[k].line_start;
+        let mut next_top = first.ordinal + 1;
+        let mut sub_style: Option<MarkerStyle> = None;
+        let mut next_sub = 1;
+
+        let mut items = Vec::new();
+        let mut sub_items: Vec<Vec<DocSpan>> = Vec::new();
+        let mut item_start = k + first.len;
+        let mut sub_start: Option<usize> = None;
+        let mut current_subs = Vec::new();
+
+        let mut pos = item_start;
+        let mut last = tokens.len() - 1;
+        while pos < tokens.len() {
+            let lettered = |style: MarkerStyle| {
+                matches!(style, MarkerStyle::Letter | MarkerStyle::DottedLetter)
+            };
+            let expected_letter = if lettered(top_style) {
+                Some(next_top)
+            } else if sub_style.is_none_or(lettered) {
+                Some(next_sub)
+            } else {
+                None
+            };
+
+            if let Some(marker) = self.markers.parse(tokens, pos, expected_letter) {
+                if let Some(start) = sub_start.take() {
+                    current_subs.extend(Self::item_span(tokens, start, pos - 1));
+                }
+                if marker.style == top_style {
+                    let subs = std::mem::take(&mut current_subs);
+                    if let Some(item) = Self::item_span(tokens, item_start, pos - 1) {
+                        items.push(item);
+                        sub_items.push(subs);
+                    }
+                    item_start = pos + marker.len;
+                    next_top = marker.ordinal + 1;
+                    sub_style = None;
+                    next_sub = 1;
+                } else {
+                    sub_style.get_or_insert(marker.style);
+                    sub_start = Some(pos + marker.len);
+                    next_sub = marker.ordinal + 1;
+                }
+                pos += marker.len;
+                continue;
+            }
+
+            let tok = &tokens[pos];
+            if line_layout && tok.line_start && pos > item_start {
+                // A line without a marker ends a one-item-per-line list
+                last = pos - 1;
+                break;
+            }
+            if tok.text == "." {
+                let continues = tokens.get(pos + 1).is_some_and(|next| {
+                    next.line_start
+                        && self
+                            .markers
+                            .parse(tokens, pos + 1, Some(next_top))
+                            .is_some_and(|m| m.style == top_style)
+                });
+                if !continues {
+                    last = pos;
+                    break;
+                }
+            }
+            pos += 1;
+        }
+
+        if let Some(start) = sub_start {
+            current_subs.extend(Self::item_span(tokens, start, last));
+        }
+        // An empty item is skipped together with its sub-items, keeping the two in step
+        if let Some(item) = Self::item_span(tokens, item_start, last) {
+            items.push(item);
+            sub_items.push(current_subs);
+        }
+
+        if items.is_empty() {
+            None
+        } else {
+            Some((items, sub_items, last))
+        }
+    }
+}
+
+impl DocumentResolver for EnumerationResolver {
+    type Attr = Enumeration;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        let mut tokens = Vec::new();
+        for (line_index, line) in doc.lines().iter().enumerate() {
+            let mut line_start = true;
+            for (token_index, token) in line.ll_tokens().iter().enumerate() {
+                if let LToken::Text(text, tag) = token.get_token() {
+                    if *tag == TextTag::SPACE {
+                        continue;
+                    }
+                    tokens.push(Tok {
+                        line: line_index,
+                        token: token_index,
+                        text: text.as_str(),
+                        tag: tag.clone(),
+                        line_start,
+                    });
+                    line_start = false;
+                }
+            }
+        }
+
+        let mut results = Vec::new();
+        let mut i = 0;
+        while i < tokens.len() {
+            let parsed = Self::match_head(&tokens, i).and_then(|(head_end, is_exhaustive)| {
+                self.parse_items(&tokens, head_end)
+                    .map(|(items, sub_items, last)| (head_end, is_exhaustive, items, sub_items, last))
+            });
+
+            match parsed {
+                Some((head_end, is_exhaustive, items, sub_items, last)) => {
+                    results.push(Enumeration {
+                        head_span: DocSpan::new(
+                            DocPosition::new(tokens[i].line, tokens[i].token),
+                            DocPosition::new(tokens[head_end].line, tokens[head_end].token),
+                        ),
+                        items,
+                        sub_items,
+                        is_exhaustive,
+                    });
+                    i = last + 1;
+                }
+                None => i += 1,
+            }
+        }
+
+        results
+    }
+}
+
+/// Ordinal of a single lowercase letter ("a" = 1).
+fn letter_ordinal(text: &str) -> Option<u32> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_lowercase() => Some(c as u32 - 'a' as u32 + 1),
+        _ => None,
+    }
+}
+
+/// Value of a lowercase roman numeral up to xxxix.
+fn roman_value(text: &str) -> Option<u32> {
+    if text.is_empty() {
+        return None;
+    }
+    let digit = |c: char| match c {
+        'i' => Some(1),
+        'v' => Some(5),
+        'x' => Some(10),
+        _ => None,
+    };
+    let values: Vec<u32> = text.chars().map(digit).collect::<Option<_>>()?;
+    let mut total = 0;
+    for (idx, &value) in values.iter().enumerate() {
+        if values.get(idx + 1).is_some_and(|&next| next > value) {
+            total -= value as i32;
+        } else {
+            total += value as i32;
+        }
+    }
+    if total > 0 && total < 40 {
+        Some(total as u32)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ContractDocument;
+
+    fn enumerations(text: &str) -> (ContractDocument, Vec<Enumeration>) {
+        let doc = ContractDocument::from_text(text);
+        let found = EnumerationResolver::new().resolve(&doc);
+        (doc, found)
+    }
+
+    fn span_text(doc: &ContractDocument, span: &DocSpan) -> String {
+        (span.start.line..=span.end.line)
+            .map(|line_index| {
+                let tokens = doc.lines()[line_index].ll_tokens();
+                let from = if line_index == span.start.line { span.start.token } else { 0 };
+                let to = if line_index == span.end.line { span.end.token } else { tokens.len() - 1 };
+                tokens[from..=to]
+                    .iter()
+                    .filter_map(|t| match t.get_token() {
+                        LToken::Text(text, _) => Some(text.as_str()),
+                        LToken::Value => None,
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_inline_lettered_non_exhaustive() {
+        let (doc, found) = enumerations(
+            "Services include, without limitation, (a) hosting, (b) support, and (c) training.",
+        );
+
+        assert_eq!(found.len(), 1);
+        let enumeration = &found[0];
+        assert!(!enumeration.is_exhaustive);
+        assert_eq!(span_text(&doc, &enumeration.head_span), "include, without limitation");
+        let items: Vec<_> = enumeration.items.iter().map(|s| span_text(&doc, s)).collect();
+        assert_eq!(items, vec!["hosting", "support", "training"]);
+    }
+
+    #[test]
+    fn test_but_not_limited_to_and_exhaustive_heads() {
+        let (_, found) = enumerations("Fees including but not limited to (1) setup and (2) renewal.");
+        assert_eq!(found.len(), 1);
+        assert!(!found[0].is_exhaustive);
+        assert_eq!(found[0].items.len(), 2);
+
+        let (_, found) = enumerations("The Deliverables consisting of (a) code and (b) documentation.");
+        assert_eq!(found.len(), 1);
+        assert!(found[0].is_exhaustive);
+    }
+
+    #[test]
+    fn test_nested_sub_items() {
+        let (doc, found) = enumerations(
+            "Data including (a) customer lists, including (i) names and (ii) addresses, \
+             (h) pricing and (i) forecasts.",
+        );
+
+        assert_eq!(found.len(), 1);
+        let enumeration = &found[0];
+        let items: Vec<_> = enumeration.items.iter().map(|s| span_text(&doc, s)).collect();
+        // "(h)" breaks the expected sequence but is still lettered; "(i)" after "(h)" is a letter
+        assert_eq!(
+            items,
+            vec!["customer lists, including (i) names and (ii) addresses", "pricing", "forecasts"]
+        );
+        let subs: Vec<_> = enumeration.sub_items[0].iter().map(|s| span_text(&doc, s)).collect();
+        assert_eq!(subs, vec!["names", "addresses"]);
+        assert!(enumeration.sub_items[1].is_empty());
+    }
+
+    #[test]
+    fn test_empty_item_is_skipped_with_its_sub_items() {
+        let (doc, found) = enumerations(
+            "Data including (a) (b) customer lists, including (i) names and (ii) addresses, \
+             and (c) pricing.",
+        );
+
+        assert_eq!(found.len(), 1);
+        let enumeration = &found[0];
+        assert_eq!(enumeration.items.len(), enumeration.sub_items.len());
+        let items: Vec<_> = enumeration.items.iter().map(|s| span_text(&doc, s)).collect();
+        assert_eq!(items, vec!["customer lists, including (i) names and (ii) addresses", "pricing"]);
+        let subs: Vec<_> = enumeration.sub_items[0].iter().map(|s| span_text(&doc, s)).collect();
+        assert_eq!(subs, vec!["names", "addresses"]);
+        assert!(enumeration.sub_items[1].is_empty());
+    }
+
+    #[test]
+    fn test_line_per_item_lists() {
+        let text = "The Supplier shall provide the following, including without limitation:\n\
+                    1. installation services;\n\
+                    2. maintenance, including\n\
+                    - patches\n\
+                    - upgrades\n\
+                    The Customer shall pay the fees.";
+        let (doc, found) = enumerations(text);
+
+        assert_eq!(found.len(), 1);
+        let enumeration = &found[0];
+        let items: Vec<_> = enumeration.items.iter().map(|s| span_text(&doc, s)).collect();
+        assert_eq!(items, vec!["installation services", "maintenance, including - patches - upgrades"]);
+        let subs: Vec<_> = enumeration.sub_items[1].iter().map(|s| span_text(&doc, s)).collect();
+        assert_eq!(subs, vec!["patches", "upgrades"]);
+        assert_eq!(enumeration.items[1].start.line, 2);
+    }
+
+    #[test]
+    fn test_unmarked_enumeration_is_ignored() {
+        let (_, found) = enumerations("Costs including travel, lodging and meals.");
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_roman_value() {
+        assert_eq!(roman_value("i"), Some(1));
+        assert_eq!(roman_value("iv"), Some(4));
+        assert_eq!(roman_value("xix"), Some(19));
+        assert_eq!(roman_value("a"), None);
+    }
+
+    #[test]
+    fn test_configured_markers() {
+        let text = "Fees including:\n\
+                    a. setup;\n\
+                    b. renewal.";
+        let (_, found) = enumerations(text);
+        assert!(found.is_empty());
+
+        let doc = ContractDocument::from_text(text);
+        let markers = ListMarkerConfig::new().with_ordered(MarkerStyle::DottedLetter);
+        let found = EnumerationResolver::new().with_markers(markers).resolve(&doc);
+        assert_eq!(found.len(), 1);
+        let items: Vec<_> = found[0].items.iter().map(|s| span_text(&doc, s)).collect();
+        assert_eq!(items, vec!["setup", "renewal"]);
+
+        // Without the parenthesized letter style "(a)" is not a marker
+        let markers = ListMarkerConfig::new().without_ordered(MarkerStyle::Letter);
+        let (doc, _) = enumerations("Services include (a) hosting and (b) support.");
+        let found = EnumerationResolver::new().with_markers(markers).resolve(&doc);
+        assert!(found.is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+//! Governing-law clauses.
+//!
+//! ```text
+//! This Agreement shall be governed by and construed in accordance with the
+//! laws of the State of New York, without regard to its conflict of laws rules.
+//! ```
+//!
+//! [`GoverningLawResolver`] annotates the "governed by ... the laws of X" phrase
+//! with the named jurisdiction. A contract should have exactly one; the
+//! `ConflictDetector` reports documents whose governing-law clauses disagree.
+
+use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver};
+
+use crate::Scored;
+
+/// Words between "governed by" and "laws of" ("and construed in accordance
+/// with the").
+const MAX_GAP_WORDS: usize = 8;
+
+/// Words allowed in a jurisdiction name.
+const MAX_JURISDICTION_WORDS: usize = 8;
+
+/// A governing-law clause naming the law that governs the contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoverningLaw {
+    /// The jurisdiction as written ("the State of New York")
+    pub jurisdiction: String,
+}
+
+impl GoverningLaw {
+    /// The jurisdiction for comparison: lowercased, without a leading "the"
+    /// or "State of" / "Commonwealth of", so "the State of New York" and
+    /// "New York" compare equal.
+    pub fn normalized_jurisdiction(&self) -> String {
+        let mut words: Vec<String> = self
+            .jurisdiction
+            .split_whitespace()
+            .map(|word| word.to_lowercase())
+            .collect();
+        if words.first().is_some_and(|word| word == "the") {
+            words.remove(0);
+        }
+        if words.len() > 2
+            && matches!(words[0].as_str(), "state" | "commonwealth" | "province")
+            && words[1] == "of"
+        {
+            words.drain(..2);
+        }
+        words.join(" ")
+    }
+}
+
+/// Per-line resolver for "governed by ... the laws of X" clauses.
+///
+/// The jurisdiction runs from "of" to the first punctuation mark or lowercase
+/// word other than "of", "the" or "and" ("New York, without regard ..." gives
+/// "New York").
+#[derive(Debug, Clone)]
+pub struct GoverningLawResolver {
+    confidence: f64,
+}
+
+impl Default for GoverningLawResolver {
+    fn default() -> Self {
+        Self { confidence: 0.9 }
+    }
+}
+
+impl GoverningLawResolver {
+    /// Create a new resolver with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The next non-whitespace token after `sel`, extending `sel` over it.
+    fn next_token(sel: &LLSelection) -> Option<(LLSelection, String)> {
+        let sel = match sel.match_first_forwards(&x::whitespace()) {
+            Some((with_space, _)) => with_space,
+            None => sel.clone(),
+        };
+        let (next, text) = sel.match_first_forwards(&x::token_text())?;
+        Some((next, text.to_string()))
+    }
+
+    /// Match "by ... law(s) of <jurisdiction>" following "governed".
+    fn match_clause(governed: &LLSelection) -> Option<(LLSelection, String)> {
+        let (mut current, by) = Self::next_token(governed)?;
+        if !by.eq_ignore_ascii_case("by") {
+            return None;
+        }
+
+        let mut gap = 0;
+        loop {
+            let (next, word) = Self::next_token(&current)?;
+            current = next;
+            let word = word.to_lowercase();
+            if word == "laws" || word == "law" {
+                break;
+            }
+            gap += 1;
+            if gap > MAX_GAP_WORDS || word.chars().all(|c| c.is_ascii_punctuation()) {
+                return None;
+            }
+        }
+        let (after_of, of) = Self::next_token(&current)?;
+        if !of.eq_ignore_ascii_case("of") {
+            return None;
+        }
+
+        let mut words: Vec<String> = Vec::new();
+        let mut end = None;
+        let mut cursor = after_of;
+        while words.len() < MAX_JURISDICTION_WORDS {
+            let (next, word) = match Self::next_token(&cursor) {
+                Some(found) => found,
+                None => break,
+            };
+            let connector = matches!(word.to_lowercase().as_str(), "of" | "the" | "and");
+            let capitalized = word.chars().next().is_some_and(|c| c.is_uppercase());
+            if !connector && !capitalized {
+                break;
+            }
+            words.push(word);
+            if !connector {
+                end = Some((next.clone(), words.len()));
+            }
+            cursor = next;
+        }
+
+        // Trailing connectors ("New York and") are not part of the name
+        let (end, len) = end?;
+        Some((end, words[..len].join(" ")))
+    }
+}
+
+impl Resolver for GoverningLawResolver {
+    type Attr = Scored<GoverningLaw>;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::token_text())
+            .into_iter()
+            .filter(|(_, text)| text.eq_ignore_ascii_case("governed"))
+            .filter_map(|(governed, _)| Self::match_clause(&governed))
+            .map(|(sel, jurisdiction)| {
+                sel.finish_with_attr(Scored::rule_based(
+                    GoverningLaw { jurisdiction },
+                    self.confidence,
+                    "governing_law",
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use layered_nlp::create_line_from_string;
+
+    fn governing_laws(text: &str) -> Vec<GoverningLaw> {
+        let line = create_line_from_string(text).run(&GoverningLawResolver::new());
+        line.find(&x::attr::<Scored<GoverningLaw>>())
+            .into_iter()
+            .map(|found| found.attr().value.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_governing_law_jurisdiction() {
+        let found = governing_laws(
+            "This Agreement shall be governed by and construed in accordance with the laws \
+             of the State of New York, without regard to its conflict of laws rules.",
+        );
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].jurisdiction, "the State of New York");
+        assert_eq!(found[0].normalized_jurisdiction(), "new york");
+
+        let found = governing_laws("This Agreement is governed by the laws of England and Wales.");
+        assert_eq!(found[0].jurisdiction, "England and Wales");
+    }
+
+    #[test]
+    fn test_not_a_governing_law_clause() {
+        assert!(governing_laws("The Supplier is governed by its board.").is_empty());
+        let no_clause = governing_laws("Each party shall comply with the laws of each country.");
+        assert!(no_clause.is_empty());
+    }
+}
@@ -0,0 +1,265 @@
+//! Incorporation by reference.
+//!
+//! ```text
+//! Section 1. Confidentiality
+//! The Recipient shall keep the Information confidential.
+//! Section 2. Affiliates
+//! The provisions of Section 1 shall apply mutatis mutandis to each Affiliate.
+//! ```
+//!
+//! The clause in Section 2 carries no obligation of its own; it borrows the
+//! terms of Section 1. [`IncorporationResolver`] detects such phrases and links
+//! them to the referenced section with [`ClauseRole::IncorporatesFrom`], and
+//! [`Incorporation::incorporated_obligations`] expands the borrowed terms.
+
+use layered_nlp::{x, LToken, TextTag};
+use layered_nlp_document::{ClauseRole, DocSpanLink, DocumentResolver, LayeredDocument};
+
+use crate::document_structure::DocumentStructureExt;
+use crate::obligation::ObligationPhrase;
+use crate::section_reference_linker::{ReferenceResolution, SectionReferenceLinker};
+use crate::{DocSpan, Scored};
+
+/// How another section's terms are brought in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncorporationKind {
+    /// "shall apply mutatis mutandis" - applied with the necessary changes
+    MutatisMutandis,
+    /// "are incorporated herein by reference" - applied as written
+    ByReference,
+}
+
+/// A clause incorporating the terms of another section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Incorporation {
+    /// The incorporating phrase ("mutatis mutandis", "incorporated ... by reference")
+    pub anchor: DocSpan,
+    /// Link to the incorporated section's content, with role `IncorporatesFrom`
+    pub link: DocSpanLink<ClauseRole>,
+    /// The section reference naming the incorporated section
+    pub reference_span: DocSpan,
+    /// Canonical identifier of the incorporated section
+    pub section: String,
+    /// How the terms are incorporated
+    pub kind: IncorporationKind,
+}
+
+impl Incorporation {
+    /// Obligations found in the incorporated section, with their spans.
+    ///
+    /// For [`IncorporationKind::MutatisMutandis`] the obligations are returned
+    /// as written; the "necessary changes" (usually substituting parties) are
+    /// left to the caller.
+    pub fn incorporated_obligations(
+        &self,
+        doc: &LayeredDocument,
+    ) -> Vec<(DocSpan, Scored<ObligationPhrase>)> {
+        let target = self.link.target;
+        let mut obligations = Vec::new();
+        for line_idx in target.start.line..=target.end.line {
+            let line = match doc.lines().get(line_idx) {
+                Some(line) => line,
+                None => break,
+            };
+            for found in line.find(&x::attr::<Scored<ObligationPhrase>>()) {
+                let span = DocSpan::from_find(line_idx, &found);
+                if target.contains_position(&span.start) {
+                    obligations.push((span, (*found.attr()).clone()));
+                }
+            }
+        }
+        obligations
+    }
+}
+
+/// Document-level resolver for incorporation-by-reference phrases.
+///
+/// Recognizes "mutatis mutandis" and "incorporated ... by reference" and pairs
+/// each phrase with the nearest resolved section reference on the same line,
+/// preferring one that precedes the phrase.
+///
+/// Requires `SectionHeaderResolver` and `SectionReferenceResolver` to have run
+/// on the document's lines.
+#[derive(Debug, Clone, Default)]
+pub struct IncorporationResolver;
+
+impl IncorporationResolver {
+    /// Create a new resolver.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Incorporation phrases on a line as (start, end, kind), from lowercased words.
+    fn phrases(words: &[(usize, String)]) -> Vec<(usize, usize, IncorporationKind)> {
+        let mut phrases = Vec::new();
+        for (i, (start, word)) in words.iter().enumerate() {
+            match word.as_str() {
+                "mutatis" => {
+                    let next = words.get(i + 1).filter(|(_, next)| next == "mutandis");
+                    if let Some((end, _)) = next {
+                        phrases.push((*start, *end, IncorporationKind::MutatisMutandis));
+                    }
+                }
+                // "incorporated herein by reference", "incorporated into this
+                // Agreement by reference"
+                "incorporated" => {
+                    let by_reference = (i + 1..words.len().min(i + 7)).find(|&j| {
+                        words[j].1 == "by"
+                            && words.get(j + 1).is_some_and(|(_, w)| w == "reference")
+                    });
+                    if let Some(j) = by_reference {
+                        phrases.push((*start, words[j + 1].0, IncorporationKind::ByReference));
+                    }
+                }
+                _ => {}
+            }
+        }
+        phrases
+    }
+}
+
+impl DocumentResolver for IncorporationResolver {
+    type Attr = Scored<Incorporation>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        let structure = doc.structure();
+        let linked = SectionReferenceLinker::link(doc, structure).value;
+        let mut results = Vec::new();
+
+        for (line_idx, line) in doc.lines_enumerated() {
+            let words: Vec<(usize, String)> = line
+                .ll_tokens()
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, token)| match token.get_token() {
+                    LToken::Text(text, TextTag::WORD) => Some((idx, text.to_lowercase())),
+                    _ => None,
+                })
+                .collect();
+
+            for (start, end, kind) in Self::phrases(&words) {
+                // Only references resolving to a section of the document can be
+                // incorporated; the nearest of those wins
+                let references = linked
+                    .resolved
+                    .iter()
+                    .filter(|r| r.line == line_idx)
+                    .filter_map(|r| match &r.resolution {
+                        ReferenceResolution::Resolved { canonical, confidence, .. } => {
+                            let section = structure.find_by_canonical(canonical)?;
+                            Some((r, canonical, *confidence, section))
+                        }
+                        _ => None,
+                    });
+                let nearest = references.min_by_key(|(r, ..)| {
+                    let ref_start = r.location.start.token;
+                    // Preceding references first, then following, each by distance
+                    if ref_start < start {
+                        (0, start - ref_start)
+                    } else {
+                        (1, ref_start - start)
+                    }
+                });
+                let Some((reference, canonical, confidence, section)) = nearest else {
+                    continue;
+                };
+
+                results.push(Scored::rule_based(
+                    Incorporation {
+                        anchor: DocSpan::single_line(line_idx, start, end),
+                        link: DocSpanLink::new(ClauseRole::IncorporatesFrom, section.content_span),
+                        reference_span: reference.location,
+                        section: canonical.clone(),
+                        kind,
+                    },
+                    confidence,
+                    "incorporation_by_reference",
+                ));
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractDocument, ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver,
+        ProhibitionResolver, PronounResolver, SectionHeaderResolver, SectionReferenceResolver,
+        TermReferenceResolver,
+    };
+    use layered_part_of_speech::POSTagResolver;
+
+    fn document(text: &str) -> ContractDocument {
+        ContractDocument::from_text(text)
+            .run_resolver(&SectionHeaderResolver::new())
+            .run_resolver(&SectionReferenceResolver::new())
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ProhibitionResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+            .run_resolver(&TermReferenceResolver::default())
+            .run_resolver(&PronounResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default())
+    }
+
+    #[test]
+    fn test_mutatis_mutandis_links_and_expands() {
+        let doc = document(
+            "Section 1. Confidentiality\n\
+             The Recipient shall keep the Information confidential.\n\
+             Section 2. Affiliates\n\
+             The provisions of Section 1 shall apply mutatis mutandis to each Affiliate.",
+        );
+
+        let found = IncorporationResolver::new().resolve(&doc);
+        assert_eq!(found.len(), 1);
+        let incorporation = &found[0].value;
+        assert_eq!(incorporation.kind, IncorporationKind::MutatisMutandis);
+        assert_eq!(incorporation.section, "SECTION:1");
+        assert_eq!(incorporation.link.role, ClauseRole::IncorporatesFrom);
+        assert_eq!(incorporation.link.target.start.line, 0);
+        assert_eq!(incorporation.anchor.start.line, 3);
+        // "Section 1" is tokens 6-8 of "The provisions of Section 1 ..."
+        assert_eq!(incorporation.reference_span, DocSpan::single_line(3, 6, 8));
+
+        let obligations = incorporation.incorporated_obligations(&doc);
+        assert_eq!(obligations.len(), 1);
+        // "shall" is token 4 of "The Recipient shall keep ..."
+        assert_eq!(obligations[0].0, DocSpan::single_line(1, 4, 4));
+        assert_eq!(obligations[0].1.value.action, "keep the Information confidential");
+    }
+
+    #[test]
+    fn test_incorporated_by_reference_requires_resolved_section() {
+        let doc = document(
+            "Section 1. Services\n\
+             The Supplier shall provide the Services.\n\
+             Section 2. Terms\n\
+             The terms of Section 1 are incorporated herein by reference.\n\
+             Section 9 is incorporated by reference.",
+        );
+
+        let found = IncorporationResolver::new().resolve(&doc);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].value.kind, IncorporationKind::ByReference);
+        assert_eq!(found[0].value.section, "SECTION:1");
+        assert_eq!(found[0].value.anchor.start.line, 3);
+    }
+
+    #[test]
+    fn test_nearest_unresolved_reference_falls_back_to_resolved_one() {
+        let doc = document(
+            "Section 1. Services\n\
+             The Supplier shall provide the Services.\n\
+             Section 2. Terms\n\
+             The terms of Section 1, as amended by Section 9, are incorporated by reference.",
+        );
+
+        let found = IncorporationResolver::new().resolve(&doc);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].value.section, "SECTION:1");
+    }
+}
@@ -0,0 +1,362 @@
+//! Limitation-of-liability caps.
+//!
+//! ```text
+//! In no event shall the Company's liability exceed $1,000,000, except for
+//! gross negligence or willful misconduct.
+//! ```
+//!
+//! [`LiabilityCapResolver`] finds "shall not exceed", "limited to" and
+//! "capped at" phrasings in sentences about liability, takes the cap from the
+//! `Amount` that follows (with its currency and any "million" scale), and
+//! records carve-outs introduced by "except", "excluding", "other than" or
+//! "save for".
+//!
+//! Requires `AmountResolver` (from `layered-amount`) to have run on the
+//! document's lines; `ObligationPhraseResolver` is used, when it has run, to
+//! find the capped party of "The Supplier shall not be liable for more than".
+
+use layered_amount::rust_decimal::Decimal;
+use layered_amount::Amount;
+use layered_nlp::{x, LToken, TextTag};
+use layered_nlp_document::{DocumentResolver, LayeredDocument};
+
+use crate::obligation::{ObligationPhrase, ObligorReference};
+use crate::{DocSpan, Scored};
+
+/// Confidence of a cap whose capped party was found.
+const CAP_CONFIDENCE: f64 = 0.85;
+
+/// Confidence of a cap that does not say whose liability it limits.
+const UNATTRIBUTED_CAP_CONFIDENCE: f64 = 0.75;
+
+/// An amount of money.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonetaryAmount {
+    /// The amount, with any scale word applied ("2.5 million" is 2500000)
+    pub value: Decimal,
+    /// ISO 4217 code, when a currency symbol or word is attached ("USD")
+    pub currency: Option<String>,
+}
+
+/// A cap on a party's liability.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiabilityCap {
+    /// Whose liability is capped ("Company"), when stated
+    pub capped_party: Option<String>,
+    /// The maximum liability
+    pub amount: MonetaryAmount,
+    /// The cap phrase, from the cue ("exceed", "limited to") through the amount
+    pub span: DocSpan,
+    /// Carve-outs from the cap ("except for gross negligence")
+    pub exclusions: Vec<DocSpan>,
+}
+
+/// Document-level resolver for limitation-of-liability caps.
+///
+/// A sentence is a cap when it mentions liability ("liability", "liable") and
+/// a cue is followed by an amount: "exceed" or "more than" after a negation
+/// ("in no event shall ... exceed", "not be liable for more than"),
+/// "limited to" or "capped at". Caps stated without an amount ("limited to
+/// the fees paid") are not reported.
+#[derive(Debug, Clone, Default)]
+pub struct LiabilityCapResolver;
+
+impl LiabilityCapResolver {
+    /// Create a new resolver.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Currency code of a symbol or word next to an amount.
+    fn currency(text: &str) -> Option<&'static str> {
+        match text.to_lowercase().as_str() {
+            "$" | "usd" | "dollars" => Some("USD"),
+            "€" | "eur" | "euros" => Some("EUR"),
+            "£" | "gbp" | "pounds" => Some("GBP"),
+            _ => None,
+        }
+    }
+
+    /// The party named by a possessive token: "Company's" -> "Company",
+    /// "Parties'" -> "Parties".
+    fn possessor(text: &str) -> Option<&str> {
+        let name = ["'s", "’s"]
+            .iter()
+            .find_map(|suffix| text.strip_suffix(suffix))
+            .or_else(|| {
+                ["'", "’"]
+                    .iter()
+                    .find_map(|apostrophe| text.strip_suffix(apostrophe))
+                    .filter(|name| name.ends_with('s'))
+            })?;
+        (!name.is_empty()).then_some(name)
+    }
+}
+
+/// Name of a resolved obligor.
+fn obligor_name(obligor: &ObligorReference) -> Option<String> {
+    match obligor {
+        ObligorReference::TermRef { term_name, .. } => Some(term_name.clone()),
+        ObligorReference::PronounRef {
+            resolved_to,
+            is_defined_term: true,
+            ..
+        } => Some(resolved_to.clone()),
+        ObligorReference::NounPhrase { text } if !obligor.is_unresolved() => Some(text.clone()),
+        _ => None,
+    }
+}
+
+impl DocumentResolver for LiabilityCapResolver {
+    type Attr = Scored<LiabilityCap>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        let mut results = Vec::new();
+
+        for (line_idx, line) in doc.lines_enumerated() {
+            let mut amounts: Vec<((usize, usize), Decimal)> = line
+                .find(&x::attr::<Amount>())
+                .iter()
+                .map(|found| (found.token_range(), *found.attr().get_decimal()))
+                .collect();
+            if amounts.is_empty() {
+                continue;
+            }
+            amounts.sort_by_key(|(range, _)| range.0);
+            let in_amount = |idx: usize| {
+                amounts
+                    .iter()
+                    .any(|((start, end), _)| (*start..=*end).contains(&idx))
+            };
+
+            let tokens = line.ll_tokens();
+            // (index, text, tag) of every non-space token
+            let words: Vec<(usize, &str, &TextTag)> = tokens
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, token)| match token.get_token() {
+                    LToken::Text(text, tag) if *tag != TextTag::SPACE => {
+                        Some((idx, text.as_str(), tag))
+                    }
+                    _ => None,
+                })
+                .collect();
+            let obligations: Vec<(DocSpan, ObligorReference)> = line
+                .find(&x::attr::<Scored<ObligationPhrase>>())
+                .iter()
+                .map(|found| {
+                    (
+                        DocSpan::from_find(line_idx, found),
+                        found.attr().value.obligor.clone(),
+                    )
+                })
+                .collect();
+
+            let sentences = words
+                .split(|(idx, text, _)| matches!(*text, "." | ";" | "!" | "?") && !in_amount(*idx));
+            for sentence in sentences {
+                let lower: Vec<String> =
+                    sentence.iter().map(|(_, t, _)| t.to_lowercase()).collect();
+                let is_word = |i: usize, word: &str| lower.get(i).is_some_and(|w| w == word);
+                let liability = lower
+                    .iter()
+                    .position(|w| matches!(w.as_str(), "liability" | "liabilities" | "liable"));
+                let liability = match liability {
+                    Some(pos) => pos,
+                    None => continue,
+                };
+
+                let negated = |pos: usize| {
+                    lower[..pos]
+                        .iter()
+                        .any(|w| matches!(w.as_str(), "no" | "not" | "never"))
+                };
+                let cue = (0..lower.len()).find_map(|i| {
+                    if (lower[i] == "exceed" || lower[i] == "exceeds") && negated(i) {
+                        Some((i, i))
+                    } else if (is_word(i, "more") && is_word(i + 1, "than") && negated(i))
+                        || (is_word(i, "limited") && is_word(i + 1, "to"))
+                        || (is_word(i, "capped") && is_word(i + 1, "at"))
+                    {
+                        Some((i, i + 1))
+                    } else {
+                        None
+                    }
+                });
+                let (cue_start, cue_end) = match cue {
+                    Some(cue) => cue,
+                    None => continue,
+                };
+
+                let amount = amounts.iter().find(|((start, _), _)| {
+                    *start > sentence[cue_end].0
+                        && sentence.last().is_some_and(|(last, _, _)| start <= last)
+                });
+                let ((amount_start, amount_end), value) = match amount {
+                    Some(amount) => *amount,
+                    None => continue,
+                };
+                let at = |token: usize| sentence.iter().position(|(idx, _, _)| *idx == token);
+                let (first, last) = match (at(amount_start), at(amount_end)) {
+                    (Some(first), Some(last)) => (first, last),
+                    _ => continue,
+                };
+
+                let mut value = value;
+                let mut end = last;
+                let scale = match lower.get(last + 1).map(String::as_str) {
+                    Some("thousand") => Some(1_000),
+                    Some("million") => Some(1_000_000),
+                    Some("billion") => Some(1_000_000_000),
+                    _ => None,
+                };
+                if let Some(scale) = scale {
+                    value *= Decimal::from(scale);
+                    end += 1;
+                }
+                let currency = first
+                    .checked_sub(1)
+                    .and_then(|before| Self::currency(sentence[before].1))
+                    .or_else(|| lower.get(end + 1).and_then(|after| Self::currency(after)));
+                if currency.is_some()
+                    && lower.get(end + 1).and_then(|w| Self::currency(w)).is_some()
+                {
+                    end += 1;
+                }
+
+                // "the Company's liability", "the liability of the Company"
+                let possessive = (liability.saturating_sub(3)..liability)
+                    .rev()
+                    .find_map(|i| Self::possessor(sentence[i].1));
+                let of_party = is_word(liability + 1, "of")
+                    .then(|| {
+                        sentence[liability + 2..]
+                            .iter()
+                            .map(|(_, text, _)| *text)
+                            .find(|text| !text.eq_ignore_ascii_case("the"))
+                    })
+                    .flatten()
+                    .filter(|text| text.starts_with(char::is_uppercase));
+                let sentence_start = sentence[0].0;
+                let capped_party = possessive.or(of_party).map(str::to_string).or_else(|| {
+                    obligations
+                        .iter()
+                        .filter(|(span, _)| {
+                            (sentence_start..sentence[cue_start].0).contains(&span.start.token)
+                        })
+                        .find_map(|(_, obligor)| obligor_name(obligor))
+                });
+
+                // Carve-outs run to the next comma or the end of the sentence
+                let exclusions = (0..lower.len())
+                    .filter(|&i| {
+                        matches!(lower[i].as_str(), "except" | "excluding")
+                            || (is_word(i, "other") && is_word(i + 1, "than"))
+                            || (is_word(i, "save") && is_word(i + 1, "for"))
+                    })
+                    .filter_map(|i| {
+                        let end = sentence[i..]
+                            .iter()
+                            .take_while(|(idx, _, tag)| **tag != TextTag::PUNC || in_amount(*idx))
+                            .last()?;
+                        Some(DocSpan::single_line(line_idx, sentence[i].0, end.0))
+                    })
+                    .collect();
+
+                let confidence = if capped_party.is_some() {
+                    CAP_CONFIDENCE
+                } else {
+                    UNATTRIBUTED_CAP_CONFIDENCE
+                };
+                results.push(Scored::rule_based(
+                    LiabilityCap {
+                        capped_party,
+                        amount: MonetaryAmount {
+                            value,
+                            currency: currency.map(str::to_string),
+                        },
+                        span: DocSpan::single_line(
+                            line_idx,
+                            sentence[cue_start].0,
+                            sentence[end].0,
+                        ),
+                        exclusions,
+                    },
+                    confidence,
+                    "liability_cap",
+                ));
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractDocument, ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver,
+        ProhibitionResolver, PronounResolver, TermReferenceResolver,
+    };
+    use layered_amount::AmountResolver;
+    use layered_part_of_speech::POSTagResolver;
+
+    fn caps(text: &str) -> Vec<Scored<LiabilityCap>> {
+        let doc = ContractDocument::from_text(text)
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&AmountResolver::english())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ProhibitionResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+            .run_resolver(&TermReferenceResolver::default())
+            .run_resolver(&PronounResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default());
+        LiabilityCapResolver::new().resolve(&doc)
+    }
+
+    #[test]
+    fn test_in_no_event_exceed_with_carve_out() {
+        let found = caps(
+            "In no event shall the Company's aggregate liability exceed $1,000,000, \
+             except for gross negligence or willful misconduct.",
+        );
+
+        assert_eq!(found.len(), 1);
+        let cap = &found[0].value;
+        assert_eq!(cap.capped_party.as_deref(), Some("Company"));
+        assert_eq!(cap.amount.value, Decimal::from(1_000_000));
+        assert_eq!(cap.amount.currency.as_deref(), Some("USD"));
+        assert_eq!(cap.exclusions.len(), 1);
+        // "except" .. "misconduct"
+        assert_eq!(cap.exclusions[0].start.token, 26);
+        assert_eq!(cap.exclusions[0].end.token, 38);
+        assert_eq!(found[0].confidence, CAP_CONFIDENCE);
+    }
+
+    #[test]
+    fn test_limited_to_and_not_liable_for_more_than() {
+        let found = caps(
+            "The liability of the Licensor shall be limited to EUR 2.5 million. \
+             The Supplier shall not be liable for more than 500,000 dollars.\n\
+             The Supplier's fees shall not exceed $900. \
+             Liability is limited to the fees paid.",
+        );
+
+        assert_eq!(
+            found.len(),
+            2,
+            "fee limits and caps without amounts are skipped"
+        );
+        let licensor = &found[0].value;
+        assert_eq!(licensor.capped_party.as_deref(), Some("Licensor"));
+        assert_eq!(licensor.amount.value, Decimal::from(2_500_000));
+        assert_eq!(licensor.amount.currency.as_deref(), Some("EUR"));
+        assert!(licensor.exclusions.is_empty());
+
+        let supplier = &found[1].value;
+        assert_eq!(supplier.capped_party.as_deref(), Some("Supplier"));
+        assert_eq!(supplier.amount.value, Decimal::from(500_000));
+        assert_eq!(supplier.amount.currency.as_deref(), Some("USD"));
+    }
+}
@@ -18,6 +18,7 @@
 //! - [`SectionReferenceResolver`] - Detects references to sections
 //! - [`SentenceBoundaryResolver`] - Detects sentence boundaries (periods, etc.)
 //! - [`TemporalExpressionResolver`] - Extracts time expressions (within 30 days)
+//! - [`GoverningLawResolver`] - Detects governing-law clauses and their jurisdiction
 //!
 //! ## Document-Level Processing
 //!
@@ -47,28 +48,50 @@
 
 mod accountability_analytics;
 mod accountability_graph;
+mod acronym;
+mod agreement;
+mod anaphoric_reference;
+mod amendment;
+mod confidence_audit;
 mod conflict_detector;
+mod conditional_permission;
+mod cure_period;
+mod liability_cap;
 mod contract_clause;
 mod clause_aggregate;
 mod contract_keyword;
+mod deadline;
+mod degradation;
 mod defined_term;
+mod definition_extent;
 mod deictic;
 mod document_aligner;
 mod document_structure;
+mod document_warnings;
+mod enumeration;
+mod governing_law;
 mod modal_negation;
 mod modal_scope;
+mod modality;
+mod number;
 mod obligation;
 mod scoped_obligation_resolver;
 mod obligation_linker;
 mod linked_obligation_resolver;
+mod list_obligation;
+mod clause_cluster;
+mod incorporation;
 mod polarity;
 mod precedence;
 mod pronoun;
 mod pronoun_chain;
+mod quoted_obligation;
 mod scope_ambiguity;
 mod scope_operators;
 mod section_header;
 mod sentence_boundary;
+mod signature_block;
+mod summary;
 mod section_reference;
 mod section_reference_linker;
 mod semantic_diff;
@@ -76,9 +99,12 @@ mod semantic_roles;
 mod temporal;
 mod term_reference;
 mod terms_of_art;
+mod timeline;
 mod token_diff;
 mod utils;
 mod verification;
+mod version_chain;
+mod workflow;
 
 // Snapshot system for testing
 pub mod snapshot;
@@ -86,11 +112,16 @@ pub mod snapshot;
 // Re-export document infrastructure from layered-nlp-document
 pub use layered_nlp_document::{
     // Core document types
-    DocPosition, DocSpan, LayeredDocument, ProcessError, ProcessResult,
+    CharSpan, DocPosition, DocSpan, DocumentWarning, Evidence, LayeredDocument, LineEnding,
+    OverlapPolicy, ProcessError, ProcessResult, WarningSeverity,
+    // Annotation queries
+    Annotation, AnnotationRef, DocQuery, HasConfidence, PositionIndex, TypedQuery,
+    // Language detection
+    Lang, detect_line_language,
     // Scoring infrastructure
-    Scored, ScoreSource,
+    ConfidenceAdjustment, Scored, ScoreSource, ScoreSourceKind, ScoreSourcePolicy,
     // Ambiguity infrastructure (M0 Gate 4)
-    AmbiguityFlag, AmbiguityConfig, Ambiguous,
+    AmbiguityFlag, AmbiguityConfig, Ambiguous, MergePolicy,
     // Span link infrastructure (M0 Gate 1)
     SpanLink, DocSpanLink, ClauseRole, AttachmentRole, SemanticRole, ConflictRole,
     // Scope operator infrastructure (M0 Gate 2)
@@ -98,6 +129,8 @@ pub use layered_nlp_document::{
     NegationOp, NegationKind, QuantifierOp, QuantifierKind, PrecedenceOp, DeicticFrame,
     // Scope index (M0 Gate 3)
     ScopeIndex,
+    // Scope composition
+    PolarityEffect, PolarityOp, ResolvedPolarity, ScopeResolver,
 };
 
 /// Backward-compatible type alias for ContractDocument.
@@ -111,29 +144,63 @@ pub mod pipeline;
 
 pub use accountability_analytics::{
     AccountabilityNodePayload, AccountabilityPayload, BeneficiaryDescriptor, BeneficiaryGroup,
-    BeneficiaryPayload, ClausePayload, ConditionPayload, ObligationGraph, PartyAnalytics,
-    PartySummary, VerificationQueueDetails, VerificationQueueItem,
+    BeneficiaryPayload, ClauseConditionality, ClausePayload, ConditionPayload, ObligationGraph,
+    PartyAnalytics, PartySummary, VerificationQueueDetails, VerificationQueueItem,
 };
 pub use accountability_graph::{
     AccountabilityGraphResolver, BeneficiaryLink, ConditionLink, ObligationNode,
 };
+pub use acronym::{Acronym, AcronymKind, AcronymResolver};
+pub use agreement::{agreement, AgreementReport, TypeAgreement};
+pub use anaphoric_reference::AnaphoricReferenceResolver;
+pub use amendment::{
+    apply_amendments, AmendmentError, AmendmentInstruction, AmendmentOperation, AmendmentResolver,
+};
 pub use clause_aggregate::{
-    ClauseAggregate, ClauseAggregateEntry, ClauseAggregationResolver,
+    ClauseAggregate, ClauseAggregateEntry, ClauseAggregationResolver, ConfidencePropagation,
 };
+pub use confidence_audit::{confidence_audit, AuditReport, ConfidenceAudit, ConfidenceStats};
 pub use conflict_detector::{
-    Conflict, ConflictDetector, ConflictType, NormalizedObligation, ObligationNormalizer,
-    ObligationTopic, TopicClassifier, group_by_topic,
+    Conflict, ConflictDelta, ConflictDetector, ConflictReport, ConflictScope, ConflictType,
+    NormalizedObligation, ObligationNormalizer, ObligationTopic, SimilarityFn, SimilarityMetric,
+    TopicClassifier, group_by_topic,
 };
+pub use conditional_permission::{ConditionalPermissionResolver, ImpliedObligation};
+pub use cure_period::{CurePeriod, CurePeriodResolver};
+pub use liability_cap::{LiabilityCap, LiabilityCapResolver, MonetaryAmount};
 pub use contract_clause::{
-    ClauseCondition, ClauseDuty, ClauseParty, ContractClause, ContractClauseResolver,
+    ClauseAmbiguity, ClauseAmbiguityKind, ClauseAmbiguitySummary, ClauseCondition, ClauseDuty,
+    ClauseParty, ContractClause, ContractClauseResolver,
 };
 pub use contract_keyword::{ContractKeyword, ContractKeywordResolver, ProhibitionResolver};
-pub use defined_term::{DefinedTerm, DefinedTermResolver, DefinitionType};
+pub use deadline::{
+    infer_missing_deadlines, DeadlineExt, DeadlineTracker, ObligationDeadline,
+    UndeterminedDeadline, UndeterminedReason,
+};
+pub use degradation::{DegradationFallback, DegradationReport};
+pub use defined_term::{
+    plural_form, DefinedTerm, DefinedTermResolver, DefinitionBody, DefinitionScope,
+    DefinitionType,
+};
+pub use definition_extent::{
+    find_definition_cycles, find_inconsistent_definitions, DefinitionExtent,
+    DefinitionExtentResolver, InconsistentDefinition,
+};
+pub use enumeration::{
+    Enumeration, EnumerationResolver, ListMarker, ListMarkerConfig, MarkerStyle,
+};
+pub use governing_law::{GoverningLaw, GoverningLawResolver};
+pub use list_obligation::{ListItemObligation, ListObligationResolver};
+pub use clause_cluster::{ClauseCluster, ClauseClusterer};
+pub use incorporation::{Incorporation, IncorporationKind, IncorporationResolver};
 pub use obligation::{
-    ConditionRef, ObligationPhrase, ObligationPhraseResolver, ObligationType, ObligorReference,
+    ConditionRef, DiscretionStandard, ObligationConfidenceBreakdown, ObligationNature, ObligationPhrase,
+    ObligationPhraseResolver, ObligationType, ObligorReference, ObligorStrategy, UNKNOWN_OBLIGOR,
 };
 pub use modal_negation::*;
 pub use modal_scope::{ModalScopeAnalyzer, ScopedObligation};
+pub use modality::{ModalityClassifier, ModalityEvidence, ModalitySignal, ModalityVote};
+pub use number::{NumberParser, ParsedNumber};
 pub use scoped_obligation_resolver::ScopedObligationResolver;
 pub use obligation_linker::{
     ClauseParticipant, LinkedObligation, ObligationPartyLinker, ObligationPartyLinkerConfig,
@@ -149,7 +216,7 @@ pub use scope_ambiguity::{
     ScopeAmbiguityFlagger, ScopeAmbiguityFlaggerConfig, ScopeAmbiguityFlag,
 };
 pub use scope_operators::{
-    NegationDetector, QuantifierDetector, ScopeBoundaryDetector,
+    NegationDetector, QuantifierDetector, ScopeBoundaryDetector, ScopeOperatorResolver,
 };
 pub use semantic_roles::{
     ArgumentRole, CanonicalModal, EnhancedNormalizedObligation, EnhancedObligationNormalizer,
@@ -160,13 +227,26 @@ pub use pronoun::{
     DocumentPronounResolver, DocumentPronounResult, PronounChainResult, PronounReference,
     PronounResolver, PronounType,
 };
-pub use pronoun_chain::{ChainMention, MentionType, PronounChain, PronounChainResolver};
+pub use pronoun_chain::{
+    entities, CanonicalEntity, ChainMention, EntityMention, MentionType, PronounChain,
+    PronounChainResolver,
+};
+pub use quoted_obligation::{QuotedObligation, QuotedObligationResolver};
 // Note: Scored and ScoreSource are now re-exported from layered_nlp_document at the top
-pub use term_reference::{TermReference, TermReferenceResolver};
+pub use term_reference::{
+    find_article_inconsistencies, find_capitalization_inconsistencies, Article,
+    DocumentTermReference, DocumentTermReferenceResolver, TermReference, TermReferenceResolver,
+};
 pub use terms_of_art::{TermOfArt, TermOfArtCategory, TermsOfArtResolver};
+pub use version_chain::{
+    TrackedSection, VersionChain, VersionChange, VersionEvent, VersionHistory,
+};
 pub use verification::{
     apply_verification_action, VerificationAction, VerificationNote, VerificationTarget,
 };
+pub use workflow::{
+    ObligationWorkflowExt, Workflow, WorkflowDeadline, WorkflowEdge, WorkflowError, WorkflowNode,
+};
 
 // Note: Document types are now re-exported from layered_nlp_document at the top of this file
 pub use document_aligner::{
@@ -174,9 +254,17 @@ pub use document_aligner::{
     AlignmentSignal, AlignmentStats, AlignmentType, DocumentAligner, HintType, SectionRef,
     SimilarityConfig,
 };
-pub use document_structure::{DocumentProcessor, DocumentStructure, DocumentStructureBuilder, SectionNode};
+pub use document_structure::{
+    DocumentProcessor, DocumentStructure, DocumentStructureBuilder, DocumentStructureExt,
+    SectionNode, SectionQueryExt,
+};
+pub use document_warnings::DocumentWarningsExt;
 pub use section_header::{SectionHeader, SectionHeaderResolver, SectionIdentifier, SectionKind};
 pub use sentence_boundary::{SentenceBoundary, SentenceBoundaryResolver, SentenceConfidence};
+pub use signature_block::{Signatory, SignatureBlock, SignatureBlockResolver};
+pub use summary::{
+    ConflictSummary, ContractSummary, DeadlineSummary, ObligationSummary, PartyObligations,
+};
 pub use section_reference::{
     ReferencePurpose, ReferenceType, RelativeReference, SectionReference, SectionReferenceResolver,
 };
@@ -187,6 +275,7 @@ pub use temporal::{
     DeadlineType, DurationUnit, NormalizedTiming, TemporalConverter, TemporalExpression,
     TemporalExpressionResolver, TemporalType, TimeRelation, TimeUnit,
 };
+pub use timeline::{Timeline, TimelineEvent, TimelineExt, TimelinePosition};
 pub use semantic_diff::{
     AffectedReference, ChangeSignal, ConditionChange, DiffConfig, DiffHint, DiffHintType,
     DiffReviewCandidates, DiffSummary, ImpactDirection, ObligationModalChange, PartyChange,
@@ -202,7 +291,10 @@ pub use token_diff::{
 // name collision with document_aligner::AlignmentStats
 
 // Deictic mapping resolver
-pub use deictic::DeicticResolver;
+pub use deictic::{
+    aggregate_deixis, DeicticCategorySummary, DeicticResolver, DeicticSubcategorySummary,
+    DeicticSummary,
+};
 
 // Re-export layered_deixis types for convenience
 pub use layered_deixis::{
@@ -0,0 +1,300 @@
+//! Obligations distributed over list items under a lead-in clause.
+//!
+//! ```text
+//! The Supplier shall:
+//! (a) deliver the Goods on time;
+//! (b) maintain insurance; and
+//! (c) notify the Buyer of any delay.
+//! ```
+//!
+//! The line-level `ObligationPhraseResolver` only sees "The Supplier shall:"
+//! with an empty action, and the item lines carry no modal or obligor.
+//! [`ListObligationResolver`] finds such colon-terminated lead-ins and produces
+//! one [`ObligationPhrase`] per item, sharing the lead-in's obligor, modality
+//! and conditions.
+
+use layered_nlp::{x, LLLine, LToken, TextTag};
+use layered_nlp_document::{DocumentResolver, LayeredDocument};
+
+use crate::enumeration::{ListMarker, ListMarkerConfig, TRAILING_SEPARATORS};
+use crate::obligation::ObligationPhrase;
+use crate::{DocSpan, Scored};
+
+/// An obligation made of a lead-in clause and one list item.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListItemObligation {
+    /// The item text the action was taken from, without its marker
+    pub item_span: DocSpan,
+    /// The modal of the lead-in clause ("shall" in "The Supplier shall:")
+    pub lead_in_span: DocSpan,
+    /// The marker opening the item; consecutive items of an ordered list
+    /// should each [follow](ListMarker::follows) the previous one
+    pub marker: ListMarker,
+    /// The lead-in's obligation completed with the item's action
+    pub obligation: ObligationPhrase,
+}
+
+/// Document-level resolver distributing a lead-in obligation over its list items.
+///
+/// A lead-in is a line ending in ":" that carries a `Scored<ObligationPhrase>`;
+/// the last obligation on the line is used. Each following line that starts
+/// with a list marker (see [`ListMarkerConfig`]) becomes an item; the first
+/// line without a marker ends the list. Any action words in the lead-in ("use reasonable efforts to:")
+/// are prefixed to each item's action.
+///
+/// Requires `ObligationPhraseResolver` to have run on the document's lines.
+#[derive(Debug, Clone)]
+pub struct ListObligationResolver {
+    /// Multiplier applied to the lead-in confidence for each item
+    item_confidence_factor: f64,
+    /// Markers that open a list item
+    markers: ListMarkerConfig,
+}
+
+impl Default for ListObligationResolver {
+    fn default() -> Self {
+        Self {
+            item_confidence_factor: 0.9,
+            markers: ListMarkerConfig::default(),
+        }
+    }
+}
+
+impl ListObligationResolver {
+    /// Create a new resolver with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the multiplier applied to the lead-in confidence for each item.
+    pub fn with_item_confidence_factor(mut self, factor: f64) -> Self {
+        self.item_confidence_factor = factor;
+        self
+    }
+
+    /// Set the list markers that open an item.
+    pub fn with_markers(mut self, markers: ListMarkerConfig) -> Self {
+        self.markers = markers;
+        self
+    }
+
+    /// The lead-in obligation of a colon-terminated line, with its span.
+    fn lead_in(line_index: usize, line: &LLLine) -> Option<(DocSpan, Scored<ObligationPhrase>)> {
+        let last_text = line.ll_tokens().iter().rev().find_map(|token| match token.get_token() {
+            LToken::Text(_, TextTag::SPACE) => None,
+            LToken::Text(text, _) => Some(text.as_str()),
+            LToken::Value => None,
+        })?;
+        if last_text != ":" {
+            return None;
+        }
+        line.find(&x::attr::<Scored<ObligationPhrase>>())
+            .into_iter()
+            .max_by_key(|found| found.token_range().0)
+            .map(|found| (DocSpan::from_find(line_index, &found), (*found.attr()).clone()))
+    }
+
+    /// The item's token range and text, without trailing separators.
+    fn item(line: &LLLine, start: usize) -> Option<(usize, usize, String)> {
+        let tokens = line.ll_tokens();
+        let text_at = |idx: usize| match tokens[idx].get_token() {
+            LToken::Text(text, tag) => Some((text.as_str(), tag)),
+            LToken::Value => None,
+        };
+
+        let mut end = tokens.len().checked_sub(1)?;
+        while end >= start {
+            match text_at(end) {
+                Some((_, TextTag::SPACE)) | None => {}
+                Some((text, _)) if TRAILING_SEPARATORS.contains(&text.to_lowercase().as_str()) => {}
+                Some(_) => break,
+            }
+            end = end.checked_sub(1)?;
+        }
+        let start = (start..=end).find(|&idx| !matches!(text_at(idx), Some((_, TextTag::SPACE))))?;
+
+        let text: String = (start..=end).filter_map(text_at).map(|(text, _)| text).collect();
+        Some((start, end, text))
+    }
+}
+
+/// Lead-in action words to keep in front of each item ("use reasonable efforts to").
+fn lead_in_prefix(action: &str) -> Vec<&str> {
+    let mut words: Vec<&str> = action.split_whitespace().collect();
+    while let Some(last) = words.last() {
+        if matches!(last.to_lowercase().as_str(), "the" | "following" | ":") {
+            words.pop();
+        } else {
+            break;
+        }
+    }
+    words
+}
+
+impl DocumentResolver for ListObligationResolver {
+    type Attr = Scored<ListItemObligation>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        let lines = doc.lines();
+        let mut results = Vec::new();
+
+        let mut line_index = 0;
+        while line_index < lines.len() {
+            let (lead_in_span, lead_in) = match Self::lead_in(line_index, &lines[line_index]) {
+                Some(found) => found,
+                None => {
+                    line_index += 1;
+                    continue;
+                }
+            };
+            let prefix = lead_in_prefix(&lead_in.value.action);
+
+            line_index += 1;
+            while let Some(line) = lines.get(line_index) {
+                let (marker, content_start) = match self.markers.item_start(line) {
+                    Some(found) => found,
+                    None => break,
+                };
+                if let Some((item_start, item_end, text)) = Self::item(line, content_start) {
+                    let mut obligation = lead_in.value.clone();
+                    obligation.action = prefix
+                        .iter()
+                        .copied()
+                        .chain(text.split_whitespace())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    obligation.qualifiers = Vec::new();
+                    results.push(Scored::rule_based(
+                        ListItemObligation {
+                            item_span: DocSpan::single_line(line_index, item_start, item_end),
+                            lead_in_span,
+                            marker,
+                            obligation,
+                        },
+                        lead_in.confidence * self.item_confidence_factor,
+                        "list_item_obligation",
+                    ));
+                }
+                line_index += 1;
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractDocument, ContractKeywordResolver, DefinedTermResolver, MarkerStyle,
+        ObligationPhraseResolver, ObligationType, ObligorReference, ProhibitionResolver,
+        PronounResolver, TermReferenceResolver,
+    };
+    use layered_part_of_speech::POSTagResolver;
+
+    fn list_obligations(text: &str) -> Vec<Scored<ListItemObligation>> {
+        list_obligations_with(text, ListObligationResolver::new())
+    }
+
+    fn list_obligations_with(
+        text: &str,
+        resolver: ListObligationResolver,
+    ) -> Vec<Scored<ListItemObligation>> {
+        let doc = ContractDocument::from_text(text)
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ProhibitionResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+            .run_resolver(&TermReferenceResolver::default())
+            .run_resolver(&PronounResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default());
+        resolver.resolve(&doc)
+    }
+
+    #[test]
+    fn test_lead_in_distributes_over_items() {
+        let found = list_obligations(
+            "ABC Corp (the \"Supplier\") shall:\n\
+             (a) deliver the Goods within thirty (30) days;\n\
+             (b) maintain insurance; and\n\
+             (c) notify the Buyer of any delay.\n\
+             The Buyer may inspect the Goods.",
+        );
+
+        let actions: Vec<&str> = found.iter().map(|s| s.value.obligation.action.as_str()).collect();
+        assert_eq!(
+            actions,
+            vec![
+                "deliver the Goods within thirty (30) days",
+                "maintain insurance",
+                "notify the Buyer of any delay"
+            ]
+        );
+        for scored in &found {
+            let obligation = &scored.value.obligation;
+            assert_eq!(obligation.obligation_type, ObligationType::Duty);
+            assert_eq!(
+                obligation.obligor,
+                ObligorReference::NounPhrase { text: "Supplier".to_string() }
+            );
+            // "shall" is token 12 of the lead-in line
+            assert_eq!(scored.value.lead_in_span, DocSpan::single_line(0, 12, 12));
+        }
+        assert_eq!(found[1].value.item_span.start.line, 2);
+    }
+
+    #[test]
+    fn test_lead_in_prefix_and_prohibition() {
+        let found = list_obligations(
+            "The Vendor shall not:\n\
+             1. disclose the source code,\n\
+             2. assign this Agreement.\n\
+             The Vendor shall use reasonable efforts to:\n\
+             • respond to requests\n\
+             Notices must be in writing.\n\
+             - not a list item of any lead-in",
+        );
+
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].value.obligation.obligation_type, ObligationType::Prohibition);
+        assert_eq!(found[0].value.obligation.action, "disclose the source code");
+        assert_eq!(found[1].value.obligation.action, "assign this Agreement");
+        let prefixed = &found[2].value.obligation.action;
+        assert_eq!(prefixed, "use reasonable efforts to respond to requests");
+    }
+
+    #[test]
+    fn test_configured_markers() {
+        let text = "The Vendor shall:\n\
+                    \u{2014} deliver the Goods;\n\
+                    \u{2014} install the Goods.";
+        assert!(list_obligations(text).is_empty());
+
+        let markers = ListMarkerConfig::new().with_bullet("\u{2014}");
+        let found = list_obligations_with(text, ListObligationResolver::new().with_markers(markers));
+        let actions: Vec<&str> = found.iter().map(|s| s.value.obligation.action.as_str()).collect();
+        assert_eq!(actions, vec!["deliver the Goods", "install the Goods"]);
+        assert!(!found[0].value.marker.style.is_ordered());
+    }
+
+    #[test]
+    fn test_ordered_items_can_be_renumber_checked() {
+        let markers = ListMarkerConfig::new().with_ordered(MarkerStyle::DottedRoman);
+        let found = list_obligations_with(
+            "The Vendor shall:\n\
+             i. deliver the Goods;\n\
+             ii. install the Goods;\n\
+             iv. train the staff.",
+            ListObligationResolver::new().with_markers(markers),
+        );
+
+        assert_eq!(found.len(), 3);
+        let markers: Vec<ListMarker> = found.iter().map(|s| s.value.marker).collect();
+        assert_eq!(markers[0].style, MarkerStyle::DottedRoman);
+        assert!(markers[1].follows(&markers[0]));
+        // "iii." was skipped
+        assert_eq!(markers[2].ordinal, 4);
+        assert!(!markers[2].follows(&markers[1]));
+    }
+}
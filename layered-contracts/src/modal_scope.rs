@@ -305,7 +305,9 @@ impl Default for ModalScopeAnalyzer {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{ConditionRef, DiscretionPattern, ObligorReference, ObligationType};
+    use crate::{
+        ConditionRef, DiscretionPattern, ObligationNature, ObligationType, ObligorReference,
+    };
     use layered_nlp_document::{DocPosition, DocSpan, NegationKind, ScopeDimension, ScopeDomain};
 
     // ========================================================================
@@ -334,6 +336,12 @@ mod tests {
             obligation_type: ObligationType::Duty,
             action: action.to_string(),
             conditions: Vec::new(),
+            confidence_breakdown: Default::default(),
+            qualifiers: Vec::new(),
+            object: None,
+            performer: None,
+            obligation_nature: ObligationNature::Performance,
+            discretion: None,
         };
         Scored::rule_based(phrase, 0.85, "obligation_phrase")
     }
@@ -581,6 +589,12 @@ mod tests {
             obligation_type: ObligationType::Duty,
             action: "do something".to_string(),
             conditions: Vec::new(),
+            confidence_breakdown: Default::default(),
+            qualifiers: Vec::new(),
+            object: None,
+            performer: None,
+            obligation_nature: ObligationNature::Performance,
+            discretion: None,
         };
         let obligation = Scored::rule_based(phrase, 0.4, "weak_heuristic");
 
@@ -778,6 +792,12 @@ mod tests {
             obligation_type: ObligationType::Duty,
             action: "do something".to_string(),
             conditions: Vec::new(),
+            confidence_breakdown: Default::default(),
+            qualifiers: Vec::new(),
+            object: None,
+            performer: None,
+            obligation_nature: ObligationNature::Performance,
+            discretion: None,
         };
         let obligation = Scored::rule_based(phrase, 0.3, "weak_heuristic");
 
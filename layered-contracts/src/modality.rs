@@ -0,0 +1,388 @@
+//! Weighted voting on the type of an obligation.
+//!
+//! ```text
+//! The Buyer shall have the right to inspect the goods.
+//!           ^^^^^ Duty (modal keyword, weight 1.0)
+//!                 ^^^^^^^^^^^^^^^^^ Permission (phrasing, weight 1.5)
+//! ```
+//!
+//! The modal keyword alone would make this a duty; the phrasing makes it a
+//! permission. [`ModalityClassifier`] collects a vote from each signal and
+//! picks the [`ObligationType`] with the most weight. When the vote is close
+//! the result is flagged as ambiguous.
+//!
+//! Coordinated modals ("shall and may", "shall and/or may") are a drafting
+//! defect: each modal votes, so both readings stay visible to a reviewer.
+
+use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+use crate::contract_keyword::ContractKeyword;
+use crate::obligation::ObligationType;
+use crate::{AmbiguityConfig, Ambiguous, Scored};
+
+/// A source of evidence about an obligation's type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModalitySignal {
+    /// The modal keyword ("shall", "may", "shall not")
+    ModalKeyword,
+    /// A negation leading the action ("shall never disclose")
+    NegationScope,
+    /// Phrasing after the modal ("have the right to", "refrain from")
+    Phrasing,
+    /// An earlier modal coordinated with this one ("shall" in "shall and may")
+    CoordinatedModal,
+}
+
+/// One signal's vote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModalityVote {
+    pub signal: ModalitySignal,
+    pub obligation_type: ObligationType,
+    pub weight: f64,
+}
+
+/// What the classifier knows about an obligation.
+#[derive(Debug, Clone, Copy)]
+pub struct ModalityEvidence<'a> {
+    /// The modal keyword
+    pub keyword: &'a ContractKeyword,
+    /// Whether a negation leads the action of a positive modal
+    pub negated_action: bool,
+    /// The action after the modal, without leading negations
+    pub action: &'a str,
+    /// An earlier modal coordinated with this one ("shall" in "shall and may")
+    pub coordinated: Option<&'a ContractKeyword>,
+}
+
+/// Phrasings that grant a permission ("shall have the right to").
+const PERMISSION_PHRASES: &[&str] = &[
+    "have the right to",
+    "have the option to",
+    "be entitled to",
+    "be permitted to",
+    "be free to",
+];
+
+/// Phrasings that forbid ("shall refrain from").
+const PROHIBITION_PHRASES: &[&str] = &["be prohibited from", "refrain from", "be restricted from"];
+
+/// Phrasings that impose a duty ("may be required to"); negated they lift
+/// one ("shall not be required to" permits not acting).
+const DUTY_PHRASES: &[&str] = &["be required to", "be obligated to", "have the obligation to"];
+
+/// Votes an obligation type by weighted signals.
+///
+/// Each signal's share of the total weight becomes the score of its type.
+/// [`AmbiguityConfig`] decides when the vote is close: the best share is
+/// below `low_confidence`, or another type is within `margin_threshold`.
+///
+/// As a line [`Resolver`], it annotates the modal keywords whose vote is
+/// close with an [`Ambiguous<ObligationType>`]; `ObligationPhraseResolver`
+/// uses the winning type either way.
+#[derive(Debug, Clone)]
+pub struct ModalityClassifier {
+    keyword_weight: f64,
+    negation_weight: f64,
+    phrasing_weight: f64,
+    coordinated_weight: f64,
+    config: AmbiguityConfig,
+}
+
+impl Default for ModalityClassifier {
+    fn default() -> Self {
+        Self {
+            keyword_weight: 1.0,
+            negation_weight: 2.0,
+            phrasing_weight: 1.5,
+            coordinated_weight: 1.0,
+            config: AmbiguityConfig::default(),
+        }
+    }
+}
+
+/// Words read after a modal as its action by the line resolver.
+const MAX_ACTION_WORDS: usize = 6;
+
+/// Conjunctions joining coordinated modals.
+const MODAL_CONJUNCTIONS: &[&str] = &["and", "or"];
+
+/// The modal coordinated before `modal_sel` ("shall" in "shall and may",
+/// "shall and/or may"), and the selection spanning both modals.
+pub(crate) fn preceding_coordinated_modal(
+    modal_sel: &LLSelection,
+) -> Option<(LLSelection, ContractKeyword)> {
+    let (ws_sel, _) = modal_sel.match_first_backwards(&x::whitespace())?;
+    let (conj_sel, conj) = ws_sel.match_first_backwards(&x::token_text())?;
+    if !MODAL_CONJUNCTIONS.contains(&conj.to_lowercase().as_str()) {
+        return None;
+    }
+    // "and/or": step back over "/" and the first conjunction
+    let conj_sel = match conj_sel.match_first_backwards(&x::token_text()) {
+        Some((slash_sel, "/")) => match slash_sel.match_first_backwards(&x::token_text()) {
+            Some((pair_sel, first)) if MODAL_CONJUNCTIONS.contains(&first.to_lowercase().as_str()) => {
+                pair_sel
+            }
+            _ => return None,
+        },
+        _ => conj_sel,
+    };
+    let (ws_sel, _) = conj_sel.match_first_backwards(&x::whitespace())?;
+    let (coordinated_sel, keyword) = ws_sel.match_first_backwards(&x::attr::<ContractKeyword>())?;
+    ObligationType::from_keyword(keyword)?;
+    Some((coordinated_sel, *keyword))
+}
+
+/// Start tokens of modals coordinated with a later modal, which that later
+/// modal's obligation covers.
+pub(crate) fn leading_coordinated_starts<'a>(
+    modal_sels: impl IntoIterator<Item = &'a LLSelection>,
+) -> Vec<usize> {
+    modal_sels
+        .into_iter()
+        .filter_map(preceding_coordinated_modal)
+        .map(|(coordinated_sel, _)| coordinated_sel.span_ref().start_idx)
+        .collect()
+}
+
+impl ModalityClassifier {
+    /// Create a classifier with the default weights.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the weight of `signal`'s votes.
+    pub fn with_weight(mut self, signal: ModalitySignal, weight: f64) -> Self {
+        match signal {
+            ModalitySignal::ModalKeyword => self.keyword_weight = weight,
+            ModalitySignal::NegationScope => self.negation_weight = weight,
+            ModalitySignal::Phrasing => self.phrasing_weight = weight,
+            ModalitySignal::CoordinatedModal => self.coordinated_weight = weight,
+        }
+        self
+    }
+
+    /// Set the thresholds deciding when a vote is close.
+    pub fn with_ambiguity_config(mut self, config: AmbiguityConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The votes cast for an obligation.
+    pub fn votes(&self, evidence: &ModalityEvidence) -> Vec<ModalityVote> {
+        let mut votes = Vec::new();
+        let keyword_type = match ObligationType::from_keyword(evidence.keyword) {
+            Some(keyword_type) => keyword_type,
+            None => return votes,
+        };
+        votes.push(ModalityVote {
+            signal: ModalitySignal::ModalKeyword,
+            obligation_type: keyword_type,
+            weight: self.keyword_weight,
+        });
+
+        let coordinated_type = evidence.coordinated.and_then(ObligationType::from_keyword);
+        if let Some(obligation_type) = coordinated_type {
+            votes.push(ModalityVote {
+                signal: ModalitySignal::CoordinatedModal,
+                obligation_type,
+                weight: self.coordinated_weight,
+            });
+        }
+
+        if evidence.negated_action {
+            votes.push(ModalityVote {
+                signal: ModalitySignal::NegationScope,
+                obligation_type: ObligationType::Prohibition,
+                weight: self.negation_weight,
+            });
+        }
+
+        let action = evidence.action.to_lowercase();
+        let starts = |phrases: &[&str]| phrases.iter().any(|phrase| action.starts_with(phrase));
+        let negated = evidence.negated_action || keyword_type == ObligationType::Prohibition;
+        let phrased = if starts(PERMISSION_PHRASES) && !negated {
+            Some(ObligationType::Permission)
+        } else if starts(PROHIBITION_PHRASES) {
+            Some(ObligationType::Prohibition)
+        } else if starts(DUTY_PHRASES) {
+            Some(if negated { ObligationType::Permission } else { ObligationType::Duty })
+        } else {
+            None
+        };
+        if let Some(obligation_type) = phrased {
+            votes.push(ModalityVote {
+                signal: ModalitySignal::Phrasing,
+                obligation_type,
+                weight: self.phrasing_weight,
+            });
+        }
+        votes
+    }
+
+    /// Tally votes into ranked types scored by their share of the weight.
+    ///
+    /// Returns `None` without votes.
+    pub fn tally(&self, votes: &[ModalityVote]) -> Option<Ambiguous<ObligationType>> {
+        let total: f64 = votes.iter().map(|vote| vote.weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let types = [ObligationType::Duty, ObligationType::Permission, ObligationType::Prohibition];
+        let candidates = types
+            .iter()
+            .map(|&obligation_type| {
+                let weight: f64 = votes
+                    .iter()
+                    .filter(|vote| vote.obligation_type == obligation_type)
+                    .map(|vote| vote.weight)
+                    .sum();
+                Scored::rule_based(obligation_type, weight / total, "modality_vote")
+            })
+            .filter(|candidate| candidate.confidence > 0.0)
+            .collect();
+        Ambiguous::from_candidates(candidates, &self.config)
+    }
+
+    /// Classify an obligation: [`votes`](Self::votes), then
+    /// [`tally`](Self::tally).
+    pub fn classify(&self, evidence: &ModalityEvidence) -> Option<Ambiguous<ObligationType>> {
+        self.tally(&self.votes(evidence))
+    }
+
+    /// The words after a modal up to punctuation, and how many of them are
+    /// leading negations.
+    fn action_after(modal_sel: &LLSelection) -> (Vec<String>, usize) {
+        let mut words = Vec::new();
+        let mut current = modal_sel.clone();
+        while words.len() < MAX_ACTION_WORDS {
+            if let Some((ws, _)) = current.match_first_forwards(&x::whitespace()) {
+                current = ws;
+            }
+            match current
+                .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            {
+                Some((next, (_, word))) => {
+                    words.push(word.to_lowercase());
+                    current = next;
+                }
+                None => break,
+            }
+        }
+        let negations = words.iter().take_while(|w| matches!(w.as_str(), "not" | "never")).count();
+        (words, negations)
+    }
+}
+
+impl Resolver for ModalityClassifier {
+    type Attr = Ambiguous<ObligationType>;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let modals = selection.find_by(&x::attr::<ContractKeyword>());
+        let leading = leading_coordinated_starts(modals.iter().map(|(sel, _)| sel));
+        modals
+            .into_iter()
+            .filter(|(modal_sel, _)| !leading.contains(&modal_sel.span_ref().start_idx))
+            .filter_map(|(modal_sel, keyword)| {
+                let keyword_type = ObligationType::from_keyword(keyword)?;
+                let (words, negations) = Self::action_after(&modal_sel);
+                let negated_action = keyword_type != ObligationType::Prohibition && negations > 0;
+                let action = if negated_action { &words[negations..] } else { &words[..] };
+                let action = action.join(" ");
+                let coordination = preceding_coordinated_modal(&modal_sel);
+                let evidence = ModalityEvidence {
+                    keyword,
+                    negated_action,
+                    action: &action,
+                    coordinated: coordination.as_ref().map(|(_, coordinated)| coordinated),
+                };
+                let vote = self.classify(&evidence)?;
+                if !vote.is_ambiguous() {
+                    return None;
+                }
+                let vote_sel = coordination.map_or(modal_sel, |(coordinated_sel, _)| coordinated_sel);
+                Some(vote_sel.finish_with_attr(vote))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AmbiguityFlag, ContractKeywordResolver};
+    use layered_nlp::create_line_from_string;
+
+    fn classify(keyword: ContractKeyword, negated_action: bool, action: &str) -> ObligationType {
+        let evidence = ModalityEvidence { keyword: &keyword, negated_action, action, coordinated: None };
+        ModalityClassifier::new().classify(&evidence).unwrap().best.value
+    }
+
+    #[test]
+    fn test_phrasing_outvotes_modal() {
+        let duty = classify(ContractKeyword::Shall, false, "deliver the goods");
+        assert_eq!(duty, ObligationType::Duty);
+        assert_eq!(
+            classify(ContractKeyword::Shall, false, "have the right to inspect"),
+            ObligationType::Permission
+        );
+        assert_eq!(
+            classify(ContractKeyword::ShallNot, false, "be required to renew"),
+            ObligationType::Permission
+        );
+        assert_eq!(classify(ContractKeyword::Shall, true, "disclose"), ObligationType::Prohibition);
+        assert_eq!(
+            classify(ContractKeyword::May, false, "refrain from competing"),
+            ObligationType::Prohibition
+        );
+    }
+
+    #[test]
+    fn test_close_vote_is_ambiguous() {
+        let classifier = ModalityClassifier::new();
+        let clear = ModalityEvidence {
+            keyword: &ContractKeyword::Shall,
+            negated_action: false,
+            action: "pay the Fees",
+            coordinated: None,
+        };
+        assert!(!classifier.classify(&clear).unwrap().is_ambiguous());
+
+        // Negation, modal and phrasing all disagree
+        let split = ModalityEvidence {
+            keyword: &ContractKeyword::Shall,
+            negated_action: true,
+            action: "be required to renew",
+            coordinated: None,
+        };
+        let vote = classifier.classify(&split).unwrap();
+        assert!(vote.is_ambiguous());
+        assert_eq!(vote.best.value, ObligationType::Prohibition);
+        assert_eq!(vote.flag, AmbiguityFlag::LowConfidence);
+
+        let line = create_line_from_string("The Supplier shall never be required to renew.")
+            .run(&ContractKeywordResolver::default())
+            .run(&classifier);
+        let found = line.find(&x::attr::<Ambiguous<ObligationType>>());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].attr().best.value, ObligationType::Prohibition);
+    }
+
+    #[test]
+    fn test_coordinated_modals_split_the_vote() {
+        let text = "The Company shall and may deliver the goods. The Buyer shall and/or may \
+                    inspect them. The Buyer shall and will pay.";
+        let line = create_line_from_string(text)
+        .run(&ContractKeywordResolver::default())
+        .run(&ModalityClassifier::new());
+        let found = line.find(&x::attr::<Ambiguous<ObligationType>>());
+        assert_eq!(found.len(), 2);
+        for vote in &found {
+            let vote = vote.attr();
+            assert_eq!(vote.best.value, ObligationType::Duty);
+            assert_eq!(vote.alternatives[0].value, ObligationType::Permission);
+            assert_eq!(vote.best.confidence, vote.alternatives[0].confidence);
+        }
+        let (start, end) = found[1].range();
+        assert_eq!(&text[start..end], "shall and/or may");
+    }
+}
@@ -0,0 +1,453 @@
+//! Shared parsing of numbers written in words or digits.
+//!
+//! ```text
+//! thirty (30) days          -> 30, written form "thirty"
+//! one hundred twenty-five   -> 125
+//! the third anniversary     -> 3, ordinal
+//! 1,000 units               -> 1000
+//! ```
+//!
+//! [`NumberParser`] is used by every resolver that reads numbers
+//! (`TemporalExpressionResolver`, `ObligationNormalizer`) so they agree on
+//! which forms are numbers.
+
+use std::ops::Range;
+
+/// A number recognized by [`NumberParser`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedNumber {
+    /// The numeric value
+    pub value: u64,
+    /// The words as written, for spelled-out numbers ("twenty-five")
+    pub written_form: Option<String>,
+    /// Whether the number is an ordinal ("third", "30th")
+    pub ordinal: bool,
+    /// The matched span, including a confirming "(30)": token indices for
+    /// [`parse_tokens`](NumberParser::parse_tokens), byte offsets for
+    /// [`find_all`](NumberParser::find_all)
+    pub span: Range<usize>,
+}
+
+/// Parser for written ("thirty", "one hundred twenty-five"), ordinal
+/// ("third", "twenty-first", "30th"), numeric ("30", "1,000") and mixed
+/// ("thirty (30)") numbers.
+///
+/// Words can be added with [`with_word`](Self::with_word) ("dozen"); a
+/// resolver given a parser through its `with_number_parser` builder
+/// recognizes them too.
+#[derive(Debug, Clone, Default)]
+pub struct NumberParser {
+    /// Additional cardinal words and their values
+    extra_words: Vec<(String, u64)>,
+}
+
+const UNITS: &[&str] = &[
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen",
+    "nineteen",
+];
+
+const TENS: &[(&str, u64)] = &[
+    ("twenty", 20),
+    ("thirty", 30),
+    ("forty", 40),
+    ("fourty", 40),
+    ("fifty", 50),
+    ("sixty", 60),
+    ("seventy", 70),
+    ("eighty", 80),
+    ("ninety", 90),
+];
+
+const ORDINALS: &[(&str, u64)] = &[
+    ("first", 1),
+    ("second", 2),
+    ("third", 3),
+    ("fourth", 4),
+    ("fifth", 5),
+    ("sixth", 6),
+    ("seventh", 7),
+    ("eighth", 8),
+    ("ninth", 9),
+    ("tenth", 10),
+    ("eleventh", 11),
+    ("twelfth", 12),
+    ("thirteenth", 13),
+    ("fourteenth", 14),
+    ("fifteenth", 15),
+    ("sixteenth", 16),
+    ("seventeenth", 17),
+    ("eighteenth", 18),
+    ("nineteenth", 19),
+    ("twentieth", 20),
+    ("thirtieth", 30),
+    ("fortieth", 40),
+    ("fiftieth", 50),
+    ("sixtieth", 60),
+    ("seventieth", 70),
+    ("eightieth", 80),
+    ("ninetieth", 90),
+];
+
+/// Multipliers closing a group ("two hundred", "five thousand").
+const SCALES: &[(&str, u64)] = &[
+    ("hundred", 100),
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+];
+
+/// Suffixes of numeric ordinals ("1st", "30th").
+const ORDINAL_SUFFIXES: &[&str] = &["st", "nd", "rd", "th"];
+
+/// Tokens a number may span ("one hundred and twenty-five (125)").
+pub(crate) const MAX_NUMBER_TOKENS: usize = 24;
+
+fn is_space(token: &str) -> bool {
+    !token.is_empty() && token.chars().all(char::is_whitespace)
+}
+
+fn is_digits(token: &str) -> bool {
+    !token.is_empty() && token.chars().all(|c| c.is_ascii_digit())
+}
+
+/// One step of a written number.
+enum Word {
+    /// A value below 100, `tens` when it can take a unit ("twenty")
+    Small { value: u64, tens: bool },
+    Ordinal(u64),
+    Scale(u64),
+    And,
+}
+
+impl NumberParser {
+    /// Create a parser for the standard English number words.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Recognize `word` (case-insensitive) as the cardinal `value`.
+    pub fn with_word(mut self, word: &str, value: u64) -> Self {
+        self.extra_words.push((word.to_lowercase(), value));
+        self
+    }
+
+    /// Parse a single word or numeral ("thirty", "twenty-five", "third",
+    /// "30", "30th").
+    pub fn parse_word(&self, word: &str) -> Option<u64> {
+        let tokens = tokenize(word);
+        let texts: Vec<&str> = tokens.iter().map(|(_, text)| *text).collect();
+        self.parse_tokens(&texts)
+            .filter(|parsed| parsed.span.end == texts.len())
+            .map(|parsed| parsed.value)
+    }
+
+    fn word(&self, word: &str) -> Option<Word> {
+        let lower = word.to_lowercase();
+        if let Some(value) = UNITS.iter().position(|unit| *unit == lower) {
+            return Some(Word::Small { value: value as u64, tens: false });
+        }
+        if let Some((_, value)) = TENS.iter().find(|(tens, _)| *tens == lower) {
+            return Some(Word::Small { value: *value, tens: true });
+        }
+        if let Some((_, value)) = self.extra_words.iter().find(|(extra, _)| *extra == lower) {
+            return Some(Word::Small { value: *value, tens: false });
+        }
+        if let Some((_, value)) = ORDINALS.iter().find(|(ordinal, _)| *ordinal == lower) {
+            return Some(Word::Ordinal(*value));
+        }
+        if let Some((_, value)) = SCALES.iter().find(|(scale, _)| *scale == lower) {
+            return Some(Word::Scale(*value));
+        }
+        if lower == "and" {
+            return Some(Word::And);
+        }
+        // A hyphenated compound kept as one token ("twenty-five")
+        let (tens, unit) = lower.split_once('-')?;
+        let (_, tens) = TENS.iter().find(|(word, _)| *word == tens)?;
+        match self.word(unit)? {
+            Word::Small { value, tens: false } if (1..10).contains(&value) => {
+                Some(Word::Small { value: tens + value, tens: false })
+            }
+            Word::Ordinal(value) if value < 10 => Some(Word::Ordinal(tens + value)),
+            _ => None,
+        }
+    }
+
+    /// Parse the number starting at `tokens[0]`.
+    ///
+    /// `tokens` are the texts of consecutive tokens, whitespace tokens
+    /// included, as produced by the line tokenizer ("twenty", "-", "five").
+    /// The returned span is the range of tokens consumed, ending on the last
+    /// token of the number.
+    pub fn parse_tokens(&self, tokens: &[&str]) -> Option<ParsedNumber> {
+        let tokens = &tokens[..tokens.len().min(MAX_NUMBER_TOKENS)];
+        let first = *tokens.first()?;
+        if first.starts_with(|c: char| c.is_ascii_digit()) {
+            return self.parse_numeral(tokens);
+        }
+
+        let mut total = 0u64;
+        let mut current = 0u64;
+        let mut ordinal = false;
+        let mut end = 0;
+        // Whether the last word was "and" (needs a following number word)
+        let mut after_and = false;
+        let mut last_was_tens = false;
+        let mut idx = 0;
+
+        while idx < tokens.len() && !ordinal {
+            if idx > 0 && is_space(tokens[idx]) {
+                idx += 1;
+                continue;
+            }
+            // "twenty" "-" "five"
+            let (word, next_idx) = match (tokens.get(idx + 1), tokens.get(idx + 2)) {
+                (Some(&"-"), Some(unit)) if last_word_is_tens(tokens[idx]) => {
+                    match self.word(&format!("{}-{}", tokens[idx], unit)) {
+                        Some(word) => (Some(word), idx + 3),
+                        None => (self.word(tokens[idx]), idx + 1),
+                    }
+                }
+                _ => (self.word(tokens[idx]), idx + 1),
+            };
+
+            match word {
+                Some(Word::Small { value, tens }) => {
+                    // "five six" is two numbers, "twenty five" is one
+                    let rem = current % 100;
+                    if !(rem == 0 || (last_was_tens && value < 10)) {
+                        break;
+                    }
+                    current += value;
+                    last_was_tens = tens;
+                }
+                Some(Word::Ordinal(value)) => {
+                    let rem = current % 100;
+                    if !(rem == 0 || (last_was_tens && value < 10)) {
+                        break;
+                    }
+                    current += value;
+                    ordinal = true;
+                }
+                Some(Word::Scale(scale)) if !after_and => {
+                    if scale == 100 {
+                        current = current.max(1).saturating_mul(100);
+                    } else {
+                        total = total.saturating_add(current.max(1).saturating_mul(scale));
+                        current = 0;
+                    }
+                    last_was_tens = false;
+                }
+                Some(Word::And) if end > 0 && !after_and && current.is_multiple_of(100) => {
+                    after_and = true;
+                    idx = next_idx;
+                    continue;
+                }
+                _ => break,
+            }
+            after_and = false;
+            end = next_idx;
+            idx = next_idx;
+        }
+
+        if end == 0 {
+            return None;
+        }
+        let value = total.saturating_add(current);
+        let written_form = tokens[..end].concat();
+        let mut span_end = end;
+        if !ordinal {
+            span_end += confirming_numeral(&tokens[end..], value);
+        }
+        Some(ParsedNumber {
+            value,
+            written_form: Some(written_form),
+            ordinal,
+            span: 0..span_end,
+        })
+    }
+
+    /// "30", "1,000", "30th" / "30" "th".
+    fn parse_numeral(&self, tokens: &[&str]) -> Option<ParsedNumber> {
+        let first = tokens[0];
+        let digits_end = first.find(|c: char| !c.is_ascii_digit()).unwrap_or(first.len());
+        let (digits, suffix) = first.split_at(digits_end);
+        let mut text = digits.to_string();
+        let mut end = 1;
+
+        if suffix.is_empty() && digits.len() <= 3 {
+            // Thousands groups: "1" "," "000"
+            while let (Some(&","), Some(group)) = (tokens.get(end), tokens.get(end + 1)) {
+                if group.len() != 3 || !is_digits(group) {
+                    break;
+                }
+                text.push_str(group);
+                end += 2;
+            }
+        }
+        let value = text.parse().ok()?;
+
+        let ordinal = match suffix {
+            "" => match tokens.get(end) {
+                Some(next) if ORDINAL_SUFFIXES.contains(&next.to_lowercase().as_str()) => {
+                    end += 1;
+                    true
+                }
+                _ => false,
+            },
+            suffix if ORDINAL_SUFFIXES.contains(&suffix.to_lowercase().as_str()) => true,
+            _ => return None,
+        };
+        Some(ParsedNumber {
+            value,
+            written_form: None,
+            ordinal,
+            span: 0..end,
+        })
+    }
+
+    /// Every number in `text`, in order, with byte spans.
+    ///
+    /// Words are matched whole, so "someone" holds no "one".
+    pub fn find_all(&self, text: &str) -> Vec<ParsedNumber> {
+        let tokens = tokenize(text);
+        let texts: Vec<&str> = tokens.iter().map(|(_, text)| *text).collect();
+
+        let mut found = Vec::new();
+        let mut idx = 0;
+        while idx < tokens.len() {
+            let parsed = match self.parse_tokens(&texts[idx..]) {
+                Some(parsed) if !is_space(texts[idx]) => parsed,
+                _ => {
+                    idx += 1;
+                    continue;
+                }
+            };
+            let last = idx + parsed.span.end - 1;
+            let start = tokens[idx].0;
+            let end = tokens[last].0 + tokens[last].1.len();
+            found.push(ParsedNumber {
+                span: start..end,
+                ..parsed
+            });
+            idx = last + 1;
+        }
+        found
+    }
+}
+
+fn last_word_is_tens(word: &str) -> bool {
+    let lower = word.to_lowercase();
+    TENS.iter().any(|(tens, _)| *tens == lower)
+}
+
+/// Tokens of a "(30)" following a written number with the same value.
+fn confirming_numeral(tokens: &[&str], value: u64) -> usize {
+    let skipped = usize::from(tokens.first().is_some_and(|token| is_space(token)));
+    let rest = &tokens[skipped..];
+    if rest.first() != Some(&"(") {
+        return 0;
+    }
+    let close = match rest.iter().position(|token| *token == ")") {
+        Some(close) => close,
+        None => return 0,
+    };
+    let digits: String = rest[1..close].iter().filter(|token| **token != ",").copied().collect();
+    if is_digits(&digits) && digits.parse() == Ok(value) {
+        skipped + close + 1
+    } else {
+        0
+    }
+}
+
+/// Split text like the line tokenizer: runs of letters, runs of digits, runs
+/// of whitespace, and single other characters, with byte offsets.
+fn tokenize(text: &str) -> Vec<(usize, &str)> {
+    #[derive(PartialEq)]
+    enum Class {
+        Alpha,
+        Digit,
+        Space,
+        Other,
+    }
+    let class = |c: char| {
+        if c.is_alphabetic() {
+            Class::Alpha
+        } else if c.is_ascii_digit() {
+            Class::Digit
+        } else if c.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Other
+        }
+    };
+
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<Class> = None;
+    for (idx, c) in text.char_indices() {
+        let current = class(c);
+        let joins = previous.as_ref().is_some_and(|previous| {
+            *previous == current && current != Class::Other
+        });
+        if !joins && idx > start {
+            tokens.push((start, &text[start..idx]));
+            start = idx;
+        }
+        previous = Some(current);
+    }
+    if start < text.len() {
+        tokens.push((start, &text[start..]));
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(text: &str) -> Vec<u64> {
+        NumberParser::new().find_all(text).iter().map(|parsed| parsed.value).collect()
+    }
+
+    #[test]
+    fn test_written_compounds() {
+        let parser = NumberParser::new();
+        assert_eq!(parser.parse_word("thirty"), Some(30));
+        assert_eq!(parser.parse_word("twenty-five"), Some(25));
+        assert_eq!(parser.parse_word("Ninety"), Some(90));
+        assert_eq!(parser.parse_word("someone"), None);
+
+        assert_eq!(values("one hundred twenty-five days"), vec![125]);
+        assert_eq!(values("two thousand and twelve units"), vec![2012]);
+        assert_eq!(values("five six"), vec![5, 6]);
+        assert_eq!(values("done within ten days"), vec![10]);
+    }
+
+    #[test]
+    fn test_ordinals_and_numerals() {
+        let found = NumberParser::new().find_all("the twenty-first day and the 30th day");
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].value, found[0].ordinal), (21, true));
+        assert_eq!((found[1].value, found[1].ordinal), (30, true));
+        assert_eq!(found[1].written_form, None);
+
+        assert_eq!(values("1,000 units, 250 boxes"), vec![1000, 250]);
+        let dozen = NumberParser::new().with_word("dozen", 12);
+        assert_eq!(dozen.parse_word("Dozen"), Some(12));
+    }
+
+    #[test]
+    fn test_mixed_form_span() {
+        let text = "within thirty (30) days";
+        let found = NumberParser::new().find_all(text);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].value, 30);
+        assert_eq!(found[0].written_form.as_deref(), Some("thirty"));
+        assert_eq!(&text[found[0].span.clone()], "thirty (30)");
+
+        // A disagreeing numeral is a separate number
+        assert_eq!(values("thirty (31) days"), vec![30, 31]);
+    }
+}
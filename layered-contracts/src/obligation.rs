@@ -12,13 +12,21 @@
 //!                      ╰────────────╯action
 //! ```
 
-use layered_nlp::{x, Association, LLCursorAssignment, LLSelection, Resolver, SpanRef, TextTag};
+use layered_nlp::{
+    x, Association, LLCursorAssignment, LLSelection, Resolver, ResolverTrace, SpanRef, TextTag,
+};
 use layered_part_of_speech::Tag;
 
-use crate::contract_keyword::ContractKeyword;
+use crate::conflict_detector::ObligationNormalizer;
+use crate::contract_keyword::{permission_idiom_penalty, ContractKeyword};
+use crate::modality::{
+    leading_coordinated_starts, preceding_coordinated_modal, ModalityClassifier, ModalityEvidence,
+};
 use crate::pronoun::PronounReference;
-use crate::Scored;
+use crate::scope_operators::NegationDetector;
+use crate::{DocSpan, NegationOp, ScopeDimension, ScopeIndex, ScopeOperator, Scored};
 use crate::sentence_boundary::SentenceBoundaryResolver;
+use crate::temporal::TemporalExpression;
 use crate::term_reference::TermReference;
 
 /// Association linking an obligation to its obligor source span.
@@ -65,7 +73,9 @@ impl ObligationType {
             ContractKeyword::Shall
             | ContractKeyword::Must
             | ContractKeyword::Will => Some(Self::Duty),
-            ContractKeyword::May | ContractKeyword::Can => Some(Self::Permission),
+            ContractKeyword::May
+            | ContractKeyword::Can
+            | ContractKeyword::PermissionIdiom => Some(Self::Permission),
             ContractKeyword::ShallNot
             | ContractKeyword::MustNot
             | ContractKeyword::MayNot
@@ -76,6 +86,78 @@ impl ObligationType {
     }
 }
 
+/// What an obligation commits its obligor to, inferred from its verb.
+///
+/// Only performance duties ask the obligor to do something; the others are
+/// statements of fact or promises that are breached rather than performed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize)]
+pub enum ObligationNature {
+    /// A duty to act or refrain: "shall deliver the Goods"
+    #[default]
+    Performance,
+    /// A statement of fact: "shall represent that it is duly organized"
+    Representation,
+    /// An assurance of quality or condition: "will warrant that the Software works"
+    Warranty,
+    /// Recognition of a fact: "shall acknowledge that the Buyer relies on ..."
+    Acknowledgment,
+    /// A promise about future conduct: "shall agree to", "will covenant that"
+    Covenant,
+}
+
+/// Words that may come between the modal and the verb ("shall hereby represent").
+const VERB_PREFIXES: &[&str] = &["also", "further", "hereby", "not"];
+
+impl ObligationNature {
+    /// Classify an action by its first verb, lemmatized with `normalizer`.
+    ///
+    /// Leading adverbs ("promptly", "hereby") are skipped; an action whose
+    /// verb is not represent, warrant, acknowledge, agree or covenant is a
+    /// performance duty.
+    pub fn from_action(action: &str, normalizer: &ObligationNormalizer) -> Self {
+        let verb = normalizer
+            .normalize_action(action)
+            .split_whitespace()
+            .find(|word| !word.ends_with("ly") && !VERB_PREFIXES.contains(word))
+            .map(str::to_string);
+        match verb.as_deref() {
+            Some("represent") => Self::Representation,
+            Some("warrant") => Self::Warranty,
+            Some("acknowledge") => Self::Acknowledgment,
+            Some("agree") | Some("covenant") => Self::Covenant,
+            _ => Self::Performance,
+        }
+    }
+
+    /// Whether the obligation is a duty to act, rather than a statement or promise.
+    pub fn is_performance(self) -> bool {
+        self == Self::Performance
+    }
+}
+
+/// The standard of discretion a party exercises, from phrases such as "in its
+/// sole discretion" or "at its option".
+///
+/// Sole discretion lets the party act for any reason; reasonable discretion
+/// can be challenged when exercised arbitrarily.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum DiscretionStandard {
+    /// "in its sole discretion", "in its sole and absolute discretion", "at its sole option"
+    Sole,
+    /// "in its reasonable discretion", "in its good faith discretion"
+    Reasonable,
+    /// "in its discretion", "at its option", "at its election"
+    Unqualified,
+}
+
+/// Words that may come between the possessive and "discretion" or "option".
+const DISCRETION_QUALIFIERS: &[&str] = &[
+    "-", "absolute", "and", "commercially", "exclusive", "faith", "good", "reasonable", "sole",
+];
+
+/// Display text for [`ObligorReference::Unknown`].
+pub const UNKNOWN_OBLIGOR: &str = "(unknown)";
+
 /// Reference to who has the obligation.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum ObligorReference {
@@ -99,6 +181,92 @@ pub enum ObligorReference {
     NounPhrase {
         text: String,
     },
+    /// Agentless passive ("The fees shall be paid"): the text does not say who
+    /// bears the obligation
+    Unknown,
+}
+
+/// Determiners that may open an obligor noun phrase ("The relevant authority").
+const OBLIGOR_DETERMINERS: &[&str] = &["a", "an", "any", "each", "its", "such", "the", "their"];
+
+/// Maximum number of words after the determiner of a lowercase obligor.
+const MAX_VAGUE_SUBJECT_WORDS: usize = 3;
+
+impl ObligorReference {
+    /// Whether the obligor could not be tied to a defined term or named party.
+    ///
+    /// True for an agentless passive and for a noun phrase without a
+    /// capitalized name ("the relevant authority shall approve"). A
+    /// capitalized noun phrase ("the Supplier") is taken to name a party
+    /// defined elsewhere in the document.
+    pub fn is_unresolved(&self) -> bool {
+        match self {
+            ObligorReference::TermRef { .. } | ObligorReference::PronounRef { .. } => false,
+            ObligorReference::NounPhrase { text } => {
+                let mut words = text.split_whitespace().peekable();
+                if let Some(first) = words.peek() {
+                    if OBLIGOR_DETERMINERS.contains(&first.to_lowercase().as_str()) {
+                        words.next();
+                    }
+                }
+                !words.any(|word| word.starts_with(char::is_uppercase))
+            }
+            ObligorReference::Unknown => true,
+        }
+    }
+}
+
+/// How [`ObligationPhraseResolver`] chooses among the obligor candidates
+/// before the modal (defined-term references, resolved pronouns and plain
+/// noun phrases).
+///
+/// Every strategy is compared against [`Nearest`](Self::Nearest): when the
+/// preferred candidate is also the nearest one the two heuristics agree and
+/// the obligor score gains [`AGREEMENT_BONUS`](Self::AGREEMENT_BONUS); when it
+/// overrides a nearer candidate the score pays the strategy's override
+/// penalty (see [`confidence_adjustment`](Self::confidence_adjustment)). A
+/// strategy that finds no candidate of its kind falls back to the nearest
+/// candidate with no adjustment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ObligorStrategy {
+    /// The defined term or resolved pronoun nearest the modal, falling back
+    /// to the nearest plain noun phrase. No adjustment.
+    #[default]
+    Nearest,
+    /// The nearest defined term, directly or through a pronoun. Overriding a
+    /// nearer candidate costs 0.05.
+    PreferDefinedTerm,
+    /// The nearest candidate of any kind, so a plain noun phrase wins over a
+    /// farther defined term. Overriding a nearer defined term or pronoun costs
+    /// 0.10, since the plain phrase is unverified.
+    NearestNounPhrase,
+    /// The first candidate of the sentence that is not the object of a
+    /// preposition (POS-tagged, looking past articles), so "Upon notice
+    /// from the Company, the Carrier shall ..." gives "Carrier". Overriding a
+    /// nearer candidate costs 0.05.
+    SentenceSubject,
+}
+
+impl ObligorStrategy {
+    /// Obligor bonus when the strategy's choice is also the nearest candidate.
+    pub const AGREEMENT_BONUS: f64 = 0.05;
+
+    /// The obligor-resolution adjustment for a choice that does or does not
+    /// agree with the nearest candidate.
+    pub fn confidence_adjustment(self, agrees_with_nearest: bool) -> f64 {
+        match (self, agrees_with_nearest) {
+            (ObligorStrategy::Nearest, _) => 0.0,
+            (_, true) => Self::AGREEMENT_BONUS,
+            (ObligorStrategy::NearestNounPhrase, false) => -0.10,
+            (_, false) => -0.05,
+        }
+    }
+}
+
+/// An obligor candidate before the modal, for [`ObligorStrategy`] selection.
+struct ObligorCandidate {
+    obligor: ObligorReference,
+    span: SpanRef,
 }
 
 /// A reference to a condition that qualifies this obligation.
@@ -110,6 +278,112 @@ pub struct ConditionRef {
     pub text_preview: String,
 }
 
+impl ConditionRef {
+    /// Whether the condition only concerns timing ("if within 30 days",
+    /// "subject to the expiry of the Term") rather than an event or state.
+    pub fn is_temporal(&self) -> bool {
+        is_temporal_condition(&self.condition_type, &self.text_preview)
+    }
+}
+
+/// Words that open a timing condition ("after", "within", ...).
+const TEMPORAL_CONDITION_STARTS: &[&str] = &[
+    "after", "before", "by", "during", "following", "once", "prior", "until", "upon", "within",
+];
+
+/// Words that make a condition about time wherever they appear.
+const TEMPORAL_CONDITION_WORDS: &[&str] = &[
+    "date", "day", "days", "expiration", "expiry", "month", "months", "week", "weeks", "year",
+    "years",
+];
+
+/// Weight of a timing condition relative to an event or state condition.
+const TEMPORAL_CONDITION_WEIGHT: f64 = 0.5;
+
+/// Whether a condition concerns timing rather than an event or state.
+///
+/// Exceptions and provisos ("unless", "provided that") lift or qualify the
+/// obligation outright, so they count as event or state conditions however
+/// their text is phrased.
+pub(crate) fn is_temporal_condition(condition_type: &ContractKeyword, text: &str) -> bool {
+    if matches!(condition_type, ContractKeyword::Unless | ContractKeyword::Provided) {
+        return false;
+    }
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    words.first().is_some_and(|first| TEMPORAL_CONDITION_STARTS.contains(&first.as_str()))
+        || words.iter().any(|word| TEMPORAL_CONDITION_WORDS.contains(&word.as_str()))
+}
+
+/// Score in `0.0..1.0` for how strongly conditions gate an obligation.
+///
+/// Each event or state condition weighs 1.0 and each timing condition
+/// [`TEMPORAL_CONDITION_WEIGHT`]; the total `w` maps to `w / (w + 1)`, so an
+/// unconditional obligation scores 0.0 and every further condition adds less.
+pub(crate) fn conditionality_score<'t>(
+    conditions: impl IntoIterator<Item = (&'t ContractKeyword, &'t str)>,
+) -> f64 {
+    let weight: f64 = conditions
+        .into_iter()
+        .map(|(condition_type, text)| {
+            if is_temporal_condition(condition_type, text) {
+                TEMPORAL_CONDITION_WEIGHT
+            } else {
+                1.0
+            }
+        })
+        .sum();
+    weight / (weight + 1.0)
+}
+
+/// The factors that produced an obligation phrase's confidence score.
+///
+/// Each field is an additive contribution; [`total`](Self::total) combines them
+/// (clamped to `0.0..=1.0`) into the `Scored<ObligationPhrase>` confidence.
+/// Reviewers can use [`weakest_factor`](Self::weakest_factor) to see which
+/// factor pulled the score down the most.
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct ObligationConfidenceBreakdown {
+    /// Base score contributed by the modal keyword anchoring the obligation
+    pub keyword_strength: f64,
+    /// Adjustment from how the obligor was resolved (defined term, pronoun
+    /// chain, competing candidates)
+    pub obligor_resolution: f64,
+    /// Adjustment from how clear the extracted action is
+    pub action_clarity: f64,
+    /// Adjustment from how completely attached conditions were captured
+    pub condition_completeness: f64,
+}
+
+impl ObligationConfidenceBreakdown {
+    /// Combine the factors into a single confidence score.
+    pub fn total(&self) -> f64 {
+        (self.keyword_strength
+            + self.obligor_resolution
+            + self.action_clarity
+            + self.condition_completeness)
+            .clamp(0.0, 1.0)
+    }
+
+    /// The adjustment that lowered the score the most, if any did.
+    ///
+    /// Returns the factor name and its (negative) contribution.
+    pub fn weakest_factor(&self) -> Option<(&'static str, f64)> {
+        [
+            ("obligor_resolution", self.obligor_resolution),
+            ("action_clarity", self.action_clarity),
+            ("condition_completeness", self.condition_completeness),
+        ]
+        .iter()
+        .copied()
+        .filter(|(_, value)| *value < 0.0)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+    }
+}
+
 /// An obligation phrase extracted from contract text.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct ObligationPhrase {
@@ -121,6 +395,92 @@ pub struct ObligationPhrase {
     pub action: String,
     /// Any conditions attached (if/unless/provided)
     pub conditions: Vec<ConditionRef>,
+    /// How the confidence score for this phrase was derived
+    pub confidence_breakdown: ObligationConfidenceBreakdown,
+    /// Parenthetical qualifiers set off from the action, e.g. "(subject to availability)".
+    ///
+    /// Spans are line-local: `line` is always 0 and the token indices refer to the
+    /// obligation's own line; [`qualifiers_on_line`](Self::qualifiers_on_line)
+    /// places them in a document. Qualifier words are not included in `action`.
+    pub qualifiers: Vec<DocSpan>,
+    /// Grammatical subject of a passive obligation, i.e. what the action is done to
+    /// ("The fees" in "The fees shall be paid by the Company"). `None` for active voice.
+    pub object: Option<String>,
+    /// Party that performs a flow-down obligation ("its Affiliates" in "The Company
+    /// shall cause its Affiliates to comply"). The obligor remains responsible and
+    /// `action` is what the performer does. `None` when the obligor acts itself.
+    pub performer: Option<String>,
+    /// Whether the obligation is a performance duty or a representation,
+    /// warranty, acknowledgment or covenant
+    pub obligation_nature: ObligationNature,
+    /// Discretion the obligor is given ("may, in its sole discretion,
+    /// terminate"). The phrase is not included in `action`.
+    pub discretion: Option<DiscretionStandard>,
+}
+
+impl ObligationPhrase {
+    /// How strongly the attached conditions gate this obligation, in `0.0..1.0`.
+    ///
+    /// An unconditional "shall pay" scores 0.0; each condition raises the
+    /// score, timing conditions ("within 30 days") half as much as event or
+    /// state conditions ("if the Buyer defaults"). Low scores mark the
+    /// obligations carrying the most exposure for the obligor.
+    pub fn conditionality_score(&self) -> f64 {
+        conditionality_score(
+            self.conditions.iter().map(|c| (&c.condition_type, c.text_preview.as_str())),
+        )
+    }
+
+    /// Whether no party can be held to this obligation: the obligor is an
+    /// agentless passive or a noun phrase naming no party (see
+    /// [`ObligorReference::is_unresolved`]).
+    ///
+    /// The obligation-level counterpart of `ClauseCondition::mentions_unknown_entity`,
+    /// for surfacing vague assignments of responsibility to reviewers.
+    pub fn has_unknown_obligor(&self) -> bool {
+        self.obligor.is_unresolved()
+    }
+
+    /// The [`qualifiers`](Self::qualifiers) placed on document line `line`,
+    /// the line the obligation was found on.
+    pub fn qualifiers_on_line(&self, line: usize) -> Vec<DocSpan> {
+        self.qualifiers
+            .iter()
+            .map(|span| DocSpan::single_line(line, span.start.token, span.end.token))
+            .collect()
+    }
+}
+
+/// Irregular past participles that can head a passive obligation ("shall be paid").
+const IRREGULAR_PARTICIPLES: &[&str] = &[
+    "borne", "bought", "brought", "built", "chosen", "done", "drawn", "given", "held", "kept",
+    "known", "laid", "made", "met", "paid", "put", "sent", "set", "shown", "sold", "sought",
+    "spent", "taken", "told", "undertaken", "withheld", "written",
+];
+
+/// Participles that read as adjectives after "be" ("shall be entitled to"), where the
+/// grammatical subject still bears the obligation or no obligation is imposed at all.
+const STATIVE_PARTICIPLES: &[&str] = &[
+    "allowed", "authorized", "bound", "concerned", "considered", "construed", "deemed",
+    "entitled", "excused", "governed", "interpreted", "obligated", "permitted", "prohibited",
+    "required",
+];
+
+/// Month names, so "paid by March 1" is read as a deadline even without temporal tagging.
+const MONTH_NAMES: &[&str] = &[
+    "january", "february", "march", "april", "may", "june", "july", "august", "september",
+    "october", "november", "december",
+];
+
+/// Maximum number of words naming the performer in "cause ... to".
+const MAX_PERFORMER_WORDS: usize = 6;
+
+/// A passive construction found after a modal.
+struct PassiveVoice {
+    /// The grammatical subject, which the action is done to
+    object: Option<String>,
+    /// The "by" agent and its span, if the sentence names one
+    agent: Option<(ObligorReference, SpanRef)>,
 }
 
 /// Resolver for detecting obligation phrases.
@@ -130,6 +490,15 @@ pub struct ObligationPhrase {
 /// - `DefinedTermResolver`
 /// - `TermReferenceResolver`
 /// - `PronounResolver`
+///
+/// The obligation type is voted by a [`ModalityClassifier`] from the modal
+/// keyword, a negation leading the action ("shall never disclose" is a
+/// prohibition) and the phrasing after the modal ("shall have the right to"
+/// is a permission).
+///
+/// Coordinated modals ("shall and may") form one obligation spanning both
+/// modals. Each modal votes, so the type is flagged as ambiguous when they
+/// disagree, and the obligation's keyword strength is penalized.
 pub struct ObligationPhraseResolver {
     /// Base confidence when modal + obligor found
     base_confidence: f64,
@@ -141,6 +510,23 @@ pub struct ObligationPhraseResolver {
     multiple_obligor_penalty: f64,
     /// Penalty for empty/minimal action span
     empty_action_penalty: f64,
+    /// Penalty per condition whose preview was truncated (disabled by default;
+    /// see [`with_truncated_condition_penalty`](Self::with_truncated_condition_penalty))
+    truncated_condition_penalty: f64,
+    /// Penalty when a passive obligation names no agent
+    unknown_obligor_penalty: f64,
+    /// Penalty when the obligor is a noun phrase naming no party
+    unresolved_obligor_penalty: f64,
+    /// Penalty when coordinated modals disagree ("shall and may")
+    mixed_modality_penalty: f64,
+    /// Finds negations inside the action that the modal keyword doesn't carry
+    negation_detector: NegationDetector,
+    /// How to choose among obligor candidates
+    obligor_strategy: ObligorStrategy,
+    /// Votes the obligation type from the modal, negation and phrasing
+    modality_classifier: ModalityClassifier,
+    /// Lemmatizes the action's verb to classify its nature
+    normalizer: ObligationNormalizer,
 }
 
 impl Default for ObligationPhraseResolver {
@@ -151,6 +537,14 @@ impl Default for ObligationPhraseResolver {
             pronoun_chain_bonus: 0.05,
             multiple_obligor_penalty: 0.15,
             empty_action_penalty: 0.10,
+            truncated_condition_penalty: 0.0,
+            unknown_obligor_penalty: 0.35,
+            unresolved_obligor_penalty: 0.10,
+            mixed_modality_penalty: 0.15,
+            negation_detector: NegationDetector::new(),
+            obligor_strategy: ObligorStrategy::default(),
+            modality_classifier: ModalityClassifier::default(),
+            normalizer: ObligationNormalizer::new(),
         }
     }
 }
@@ -161,6 +555,32 @@ impl ObligationPhraseResolver {
         Self::default()
     }
 
+    /// Choose obligors with `strategy` instead of the nearest candidate.
+    pub fn with_obligor_strategy(mut self, strategy: ObligorStrategy) -> Self {
+        self.obligor_strategy = strategy;
+        self
+    }
+
+    /// Vote obligation types with `classifier` (custom weights or thresholds).
+    pub fn with_modality_classifier(mut self, classifier: ModalityClassifier) -> Self {
+        self.modality_classifier = classifier;
+        self
+    }
+
+    /// Set the penalty for a noun-phrase obligor naming no party
+    /// ("the relevant authority"); 0.0 disables it.
+    pub fn with_unresolved_obligor_penalty(mut self, penalty: f64) -> Self {
+        self.unresolved_obligor_penalty = penalty;
+        self
+    }
+
+    /// Set the penalty per condition whose preview had to be truncated; 0.0
+    /// (the default) disables it.
+    pub fn with_truncated_condition_penalty(mut self, penalty: f64) -> Self {
+        self.truncated_condition_penalty = penalty;
+        self
+    }
+
     /// Trim trailing conjunctions and incomplete party references from action text.
     ///
     /// Handles several patterns:
@@ -232,6 +652,7 @@ impl ObligationPhraseResolver {
                 | ContractKeyword::May
                 | ContractKeyword::Can
                 | ContractKeyword::Will
+                | ContractKeyword::PermissionIdiom
                 | ContractKeyword::ShallNot
                 | ContractKeyword::MustNot
                 | ContractKeyword::MayNot
@@ -248,9 +669,155 @@ impl ObligationPhraseResolver {
                 | ContractKeyword::May
                 | ContractKeyword::Can
                 | ContractKeyword::Will
+                | ContractKeyword::PermissionIdiom
         )
     }
 
+    /// Discretion phrases in the line: "in its sole discretion", "at its option".
+    ///
+    /// `texts` are the lowercased token texts from [`token_texts`](Self::token_texts).
+    fn discretion_phrases(texts: &[Option<String>]) -> Vec<(SpanRef, DiscretionStandard)> {
+        let words: Vec<(usize, &str)> = texts
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, text)| Some((idx, text.as_deref()?)))
+            .filter(|(_, text)| !text.trim().is_empty())
+            .collect();
+
+        let mut phrases = Vec::new();
+        for (i, &(start_idx, opener)) in words.iter().enumerate() {
+            let possessive = words.get(i + 1).map(|(_, text)| *text);
+            if !matches!(opener, "in" | "at")
+                || !matches!(possessive, Some("its" | "their" | "his" | "her"))
+            {
+                continue;
+            }
+            let qualifiers: Vec<&str> = words[i + 2..]
+                .iter()
+                .map(|(_, text)| *text)
+                .take_while(|text| DISCRETION_QUALIFIERS.contains(text))
+                .collect();
+            let Some(&(end_idx, head)) = words.get(i + 2 + qualifiers.len()) else {
+                continue;
+            };
+            if !(head == "discretion" || opener == "at" && matches!(head, "option" | "election")) {
+                continue;
+            }
+            let has = |options: &[&str]| qualifiers.iter().any(|word| options.contains(word));
+            let standard = if has(&["sole", "absolute", "exclusive"]) {
+                DiscretionStandard::Sole
+            } else if has(&["reasonable", "faith"]) {
+                DiscretionStandard::Reasonable
+            } else if qualifiers.is_empty() {
+                DiscretionStandard::Unqualified
+            } else {
+                continue;
+            };
+            phrases.push((SpanRef { start_idx, end_idx }, standard));
+        }
+        phrases
+    }
+
+    /// The modal a discretion phrase qualifies: the nearest modal before it in
+    /// the same sentence, or else the first one after it ("In its sole
+    /// discretion, the Licensor may revoke").
+    fn discretion_owner(
+        texts: &[Option<String>],
+        modal_starts: &[usize],
+        phrase: SpanRef,
+    ) -> Option<usize> {
+        let is_boundary = |idx: usize| matches!(texts[idx].as_deref(), Some("." | ";" | "!" | "?"));
+        let same_sentence = |from: usize, to: usize| !(from..to).any(is_boundary);
+        modal_starts
+            .iter()
+            .rev()
+            .find(|&&start| start < phrase.start_idx)
+            .filter(|&&start| same_sentence(start, phrase.start_idx))
+            .or_else(|| {
+                modal_starts
+                    .iter()
+                    .find(|&&start| start > phrase.end_idx)
+                    .filter(|&&start| same_sentence(phrase.end_idx, start))
+            })
+            .copied()
+    }
+
+    /// Extend a selection through the discretion phrase starting right after it.
+    fn skip_discretion(selection: &LLSelection, discretion: &[SpanRef]) -> Option<LLSelection> {
+        let next_idx = selection.span_ref().end_idx + 1;
+        let phrase = discretion.iter().find(|span| span.start_idx == next_idx)?;
+        let mut current = selection.clone();
+        while current.span_ref().end_idx < phrase.end_idx {
+            current = current.match_first_forwards(&x::token_text())?.0;
+        }
+        Some(current)
+    }
+
+    /// Whether a span lies inside one of the discretion phrases.
+    fn in_discretion_phrase(discretion: &[SpanRef], span: SpanRef) -> bool {
+        discretion
+            .iter()
+            .any(|phrase| phrase.start_idx <= span.start_idx && span.end_idx <= phrase.end_idx)
+    }
+
+    /// Lowercased text of each token in the line, `None` for non-text tokens.
+    fn token_texts(selection: &LLSelection) -> Vec<Option<String>> {
+        let mut texts = vec![None; selection.span_ref().end_idx + 1];
+        for (token_sel, text) in selection.find_by(&x::token_text()) {
+            texts[token_sel.span_ref().start_idx] = Some(text.to_lowercase());
+        }
+        texts
+    }
+
+    /// Count the leading action words that are negation triggers scoping over
+    /// the end of the action ("never" in "shall never disclose").
+    ///
+    /// Word spans run from the modal, so each word's own token is its `end_idx`.
+    fn leading_negations(negations: &ScopeIndex<NegationOp>, word_spans: &[SpanRef]) -> usize {
+        let last = match word_spans.last() {
+            Some(last) => DocSpan::single_line(0, last.end_idx, last.end_idx),
+            None => return 0,
+        };
+        word_spans
+            .iter()
+            .take_while(|word| {
+                negations
+                    .of_dimension_covering_span(ScopeDimension::Negation, &last)
+                    .any(|op| op.trigger.start.token == word.end_idx)
+            })
+            .count()
+    }
+
+    /// Detect a flow-down frame ("shall cause its Affiliates to comply") at the
+    /// start of the action.
+    ///
+    /// "cause" or "procure" must be tagged as a verb and be followed, within a
+    /// few words, by "to" and another verb. Returns the number of words before
+    /// the performed action and the performing party.
+    fn detect_flow_down(
+        selection: &LLSelection,
+        words: &[&str],
+        word_spans: &[SpanRef],
+    ) -> Option<(usize, String)> {
+        let verbs: Vec<usize> = selection
+            .find_by(&x::attr_eq(&Tag::Verb))
+            .iter()
+            .map(|(sel, _)| sel.span_ref().start_idx)
+            .collect();
+        let is_verb = |word: usize| verbs.contains(&word_spans[word].end_idx);
+
+        let frame = words.first()?.to_lowercase();
+        if !matches!(frame.as_str(), "cause" | "procure") || !is_verb(0) {
+            return None;
+        }
+        let to = (2..words.len().min(MAX_PERFORMER_WORDS + 2))
+            .find(|&i| words[i].eq_ignore_ascii_case("to"))?;
+        if to + 1 >= words.len() || !is_verb(to + 1) {
+            return None;
+        }
+        Some((to + 1, words[1..to].join(" ")))
+    }
+
     fn is_negated_modal(keyword: &ContractKeyword) -> bool {
         matches!(
             keyword,
@@ -262,14 +829,160 @@ impl ObligationPhraseResolver {
         )
     }
 
-    /// Find the nearest obligor (TermReference or PronounReference) before the modal.
+    /// Find the obligor before the modal using the configured [`ObligorStrategy`].
     ///
-    /// Returns (obligor, has_multiple_candidates, source_span) where source_span
-    /// is the token span of the obligor reference.
+    /// Returns (obligor, has_multiple_candidates, source_span, strategy_adjustment)
+    /// where source_span is the token span of the obligor reference.
     fn find_obligor(
         &self,
         selection: &LLSelection,
         modal_sel: &LLSelection,
+        discretion: &[SpanRef],
+    ) -> Option<(ObligorReference, bool, SpanRef, f64)> {
+        let (nearest, has_multiple, nearest_span) =
+            self.find_nearest_obligor(selection, modal_sel, discretion)?;
+
+        let candidates = match self.obligor_strategy {
+            ObligorStrategy::Nearest => Vec::new(),
+            _ => self.obligor_candidates(selection, modal_sel, discretion),
+        };
+        let preferred = match self.obligor_strategy {
+            ObligorStrategy::Nearest => None,
+            ObligorStrategy::PreferDefinedTerm => candidates.into_iter().rev().find(|c| {
+                matches!(
+                    c.obligor,
+                    ObligorReference::TermRef { .. }
+                        | ObligorReference::PronounRef { is_defined_term: true, .. }
+                )
+            }),
+            ObligorStrategy::NearestNounPhrase => candidates.into_iter().last(),
+            ObligorStrategy::SentenceSubject => {
+                self.sentence_subject(selection, modal_sel, candidates)
+            }
+        };
+
+        match preferred {
+            Some(candidate) => {
+                let agrees = candidate.span == nearest_span;
+                let adjustment = self.obligor_strategy.confidence_adjustment(agrees);
+                Some((candidate.obligor, has_multiple, candidate.span, adjustment))
+            }
+            None => Some((nearest, has_multiple, nearest_span, 0.0)),
+        }
+    }
+
+    /// Every obligor candidate before the modal, in text order: defined-term
+    /// references, resolved pronouns outside discretion phrases, and plain
+    /// noun phrases that don't overlap either.
+    fn obligor_candidates(
+        &self,
+        selection: &LLSelection,
+        modal_sel: &LLSelection,
+        discretion: &[SpanRef],
+    ) -> Vec<ObligorCandidate> {
+        let mut candidates: Vec<ObligorCandidate> = selection
+            .find_by(&x::attr::<Scored<TermReference>>())
+            .into_iter()
+            .filter(|(sel, _)| self.selection_is_before(sel, modal_sel))
+            .map(|(sel, term_ref)| ObligorCandidate {
+                obligor: self.term_to_obligor(term_ref),
+                span: sel.span_ref(),
+            })
+            .collect();
+        candidates.extend(
+            selection
+                .find_by(&x::attr::<Scored<PronounReference>>())
+                .into_iter()
+                .filter(|(sel, pron_ref)| {
+                    self.selection_is_before(sel, modal_sel)
+                        && !pron_ref.value.candidates.is_empty()
+                        && !Self::in_discretion_phrase(discretion, sel.span_ref())
+                })
+                .map(|(sel, pron_ref)| ObligorCandidate {
+                    obligor: self.pronoun_to_obligor(pron_ref),
+                    span: sel.span_ref(),
+                }),
+        );
+
+        let overlaps = |span: &SpanRef, other: &SpanRef| {
+            span.start_idx <= other.end_idx && other.start_idx <= span.end_idx
+        };
+        let phrases: Vec<ObligorCandidate> = self
+            .noun_phrases(selection, modal_sel)
+            .into_iter()
+            .filter(|(_, span)| !candidates.iter().any(|c| overlaps(span, &c.span)))
+            .map(|(text, span)| ObligorCandidate {
+                obligor: ObligorReference::NounPhrase { text },
+                span,
+            })
+            .collect();
+        candidates.extend(phrases);
+        candidates.sort_by_key(|c| c.span.start_idx);
+        candidates
+    }
+
+    /// The first candidate in the modal's sentence that is not the object of
+    /// a preposition.
+    ///
+    /// The sentence starts after the last ".", ";", "!" or "?" before the
+    /// modal. A candidate is a prepositional object when the nearest word
+    /// before it, skipping articles and determiners, is tagged as a preposition.
+    fn sentence_subject(
+        &self,
+        selection: &LLSelection,
+        modal_sel: &LLSelection,
+        candidates: Vec<ObligorCandidate>,
+    ) -> Option<ObligorCandidate> {
+        let modal_start = modal_sel.span_ref().start_idx;
+        let texts = Self::token_texts(selection);
+        let sentence_start = (0..modal_start)
+            .rev()
+            .find(|&idx| matches!(texts[idx].as_deref(), Some("." | ";" | "!" | "?")))
+            .map_or(0, |idx| idx + 1);
+
+        let tagged = |tag: &Tag| -> Vec<usize> {
+            selection
+                .find_by(&x::attr_eq(tag))
+                .iter()
+                .map(|(sel, _)| sel.span_ref().start_idx)
+                .collect()
+        };
+        let determiners = tagged(&Tag::Determiner);
+        let prepositions = tagged(&Tag::Preposition);
+        let words: Vec<usize> = selection
+            .find_by(&x::attr_eq(&TextTag::WORD))
+            .iter()
+            .map(|(sel, _)| sel.span_ref().start_idx)
+            .collect();
+
+        // The tagger gives articles no determiner tag ("the" is a preposition
+        // or adverb), so they are also skipped by text
+        let is_determiner = |idx: usize| {
+            determiners.contains(&idx)
+                || matches!(texts[idx].as_deref(), Some("the" | "a" | "an" | "its" | "their"))
+        };
+        let is_prepositional_object = |start: usize| {
+            let mut previous = words.iter().rev().filter(|&&idx| idx < start);
+            previous
+                .find(|&&idx| !is_determiner(idx))
+                .is_some_and(|&idx| idx >= sentence_start && prepositions.contains(&idx))
+        };
+
+        candidates.into_iter().find(|c| {
+            c.span.start_idx >= sentence_start && !is_prepositional_object(c.span.start_idx)
+        })
+    }
+
+    /// Find the nearest obligor (TermReference or PronounReference) before the modal.
+    ///
+    /// Pronouns inside discretion phrases ("its" in "in its sole discretion")
+    /// are not candidates. Returns (obligor, has_multiple_candidates,
+    /// source_span) where source_span is the token span of the obligor reference.
+    fn find_nearest_obligor(
+        &self,
+        selection: &LLSelection,
+        modal_sel: &LLSelection,
+        discretion: &[SpanRef],
     ) -> Option<(ObligorReference, bool, SpanRef)> {
         // Returns (obligor, has_multiple_candidates, source_span)
 
@@ -284,7 +997,10 @@ impl ObligationPhraseResolver {
         let pronoun_refs: Vec<_> = selection
             .find_by(&x::attr::<Scored<PronounReference>>())
             .into_iter()
-            .filter(|(sel, _)| self.selection_is_before(sel, modal_sel))
+            .filter(|(sel, _)| {
+                self.selection_is_before(sel, modal_sel)
+                    && !Self::in_discretion_phrase(discretion, sel.span_ref())
+            })
             .collect();
 
         // Find the nearest candidate (closest to the modal)
@@ -363,18 +1079,66 @@ impl ObligationPhraseResolver {
         }
     }
 
-    /// Find a plain noun phrase to use as obligor (fallback).
+    /// Find a plain noun phrase to use as obligor (fallback): the one nearest
+    /// the modal.
+    fn find_noun_obligor(
+        &self,
+        selection: &LLSelection,
+        modal_sel: &LLSelection,
+    ) -> Option<(ObligorReference, bool, SpanRef)> {
+        let (text, span_ref) = self
+            .noun_phrases(selection, modal_sel)
+            .pop()
+            .or_else(|| self.vague_subject(modal_sel))?;
+        Some((ObligorReference::NounPhrase { text }, false, span_ref))
+    }
+
+    /// The lowercase noun phrase directly before the modal, opened by a
+    /// determiner: "the relevant authority" in "the relevant authority shall
+    /// approve". Such an obligor names no party, see
+    /// [`ObligorReference::is_unresolved`].
+    fn vague_subject(&self, modal_sel: &LLSelection) -> Option<(String, SpanRef)> {
+        let word = x::all((x::attr_eq(&TextTag::WORD), x::token_text()));
+        let mut current = modal_sel.clone();
+        // Nearest the modal first
+        let mut words: Vec<(LLSelection, String)> = Vec::new();
+        for _ in 0..=MAX_VAGUE_SUBJECT_WORDS {
+            let (ws_sel, _) = current.match_first_backwards(&x::whitespace())?;
+            let (word_sel, (_, text)) = ws_sel.match_first_backwards(&word)?;
+            if OBLIGOR_DETERMINERS.contains(&text.to_lowercase().as_str()) {
+                let (head, _) = words.first()?;
+                if head.find_by(&x::attr_eq(&Tag::Noun)).is_empty() {
+                    return None;
+                }
+                let span = SpanRef {
+                    start_idx: word_sel.span_ref().start_idx,
+                    end_idx: head.span_ref().end_idx,
+                };
+                let mut phrase = vec![text.to_string()];
+                phrase.extend(words.iter().rev().map(|(_, text)| text.clone()));
+                return Some((phrase.join(" "), span));
+            }
+            if !text.chars().all(|c| c.is_ascii_lowercase()) {
+                return None;
+            }
+            words.push((word_sel.clone(), text.to_string()));
+            current = word_sel;
+        }
+        None
+    }
+
+    /// Plain noun phrases before the modal, in text order.
     ///
     /// Requires POS tag to be Noun or ProperNoun to avoid mis-tagging words like
     /// "If" or "Performance" (in "If Performance is late...") as obligors.
     ///
     /// Captures multi-word noun phrases like "Service Provider" or "Customer Group"
-    /// by peeking ahead for contiguous capitalized nouns.
-    fn find_noun_obligor(
+    /// by joining contiguous capitalized nouns.
+    fn noun_phrases(
         &self,
         selection: &LLSelection,
         modal_sel: &LLSelection,
-    ) -> Option<(ObligorReference, bool, SpanRef)> {
+    ) -> Vec<(String, SpanRef)> {
         // Look for capitalized words before the modal that are tagged as nouns
         let noun_words: Vec<_> = selection
             .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
@@ -395,56 +1159,25 @@ impl ObligationPhraseResolver {
             })
             .collect();
 
-        if noun_words.is_empty() {
-            return None;
-        }
-
-        // Find the nearest multi-word noun phrase before the modal
-        // Start from the last noun (nearest to modal) and look backwards for contiguous nouns
-        let mut phrase_parts: Vec<&str> = Vec::new();
-        let mut first_sel: Option<&LLSelection> = None;
+        // Group contiguous nouns into phrases
+        let mut phrases: Vec<(Vec<&str>, SpanRef)> = Vec::new();
         let mut last_sel: Option<&LLSelection> = None;
-
-        // Start from the last noun and work backwards to find contiguous nouns
-        for i in (0..noun_words.len()).rev() {
-            let (sel, (_, text)) = &noun_words[i];
-
-            if phrase_parts.is_empty() {
-                // First word of the phrase (nearest to modal)
-                phrase_parts.push(text);
-                first_sel = Some(sel);
-                last_sel = Some(sel);
-            } else if let Some(prev_sel) = last_sel {
-                // Check if this noun is immediately before the previous one
-                // (i.e., they form a contiguous phrase like "Service Provider")
-                if self.are_adjacent_words(sel, prev_sel) {
-                    phrase_parts.push(text);
-                    last_sel = Some(sel);
-                } else {
-                    // Not contiguous, stop here
-                    break;
+        for (sel, (_, text)) in &noun_words {
+            let contiguous = last_sel.is_some_and(|prev| self.are_adjacent_words(prev, sel));
+            match phrases.last_mut() {
+                Some((parts, span)) if contiguous => {
+                    parts.push(text);
+                    span.end_idx = sel.span_ref().end_idx;
                 }
+                _ => phrases.push((vec![text], sel.span_ref())),
             }
+            last_sel = Some(sel);
         }
 
-        // Reverse to get correct order (we collected backwards)
-        phrase_parts.reverse();
-        let phrase_text = phrase_parts.join(" ");
-
-        // Build span from last_sel (earliest) to first_sel (nearest to modal)
-        let span_ref = match (last_sel, first_sel) {
-            (Some(start), Some(end)) => SpanRef {
-                start_idx: start.span_ref().start_idx,
-                end_idx: end.span_ref().end_idx,
-            },
-            _ => return None,
-        };
-
-        Some((
-            ObligorReference::NounPhrase { text: phrase_text },
-            false,
-            span_ref,
-        ))
+        phrases
+            .into_iter()
+            .map(|(parts, span)| (parts.join(" "), span))
+            .collect()
     }
 
     /// Check if two word selections are adjacent (only whitespace between them).
@@ -474,19 +1207,160 @@ impl ObligationPhraseResolver {
         false
     }
 
+    /// Detect a passive construction ("shall be paid by the Company") after the modal.
+    ///
+    /// The participle must be POS-tagged as a verb and either end in "-ed" or be a
+    /// known irregular participle. Adjectival participles such as "entitled" or
+    /// "deemed" are left to the active-voice path. Returns `None` for active voice.
+    fn detect_passive(&self, selection: &LLSelection, modal_sel: &LLSelection) -> Option<PassiveVoice> {
+        let mut tokens: Vec<(usize, &str)> = selection
+            .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            .into_iter()
+            .chain(selection.find_by(&x::all((x::attr_eq(&TextTag::PUNC), x::token_text()))))
+            .map(|(sel, (_, text))| (sel.span_ref().start_idx, text))
+            .collect();
+        tokens.sort_by_key(|(idx, _)| *idx);
+
+        let modal = modal_sel.span_ref();
+        let be_pos = tokens.iter().position(|(idx, _)| *idx > modal.end_idx)?;
+        let (_, be) = tokens[be_pos];
+        let &(participle_idx, participle) = tokens.get(be_pos + 1)?;
+        let participle = participle.to_lowercase();
+        if !be.eq_ignore_ascii_case("be")
+            || STATIVE_PARTICIPLES.contains(&participle.as_str())
+            || !(participle.ends_with("ed") || IRREGULAR_PARTICIPLES.contains(&participle.as_str()))
+        {
+            return None;
+        }
+        let is_verb = selection
+            .find_by(&x::attr_eq(&Tag::Verb))
+            .iter()
+            .any(|(sel, _)| sel.span_ref().start_idx == participle_idx);
+        if !is_verb {
+            return None;
+        }
+
+        // The subject is everything between the previous clause punctuation and the modal
+        let object_words: Vec<&str> = tokens[..be_pos]
+            .iter()
+            .filter(|(idx, _)| *idx < modal.start_idx)
+            .rev()
+            .take_while(|(_, text)| !matches!(*text, "," | ";" | "." | ":" | "(" | ")"))
+            .map(|(_, text)| *text)
+            .collect();
+        let object = if object_words.is_empty() {
+            None
+        } else {
+            Some(object_words.into_iter().rev().collect::<Vec<_>>().join(" "))
+        };
+
+        // "by" before the end of the clause introduces the agent
+        let modal_starts: Vec<usize> = selection
+            .find_by(&x::attr::<ContractKeyword>())
+            .into_iter()
+            .filter(|(_, kw)| Self::is_modal_keyword(kw))
+            .map(|(sel, _)| sel.span_ref().start_idx)
+            .collect();
+        let agent = tokens[be_pos + 2..]
+            .iter()
+            .enumerate()
+            .take_while(|(_, (idx, text))| {
+                !matches!(*text, "." | ";" | "!" | "?") && !modal_starts.contains(idx)
+            })
+            .find(|(_, (_, text))| text.eq_ignore_ascii_case("by"))
+            .and_then(|(offset, _)| self.find_agent(selection, &tokens[be_pos + 3 + offset..]));
+
+        Some(PassiveVoice { object, agent })
+    }
+
+    /// Resolve the agent of a "by" phrase from the words that follow it.
+    ///
+    /// Accepts a defined term, a resolved pronoun or a capitalized noun phrase.
+    /// Temporal phrases ("by March 1", "by the Effective Date") and lowercase
+    /// means ("by wire transfer") are not agents.
+    fn find_agent(
+        &self,
+        selection: &LLSelection,
+        tokens: &[(usize, &str)],
+    ) -> Option<(ObligorReference, SpanRef)> {
+        let mut words = tokens
+            .iter()
+            .skip_while(|(_, text)| matches!(text.to_lowercase().as_str(), "the" | "a" | "an"));
+        let &(start_idx, first) = words.next()?;
+
+        let covers = |sel: &LLSelection| {
+            let span = sel.span_ref();
+            span.start_idx <= start_idx && start_idx <= span.end_idx
+        };
+        if selection
+            .find_by(&x::attr::<TemporalExpression>())
+            .iter()
+            .any(|(sel, _)| covers(sel))
+        {
+            return None;
+        }
+        if let Some((sel, term_ref)) = selection
+            .find_by(&x::attr::<Scored<TermReference>>())
+            .into_iter()
+            .find(|(sel, _)| covers(sel))
+        {
+            return Some((self.term_to_obligor(term_ref), sel.span_ref()));
+        }
+        if let Some((sel, pron_ref)) = selection
+            .find_by(&x::attr::<Scored<PronounReference>>())
+            .into_iter()
+            .find(|(sel, scored)| covers(sel) && !scored.value.candidates.is_empty())
+        {
+            return Some((self.pronoun_to_obligor(pron_ref), sel.span_ref()));
+        }
+
+        let is_capitalized = |text: &str| text.chars().next().is_some_and(char::is_uppercase);
+        if !is_capitalized(first) || MONTH_NAMES.contains(&first.to_lowercase().as_str()) {
+            return None;
+        }
+        let mut end_idx = start_idx;
+        let mut phrase = vec![first];
+        for &(idx, text) in words.take_while(|(_, text)| is_capitalized(text)) {
+            end_idx = idx;
+            phrase.push(text);
+        }
+        Some((
+            ObligorReference::NounPhrase { text: phrase.join(" ") },
+            SpanRef { start_idx, end_idx },
+        ))
+    }
+
     /// Extract the action span following the modal.
     ///
-    /// Returns (action_text, word_spans) where word_spans contains the span for each word.
+    /// Returns (action_text, word_spans, qualifiers) where word_spans contains the span for
+    /// each word and qualifiers the token range of each parenthetical skipped along the way.
     /// This allows trimming to adjust the span to match only the retained words.
+    ///
+    /// Discretion phrases are skipped too, including one set off by commas
+    /// right after the modal ("may, in its sole discretion, terminate").
     fn extract_action(
         &self,
         _selection: &LLSelection,
         modal_sel: &LLSelection,
-    ) -> (String, Vec<SpanRef>) {
+        discretion: &[SpanRef],
+    ) -> (String, Vec<SpanRef>, Vec<SpanRef>) {
         let mut action_words = Vec::new();
         let mut word_spans = Vec::new();
+        let mut qualifiers = Vec::new();
         let mut current = modal_sel.clone();
 
+        if let Some((comma_sel, ",")) = current.match_first_forwards(&x::token_text()) {
+            let phrase_sel = comma_sel
+                .match_first_forwards(&x::whitespace())
+                .and_then(|(ws_sel, _)| Self::skip_discretion(&ws_sel, discretion));
+            if let Some(phrase_sel) = phrase_sel {
+                current = match phrase_sel.match_first_forwards(&x::token_text()) {
+                    Some((close_sel, ",")) => close_sel,
+                    _ => phrase_sel,
+                };
+            }
+        }
+
         // Walk forward collecting words until we hit a boundary
         loop {
             // Skip whitespace
@@ -496,6 +1370,27 @@ impl ObligationPhraseResolver {
                 break;
             }
 
+            if let Some(phrase_sel) = Self::skip_discretion(&current, discretion) {
+                current = phrase_sel;
+                continue;
+            }
+
+            // Parenthetical qualifier: record it and keep its words out of the action.
+            // An unclosed parenthesis ends the action.
+            if let Some((open_sel, "(")) = current.match_first_forwards(&x::token_text()) {
+                match Self::close_parenthetical(&open_sel) {
+                    Some(close_sel) => {
+                        qualifiers.push(SpanRef {
+                            start_idx: open_sel.span_ref().end_idx,
+                            end_idx: close_sel.span_ref().end_idx,
+                        });
+                        current = close_sel;
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+
             // Check for punctuation (sentence boundary)
             if let Some((punc_sel, _)) =
                 current.match_first_forwards(&x::attr_eq(&TextTag::PUNC))
@@ -538,6 +1433,10 @@ impl ObligationPhraseResolver {
             if let Some((word_sel, (_, text))) =
                 current.match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
             {
+                // "only if"/"only upon" belongs to the condition, not the action
+                if text.eq_ignore_ascii_case("only") && Self::opens_condition(&word_sel) {
+                    break;
+                }
                 action_words.push(text.to_string());
                 word_spans.push(word_sel.span_ref());
                 current = word_sel;
@@ -547,7 +1446,37 @@ impl ObligationPhraseResolver {
         }
 
         let action_text = action_words.join(" ");
-        (action_text, word_spans)
+        (action_text, word_spans, qualifiers)
+    }
+
+    /// Whether an "if"/"upon" or "provided" condition follows the selection.
+    fn opens_condition(selection: &LLSelection) -> bool {
+        selection
+            .match_first_forwards(&x::whitespace())
+            .is_some_and(|(ws_sel, _)| {
+                ws_sel.match_first_forwards(&x::attr_eq(&ContractKeyword::If)).is_some()
+                    || ws_sel
+                        .match_first_forwards(&x::attr_eq(&ContractKeyword::Provided))
+                        .is_some()
+            })
+    }
+
+    /// Extend a selection ending on "(" through its matching ")", if the line has one.
+    fn close_parenthetical(open_sel: &LLSelection) -> Option<LLSelection> {
+        let mut depth = 1;
+        let mut current = open_sel.clone();
+        while let Some((next_sel, text)) = current.match_first_forwards(&x::token_text()) {
+            match text {
+                "(" => depth += 1,
+                ")" => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return Some(next_sel);
+            }
+            current = next_sel;
+        }
+        None
     }
 
     /// Find the next modal keyword after the given position, if any.
@@ -712,50 +1641,73 @@ impl ObligationPhraseResolver {
         tokens.join(" ")
     }
 
-    /// Calculate confidence for an obligation phrase.
+    /// Calculate the confidence breakdown for an obligation phrase.
     ///
     /// Scoring heuristics:
-    /// - Base: 0.75 when modal + obligor found
-    /// - +0.10 if obligor is a defined term
-    /// - +0.05 if resolved through pronoun chain
-    /// - -0.15 if multiple obligor candidates compete
-    /// - -0.10 if action span is empty/only stop words
+    /// - Keyword: 0.75 base when modal + obligor found
+    /// - Obligor: +0.10 if obligor is a defined term, +0.05 if resolved through
+    ///   pronoun chain, -0.15 if multiple obligor candidates compete, -0.35 if a
+    ///   passive obligation names no agent, -0.10 if a noun phrase names no party
+    /// - Action: -0.10 if action span is empty/only stop words
+    /// - Conditions: penalty per truncated condition preview (0.0 by default)
     fn calculate_confidence(
         &self,
         obligor: &ObligorReference,
         action: &str,
+        conditions: &[ConditionRef],
         has_multiple_candidates: bool,
-    ) -> f64 {
-        let mut confidence = self.base_confidence;
+    ) -> ObligationConfidenceBreakdown {
+        let mut obligor_resolution = 0.0;
 
         // Bonus for defined term obligor
         match obligor {
             ObligorReference::TermRef { .. } => {
-                confidence += self.defined_term_bonus;
+                obligor_resolution += self.defined_term_bonus;
             }
             ObligorReference::PronounRef { is_defined_term, .. } => {
-                confidence += self.pronoun_chain_bonus;
+                obligor_resolution += self.pronoun_chain_bonus;
                 if *is_defined_term {
-                    confidence += self.defined_term_bonus;
+                    obligor_resolution += self.defined_term_bonus;
                 }
             }
             ObligorReference::NounPhrase { .. } => {
-                // No bonus for plain noun phrases
+                // No bonus for plain noun phrases, a penalty if they name no party
+                if obligor.is_unresolved() {
+                    obligor_resolution -= self.unresolved_obligor_penalty;
+                }
+            }
+            ObligorReference::Unknown => {
+                obligor_resolution -= self.unknown_obligor_penalty;
             }
         }
 
         // Penalty for multiple candidates
         if has_multiple_candidates {
-            confidence -= self.multiple_obligor_penalty;
+            obligor_resolution -= self.multiple_obligor_penalty;
         }
 
         // Penalty for empty/minimal action
-        let action_word_count = action.split_whitespace().count();
-        if action_word_count == 0 {
-            confidence -= self.empty_action_penalty;
+        let mut action_clarity = 0.0;
+        if action.split_whitespace().count() == 0 {
+            action_clarity -= self.empty_action_penalty;
         }
 
-        confidence.clamp(0.0, 1.0)
+        // Penalty for conditions we could only partially capture
+        let mut condition_completeness = 0.0;
+        let truncated = conditions
+            .iter()
+            .filter(|c| c.text_preview.ends_with("..."))
+            .count();
+        if truncated > 0 {
+            condition_completeness -= truncated as f64 * self.truncated_condition_penalty;
+        }
+
+        ObligationConfidenceBreakdown {
+            keyword_strength: self.base_confidence,
+            obligor_resolution,
+            action_clarity,
+            condition_completeness,
+        }
     }
 }
 
@@ -763,7 +1715,25 @@ impl Resolver for ObligationPhraseResolver {
     type Attr = Scored<ObligationPhrase>;
 
     fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        self.go_traced(selection, &mut ResolverTrace::disabled())
+    }
+
+    fn go_traced(
+        &self,
+        selection: LLSelection,
+        trace: &mut ResolverTrace,
+    ) -> Vec<LLCursorAssignment<Self::Attr>> {
         let mut results = Vec::new();
+        let texts = Self::token_texts(&selection);
+
+        // Negation scopes over the line, with line-relative spans
+        let negations: Vec<ScopeOperator<NegationOp>> = self
+            .negation_detector
+            .detect_in_tokens(0, &texts)
+            .into_iter()
+            .map(|scored| scored.value)
+            .collect();
+        let negations = ScopeIndex::new(&negations);
 
         // Find all modal keywords
         let modals: Vec<_> = selection
@@ -772,6 +1742,19 @@ impl Resolver for ObligationPhraseResolver {
             .filter(|(_, kw)| Self::is_modal_keyword(kw))
             .collect();
 
+        // Discretion phrases, each with the start of the modal it qualifies
+        let modal_starts: Vec<usize> =
+            modals.iter().map(|(sel, _)| sel.span_ref().start_idx).collect();
+        let discretion = Self::discretion_phrases(&texts);
+        let discretion_owners: Vec<Option<usize>> = discretion
+            .iter()
+            .map(|(span, _)| Self::discretion_owner(&texts, &modal_starts, *span))
+            .collect();
+        let discretion_spans: Vec<SpanRef> = discretion.iter().map(|(span, _)| *span).collect();
+
+        // Modals coordinated with a later one ("shall" in "shall and may")
+        let leading_coordinated = leading_coordinated_starts(modals.iter().map(|(sel, _)| sel));
+
         // Collect negated modal positions to skip standalone positives that are part of them
         let negated_positions: Vec<_> = modals
             .iter()
@@ -788,35 +1771,124 @@ impl Resolver for ObligationPhraseResolver {
                     before.is_none() && after.is_none()
                 });
                 if is_part_of_negated {
+                    trace.reject(&modal_sel, "modal is part of a negated modal");
                     continue;
                 }
             }
-            // Determine obligation type
-            let obligation_type = match ObligationType::from_keyword(keyword) {
+            if leading_coordinated.contains(&modal_sel.span_ref().start_idx) {
+                trace.reject(&modal_sel, "modal is coordinated with a later modal");
+                continue;
+            }
+            // The modal's own type; the final type is voted below
+            let keyword_type = match ObligationType::from_keyword(keyword) {
                 Some(t) => t,
-                None => continue,
+                None => {
+                    trace.reject(&modal_sel, "keyword is not an obligation modal");
+                    continue;
+                }
+            };
+            let modal_start = modal_sel.span_ref().start_idx;
+            let obligation_discretion = discretion
+                .iter()
+                .zip(&discretion_owners)
+                .find(|(_, owner)| **owner == Some(modal_start))
+                .map(|((_, standard), _)| *standard);
+            // Idioms other than "reserves the right to" grant a permission less explicitly
+            let idiom_penalty = if *keyword == ContractKeyword::PermissionIdiom {
+                let words: Vec<&str> = modal_sel
+                    .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+                    .into_iter()
+                    .map(|(_, (_, text))| text)
+                    .collect();
+                permission_idiom_penalty(&words.join(" "))
+            } else {
+                0.0
+            };
+            // "shall and may": the obligation spans both modals
+            let (modal_sel, coordinated) = match preceding_coordinated_modal(&modal_sel) {
+                Some((coordinated_sel, coordinated)) => (coordinated_sel, Some(coordinated)),
+                None => (modal_sel, None),
+            };
+            let mixed_modality = coordinated
+                .and_then(|coordinated| ObligationType::from_keyword(&coordinated))
+                .is_some_and(|coordinated_type| coordinated_type != keyword_type);
+
+            // In passive voice the "by" agent bears the obligation and the subject
+            // is its object; without an agent the obligor is unknown.
+            let passive = self.detect_passive(&selection, &modal_sel);
+            let mut strategy_adjustment = 0.0;
+            let (obligor, has_multiple, obligor_span) = match &passive {
+                Some(PassiveVoice { agent: Some((agent, span)), .. }) => {
+                    (agent.clone(), false, Some(*span))
+                }
+                Some(PassiveVoice { agent: None, .. }) => (ObligorReference::Unknown, false, None),
+                None => match self.find_obligor(&selection, &modal_sel, &discretion_spans) {
+                    Some((obligor, has_multiple, span, adjustment)) => {
+                        strategy_adjustment = adjustment;
+                        (obligor, has_multiple, Some(span))
+                    }
+                    None => {
+                        trace.reject(&modal_sel, "obligor resolution failed");
+                        continue;
+                    }
+                },
             };
-
-            // Find the obligor (now returns source span)
-            let (obligor, has_multiple, obligor_span) =
-                match self.find_obligor(&selection, &modal_sel) {
-                    Some(o) => o,
-                    None => continue, // Skip if no obligor found
-                };
 
             // Extract the action words and their spans
-            let (raw_action, word_spans) = self.extract_action(&selection, &modal_sel);
+            let (raw_action, word_spans, qualifier_spans) =
+                self.extract_action(&selection, &modal_sel, &discretion_spans);
 
             // Trim trailing conjunction and get count of words to keep
-            let (action, words_to_keep) =
+            let (mut action, words_to_keep) =
                 self.trim_trailing_conjunction(&raw_action, word_spans.len());
 
+            // A negation leading the action negates the modal itself: drop the
+            // marker from the action and let it vote for a prohibition
+            let negated = Self::leading_negations(&negations, &word_spans[..words_to_keep]);
+            let negated_action = keyword_type != ObligationType::Prohibition
+                && negated > 0
+                && negated < words_to_keep;
+            let mut first_word = if negated_action {
+                action = action.split_whitespace().skip(negated).collect::<Vec<_>>().join(" ");
+                negated
+            } else {
+                0
+            };
+            let evidence = ModalityEvidence {
+                keyword,
+                negated_action,
+                action: &action,
+                coordinated: coordinated.as_ref(),
+            };
+            let obligation_type = self
+                .modality_classifier
+                .classify(&evidence)
+                .map_or(keyword_type, |vote| vote.best.value);
+
+            // In a flow-down obligation the obligor answers for another party's
+            // action: keep the obligor, record the performer, and drop the frame
+            let mut performer = None;
+            let whole_action = action.split_whitespace().count() == words_to_keep;
+            if first_word == 0 && passive.is_none() && whole_action {
+                let words: Vec<&str> = action.split_whitespace().collect();
+                let flow_down =
+                    Self::detect_flow_down(&selection, &words, &word_spans[..words_to_keep]);
+                if let Some((frame_words, party)) = flow_down {
+                    action = words[frame_words..].join(" ");
+                    first_word = frame_words;
+                    performer = Some(party);
+                }
+            }
+
             // Compute action span from only the retained words
-            let action_span = if words_to_keep > 0 && !word_spans.is_empty() {
-                let first = &word_spans[0];
+            let action_span = if words_to_keep > first_word && !word_spans.is_empty() {
+                let start_idx = match first_word {
+                    0 => word_spans[0].start_idx,
+                    _ => word_spans[first_word - 1].end_idx + 1,
+                };
                 let last = &word_spans[words_to_keep - 1];
                 Some(SpanRef {
-                    start_idx: first.start_idx,
+                    start_idx,
                     end_idx: last.end_idx,
                 })
             } else {
@@ -827,19 +1899,51 @@ impl Resolver for ObligationPhraseResolver {
             let conditions = self.find_conditions(&selection, &modal_sel);
 
             // Calculate confidence
-            let confidence = self.calculate_confidence(&obligor, &action, has_multiple);
+            let mut confidence_breakdown =
+                self.calculate_confidence(&obligor, &action, &conditions, has_multiple);
+            confidence_breakdown.obligor_resolution += strategy_adjustment;
+            if mixed_modality {
+                confidence_breakdown.keyword_strength -= self.mixed_modality_penalty;
+            }
+            confidence_breakdown.keyword_strength -= idiom_penalty;
+            let confidence = confidence_breakdown.total();
+            if trace.is_enabled() {
+                let obligor_text = match &obligor {
+                    ObligorReference::TermRef { term_name, .. } => term_name.as_str(),
+                    ObligorReference::PronounRef { resolved_to, .. } => resolved_to.as_str(),
+                    ObligorReference::NounPhrase { text } => text.as_str(),
+                    ObligorReference::Unknown => UNKNOWN_OBLIGOR,
+                };
+                let reason = format!(
+                    "{:?} for {:?}, confidence {:.2}",
+                    obligation_type, obligor_text, confidence
+                );
+                trace.accept(&modal_sel, reason);
+            }
 
+            let obligation_nature = ObligationNature::from_action(&action, &self.normalizer);
             let phrase = ObligationPhrase {
                 obligor,
                 obligation_type,
                 action,
                 conditions,
+                confidence_breakdown,
+                qualifiers: qualifier_spans
+                    .iter()
+                    .map(|span| DocSpan::single_line(0, span.start_idx, span.end_idx))
+                    .collect(),
+                object: passive.and_then(|p| p.object),
+                performer,
+                obligation_nature,
+                discretion: obligation_discretion,
             };
 
             // Build assignment with associations
-            let mut builder = modal_sel
-                .assign(Scored::rule_based(phrase, confidence, "obligation_phrase"))
-                .with_association(ObligorSource, obligor_span);
+            let mut builder =
+                modal_sel.assign(Scored::rule_based(phrase, confidence, "obligation_phrase"));
+            if let Some(span) = obligor_span {
+                builder = builder.with_association(ObligorSource, span);
+            }
 
             // Add action span association if we have one
             if let Some(span) = action_span {
@@ -424,9 +424,32 @@ impl ObligationPartyLinker {
                     ReviewableResult::certain(participant)
                 }
             }
+
+            ObligorReference::Unknown => self.implicit_obligor(),
         }
     }
 
+    /// Placeholder participant for a passive obligation that names no agent.
+    fn implicit_obligor(&self) -> ReviewableResult<ClauseParticipant> {
+        let participant = ClauseParticipant {
+            span: None,
+            text: "(implicit)".to_string(),
+            role: ParticipantRole::Subject,
+            resolved_to: None,
+            resolved_text: None,
+            is_pronoun: false,
+            confidence: self.config.implicit_obligor_confidence,
+            needs_review: true,
+            review_reason: Some("Implicit obligor - passive voice without agent".to_string()),
+        };
+
+        ReviewableResult::uncertain(
+            participant,
+            vec![],
+            "Passive voice without explicit agent - obligor is implicit",
+        )
+    }
+
     /// Detect passive voice and extract "by X" obligor.
     ///
     /// Pattern: "be {past participle}(ed|en) by {agent}"
@@ -489,23 +512,7 @@ impl ObligationPartyLinker {
 
         if has_implicit_passive && !action_lower.contains(" by ") {
             // Passive voice without "by X" - implicit obligor, flag for review
-            let participant = ClauseParticipant {
-                span: None,
-                text: "(implicit)".to_string(),
-                role: ParticipantRole::Subject,
-                resolved_to: None,
-                resolved_text: None,
-                is_pronoun: false,
-                confidence: self.config.implicit_obligor_confidence,
-                needs_review: true,
-                review_reason: Some("Implicit obligor - passive voice without agent".to_string()),
-            };
-
-            return Some(ReviewableResult::uncertain(
-                participant,
-                vec![],
-                "Passive voice without explicit agent - obligor is implicit",
-            ));
+            return Some(self.implicit_obligor());
         }
 
         None
@@ -657,7 +664,9 @@ impl Default for ObligationPartyLinker {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{ModalObligationType, ObligationPhrase, ObligationType, Polarity};
+    use crate::{
+        ModalObligationType, ObligationNature, ObligationPhrase, ObligationType, Polarity,
+    };
     use layered_nlp_document::Scored;
 
     fn make_obligation(obligor: ObligorReference, action: &str) -> Scored<ObligationPhrase> {
@@ -666,6 +675,12 @@ mod tests {
             obligation_type: ObligationType::Duty,
             action: action.to_string(),
             conditions: Vec::new(),
+            confidence_breakdown: Default::default(),
+            qualifiers: Vec::new(),
+            object: None,
+            performer: None,
+            obligation_nature: ObligationNature::Performance,
+            discretion: None,
         };
         Scored::rule_based(phrase, 0.85, "obligation_phrase")
     }
@@ -1006,6 +1021,12 @@ mod tests {
             obligation_type: ObligationType::Duty,
             action: "deliver goods to Landlord".to_string(),
             conditions: vec![],
+            confidence_breakdown: Default::default(),
+            qualifiers: Vec::new(),
+            object: None,
+            performer: None,
+            obligation_nature: ObligationNature::Performance,
+            discretion: None,
         };
         let chains = vec![];
 
@@ -1027,6 +1048,12 @@ mod tests {
             obligation_type: ObligationType::Duty,
             action: "pay Landlord rent".to_string(),
             conditions: vec![],
+            confidence_breakdown: Default::default(),
+            qualifiers: Vec::new(),
+            object: None,
+            performer: None,
+            obligation_nature: ObligationNature::Performance,
+            discretion: None,
         };
         let chains = vec![];
 
@@ -1048,6 +1075,12 @@ mod tests {
             obligation_type: ObligationType::Prohibition,
             action: "disclose information".to_string(),
             conditions: vec![],
+            confidence_breakdown: Default::default(),
+            qualifiers: Vec::new(),
+            object: None,
+            performer: None,
+            obligation_nature: ObligationNature::Performance,
+            discretion: None,
         };
         let chains = vec![];
 
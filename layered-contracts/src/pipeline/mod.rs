@@ -1,7 +1,15 @@
 //! Pipeline presets for running contract analysis resolvers in correct order.
 //!
 //! The Pipeline struct provides preset configurations that automatically order
-//! resolvers based on their dependencies.
+//! resolvers based on their dependencies. A [`PipelineObserver`] passed to
+//! [`Pipeline::run_on_text_observed`] follows the run, e.g. for a progress bar.
+//! With the `tracing` feature each run is also traced as a `pipeline` span
+//! around the spans of its resolvers (see [`StageSpan`]).
+
+use std::time::{Duration, Instant};
+
+use layered_nlp::Resolver;
+use layered_nlp_document::StageSpan;
 
 use crate::{
     ContractDocument, ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver,
@@ -10,6 +18,49 @@ use crate::{
     TermsOfArtResolver,
 };
 
+/// Callbacks following a [`Pipeline`] run.
+///
+/// Every method defaults to doing nothing. Resolver names are full type names
+/// (`layered_contracts::obligation::ObligationPhraseResolver`). Observers are
+/// `Send` so that one can be handed to each document of a parallel batch.
+pub trait PipelineObserver: Send {
+    /// `resolver` is about to run on the document's `line_count` lines.
+    fn resolver_started(&mut self, resolver: &'static str, line_count: usize) {
+        let _ = (resolver, line_count);
+    }
+
+    /// `resolver` has processed `lines_done` of `line_count` lines.
+    fn line_processed(&mut self, resolver: &'static str, lines_done: usize, line_count: usize) {
+        let _ = (resolver, lines_done, line_count);
+    }
+
+    /// `resolver` has run on every line, taking `elapsed`.
+    fn resolver_finished(&mut self, resolver: &'static str, elapsed: Duration) {
+        let _ = (resolver, elapsed);
+    }
+}
+
+/// Run `resolver` on `doc`, reporting to `observer` if there is one.
+fn run_observed<R: Resolver>(
+    doc: ContractDocument,
+    resolver: &R,
+    observer: &mut Option<&mut dyn PipelineObserver>,
+) -> ContractDocument {
+    let observer = match observer {
+        Some(observer) => observer,
+        None => return doc.run_resolver(resolver),
+    };
+    let name = std::any::type_name::<R>();
+    let line_count = doc.line_count();
+    observer.resolver_started(name, line_count);
+    let started = Instant::now();
+    let doc = doc.run_resolver_with_progress(resolver, |line_idx| {
+        observer.line_processed(name, line_idx + 1, line_count)
+    });
+    observer.resolver_finished(name, started.elapsed());
+    doc
+}
+
 /// Pipeline preset for running contract analysis resolvers.
 ///
 /// Provides preset configurations that run resolvers in correct dependency order:
@@ -87,25 +138,100 @@ impl Pipeline {
 
     /// Run the pipeline on text, returning a fully analyzed ContractDocument.
     pub fn run_on_text(&self, text: &str) -> Result<ContractDocument, ProcessError> {
+        self.run(text, None)
+    }
+
+    /// Run the pipeline on text, reporting progress to `observer`.
+    pub fn run_on_text_observed(
+        &self,
+        text: &str,
+        observer: &mut dyn PipelineObserver,
+    ) -> Result<ContractDocument, ProcessError> {
+        self.run(text, Some(observer))
+    }
+
+    fn run(
+        &self,
+        text: &str,
+        mut observer: Option<&mut dyn PipelineObserver>,
+    ) -> Result<ContractDocument, ProcessError> {
+        let stage = StageSpan::start("pipeline", "Pipeline::run");
         let mut doc = ContractDocument::from_text(text);
+        let observer = &mut observer;
 
         for resolver_type in &self.resolvers {
             doc = match resolver_type {
-                ResolverType::SectionHeader => doc.run_resolver(&SectionHeaderResolver::new()),
+                ResolverType::SectionHeader => {
+                    run_observed(doc, &SectionHeaderResolver::new(), observer)
+                }
                 ResolverType::SectionReference => {
-                    doc.run_resolver(&SectionReferenceResolver::new())
+                    run_observed(doc, &SectionReferenceResolver::new(), observer)
+                }
+                ResolverType::ContractKeyword => {
+                    run_observed(doc, &ContractKeywordResolver::new(), observer)
+                }
+                ResolverType::TermsOfArt => run_observed(doc, &TermsOfArtResolver::new(), observer),
+                ResolverType::DefinedTerm => {
+                    run_observed(doc, &DefinedTermResolver::new(), observer)
+                }
+                ResolverType::TermReference => {
+                    run_observed(doc, &TermReferenceResolver::new(), observer)
+                }
+                ResolverType::Temporal => {
+                    run_observed(doc, &TemporalExpressionResolver::new(), observer)
+                }
+                ResolverType::Pronoun => run_observed(doc, &PronounResolver::new(), observer),
+                ResolverType::PronounChain => {
+                    run_observed(doc, &PronounChainResolver::new(), observer)
+                }
+                ResolverType::Obligation => {
+                    run_observed(doc, &ObligationPhraseResolver::new(), observer)
                 }
-                ResolverType::ContractKeyword => doc.run_resolver(&ContractKeywordResolver::new()),
-                ResolverType::TermsOfArt => doc.run_resolver(&TermsOfArtResolver::new()),
-                ResolverType::DefinedTerm => doc.run_resolver(&DefinedTermResolver::new()),
-                ResolverType::TermReference => doc.run_resolver(&TermReferenceResolver::new()),
-                ResolverType::Temporal => doc.run_resolver(&TemporalExpressionResolver::new()),
-                ResolverType::Pronoun => doc.run_resolver(&PronounResolver::new()),
-                ResolverType::PronounChain => doc.run_resolver(&PronounChainResolver::new()),
-                ResolverType::Obligation => doc.run_resolver(&ObligationPhraseResolver::new()),
             };
         }
 
+        stage.finish(|| doc.annotation_map().len());
         Ok(doc)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+    }
+
+    impl PipelineObserver for Recorder {
+        fn resolver_started(&mut self, resolver: &'static str, line_count: usize) {
+            let name = resolver.rsplit("::").next().unwrap();
+            self.events.push(format!("start {} {}", name, line_count));
+        }
+
+        fn line_processed(&mut self, _: &'static str, lines_done: usize, line_count: usize) {
+            self.events.push(format!("{}/{}", lines_done, line_count));
+        }
+    }
+
+    #[test]
+    fn test_observer_follows_each_resolver_and_line() {
+        let mut recorder = Recorder::default();
+        let doc = Pipeline::structure_only()
+            .run_on_text_observed("Section 1. Payment\nFees are due.", &mut recorder)
+            .unwrap();
+        assert_eq!(doc.line_count(), 2);
+        assert_eq!(
+            recorder.events,
+            vec![
+                "start SectionHeaderResolver 2",
+                "1/2",
+                "2/2",
+                "start SectionReferenceResolver 2",
+                "1/2",
+                "2/2",
+            ]
+        );
+    }
+}
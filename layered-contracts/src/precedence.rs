@@ -21,7 +21,10 @@
 //! let resolutions = resolver.resolve_conflicts(&conflicts, &doc);
 //! ```
 
+use crate::section_reference::{SectionReference, SectionReferenceResolver};
 use crate::{Conflict, DocPosition, DocSpan, SectionKind};
+use layered_nlp::{create_line_from_string, x};
+use layered_nlp_document::StageSpan;
 
 // ============================================================================
 // Gate 0: Core Types
@@ -81,6 +84,9 @@ pub struct PrecedenceRule {
     pub overriding_span: DocSpan,
     /// The clause being overridden (referenced section)
     pub overridden_span: Option<DocSpan>,
+    /// Canonical identifier of the referenced section ("SECTION:3.1"), when
+    /// the connective names one
+    pub overridden_section: Option<String>,
     /// Whether this is a "notwithstanding" (override) or "subject to" (defers) clause
     pub is_override: bool,
     /// The connective used ("notwithstanding", "subject to", etc.)
@@ -103,11 +109,18 @@ impl PrecedenceRule {
             declaration_span,
             overriding_span,
             overridden_span,
+            overridden_section: None,
             is_override,
             connective: connective.into(),
             confidence,
         }
     }
+
+    /// Records the canonical identifier of the referenced section.
+    pub fn with_overridden_section(mut self, canonical: impl Into<String>) -> Self {
+        self.overridden_section = Some(canonical.into());
+        self
+    }
 }
 
 /// How a conflict was resolved.
@@ -237,135 +250,123 @@ impl PrecedenceDetector {
 
     /// Detects precedence rules in the given text.
     ///
-    /// Returns a list of `PrecedenceRule` instances for each detected
-    /// precedence connective with sufficient confidence.
+    /// Returns one `PrecedenceRule` per occurrence of each precedence
+    /// connective with sufficient confidence, in text order. Spans hold byte
+    /// offsets into `text`: the declaration covers the connective, the
+    /// overriding span the sentence it appears in, and the overridden span
+    /// the section reference directly following the connective
+    /// ("Notwithstanding Section 3.1"), parsed by `SectionReferenceResolver`.
     pub fn detect_in_text(&self, text: &str) -> Vec<PrecedenceRule> {
         let mut rules = Vec::new();
-        let text_lower = text.to_lowercase();
+        // ASCII lowercasing keeps byte offsets aligned with `text`
+        let text_lower = text.to_ascii_lowercase();
 
         // Pattern 1: "notwithstanding [section reference]"
-        if let Some(rule) = self.detect_notwithstanding(&text_lower, text) {
-            if rule.confidence >= self.min_confidence {
-                rules.push(rule);
-            }
-        }
+        rules.extend(self.detect_notwithstanding(&text_lower, text));
 
         // Pattern 2: "subject to [section reference]"
-        if let Some(rule) = self.detect_subject_to(&text_lower, text) {
-            if rule.confidence >= self.min_confidence {
-                rules.push(rule);
-            }
-        }
+        rules.extend(self.detect_subject_to(&text_lower, text));
 
         // Pattern 3: "except as provided in [section reference]"
-        if let Some(rule) = self.detect_except_as_provided(&text_lower, text) {
-            if rule.confidence >= self.min_confidence {
-                rules.push(rule);
-            }
-        }
+        rules.extend(self.detect_except_as_provided(&text_lower, text));
 
         // Pattern 4: "in case of conflict, [section] shall prevail"
-        if let Some(rule) = self.detect_in_case_of_conflict(&text_lower, text) {
-            if rule.confidence >= self.min_confidence {
-                rules.push(rule);
-            }
-        }
+        rules.extend(self.detect_in_case_of_conflict(&text_lower, text));
 
+        rules.retain(|rule| rule.confidence >= self.min_confidence);
+        rules.sort_by_key(|rule| rule.declaration_span.start.token);
         rules
     }
 
-    /// Detects "notwithstanding X" pattern (override).
-    fn detect_notwithstanding(&self, text_lower: &str, original: &str) -> Option<PrecedenceRule> {
-        let pattern = "notwithstanding";
-        if let Some(start_idx) = text_lower.find(pattern) {
-            let confidence = 0.9; // High confidence for explicit override
-            let declaration_start = start_idx;
-            let declaration_end = start_idx + pattern.len();
-
-            // Create spans (simplified - in real implementation would use DocSpan)
-            let declaration_span = self.make_span(0, declaration_start, declaration_end);
-            let overriding_span = self.make_span(0, 0, original.len());
-
-            return Some(PrecedenceRule::new(
-                declaration_span,
-                overriding_span,
-                None, // Would extract section reference in full implementation
-                true, // is_override
-                pattern,
-                confidence,
-            ));
-        }
-        None
+    /// Detects "notwithstanding X" patterns (override).
+    fn detect_notwithstanding(&self, text_lower: &str, original: &str) -> Vec<PrecedenceRule> {
+        // High confidence for explicit override
+        self.detect_connective(text_lower, original, "notwithstanding", true, 0.9)
     }
 
-    /// Detects "subject to X" pattern (subordination).
-    fn detect_subject_to(&self, text_lower: &str, original: &str) -> Option<PrecedenceRule> {
-        let pattern = "subject to";
-        if let Some(start_idx) = text_lower.find(pattern) {
-            let confidence = 0.85; // High confidence for explicit subordination
-            let declaration_start = start_idx;
-            let declaration_end = start_idx + pattern.len();
-
-            let declaration_span = self.make_span(0, declaration_start, declaration_end);
-            let overriding_span = self.make_span(0, 0, original.len());
-
-            return Some(PrecedenceRule::new(
-                declaration_span,
-                overriding_span,
-                None,
-                false, // is_override = false (this clause defers to another)
-                pattern,
-                confidence,
-            ));
-        }
-        None
+    /// Detects "subject to X" patterns (subordination).
+    fn detect_subject_to(&self, text_lower: &str, original: &str) -> Vec<PrecedenceRule> {
+        // High confidence for explicit subordination; is_override = false
+        // (this clause defers to another)
+        self.detect_connective(text_lower, original, "subject to", false, 0.85)
     }
 
-    /// Detects "except as provided in X" pattern (exception).
-    fn detect_except_as_provided(&self, text_lower: &str, original: &str) -> Option<PrecedenceRule> {
-        let pattern = "except as provided in";
-        if let Some(start_idx) = text_lower.find(pattern) {
-            let confidence = 0.8; // Good confidence for exception pattern
-            let declaration_start = start_idx;
-            let declaration_end = start_idx + pattern.len();
-
-            let declaration_span = self.make_span(0, declaration_start, declaration_end);
-            let overriding_span = self.make_span(0, 0, original.len());
-
-            return Some(PrecedenceRule::new(
-                declaration_span,
-                overriding_span,
-                None,
-                true, // is_override (the referenced section overrides this one)
-                pattern,
-                confidence,
-            ));
-        }
-        None
+    /// Detects "except as provided in X" patterns (exception).
+    fn detect_except_as_provided(&self, text_lower: &str, original: &str) -> Vec<PrecedenceRule> {
+        // Good confidence for exception pattern; the referenced section
+        // overrides this one
+        self.detect_connective(text_lower, original, "except as provided in", true, 0.8)
     }
 
-    /// Detects "in case of conflict, X shall prevail" pattern.
-    fn detect_in_case_of_conflict(&self, text_lower: &str, original: &str) -> Option<PrecedenceRule> {
-        let pattern = "in case of conflict";
-        if text_lower.contains(pattern) && text_lower.contains("prevail") {
-            let start_idx = text_lower.find(pattern)?;
-            let confidence = 0.95; // Very high confidence for explicit conflict resolution
-            let declaration_start = start_idx;
-            let declaration_end = start_idx + pattern.len();
-
-            let declaration_span = self.make_span(0, declaration_start, declaration_end);
-            let overriding_span = self.make_span(0, 0, original.len());
-
-            return Some(PrecedenceRule::new(
-                declaration_span,
-                overriding_span,
-                None,
-                true,
-                pattern,
-                confidence,
-            ));
-        }
-        None
+    /// Detects "in case of conflict, X shall prevail" patterns.
+    ///
+    /// Only occurrences whose sentence says what prevails count.
+    fn detect_in_case_of_conflict(&self, text_lower: &str, original: &str) -> Vec<PrecedenceRule> {
+        // Very high confidence for explicit conflict resolution
+        self.detect_connective(text_lower, original, "in case of conflict", true, 0.95)
+            .into_iter()
+            .filter(|rule| {
+                let sentence = rule.overriding_span.start.token..rule.overriding_span.end.token;
+                text_lower[sentence].contains("prevail")
+            })
+            .collect()
+    }
+
+    /// One rule per non-overlapping occurrence of `pattern`.
+    fn detect_connective(
+        &self,
+        text_lower: &str,
+        original: &str,
+        pattern: &str,
+        is_override: bool,
+        confidence: f64,
+    ) -> Vec<PrecedenceRule> {
+        text_lower
+            .match_indices(pattern)
+            .map(|(start_idx, _)| {
+                let declaration_end = start_idx + pattern.len();
+                let declaration_span = self.make_span(0, start_idx, declaration_end);
+                let (sentence_start, sentence_end) =
+                    sentence_bounds(text_lower, start_idx, declaration_end);
+                let overriding_span = self.make_span(0, sentence_start, sentence_end);
+                let reference =
+                    self.referenced_section(&original[declaration_end..sentence_end]);
+
+                let rule = PrecedenceRule::new(
+                    declaration_span,
+                    overriding_span,
+                    reference.as_ref().map(|(start, end, _)| {
+                        self.make_span(0, declaration_end + start, declaration_end + end)
+                    }),
+                    is_override,
+                    pattern,
+                    confidence,
+                );
+                match reference {
+                    Some((_, _, canonical)) => rule.with_overridden_section(canonical),
+                    None => rule,
+                }
+            })
+            .collect()
+    }
+
+    /// The section reference opening `text`, as byte offsets into `text` and
+    /// the target's canonical identifier.
+    ///
+    /// References further into the sentence ("in case of conflict between
+    /// this Agreement and Schedule 1") name the conflicting parties rather
+    /// than the overridden section, and relative references ("this Section")
+    /// have no target, so neither is returned.
+    fn referenced_section(&self, text: &str) -> Option<(usize, usize, String)> {
+        let line = create_line_from_string(text).run(&SectionReferenceResolver::new());
+        line.find(&x::attr::<SectionReference>())
+            .into_iter()
+            .filter(|found| text[..found.range().0].trim().is_empty())
+            .find_map(|found| {
+                let (start, end) = found.range();
+                let target = found.attr().target.as_ref()?;
+                Some((start, end, target.canonical()))
+            })
     }
 
     /// Helper to create a DocSpan (simplified for this implementation).
@@ -383,6 +384,29 @@ impl Default for PrecedenceDetector {
     }
 }
 
+/// Byte range of the sentence around `start..end`.
+///
+/// Sentences end at "." or ";" followed by whitespace or the end of the text,
+/// so section numbers like "3.1" do not split them. The range starts at the
+/// sentence's first non-whitespace byte and includes the closing terminator.
+fn sentence_bounds(text: &str, start: usize, end: usize) -> (usize, usize) {
+    let is_terminator = |&(idx, c): &(usize, char)| {
+        matches!(c, '.' | ';') && text[idx + 1..].chars().next().is_none_or(char::is_whitespace)
+    };
+    let sentence_start = text[..start]
+        .char_indices()
+        .rev()
+        .find(is_terminator)
+        .map_or(0, |(idx, _)| idx + 1);
+    let sentence_start = start - text[sentence_start..start].trim_start().len();
+    let sentence_end = text[end..]
+        .char_indices()
+        .map(|(idx, c)| (end + idx, c))
+        .find(is_terminator)
+        .map_or(text.len(), |(idx, _)| idx + 1);
+    (sentence_start, sentence_end)
+}
+
 // ============================================================================
 // Gate 3: Section Classification
 // ============================================================================
@@ -423,18 +447,75 @@ impl<'a> SectionClassifier<'a> {
             .and_then(|node| self.extract_section_kind(&node.header.identifier))
     }
 
+    /// Finds the span of the innermost exhibit, schedule, annex or appendix
+    /// containing `span`.
+    ///
+    /// An ancillary section runs from its header to the next ancillary header
+    /// (or the end of the document), so numbered paragraphs inside an exhibit
+    /// belong to it. Sections are recognized by their header keyword.
+    pub fn ancillary_section_span(&self, span: &DocSpan) -> Option<DocSpan> {
+        use crate::section_header::{SectionIdentifier, SectionKind as HeaderKind};
+
+        let is_ancillary = |node: &crate::document_structure::SectionNode| {
+            matches!(
+                node.header.identifier,
+                SectionIdentifier::Named {
+                    kind: HeaderKind::Exhibit
+                        | HeaderKind::Schedule
+                        | HeaderKind::Annex
+                        | HeaderKind::Appendix,
+                    ..
+                }
+            )
+        };
+
+        let mut nodes = self.structure.flatten();
+        nodes.sort_by_key(|node| node.start_line);
+
+        let mut innermost = None;
+        for (i, node) in nodes.iter().enumerate() {
+            if !is_ancillary(node) {
+                continue;
+            }
+            let last = nodes[i + 1..]
+                .iter()
+                .take_while(|next| !is_ancillary(next))
+                .last()
+                .unwrap_or(node);
+            let section = DocSpan::new(node.content_span.start, last.content_span.end);
+            if section.contains(span) {
+                innermost = Some(section);
+            }
+        }
+        innermost
+    }
+
+    /// Finds the section a precedence rule refers to by canonical identifier.
+    ///
+    /// "SECTION:3.1" also matches a bare "3.1" header, as in
+    /// `SectionReferenceLinker`.
+    pub(crate) fn find_referenced_section(
+        &self,
+        canonical: &str,
+    ) -> Option<&'a crate::document_structure::SectionNode> {
+        self.structure.find_by_label(canonical).or_else(|| {
+            let (_, sub) = canonical.split_once(':')?;
+            self.structure.find_by_label(sub)
+        })
+    }
+
     /// Finds the section node that contains the given span.
     ///
     /// Returns the deepest (most nested) section containing the span.
-    fn find_containing_section(
+    pub(crate) fn find_containing_section(
         &self,
         span: &DocSpan,
-    ) -> Option<&crate::document_structure::SectionNode> {
-        let mut best_match: Option<&crate::document_structure::SectionNode> = None;
+    ) -> Option<&'a crate::document_structure::SectionNode> {
+        let mut best_match: Option<&'a crate::document_structure::SectionNode> = None;
         let mut best_depth: u8 = 0;
 
         for node in self.structure.flatten() {
-            if self.span_contains(&node.content_span, span) {
+            if node.content_span.contains(span) {
                 let depth = node.depth();
                 if depth >= best_depth {
                     best_match = Some(node);
@@ -446,19 +527,6 @@ impl<'a> SectionClassifier<'a> {
         best_match
     }
 
-    /// Checks if container_span contains the target_span.
-    fn span_contains(&self, container: &DocSpan, target: &DocSpan) -> bool {
-        // Container start <= target start
-        let starts_before_or_at = (container.start.line, container.start.token)
-            <= (target.start.line, target.start.token);
-
-        // Container end >= target end
-        let ends_after_or_at =
-            (container.end.line, container.end.token) >= (target.end.line, target.end.token);
-
-        starts_before_or_at && ends_after_or_at
-    }
-
     /// Extracts the SectionKind from a SectionIdentifier.
     fn extract_section_kind(
         &self,
@@ -623,7 +691,7 @@ impl PrecedenceResolver {
         rules: &[PrecedenceRule],
     ) -> ConflictResolution {
         // Strategy 1: Try explicit precedence rules
-        if let Some(resolution) = self.try_explicit_resolution(conflict, rules) {
+        if let Some(resolution) = self.try_explicit_resolution(conflict, rules, None) {
             return resolution;
         }
 
@@ -659,7 +727,7 @@ impl PrecedenceResolver {
         classifier: &SectionClassifier,
     ) -> ConflictResolution {
         // Strategy 1: Try explicit precedence rules
-        if let Some(resolution) = self.try_explicit_resolution(conflict, rules) {
+        if let Some(resolution) = self.try_explicit_resolution(conflict, rules, Some(classifier)) {
             return resolution;
         }
 
@@ -712,22 +780,28 @@ impl PrecedenceResolver {
     }
 
     /// Tries to resolve using explicit precedence rules.
+    ///
+    /// With a classifier, rules naming a section only apply when the other
+    /// clause lies in that section.
     fn try_explicit_resolution(
         &self,
         conflict: &Conflict,
         rules: &[PrecedenceRule],
+        classifier: Option<&SectionClassifier>,
     ) -> Option<ConflictResolution> {
         // Find rules that apply to either span in the conflict
         for rule in rules {
             // Check if rule applies to this conflict
-            if self.rule_applies_to_conflict(rule, conflict) {
+            if let Some((with_rule, other)) =
+                self.rule_applies_to_conflict(rule, conflict, classifier)
+            {
                 // Determine winner based on rule type
                 let (winning_span, losing_span) = if rule.is_override {
                     // Override rule: the clause with the rule wins
-                    (Some(conflict.span_a), Some(conflict.span_b))
+                    (Some(with_rule), Some(other))
                 } else {
                     // Subordination rule: the referenced clause wins
-                    (Some(conflict.span_b), Some(conflict.span_a))
+                    (Some(other), Some(with_rule))
                 };
 
                 return Some(ConflictResolution::new(
@@ -818,18 +892,30 @@ impl PrecedenceResolver {
     }
 
     /// Checks if a precedence rule applies to a conflict.
-    fn rule_applies_to_conflict(&self, rule: &PrecedenceRule, conflict: &Conflict) -> bool {
-        // Check if rule's overriding span matches either conflict span
-        self.spans_overlap(&rule.overriding_span, &conflict.span_a)
-            || self.spans_overlap(&rule.overriding_span, &conflict.span_b)
-    }
-
-    /// Checks if two spans overlap.
-    fn spans_overlap(&self, span_a: &DocSpan, span_b: &DocSpan) -> bool {
-        // Spans overlap if they share any position
-        let a_before_b = (span_a.end.line, span_a.end.token) < (span_b.start.line, span_b.start.token);
-        let b_before_a = (span_b.end.line, span_b.end.token) < (span_a.start.line, span_a.start.token);
-        !a_before_b && !b_before_a
+    ///
+    /// Returns the conflicting clause carrying the rule and the other clause.
+    /// The rule's overriding span must match one clause; when the rule names a
+    /// section the classifier can find, the other clause must lie in it.
+    fn rule_applies_to_conflict(
+        &self,
+        rule: &PrecedenceRule,
+        conflict: &Conflict,
+        classifier: Option<&SectionClassifier>,
+    ) -> Option<(DocSpan, DocSpan)> {
+        let referenced = match (&rule.overridden_section, classifier) {
+            (Some(canonical), Some(classifier)) => classifier
+                .find_referenced_section(canonical)
+                .map(|section| section.full_span()),
+            _ => None,
+        };
+        let orientations = [
+            (conflict.span_a, conflict.span_b),
+            (conflict.span_b, conflict.span_a),
+        ];
+        orientations.iter().copied().find(|(with_rule, other)| {
+            rule.overriding_span.intersects(with_rule)
+                && referenced.is_none_or(|section| section.intersects(other))
+        })
     }
 
     /// Checks if span_a appears after span_b in document order.
@@ -880,15 +966,16 @@ impl Default for PrecedenceResolver {
 /// }
 /// ```
 pub fn resolve_in_document(doc: &crate::ContractDocument) -> Vec<ConflictResolution> {
-    // Step 1: Build document structure for section classification
-    let structure = crate::document_structure::DocumentStructureBuilder::build(doc).value;
+    let stage = StageSpan::start("analysis", "resolve_in_document");
+    // Step 1: Get the (cached) document structure for section classification
+    let structure = crate::document_structure::DocumentStructureExt::structure(doc);
 
     // Step 2: Detect conflicts using M1 ConflictDetector
     let conflict_detector = crate::ConflictDetector::new();
     let detected_conflicts = conflict_detector.detect_in_document(doc);
 
     // Step 3: Create section classifier
-    let classifier = SectionClassifier::new(&structure);
+    let classifier = SectionClassifier::new(structure);
 
     // Step 4: Resolve each conflict
     // Note: Precedence rule detection from document text (Step 3) is deferred
@@ -897,12 +984,14 @@ pub fn resolve_in_document(doc: &crate::ContractDocument) -> Vec<ConflictResolut
     let resolver = PrecedenceResolver::new();
     let empty_rules: Vec<PrecedenceRule> = Vec::new();
 
-    detected_conflicts
+    let resolutions: Vec<ConflictResolution> = detected_conflicts
         .into_iter()
         .map(|scored_conflict| {
             resolver.resolve_with_classifier(&scored_conflict.value, &empty_rules, &classifier)
         })
-        .collect()
+        .collect();
+    stage.finish(|| resolutions.len());
+    resolutions
 }
 
 // ============================================================================
@@ -1224,6 +1313,62 @@ mod tests {
         assert!(rules.iter().any(|r| r.connective == "subject to"));
     }
 
+    #[test]
+    fn test_precedence_detector_repeated_connective() {
+        let detector = PrecedenceDetector::new();
+        let text = "Notwithstanding Section 2.1, the Vendor may suspend delivery. \
+                    Notwithstanding Section 4, the Buyer may withhold payment.";
+        let rules = detector.detect_in_text(text);
+
+        assert_eq!(rules.len(), 2);
+        let second_start = text.rfind("Notwithstanding").unwrap();
+        let first_end = text.find(". ").unwrap() + 1;
+
+        assert_eq!(rules[0].declaration_span, make_span(0, 0, "Notwithstanding".len()));
+        assert_eq!(rules[0].overriding_span, make_span(0, 0, first_end));
+        assert_eq!(
+            rules[1].declaration_span,
+            make_span(0, second_start, second_start + "Notwithstanding".len())
+        );
+        assert_eq!(rules[1].overriding_span.start.token, second_start);
+        assert_eq!(rules[1].overriding_span.end.token, text.len());
+        assert!(!rules[0].overriding_span.intersects(&rules[1].overriding_span));
+    }
+
+    #[test]
+    fn test_precedence_detector_extracts_referenced_section() {
+        let detector = PrecedenceDetector::new();
+        let text = "Notwithstanding Section 3.1, the Company may terminate. \
+                    Subject to Article IV, the Vendor shall deliver. \
+                    Except as provided in this Section, fees are final.";
+        let rules = detector.detect_in_text(text);
+        assert_eq!(rules.len(), 3);
+
+        let section = text.find("Section 3.1").unwrap();
+        assert_eq!(
+            rules[0].overridden_span,
+            Some(make_span(0, section, section + "Section 3.1".len()))
+        );
+        assert_eq!(rules[0].overridden_section.as_deref(), Some("SECTION:3.1"));
+
+        let article = text.find("Article IV").unwrap();
+        assert_eq!(
+            rules[1].overridden_span,
+            Some(make_span(0, article, article + "Article IV".len()))
+        );
+        assert_eq!(rules[1].overridden_section.as_deref(), Some("ARTICLE:R4"));
+
+        // A relative reference names no section
+        assert_eq!(rules[2].overridden_span, None);
+        assert_eq!(rules[2].overridden_section, None);
+
+        // Only a reference directly after the connective is the overridden one
+        let conflict = detector.detect_in_text(
+            "In case of conflict between this Agreement and Schedule 1, this Agreement shall prevail.",
+        );
+        assert_eq!(conflict[0].overridden_section, None);
+    }
+
     #[test]
     fn test_precedence_detector_case_insensitive() {
         let detector = PrecedenceDetector::new();
@@ -1373,25 +1518,6 @@ mod tests {
         assert!(resolutions.iter().all(|r| r.is_resolved()));
     }
 
-    #[test]
-    fn test_precedence_resolver_span_overlap_detection() {
-        let resolver = PrecedenceResolver::new();
-
-        // Overlapping spans
-        let span_a = make_span(5, 0, 100);
-        let span_b = make_span(5, 50, 150);
-        assert!(resolver.spans_overlap(&span_a, &span_b));
-
-        // Contained span
-        let span_c = make_span(5, 10, 20);
-        assert!(resolver.spans_overlap(&span_a, &span_c));
-
-        // Non-overlapping spans
-        let span_d = make_span(10, 0, 50);
-        let span_e = make_span(15, 0, 50);
-        assert!(!resolver.spans_overlap(&span_d, &span_e));
-    }
-
     #[test]
     fn test_precedence_resolver_span_ordering() {
         let resolver = PrecedenceResolver::new();
@@ -1705,6 +1831,55 @@ ABC Corp (the "Company") may deliver goods."#;
         ));
     }
 
+    #[test]
+    fn test_explicit_rule_applies_only_to_referenced_section() {
+        use crate::document_structure::DocumentStructureBuilder;
+
+        let text = r#"Section 1 Main Terms
+Notwithstanding Section 3, ABC Corp (the "Company") shall deliver goods.
+Section 2 Other Terms
+ABC Corp (the "Company") may deliver goods.
+Section 3 Special Cases
+ABC Corp (the "Company") may deliver goods."#;
+
+        let doc = run_full_precedence_pipeline(text);
+        let structure = DocumentStructureBuilder::build(&doc).value;
+        let classifier = SectionClassifier::new(&structure);
+        let resolver = PrecedenceResolver::new();
+        let rule = PrecedenceRule::new(
+            make_span(1, 0, 0),
+            make_span(1, 0, 15),
+            Some(make_span(1, 2, 4)),
+            true,
+            "notwithstanding",
+            0.9,
+        )
+        .with_overridden_section("SECTION:3");
+
+        // The referenced Section 3 clause loses, even listed first
+        let with_section_3 = Conflict::new(
+            make_span(5, 5, 10),
+            make_span(1, 5, 10),
+            ConflictType::ModalConflict,
+            "Modal conflict: shall vs may",
+        );
+        let rules = std::slice::from_ref(&rule);
+        let resolution = resolver.resolve_with_classifier(&with_section_3, rules, &classifier);
+        assert!(matches!(resolution.basis, ResolutionBasis::ExplicitPrecedence { .. }));
+        assert_eq!(resolution.winning_span, Some(make_span(1, 5, 10)));
+        assert_eq!(resolution.losing_span, Some(make_span(5, 5, 10)));
+
+        // A conflict with Section 2 is outside the rule
+        let with_section_2 = Conflict::new(
+            make_span(1, 5, 10),
+            make_span(3, 5, 10),
+            ConflictType::ModalConflict,
+            "Modal conflict: shall vs may",
+        );
+        let resolution = resolver.resolve_with_classifier(&with_section_2, &[rule], &classifier);
+        assert!(!matches!(resolution.basis, ResolutionBasis::ExplicitPrecedence { .. }));
+    }
+
     #[test]
     fn test_resolve_in_document_temporal_fallback() {
         let text = r#"ABC Corp (the "Company") shall deliver goods.
@@ -13,15 +13,20 @@
 //!
 //! Chains enable downstream systems to answer questions like "who is 'it' referring to?"
 //! and to aggregate all obligations/permissions for a given party.
+//!
+//! Chains are built per line. [`PronounChain::to_entity`] turns one chain into
+//! a [`CanonicalEntity`], and [`entities`] merges the chains of every line that
+//! name the same entity into one record per entity.
 
 use std::collections::HashMap;
 
-use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver};
+use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, ResolverTrace};
 
 use crate::defined_term::DefinedTerm;
+use crate::obligation::{ObligationPhrase, ObligorReference};
 use crate::pronoun::PronounReference;
-use crate::Scored;
 use crate::term_reference::TermReference;
+use crate::{ContractDocument, Scored};
 
 /// A single mention within a pronoun chain.
 #[derive(Debug, Clone, PartialEq)]
@@ -76,6 +81,144 @@ impl PronounChain {
             .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
             .unwrap_or(0.0)
     }
+
+    /// Collapse this chain into an entity record.
+    ///
+    /// `self` must be a chain annotated on one of `doc`'s lines; its mentions
+    /// are placed on that line and the obligations counted are those of that
+    /// line whose obligor names the entity, directly or through a pronoun.
+    /// A chain not found in `doc` gives an entity without mentions.
+    pub fn to_entity(&self, doc: &ContractDocument) -> CanonicalEntity {
+        let Some(line_index) = self.line_in(doc) else {
+            return CanonicalEntity {
+                name: self.canonical_name.clone(),
+                is_defined_term: self.is_defined_term,
+                surface_forms: Vec::new(),
+                mentions: Vec::new(),
+                obligation_count: 0,
+            };
+        };
+
+        let mentions: Vec<EntityMention> = self
+            .mentions
+            .iter()
+            .map(|mention| EntityMention {
+                line: line_index,
+                mention: mention.clone(),
+            })
+            .collect();
+        let mut surface_forms: Vec<String> = Vec::new();
+        for mention in &self.mentions {
+            if !surface_forms.contains(&mention.text) {
+                surface_forms.push(mention.text.clone());
+            }
+        }
+        let obligation_count = doc.lines()[line_index]
+            .find(&x::attr::<Scored<ObligationPhrase>>())
+            .iter()
+            .filter(|found| names_entity(&found.attr().value.obligor, &self.canonical_name))
+            .count();
+
+        CanonicalEntity {
+            name: self.canonical_name.clone(),
+            is_defined_term: self.is_defined_term,
+            surface_forms,
+            mentions,
+            obligation_count,
+        }
+    }
+
+    /// Index of the line of `doc` carrying this chain.
+    fn line_in(&self, doc: &ContractDocument) -> Option<usize> {
+        let carries = |line: &layered_nlp::LLLine, same: &dyn Fn(&PronounChain) -> bool| {
+            line.find(&x::attr::<Scored<PronounChain>>())
+                .iter()
+                .any(|found| same(&found.attr().value))
+        };
+        // Prefer the very chain borrowed from `doc`, then an equal one
+        let lines = doc.lines();
+        lines
+            .iter()
+            .position(|line| carries(line, &|chain| std::ptr::eq(chain, self)))
+            .or_else(|| lines.iter().position(|line| carries(line, &|chain| chain == self)))
+    }
+}
+
+/// Whether an obligor names the entity called `name`.
+fn names_entity(obligor: &ObligorReference, name: &str) -> bool {
+    match obligor {
+        ObligorReference::TermRef { term_name, .. } => term_name.eq_ignore_ascii_case(name),
+        ObligorReference::PronounRef { resolved_to, .. } => resolved_to.eq_ignore_ascii_case(name),
+        ObligorReference::NounPhrase { .. } | ObligorReference::Unknown => false,
+    }
+}
+
+/// A chain mention placed on its line of the document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityMention {
+    /// Line of the document holding the mention
+    pub line: usize,
+    pub mention: ChainMention,
+}
+
+/// One entity of a document with every mention of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanonicalEntity {
+    /// The canonical name (usually the defined term name)
+    pub name: String,
+    /// Whether any merged chain is rooted in a formally defined term
+    pub is_defined_term: bool,
+    /// Distinct surface texts of the mentions, in order of first appearance
+    pub surface_forms: Vec<String>,
+    /// Definitions, term references and pronouns, in document order
+    pub mentions: Vec<EntityMention>,
+    /// Obligations whose obligor is this entity
+    pub obligation_count: usize,
+}
+
+impl CanonicalEntity {
+    /// Get the number of pronoun mentions.
+    pub fn pronoun_count(&self) -> usize {
+        self.mentions
+            .iter()
+            .filter(|m| m.mention.mention_type == MentionType::Pronoun)
+            .count()
+    }
+
+    /// Fold another record of the same entity into this one.
+    fn merge(&mut self, other: CanonicalEntity) {
+        self.is_defined_term |= other.is_defined_term;
+        for form in other.surface_forms {
+            if !self.surface_forms.contains(&form) {
+                self.surface_forms.push(form);
+            }
+        }
+        self.mentions.extend(other.mentions);
+        self.mentions.sort_by_key(|m| (m.line, m.mention.token_offset));
+        self.obligation_count += other.obligation_count;
+    }
+}
+
+/// Every entity of a document, merging the per-line chains with the same
+/// canonical name (compared case-insensitively), e.g. "the Company" in two
+/// sections split by a line break.
+///
+/// Requires `PronounChainResolver` to have run on the document's lines, and
+/// `ObligationPhraseResolver` for obligation counts. Entities are ordered by
+/// first mention.
+pub fn entities(doc: &ContractDocument) -> Vec<CanonicalEntity> {
+    let mut entities: Vec<CanonicalEntity> = Vec::new();
+    for line in doc.lines() {
+        for found in line.find(&x::attr::<Scored<PronounChain>>()) {
+            let entity = found.attr().value.to_entity(doc);
+            match entities.iter_mut().find(|e| e.name.eq_ignore_ascii_case(&entity.name)) {
+                Some(existing) => existing.merge(entity),
+                None => entities.push(entity),
+            }
+        }
+    }
+    entities.sort_by_key(|e| e.mentions.first().map(|m| (m.line, m.mention.token_offset)));
+    entities
 }
 
 /// Internal structure for building chains during resolution.
@@ -198,6 +341,14 @@ impl Resolver for PronounChainResolver {
     type Attr = Scored<PronounChain>;
 
     fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        self.go_traced(selection, &mut ResolverTrace::disabled())
+    }
+
+    fn go_traced(
+        &self,
+        selection: LLSelection,
+        trace: &mut ResolverTrace,
+    ) -> Vec<LLCursorAssignment<Self::Attr>> {
         // Step 1: Collect all defined terms as chain seeds
         let defined_terms: Vec<_> = selection
             .find_by(&x::attr::<Scored<DefinedTerm>>())
@@ -272,12 +423,22 @@ impl Resolver for PronounChainResolver {
             // Find the best candidate that has a chain
             for candidate in &scored_pron.value.candidates {
                 if candidate.confidence < self.min_attachment_confidence {
+                    if trace.is_enabled() {
+                        let reason = format!(
+                            "candidate {:?} confidence {:.2} below threshold {:.2}",
+                            candidate.text, candidate.confidence, self.min_attachment_confidence
+                        );
+                        trace.reject(sel, reason);
+                    }
                     continue;
                 }
 
                 let key = candidate.text.to_lowercase();
 
                 if let Some(chain) = chains.get_mut(&key) {
+                    if trace.is_enabled() {
+                        trace.accept(sel, format!("attached to chain {:?}", chain.canonical_name));
+                    }
                     chain.add_mention(ChainMention {
                         text: scored_pron.value.pronoun.clone(),
                         mention_type: MentionType::Pronoun,
@@ -0,0 +1,377 @@
+//! Obligations whose body is a quotation after a colon.
+//!
+//! ```text
+//! The Company agrees as follows: "all fees are due within 30 days."
+//! The Supplier shall comply with the following:
+//! "Records shall be kept for seven years."
+//! ```
+//!
+//! The lead-in names the obligor, and the modal if there is one, but the
+//! substance is quoted, typically from an incorporated policy. The line-level
+//! `ObligationPhraseResolver` stops the action at the colon ("comply with the
+//! following") and finds no obligation at all in "agrees as follows".
+//! [`QuotedObligationResolver`] produces one [`ObligationPhrase`] per quotation,
+//! taking the obligor and modality from the lead-in and the action from the
+//! quoted text.
+
+use layered_nlp::{x, LLLine, LToken, TextTag};
+use layered_nlp_document::{DocumentResolver, LayeredDocument};
+
+use crate::obligation::{
+    ObligationConfidenceBreakdown, ObligationNature, ObligationPhrase, ObligationType,
+    ObligorReference,
+};
+use crate::pronoun::PronounReference;
+use crate::term_reference::TermReference;
+use crate::{DocPosition, DocSpan, Scored};
+
+/// Verbs that commit their subject without a modal ("The Company agrees as follows:").
+const COMMITMENT_VERBS: &[&str] =
+    &["agree", "agrees", "covenant", "covenants", "undertake", "undertakes"];
+
+/// Keyword strength of a commitment-verb lead-in, below that of a modal.
+const COMMITMENT_VERB_STRENGTH: f64 = 0.65;
+
+/// Separators trimmed from the end of the quoted text.
+const TRAILING_SEPARATORS: &[&str] = &[",", ";", ".", ":"];
+
+/// An obligation made of a lead-in clause and a quotation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotedObligation {
+    /// The quoted text the action was taken from, without its quotes
+    pub body_span: DocSpan,
+    /// The modal or commitment verb of the lead-in
+    pub lead_in_span: DocSpan,
+    /// The lead-in's obligation with the quoted text as its action
+    pub obligation: ObligationPhrase,
+}
+
+/// Document-level resolver for obligations quoted after a colon lead-in.
+///
+/// The quotation must open directly after the colon, on the same line or at
+/// the start of the next, with `"` or `“`, and may run over several lines.
+/// The lead-in is the last `Scored<ObligationPhrase>` before the colon, or
+/// failing that a commitment verb ("agrees", "undertakes", "covenants")
+/// whose obligor is the defined term, pronoun or capitalized word before it.
+///
+/// Requires `ObligationPhraseResolver` (and its prerequisites) to have run on
+/// the document's lines.
+#[derive(Debug, Clone)]
+pub struct QuotedObligationResolver {
+    /// Multiplier applied to the lead-in confidence
+    quote_confidence_factor: f64,
+}
+
+impl Default for QuotedObligationResolver {
+    fn default() -> Self {
+        Self {
+            quote_confidence_factor: 0.9,
+        }
+    }
+}
+
+/// A lead-in clause: its span, its obligation and that obligation's confidence.
+struct LeadIn {
+    span: DocSpan,
+    obligation: ObligationPhrase,
+    confidence: f64,
+}
+
+impl QuotedObligationResolver {
+    /// Create a new resolver with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the multiplier applied to the lead-in confidence.
+    pub fn with_quote_confidence_factor(mut self, factor: f64) -> Self {
+        self.quote_confidence_factor = factor;
+        self
+    }
+
+    /// The lead-in before the colon at `colon`.
+    fn lead_in(line_idx: usize, line: &LLLine, colon: usize) -> Option<LeadIn> {
+        let modal = line
+            .find(&x::attr::<Scored<ObligationPhrase>>())
+            .into_iter()
+            .filter(|found| found.token_range().1 < colon)
+            .max_by_key(|found| found.token_range().0);
+        if let Some(found) = modal {
+            let (start, end) = found.token_range();
+            return Some(LeadIn {
+                span: DocSpan::single_line(line_idx, start, end),
+                obligation: found.attr().value.clone(),
+                confidence: found.attr().confidence,
+            });
+        }
+
+        let tokens = line.ll_tokens();
+        let verb = (0..colon).rev().find(|&idx| match tokens[idx].get_token() {
+            LToken::Text(text, TextTag::WORD) => {
+                COMMITMENT_VERBS.contains(&text.to_lowercase().as_str())
+            }
+            _ => false,
+        })?;
+        let obligor = Self::obligor_before(line, verb)?;
+        let obligation = ObligationPhrase {
+            obligor,
+            obligation_type: ObligationType::Duty,
+            action: String::new(),
+            conditions: Vec::new(),
+            confidence_breakdown: ObligationConfidenceBreakdown {
+                keyword_strength: COMMITMENT_VERB_STRENGTH,
+                ..Default::default()
+            },
+            qualifiers: Vec::new(),
+            object: None,
+            performer: None,
+            obligation_nature: ObligationNature::Covenant,
+            discretion: None,
+        };
+        Some(LeadIn {
+            span: DocSpan::single_line(line_idx, verb, verb),
+            confidence: obligation.confidence_breakdown.total(),
+            obligation,
+        })
+    }
+
+    /// The subject of a commitment verb: the nearest defined-term reference or
+    /// resolved pronoun before it, else the capitalized word directly before it.
+    fn obligor_before(line: &LLLine, verb: usize) -> Option<ObligorReference> {
+        let term = line
+            .find(&x::attr::<Scored<TermReference>>())
+            .into_iter()
+            .filter(|found| found.token_range().1 < verb)
+            .max_by_key(|found| found.token_range().0)
+            .map(|found| {
+                let term_ref = found.attr();
+                (
+                    found.token_range().0,
+                    ObligorReference::TermRef {
+                        term_name: term_ref.value.term_name.clone(),
+                        confidence: term_ref.confidence,
+                    },
+                )
+            });
+        let pronoun = line
+            .find(&x::attr::<Scored<PronounReference>>())
+            .into_iter()
+            .filter(|found| {
+                found.token_range().1 < verb && !found.attr().value.candidates.is_empty()
+            })
+            .max_by_key(|found| found.token_range().0)
+            .map(|found| {
+                let pron_ref = found.attr();
+                let best = &pron_ref.value.candidates[0];
+                (
+                    found.token_range().0,
+                    ObligorReference::PronounRef {
+                        pronoun: pron_ref.value.pronoun.clone(),
+                        resolved_to: best.text.clone(),
+                        is_defined_term: best.is_defined_term,
+                        confidence: pron_ref.confidence,
+                    },
+                )
+            });
+        let nearest = term.into_iter().chain(pronoun).max_by_key(|(start, _)| *start);
+        if let Some((_, obligor)) = nearest {
+            return Some(obligor);
+        }
+
+        // Skip punctuation so "ABC Corp (the "Buyer") undertakes" names the Buyer
+        let tokens = line.ll_tokens();
+        (0..verb).rev().find_map(|idx| match tokens[idx].get_token() {
+            LToken::Text(text, TextTag::WORD) if text.starts_with(char::is_uppercase) => {
+                Some(Some(ObligorReference::NounPhrase { text: text.clone() }))
+            }
+            LToken::Text(_, TextTag::WORD | TextTag::NATN) => Some(None),
+            _ => None,
+        })?
+    }
+}
+
+/// The first non-space token at or after `start`, as (index, text).
+fn next_text(line: &LLLine, start: usize) -> Option<(usize, &str)> {
+    line.ll_tokens()
+        .iter()
+        .enumerate()
+        .skip(start)
+        .find_map(|(idx, token)| match token.get_token() {
+            LToken::Text(_, TextTag::SPACE) => None,
+            LToken::Text(text, _) => Some((idx, text.as_str())),
+            LToken::Value => None,
+        })
+}
+
+fn is_open_quote(text: &str) -> bool {
+    matches!(text, "\"" | "“")
+}
+
+fn is_close_quote(text: &str) -> bool {
+    matches!(text, "\"" | "”")
+}
+
+/// The quoted text opening at `open`, as its span and its words, without
+/// trailing separators. `None` if the quotation is never closed or is empty.
+fn quotation(lines: &[LLLine], open: DocPosition) -> Option<(DocSpan, String)> {
+    let mut pieces: Vec<(DocPosition, &str)> = Vec::new();
+    let mut position = DocPosition::new(open.line, open.token + 1);
+    loop {
+        let tokens = lines.get(position.line)?.ll_tokens();
+        if position.token >= tokens.len() {
+            pieces.push((position, " "));
+            position = DocPosition::new(position.line + 1, 0);
+            continue;
+        }
+        if let LToken::Text(text, tag) = tokens[position.token].get_token() {
+            if is_close_quote(text) {
+                break;
+            }
+            pieces.push((position, if *tag == TextTag::SPACE { " " } else { text.as_str() }));
+        }
+        position.token += 1;
+    }
+
+    while pieces.last().is_some_and(|(_, text)| {
+        text.trim().is_empty() || TRAILING_SEPARATORS.contains(text)
+    }) {
+        pieces.pop();
+    }
+    let first = pieces.iter().position(|(_, text)| !text.trim().is_empty())?;
+    let pieces = &pieces[first..];
+    let text = pieces.iter().map(|(_, text)| *text).collect::<String>();
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let span = DocSpan::new(pieces[0].0, pieces[pieces.len() - 1].0);
+    Some((span, text))
+}
+
+impl DocumentResolver for QuotedObligationResolver {
+    type Attr = Scored<QuotedObligation>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        let lines = doc.lines();
+        let mut results = Vec::new();
+
+        for (line_idx, line) in doc.lines_enumerated() {
+            let colons = line.ll_tokens().iter().enumerate().filter_map(|(idx, token)| {
+                match token.get_token() {
+                    LToken::Text(text, _) if text == ":" => Some(idx),
+                    _ => None,
+                }
+            });
+            for colon in colons {
+                let open = match next_text(line, colon + 1) {
+                    Some((idx, text)) if is_open_quote(text) => DocPosition::new(line_idx, idx),
+                    Some(_) => continue,
+                    None => match lines.get(line_idx + 1).and_then(|next| next_text(next, 0)) {
+                        Some((idx, text)) if is_open_quote(text) => {
+                            DocPosition::new(line_idx + 1, idx)
+                        }
+                        _ => continue,
+                    },
+                };
+                let lead_in = match Self::lead_in(line_idx, line, colon) {
+                    Some(lead_in) => lead_in,
+                    None => continue,
+                };
+                let (body_span, text) = match quotation(lines, open) {
+                    Some(found) => found,
+                    None => continue,
+                };
+
+                let mut obligation = lead_in.obligation;
+                obligation.action = text;
+                obligation.qualifiers = Vec::new();
+                results.push(Scored::rule_based(
+                    QuotedObligation {
+                        body_span,
+                        lead_in_span: lead_in.span,
+                        obligation,
+                    },
+                    lead_in.confidence * self.quote_confidence_factor,
+                    "quoted_obligation",
+                ));
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractDocument, ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver,
+        ProhibitionResolver, PronounResolver, TermReferenceResolver,
+    };
+    use layered_part_of_speech::POSTagResolver;
+
+    fn quoted_obligations(text: &str) -> Vec<Scored<QuotedObligation>> {
+        let doc = ContractDocument::from_text(text)
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ProhibitionResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+            .run_resolver(&TermReferenceResolver::default())
+            .run_resolver(&PronounResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default());
+        QuotedObligationResolver::new().resolve(&doc)
+    }
+
+    #[test]
+    fn test_commitment_verb_lead_in() {
+        let found = quoted_obligations(
+            "The Company agrees as follows: \"all fees are due within 30 days.\"",
+        );
+
+        assert_eq!(found.len(), 1);
+        let quoted = &found[0].value;
+        assert_eq!(quoted.obligation.action, "all fees are due within 30 days");
+        assert_eq!(
+            quoted.obligation.obligor,
+            ObligorReference::NounPhrase { text: "Company".to_string() }
+        );
+        assert_eq!(quoted.obligation.obligation_type, ObligationType::Duty);
+        assert_eq!(quoted.lead_in_span, DocSpan::single_line(0, 4, 4));
+        // From "all" to "days", leaving out the quotes and the period
+        assert_eq!(quoted.body_span, DocSpan::single_line(0, 12, 24));
+        assert!(found[0].confidence < 0.75);
+    }
+
+    #[test]
+    fn test_modal_lead_in_with_quote_on_next_lines() {
+        let found = quoted_obligations(
+            "ABC Corp (the \"Supplier\") shall comply with the following:\n\
+             \u{201c}Records shall be kept\n\
+             for seven years.\u{201d}\n\
+             The Supplier agrees that the fees are final.\n\
+             XYZ Inc (the \"Buyer\") undertakes: \"Invoices shall be paid on receipt.\"",
+        );
+
+        assert_eq!(found.len(), 2);
+        let quoted = &found[0].value;
+        assert_eq!(quoted.obligation.action, "Records shall be kept for seven years");
+        assert_eq!(
+            quoted.obligation.obligor,
+            ObligorReference::NounPhrase { text: "Supplier".to_string() }
+        );
+        assert_eq!(quoted.lead_in_span, DocSpan::single_line(0, 12, 12));
+        assert_eq!(quoted.body_span, DocSpan::new(DocPosition::new(1, 1), DocPosition::new(2, 4)));
+
+        let buyer = &found[1].value;
+        assert_eq!(buyer.obligation.action, "Invoices shall be paid on receipt");
+        assert_eq!(
+            buyer.obligation.obligor,
+            ObligorReference::NounPhrase { text: "Buyer".to_string() }
+        );
+        assert_eq!(buyer.lead_in_span.start.line, 4);
+    }
+
+    #[test]
+    fn test_unquoted_or_unclosed_body_is_ignored() {
+        assert!(quoted_obligations("The Company agrees as follows: all fees are due.").is_empty());
+        assert!(quoted_obligations("The Company agrees as follows: \"all fees are due.").is_empty());
+        assert!(quoted_obligations("The following applies: \"all fees are due.\"").is_empty());
+    }
+}
@@ -21,12 +21,17 @@
 //! let negations = neg_detector.detect(&doc);
 //! let quantifiers = quant_detector.detect(&doc);
 //! ```
+//!
+//! Both detectors are also [`DocumentResolver`]s; [`ScopeOperatorResolver`]
+//! runs the two and stores their operators on the document, where they can be
+//! queried with `doc.query_doc::<Scored<ScopeOperator<NegationOp>>>()`.
 
 use crate::{
     ContractDocument, DocPosition, DocSpan, NegationKind, NegationOp, QuantifierKind,
     QuantifierOp, ScopeDimension, ScopeDomain, ScopeOperator, Scored,
 };
 use layered_nlp::{LLLine, LToken};
+use layered_nlp_document::{DocumentResolver, LayeredDocument};
 use std::collections::{HashMap, HashSet};
 
 // ============================================================================
@@ -58,34 +63,54 @@ impl NegationDetector {
     ///
     /// Returns scored scope operators with trigger spans and computed domains.
     pub fn detect(&self, doc: &ContractDocument) -> Vec<Scored<ScopeOperator<NegationOp>>> {
-        let mut results = Vec::new();
-
-        for (line_idx, line) in doc.lines_enumerated() {
-            let tokens = line.ll_tokens();
-
-            for (token_idx, token) in tokens.iter().enumerate() {
-                let text = match token.get_token() {
-                    LToken::Text(text, _) => text.to_lowercase(),
-                    LToken::Value => continue,
-                };
+        doc.lines_enumerated()
+            .flat_map(|(line_idx, line)| {
+                let tokens: Vec<Option<String>> = line
+                    .ll_tokens()
+                    .iter()
+                    .map(|token| match token.get_token() {
+                        LToken::Text(text, _) => Some(text.to_lowercase()),
+                        LToken::Value => None,
+                    })
+                    .collect();
+                self.detect_in_tokens(line_idx, &tokens)
+            })
+            .collect()
+    }
 
-                if self.markers.contains(text.as_str()) {
-                    let kind = self.classify_negation(&text);
-                    let trigger = self.make_trigger_span(line_idx, token_idx);
-                    let domain = self.compute_domain(line, line_idx, token_idx);
+    /// Detect negation operators in a single line given its lowercased token texts.
+    ///
+    /// `None` entries stand for non-text tokens, which are skipped but still
+    /// count toward token positions. Spans are reported on line `line_idx`.
+    pub fn detect_in_tokens(
+        &self,
+        line_idx: usize,
+        tokens: &[Option<String>],
+    ) -> Vec<Scored<ScopeOperator<NegationOp>>> {
+        let mut results = Vec::new();
 
-                    let op = ScopeOperator::new(
-                        ScopeDimension::Negation,
-                        trigger,
-                        domain,
-                        NegationOp {
-                            marker: text.clone(),
-                            kind,
-                        },
-                    );
+        for (token_idx, token) in tokens.iter().enumerate() {
+            let text = match token {
+                Some(text) => text,
+                None => continue,
+            };
 
-                    results.push(Scored::rule_based(op, 0.9, "negation_detector"));
-                }
+            if self.markers.contains(text.as_str()) {
+                let kind = self.classify_negation(text);
+                let trigger = self.make_trigger_span(line_idx, token_idx);
+                let domain = self.compute_domain(tokens, line_idx, token_idx);
+
+                let op = ScopeOperator::new(
+                    ScopeDimension::Negation,
+                    trigger,
+                    domain,
+                    NegationOp {
+                        marker: text.clone(),
+                        kind,
+                    },
+                );
+
+                results.push(Scored::rule_based(op, 0.9, "negation_detector"));
             }
         }
 
@@ -117,18 +142,21 @@ impl NegationDetector {
     /// - Ends at clause boundary (comma, semicolon, "except", "unless")
     /// - Ends at coordinating conjunction ("and", "or") at same clause level
     /// - Ends at end of line (sentence)
-    fn compute_domain(&self, line: &LLLine, line_idx: usize, start_token: usize) -> ScopeDomain {
-        let tokens = line.ll_tokens();
-
+    fn compute_domain(
+        &self,
+        tokens: &[Option<String>],
+        line_idx: usize,
+        start_token: usize,
+    ) -> ScopeDomain {
         // Start after the negation marker
         let domain_start = start_token + 1;
         let mut domain_end = tokens.len();
 
         // Scan rightward for scope boundaries
         for idx in domain_start..tokens.len() {
-            let text = match tokens[idx].get_token() {
-                LToken::Text(text, _) => text.to_lowercase(),
-                LToken::Value => continue,
+            let text = match &tokens[idx] {
+                Some(text) => text,
+                None => continue,
             };
 
             // Clause boundaries end scope
@@ -140,13 +168,9 @@ impl NegationDetector {
             // Coordinating conjunction at clause level
             if matches!(text.as_str(), "and" | "or") {
                 // Heuristic: if preceded by comma or at low nesting, ends scope
-                if idx > 0 {
-                    if let LToken::Text(prev_text, _) = tokens[idx - 1].get_token() {
-                        if prev_text == "," {
-                            domain_end = idx;
-                            break;
-                        }
-                    }
+                if idx > 0 && tokens[idx - 1].as_deref() == Some(",") {
+                    domain_end = idx;
+                    break;
                 }
             }
         }
@@ -172,6 +196,14 @@ impl Default for NegationDetector {
     }
 }
 
+impl DocumentResolver for NegationDetector {
+    type Attr = Scored<ScopeOperator<NegationOp>>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        self.detect(doc)
+    }
+}
+
 // ============================================================================
 // Gate 2: Quantifier Detection
 // ============================================================================
@@ -328,6 +360,41 @@ impl Default for QuantifierDetector {
     }
 }
 
+impl DocumentResolver for QuantifierDetector {
+    type Attr = Scored<ScopeOperator<QuantifierOp>>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        self.detect(doc)
+    }
+}
+
+// ============================================================================
+// Combined resolver
+// ============================================================================
+
+/// Emits negation and quantifier scope operators onto a document.
+///
+/// Runs [`NegationDetector`] and [`QuantifierDetector`] as document resolvers.
+/// Each operator carries its dimension, a trigger span, a confidence, and a
+/// tentative domain running from the marker to the end of its clause.
+#[derive(Default)]
+pub struct ScopeOperatorResolver {
+    negation: NegationDetector,
+    quantifier: QuantifierDetector,
+}
+
+impl ScopeOperatorResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store the document's negation and quantifier operators on it.
+    pub fn run(&self, doc: ContractDocument) -> ContractDocument {
+        doc.run_document_resolver(&self.negation)
+            .run_document_resolver(&self.quantifier)
+    }
+}
+
 // ============================================================================
 // Gate 3: Scope Boundary Detection
 // ============================================================================
@@ -414,7 +481,7 @@ impl ScopeBoundaryDetector {
 
         if let (Some(neg_span), Some(quant_span)) = (neg_domain, quant_domain) {
             // Check if spans overlap
-            neg_span.overlaps(quant_span)
+            neg_span.intersects(quant_span)
         } else {
             false
         }
@@ -454,6 +521,40 @@ mod tests {
         assert!(domain.start.token > 3); // After "not"
     }
 
+    #[test]
+    fn test_scope_operator_resolver_stores_operators() {
+        let doc = ScopeOperatorResolver::new().run(ContractDocument::from_text(
+            "Each party shall not assign any rights, unless agreed.",
+        ));
+
+        let negations = doc.query_doc::<Scored<ScopeOperator<NegationOp>>>();
+        assert_eq!(negations.len(), 1);
+        let negation = &negations[0];
+        assert_eq!(negation.value.dimension, ScopeDimension::Negation);
+        assert_eq!(negation.value.payload.marker, "not");
+        assert!(negation.confidence > 0.0);
+
+        // Tentative scope stops at the clause boundary before "unless"
+        let domain = negation.value.domain.primary().unwrap();
+        assert!(domain.start.token > negation.value.trigger.start.token);
+        let comma_idx = doc.lines()[0]
+            .ll_tokens()
+            .iter()
+            .position(|t| matches!(t.get_token(), LToken::Text(text, _) if text == ","))
+            .unwrap();
+        assert_eq!(domain.end.token, comma_idx);
+
+        let quantifiers = doc.query_doc::<Scored<ScopeOperator<QuantifierOp>>>();
+        let markers: Vec<&str> = quantifiers
+            .iter()
+            .map(|q| q.value.payload.marker.as_str())
+            .collect();
+        assert_eq!(markers, vec!["each", "any"]);
+        assert!(quantifiers
+            .iter()
+            .all(|q| q.value.dimension == ScopeDimension::Quantifier));
+    }
+
     #[test]
     fn test_negation_never() {
         let text = "The Buyer shall never terminate this Agreement.";
@@ -14,8 +14,12 @@ use std::collections::HashMap;
 
 use layered_nlp::x;
 
-use crate::{ContractDocument, DocSpan, ProcessError, ProcessResult};
-use crate::document_structure::{DocumentProcessor, DocumentStructure, SectionNode};
+use crate::{
+    ContractDocument, DocSpan, DocumentWarning, ProcessError, ProcessResult, WarningSeverity,
+};
+use crate::document_structure::{
+    DocumentProcessor, DocumentStructure, DocumentStructureExt, SectionNode,
+};
 use crate::section_header::{SectionHeader, SectionIdentifier};
 use crate::section_reference::{ReferenceType, SectionReference};
 
@@ -157,15 +161,14 @@ impl SectionReferenceLinker {
             let line_headers: Vec<_> = line
                 .find(&x::attr::<SectionHeader>())
                 .into_iter()
-                .map(|found| found.range())
+                .map(|found| found.token_range())
                 .collect();
 
             // Process each reference on this line
             for found in line.find(&x::attr::<SectionReference>()) {
                 let reference = (*found.attr()).clone();
-                let (ref_start, ref_end) = found.range();
-
-                let location = DocSpan::single_line(line_idx, ref_start, ref_end);
+                let (ref_start, ref_end) = found.token_range();
+                let location = DocSpan::from_find(line_idx, &found);
 
                 // Check for overlap with headers
                 if Self::overlaps_with_header(ref_start, ref_end, &line_headers) {
@@ -197,12 +200,13 @@ impl SectionReferenceLinker {
                 match &linked.resolution {
                     ReferenceResolution::Resolved { .. } => result.resolved.push(linked),
                     ReferenceResolution::Unresolved { reason, .. } => {
-                        warnings.push(format!(
-                            "Line {}: unresolved reference '{}': {}",
-                            doc.source_line_number(line_idx).unwrap_or(line_idx),
-                            linked.reference.reference_text,
-                            reason
-                        ));
+                        let message = format!(
+                            "unresolved reference '{}': {}",
+                            linked.reference.reference_text, reason
+                        );
+                        warnings.push(
+                            DocumentWarning::new(WarningSeverity::Warning, message).at(location),
+                        );
                         result.unresolved.push(linked);
                     }
                     ReferenceResolution::FilteredAsHeader => {
@@ -210,12 +214,13 @@ impl SectionReferenceLinker {
                         result.filtered.push(linked);
                     }
                     ReferenceResolution::Ambiguous { candidates, .. } => {
-                        warnings.push(format!(
-                            "Line {}: ambiguous reference '{}': could be {:?}",
-                            doc.source_line_number(line_idx).unwrap_or(line_idx),
-                            linked.reference.reference_text,
-                            candidates
-                        ));
+                        let message = format!(
+                            "ambiguous reference '{}': could be {:?}",
+                            linked.reference.reference_text, candidates
+                        );
+                        warnings.push(
+                            DocumentWarning::new(WarningSeverity::Warning, message).at(location),
+                        );
                         result.ambiguous.push(linked);
                     }
                 }
@@ -239,6 +244,20 @@ impl SectionReferenceLinker {
         }
     }
 
+    /// Resolve a single reference against `structure`, which may belong to
+    /// another document (an amendment naming sections of its base contract).
+    ///
+    /// Relative references ("this Section") have no position in `structure`
+    /// and do not resolve.
+    pub fn resolve(
+        reference: &SectionReference,
+        structure: &DocumentStructure,
+    ) -> ReferenceResolution {
+        let section_map = Self::build_section_map(structure);
+        let flattened_sections = structure.flatten();
+        Self::resolve_reference(reference, &section_map, &flattened_sections, usize::MAX)
+    }
+
     /// Build a map from canonical identifiers to section nodes.
     fn build_section_map(structure: &DocumentStructure) -> HashMap<String, SectionInfo> {
         let mut map = HashMap::new();
@@ -415,19 +434,14 @@ struct SectionInfo {
     line: usize,
 }
 
-/// Note: If you already have a `DocumentStructure`, prefer using
-/// `SectionReferenceLinker::link(doc, &structure)` directly to avoid
-/// rebuilding the structure. This impl is provided for convenience when
-/// you don't need to reuse the structure for other purposes.
+/// Uses the document's cached structure (see `DocumentStructureExt`), so
+/// repeated calls do not rebuild it. Use `SectionReferenceLinker::link`
+/// directly to link against a different structure.
 impl DocumentProcessor for SectionReferenceLinker {
     type Output = LinkedReferences;
 
     fn process(&self, doc: &ContractDocument) -> ProcessResult<Self::Output> {
-        // Build structure first (consider using link() directly if you already have one)
-        let structure_result = crate::document_structure::DocumentStructureBuilder::build(doc);
-
-        // Then link references
-        Self::link(doc, &structure_result.value)
+        Self::link(doc, doc.structure())
     }
 }
 
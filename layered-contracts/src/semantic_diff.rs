@@ -57,9 +57,9 @@ use std::sync::atomic::{AtomicU32, Ordering};
 use layered_nlp::x;
 use serde::{Deserialize, Serialize};
 
-use crate::ContractDocument;
+use crate::{ContractDocument, DocSpan};
 use crate::document_aligner::{AlignedPair, AlignmentResult, AlignmentType};
-use crate::obligation::ObligationType;
+use crate::obligation::{ObligationType, UNKNOWN_OBLIGOR};
 
 // ============================================================================
 // CORE CHANGE TYPES
@@ -134,9 +134,16 @@ pub struct ObligationModalChange {
     pub original_text: String,
     /// Revised text excerpt
     pub revised_text: String,
+    /// The obligation's modal in the original document
+    pub before_span: DocSpan,
+    /// The obligation's modal in the revised document
+    pub after_span: DocSpan,
 }
 
 /// Condition change on obligation.
+///
+/// `before_span` and `after_span` locate the modal of the qualified
+/// obligation in the original and revised documents.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConditionChange {
     /// New condition added
@@ -144,12 +151,16 @@ pub enum ConditionChange {
         condition_type: String,
         condition_text: String,
         obligation_action: String,
+        before_span: DocSpan,
+        after_span: DocSpan,
     },
     /// Existing condition removed
     Removed {
         condition_type: String,
         condition_text: String,
         obligation_action: String,
+        before_span: DocSpan,
+        after_span: DocSpan,
     },
     /// Condition text modified
     Modified {
@@ -157,6 +168,8 @@ pub enum ConditionChange {
         original_text: String,
         revised_text: String,
         obligation_action: String,
+        before_span: DocSpan,
+        after_span: DocSpan,
     },
 }
 
@@ -490,6 +503,7 @@ struct ExtractedObligation {
     conditions: Vec<String>,
     beneficiary: Option<String>,
     source_line: usize,
+    span: DocSpan,
     confidence: f64,
     full_text: String,
 }
@@ -845,6 +859,7 @@ impl SemanticDiffEngine {
                         resolved_to.clone()
                     }
                     crate::obligation::ObligorReference::NounPhrase { text } => text.clone(),
+                    crate::obligation::ObligorReference::Unknown => UNKNOWN_OBLIGOR.to_string(),
                 };
 
                 semantics.obligations.push(ExtractedObligation {
@@ -858,6 +873,7 @@ impl SemanticDiffEngine {
                         .collect(),
                     beneficiary: None,
                     source_line,
+                    span: DocSpan::from_find(idx, &found),
                     confidence: scored.confidence,
                     full_text: self.extract_line_text(line),
                 });
@@ -966,6 +982,8 @@ impl SemanticDiffEngine {
                             action: orig_obl.action.clone(),
                             original_text: orig_obl.full_text.clone(),
                             revised_text: rev_obl.full_text.clone(),
+                            before_span: orig_obl.span,
+                            after_span: rev_obl.span,
                         }),
                         risk_level,
                         party_impacts,
@@ -1026,6 +1044,8 @@ impl SemanticDiffEngine {
                         condition_type: "condition".to_string(),
                         condition_text: rev_cond.clone(),
                         obligation_action: original.action.clone(),
+                        before_span: original.span,
+                        after_span: revised.span,
                     }),
                     risk_level: RiskLevel::Medium,
                     party_impacts: vec![PartyImpact {
@@ -1058,6 +1078,8 @@ impl SemanticDiffEngine {
                             condition_type: "condition".to_string(),
                             condition_text: orig_cond.clone(),
                             obligation_action: original.action.clone(),
+                            before_span: original.span,
+                            after_span: revised.span,
                         },
                     ),
                     risk_level: RiskLevel::Medium,
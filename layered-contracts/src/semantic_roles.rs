@@ -25,6 +25,7 @@
 //! }
 //! ```
 
+use crate::obligation::UNKNOWN_OBLIGOR;
 use crate::{DocPosition, DocSpan, ObligationPhrase, ObligationType, ObligorReference, Scored};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -363,6 +364,7 @@ impl SemanticRoleLabeler {
             ObligorReference::TermRef { term_name, .. } => term_name.clone(),
             ObligorReference::PronounRef { resolved_to, .. } => resolved_to.clone(),
             ObligorReference::NounPhrase { text } => text.clone(),
+            ObligorReference::Unknown => UNKNOWN_OBLIGOR.to_string(),
         }
     }
 
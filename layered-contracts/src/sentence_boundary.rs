@@ -95,6 +95,14 @@ impl SentenceBoundaryResolver {
         self.abbreviations.contains(&normalized)
     }
 
+    /// Whether `text` ends a sentence, given the token before it.
+    ///
+    /// Used by document-level passes that walk tokens across lines.
+    pub(crate) fn ends_sentence(&self, previous: Option<&str>, text: &str) -> bool {
+        self.is_sentence_ending_punctuation(text)
+            && !previous.is_some_and(|previous| self.is_abbreviation(previous))
+    }
+
     fn starts_with_uppercase(text: &str) -> bool {
         text.chars().next().map_or(false, |c| c.is_uppercase())
     }
@@ -0,0 +1,373 @@
+//! Signature block and execution date detection.
+//!
+//! ```text
+//! IN WITNESS WHEREOF, the parties have executed this Agreement as of March 1, 2024.
+//! ACME CORPORATION
+//! By: ______________________
+//! Name: Jane Smith
+//! Title: Chief Executive Officer
+//! ```
+//!
+//! [`SignatureBlockResolver`] finds the block, reads one [`Signatory`] per
+//! "By:" entry, and picks up the date the contract was executed. Name and
+//! title may be labelled ("Name:", "Title:", "Its:") or given unlabelled on
+//! the lines following "By:", as in the common table-like layout.
+
+use chrono::NaiveDate;
+use layered_nlp::{x, LLLine};
+use layered_nlp_document::{DocumentResolver, LayeredDocument};
+
+use crate::section_header::SectionHeader;
+use crate::temporal::{TemporalExpression, TemporalType};
+use crate::utils::line_text;
+use crate::{DocPosition, DocSpan, Scored};
+
+/// A person signing on behalf of a party.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signatory {
+    /// The party signing ("ACME CORPORATION"), from the line above "By:"
+    pub party: Option<String>,
+    /// Name of the person signing
+    pub name: Option<String>,
+    /// Title or capacity ("Chief Executive Officer")
+    pub title: Option<String>,
+    /// Lines from "By:" through the last detail of this signatory
+    pub span: DocSpan,
+}
+
+/// The execution section of a contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureBlock {
+    /// Lines from the testimonium ("IN WITNESS WHEREOF") or first signatory
+    /// to the end of the block
+    pub span: DocSpan,
+    /// Signatories in document order
+    pub signatories: Vec<Signatory>,
+    /// The date the contract was executed ("executed as of March 1, 2024")
+    pub execution_date: Option<TemporalExpression>,
+}
+
+impl SignatureBlock {
+    /// The execution date as a calendar date, when it is complete.
+    ///
+    /// Use this to anchor relative deadlines when no Effective Date is stated.
+    pub fn execution_date_value(&self) -> Option<NaiveDate> {
+        match self.execution_date.as_ref()?.temporal_type {
+            TemporalType::Date {
+                year: Some(year),
+                month: Some(month),
+                day: Some(day),
+            } => NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32),
+            _ => None,
+        }
+    }
+}
+
+/// A line of a signature block, classified.
+#[derive(Debug)]
+enum BlockLine {
+    /// "By: ____" or "By: /s/ Jane Smith"
+    By(Option<String>),
+    /// "Name: Jane Smith"
+    Name(Option<String>),
+    /// "Title: CEO" / "Its: CEO"
+    Title(Option<String>),
+    /// "Date: March 1, 2024"
+    Date,
+    /// Anything else: a party name, or an unlabelled name or title
+    Text(String),
+}
+
+/// Document-level resolver for signature blocks.
+///
+/// A block starts at a line containing "IN WITNESS WHEREOF" or, without one,
+/// at the party line above the first "By:" line. It ends before the next
+/// section header or at the end of the document.
+///
+/// The execution date is the first date on a line of the block mentioning
+/// "executed", "dated" or "as of"; failing that, any such line in the
+/// document; failing that, the first "Date:" line of the block.
+///
+/// Requires `TemporalExpressionResolver` (for the execution date) and
+/// `SectionHeaderResolver` (to end the block) to have run on the lines.
+#[derive(Debug, Clone)]
+pub struct SignatureBlockResolver {
+    /// Confidence when the block opens with "IN WITNESS WHEREOF"
+    witness_confidence: f64,
+    /// Confidence when the block is inferred from "By:" lines alone
+    inferred_confidence: f64,
+}
+
+impl Default for SignatureBlockResolver {
+    fn default() -> Self {
+        Self {
+            witness_confidence: 0.9,
+            inferred_confidence: 0.7,
+        }
+    }
+}
+
+impl SignatureBlockResolver {
+    /// Create a new resolver with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn classify(text: &str) -> BlockLine {
+        let text = text.trim();
+        if let Some((label, value)) = text.split_once(':') {
+            let value = clean_value(value);
+            match label.trim().to_lowercase().as_str() {
+                "by" => return BlockLine::By(value),
+                "name" | "print name" | "printed name" => return BlockLine::Name(value),
+                "title" | "its" => return BlockLine::Title(value),
+                "date" | "dated" => return BlockLine::Date,
+                _ => {}
+            }
+        }
+        BlockLine::Text(text.to_string())
+    }
+
+    /// Read signatories from the classified lines of a block.
+    fn signatories(lines: &[(usize, usize, BlockLine)]) -> Vec<Signatory> {
+        let mut signatories: Vec<Signatory> = Vec::new();
+        // Unlabelled lines directly after "By:" fill name, then title
+        let mut expecting_details = false;
+        let mut party: Option<String> = None;
+
+        for (line, last_token, block_line) in lines {
+            let end = DocPosition::new(*line, *last_token);
+            match block_line {
+                BlockLine::By(name) => {
+                    signatories.push(Signatory {
+                        party: party.take(),
+                        name: name.clone(),
+                        title: None,
+                        span: DocSpan::new(DocPosition::new(*line, 0), end),
+                    });
+                    expecting_details = true;
+                }
+                BlockLine::Name(name) => {
+                    match signatories.last_mut().filter(|s| s.name.is_none()) {
+                        Some(current) => {
+                            current.name = name.clone();
+                            current.span.end = end;
+                        }
+                        // A "Name:" line without "By:" starts a signatory
+                        None => signatories.push(Signatory {
+                            party: party.take(),
+                            name: name.clone(),
+                            title: None,
+                            span: DocSpan::new(DocPosition::new(*line, 0), end),
+                        }),
+                    }
+                    expecting_details = true;
+                }
+                BlockLine::Title(title) => {
+                    if let Some(current) = signatories.last_mut() {
+                        current.title = title.clone();
+                        current.span.end = end;
+                    }
+                    expecting_details = false;
+                }
+                BlockLine::Date => expecting_details = false,
+                BlockLine::Text(text) => {
+                    let current = signatories.last_mut().filter(|_| expecting_details);
+                    match current {
+                        Some(current) if current.name.is_none() => {
+                            current.name = Some(text.clone());
+                            current.span.end = end;
+                        }
+                        Some(current) if current.title.is_none() => {
+                            current.title = Some(text.clone());
+                            current.span.end = end;
+                            expecting_details = false;
+                        }
+                        _ => {
+                            // The last free-standing line before "By:" names the party
+                            party = Some(text.trim_end_matches(':').trim().to_string());
+                            expecting_details = false;
+                        }
+                    }
+                }
+            }
+        }
+
+        signatories
+    }
+
+    /// The first complete or partial date on a line.
+    fn first_date(line: &LLLine) -> Option<TemporalExpression> {
+        let mut dates: Vec<((usize, usize), TemporalExpression)> = line
+            .find(&x::attr::<TemporalExpression>())
+            .into_iter()
+            .filter(|found| matches!(found.attr().temporal_type, TemporalType::Date { .. }))
+            .map(|found| (found.range(), (*found.attr()).clone()))
+            .collect();
+        dates.sort_by_key(|(range, _)| *range);
+        dates.into_iter().next().map(|(_, date)| date)
+    }
+
+    fn mentions_execution(text: &str) -> bool {
+        let lower = text.to_lowercase();
+        ["executed", "dated", "as of"].iter().any(|cue| lower.contains(cue))
+    }
+}
+
+impl DocumentResolver for SignatureBlockResolver {
+    type Attr = Scored<SignatureBlock>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        let texts: Vec<String> = doc.lines().iter().map(line_text).collect();
+
+        let witness = texts
+            .iter()
+            .position(|text| text.to_lowercase().contains("in witness whereof"));
+        let first_by = texts
+            .iter()
+            .position(|text| matches!(Self::classify(text), BlockLine::By(_)));
+        let (start, confidence) = match (witness, first_by) {
+            (Some(line), _) => (line, self.witness_confidence),
+            // Include the party line above "By:"
+            (None, Some(line)) => (line.saturating_sub(1), self.inferred_confidence),
+            (None, None) => return Vec::new(),
+        };
+
+        let end = (start + 1..doc.line_count())
+            .find(|&line| {
+                doc.lines()[line]
+                    .find(&x::attr::<SectionHeader>())
+                    .iter()
+                    .any(|found| found.range().0 <= 1)
+            })
+            .unwrap_or(doc.line_count());
+
+        let body_start = if witness.is_some() { start + 1 } else { start };
+        let lines: Vec<(usize, usize, BlockLine)> = (body_start..end)
+            .map(|line| {
+                let last_token = doc.lines()[line].ll_tokens().len().saturating_sub(1);
+                (line, last_token, Self::classify(&texts[line]))
+            })
+            .collect();
+        let signatories = Self::signatories(&lines);
+        if witness.is_none() && signatories.is_empty() {
+            return Vec::new();
+        }
+
+        let block_lines = start..end;
+        let execution_date = block_lines
+            .clone()
+            .chain((0..doc.line_count()).filter(|line| !block_lines.contains(line)))
+            .filter(|&line| Self::mentions_execution(&texts[line]))
+            .find_map(|line| Self::first_date(&doc.lines()[line]))
+            .or_else(|| {
+                lines
+                    .iter()
+                    .filter(|(_, _, block_line)| matches!(block_line, BlockLine::Date))
+                    .find_map(|(line, _, _)| Self::first_date(&doc.lines()[*line]))
+            });
+
+        let last_line = end - 1;
+        let last_token = doc.lines()[last_line].ll_tokens().len().saturating_sub(1);
+        vec![Scored::rule_based(
+            SignatureBlock {
+                span: DocSpan::new(
+                    DocPosition::new(start, 0),
+                    DocPosition::new(last_line, last_token),
+                ),
+                signatories,
+                execution_date,
+            },
+            confidence,
+            "signature_block",
+        )]
+    }
+}
+
+/// A label's value without signature underscores or a "/s/" marker.
+fn clean_value(value: &str) -> Option<String> {
+    let value = value.trim().trim_matches('_').trim();
+    let value = value.strip_prefix("/s/").unwrap_or(value).trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContractDocument, SectionHeaderResolver, TemporalExpressionResolver};
+
+    fn blocks(text: &str) -> Vec<Scored<SignatureBlock>> {
+        let doc = ContractDocument::from_text(text)
+            .run_resolver(&SectionHeaderResolver::new())
+            .run_resolver(&TemporalExpressionResolver::new());
+        SignatureBlockResolver::new().resolve(&doc)
+    }
+
+    #[test]
+    fn test_labelled_signatories_and_execution_date() {
+        let found = blocks(
+            "Section 9. Miscellaneous\n\
+             This Agreement is governed by the laws of Delaware.\n\
+             IN WITNESS WHEREOF, the parties have executed this Agreement as of March 1, 2024.\n\
+             ACME CORPORATION\n\
+             By: ______________________\n\
+             Name: Jane Smith\n\
+             Title: Chief Executive Officer\n\
+             WIDGET LLC\n\
+             By: /s/ John Doe\n\
+             Its: Managing Member",
+        );
+
+        assert_eq!(found.len(), 1);
+        let block = &found[0].value;
+        assert_eq!(found[0].confidence, 0.9);
+        assert_eq!(block.span.start.line, 2);
+        assert_eq!(block.span.end.line, 9);
+        assert_eq!(
+            block.execution_date_value(),
+            NaiveDate::from_ymd_opt(2024, 3, 1)
+        );
+
+        assert_eq!(block.signatories.len(), 2);
+        let acme = &block.signatories[0];
+        assert_eq!(acme.party.as_deref(), Some("ACME CORPORATION"));
+        assert_eq!(acme.name.as_deref(), Some("Jane Smith"));
+        assert_eq!(acme.title.as_deref(), Some("Chief Executive Officer"));
+        assert_eq!((acme.span.start.line, acme.span.end.line), (4, 6));
+        let widget = &block.signatories[1];
+        assert_eq!(widget.party.as_deref(), Some("WIDGET LLC"));
+        assert_eq!(widget.name.as_deref(), Some("John Doe"));
+        assert_eq!(widget.title.as_deref(), Some("Managing Member"));
+    }
+
+    #[test]
+    fn test_unlabelled_layout_and_dated_line() {
+        let found = blocks(
+            "This Agreement is dated as of January 15, 2023.\n\
+             The Supplier shall deliver the Goods.\n\
+             SUPPLIER:\n\
+             By: ____________\n\
+             Mary Major\n\
+             President\n\
+             Section 10. Exhibits\n\
+             Exhibit A lists the Goods.",
+        );
+
+        assert_eq!(found.len(), 1);
+        let block = &found[0].value;
+        assert_eq!(found[0].confidence, 0.7);
+        assert_eq!((block.span.start.line, block.span.end.line), (2, 5));
+        assert_eq!(block.execution_date.as_ref().unwrap().text, "January 15, 2023");
+        assert_eq!(block.signatories.len(), 1);
+        let supplier = &block.signatories[0];
+        assert_eq!(supplier.party.as_deref(), Some("SUPPLIER"));
+        assert_eq!(supplier.name.as_deref(), Some("Mary Major"));
+        assert_eq!(supplier.title.as_deref(), Some("President"));
+
+        assert!(blocks("The Buyer shall pay the fees.").is_empty());
+    }
+}
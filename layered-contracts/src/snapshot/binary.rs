@@ -0,0 +1,203 @@
+//! Compact binary encoding of snapshots (feature `bincode`).
+//!
+//! A [`Snapshot`] is the serializable form of a document's analysis; the
+//! document itself holds type-erased attributes and cannot be encoded
+//! directly. The binary form is meant for caches: it is smaller and much
+//! faster to load than RON or JSON, but is not human-readable and is only
+//! readable by a build with the same [`BINARY_FORMAT_VERSION`].
+//!
+//! Layout: the magic bytes `LNSB`, the format version as a little-endian
+//! `u32`, then the bincode-encoded snapshot. Span values are stored as RON
+//! text, since bincode cannot decode self-describing values.
+
+use serde::{Deserialize, Serialize};
+
+use super::types::{
+    AssociationData, InputSource, Snapshot, SnapshotDocSpan, SnapshotError, SnapshotSpanId,
+    SpanData,
+};
+
+/// Version of the binary snapshot layout.
+///
+/// Bump this whenever [`Snapshot`] or any annotation type stored in it
+/// changes shape, so stale cache entries are rejected by
+/// [`Snapshot::from_bytes`] instead of decoding into wrong values.
+pub const BINARY_FORMAT_VERSION: u32 = 1;
+
+const MAGIC: &[u8; 4] = b"LNSB";
+
+/// [`AssociationData`] without skipped fields, which bincode cannot represent.
+#[derive(Serialize, Deserialize)]
+struct BinaryAssociation {
+    label: String,
+    target: SnapshotSpanId,
+    glyph: Option<String>,
+}
+
+/// [`SpanData`] with its value as RON text.
+#[derive(Serialize, Deserialize)]
+struct BinarySpan {
+    id: SnapshotSpanId,
+    position: SnapshotDocSpan,
+    type_name: String,
+    value: String,
+    confidence: Option<f64>,
+    source: Option<String>,
+    associations: Vec<BinaryAssociation>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinarySnapshot {
+    version: u32,
+    input: InputSource,
+    spans: Vec<(String, Vec<BinarySpan>)>,
+    auxiliary: Vec<(String, String)>,
+}
+
+impl Snapshot {
+    /// Encode the snapshot as versioned bincode.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SnapshotError> {
+        let to_ron = |value: &ron::Value| ron::to_string(value).map_err(SnapshotError::Encode);
+        let spans = self
+            .spans
+            .iter()
+            .map(|(type_name, spans)| {
+                let spans = spans
+                    .iter()
+                    .map(|span| {
+                        Ok(BinarySpan {
+                            id: span.id.clone(),
+                            position: span.position,
+                            type_name: span.type_name.clone(),
+                            value: to_ron(&span.value)?,
+                            confidence: span.confidence,
+                            source: span.source.clone(),
+                            associations: span
+                                .associations
+                                .iter()
+                                .map(|assoc| BinaryAssociation {
+                                    label: assoc.label.clone(),
+                                    target: assoc.target.clone(),
+                                    glyph: assoc.glyph.clone(),
+                                })
+                                .collect(),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, SnapshotError>>()?;
+                Ok((type_name.clone(), spans))
+            })
+            .collect::<Result<Vec<_>, SnapshotError>>()?;
+        let auxiliary = self
+            .auxiliary
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), to_ron(value)?)))
+            .collect::<Result<Vec<_>, SnapshotError>>()?;
+
+        let binary = BinarySnapshot {
+            version: self.version,
+            input: self.input.clone(),
+            spans,
+            auxiliary,
+        };
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&BINARY_FORMAT_VERSION.to_le_bytes());
+        bincode::serialize_into(&mut bytes, &binary).map_err(SnapshotError::Binary)?;
+        Ok(bytes)
+    }
+
+    /// Decode a snapshot written by [`to_bytes`](Self::to_bytes).
+    ///
+    /// Fails with [`SnapshotError::StaleBinary`] when the bytes were written
+    /// with a different [`BINARY_FORMAT_VERSION`] or are not a binary snapshot.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let found = match bytes.get(..8) {
+            Some(header) if &header[..4] == MAGIC => {
+                Some(u32::from_le_bytes([header[4], header[5], header[6], header[7]]))
+            }
+            _ => None,
+        };
+        if found != Some(BINARY_FORMAT_VERSION) {
+            return Err(SnapshotError::StaleBinary {
+                found,
+                expected: BINARY_FORMAT_VERSION,
+            });
+        }
+
+        let binary: BinarySnapshot =
+            bincode::deserialize(&bytes[8..]).map_err(SnapshotError::Binary)?;
+        let from_ron = |text: &str| ron::from_str(text).map_err(SnapshotError::Parse);
+        let mut snapshot = Snapshot {
+            version: binary.version,
+            input: binary.input,
+            ..Snapshot::default()
+        };
+        for (type_name, spans) in binary.spans {
+            let spans = spans
+                .into_iter()
+                .map(|span| {
+                    Ok(SpanData {
+                        id: span.id,
+                        position: span.position,
+                        type_name: span.type_name,
+                        value: from_ron(&span.value)?,
+                        confidence: span.confidence,
+                        source: span.source,
+                        associations: span
+                            .associations
+                            .into_iter()
+                            .map(|assoc| AssociationData {
+                                label: assoc.label,
+                                target: assoc.target,
+                                glyph: assoc.glyph,
+                            })
+                            .collect(),
+                    })
+                })
+                .collect::<Result<Vec<_>, SnapshotError>>()?;
+            snapshot.spans.insert(type_name, spans);
+        }
+        for (key, value) in binary.auxiliary {
+            snapshot.auxiliary.insert(key, from_ron(&value)?);
+        }
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContractDocument, ContractKeywordResolver, DefinedTermResolver};
+
+    #[test]
+    fn test_binary_round_trip() {
+        let doc = ContractDocument::from_text(
+            "ABC Corp (the \"Company\") shall deliver goods.\n\"Goods\" means the products.",
+        )
+        .run_resolver(&ContractKeywordResolver::default())
+        .run_resolver(&DefinedTermResolver::default());
+        let snapshot = Snapshot::from_document(&doc);
+        assert!(snapshot.span_count() > 0);
+
+        let bytes = snapshot.to_bytes().unwrap();
+        assert_eq!(&bytes[..4], b"LNSB");
+        let loaded = Snapshot::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded, snapshot);
+        assert_eq!(loaded.to_ron_string().unwrap(), snapshot.to_ron_string().unwrap());
+    }
+
+    #[test]
+    fn test_stale_and_foreign_bytes_are_rejected() {
+        let mut bytes = Snapshot::new().to_bytes().unwrap();
+        bytes[4..8].copy_from_slice(&(BINARY_FORMAT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            Snapshot::from_bytes(&bytes),
+            Err(SnapshotError::StaleBinary { found: Some(v), .. }) if v == BINARY_FORMAT_VERSION + 1
+        ));
+
+        let json = br#"{"version": 1}"#;
+        assert!(matches!(
+            Snapshot::from_bytes(json),
+            Err(SnapshotError::StaleBinary { found: None, .. })
+        ));
+    }
+}
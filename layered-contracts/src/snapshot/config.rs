@@ -19,6 +19,8 @@ pub enum RenderMode {
     Annotated,
     /// Association graph view
     Graph,
+    /// Text with one underline track per semantic category
+    Layered,
 }
 
 /// Configuration for snapshot rendering.
@@ -202,6 +204,24 @@ impl Snapshot {
                         output.push_str(&graph);
                     }
                 }
+
+                RenderMode::Layered => {
+                    output.push_str("═══ LAYERED TEXT ═══\n\n");
+                    let renderer = SnapshotRenderer::new()
+                        .with_confidence_threshold(if config.verbose { 1.0 } else { 0.8 });
+
+                    let layered = if config.included_types.is_empty() {
+                        renderer.render_layered(self)
+                    } else {
+                        renderer.render_layered(&self.filter_types(&config.included_types))
+                    };
+
+                    if layered.is_empty() {
+                        output.push_str("(no text)");
+                    } else {
+                        output.push_str(&layered);
+                    }
+                }
             }
         }
 
@@ -306,6 +326,17 @@ mod tests {
         assert!(!output.contains("═══ ASSOCIATION GRAPH ═══"));
     }
 
+    #[test]
+    fn test_render_all_layered() {
+        let snapshot = make_snapshot_with_spans();
+        let config = SnapshotConfig::minimal().with_modes(vec![RenderMode::Layered]);
+        let output = snapshot.render_all(&config);
+
+        assert!(output.starts_with("═══ LAYERED TEXT ═══"));
+        assert!(!output.contains("═══ SEMANTIC SUMMARY ═══"));
+        assert!(output.contains("sh-0"), "Should list the section header's track");
+    }
+
     #[test]
     fn test_render_all_type_filtering() {
         let snapshot = make_snapshot_with_spans();
@@ -9,7 +9,7 @@ use crate::{ContractDocument, Scored, ScoreSource};
 
 use super::types::{
     InputSource, Snapshot, SnapshotDocPos, SnapshotDocSpan, SnapshotKind,
-    SnapshotSpanId, SpanData,
+    SnapshotSpanId, SpanData, SNAPSHOT_FORMAT_VERSION,
 };
 
 /// Builder for constructing snapshots from documents.
@@ -139,7 +139,7 @@ impl<'a> SnapshotBuilder<'a> {
         );
 
         Snapshot {
-            version: 1,
+            version: SNAPSHOT_FORMAT_VERSION,
             input,
             spans,
             auxiliary: BTreeMap::new(),
@@ -22,8 +22,13 @@
 //! let snapshot = SnapshotBuilder::from_document(&doc).build();
 //! let ron_string = snapshot.to_ron_string().unwrap();
 //! ```
+//!
+//! With the `bincode` feature, `Snapshot::to_bytes`/`from_bytes` provide a
+//! compact, versioned binary form for caching analyzed documents.
 
 mod types;
+#[cfg(feature = "bincode")]
+mod binary;
 mod construction;
 mod semantic;
 pub mod display;
@@ -34,8 +39,11 @@ mod macros;
 
 pub use types::{
     AssociationData, InputSource, SnapshotSpanId, SnapshotDocPos, SnapshotDocSpan, SnapshotKind,
-    Snapshot, SpanData,
+    Snapshot, SnapshotError, SnapshotMigration, SnapshotMigrations, SpanData,
+    SNAPSHOT_FORMAT_VERSION,
 };
+#[cfg(feature = "bincode")]
+pub use binary::BINARY_FORMAT_VERSION;
 pub use construction::SnapshotBuilder;
 pub use semantic::{classify_type_name, SemanticCategory, SnapshotRenderer};
 pub use display::{DocDisplay, index_to_label};
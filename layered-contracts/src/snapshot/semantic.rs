@@ -1,11 +1,12 @@
 //! Semantic summary rendering for snapshots.
 //!
 //! This module provides `SnapshotRenderer` which produces human-readable
-//! semantic summaries from `Snapshot` data, grouped by category.
+//! semantic summaries from `Snapshot` data, grouped by category, and a
+//! layered text view with one underline track per category.
 
 use std::fmt::Write;
 
-use super::types::{Snapshot, SpanData};
+use super::types::{InputSource, Snapshot, SpanData};
 
 /// Semantic categories for grouping spans in rendered output.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -215,6 +216,96 @@ impl SnapshotRenderer {
         output.trim_end().to_string()
     }
 
+    /// Render the text with one underline track per semantic category.
+    ///
+    /// Each line is followed by a row for every category with spans on it,
+    /// in [`SemanticCategory::all_in_order`] order, so a keyword inside an
+    /// obligation inside a reference stays readable. Overlapping spans of one
+    /// category are stacked onto extra rows of that category. Underlines are
+    /// placed at the tokens' actual columns; a span continuing from the
+    /// previous line or onto the next has an open end (`─`).
+    ///
+    /// ```text
+    /// 1│ The Company shall deliver.
+    ///  │     ╰─────╯                REFERENCES tr-0
+    ///  │ ╰────────────────────────╯ OBLIGATIONS ob-0
+    ///  │             ╰───╯          OBLIGATIONS kw-0
+    /// ```
+    pub fn render_layered(&self, snapshot: &Snapshot) -> String {
+        let lines = match &snapshot.input {
+            InputSource::Inline(lines) => lines,
+            InputSource::FileRef(_) => {
+                return "[file reference - layered view unavailable; see .ron snapshot for data]"
+                    .to_string()
+            }
+        };
+        let number_width = lines.len().max(1).to_string().len();
+
+        let mut output = String::new();
+        for (line_idx, text) in lines.iter().enumerate() {
+            writeln!(output, "{:>width$}│ {}", line_idx + 1, text, width = number_width).unwrap();
+            let columns = token_columns(text);
+            let line_width = text.chars().count();
+
+            let mut tracks: std::collections::BTreeMap<SemanticCategory, Vec<TrackSpan>> =
+                std::collections::BTreeMap::new();
+            for (type_name, spans) in &snapshot.spans {
+                let category = classify_type_name(type_name);
+                for span in spans {
+                    if let Some(track_span) = TrackSpan::on_line(span, line_idx as u32, &columns, line_width) {
+                        tracks.entry(category).or_default().push(track_span);
+                    }
+                }
+            }
+
+            for (category, mut spans) in tracks {
+                spans.sort_by(|a, b| {
+                    (a.start, std::cmp::Reverse(a.end), &a.span.id)
+                        .cmp(&(b.start, std::cmp::Reverse(b.end), &b.span.id))
+                });
+                // Greedy packing: each span goes on the first row it doesn't overlap
+                let mut rows: Vec<Vec<TrackSpan>> = Vec::new();
+                for span in spans {
+                    match rows.iter_mut().find(|row| row.last().is_some_and(|last| last.end <= span.start)) {
+                        Some(row) => row.push(span),
+                        None => rows.push(vec![span]),
+                    }
+                }
+
+                for row in rows {
+                    let width = row.iter().map(|s| s.end).max().unwrap_or(0).max(line_width);
+                    let mut underline = vec![' '; width];
+                    for span in &row {
+                        span.draw(&mut underline);
+                    }
+                    let labels: Vec<String> = row.iter().map(|s| self.track_label(s.span)).collect();
+                    writeln!(
+                        output,
+                        "{:width$}│ {} {} {}",
+                        "",
+                        underline.into_iter().collect::<String>(),
+                        category.display_name(),
+                        labels.join(", "),
+                        width = number_width
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        output.trim_end().to_string()
+    }
+
+    /// A span's ID, with its confidence when below the threshold.
+    fn track_label(&self, span: &SpanData) -> String {
+        match span.confidence {
+            Some(conf) if self.show_confidence && conf < self.confidence_threshold => {
+                format!("{} ({:.2})", span.id, conf)
+            }
+            _ => span.id.to_string(),
+        }
+    }
+
     /// Render a single span.
     fn render_span(&self, output: &mut String, type_name: &str, span: &SpanData) {
         // Extract a brief value summary from the Debug string
@@ -278,6 +369,74 @@ impl SnapshotRenderer {
     }
 }
 
+/// The character columns `[start, end)` of each token of a line, tokenized
+/// as the document's lines are.
+fn token_columns(text: &str) -> Vec<(usize, usize)> {
+    let column = |byte: usize| text.get(..byte).map_or(0, |prefix| prefix.chars().count());
+    layered_nlp::create_line_from_string(text)
+        .ll_tokens()
+        .iter()
+        .map(|token| (column(token.pos_starts_at()), column(token.pos_ends_at())))
+        .collect()
+}
+
+/// The part of a span on one line of the layered view, in character columns.
+struct TrackSpan<'a> {
+    span: &'a SpanData,
+    start: usize,
+    /// Exclusive
+    end: usize,
+    /// Continues from the previous line
+    open_start: bool,
+    /// Continues onto the next line
+    open_end: bool,
+}
+
+impl<'a> TrackSpan<'a> {
+    fn on_line(
+        span: &'a SpanData,
+        line: u32,
+        columns: &[(usize, usize)],
+        line_width: usize,
+    ) -> Option<Self> {
+        let (start, end) = (&span.position.start, &span.position.end);
+        if line < start.line || line > end.line {
+            return None;
+        }
+        let open_start = start.line < line;
+        let open_end = end.line > line;
+        let start_col = if open_start {
+            0
+        } else {
+            columns.get(start.token as usize).map_or(line_width, |c| c.0)
+        };
+        let end_col = if open_end {
+            line_width
+        } else {
+            columns.get(end.token as usize).map_or(line_width, |c| c.1)
+        };
+        Some(TrackSpan {
+            span,
+            start: start_col,
+            end: end_col.max(start_col + 1),
+            open_start,
+            open_end,
+        })
+    }
+
+    fn draw(&self, row: &mut [char]) {
+        for cell in &mut row[self.start..self.end] {
+            *cell = '─';
+        }
+        if !self.open_start {
+            row[self.start] = '╰';
+        }
+        if !self.open_end && self.end - self.start > 1 {
+            row[self.end - 1] = '╯';
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -307,6 +466,51 @@ mod tests {
         assert!((renderer.confidence_threshold - 0.8).abs() < 0.01);
     }
 
+    fn span(id: &str, idx: usize, type_name: &str, (start, end): ((u32, u32), (u32, u32))) -> SpanData {
+        use crate::snapshot::{SnapshotDocPos, SnapshotDocSpan, SnapshotSpanId};
+        SpanData {
+            id: SnapshotSpanId::new(id, idx),
+            position: SnapshotDocSpan::new(
+                SnapshotDocPos::new(start.0, start.1),
+                SnapshotDocPos::new(end.0, end.1),
+            ),
+            type_name: type_name.to_string(),
+            value: ron::Value::String(String::new()),
+            confidence: None,
+            source: None,
+            associations: vec![],
+        }
+    }
+
+    #[test]
+    fn test_render_layered_stacks_overlapping_tracks() {
+        let mut snapshot = Snapshot::with_inline_input(vec![
+            "The Company shall deliver.".to_string(),
+            "It shall pay within 30 days.".to_string(),
+        ]);
+        // Tokens: The _ Company _ shall _ deliver .
+        snapshot.spans.insert("TermReference".to_string(), vec![span("tr", 0, "TermReference", ((0, 2), (0, 2)))]);
+        snapshot.spans.insert("ContractKeyword".to_string(), vec![span("kw", 0, "ContractKeyword", ((0, 4), (0, 4)))]);
+        let mut obligation = span("ob", 0, "ObligationPhrase", ((0, 0), (1, 4)));
+        obligation.confidence = Some(0.6);
+        snapshot.spans.insert("ObligationPhrase".to_string(), vec![obligation]);
+        snapshot.spans.insert(
+            "TemporalExpression".to_string(),
+            vec![span("te", 0, "TemporalExpression", ((1, 6), (1, 10)))],
+        );
+
+        let output = SnapshotRenderer::new().render_layered(&snapshot);
+        let expected = "\
+1│ The Company shall deliver.
+ │     ╰─────╯                REFERENCES tr-0
+ │ ╰───────────────────────── OBLIGATIONS ob-0 (0.60)
+ │             ╰───╯          OBLIGATIONS kw-0
+2│ It shall pay within 30 days.
+ │ ───────────╯                 OBLIGATIONS ob-0 (0.60)
+ │              ╰────────────╯  TEMPORAL te-0";
+        assert_eq!(output, expected, "\n{}", output);
+    }
+
     #[test]
     fn test_render_empty_snapshot() {
         let snapshot = Snapshot::new();
@@ -9,9 +9,14 @@
 //!    Note: associations field exists but is not populated yet
 //! 6. RON round-trip — serialize -> deserialize -> serialize produces identical output
 //! 7. Empty document — Empty input produces valid empty snapshot
+//! 8. Loading — `from_ron_str` restores every field and rejects misfiled spans
+//! 9. Versioning — other format versions are migrated or rejected
 
 use crate::ContractDocument;
-use crate::snapshot::{Snapshot, SnapshotBuilder, SnapshotSpanId};
+use crate::snapshot::{
+    AssociationData, Snapshot, SnapshotBuilder, SnapshotDocPos, SnapshotDocSpan, SnapshotError,
+    SnapshotMigrations, SnapshotSpanId, SpanData, SNAPSHOT_FORMAT_VERSION,
+};
 use crate::section_header::SectionHeaderResolver;
 use crate::defined_term::DefinedTermResolver;
 use crate::contract_keyword::ContractKeywordResolver;
@@ -351,3 +356,99 @@ fn test_snapshot_from_document_convenience() {
     // Should have extracted some spans with standard types
     assert!(snapshot.span_count() > 0, "Should extract some spans with standard types");
 }
+
+#[test]
+fn test_snapshot_from_ron_str_roundtrip() {
+    let text = "Section 1.1 Definitions\n\"Company\" means ABC Corp.";
+    let doc = ContractDocument::from_text(text)
+        .run_resolver(&SectionHeaderResolver::new())
+        .run_resolver(&ContractKeywordResolver::default())
+        .run_resolver(&DefinedTermResolver::new());
+    let mut snapshot = SnapshotBuilder::new(&doc).with_standard_types().build();
+
+    // Exercise the optional fields the builder leaves empty
+    snapshot.spans.entry("DefinedTerm".to_string()).or_default().push(SpanData {
+        id: SnapshotSpanId::new("dt", 9),
+        position: SnapshotDocSpan::new(SnapshotDocPos::new(1, 0), SnapshotDocPos::new(1, 2)),
+        type_name: "DefinedTerm".to_string(),
+        value: ron::Value::Seq(vec![ron::Value::Number(ron::Number::from(0.1 + 0.2))]),
+        confidence: Some(1.0 / 3.0),
+        source: None,
+        associations: vec![AssociationData {
+            label: "term_source".to_string(),
+            target: SnapshotSpanId::new("dt", 0),
+            glyph: Some("@".to_string()),
+        }],
+    });
+    snapshot
+        .auxiliary
+        .insert("reviewer".to_string(), ron::Value::String("legal".to_string()));
+
+    let ron_str = snapshot.to_ron_string().expect("serialization failed");
+    let loaded = Snapshot::from_ron_str(&ron_str).expect("loading failed");
+
+    assert_eq!(loaded, snapshot);
+    assert_eq!(loaded.to_ron_string().expect("re-serialization failed"), ron_str);
+}
+
+#[test]
+fn test_snapshot_from_ron_str_errors() {
+    let err = Snapshot::from_ron_str("(version: 1, input: Inline([])").unwrap_err();
+    assert!(matches!(err, SnapshotError::Parse(_)));
+
+    let mut snapshot = Snapshot::new();
+    snapshot.spans.insert(
+        "DefinedTerm".to_string(),
+        vec![SpanData {
+            id: SnapshotSpanId::new("sh", 0),
+            position: SnapshotDocSpan::new(SnapshotDocPos::new(0, 0), SnapshotDocPos::new(0, 1)),
+            type_name: "SectionHeader".to_string(),
+            value: ron::Value::Unit,
+            confidence: None,
+            source: None,
+            associations: vec![],
+        }],
+    );
+    let ron_str = snapshot.to_ron_string().expect("serialization failed");
+    let err = Snapshot::from_ron_str(&ron_str).unwrap_err();
+    assert!(matches!(err, SnapshotError::MismatchedTypeName { .. }));
+    assert_eq!(
+        err.to_string(),
+        "Span sh-0 has type 'SectionHeader' but is stored under 'DefinedTerm'"
+    );
+}
+
+#[test]
+fn test_snapshot_format_version() {
+    let doc = ContractDocument::from_text("\"Company\" means ABC Corp.")
+        .run_resolver(&ContractKeywordResolver::default())
+        .run_resolver(&DefinedTermResolver::new());
+    let snapshot = Snapshot::from_document(&doc);
+    assert_eq!(snapshot.version, SNAPSHOT_FORMAT_VERSION);
+    let current = snapshot.to_ron_string().expect("serialization failed");
+
+    let newer = current.replacen("version: 1,", "version: 7,", 1);
+    let err = Snapshot::from_ron_str(&newer).unwrap_err();
+    assert!(matches!(err, SnapshotError::UnsupportedVersion { found: Some(7), expected: 1 }));
+    assert_eq!(
+        err.to_string(),
+        "Snapshot has format version 7 but this build reads version 1 and has no migration for it"
+    );
+
+    let unversioned = current.replacen("version: 1,", "", 1);
+    let err = Snapshot::from_ron_str(&unversioned).unwrap_err();
+    assert!(matches!(err, SnapshotError::UnsupportedVersion { found: None, .. }));
+
+    // A migration loads the old version and the result is stamped as current
+    let old = current.replacen("version: 1,", "version: 0,", 1);
+    let migrations = SnapshotMigrations::new().with_migration(0, |s| {
+        let mut snapshot: Snapshot = ron::from_str(s)?;
+        snapshot.auxiliary.insert("migrated".to_string(), ron::Value::Bool(true));
+        Ok(snapshot)
+    });
+    assert_eq!(migrations.supported_versions(), vec![0, 1]);
+    let migrated = Snapshot::from_ron_str_with(&old, &migrations).expect("migration failed");
+    assert_eq!(migrated.version, SNAPSHOT_FORMAT_VERSION);
+    assert_eq!(migrated.spans, snapshot.spans);
+    assert!(Snapshot::from_ron_str(&old).is_err(), "no built-in migration for version 0");
+}
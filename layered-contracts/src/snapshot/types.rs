@@ -6,6 +6,13 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
+/// Version of the RON snapshot format, stored as [`Snapshot::version`].
+///
+/// Bump this whenever the stored shape of [`Snapshot`] changes, and register
+/// a migration for the previous version in [`SnapshotMigrations::new`] so
+/// stored snapshots keep loading.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
 /// Stable identifier for a span within a snapshot.
 ///
 /// IDs follow the pattern `{prefix}-{index}` where:
@@ -107,7 +114,7 @@ pub struct AssociationData {
     /// The target span ID this association points to
     pub target: SnapshotSpanId,
     /// Optional display glyph (e.g., "@", "#")
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub glyph: Option<String>,
 }
 
@@ -123,10 +130,10 @@ pub struct SpanData {
     /// The serialized value (stored as RON Value for flexibility)
     pub value: ron::Value,
     /// Confidence score (0.0-1.0), if applicable
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub confidence: Option<f64>,
     /// Score source description, if applicable
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
     /// Associations linking this span to other spans
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -169,7 +176,7 @@ pub struct Snapshot {
 impl Default for Snapshot {
     fn default() -> Self {
         Self {
-            version: 1,
+            version: SNAPSHOT_FORMAT_VERSION,
             input: InputSource::Inline(Vec::new()),
             spans: BTreeMap::new(),
             auxiliary: BTreeMap::new(),
@@ -211,10 +218,65 @@ impl Snapshot {
     }
 
     /// Deserialize from a RON string.
+    ///
+    /// Does not check the format version; prefer
+    /// [`from_ron_str`](Self::from_ron_str) for stored snapshots.
     pub fn from_ron_string(s: &str) -> Result<Self, ron::error::SpannedError> {
         ron::from_str(s)
     }
 
+    /// Load a snapshot written by [`to_ron_string`](Self::to_ron_string).
+    ///
+    /// Unlike [`from_ron_string`](Self::from_ron_string), this also checks
+    /// that every span is filed under its own `type_name`, so that
+    /// re-serializing the loaded snapshot reproduces the input exactly.
+    ///
+    /// Snapshots of an older format version are upgraded with the built-in
+    /// [`SnapshotMigrations`]; other versions fail with
+    /// [`SnapshotError::UnsupportedVersion`].
+    pub fn from_ron_str(s: &str) -> Result<Self, SnapshotError> {
+        Self::from_ron_str_with(s, &SnapshotMigrations::new())
+    }
+
+    /// [`from_ron_str`](Self::from_ron_str) with additional migrations.
+    pub fn from_ron_str_with(
+        s: &str,
+        migrations: &SnapshotMigrations,
+    ) -> Result<Self, SnapshotError> {
+        let version = ron::from_str::<VersionProbe>(s)?.version;
+        let snapshot = match version {
+            Some(SNAPSHOT_FORMAT_VERSION) => ron::from_str(s)?,
+            Some(found) => match migrations.migrations.get(&found) {
+                Some(migrate) => Self {
+                    version: SNAPSHOT_FORMAT_VERSION,
+                    ..migrate(s)?
+                },
+                None => {
+                    return Err(SnapshotError::UnsupportedVersion {
+                        found: Some(found),
+                        expected: SNAPSHOT_FORMAT_VERSION,
+                    })
+                }
+            },
+            None => {
+                return Err(SnapshotError::UnsupportedVersion {
+                    found: None,
+                    expected: SNAPSHOT_FORMAT_VERSION,
+                })
+            }
+        };
+        for (type_name, spans) in &snapshot.spans {
+            if let Some(span) = spans.iter().find(|span| &span.type_name != type_name) {
+                return Err(SnapshotError::MismatchedTypeName {
+                    id: span.id.clone(),
+                    group: type_name.clone(),
+                    type_name: span.type_name.clone(),
+                });
+            }
+        }
+        Ok(snapshot)
+    }
+
     /// Get all spans of a given type.
     pub fn spans_of_type(&self, type_name: &str) -> &[SpanData] {
         self.spans.get(type_name).map(|v| v.as_slice()).unwrap_or(&[])
@@ -256,6 +318,159 @@ impl Snapshot {
     }
 }
 
+/// Just the format version of a stored snapshot, read before the rest.
+#[derive(Deserialize)]
+#[serde(rename = "Snapshot")]
+struct VersionProbe {
+    #[serde(default, deserialize_with = "present_version")]
+    version: Option<u32>,
+}
+
+/// The stored version is a bare number; only its absence maps to `None`.
+fn present_version<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u32>, D::Error> {
+    u32::deserialize(deserializer).map(Some)
+}
+
+/// Loads a snapshot stored in an older format version as a current [`Snapshot`].
+pub type SnapshotMigration = fn(&str) -> Result<Snapshot, SnapshotError>;
+
+/// Migrations from older snapshot format versions, keyed by the version
+/// they read.
+///
+/// A migration parses the old RON itself (typically into a copy of the old
+/// types) and converts it; the loaded snapshot is then stamped with
+/// [`SNAPSHOT_FORMAT_VERSION`].
+///
+/// ```ignore
+/// let migrations = SnapshotMigrations::new().with_migration(0, load_v0);
+/// let snapshot = Snapshot::from_ron_str_with(&stored, &migrations)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SnapshotMigrations {
+    migrations: BTreeMap<u32, SnapshotMigration>,
+}
+
+impl Default for SnapshotMigrations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SnapshotMigrations {
+    /// The built-in migrations of every older format version this build reads.
+    pub fn new() -> Self {
+        // Version 1 is the first stored format; nothing to migrate yet
+        Self {
+            migrations: BTreeMap::new(),
+        }
+    }
+
+    /// Load snapshots of `from_version` with `migration`, replacing any
+    /// built-in migration for that version.
+    pub fn with_migration(mut self, from_version: u32, migration: SnapshotMigration) -> Self {
+        self.migrations.insert(from_version, migration);
+        self
+    }
+
+    /// Format versions that can be loaded: the current one and every
+    /// version with a migration, in ascending order.
+    pub fn supported_versions(&self) -> Vec<u32> {
+        let mut versions: Vec<u32> = self.migrations.keys().copied().collect();
+        if !self.migrations.contains_key(&SNAPSHOT_FORMAT_VERSION) {
+            versions.push(SNAPSHOT_FORMAT_VERSION);
+            versions.sort_unstable();
+        }
+        versions
+    }
+}
+
+/// Error loading a snapshot.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The input is not valid snapshot RON
+    Parse(ron::error::SpannedError),
+    /// The snapshot was written in a format version with no migration
+    /// (`found` is `None` when it has no version at all)
+    UnsupportedVersion { found: Option<u32>, expected: u32 },
+    /// A span is stored under a different type group than its `type_name`
+    MismatchedTypeName {
+        id: SnapshotSpanId,
+        group: String,
+        type_name: String,
+    },
+    /// A span value could not be encoded as RON
+    #[cfg(feature = "bincode")]
+    Encode(ron::Error),
+    /// The binary snapshot is malformed
+    #[cfg(feature = "bincode")]
+    Binary(bincode::Error),
+    /// The bytes are not a binary snapshot of the current format version
+    /// (`found` is `None` when there is no binary snapshot header at all)
+    #[cfg(feature = "bincode")]
+    StaleBinary { found: Option<u32>, expected: u32 },
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Parse(err) => write!(f, "Invalid snapshot RON: {}", err),
+            SnapshotError::MismatchedTypeName { id, group, type_name } => write!(
+                f,
+                "Span {} has type '{}' but is stored under '{}'",
+                id, type_name, group
+            ),
+            SnapshotError::UnsupportedVersion { found: Some(found), expected } => write!(
+                f,
+                "Snapshot has format version {} but this build reads version {} and has no migration for it",
+                found, expected
+            ),
+            SnapshotError::UnsupportedVersion { found: None, expected } => write!(
+                f,
+                "Snapshot has no format version (expected version {})",
+                expected
+            ),
+            #[cfg(feature = "bincode")]
+            SnapshotError::Encode(err) => write!(f, "Cannot encode span value: {}", err),
+            #[cfg(feature = "bincode")]
+            SnapshotError::Binary(err) => write!(f, "Invalid binary snapshot: {}", err),
+            #[cfg(feature = "bincode")]
+            SnapshotError::StaleBinary { found: Some(found), expected } => write!(
+                f,
+                "Binary snapshot has format version {} but {} is required",
+                found, expected
+            ),
+            #[cfg(feature = "bincode")]
+            SnapshotError::StaleBinary { found: None, .. } => {
+                write!(f, "Not a binary snapshot")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SnapshotError::Parse(err) => Some(err),
+            SnapshotError::MismatchedTypeName { .. } => None,
+            SnapshotError::UnsupportedVersion { .. } => None,
+            #[cfg(feature = "bincode")]
+            SnapshotError::Encode(err) => Some(err),
+            #[cfg(feature = "bincode")]
+            SnapshotError::Binary(err) => Some(err),
+            #[cfg(feature = "bincode")]
+            SnapshotError::StaleBinary { .. } => None,
+        }
+    }
+}
+
+impl From<ron::error::SpannedError> for SnapshotError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        SnapshotError::Parse(err)
+    }
+}
+
 /// Trait for types that can be stored in snapshots.
 ///
 /// Implement this trait to provide type-specific prefixes for stable ID generation.
@@ -0,0 +1,255 @@
+//! One-call contract analysis.
+//!
+//! ```ignore
+//! let summary = ContractSummary::analyze(contract_text);
+//! for party in &summary.obligations {
+//!     println!("{}: {} obligations", party.party, party.obligations.len());
+//! }
+//! let json = serde_json::to_string(&summary)?;
+//! ```
+//!
+//! [`ContractSummary`] runs the resolvers in dependency order and collects the
+//! key facts of a contract. It composes lower-level APIs that can be used
+//! directly for more control:
+//!
+//! - the line resolvers, as in [`Pipeline::standard`](crate::pipeline::Pipeline::standard),
+//!   plus `POSTagResolver`, `ProhibitionResolver` and [`GoverningLawResolver`]
+//! - [`DeadlineTracker`] for deadlines
+//! - [`ConflictDetector`] for conflicts
+
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+use layered_nlp::x;
+use layered_part_of_speech::POSTagResolver;
+use serde::Serialize;
+
+use crate::conflict_detector::{ConflictDetector, ConflictType};
+use crate::deadline::{DeadlineTracker, UndeterminedReason};
+use crate::defined_term::DefinedTerm;
+use crate::governing_law::{GoverningLaw, GoverningLawResolver};
+use crate::obligation::{ObligationPhrase, ObligationType, ObligorReference};
+use crate::utils::normalize_party_name;
+use crate::{
+    ContractDocument, ContractKeywordResolver, DefinedTermResolver, DocSpan,
+    ObligationPhraseResolver, ProhibitionResolver, PronounChainResolver, PronounResolver, Scored,
+    SectionHeaderResolver, SectionReferenceResolver, TemporalExpressionResolver,
+    TermReferenceResolver, TermsOfArtResolver,
+};
+
+/// The key facts of a contract.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ContractSummary {
+    /// Defined-term parties with obligations, in order of appearance
+    pub parties: Vec<String>,
+    /// Obligations grouped by party, in the order of `parties`
+    pub obligations: Vec<PartyObligations>,
+    /// Obligations with timing, resolved to a date where possible
+    pub deadlines: Vec<DeadlineSummary>,
+    /// The jurisdiction of the first governing-law clause
+    pub governing_law: Option<String>,
+    /// Conflicts between obligations or governing-law clauses
+    pub conflicts: Vec<ConflictSummary>,
+}
+
+/// The obligations of one party.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PartyObligations {
+    pub party: String,
+    pub obligations: Vec<ObligationSummary>,
+}
+
+/// An obligation of a party.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ObligationSummary {
+    pub obligation_type: ObligationType,
+    pub action: String,
+    pub span: DocSpan,
+    pub confidence: f64,
+}
+
+/// An obligation's deadline.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeadlineSummary {
+    /// Span of the obligation phrase
+    pub span: DocSpan,
+    /// Text of the timing expression
+    pub text: String,
+    /// Due date as `YYYY-MM-DD`, when it could be resolved
+    pub due: Option<String>,
+    /// Why the due date could not be resolved
+    pub undetermined_reason: Option<String>,
+}
+
+/// A detected conflict.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConflictSummary {
+    pub conflict_type: ConflictType,
+    pub span_a: DocSpan,
+    pub span_b: DocSpan,
+    pub explanation: String,
+    pub confidence: f64,
+}
+
+impl ContractSummary {
+    /// Analyze contract text and summarize it.
+    pub fn analyze(text: &str) -> Self {
+        Self::from_document(&Self::run_resolvers(ContractDocument::from_text(text)))
+    }
+
+    /// Run the line resolvers `analyze` depends on, in dependency order.
+    pub fn run_resolvers(doc: ContractDocument) -> ContractDocument {
+        doc.run_resolver(&POSTagResolver::default())
+            .run_resolver(&SectionHeaderResolver::new())
+            .run_resolver(&SectionReferenceResolver::new())
+            .run_resolver(&ContractKeywordResolver::new())
+            .run_resolver(&ProhibitionResolver::new())
+            .run_resolver(&TermsOfArtResolver::new())
+            .run_resolver(&DefinedTermResolver::new())
+            .run_resolver(&TermReferenceResolver::new())
+            .run_resolver(&TemporalExpressionResolver::new())
+            .run_resolver(&PronounResolver::new())
+            .run_resolver(&PronounChainResolver::new())
+            .run_resolver(&ObligationPhraseResolver::new())
+            .run_resolver(&GoverningLawResolver::new())
+    }
+
+    /// Summarize a document the resolvers of
+    /// [`run_resolvers`](Self::run_resolvers) have run on.
+    pub fn from_document(doc: &ContractDocument) -> Self {
+        let mut summary = Self::default();
+        let mut party_keys: Vec<String> = Vec::new();
+        let defined: HashSet<String> = doc
+            .lines()
+            .iter()
+            .flat_map(|line| line.find(&x::attr::<Scored<DefinedTerm>>()))
+            .map(|found| normalize_party_name(&found.attr().value.term_name))
+            .collect();
+
+        for (line_idx, line) in doc.lines_enumerated() {
+            for found in line.find(&x::attr::<Scored<ObligationPhrase>>()) {
+                let scored = found.attr();
+                let party = match party_name(&scored.value.obligor, &defined) {
+                    Some(party) => party,
+                    None => continue,
+                };
+                let key = normalize_party_name(party);
+                let idx = match party_keys.iter().position(|k| *k == key) {
+                    Some(idx) => idx,
+                    None => {
+                        party_keys.push(key);
+                        summary.parties.push(party.to_string());
+                        summary.obligations.push(PartyObligations {
+                            party: party.to_string(),
+                            obligations: Vec::new(),
+                        });
+                        party_keys.len() - 1
+                    }
+                };
+                summary.obligations[idx].obligations.push(ObligationSummary {
+                    obligation_type: scored.value.obligation_type,
+                    action: scored.value.action.clone(),
+                    span: DocSpan::from_find(line_idx, &found),
+                    confidence: scored.confidence,
+                });
+            }
+
+            if summary.governing_law.is_none() {
+                summary.governing_law = line
+                    .find(&x::attr::<Scored<GoverningLaw>>())
+                    .first()
+                    .map(|found| found.attr().value.jurisdiction.clone());
+            }
+        }
+
+        let tracker = DeadlineTracker::from_document(doc);
+        summary.deadlines = tracker
+            .deadlines()
+            .iter()
+            .map(|deadline| DeadlineSummary {
+                span: deadline.span,
+                text: deadline.text.clone(),
+                due: Some(format_date(deadline.due)),
+                undetermined_reason: None,
+            })
+            .chain(tracker.undetermined_deadlines().iter().map(|deadline| DeadlineSummary {
+                span: deadline.span,
+                text: deadline.text.clone(),
+                due: None,
+                undetermined_reason: Some(reason_text(deadline.reason).to_string()),
+            }))
+            .collect();
+        summary
+            .deadlines
+            .sort_by_key(|deadline| (deadline.span.start.line, deadline.span.start.token));
+
+        summary.conflicts = ConflictDetector::new()
+            .detect_in_document(doc)
+            .into_iter()
+            .map(|scored| ConflictSummary {
+                conflict_type: scored.value.conflict_type,
+                span_a: scored.value.span_a,
+                span_b: scored.value.span_b,
+                explanation: scored.value.explanation,
+                confidence: scored.confidence,
+            })
+            .collect();
+
+        summary
+    }
+}
+
+/// The party named by an obligor.
+///
+/// Only defined terms count as parties: a plain noun phrase is as often the
+/// subject of a boilerplate clause ("This Agreement shall be governed by").
+fn party_name<'a>(obligor: &'a ObligorReference, defined: &HashSet<String>) -> Option<&'a str> {
+    let name = match obligor {
+        ObligorReference::TermRef { term_name, .. } => return Some(term_name),
+        ObligorReference::PronounRef { resolved_to, .. } => resolved_to,
+        ObligorReference::NounPhrase { text } => text,
+        ObligorReference::Unknown => return None,
+    };
+    Some(name.as_str()).filter(|name| defined.contains(&normalize_party_name(name)))
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn reason_text(reason: UndeterminedReason) -> &'static str {
+    match reason {
+        UndeterminedReason::Vague => "vague",
+        UndeterminedReason::Unanchored => "unanchored",
+        UndeterminedReason::IncompleteDate => "incomplete date",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_collects_key_facts() {
+        let summary = ContractSummary::analyze(
+            "ABC Corp (the \"Company\") shall deliver the goods by March 31, 2025.\n\
+             XYZ Inc (the \"Customer\") shall pay the invoice.\n\
+             The Company may deliver the goods by March 31, 2025.\n\
+             This Agreement shall be governed by the laws of the State of New York.",
+        );
+
+        assert_eq!(summary.parties, vec!["Company", "Customer"]);
+        assert_eq!(summary.obligations[0].obligations.len(), 2);
+        assert_eq!(summary.obligations[1].obligations[0].action, "pay the invoice");
+        assert_eq!(summary.governing_law.as_deref(), Some("the State of New York"));
+        assert!(summary.deadlines.iter().any(|d| d.due.as_deref() == Some("2025-03-31")));
+        assert!(summary
+            .conflicts
+            .iter()
+            .any(|c| c.conflict_type == ConflictType::ModalConflict));
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["parties"][1], "Customer");
+        assert_eq!(json["conflicts"][0]["conflict_type"], "ModalConflict");
+    }
+}
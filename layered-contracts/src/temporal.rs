@@ -8,8 +8,27 @@
 //! - **Deadlines**: "within 30 days", "no later than December 31"
 //! - **Relative times**: "upon termination", "following receipt"
 
+use std::convert::TryFrom;
+
 use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
 
+use crate::number::{NumberParser, MAX_NUMBER_TOKENS};
+
+/// Confidence factor for a date without a year ("January 15"): the year has
+/// to be inferred, and a bare month and day is more often a partial reference.
+const MISSING_YEAR_FACTOR: f64 = 0.85;
+
+/// Confidence factor for a date without a day ("December 2024").
+const MISSING_DAY_FACTOR: f64 = 0.9;
+
+/// Confidence factor for a date without a month.
+const MISSING_MONTH_FACTOR: f64 = 0.8;
+
+/// Confidence factor for a duration whose number is not confirmed by a
+/// numeral in parentheses: "thirty (30) days" states its value twice, while
+/// "30 days" and "thirty days" state it once.
+const UNCONFIRMED_DURATION_FACTOR: f64 = 0.9;
+
 /// A temporal expression detected in contract text.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TemporalExpression {
@@ -220,7 +239,7 @@ impl TemporalConverter {
     }
 
     /// Check if the expression text contains vague patterns.
-    fn is_vague(&self, text: &str) -> bool {
+    pub(crate) fn is_vague(&self, text: &str) -> bool {
         let lower = text.to_lowercase();
         self.vague_patterns.iter().any(|p| lower.contains(p))
     }
@@ -292,6 +311,8 @@ pub struct TemporalExpressionResolver {
     deadline_confidence: f64,
     /// Confidence for defined date terms
     defined_date_confidence: f64,
+    /// Parser for duration numbers
+    number_parser: NumberParser,
 }
 
 impl Default for TemporalExpressionResolver {
@@ -301,6 +322,7 @@ impl Default for TemporalExpressionResolver {
             duration_confidence: 0.90,
             deadline_confidence: 0.85,
             defined_date_confidence: 0.80,
+            number_parser: NumberParser::default(),
         }
     }
 }
@@ -310,6 +332,44 @@ impl TemporalExpressionResolver {
         Self::default()
     }
 
+    /// Use `parser` for the numbers of durations ("thirty (30) days").
+    pub fn with_number_parser(mut self, parser: NumberParser) -> Self {
+        self.number_parser = parser;
+        self
+    }
+
+    /// How completely `temporal_type` was matched, as a factor on its base
+    /// confidence: 1.0 for a full date or a confirmed duration, lower for each
+    /// missing date part or an unconfirmed number. A deadline takes the factor
+    /// of the duration or date it references. `text` is the matched text.
+    fn completeness(temporal_type: &TemporalType, text: &str) -> f64 {
+        match temporal_type {
+            TemporalType::Date { year, month, day } => {
+                let mut factor = 1.0;
+                if year.is_none() {
+                    factor *= MISSING_YEAR_FACTOR;
+                }
+                if month.is_none() {
+                    factor *= MISSING_MONTH_FACTOR;
+                }
+                if day.is_none() {
+                    factor *= MISSING_DAY_FACTOR;
+                }
+                factor
+            }
+            // The confirming numeral is the only parenthesis a duration's text can hold
+            TemporalType::Duration { written_form, .. } => {
+                if written_form.is_some() && text.contains('(') {
+                    1.0
+                } else {
+                    UNCONFIRMED_DURATION_FACTOR
+                }
+            }
+            TemporalType::Deadline { reference, .. } => Self::completeness(reference, text),
+            TemporalType::DefinedDate { .. } | TemporalType::RelativeTime { .. } => 1.0,
+        }
+    }
+
     /// Parse a month name to its number (1-12).
     fn parse_month(text: &str) -> Option<u8> {
         match text.to_lowercase().as_str() {
@@ -329,39 +389,65 @@ impl TemporalExpressionResolver {
         }
     }
 
-    /// Parse a written number to its numeric value.
-    fn parse_written_number(text: &str) -> Option<u32> {
-        match text.to_lowercase().as_str() {
-            "one" | "a" | "an" => Some(1),
-            "two" => Some(2),
-            "three" => Some(3),
-            "four" => Some(4),
-            "five" => Some(5),
-            "six" => Some(6),
-            "seven" => Some(7),
-            "eight" => Some(8),
-            "nine" => Some(9),
-            "ten" => Some(10),
-            "eleven" => Some(11),
-            "twelve" => Some(12),
-            "thirteen" => Some(13),
-            "fourteen" => Some(14),
-            "fifteen" => Some(15),
-            "sixteen" => Some(16),
-            "seventeen" => Some(17),
-            "eighteen" => Some(18),
-            "nineteen" => Some(19),
-            "twenty" => Some(20),
-            "thirty" => Some(30),
-            "forty" | "fourty" => Some(40),
-            "fifty" => Some(50),
-            "sixty" => Some(60),
-            "seventy" => Some(70),
-            "eighty" => Some(80),
-            "ninety" => Some(90),
-            "hundred" => Some(100),
-            _ => None,
+    /// Match a number whose first token is the end of `first_sel` ("thirty (30)",
+    /// "twenty-five", "30"), returning the selection through its last token, its
+    /// value, its written form and its text. "a" and "an" count as one ("a year").
+    fn match_number(
+        &self,
+        first_sel: &LLSelection,
+        first: &str,
+    ) -> Option<(LLSelection, u32, Option<String>, String)> {
+        if first.eq_ignore_ascii_case("a") || first.eq_ignore_ascii_case("an") {
+            return Some((first_sel.clone(), 1, Some(first.to_string()), first.to_string()));
+        }
+
+        let mut tokens: Vec<(LLSelection, String)> = vec![(first_sel.clone(), first.to_string())];
+        while tokens.len() < MAX_NUMBER_TOKENS {
+            let last = &tokens[tokens.len() - 1].0;
+            let (next, text) = match last.match_first_forwards(&x::token_text()) {
+                Some((next, text)) => (next, text.to_string()),
+                None => break,
+            };
+            tokens.push((next, text));
         }
+
+        let texts: Vec<&str> = tokens.iter().map(|(_, text)| text.as_str()).collect();
+        let parsed = self.number_parser.parse_tokens(&texts)?;
+        if parsed.ordinal {
+            return None;
+        }
+        let value = u32::try_from(parsed.value).ok()?;
+        let end = parsed.span.end;
+        Some((tokens[end - 1].0.clone(), value, parsed.written_form, texts[..end].concat()))
+    }
+
+    /// Match a duration unit right after `selection`, including the two-token
+    /// "business days" / "working days".
+    fn match_unit(selection: &LLSelection) -> Option<(LLSelection, DurationUnit, String)> {
+        let (unit_sel, (_, unit_text)) =
+            selection.match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))?;
+
+        if DurationUnit::is_business_prefix(unit_text) {
+            let mut check_sel = unit_sel.clone();
+            let mut text = unit_text.to_string();
+            if let Some((ws_sel, _)) = check_sel.match_first_forwards(&x::whitespace()) {
+                text.push(' ');
+                check_sel = ws_sel;
+            }
+            if let Some((day_sel, (_, day_text))) = check_sel
+                .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            {
+                let lower = day_text.to_lowercase();
+                if lower == "day" || lower == "days" {
+                    text.push_str(day_text);
+                    return Some((day_sel, DurationUnit::BusinessDays, text));
+                }
+            }
+            // If not followed by day/days, fall through (might be "business" used differently)
+        }
+
+        let unit = DurationUnit::from_text(unit_text)?;
+        Some((unit_sel, unit, unit_text.to_string()))
     }
 
     /// Check if text is a deadline keyword and return its type.
@@ -391,116 +477,42 @@ impl TemporalExpressionResolver {
         &self,
         selection: &LLSelection,
     ) -> Option<(TemporalType, LLSelection, String)> {
-        let mut current = selection.clone();
-        let mut raw_text = String::new();
-        let mut value: Option<u32> = None;
-        let mut written_form: Option<String> = None;
-
-        // Try to match a number (written or numeric)
-        if let Some((num_sel, (_, num_text))) =
-            current.match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
-        {
-            if let Some(num) = Self::parse_written_number(num_text) {
-                value = Some(num);
-                written_form = Some(num_text.to_string());
-                raw_text.push_str(num_text);
-                current = num_sel;
-            }
-        }
-
-        // If no written number, try numeric
-        if value.is_none() {
-            if let Some((num_sel, (_, num_text))) = current
-                .match_first_forwards(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))
-            {
-                if let Ok(num) = num_text.parse::<u32>() {
-                    value = Some(num);
-                    raw_text.push_str(num_text);
-                    current = num_sel;
-                }
-            }
-        }
+        let (first_sel, first) = selection.match_first_forwards(&x::token_text())?;
+        self.parse_duration_at(&first_sel, first, false)
+    }
 
-        let value = value?;
+    /// Parse a duration whose number starts with the last token of `first_sel`.
+    ///
+    /// With `space_required`, the number and unit must be separated by
+    /// whitespace ("30 days" but not "30-day").
+    fn parse_duration_at(
+        &self,
+        first_sel: &LLSelection,
+        first: &str,
+        space_required: bool,
+    ) -> Option<(TemporalType, LLSelection, String)> {
+        let (mut current, value, written_form, mut raw_text) =
+            self.match_number(first_sel, first)?;
 
         // Skip whitespace
         if let Some((ws_sel, _)) = current.match_first_forwards(&x::whitespace()) {
             raw_text.push(' ');
             current = ws_sel;
+        } else if space_required {
+            return None;
         }
 
-        // Check for parenthetical confirmation: "(30)"
-        if let Some((paren_sel, _)) = current.match_first_forwards(&x::attr_eq(&'(')) {
-            if let Some((num_sel, (_, num_text))) = paren_sel
-                .match_first_forwards(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))
-            {
-                if let Some((close_sel, _)) = num_sel.match_first_forwards(&x::attr_eq(&')')) {
-                    // Verify the parenthetical number matches
-                    if let Ok(paren_num) = num_text.parse::<u32>() {
-                        if paren_num == value {
-                            raw_text.push_str(&format!("({}) ", num_text));
-                            current = close_sel;
-                            // Skip whitespace after close paren
-                            if let Some((ws_sel, _)) = current.match_first_forwards(&x::whitespace())
-                            {
-                                current = ws_sel;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        // Now look for the unit (days, months, years, etc.)
-        if let Some((unit_sel, (_, unit_text))) =
-            current.match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
-        {
-            // Check for "business days" / "working days" (two-token pattern)
-            if DurationUnit::is_business_prefix(unit_text) {
-                let mut check_sel = unit_sel.clone();
-                raw_text.push_str(unit_text);
-
-                // Skip whitespace
-                if let Some((ws_sel, _)) = check_sel.match_first_forwards(&x::whitespace()) {
-                    raw_text.push(' ');
-                    check_sel = ws_sel;
-                }
-
-                // Look for "day" or "days"
-                if let Some((day_sel, (_, day_text))) = check_sel
-                    .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
-                {
-                    if day_text.to_lowercase() == "day" || day_text.to_lowercase() == "days" {
-                        raw_text.push_str(day_text);
-                        return Some((
-                            TemporalType::Duration {
-                                value,
-                                unit: DurationUnit::BusinessDays,
-                                written_form,
-                            },
-                            day_sel,
-                            raw_text,
-                        ));
-                    }
-                }
-                // If not followed by day/days, fall through (might be "business" used differently)
-            }
-
-            if let Some(unit) = DurationUnit::from_text(unit_text) {
-                raw_text.push_str(unit_text);
-                return Some((
-                    TemporalType::Duration {
-                        value,
-                        unit,
-                        written_form,
-                    },
-                    unit_sel,
-                    raw_text,
-                ));
-            }
-        }
-
-        None
+        let (unit_sel, unit, unit_text) = Self::match_unit(&current)?;
+        raw_text.push_str(&unit_text);
+        Some((
+            TemporalType::Duration {
+                value,
+                unit,
+                written_form,
+            },
+            unit_sel,
+            raw_text,
+        ))
     }
 }
 
@@ -564,14 +576,17 @@ impl Resolver for TemporalExpressionResolver {
                         }
                     }
 
+                    let temporal_type = TemporalType::Date {
+                        year,
+                        month: Some(month),
+                        day: Some(day),
+                    };
+                    let confidence =
+                        self.date_confidence * Self::completeness(&temporal_type, &raw_text);
                     assignments.push(final_sel.finish_with_attr(TemporalExpression {
-                        temporal_type: TemporalType::Date {
-                            year,
-                            month: Some(month),
-                            day: Some(day),
-                        },
+                        temporal_type,
                         text: raw_text,
-                        confidence: self.date_confidence,
+                        confidence,
                     }));
                 }
             }
@@ -599,13 +614,16 @@ impl Resolver for TemporalExpressionResolver {
                 {
                     raw_text.push_str(&duration_text);
 
+                    let temporal_type = TemporalType::Deadline {
+                        deadline_type,
+                        reference: Box::new(duration_type),
+                    };
+                    let confidence =
+                        self.deadline_confidence * Self::completeness(&temporal_type, &raw_text);
                     assignments.push(final_sel.finish_with_attr(TemporalExpression {
-                        temporal_type: TemporalType::Deadline {
-                            deadline_type,
-                            reference: Box::new(duration_type),
-                        },
+                        temporal_type,
                         text: raw_text,
-                        confidence: self.deadline_confidence,
+                        confidence,
                     }));
                 }
             }
@@ -719,176 +737,43 @@ impl Resolver for TemporalExpressionResolver {
                     self.try_parse_duration(&current)
                 {
                     raw_text.push_str(&duration_text);
+                    let temporal_type = TemporalType::Deadline {
+                        deadline_type: dtype,
+                        reference: Box::new(duration_type),
+                    };
+                    let confidence =
+                        self.deadline_confidence * Self::completeness(&temporal_type, &raw_text);
                     assignments.push(final_sel.finish_with_attr(TemporalExpression {
-                        temporal_type: TemporalType::Deadline {
-                            deadline_type: dtype,
-                            reference: Box::new(duration_type),
-                        },
+                        temporal_type,
                         text: raw_text,
-                        confidence: self.deadline_confidence,
+                        confidence,
                     }));
                 }
             }
         }
 
-        // Pattern 3: Standalone durations with written numbers (e.g., "thirty (30) days", "five years")
-        for (sel, (_, first_text)) in
-            selection.find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
-        {
-            // Only match if it's a written number
-            if let Some(value) = Self::parse_written_number(first_text) {
-                let mut current = sel.clone();
-                let mut raw_text = first_text.to_string();
-                let written_form = Some(first_text.to_string());
-
-                // Skip whitespace
-                if let Some((ws_sel, _)) = current.match_first_forwards(&x::whitespace()) {
-                    raw_text.push(' ');
-                    current = ws_sel;
-                } else {
-                    continue;
-                }
-
-                // Check for parenthetical confirmation: "(30)"
-                if let Some((paren_sel, _)) = current.match_first_forwards(&x::attr_eq(&'(')) {
-                    if let Some((num_sel, (_, num_text))) = paren_sel
-                        .match_first_forwards(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))
-                    {
-                        if let Some((close_sel, _)) = num_sel.match_first_forwards(&x::attr_eq(&')'))
-                        {
-                            if let Ok(paren_num) = num_text.parse::<u32>() {
-                                if paren_num == value {
-                                    raw_text.push_str(&format!("({}) ", num_text));
-                                    current = close_sel;
-                                    if let Some((ws_sel, _)) =
-                                        current.match_first_forwards(&x::whitespace())
-                                    {
-                                        current = ws_sel;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-
-                // Match the unit (including "business days" / "working days")
-                if let Some((unit_sel, (_, unit_text))) = current
-                    .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
-                {
-                    // Check for "business days" / "working days"
-                    if DurationUnit::is_business_prefix(unit_text) {
-                        let mut check_sel = unit_sel.clone();
-                        let mut check_text = raw_text.clone();
-                        check_text.push_str(unit_text);
-
-                        if let Some((ws_sel, _)) = check_sel.match_first_forwards(&x::whitespace())
-                        {
-                            check_text.push(' ');
-                            check_sel = ws_sel;
-                        }
-
-                        if let Some((day_sel, (_, day_text))) = check_sel.match_first_forwards(
-                            &x::all((x::attr_eq(&TextTag::WORD), x::token_text())),
-                        ) {
-                            let lower = day_text.to_lowercase();
-                            if lower == "day" || lower == "days" {
-                                check_text.push_str(day_text);
-                                assignments.push(day_sel.finish_with_attr(TemporalExpression {
-                                    temporal_type: TemporalType::Duration {
-                                        value,
-                                        unit: DurationUnit::BusinessDays,
-                                        written_form,
-                                    },
-                                    text: check_text,
-                                    confidence: self.duration_confidence,
-                                }));
-                                continue;
-                            }
-                        }
-                    }
-
-                    if let Some(unit) = DurationUnit::from_text(unit_text) {
-                        raw_text.push_str(unit_text);
-                        assignments.push(unit_sel.finish_with_attr(TemporalExpression {
-                            temporal_type: TemporalType::Duration {
-                                value,
-                                unit,
-                                written_form,
-                            },
-                            text: raw_text,
-                            confidence: self.duration_confidence,
-                        }));
-                    }
-                }
-            }
-        }
-
-        // Pattern 4: Standalone numeric durations (e.g., "30 days", "5 business days")
-        for (sel, (_, num_text)) in
-            selection.find_by(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))
-        {
-            if let Ok(value) = num_text.parse::<u32>() {
-                let mut current = sel.clone();
-                let mut raw_text = num_text.to_string();
-
-                // Skip whitespace
-                if let Some((ws_sel, _)) = current.match_first_forwards(&x::whitespace()) {
-                    raw_text.push(' ');
-                    current = ws_sel;
-                } else {
-                    continue;
-                }
-
-                // Match the unit (including "business days" / "working days")
-                if let Some((unit_sel, (_, unit_text))) = current
-                    .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
-                {
-                    // Check for "business days" / "working days"
-                    if DurationUnit::is_business_prefix(unit_text) {
-                        let mut check_sel = unit_sel.clone();
-                        let mut check_text = raw_text.clone();
-                        check_text.push_str(unit_text);
-
-                        if let Some((ws_sel, _)) = check_sel.match_first_forwards(&x::whitespace())
-                        {
-                            check_text.push(' ');
-                            check_sel = ws_sel;
-                        }
-
-                        if let Some((day_sel, (_, day_text))) = check_sel.match_first_forwards(
-                            &x::all((x::attr_eq(&TextTag::WORD), x::token_text())),
-                        ) {
-                            let lower = day_text.to_lowercase();
-                            if lower == "day" || lower == "days" {
-                                check_text.push_str(day_text);
-                                assignments.push(day_sel.finish_with_attr(TemporalExpression {
-                                    temporal_type: TemporalType::Duration {
-                                        value,
-                                        unit: DurationUnit::BusinessDays,
-                                        written_form: None,
-                                    },
-                                    text: check_text,
-                                    confidence: self.duration_confidence,
-                                }));
-                                continue;
-                            }
-                        }
-                    }
-
-                    if let Some(unit) = DurationUnit::from_text(unit_text) {
-                        raw_text.push_str(unit_text);
-                        assignments.push(unit_sel.finish_with_attr(TemporalExpression {
-                            temporal_type: TemporalType::Duration {
-                                value,
-                                unit,
-                                written_form: None,
-                            },
-                            text: raw_text,
-                            confidence: self.duration_confidence,
-                        }));
-                    }
-                }
+        // Pattern 3: Standalone durations (e.g., "thirty (30) days", "five years",
+        // "5 business days"). A number inside an earlier match ("five" of
+        // "twenty-five days") does not start another.
+        let mut covered_until: Option<usize> = None;
+        for (sel, first) in selection.find_by(&x::token_text()) {
+            let start = sel.span_ref().start_idx;
+            if covered_until.is_some_and(|end| start <= end) {
+                continue;
             }
+            let (duration_type, final_sel, raw_text) =
+                match self.parse_duration_at(&sel, first, true) {
+                    Some(found) => found,
+                    None => continue,
+                };
+            covered_until = Some(final_sel.span_ref().end_idx);
+            let confidence =
+                self.duration_confidence * Self::completeness(&duration_type, &raw_text);
+            assignments.push(final_sel.finish_with_attr(TemporalExpression {
+                temporal_type: duration_type,
+                text: raw_text,
+                confidence,
+            }));
         }
 
         // Pattern 5: "the [Something] Date" (defined date terms)
@@ -1233,6 +1118,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_duration_compound_number() {
+        let exprs = detect_temporal("a term of one hundred twenty-five (125) days");
+        let durations: Vec<_> = exprs
+            .iter()
+            .filter_map(|e| match &e.temporal_type {
+                TemporalType::Duration { value, .. } => Some((*value, e.text.as_str())),
+                _ => None,
+            })
+            .collect();
+        // "five days" inside the compound is not a second duration
+        assert_eq!(durations, vec![(125, "one hundred twenty-five (125) days")]);
+
+        let dozen = TemporalExpressionResolver::new()
+            .with_number_parser(NumberParser::new().with_word("dozen", 12));
+        let line = create_line_from_string("for a dozen months").run(&dozen);
+        let found = line.find(&x::attr::<TemporalExpression>());
+        assert!(matches!(
+            found[0].attr().temporal_type,
+            TemporalType::Duration { value: 12, unit: DurationUnit::Months, .. }
+        ));
+    }
+
+    #[test]
+    fn test_confidence_reflects_completeness() {
+        let confidence = |text: &str| {
+            let exprs = detect_temporal(text);
+            assert_eq!(exprs.len(), 1, "{:?}", exprs);
+            exprs[0].confidence
+        };
+
+        let full_date = confidence("due December 31, 2024");
+        let bare_date = confidence("due December 31");
+        assert_eq!(full_date, 0.95);
+        assert!(bare_date < full_date);
+
+        let confirmed = confidence("a term of thirty (30) days");
+        assert_eq!(confirmed, 0.90);
+        assert!(confidence("a term of 30 days") < confirmed);
+        assert!(confidence("a term of thirty days") < confirmed);
+
+        // Deadlines take the completeness of the duration they reference
+        let within = |text: &str| {
+            detect_temporal(text)
+                .into_iter()
+                .find(|e| matches!(e.temporal_type, TemporalType::Deadline { .. }))
+                .unwrap()
+                .confidence
+        };
+        assert!(within("within 30 days") < within("within thirty (30) days"));
+    }
+
     #[test]
     fn test_deadline_within() {
         let exprs = detect_temporal("The Company shall respond within 10 days.");
@@ -3,13 +3,24 @@
 //! This resolver identifies subsequent uses of defined terms and links them back
 //! to their definitions. For example, if "Contractor" is defined via
 //! `(the "Contractor")`, later bare mentions of `Contractor` get tagged as references.
+//!
+//! [`TermReferenceResolver`] links mentions on the same line as the definition.
+//! [`DocumentTermReferenceResolver`] links across lines and honours
+//! [`DefinitionScope`]: a term defined inside an exhibit only applies there.
+//! [`find_capitalization_inconsistencies`] proofreads uses of defined terms
+//! written in a different case than their definition, and
+//! [`find_article_inconsistencies`] uses of a specific entity with "a" or "an".
 
 use std::collections::HashMap;
 
 use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+use layered_nlp_document::{DocumentResolver, LayeredDocument};
 
-use crate::defined_term::{DefinedTerm, DefinitionType};
-use crate::Scored;
+use crate::defined_term::{DefinedTerm, DefinitionScope, DefinitionType};
+use crate::document_structure::DocumentStructureExt;
+use crate::precedence::SectionClassifier;
+use crate::utils::content_tokens;
+use crate::{DocPosition, DocSpan, Scored};
 
 /// A reference to a previously defined term.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -18,19 +29,115 @@ pub struct TermReference {
     pub term_name: String,
     /// How the original term was defined
     pub definition_type: DefinitionType,
+    /// The article or determiner directly before the reference, if any
+    pub article: Option<Article>,
+    /// The defined term qualifying this one through "of": "Disclosing Party"
+    /// in "Confidential Information of the Disclosing Party". The reference
+    /// then spans the whole phrase; the qualifier keeps its own reference.
+    pub qualifier: Option<String>,
+}
+
+/// The article or determiner before a term reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Article {
+    /// "the Company": the specific defined entity
+    Definite,
+    /// "a Company", "an Affiliate": some instance, usually not the defined one
+    Indefinite,
+    /// "this Agreement", "such Party", "these Services"
+    Demonstrative,
+}
+
+impl Article {
+    /// The article `word` is, ignoring case.
+    pub fn from_word(word: &str) -> Option<Self> {
+        match word.to_lowercase().as_str() {
+            "the" => Some(Article::Definite),
+            "a" | "an" => Some(Article::Indefinite),
+            "this" | "that" | "these" | "those" | "such" => Some(Article::Demonstrative),
+            _ => None,
+        }
+    }
 }
 
+/// Ordinary English words that contracts commonly define as terms, sorted.
+///
+/// A term made only of these words ("Business Day", "Control") shadows the
+/// everyday word, so most of its occurrences are not references.
+const COMMON_WORDS: &[&str] = &[
+    "access", "affiliate", "affiliates", "amount", "approval", "assets", "business", "change",
+    "charges", "claim", "claims", "control", "cost", "costs", "data", "day", "days", "default",
+    "event", "fee", "fees", "goods", "including", "information", "interest", "law", "laws",
+    "loss", "losses", "material", "materials", "month", "notice", "order", "party", "payment",
+    "period", "person", "price", "product", "products", "property", "purpose", "records",
+    "rights", "services", "software", "system", "term", "territory", "time", "use", "work",
+    "year",
+];
+
 /// Resolver for detecting references to defined terms.
 ///
 /// Links subsequent mentions of defined terms back to their definitions.
 /// Requires that `DefinedTermResolver` has already been run on the line.
-#[derive(Default)]
-pub struct TermReferenceResolver;
+///
+/// Terms that are also common words (see
+/// [`is_common_word_term`](Self::is_common_word_term)) are linked only where
+/// written exactly as defined and not at the start of a sentence, so that
+/// defining "Control" doesn't link every ordinary "control".
+#[derive(Debug, Clone, Default)]
+pub struct TermReferenceResolver {
+    /// Words treated as common in addition to the built-in lexicon
+    extra_common_words: Vec<String>,
+}
 
 impl TermReferenceResolver {
     /// Create a new resolver.
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Treat `word` as a common word.
+    pub fn with_common_word(mut self, word: impl Into<String>) -> Self {
+        self.extra_common_words.push(word.into().to_lowercase());
+        self
+    }
+
+    /// True if every word of `term` is a common English word.
+    pub fn is_common_word_term(&self, term: &str) -> bool {
+        let mut words = term.split_whitespace().map(str::to_lowercase).peekable();
+        words.peek().is_some()
+            && words.all(|word| {
+                COMMON_WORDS.binary_search(&word.as_str()).is_ok()
+                    || self.extra_common_words.contains(&word)
+            })
+    }
+
+    /// Definitions of common-word terms, which shadow the ordinary word
+    /// throughout the document: the span of each definition with its term.
+    ///
+    /// Requires `DefinedTermResolver` to have run on the document's lines.
+    pub fn risky_definitions(&self, doc: &LayeredDocument) -> Vec<(DocSpan, String)> {
+        let mut risky = Vec::new();
+        for (line_index, line) in doc.lines_enumerated() {
+            for found in line.find(&x::attr::<Scored<DefinedTerm>>()) {
+                let term_name = &found.attr().value.term_name;
+                if self.is_common_word_term(term_name) {
+                    risky.push((DocSpan::from_find(line_index, &found), term_name.clone()));
+                }
+            }
+        }
+        risky
+    }
+
+    /// Check if `selection` starts its line or follows sentence-ending punctuation.
+    fn is_sentence_initial(&self, selection: &LLSelection) -> bool {
+        let selection = match selection.match_first_backwards(&x::whitespace()) {
+            Some((ws_sel, _)) => ws_sel,
+            None => selection.clone(),
+        };
+        match selection.match_first_backwards(&x::token_text()) {
+            Some((_, text)) => matches!(text, "." | "!" | "?" | ";" | ":"),
+            None => true,
+        }
     }
 
     /// Calculate confidence score based on case matching and article presence.
@@ -41,7 +148,7 @@ impl TermReferenceResolver {
     /// | Case-insensitive match + capitalized | 0.85 | Likely intentional reference |
     /// | Exact match + lowercase | 0.70 | Could be generic word usage |
     /// | Case-insensitive match + lowercase | 0.65 | Weakest signal |
-    /// | With article "the" preceding | +0.05 | "the Contractor" is more specific |
+    /// | With a definite or demonstrative article | +0.05 | "the Contractor" is more specific |
     fn calculate_confidence(&self, surface: &str, canonical: &str, has_article: bool) -> f64 {
         let surface_capitalized = surface
             .chars()
@@ -64,21 +171,47 @@ impl TermReferenceResolver {
         score.clamp(0.0, 1.0)
     }
 
-    /// Check if the previous non-whitespace token is a determiner article.
-    /// Currently recognizes: the, The, THE, this, such
-    fn has_preceding_article(&self, selection: &LLSelection) -> bool {
-        // Try to match backwards: skip whitespace, then check for article
-        if let Some((ws_sel, _)) = selection.match_first_backwards(&x::whitespace()) {
-            if let Some((_, text)) = ws_sel.match_first_backwards(&x::token_text()) {
-                return matches!(text.to_lowercase().as_str(), "the" | "this" | "such");
-            }
-        } else {
-            // No whitespace, check directly
-            if let Some((_, text)) = selection.match_first_backwards(&x::token_text()) {
-                return matches!(text.to_lowercase().as_str(), "the" | "this" | "such");
-            }
+    /// The article before `selection`, skipping whitespace.
+    fn preceding_article(&self, selection: &LLSelection) -> Option<Article> {
+        let selection = match selection.match_first_backwards(&x::whitespace()) {
+            Some((ws_sel, _)) => ws_sel,
+            None => selection.clone(),
+        };
+        let (_, text) = selection.match_first_backwards(&x::token_text())?;
+        Article::from_word(text)
+    }
+
+    /// A reference qualifying the one at `head` through "of" ("Confidential
+    /// Information of the Disclosing Party"), with the selection spanning both.
+    fn match_qualifier(
+        &self,
+        head: &LLSelection,
+        references: &[(LLSelection, LLSelection, &str, TermReference, f64)],
+    ) -> Option<(LLSelection, String)> {
+        let word = x::all((x::attr_eq(&TextTag::WORD), x::token_text()));
+        let (current, _) = head.match_first_forwards(&x::whitespace())?;
+        let (current, (_, of)) = current.match_first_forwards(&word)?;
+        if !of.eq_ignore_ascii_case("of") {
+            return None;
         }
-        false
+        let (mut current, _) = current.match_first_forwards(&x::whitespace())?;
+        let mut next = current.match_first_forwards(&word)?;
+        if Article::from_word(next.1 .1).is_some() {
+            let (ws_sel, _) = next.0.match_first_forwards(&x::whitespace())?;
+            current = ws_sel;
+            next = current.match_first_forwards(&word)?;
+        }
+        let (extended, (_, text)) = next;
+
+        // The word after "of" must start another reference
+        let end = extended.span_ref().end_idx;
+        let (_, _, form, reference, _) = references.iter().find(|(first, ..)| {
+            let first = first.span_ref();
+            first.start_idx == end && first.end_idx == end
+        })?;
+        let term_words: Vec<&str> = form.split_whitespace().collect();
+        let (composite, _) = self.match_multiword_term(&extended, text, &term_words)?;
+        Some((composite, reference.term_name.clone()))
     }
 
     /// Match a multi-word term starting from the given selection.
@@ -193,8 +326,7 @@ impl Resolver for TermReferenceResolver {
             .into_iter()
             .map(|(sel, scored)| {
                 (
-                    scored.value.term_name.clone(),
-                    scored.value.definition_type.clone(),
+                    scored.value.clone(),
                     sel, // Keep the selection for overlap checking
                 )
             })
@@ -206,22 +338,23 @@ impl Resolver for TermReferenceResolver {
 
         // Collect just the definition selections for containment checking
         let definition_sels: Vec<LLSelection> =
-            defined_terms.iter().map(|(_, _, sel)| sel.clone()).collect();
-
-        // Step 2: Build lookup map (lowercase first word -> Vec of (full term, original case, type))
-        // This handles multi-word terms and allows multiple definitions with same first word
-        let mut term_lookup: HashMap<String, Vec<(String, DefinitionType)>> = HashMap::new();
-
-        for (term_name, def_type, _) in &defined_terms {
-            let first_word = term_name
-                .split_whitespace()
-                .next()
-                .unwrap_or(term_name)
-                .to_lowercase();
-            term_lookup
-                .entry(first_word)
-                .or_default()
-                .push((term_name.clone(), def_type.clone()));
+            defined_terms.iter().map(|(_, sel)| sel.clone()).collect();
+
+        // Step 2: Build lookup map (lowercase first word -> Vec of (form, term name, type))
+        // This handles multi-word terms and allows multiple definitions with same first word.
+        // Every form is a key, so "Parties" and "Party's" resolve to the term "Party".
+        let mut term_lookup: HashMap<String, Vec<(&str, &str, &DefinitionType)>> =
+            HashMap::new();
+
+        for (term, _) in &defined_terms {
+            for form in &term.forms {
+                let first_word = form.split_whitespace().next().unwrap_or(form).to_lowercase();
+                term_lookup.entry(first_word).or_default().push((
+                    form,
+                    &term.term_name,
+                    &term.definition_type,
+                ));
+            }
         }
 
         // Step 3: Find word tokens that match term names
@@ -233,6 +366,9 @@ impl Resolver for TermReferenceResolver {
 
         // Track selections we've already matched (for multi-word terms)
         let mut matched_selections: Vec<LLSelection> = Vec::new();
+        // (first word, matched selection, matched form, reference, confidence)
+        let mut references: Vec<(LLSelection, LLSelection, &str, TermReference, f64)> =
+            Vec::new();
 
         for (word_sel, (_, text)) in word_matches {
             // Skip if this word selection is already part of a matched multi-word term
@@ -253,11 +389,12 @@ impl Resolver for TermReferenceResolver {
             // Check if this word starts any defined term
             if let Some(candidates) = term_lookup.get(&lowercase) {
                 // Try to match the longest term first (multi-word terms)
-                // Store: (selection, canonical_term_name, definition_type, surface_text)
-                let mut best_match: Option<(LLSelection, &str, &DefinitionType, String)> = None;
+                // Store: (selection, form, canonical_term_name, definition_type, surface_text)
+                let mut best_match: Option<(LLSelection, &str, &str, &DefinitionType, String)> =
+                    None;
 
-                for (term_name, def_type) in candidates {
-                    let term_words: Vec<&str> = term_name.split_whitespace().collect();
+                for &(form, term_name, def_type) in candidates {
+                    let term_words: Vec<&str> = form.split_whitespace().collect();
 
                     if term_words.len() > 1 {
                         // Try multi-word match
@@ -272,32 +409,49 @@ impl Resolver for TermReferenceResolver {
                                         > best_match.as_ref().unwrap().1.split_whitespace().count();
 
                                 if is_better {
-                                    best_match =
-                                        Some((extended_sel, term_name, def_type, surface_text));
+                                    best_match = Some((
+                                        extended_sel,
+                                        form,
+                                        term_name,
+                                        def_type,
+                                        surface_text,
+                                    ));
                                 }
                             }
                         }
                     } else {
-                        // Single word term - already matched
-                        if best_match.is_none() {
+                        // Single word form - already matched. A term defined as
+                        // "Services" wins over the plural of a term "Service".
+                        let replaces = |best: &(LLSelection, &str, &str, _, _)| {
+                            best.1.split_whitespace().count() == 1 && form == term_name
+                        };
+                        if best_match.as_ref().is_none_or(replaces) {
+                            let surface_text = text.to_string();
                             best_match =
-                                Some((word_sel.clone(), term_name, def_type, text.to_string()));
+                                Some((word_sel.clone(), form, term_name, def_type, surface_text));
                         }
                     }
                 }
 
-                if let Some((matched_sel, term_name, def_type, surface_text)) = best_match {
-                    let has_article = self.has_preceding_article(&word_sel);
+                if let Some((matched_sel, form, term_name, def_type, surface_text)) = best_match {
+                    if self.is_common_word_term(term_name)
+                        && (surface_text != form || self.is_sentence_initial(&word_sel))
+                    {
+                        continue;
+                    }
+                    let article = self.preceding_article(&word_sel);
+                    let has_article =
+                        matches!(article, Some(Article::Definite | Article::Demonstrative));
                     let confidence =
-                        self.calculate_confidence(&surface_text, term_name, has_article);
+                        self.calculate_confidence(&surface_text, form, has_article);
 
                     // If multi-word, track all word selections that are part of this match
-                    if term_name.split_whitespace().count() > 1 {
+                    if form.split_whitespace().count() > 1 {
                         // Mark all words in this multi-word term as matched
                         let mut current = word_sel.clone();
                         matched_selections.push(current.clone());
 
-                        let term_words: Vec<&str> = term_name.split_whitespace().collect();
+                        let term_words: Vec<&str> = form.split_whitespace().collect();
                         for _ in term_words.iter().skip(1) {
                             if let Some((ws_sel, _)) =
                                 current.match_first_forwards(&x::whitespace())
@@ -313,18 +467,326 @@ impl Resolver for TermReferenceResolver {
                         }
                     }
 
-                    results.push(matched_sel.finish_with_attr(Scored::rule_based(
+                    references.push((
+                        word_sel.clone(),
+                        matched_sel,
+                        form,
                         TermReference {
                             term_name: term_name.to_string(),
                             definition_type: def_type.clone(),
+                            article,
+                            qualifier: None,
                         },
                         confidence,
-                        "term_reference",
-                    )));
+                    ));
                 }
             }
         }
 
+        for i in 0..references.len() {
+            let qualified = self.match_qualifier(&references[i].1, &references);
+            let (_, matched_sel, _, reference, confidence) = &mut references[i];
+            if let Some((composite_sel, qualifier)) = qualified {
+                *matched_sel = composite_sel;
+                reference.qualifier = Some(qualifier);
+            }
+            results.push(matched_sel.finish_with_attr(Scored::rule_based(
+                reference.clone(),
+                *confidence,
+                "term_reference",
+            )));
+        }
+
+        results
+    }
+}
+
+/// A mention of a defined term linked to its definition anywhere in the document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentTermReference {
+    /// The canonical term name (matches DefinedTerm.term_name)
+    pub term_name: String,
+    /// How the referenced definition was made
+    pub definition_type: DefinitionType,
+    /// Where the mention appears
+    pub span: DocSpan,
+    /// The quoted term of the referenced definition
+    pub definition_span: DocSpan,
+    /// Scope of the referenced definition
+    pub scope: DefinitionScope,
+    /// False if the mention lies outside every definition of the term
+    pub in_scope: bool,
+}
+
+/// Document-level resolver linking term mentions to definitions on any line.
+///
+/// Each `DefinedTerm` is scoped with [`SectionClassifier`]: definitions inside an
+/// exhibit, schedule, annex or appendix apply only within that section, all
+/// others apply globally. A mention links to the innermost definition whose
+/// scope contains it. Mentions outside every scope still link to the first
+/// definition, with `in_scope: false` and reduced confidence.
+///
+/// Requires `DefinedTermResolver` (and `SectionHeaderResolver`, for scoping)
+/// to have run on the document's lines.
+#[derive(Debug, Clone)]
+pub struct DocumentTermReferenceResolver {
+    /// Confidence subtracted from out-of-scope mentions
+    out_of_scope_penalty: f64,
+}
+
+impl Default for DocumentTermReferenceResolver {
+    fn default() -> Self {
+        Self {
+            out_of_scope_penalty: 0.5,
+        }
+    }
+}
+
+/// A definition collected from the document, with its inferred scope.
+struct ScopedDefinition {
+    term: DefinedTerm,
+    span: DocSpan,
+    words: Vec<String>,
+}
+
+impl ScopedDefinition {
+    fn applies_at(&self, position: &DocPosition) -> bool {
+        match &self.term.scope {
+            DefinitionScope::Global => true,
+            DefinitionScope::Section(span) => span.contains_position(position),
+        }
+    }
+}
+
+impl DocumentTermReferenceResolver {
+    /// Create a new resolver with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the confidence penalty for mentions outside the definition's scope.
+    pub fn with_out_of_scope_penalty(mut self, penalty: f64) -> Self {
+        self.out_of_scope_penalty = penalty;
+        self
+    }
+
+    /// Every `DefinedTerm` in the document with its scope inferred from the
+    /// enclosing section, paired with the span of the definition.
+    pub fn scoped_definitions(&self, doc: &LayeredDocument) -> Vec<(DocSpan, Scored<DefinedTerm>)> {
+        let classifier = SectionClassifier::new(doc.structure());
+
+        let mut definitions = Vec::new();
+        for (line_index, line) in doc.lines_enumerated() {
+            for found in line.find(&x::attr::<Scored<DefinedTerm>>()) {
+                let span = DocSpan::from_find(line_index, &found);
+                let mut scored = (*found.attr()).clone();
+                scored.value.scope = match classifier.ancillary_section_span(&span) {
+                    Some(section) => DefinitionScope::Section(section),
+                    None => DefinitionScope::Global,
+                };
+                definitions.push((span, scored));
+            }
+        }
+        definitions
+    }
+}
+
+impl DocumentResolver for DocumentTermReferenceResolver {
+    type Attr = Scored<DocumentTermReference>;
+
+    fn resolve(&self, doc: &LayeredDocument) -> Vec<Self::Attr> {
+        let definitions: Vec<ScopedDefinition> = self
+            .scoped_definitions(doc)
+            .into_iter()
+            .map(|(span, scored)| ScopedDefinition {
+                words: scored
+                    .value
+                    .term_name
+                    .split_whitespace()
+                    .map(str::to_lowercase)
+                    .collect(),
+                term: scored.value,
+                span,
+            })
+            .collect();
+        if definitions.is_empty() {
+            return Vec::new();
+        }
+
+        let mut results = Vec::new();
+        for (line_index, line) in doc.lines_enumerated() {
+            let tokens = content_tokens(line);
+
+            let mut i = 0;
+            while i < tokens.len() {
+                let position = DocPosition::new(line_index, tokens[i].0);
+                let inside_definition =
+                    definitions.iter().any(|def| within_definition(&def.span, &position));
+                let matches_at = |def: &&ScopedDefinition| {
+                    def.words.len() <= tokens.len() - i
+                        && def.words.iter().zip(&tokens[i..]).all(|(word, (_, text, is_word))| {
+                            *is_word && text.to_lowercase() == *word
+                        })
+                };
+                let longest = definitions
+                    .iter()
+                    .filter(matches_at)
+                    .map(|def| def.words.len())
+                    .max();
+                let word_count = match longest {
+                    Some(count) if !inside_definition => count,
+                    _ => {
+                        i += 1;
+                        continue;
+                    }
+                };
+
+                let candidates: Vec<&ScopedDefinition> = definitions
+                    .iter()
+                    .filter(|def| def.words.len() == word_count)
+                    .filter(matches_at)
+                    .collect();
+                // Section-scoped definitions shadow global ones
+                let in_scope = candidates
+                    .iter()
+                    .filter(|def| def.applies_at(&position))
+                    .max_by_key(|def| matches!(def.term.scope, DefinitionScope::Section(_)));
+                let definition = in_scope.copied().unwrap_or(candidates[0]);
+
+                let surface: Vec<&str> = tokens[i..i + word_count].iter().map(|t| t.1).collect();
+                let has_article = i > 0
+                    && matches!(tokens[i - 1].1.to_lowercase().as_str(), "the" | "this" | "such");
+                let mut confidence = TermReferenceResolver::default().calculate_confidence(
+                    &surface.join(" "),
+                    &definition.term.term_name,
+                    has_article,
+                );
+                if in_scope.is_none() {
+                    confidence = (confidence - self.out_of_scope_penalty).max(0.0);
+                }
+
+                let last_token = tokens[i + word_count - 1].0;
+                results.push(Scored::rule_based(
+                    DocumentTermReference {
+                        term_name: definition.term.term_name.clone(),
+                        definition_type: definition.term.definition_type.clone(),
+                        span: DocSpan::single_line(line_index, tokens[i].0, last_token),
+                        definition_span: definition.span,
+                        scope: definition.term.scope.clone(),
+                        in_scope: in_scope.is_some(),
+                    },
+                    confidence,
+                    "document_term_reference",
+                ));
+                i += word_count;
+            }
+        }
         results
     }
 }
+
+/// Whether `position` lies in a definition: the quoted term or the defining
+/// text after it on the same line ("the annual fee" in `"Fee" means the
+/// annual fee`), which describes the term rather than referring to it.
+fn within_definition(definition: &DocSpan, position: &DocPosition) -> bool {
+    position.line == definition.start.line && position.token >= definition.start.token
+}
+
+/// Find uses of defined terms written in a different case than their definition.
+///
+/// "company" where "Company" is defined is either a drafting error or an
+/// unintended non-reference. Only word sequences that equal a defined term
+/// ignoring case are reported, and never at the start of a line or sentence,
+/// where capitalization says nothing. The definitions themselves are skipped.
+///
+/// Requires `DefinedTermResolver` to have run on the document's lines.
+/// Returns the span of each inconsistent mention with the term's canonical name.
+pub fn find_capitalization_inconsistencies(doc: &LayeredDocument) -> Vec<(DocSpan, String)> {
+    let mut definitions: Vec<(DocSpan, String)> = Vec::new();
+    for (line_index, line) in doc.lines_enumerated() {
+        for found in line.find(&x::attr::<Scored<DefinedTerm>>()) {
+            let span = DocSpan::from_find(line_index, &found);
+            definitions.push((span, found.attr().value.term_name.clone()));
+        }
+    }
+    let mut terms: Vec<(Vec<&str>, &str)> = definitions
+        .iter()
+        .map(|(_, name)| (name.split_whitespace().collect(), name.as_str()))
+        .collect();
+    terms.sort_by_key(|(words, name)| (std::cmp::Reverse(words.len()), *name));
+    terms.dedup_by(|a, b| a.1 == b.1);
+
+    let mut results = Vec::new();
+    for (line_index, line) in doc.lines_enumerated() {
+        let tokens = content_tokens(line);
+        let mut i = 0;
+        while i < tokens.len() {
+            let sentence_start =
+                i == 0 || matches!(tokens[i - 1].1, "." | "!" | "?" | ";" | ":");
+            let position = DocPosition::new(line_index, tokens[i].0);
+            let inside_definition =
+                definitions.iter().any(|(span, _)| within_definition(span, &position));
+            // Longest term first, so "Confidential Information" wins over "Information"
+            let matched = terms.iter().find(|(words, _)| {
+                words.len() <= tokens.len() - i
+                    && words.iter().zip(&tokens[i..]).all(|(word, (_, text, is_word))| {
+                        *is_word && text.eq_ignore_ascii_case(word)
+                    })
+            });
+            let (words, name) = match matched {
+                Some(term) if !inside_definition => term,
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+
+            let surface = &tokens[i..i + words.len()];
+            let exact = words.iter().zip(surface).all(|(word, (_, text, _))| word == text);
+            if !exact && !sentence_start {
+                let last_token = surface[surface.len() - 1].0;
+                results.push((
+                    DocSpan::single_line(line_index, tokens[i].0, last_token),
+                    name.to_string(),
+                ));
+            }
+            i += words.len();
+        }
+    }
+    results
+}
+
+/// Find references to specific defined entities that use an indefinite article.
+///
+/// "a Company" where "the Company" names one party is either not a reference
+/// at all or a drafting error. A term is specific when it was defined in a
+/// parenthetical (`ABC Corp (the "Company")`) or is referred to with "the"
+/// anywhere in the document.
+///
+/// Requires `TermReferenceResolver` to have run on the document's lines.
+/// Returns the span of each indefinite reference with the term's name.
+pub fn find_article_inconsistencies(doc: &LayeredDocument) -> Vec<(DocSpan, String)> {
+    let references: Vec<(DocSpan, TermReference)> = doc
+        .lines_enumerated()
+        .flat_map(|(line_index, line)| {
+            line.find(&x::attr::<Scored<TermReference>>())
+                .into_iter()
+                .map(move |found| (DocSpan::from_find(line_index, &found), found.attr().value.clone()))
+        })
+        .collect();
+
+    let is_specific = |term_name: &str| {
+        references.iter().any(|(_, reference)| {
+            reference.term_name == term_name
+                && (reference.definition_type == DefinitionType::Parenthetical
+                    || reference.article == Some(Article::Definite))
+        })
+    };
+
+    references
+        .iter()
+        .filter(|(_, reference)| reference.article == Some(Article::Indefinite))
+        .filter(|(_, reference)| is_specific(&reference.term_name))
+        .map(|(span, reference)| (*span, reference.term_name.clone()))
+        .collect()
+}
@@ -52,6 +52,22 @@ fn party_explorer_groups_by_beneficiary() {
     insta::assert_snapshot!(format!("{analytics:#?}"));
 }
 
+#[test]
+fn party_clauses_ordered_by_conditionality() {
+    let input = r#"ABC Corp (the "Seller") shall deliver goods if the Buyer submits a written request. The Seller shall remit fees to Regional Authority."#;
+    let nodes = graph_nodes(input);
+    let graph = ObligationGraph::new(&nodes);
+    let analytics = graph.for_party_name("Seller");
+
+    let clauses = analytics.clauses_by_conditionality();
+    assert_eq!(clauses.len(), 2);
+    assert_eq!(clauses[0].score, 0.0);
+    assert!(clauses[0].clause.duty.action.starts_with("remit fees"));
+    assert!(clauses[1].score > 0.0);
+    assert_eq!(analytics.mean_conditionality(), Some(clauses[1].score / 2.0));
+    assert_eq!(graph.for_party_name("Nobody").mean_conditionality(), None);
+}
+
 #[test]
 fn condition_filters_reference_section() {
     let nodes = graph_nodes(
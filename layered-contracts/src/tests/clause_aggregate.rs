@@ -2,9 +2,10 @@ use layered_nlp::{create_line_from_string, LLLineDisplay};
 use layered_part_of_speech::POSTagResolver;
 
 use crate::{
-    ClauseAggregate, ClauseAggregationResolver, ContractClause, ContractClauseResolver,
-    ContractKeyword, ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver,
-    PronounChainResolver, PronounResolver, ProhibitionResolver, Scored, TermReferenceResolver,
+    ClauseAggregate, ClauseAggregationResolver, ConfidencePropagation, ContractClause,
+    ContractClauseResolver, ContractKeyword, ContractKeywordResolver, DefinedTermResolver,
+    ObligationPhraseResolver, PronounChainResolver, PronounResolver, ProhibitionResolver, Scored,
+    TermReferenceResolver,
 };
 
 fn test_clause_aggregates(input: &str) -> String {
@@ -157,3 +158,51 @@ fn all_conditions_rollup() {
         "expected Unless condition"
     );
 }
+
+// ============ Confidence Propagation ============
+
+#[test]
+fn propagation_combines_clause_confidences() {
+    assert_eq!(ConfidencePropagation::Min.combine([0.9, 0.6, 0.8]), 0.6);
+    assert!((ConfidencePropagation::Mean.combine([0.9, 0.6, 0.9]) - 0.8).abs() < 1e-9);
+    assert!((ConfidencePropagation::Product.combine([0.5, 0.8]) - 0.4).abs() < 1e-9);
+    assert_eq!(ConfidencePropagation::Mean.combine(Vec::new()), 1.0);
+    assert_eq!(ConfidencePropagation::Product.combine(Vec::new()), 1.0);
+}
+
+#[test]
+fn propagation_mode_sets_aggregate_confidence_and_breakdown() {
+    let aggregate = |propagation| {
+        let ll_line = create_line_from_string(
+            r#"ABC Corp (the "Company") shall deliver goods. The Company shall."#,
+        )
+        .run(&POSTagResolver::default())
+        .run(&ContractKeywordResolver::default())
+        .run(&ProhibitionResolver::default())
+        .run(&DefinedTermResolver::default())
+        .run(&TermReferenceResolver::default())
+        .run(&PronounResolver::default())
+        .run(&ObligationPhraseResolver::default())
+        .run(&PronounChainResolver::default())
+        .run(&ContractClauseResolver::default())
+        .run(&ClauseAggregationResolver::default().with_propagation(propagation));
+        let aggregates = ll_line.query::<Scored<ClauseAggregate>>();
+        assert_eq!(aggregates.len(), 1);
+        aggregates[0].2[0].clone()
+    };
+
+    let min = aggregate(ConfidencePropagation::Min);
+    let mean = aggregate(ConfidencePropagation::Mean);
+    let product = aggregate(ConfidencePropagation::Product);
+    let clause_confidences: Vec<f64> =
+        min.value.clauses.iter().map(|entry| entry.clause_confidence).collect();
+    assert_eq!(clause_confidences.len(), 2);
+    assert!(clause_confidences[1] < clause_confidences[0]);
+
+    assert!(product.confidence < min.confidence);
+    assert!(min.confidence < mean.confidence);
+    assert!(min.value.confidence_breakdown[0].starts_with("Clauses (min of 2)"));
+    assert!(product.value.confidence_breakdown[0].starts_with("Clauses (product of 2)"));
+    let last = min.value.confidence_breakdown.last().unwrap();
+    assert_eq!(last, &format!("Layer8 result: {:.2}", min.confidence));
+}
@@ -1,12 +1,15 @@
 use layered_nlp::{
     create_line_from_string, x, LLCursorAssignment, LLLine, LLLineDisplay, LLSelection, Resolver,
 };
+use layered_deixis::DeicticCategory;
 use layered_part_of_speech::POSTagResolver;
 
 use crate::{
+    AmbiguityFlag, AnaphoricReferenceResolver, ClauseAmbiguityKind, ClauseAmbiguitySummary,
     ContractClause, ContractClauseResolver, ContractKeywordResolver, DefinedTermResolver,
-    ObligationPhrase, ObligationPhraseResolver, PronounChain, PronounChainResolver,
-    PronounResolver, ProhibitionResolver, Scored, TermReferenceResolver,
+    ModalityClassifier, ObligationPhrase, ObligationPhraseResolver, ObligorReference,
+    PronounChain, PronounChainResolver, PronounResolver, ProhibitionResolver, Scored,
+    TermReferenceResolver,
 };
 
 fn base_clause_pipeline(input: &str) -> LLLine {
@@ -62,6 +65,44 @@ impl Resolver for MarkChainsVerified {
     }
 }
 
+/// Re-emits each named-obligor obligation over the same span as a pronoun
+/// reading, mimicking a second detector resolving "it" to the same party.
+struct RedetectAsPronoun;
+
+impl Resolver for RedetectAsPronoun {
+    type Attr = Scored<ObligationPhrase>;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::attr::<Scored<ObligationPhrase>>())
+            .into_iter()
+            .filter_map(|(sel, obligation)| {
+                let resolved_to = match &obligation.value.obligor {
+                    ObligorReference::TermRef { term_name, .. } => term_name.clone(),
+                    ObligorReference::NounPhrase { text } => text.clone(),
+                    _ => return None,
+                };
+                let mut copy = obligation.value.clone();
+                copy.obligor = ObligorReference::PronounRef {
+                    pronoun: "It".to_string(),
+                    resolved_to,
+                    is_defined_term: true,
+                    confidence: 0.6,
+                };
+                Some(sel.finish_with_attr(Scored::rule_based(copy, 0.6, "redetected")))
+            })
+            .collect()
+    }
+}
+
+fn clause_actions(ll_line: &LLLine) -> Vec<(String, f64)> {
+    ll_line
+        .find(&x::attr::<Scored<ContractClause>>())
+        .into_iter()
+        .map(|found| (found.attr().value.duty.action.clone(), found.attr().confidence))
+        .collect()
+}
+
 // ============ Basic Clause Formation ============
 
 #[test]
@@ -126,3 +167,65 @@ fn clause_verified_chain_bonus() {
         r#"ABC Corp (the "Company") exists. It shall deliver replacement parts."#
     ));
 }
+
+// ============ Deduplication ============
+
+#[test]
+fn deduplication_collapses_overlapping_redetections() {
+    let input = r#"ABC Corp (the "Company") shall deliver goods. The Company shall deliver goods."#;
+    let redetected = || base_clause_pipeline(input).run(&RedetectAsPronoun);
+
+    let plain = redetected().run(&ContractClauseResolver::default());
+    assert_eq!(clause_actions(&plain).len(), 4);
+
+    let deduped = redetected().run(&ContractClauseResolver::new().with_deduplication(true));
+    let clauses = clause_actions(&deduped);
+    // Two separate "shall deliver" sentences remain distinct; only the
+    // re-detections over the same spans are collapsed, keeping the stronger reading.
+    assert_eq!(clauses.len(), 2);
+    for (action, confidence) in clauses {
+        assert_eq!(action, "deliver goods");
+        assert!(confidence > 0.6);
+    }
+}
+
+// ============ Ambiguity Summary ============
+
+#[test]
+fn ambiguity_summary_ranks_clauses_by_weighted_ambiguity() {
+    let ll_line = base_clause_pipeline(
+        r#"ABC Corp (the "Seller") and XYZ Inc (the "Buyer") signed the Supply Agreement and the License Agreement. It shall and may terminate said Agreement. The Buyer shall pay the fee."#,
+    )
+    .run(&ModalityClassifier::default())
+    .run(&AnaphoricReferenceResolver::new())
+    .run(&ContractClauseResolver::default());
+
+    let summaries: Vec<(String, ClauseAmbiguitySummary)> = ll_line
+        .find(&x::attr::<Scored<ContractClause>>())
+        .into_iter()
+        .map(|found| {
+            let clause = &found.attr().value;
+            (clause.duty.action.clone(), clause.ambiguity_summary())
+        })
+        .collect();
+    assert_eq!(summaries.len(), 2);
+
+    let (action, terminate) = &summaries[0];
+    assert_eq!(action, "terminate said Agreement");
+    assert_eq!(terminate.flag, AmbiguityFlag::CompetingAlternatives);
+    assert_eq!(terminate.count, 3);
+    assert_eq!(
+        terminate.kinds,
+        vec![
+            ClauseAmbiguityKind::Modality,
+            ClauseAmbiguityKind::PronounReference,
+            ClauseAmbiguityKind::Deixis(DeicticCategory::Discourse),
+        ]
+    );
+
+    // "said Agreement" belongs to the first sentence, not the next clause
+    let (_, pay) = &summaries[1];
+    assert!(!pay.is_ambiguous());
+    assert_eq!(pay.score, 0.0);
+    assert!(terminate.score > pay.score);
+}
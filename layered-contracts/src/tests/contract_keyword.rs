@@ -241,3 +241,20 @@ fn party_obligations() {
                                                                            ╰────────────╯SubjectTo
     "###);
 }
+
+#[test]
+fn permission_idioms() {
+    insta::assert_snapshot!(test_with_prohibition("The Company reserves the right to audit the Vendor"), @r###"
+    The     Company     reserves     the     right     to     audit     the     Vendor
+                        ╰───────────────────────────────╯PermissionIdiom
+    "###);
+    insta::assert_snapshot!(test_with_prohibition("The Vendor is not permitted to subcontract"), @r###"
+    The     Vendor     is     not     permitted     to     subcontract
+                       ╰─────────────────────────────╯MayNot
+    "###);
+    // After a modal the idiom is part of the modal's action
+    insta::assert_snapshot!(test_with_prohibition("The Vendor shall reserve the right to audit"), @r###"
+    The     Vendor     shall     reserve     the     right     to     audit
+                       ╰───╯Shall
+    "###);
+}
@@ -1,6 +1,9 @@
 use layered_nlp::{create_line_from_string, LLLineDisplay};
 
-use crate::{ContractKeyword, ContractKeywordResolver, DefinedTerm, DefinedTermResolver, Scored};
+use crate::{
+    plural_form, ContractKeyword, ContractKeywordResolver, DefinedTerm, DefinedTermResolver,
+    DefinitionType, Scored,
+};
 
 fn test_defined_terms(input: &str) -> String {
     let ll_line = create_line_from_string(input)
@@ -51,6 +54,54 @@ fn parenthetical_multiword() {
     insta::assert_snapshot!(test_defined_terms(r#"Acme Inc. (the "Purchasing Party")"#));
 }
 
+#[test]
+fn alias_of_common_noun_phrase() {
+    insta::assert_snapshot!(test_defined_terms(
+        r#"Vendor will provide the consulting services (the "Services") and hardware ("Hardware")."#
+    ));
+}
+
+#[test]
+fn alias_links_antecedent_and_scores_clarity() {
+    let definitions = |input: &str| {
+        let ll_line = create_line_from_string(input)
+            .run(&ContractKeywordResolver::default())
+            .run(&DefinedTermResolver::default());
+        ll_line
+            .query_with_associations::<Scored<DefinedTerm>>()
+            .into_iter()
+            .flat_map(|(_, _, found)| {
+                found.into_iter().map(|(term, associations)| {
+                    let body = associations
+                        .iter()
+                        .find(|a| a.label() == "definition_body")
+                        .map(|a| (a.span.start_idx, a.span.end_idx));
+                    (term.value.definition_type.clone(), term.confidence, body)
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // "consulting services" is bounded by "the": tokens 6..=8
+    let bounded = definitions(r#"We provide the consulting services (the "Services")."#);
+    assert_eq!(bounded, vec![(DefinitionType::Alias, 0.85, Some((6, 8)))]);
+
+    // No determiner: "provision of" stops the phrase at "services"
+    let unbounded = definitions(r#"provision of services ("Services")"#);
+    assert_eq!(unbounded[0].0, DefinitionType::Alias);
+    assert!((unbounded[0].1 - 0.75).abs() < 1e-9);
+    assert_eq!(unbounded[0].2, Some((4, 4)));
+
+    let missing = definitions(r#"("Services")"#);
+    assert_eq!(missing[0].0, DefinitionType::Alias);
+    assert!((missing[0].1 - 0.6).abs() < 1e-9);
+    assert_eq!(missing[0].2, None);
+
+    // Capitalized names keep the parenthetical type
+    let named = definitions(r#"ABC Corp (the "Company")"#);
+    assert_eq!(named, vec![(DefinitionType::Parenthetical, 0.9, Some((0, 2)))]);
+}
+
 // ============ Pattern 3: Hereinafter Tests ============
 
 #[test]
@@ -121,3 +172,52 @@ fn term_with_obligation() {
         r#""Contractor" means John Doe. The Contractor shall deliver the goods."#
     ));
 }
+
+#[test]
+fn defined_term_forms_include_plural_and_possessive() {
+    let term = DefinedTerm::new("Party".to_string(), DefinitionType::QuotedMeans);
+    assert_eq!(term.forms, vec!["Party", "Parties", "Party's", "Parties'"]);
+
+    assert_eq!(plural_form("Business Day"), "Business Days");
+    assert_eq!(plural_form("Person"), "Persons");
+    assert_eq!(plural_form("Services"), "Services");
+    assert_eq!(plural_form("Software"), "Software");
+    assert_eq!(plural_form("Lease"), "Leases");
+    assert_eq!(plural_form("Purchase Price"), "Purchase Prices");
+    assert_eq!(plural_form("Losses"), "Losses");
+    assert_eq!(plural_form("Tax"), "Taxes");
+    assert_eq!(plural_form("CHILD"), "CHILDREN");
+    assert_eq!(plural_form("Criterion"), "Criteria");
+}
+
+#[test]
+fn quoted_means_coordinated_terms() {
+    insta::assert_snapshot!(test_defined_terms(
+        r#""Buyer" and "Purchaser" mean the party identified above"#
+    ));
+}
+
+#[test]
+fn coordinated_terms_are_synonyms() {
+    let ll_line = create_line_from_string(r#""Seller", "Vendor", and "Supplier" mean ABC Corp"#)
+        .run(&ContractKeywordResolver::default())
+        .run(&DefinedTermResolver::default());
+
+    let mut terms: Vec<(String, Vec<String>)> = ll_line
+        .query::<Scored<DefinedTerm>>()
+        .into_iter()
+        .flat_map(|(_, _, found)| found)
+        .map(|scored| (scored.value.term_name.clone(), scored.value.synonyms.clone()))
+        .collect();
+    terms.sort();
+
+    let synonyms = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+    assert_eq!(
+        terms,
+        vec![
+            ("Seller".to_string(), synonyms(&["Vendor", "Supplier"])),
+            ("Supplier".to_string(), synonyms(&["Seller", "Vendor"])),
+            ("Vendor".to_string(), synonyms(&["Seller", "Supplier"])),
+        ]
+    );
+}
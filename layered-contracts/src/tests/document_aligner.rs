@@ -543,3 +543,40 @@ Section 1. DEFINITIONS
     });
     assert!(def_section.is_some(), "Definition section should be aligned");
 }
+
+#[test]
+fn test_max_sections_aligns_within_windows() {
+    let original = "Section 1\nThe Supplier shall deliver the goods.\n\
+                    Section 2\nThe Buyer shall pay the fees.\n\
+                    Section 3\nEither party may terminate on notice.\n\
+                    Section 4\nThis Agreement is governed by Delaware law.";
+    let revised = original
+        .replace("Section 1", "Section 11")
+        .replace("Section 2", "Section 12")
+        .replace("Section 3", "Section 13")
+        .replace("Section 4", "Section 14");
+    let (orig_struct, orig_doc) = build_structure(original);
+    let (rev_struct, rev_doc) = build_structure(&revised);
+
+    let full = DocumentAligner::new().align(&orig_struct, &rev_struct, &orig_doc, &rev_doc);
+    assert!(full.degradation.is_none());
+
+    let aligner = DocumentAligner::new().with_max_sections(2);
+    let result = aligner.align(&orig_struct, &rev_struct, &orig_doc, &rev_doc);
+    let degradation = result.degradation.as_ref().expect("four sections exceed a budget of two");
+    assert_eq!(degradation.required, 4);
+    assert_eq!(degradation.compared, 8);
+    assert_eq!(degradation.skipped_stages, vec!["split/merge detection".to_string()]);
+    assert!(result.warnings.iter().any(|w| w.contains("4 sections exceed the budget of 2")));
+
+    // Content matching still pairs each section with its renumbered copy
+    assert_eq!(result.stats.deleted, 0);
+    assert_eq!(result.stats.inserted, 0);
+    let pairs: Vec<(&str, &str)> = result
+        .alignments
+        .iter()
+        .map(|a| (a.original[0].canonical_id.as_str(), a.revised[0].canonical_id.as_str()))
+        .collect();
+    assert_eq!(pairs[0], ("SECTION:1", "SECTION:11"));
+    assert_eq!(pairs[3], ("SECTION:4", "SECTION:14"));
+}
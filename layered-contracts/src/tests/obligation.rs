@@ -1,10 +1,11 @@
-use layered_nlp::{create_line_from_string, LLLineDisplay};
+use layered_nlp::{create_line_from_string, LLLineDisplay, ResolverTrace, TraceOutcome};
 use layered_part_of_speech::POSTagResolver;
 
 use crate::{
-    ContractKeyword, ContractKeywordResolver, DefinedTerm, DefinedTermResolver,
-    ObligationPhrase, ObligationPhraseResolver, ProhibitionResolver, PronounReference,
-    PronounResolver, Scored, TermReference, TermReferenceResolver,
+    ConditionRef, ContractKeyword, ContractKeywordResolver, DefinedTerm, DefinedTermResolver,
+    DiscretionStandard, ObligationConfidenceBreakdown, ObligationNature, ObligationPhrase, ObligationPhraseResolver, ObligationType,
+    ObligorReference, ObligorStrategy, ProhibitionResolver, PronounReference, PronounResolver,
+    Scored, TermReference, TermReferenceResolver, UNKNOWN_OBLIGOR,
 };
 
 fn test_obligations(input: &str) -> String {
@@ -269,3 +270,546 @@ fn action_span_aligns_with_trimmed_text() {
         "action_span should have '#' glyph"
     );
 }
+
+// ============ Confidence Breakdown ============
+
+fn find_obligations(input: &str) -> Vec<Scored<ObligationPhrase>> {
+    let ll_line = create_line_from_string(input)
+        .run(&POSTagResolver::default())
+        .run(&ContractKeywordResolver::default())
+        .run(&ProhibitionResolver::default())
+        .run(&DefinedTermResolver::default())
+        .run(&TermReferenceResolver::default())
+        .run(&PronounResolver::default())
+        .run(&ObligationPhraseResolver::default());
+
+    ll_line
+        .find(&layered_nlp::x::attr::<Scored<ObligationPhrase>>())
+        .into_iter()
+        .map(|found| (*found.attr()).clone())
+        .collect()
+}
+
+#[test]
+fn confidence_breakdown_combines_into_confidence() {
+    let obligations = find_obligations(
+        r#"ABC Corp (the "Company") shall deliver goods. The Company shall ensure quality. It shall report."#,
+    );
+    assert!(!obligations.is_empty());
+
+    for scored in &obligations {
+        let breakdown = &scored.value.confidence_breakdown;
+        assert!(
+            (breakdown.total() - scored.confidence).abs() < 1e-9,
+            "breakdown {:?} should combine into confidence {}",
+            breakdown,
+            scored.confidence
+        );
+    }
+}
+
+#[test]
+fn confidence_breakdown_reports_weakest_factor() {
+    let breakdown = ObligationConfidenceBreakdown {
+        keyword_strength: 0.75,
+        obligor_resolution: -0.05,
+        action_clarity: -0.10,
+        condition_completeness: 0.0,
+    };
+    assert_eq!(breakdown.weakest_factor(), Some(("action_clarity", -0.10)));
+    assert!((breakdown.total() - 0.60).abs() < 1e-9);
+
+    // A plain noun obligor with a clear action has no negative factor
+    let plain = find_obligations("The Vendor shall deliver products.");
+    assert_eq!(plain[0].value.confidence_breakdown.weakest_factor(), None);
+}
+
+// ============ Parenthetical Qualifier Tests ============
+
+#[test]
+fn obligation_parenthetical_qualifier() {
+    insta::assert_snapshot!(test_obligations(
+        "The Vendor shall deliver (subject to availability) the products within fifteen days."
+    ));
+}
+
+#[test]
+fn parenthetical_qualifiers_are_kept_out_of_action() {
+    let obligations = find_obligations(
+        "The Vendor shall deliver (subject to availability) the products (as defined below).",
+    );
+    assert_eq!(obligations.len(), 1);
+
+    let phrase = &obligations[0].value;
+    assert_eq!(phrase.action, "deliver the products");
+    assert_eq!(phrase.qualifiers.len(), 2);
+    // "(" at token 8 through ")" at token 14
+    assert_eq!(phrase.qualifiers[0].to_lrange(), Some((8, 14)));
+    assert_eq!(phrase.qualifiers_on_line(3)[0], crate::DocSpan::single_line(3, 8, 14));
+}
+
+// ============ Passive Voice Tests ============
+
+#[test]
+fn obligation_passive_voice_agent() {
+    insta::assert_snapshot!(test_obligations(
+        r#"ABC Corp (the "Company") exists. The fees shall be paid by the Company."#
+    ));
+}
+
+#[test]
+fn passive_voice_uses_by_agent_as_obligor() {
+    let obligations = find_obligations(
+        r#"ABC Corp (the "Company") exists. The fees shall be paid by the Company."#,
+    );
+    assert_eq!(obligations.len(), 1);
+
+    let phrase = &obligations[0].value;
+    assert!(
+        matches!(&phrase.obligor, ObligorReference::TermRef { term_name, .. } if term_name == "Company"),
+        "expected Company as obligor, got {:?}",
+        phrase.obligor
+    );
+    assert_eq!(phrase.object.as_deref(), Some("The fees"));
+}
+
+#[test]
+fn passive_voice_without_agent_has_unknown_obligor() {
+    let obligations = find_obligations("The fees shall be paid within thirty days.");
+    assert_eq!(obligations.len(), 1);
+
+    let scored = &obligations[0];
+    assert_eq!(scored.value.obligor, ObligorReference::Unknown);
+    assert_eq!(scored.value.object.as_deref(), Some("The fees"));
+    assert!(scored.confidence < 0.5, "confidence {} should be low", scored.confidence);
+
+    // "by" introducing a date is not an agent
+    let dated = find_obligations("The fees shall be paid by March 1, 2025.");
+    assert_eq!(dated[0].value.obligor, ObligorReference::Unknown);
+
+    // Adjectival participles keep the active-voice obligor
+    let entitled = find_obligations("The Vendor shall be entitled to a refund.");
+    assert!(entitled[0].value.object.is_none());
+    assert!(matches!(entitled[0].value.obligor, ObligorReference::NounPhrase { .. }));
+}
+
+#[test]
+fn leading_negation_makes_prohibition() {
+    // ProhibitionResolver only pairs modals with "not"
+    let obligations = find_obligations("The Vendor shall never disclose the Confidential Information.");
+    assert_eq!(obligations.len(), 1);
+    assert_eq!(obligations[0].value.obligation_type, ObligationType::Prohibition);
+    assert_eq!(obligations[0].value.action, "disclose the Confidential Information");
+
+    // A negation later in the action leaves the duty alone
+    let duty = find_obligations("The Vendor shall pay all amounts not disputed.");
+    assert_eq!(duty[0].value.obligation_type, ObligationType::Duty);
+    assert_eq!(duty[0].value.action, "pay all amounts not disputed");
+}
+
+// ============ Flow-Down Tests ============
+
+#[test]
+fn obligation_cause_to_perform() {
+    insta::assert_snapshot!(test_obligations(
+        r#"ABC Corp (the "Company") shall cause its Affiliates to comply with this Agreement."#
+    ));
+}
+
+#[test]
+fn cause_to_perform_keeps_obligor_and_records_performer() {
+    let obligations = find_obligations(
+        r#"ABC Corp (the "Company") exists. The Company shall cause its Affiliates to comply."#,
+    );
+    assert_eq!(obligations.len(), 1);
+
+    let phrase = &obligations[0].value;
+    assert!(
+        matches!(&phrase.obligor, ObligorReference::TermRef { term_name, .. } if term_name == "Company"),
+        "expected Company as responsible party, got {:?}",
+        phrase.obligor
+    );
+    assert_eq!(phrase.performer.as_deref(), Some("its Affiliates"));
+    assert_eq!(phrase.action, "comply");
+
+    let prohibited = find_obligations("The Supplier shall not procure any subcontractor to bid.");
+    assert_eq!(prohibited[0].value.obligation_type, ObligationType::Prohibition);
+    assert_eq!(prohibited[0].value.performer.as_deref(), Some("any subcontractor"));
+
+    // Without "to" and a verb there is no flow-down frame
+    let direct = find_obligations("The Supplier shall cause no damage to the premises.");
+    assert!(direct[0].value.performer.is_none());
+    assert_eq!(direct[0].value.action, "cause no damage to the premises");
+}
+
+#[test]
+fn conditionality_score_weighs_event_over_timing_conditions() {
+    let unconditional = find_obligations("The Buyer shall pay the fees.");
+    assert_eq!(unconditional[0].value.conditionality_score(), 0.0);
+
+    let event = find_obligations("The Buyer shall pay the fees if the Goods are accepted.");
+    let timing =
+        find_obligations("The Buyer shall pay the fees if within thirty days of delivery.");
+    let event_score = event[0].value.conditionality_score();
+    let timing_score = timing[0].value.conditionality_score();
+    assert!(timing[0].value.conditions[0].is_temporal());
+    assert!(!event[0].value.conditions[0].is_temporal());
+    assert!(0.0 < timing_score && timing_score < event_score && event_score < 1.0);
+
+    let mut gated = event[0].value.clone();
+    gated.conditions.push(ConditionRef {
+        condition_type: ContractKeyword::Unless,
+        text_preview: "the Seller is in default".to_string(),
+    });
+    assert!(gated.conditionality_score() > event_score);
+}
+
+#[test]
+fn condition_type_decides_timing_conditions() {
+    let when = find_obligations("The Seller shall refund the fees when the Buyer defaults.");
+    assert_eq!(when[0].value.conditions[0].condition_type, ContractKeyword::If);
+    assert!(!when[0].value.conditions[0].is_temporal());
+
+    // An exception lifts the duty however it is phrased
+    let condition = |condition_type| ConditionRef {
+        condition_type,
+        text_preview: "within thirty days of delivery".to_string(),
+    };
+    assert!(condition(ContractKeyword::If).is_temporal());
+    assert!(!condition(ContractKeyword::Unless).is_temporal());
+    assert!(!condition(ContractKeyword::Provided).is_temporal());
+
+    let event = ConditionRef {
+        condition_type: ContractKeyword::SubjectTo,
+        text_preview: "when the Buyer defaults".to_string(),
+    };
+    assert!(!event.is_temporal());
+}
+
+#[test]
+fn traced_run_explains_accepted_and_rejected_modals() {
+    let mut trace = ResolverTrace::new();
+    create_line_from_string("Shall deliver the goods. The Buyer shall pay the fees.")
+        .run(&POSTagResolver::default())
+        .run_traced(&ContractKeywordResolver::default(), &mut trace)
+        .run(&ProhibitionResolver::default())
+        .run(&DefinedTermResolver::default())
+        .run(&TermReferenceResolver::default())
+        .run(&PronounResolver::default())
+        .run_traced(&ObligationPhraseResolver::default(), &mut trace);
+
+    let keywords: Vec<_> = trace
+        .events()
+        .iter()
+        .filter(|e| e.resolver.ends_with("ContractKeywordResolver"))
+        .collect();
+    assert_eq!(keywords.len(), 2);
+    assert!(keywords.iter().all(|e| e.reason == "keyword matched: Shall"));
+
+    let rejected: Vec<_> = trace.rejected().collect();
+    assert_eq!(rejected.len(), 1);
+    assert_eq!(rejected[0].reason, "obligor resolution failed");
+    assert_eq!(rejected[0].span.start_idx, 0);
+
+    let accepted = trace.accepted().last().unwrap();
+    assert_eq!(accepted.outcome, TraceOutcome::Accepted);
+    assert!(accepted.reason.starts_with("Duty for \"Buyer\""), "{}", trace);
+
+    // A disabled trace records nothing
+    let mut disabled = ResolverTrace::disabled();
+    create_line_from_string("The Buyer shall pay.")
+        .run_traced(&ContractKeywordResolver::default(), &mut disabled);
+    assert!(disabled.events().is_empty());
+}
+
+// ============ Obligor Strategies ============
+
+fn find_obligations_with(
+    input: &str,
+    strategy: ObligorStrategy,
+) -> Vec<Scored<ObligationPhrase>> {
+    let ll_line = create_line_from_string(input)
+        .run(&POSTagResolver::default())
+        .run(&ContractKeywordResolver::default())
+        .run(&ProhibitionResolver::default())
+        .run(&DefinedTermResolver::default())
+        .run(&TermReferenceResolver::default())
+        .run(&PronounResolver::default())
+        .run(&ObligationPhraseResolver::new().with_obligor_strategy(strategy));
+
+    ll_line
+        .find(&layered_nlp::x::attr::<Scored<ObligationPhrase>>())
+        .into_iter()
+        .map(|found| (*found.attr()).clone())
+        .collect()
+}
+
+fn obligor_name(obligor: &ObligorReference) -> &str {
+    match obligor {
+        ObligorReference::TermRef { term_name, .. } => term_name,
+        ObligorReference::PronounRef { resolved_to, .. } => resolved_to,
+        ObligorReference::NounPhrase { text } => text,
+        ObligorReference::Unknown => UNKNOWN_OBLIGOR,
+    }
+}
+
+#[test]
+fn obligor_strategies_choose_different_candidates() {
+    let input = r#"ABC Corp (the "Company") hires carriers. Upon notice from the Company, the Carrier shall deliver."#;
+    let run = |strategy| {
+        let obligations = find_obligations_with(input, strategy);
+        assert_eq!(obligations.len(), 1, "{:?}", strategy);
+        let obligation = &obligations[0].value;
+        (
+            obligor_name(&obligation.obligor).to_string(),
+            obligation.confidence_breakdown.obligor_resolution,
+        )
+    };
+
+    let (nearest, nearest_score) = run(ObligorStrategy::Nearest);
+    assert_eq!(nearest, "Company");
+
+    let (defined, defined_score) = run(ObligorStrategy::PreferDefinedTerm);
+    assert_eq!(defined, "Company");
+    assert!((defined_score - nearest_score - ObligorStrategy::AGREEMENT_BONUS).abs() < 1e-9);
+
+    // "Company" is the object of "from"; "Carrier" is nearer and the subject
+    let (noun, noun_score) = run(ObligorStrategy::NearestNounPhrase);
+    assert_eq!(noun, "Carrier");
+    assert!(noun_score < nearest_score);
+
+    let (subject, subject_score) = run(ObligorStrategy::SentenceSubject);
+    assert_eq!(subject, "Carrier");
+    assert!(subject_score > noun_score);
+}
+
+#[test]
+fn obligor_strategy_adjustments() {
+    assert_eq!(ObligorStrategy::default(), ObligorStrategy::Nearest);
+    assert_eq!(ObligorStrategy::Nearest.confidence_adjustment(false), 0.0);
+    assert_eq!(ObligorStrategy::SentenceSubject.confidence_adjustment(true), 0.05);
+    assert_eq!(ObligorStrategy::PreferDefinedTerm.confidence_adjustment(false), -0.05);
+    assert_eq!(ObligorStrategy::NearestNounPhrase.confidence_adjustment(false), -0.10);
+
+    // With a single candidate every strategy agrees with the nearest one
+    let input = r#"ABC Corp (the "Company") sells goods. The Company shall report."#;
+    let nearest = find_obligations_with(input, ObligorStrategy::Nearest);
+    let subject = find_obligations_with(input, ObligorStrategy::SentenceSubject);
+    assert_eq!(nearest.len(), subject.len());
+    for (n, s) in nearest.iter().zip(&subject) {
+        assert_eq!(n.value.obligor, s.value.obligor);
+    }
+}
+
+#[test]
+fn obligation_type_voted_from_phrasing() {
+    let obligation_type = |input: &str| {
+        let found = find_obligations_with(input, ObligorStrategy::Nearest);
+        found[0].value.obligation_type
+    };
+    let right = r#"ABC Corp (the "Company") shall have the right to audit the records."#;
+    assert_eq!(obligation_type(right), ObligationType::Permission);
+    let lifted = r#"ABC Corp (the "Company") shall not be required to renew."#;
+    assert_eq!(obligation_type(lifted), ObligationType::Permission);
+    let duty = r#"ABC Corp (the "Company") shall audit the records."#;
+    assert_eq!(obligation_type(duty), ObligationType::Duty);
+}
+
+#[test]
+fn unresolved_obligor_lowers_confidence() {
+    let vague = find_obligations_with(
+        "The relevant authority shall approve the plan.",
+        ObligorStrategy::Nearest,
+    );
+    let named =
+        find_obligations_with("The Supplier shall approve the plan.", ObligorStrategy::Nearest);
+    assert_eq!(vague.len(), 1);
+    assert!(vague[0].value.has_unknown_obligor());
+    assert!(!named[0].value.has_unknown_obligor());
+    assert!((named[0].confidence - vague[0].confidence - 0.10).abs() < 1e-9);
+    assert!(ObligorReference::Unknown.is_unresolved());
+}
+
+#[test]
+fn truncated_condition_lowers_confidence() {
+    let input = "If the Buyer pays the full invoice amount on time, the Seller shall deliver goods.";
+    let run = |resolver: &ObligationPhraseResolver| {
+        let ll_line = create_line_from_string(input)
+            .run(&POSTagResolver::default())
+            .run(&ContractKeywordResolver::default())
+            .run(&ProhibitionResolver::default())
+            .run(&DefinedTermResolver::default())
+            .run(&TermReferenceResolver::default())
+            .run(&PronounResolver::default())
+            .run(resolver);
+        ll_line
+            .find(&layered_nlp::x::attr::<Scored<ObligationPhrase>>())
+            .into_iter()
+            .map(|found| (*found.attr()).clone())
+            .collect::<Vec<_>>()
+    };
+
+    let lenient = run(&ObligationPhraseResolver::new());
+    let strict = run(&ObligationPhraseResolver::new().with_truncated_condition_penalty(0.05));
+    assert_eq!(strict.len(), 1);
+    assert!(strict[0].value.conditions[0].text_preview.ends_with("..."));
+    assert_eq!(lenient[0].value.confidence_breakdown.condition_completeness, 0.0);
+    assert!((strict[0].value.confidence_breakdown.condition_completeness + 0.05).abs() < 1e-9);
+    assert!((lenient[0].confidence - strict[0].confidence - 0.05).abs() < 1e-9);
+}
+
+#[test]
+fn obligation_evidence_includes_associated_text() {
+    use crate::{ContractDocument, DocSpan};
+
+    let doc = ContractDocument::from_text(
+        "ARTICLE 1\nABC Corp (the \"Company\") shall deliver the  goods.",
+    )
+    .run_resolver(&POSTagResolver::default())
+    .run_resolver(&ContractKeywordResolver::default())
+    .run_resolver(&DefinedTermResolver::default())
+    .run_resolver(&TermReferenceResolver::default())
+    .run_resolver(&ObligationPhraseResolver::default());
+
+    let found = doc.lines()[1].find(&layered_nlp::x::attr::<Scored<ObligationPhrase>>());
+    let span = DocSpan::from_find(1, &found[0]);
+    let scored = (*found[0].attr()).clone();
+
+    // The obligation is anchored on its modal; the associations carry the rest
+    let evidence = doc.evidence(&scored, &span);
+    assert_eq!(evidence.text, "shall");
+    assert!(evidence.associations.contains(&("obligor_source", "Company".to_string())));
+    let action = ("action_span", "shall deliver the  goods".to_string());
+    assert!(evidence.associations.contains(&action));
+    assert_eq!(doc.evidence_text(&scored, &span), evidence.text);
+}
+
+#[test]
+fn obligation_nature_follows_the_verb() {
+    let nature = |input: &str| {
+        find_obligations_with(input, ObligorStrategy::Nearest)[0].value.obligation_nature
+    };
+    assert_eq!(nature("The Supplier shall deliver the goods."), ObligationNature::Performance);
+    assert_eq!(
+        nature("The Supplier shall represent that it is duly organized."),
+        ObligationNature::Representation
+    );
+    assert_eq!(
+        nature("The Supplier will warrant that the goods are new."),
+        ObligationNature::Warranty
+    );
+    assert_eq!(
+        nature("The Buyer shall hereby acknowledge receipt of the goods."),
+        ObligationNature::Acknowledgment
+    );
+    assert_eq!(nature("The Buyer shall agree to the terms."), ObligationNature::Covenant);
+}
+
+#[test]
+fn coordinated_modals_form_one_penalized_obligation() {
+    let found = find_obligations_with(
+        "The Company shall and may at its discretion deliver the goods.",
+        ObligorStrategy::Nearest,
+    );
+    assert_eq!(found.len(), 1);
+    let mixed = &found[0];
+    // The stricter reading wins the tied vote
+    assert_eq!(mixed.value.obligation_type, ObligationType::Duty);
+    assert_eq!(mixed.value.action, "deliver the goods");
+    assert_eq!(mixed.value.discretion, Some(DiscretionStandard::Unqualified));
+
+    let plain = &find_obligations_with(
+        "The Company shall at its discretion deliver the goods.",
+        ObligorStrategy::Nearest,
+    )[0];
+    assert!(mixed.confidence < plain.confidence);
+
+    // Agreeing modals are merged without a penalty
+    let agreeing =
+        find_obligations_with("The Company shall and will deliver the goods.", ObligorStrategy::Nearest);
+    assert_eq!(agreeing.len(), 1);
+    assert_eq!(
+        agreeing[0].value.confidence_breakdown.keyword_strength,
+        plain.value.confidence_breakdown.keyword_strength
+    );
+}
+
+#[test]
+fn only_before_condition_is_not_part_of_action() {
+    let found = find_obligations_with(
+        "The Tenant may terminate this Lease only upon 30 days notice.",
+        ObligorStrategy::Nearest,
+    );
+    assert_eq!(found[0].value.action, "terminate this Lease");
+    assert_eq!(found[0].value.conditions[0].text_preview, "30 days notice");
+
+    let found =
+        find_obligations_with("The Tenant may use the Premises only for storage.", ObligorStrategy::Nearest);
+    assert_eq!(found[0].value.action, "use the Premises only for storage");
+}
+
+#[test]
+fn permission_idioms_are_permissions() {
+    let reserves = &find_obligations(
+        r#"ABC Corp (the "Company") reserves the right to suspend the Services."#,
+    )[0];
+    assert_eq!(reserves.value.obligation_type, ObligationType::Permission);
+    assert_eq!(reserves.value.action, "suspend the Services");
+    assert_eq!(obligor_name(&reserves.value.obligor), "Company");
+
+    let permitted =
+        &find_obligations(r#"ABC Corp (the "Company") is permitted to assign this Agreement."#)[0];
+    assert_eq!(permitted.value.obligation_type, ObligationType::Permission);
+    assert_eq!(permitted.value.action, "assign this Agreement");
+
+    // "is entitled to" is the least explicit idiom
+    let entitled =
+        &find_obligations(r#"ABC Corp (the "Company") is entitled to receive the fees."#)[0];
+    assert!(
+        entitled.value.confidence_breakdown.keyword_strength
+            < reserves.value.confidence_breakdown.keyword_strength
+    );
+
+    let forbidden =
+        &find_obligations(r#"ABC Corp (the "Company") is not permitted to assign this Agreement."#)[0];
+    assert_eq!(forbidden.value.obligation_type, ObligationType::Prohibition);
+
+    // After a modal the phrasing is voted by the modality classifier instead
+    let modal = find_obligations(r#"ABC Corp (the "Company") shall reserve the right to audit."#);
+    assert_eq!(modal.len(), 1);
+    assert_eq!(modal[0].value.action, "reserve the right to audit");
+}
+
+#[test]
+fn discretion_phrases_are_recorded_outside_the_action() {
+    let discretion = |input: &str| {
+        let found = &find_obligations(input)[0];
+        (found.value.action.clone(), found.value.discretion)
+    };
+
+    assert_eq!(
+        discretion(
+            r#"ABC Corp (the "Company") may, in its sole discretion, terminate this Agreement."#
+        ),
+        ("terminate this Agreement".to_string(), Some(DiscretionStandard::Sole))
+    );
+    assert_eq!(
+        discretion(r#"ABC Corp (the "Buyer") may reject the Goods in its reasonable discretion."#),
+        ("reject the Goods".to_string(), Some(DiscretionStandard::Reasonable))
+    );
+    assert_eq!(
+        discretion(r#"ABC Corp (the "Company"), at its option, may repair the Goods."#),
+        ("repair the Goods".to_string(), Some(DiscretionStandard::Unqualified))
+    );
+    assert_eq!(
+        discretion(r#"ABC Corp (the "Company") reserves the right to audit the Vendor."#),
+        ("audit the Vendor".to_string(), None)
+    );
+
+    // The possessive in a leading discretion phrase is not the obligor
+    let found = &find_obligations(
+        "In its sole and absolute discretion, the Licensor may revoke the license.",
+    )[0];
+    assert_eq!(found.value.discretion, Some(DiscretionStandard::Sole));
+    assert_eq!(obligor_name(&found.value.obligor), "Licensor");
+}
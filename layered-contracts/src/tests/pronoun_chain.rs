@@ -2,8 +2,9 @@ use layered_nlp::{create_line_from_string, LLLineDisplay};
 use layered_part_of_speech::POSTagResolver;
 
 use crate::{
-    ContractKeywordResolver, DefinedTerm, DefinedTermResolver, PronounChain, PronounChainResolver,
-    PronounReference, PronounResolver, Scored, TermReference, TermReferenceResolver,
+    entities, ContractDocument, ContractKeywordResolver, DefinedTerm, DefinedTermResolver,
+    MentionType, ObligationPhraseResolver, PronounChain, PronounChainResolver, PronounReference,
+    PronounResolver, Scored, TermReference, TermReferenceResolver,
 };
 
 fn test_chains(input: &str) -> String {
@@ -152,3 +153,46 @@ fn contract_multiple_parties_chain() {
         r#"ABC Corp (the "Licensor") and XYZ Inc (the "Licensee") enter this Agreement. The Licensor grants rights. The Licensee shall pay royalties. It shall report usage."#
     ));
 }
+
+// ============ Canonical Entities ============
+
+#[test]
+fn entities_merge_chains_across_lines() {
+    let doc = ContractDocument::from_text(
+        "ABC Corp (the \"Company\") exists. The Company shall deliver the goods.\n\
+         XYZ Inc (the \"Vendor\") exists. The Vendor agrees.\n\
+         ABC Corp (the \"Company\") remains liable. It shall keep records.",
+    )
+    .run_resolver(&POSTagResolver::default())
+    .run_resolver(&ContractKeywordResolver::default())
+    .run_resolver(&DefinedTermResolver::default())
+    .run_resolver(&TermReferenceResolver::default())
+    .run_resolver(&PronounResolver::default())
+    .run_resolver(&PronounChainResolver::default())
+    .run_resolver(&ObligationPhraseResolver::default());
+
+    let entities = entities(&doc);
+    let names: Vec<&str> = entities.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["Company", "Vendor"]);
+
+    let company = &entities[0];
+    assert!(company.is_defined_term);
+    let lines: Vec<usize> = company.mentions.iter().map(|m| m.line).collect();
+    assert_eq!(lines, vec![0, 0, 2, 2]);
+    assert_eq!(company.mentions[0].mention.mention_type, MentionType::Definition);
+    assert_eq!(company.pronoun_count(), 1);
+    assert_eq!(company.surface_forms, vec!["Company", "It"]);
+    assert_eq!(company.obligation_count, 2);
+
+    // A single chain only covers its own line
+    let line_chain = doc.lines()[2]
+        .find(&layered_nlp::x::attr::<Scored<PronounChain>>())
+        .into_iter()
+        .map(|found| found.attr().value.clone())
+        .next()
+        .unwrap();
+    let partial = line_chain.to_entity(&doc);
+    assert_eq!(partial.name, "Company");
+    assert!(partial.mentions.iter().all(|m| m.line == 2));
+    assert_eq!(partial.obligation_count, 1);
+}
@@ -1,15 +1,17 @@
 //! Tests for SemanticDiffEngine - semantic contract comparison.
 
 use crate::{
-    ContractDocument, ContractKeywordResolver, DefinedTermResolver, DocumentAligner,
-    DocumentStructureBuilder, ImpactDirection, ObligationPhraseResolver, RiskLevel,
+    ConditionChange, ContractDocument, ContractKeywordResolver, DefinedTermResolver, DocSpan,
+    DocumentAligner, DocumentStructureBuilder, ImpactDirection, ObligationPhraseResolver, RiskLevel,
     SectionHeaderResolver, SemanticChangeType, SemanticDiffEngine, TermReferenceResolver,
 };
+use layered_part_of_speech::POSTagResolver;
 
 /// Helper to process a document through all necessary resolvers for semantic analysis.
 fn process_document(text: &str) -> ContractDocument {
     ContractDocument::from_text(text)
         .run_resolver(&SectionHeaderResolver::new())
+        .run_resolver(&POSTagResolver::default())
         .run_resolver(&ContractKeywordResolver::new())
         .run_resolver(&DefinedTermResolver::new())
         .run_resolver(&TermReferenceResolver::new())
@@ -87,6 +89,62 @@ The Company may deliver the goods within the specified timeframe.
     }
 }
 
+#[test]
+fn test_obligation_changes_locate_both_versions() {
+    let original = r#"
+Section 2.1 Obligations
+The Company shall deliver the goods.
+"#;
+
+    let revised = r#"
+Section 2.1 Obligations
+Deliveries are scheduled weekly.
+The Company may deliver the goods if requested.
+"#;
+
+    let orig_doc = process_document(original);
+    let rev_doc = process_document(revised);
+    let alignments = DocumentAligner::new().align(
+        &build_structure(&orig_doc),
+        &build_structure(&rev_doc),
+        &orig_doc,
+        &rev_doc,
+    );
+    let result = SemanticDiffEngine::new().compute_diff(&alignments, &orig_doc, &rev_doc);
+
+    let modal = result
+        .changes
+        .iter()
+        .find_map(|change| match &change.change_type {
+            SemanticChangeType::ObligationModal(modal) => Some(modal),
+            _ => None,
+        })
+        .expect("shall -> may should be reported");
+    assert_eq!(modal.before_span.start.line, 1);
+    assert_eq!(modal.after_span.start.line, 2);
+    assert_eq!(span_text(&orig_doc, &modal.before_span), "shall");
+    assert_eq!(span_text(&rev_doc, &modal.after_span), "may");
+
+    let added = result.changes.iter().find_map(|change| match &change.change_type {
+        SemanticChangeType::ObligationCondition(ConditionChange::Added {
+            before_span, after_span, ..
+        }) => Some((*before_span, *after_span)),
+        _ => None,
+    });
+    assert_eq!(added, Some((modal.before_span, modal.after_span)));
+}
+
+/// Text of a single-line span.
+fn span_text(doc: &ContractDocument, span: &DocSpan) -> String {
+    doc.lines()[span.start.line].ll_tokens()[span.start.token..=span.end.token]
+        .iter()
+        .filter_map(|token| match token.get_token() {
+            layered_nlp::LToken::Text(text, _) => Some(text.as_str()),
+            layered_nlp::LToken::Value => None,
+        })
+        .collect()
+}
+
 #[test]
 fn test_section_removal_detected() {
     let original = r#"
@@ -2,7 +2,7 @@
 
 use crate::{
     ArgumentRole, CanonicalModal, EnhancedObligationNormalizer, EquivalenceResult,
-    ObligationPhrase, ObligationType, ObligorReference, SemanticRoleLabeler,
+    ObligationNature, ObligationPhrase, ObligationType, ObligorReference, SemanticRoleLabeler,
 };
 
 #[test]
@@ -15,6 +15,12 @@ fn test_extract_agent_active() {
         obligation_type: ObligationType::Duty,
         action: "deliver goods to the Buyer".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let labeler = SemanticRoleLabeler::new();
@@ -38,6 +44,12 @@ fn test_extract_agent_passive() {
         obligation_type: ObligationType::Duty,
         action: "be indemnified by the Seller".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let labeler = SemanticRoleLabeler::new();
@@ -61,6 +73,12 @@ fn test_extract_prepositional_arguments() {
         obligation_type: ObligationType::Duty,
         action: "deliver Products to the Buyer within thirty days".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let labeler = SemanticRoleLabeler::new();
@@ -85,6 +103,12 @@ fn test_normalize_obligation() {
         obligation_type: ObligationType::Duty,
         action: "deliver goods".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let normalizer = EnhancedObligationNormalizer::new();
@@ -105,6 +129,12 @@ fn test_equivalence_active_passive() {
         obligation_type: ObligationType::Duty,
         action: "deliver Products".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let passive = ObligationPhrase {
@@ -115,6 +145,12 @@ fn test_equivalence_active_passive() {
         obligation_type: ObligationType::Duty,
         action: "Products be delivered".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let normalizer = EnhancedObligationNormalizer::new();
@@ -141,6 +177,12 @@ fn test_equivalence_synonyms() {
         obligation_type: ObligationType::Duty,
         action: "deliver Products".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let provide = ObligationPhrase {
@@ -151,6 +193,12 @@ fn test_equivalence_synonyms() {
         obligation_type: ObligationType::Duty,
         action: "provide Products".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let normalizer = EnhancedObligationNormalizer::new();
@@ -175,6 +223,12 @@ fn test_modal_difference() {
         obligation_type: ObligationType::Duty,
         action: "pay within 30 days".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let may = ObligationPhrase {
@@ -185,6 +239,12 @@ fn test_modal_difference() {
         obligation_type: ObligationType::Permission,
         action: "pay within 30 days".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let normalizer = EnhancedObligationNormalizer::new();
@@ -226,6 +286,12 @@ fn test_extract_theme_active() {
         obligation_type: ObligationType::Duty,
         action: "deliver goods".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let labeler = SemanticRoleLabeler::new();
@@ -248,6 +314,12 @@ fn test_extract_recipient() {
         obligation_type: ObligationType::Duty,
         action: "deliver goods to the Buyer".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let labeler = SemanticRoleLabeler::new();
@@ -270,6 +342,12 @@ fn test_extract_manner() {
         obligation_type: ObligationType::Duty,
         action: "notify in writing".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let labeler = SemanticRoleLabeler::new();
@@ -292,6 +370,12 @@ fn test_different_obligations() {
         obligation_type: ObligationType::Duty,
         action: "deliver goods".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let pay = ObligationPhrase {
@@ -302,6 +386,12 @@ fn test_different_obligations() {
         obligation_type: ObligationType::Duty,
         action: "pay the price".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let normalizer = EnhancedObligationNormalizer::new();
@@ -324,6 +414,12 @@ fn test_pronoun_reference_as_obligor() {
         obligation_type: ObligationType::Duty,
         action: "deliver goods".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let labeler = SemanticRoleLabeler::new();
@@ -342,6 +438,12 @@ fn test_noun_phrase_as_obligor() {
         obligation_type: ObligationType::Duty,
         action: "comply with laws".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let labeler = SemanticRoleLabeler::new();
@@ -361,6 +463,12 @@ fn test_prohibition_modal() {
         obligation_type: ObligationType::Prohibition,
         action: "disclose information".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let normalizer = EnhancedObligationNormalizer::new();
@@ -379,6 +487,12 @@ fn test_permission_modal() {
         obligation_type: ObligationType::Permission,
         action: "inspect the goods".to_string(),
         conditions: vec![],
+        confidence_breakdown: Default::default(),
+        qualifiers: Vec::new(),
+        object: None,
+        performer: None,
+        obligation_nature: ObligationNature::Performance,
+        discretion: None,
     };
 
     let normalizer = EnhancedObligationNormalizer::new();
@@ -136,3 +136,246 @@ fn reference_in_obligation() {
         r#""Service Provider" means ABC Corp. The Service Provider shall deliver services."#
     ));
 }
+
+// ============ Document-Level Scope Tests ============
+
+fn document_references(text: &str) -> Vec<crate::DocumentTermReference> {
+    use crate::{ContractDocument, DocumentTermReferenceResolver, SectionHeaderResolver};
+
+    let doc = ContractDocument::from_text(text)
+        .run_resolver(&SectionHeaderResolver::new())
+        .run_resolver(&ContractKeywordResolver::default())
+        .run_resolver(&DefinedTermResolver::default())
+        .run_document_resolver(&DocumentTermReferenceResolver::new());
+
+    doc.query_doc::<Scored<crate::DocumentTermReference>>()
+        .into_iter()
+        .map(|scored| scored.value.clone())
+        .collect()
+}
+
+#[test]
+fn document_references_respect_exhibit_scope() {
+    use crate::DefinitionScope;
+
+    let refs = document_references(
+        "ARTICLE 1\n\
+         \"Services\" means consulting services.\n\
+         The Supplier shall perform the Services.\n\
+         EXHIBIT 1\n\
+         \"Deliverables\" means the reports listed below.\n\
+         The Supplier shall provide the Deliverables and the Services.\n\
+         EXHIBIT 2\n\
+         The Supplier shall provide Deliverables.",
+    );
+
+    let services: Vec<_> = refs.iter().filter(|r| r.term_name == "Services").collect();
+    assert_eq!(services.len(), 2);
+    assert!(services.iter().all(|r| r.in_scope && r.scope == DefinitionScope::Global));
+
+    let deliverables: Vec<_> = refs.iter().filter(|r| r.term_name == "Deliverables").collect();
+    assert_eq!(deliverables.len(), 2);
+    assert!(matches!(deliverables[0].scope, DefinitionScope::Section(_)));
+    assert!(deliverables[0].in_scope, "use inside Exhibit 1 is in scope");
+    assert!(!deliverables[1].in_scope, "use in Exhibit 2 is out of scope");
+}
+
+#[test]
+fn exhibit_redefinition_shadows_global_definition() {
+    let refs = document_references(
+        "\"Fee\" means the annual fee.\n\
+         The Customer shall pay the Fee.\n\
+         SCHEDULE 1\n\
+         \"Fee\" means the setup fee.\n\
+         The Customer shall pay the Fee.",
+    );
+
+    let fees: Vec<_> = refs.iter().filter(|r| r.term_name == "Fee").collect();
+    assert_eq!(fees.len(), 2);
+    assert_eq!(fees[0].definition_span.start.line, 0);
+    assert_eq!(fees[1].definition_span.start.line, 3);
+    assert!(fees.iter().all(|r| r.in_scope));
+}
+
+#[test]
+fn definition_spans_are_token_indices() {
+    use crate::DocSpan;
+
+    let refs = document_references(
+        "\"Fee\" means the annual fee payable under this agreement, being the Fee for support.\n\
+         The Customer shall pay the Fee.",
+    );
+
+    // The defining text is not a mention, however far it runs past the quoted term
+    let fees: Vec<_> = refs.iter().filter(|r| r.term_name == "Fee").collect();
+    assert_eq!(fees.len(), 1);
+    assert_eq!(fees[0].span.start.line, 1);
+    // `"Fee" means` spans tokens 0-4 of the first line
+    assert_eq!(fees[0].definition_span, DocSpan::single_line(0, 0, 4));
+}
+
+#[test]
+fn capitalization_inconsistencies_skip_sentence_starts() {
+    use crate::{find_capitalization_inconsistencies, ContractDocument};
+
+    let doc = ContractDocument::from_text(
+        "ABC Corp (the \"Company\") owns trade secrets (the \"Confidential Information\").\n\
+         The company shall protect the confidential information. Company records are excluded.\n\
+         company policies apply, and the Company shall notify each companion.",
+    )
+    .run_resolver(&ContractKeywordResolver::default())
+    .run_resolver(&DefinedTermResolver::default());
+
+    let found = find_capitalization_inconsistencies(&doc);
+    let found: Vec<(usize, usize, &str)> = found
+        .iter()
+        .map(|(span, term)| (span.start.line, span.start.token, term.as_str()))
+        .collect();
+    // "company" and "confidential information" on line 1; the sentence-initial
+    // "company" on line 2, exact "Company" uses and "companion" are not flagged
+    assert_eq!(found, vec![(1, 2, "Company"), (1, 10, "Confidential Information")]);
+}
+
+#[test]
+fn article_inconsistencies_flag_indefinite_uses_of_specific_terms() {
+    use crate::{find_article_inconsistencies, Article, ContractDocument};
+
+    let doc = ContractDocument::from_text(
+        "ABC Corp (the \"Company\") shall appoint a Company to audit the Company. \
+         \"Affiliate\" means any controlled entity. An Affiliate may assign to another Affiliate.",
+    )
+    .run_resolver(&ContractKeywordResolver::default())
+    .run_resolver(&DefinedTermResolver::default())
+    .run_resolver(&TermReferenceResolver::default());
+
+    let articles: Vec<(String, Option<Article>)> = doc.lines()[0]
+        .find(&layered_nlp::x::attr::<Scored<TermReference>>())
+        .into_iter()
+        .map(|found| (found.attr().value.term_name.clone(), found.attr().value.article))
+        .collect();
+    assert_eq!(
+        articles,
+        vec![
+            ("Company".to_string(), Some(Article::Indefinite)),
+            ("Company".to_string(), Some(Article::Definite)),
+            ("Affiliate".to_string(), Some(Article::Indefinite)),
+            ("Affiliate".to_string(), None),
+        ]
+    );
+
+    // "Affiliate" is never used as a specific entity, so "An Affiliate" is fine
+    let found = find_article_inconsistencies(&doc);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].1, "Company");
+}
+
+#[test]
+fn capitalization_inconsistencies_skip_defining_text() {
+    use crate::{find_capitalization_inconsistencies, ContractDocument};
+
+    let doc = ContractDocument::from_text(
+        "\"Fee\" means the annual fee payable under this agreement, being the fee for support.\n\
+         The Customer shall pay the fee.",
+    )
+    .run_resolver(&ContractKeywordResolver::default())
+    .run_resolver(&DefinedTermResolver::default());
+
+    let found = find_capitalization_inconsistencies(&doc);
+    let found: Vec<(usize, usize)> =
+        found.iter().map(|(span, _)| (span.start.line, span.start.token)).collect();
+    // Only the use on line 1 is flagged, not the defining text after `means`
+    assert_eq!(found, vec![(1, 10)]);
+}
+
+#[test]
+fn common_word_terms_link_only_exact_mid_sentence_uses() {
+    let line = create_line_from_string(
+        r#""Control" means ownership of a majority. Control passes to the Buyer, who shall have control of the plant and exercise Control."#,
+    )
+    .run(&ContractKeywordResolver::default())
+    .run(&DefinedTermResolver::default())
+    .run(&TermReferenceResolver::default());
+
+    let refs = line.find(&layered_nlp::x::attr::<Scored<TermReference>>());
+    // Neither the sentence-initial "Control" nor the lowercase "control"
+    assert_eq!(refs.len(), 1);
+    assert_eq!(refs[0].attr().value.term_name, "Control");
+}
+
+#[test]
+fn risky_definitions_report_common_word_terms() {
+    use crate::ContractDocument;
+
+    let doc = ContractDocument::from_text(
+        "\"Business Day\" means a day on which banks are open.\n\
+         ABC Corp (the \"Company\") shall pay the Fees.",
+    )
+    .run_resolver(&ContractKeywordResolver::default())
+    .run_resolver(&DefinedTermResolver::default());
+
+    let resolver = TermReferenceResolver::new();
+    let risky: Vec<_> =
+        resolver.risky_definitions(&doc).into_iter().map(|(_, term)| term).collect();
+    assert_eq!(risky, vec!["Business Day"]);
+    assert!(resolver.is_common_word_term("including"));
+    assert!(!resolver.is_common_word_term("Company"));
+    assert!(resolver.clone().with_common_word("Company").is_common_word_term("company"));
+}
+
+#[test]
+fn plural_and_possessive_references_link_to_singular_term() {
+    let line = create_line_from_string(
+        r#"ABC Corp (the "Party") and XYZ Inc. The Parties agree that the Party's rights survive."#,
+    )
+    .run(&ContractKeywordResolver::default())
+    .run(&DefinedTermResolver::default())
+    .run(&TermReferenceResolver::default());
+
+    let refs = line.find(&layered_nlp::x::attr::<Scored<TermReference>>());
+    let names: Vec<_> = refs.iter().map(|found| found.attr().value.term_name.as_str()).collect();
+    // "Parties" and "Party's"
+    assert_eq!(names, vec!["Party", "Party"]);
+}
+
+#[test]
+fn nested_terms_record_the_qualifier() {
+    let line = create_line_from_string(
+        "\"Confidential Information\" means secret data. \"Disclosing Party\" means the discloser. \
+         The Recipient shall protect the Confidential Information of the Disclosing Party \
+         and the Confidential Information of others.",
+    )
+    .run(&ContractKeywordResolver::default())
+    .run(&DefinedTermResolver::default())
+    .run(&TermReferenceResolver::default());
+
+    let references: Vec<(String, Option<String>, String)> = line
+        .find(&layered_nlp::x::attr::<Scored<TermReference>>())
+        .into_iter()
+        .map(|found| {
+            let (start, end) = found.token_range();
+            let text: String = line.ll_tokens()[start..=end]
+                .iter()
+                .map(|token| match token.get_token() {
+                    layered_nlp::LToken::Text(text, _) => text.as_str(),
+                    layered_nlp::LToken::Value => "",
+                })
+                .collect();
+            let reference = &found.attr().value;
+            (reference.term_name.clone(), reference.qualifier.clone(), text)
+        })
+        .collect();
+
+    assert_eq!(
+        references,
+        vec![
+            (
+                "Confidential Information".to_string(),
+                Some("Disclosing Party".to_string()),
+                "Confidential Information of the Disclosing Party".to_string()
+            ),
+            ("Disclosing Party".to_string(), None, "Disclosing Party".to_string()),
+            // "others" is not a defined term
+            ("Confidential Information".to_string(), None, "Confidential Information".to_string()),
+        ]
+    );
+}
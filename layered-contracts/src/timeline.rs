@@ -0,0 +1,342 @@
+//! A contract's temporal expressions placed on a timeline.
+//!
+//! ```text
+//! This Agreement is effective as of January 1, 2024 (the "Effective Date").
+//! The Vendor shall deliver the goods within thirty (30) days after the Effective Date.
+//! The Buyer shall pay the invoice by December 31, 2024.
+//! The Vendor shall provide support upon termination.
+//!
+//! 2024-01-01  January 1, 2024
+//! 2024-01-31  within thirty (30) days   (The Vendor shall deliver ...)
+//! 2024-12-31  December 31, 2024         (The Buyer shall pay ...)
+//! unanchored  upon termination          (The Vendor shall provide ...)
+//! ```
+//!
+//! [`TimelineExt::timeline`] places every [`TemporalExpression`] of a document
+//! at a calendar date, or at an offset from the Effective Date when no date
+//! anchors it, and labels it with the obligation it times. Expressions that
+//! cannot be placed go to a separate list with the reason.
+//!
+//! Requires that `TemporalExpressionResolver` and, for labels,
+//! `ObligationPhraseResolver` have been run on the document.
+
+use std::cmp::Ordering;
+
+use chrono::NaiveDate;
+use layered_nlp::x;
+
+use crate::deadline::{add_duration, to_date, DeadlineTracker};
+use crate::obligation::ObligationPhrase;
+use crate::temporal::{
+    DeadlineType, DurationUnit, NormalizedTiming, TemporalConverter, TemporalExpression,
+    TemporalType, TimeUnit,
+};
+use crate::{ContractDocument, DocSpan, Scored, UndeterminedReason};
+
+/// Where a [`TimelineEvent`] falls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimelinePosition {
+    /// A calendar date
+    Date(NaiveDate),
+    /// A duration after the Effective Date, when its date is unknown
+    AfterEffectiveDate { value: u32, unit: DurationUnit },
+    /// Not placeable on the timeline
+    Unanchored(UndeterminedReason),
+}
+
+/// A temporal expression placed on the timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEvent {
+    /// Span of the temporal expression
+    pub span: DocSpan,
+    /// Text of the temporal expression ("within thirty (30) days")
+    pub text: String,
+    pub temporal_type: TemporalType,
+    pub position: TimelinePosition,
+    /// What happens at this point: the action of the obligation the
+    /// expression times, or else the event or defined date it names
+    pub label: Option<String>,
+    /// Span of the obligation the expression times
+    pub obligation: Option<DocSpan>,
+}
+
+/// A document's temporal expressions, split into placed and unplaced events.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Timeline {
+    /// The Effective Date relative positions were resolved against
+    pub anchor: Option<NaiveDate>,
+    /// Placed events: calendar dates in order, then offsets from the
+    /// Effective Date from nearest to furthest
+    pub events: Vec<TimelineEvent>,
+    /// Vague or unanchored events, in document order
+    pub unanchored: Vec<TimelineEvent>,
+}
+
+/// Builds the timeline of a contract document.
+pub trait TimelineExt {
+    /// Place every temporal expression on a timeline.
+    ///
+    /// `anchor` is the Effective Date; when `None` it is detected in the
+    /// document as [`DeadlineTracker`] does. Without either, durations after
+    /// the Effective Date keep a relative position.
+    fn timeline(&self, anchor: Option<NaiveDate>) -> Timeline;
+}
+
+impl TimelineExt for ContractDocument {
+    fn timeline(&self, anchor: Option<NaiveDate>) -> Timeline {
+        let anchor = anchor.or_else(|| DeadlineTracker::detect_effective_date(self));
+        let converter = TemporalConverter::new();
+        let mut timeline = Timeline {
+            anchor,
+            ..Timeline::default()
+        };
+
+        for (line_idx, line) in self.lines_enumerated() {
+            let mut obligations: Vec<((usize, usize), String)> = line
+                .find(&x::attr::<Scored<ObligationPhrase>>())
+                .into_iter()
+                .map(|found| (found.token_range(), found.attr().value.action.clone()))
+                .collect();
+            obligations.sort_by_key(|((start, _), _)| *start);
+
+            let found: Vec<((usize, usize), TemporalExpression)> = line
+                .find(&x::attr::<TemporalExpression>())
+                .into_iter()
+                .map(|found| (found.token_range(), (*found.attr()).clone()))
+                .collect();
+            // Only outermost expressions: "thirty (30) days" is part of
+            // "within thirty (30) days"
+            let mut temporals: Vec<&((usize, usize), TemporalExpression)> = found
+                .iter()
+                .filter(|((start, end), _)| {
+                    !found.iter().any(|((other_start, other_end), _)| {
+                        other_start <= start
+                            && end <= other_end
+                            && (other_start, other_end) != (start, end)
+                    })
+                })
+                .collect();
+            temporals.sort_by_key(|((start, _), _)| *start);
+
+            // The obligation whose window, up to the next obligation, holds `token`
+            let obligation_at = |token: usize| {
+                obligations.iter().rev().find(|((start, _), _)| *start <= token)
+            };
+
+            for (idx, ((start, end), expr)) in temporals.iter().enumerate() {
+                let obligation = obligation_at(*start);
+                let same_obligation = |other: usize| {
+                    obligation_at(other).map(|(range, _)| range) == obligation.map(|(range, _)| range)
+                };
+                // "after the Effective Date" anchors the duration before it
+                let anchors_previous = idx > 0
+                    && is_effective_date(&expr.temporal_type)
+                    && is_duration(&temporals[idx - 1].1.temporal_type)
+                    && same_obligation(temporals[idx - 1].0 .0);
+                if anchors_previous {
+                    continue;
+                }
+                let anchored_to_effective = temporals[idx + 1..].iter().any(|((next, _), next_expr)| {
+                    is_effective_date(&next_expr.temporal_type) && same_obligation(*next)
+                });
+
+                let position = if converter.is_vague(&expr.text) {
+                    TimelinePosition::Unanchored(UndeterminedReason::Vague)
+                } else {
+                    place(&expr.temporal_type, anchor, anchored_to_effective)
+                };
+                let label = match (obligation, &expr.temporal_type) {
+                    (Some((_, action)), _) => Some(action.clone()),
+                    (None, TemporalType::RelativeTime { trigger, .. }) => Some(trigger.clone()),
+                    (None, TemporalType::DefinedDate { term }) => Some(term.clone()),
+                    (None, _) => None,
+                };
+                let event = TimelineEvent {
+                    span: DocSpan::single_line(line_idx, *start, *end),
+                    text: expr.text.clone(),
+                    temporal_type: expr.temporal_type.clone(),
+                    position,
+                    label,
+                    obligation: obligation
+                        .map(|((o_start, o_end), _)| DocSpan::single_line(line_idx, *o_start, *o_end)),
+                };
+                match position {
+                    TimelinePosition::Unanchored(_) => timeline.unanchored.push(event),
+                    _ => timeline.events.push(event),
+                }
+            }
+        }
+
+        // Stable: simultaneous events stay in document order
+        timeline.events.sort_by(|a, b| compare_positions(&a.position, &b.position));
+        timeline
+    }
+}
+
+fn is_effective_date(temporal_type: &TemporalType) -> bool {
+    matches!(temporal_type, TemporalType::DefinedDate { term }
+        if term.to_lowercase().contains("effective date"))
+}
+
+fn is_duration(temporal_type: &TemporalType) -> bool {
+    match temporal_type {
+        TemporalType::Duration { .. } => true,
+        TemporalType::Deadline { reference, .. } => is_duration(reference),
+        _ => false,
+    }
+}
+
+/// Place one expression. Durations are placed only when an Effective Date
+/// follows them ("within 30 days after the Effective Date").
+fn place(
+    temporal_type: &TemporalType,
+    anchor: Option<NaiveDate>,
+    anchored_to_effective: bool,
+) -> TimelinePosition {
+    match temporal_type {
+        TemporalType::Date { year, month, day } => match to_date(*year, *month, *day) {
+            Some(date) => TimelinePosition::Date(date),
+            None => TimelinePosition::Unanchored(UndeterminedReason::IncompleteDate),
+        },
+        TemporalType::Duration { value, unit, .. } if anchored_to_effective => {
+            match anchor.map(|anchor| add_duration(anchor, *value, *unit)) {
+                Some(Some(date)) => TimelinePosition::Date(date),
+                Some(None) => TimelinePosition::Unanchored(UndeterminedReason::Unanchored),
+                None => TimelinePosition::AfterEffectiveDate { value: *value, unit: *unit },
+            }
+        }
+        TemporalType::Deadline { deadline_type: DeadlineType::PromptlyFollowing, .. } => {
+            TimelinePosition::Unanchored(UndeterminedReason::Vague)
+        }
+        TemporalType::Deadline { reference, .. } => place(reference, anchor, anchored_to_effective),
+        TemporalType::DefinedDate { .. } if is_effective_date(temporal_type) => match anchor {
+            Some(date) => TimelinePosition::Date(date),
+            None => TimelinePosition::AfterEffectiveDate { value: 0, unit: DurationUnit::Days },
+        },
+        TemporalType::Duration { .. }
+        | TemporalType::DefinedDate { .. }
+        | TemporalType::RelativeTime { .. } => {
+            TimelinePosition::Unanchored(UndeterminedReason::Unanchored)
+        }
+    }
+}
+
+/// Dates in order, then offsets from the Effective Date by approximate length.
+fn compare_positions(a: &TimelinePosition, b: &TimelinePosition) -> Ordering {
+    let offset_days =
+        |value: u32, unit: DurationUnit| NormalizedTiming::new(value as f64, TimeUnit::from(unit), false).to_approx_days();
+    match (a, b) {
+        (TimelinePosition::Date(a), TimelinePosition::Date(b)) => a.cmp(b),
+        (TimelinePosition::Date(_), _) => Ordering::Less,
+        (_, TimelinePosition::Date(_)) => Ordering::Greater,
+        (
+            TimelinePosition::AfterEffectiveDate { value: a_value, unit: a_unit },
+            TimelinePosition::AfterEffectiveDate { value: b_value, unit: b_unit },
+        ) => offset_days(*a_value, *a_unit)
+            .partial_cmp(&offset_days(*b_value, *b_unit))
+            .unwrap_or(Ordering::Equal),
+        _ => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver,
+        ProhibitionResolver, PronounResolver, TemporalExpressionResolver, TermReferenceResolver,
+    };
+    use layered_part_of_speech::POSTagResolver;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn analyze(text: &str) -> ContractDocument {
+        ContractDocument::from_text(text)
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ProhibitionResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+            .run_resolver(&TermReferenceResolver::default())
+            .run_resolver(&PronounResolver::default())
+            .run_resolver(&TemporalExpressionResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default())
+    }
+
+    fn summary(events: &[TimelineEvent]) -> Vec<(&str, TimelinePosition, Option<&str>)> {
+        events
+            .iter()
+            .map(|event| (event.text.as_str(), event.position, event.label.as_deref()))
+            .collect()
+    }
+
+    const CONTRACT: &str = "The Buyer shall pay the invoice by December 31, 2024.\n\
+        This Agreement is effective as of January 1, 2024 (the \"Effective Date\").\n\
+        The Vendor shall deliver the goods within thirty (30) days after the Effective Date.\n\
+        The Vendor shall provide support upon termination.";
+
+    #[test]
+    fn test_events_are_sorted_by_date() {
+        let timeline = analyze(CONTRACT).timeline(None);
+        assert_eq!(timeline.anchor, Some(date(2024, 1, 1)));
+        assert_eq!(
+            summary(&timeline.events),
+            vec![
+                ("January 1, 2024", TimelinePosition::Date(date(2024, 1, 1)), None),
+                (
+                    "within thirty (30) days",
+                    TimelinePosition::Date(date(2024, 1, 31)),
+                    Some("deliver the goods within thirty days after the Effective Date")
+                ),
+                (
+                    "December 31, 2024",
+                    TimelinePosition::Date(date(2024, 12, 31)),
+                    Some("pay the invoice by December")
+                ),
+            ]
+        );
+        assert_eq!(timeline.events[1].obligation.map(|span| span.start.line), Some(2));
+    }
+
+    #[test]
+    fn test_unplaceable_events_are_kept_apart() {
+        let timeline = analyze(CONTRACT).timeline(None);
+        assert_eq!(
+            summary(&timeline.unanchored),
+            vec![(
+                "upon termination",
+                TimelinePosition::Unanchored(UndeterminedReason::Unanchored),
+                Some("provide support")
+            )]
+        );
+    }
+
+    #[test]
+    fn test_relative_positions_without_an_effective_date() {
+        let doc = analyze(
+            "The Vendor shall deliver the goods within two (2) months after the Effective Date.\n\
+             The Vendor shall install them within ten (10) days after the Effective Date.",
+        );
+        let timeline = doc.timeline(None);
+        assert_eq!(timeline.anchor, None);
+        let positions: Vec<_> = timeline.events.iter().map(|event| event.position).collect();
+        assert_eq!(
+            positions,
+            vec![
+                TimelinePosition::AfterEffectiveDate { value: 10, unit: DurationUnit::Days },
+                TimelinePosition::AfterEffectiveDate { value: 2, unit: DurationUnit::Months },
+            ]
+        );
+
+        let anchored = doc.timeline(Some(date(2024, 3, 1)));
+        let dates: Vec<_> = anchored.events.iter().map(|event| event.position).collect();
+        assert_eq!(
+            dates,
+            vec![
+                TimelinePosition::Date(date(2024, 3, 11)),
+                TimelinePosition::Date(date(2024, 5, 1)),
+            ]
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! Shared helpers used across contract analysis layers.
 
+use layered_nlp::{LLLine, LToken, TextTag};
+
 /// Normalize party/beneficiary names by trimming punctuation, lowercasing, and
 /// removing leading articles so we can compare display texts consistently.
 pub(crate) fn normalize_party_name(name: &str) -> String {
@@ -78,3 +80,27 @@ pub(crate) fn parse_roman(text: &str) -> Option<(u32, bool)> {
 
     Some((total, uppercase))
 }
+
+/// Non-whitespace tokens of a line: (token index, text, is word).
+pub(crate) fn content_tokens(line: &LLLine) -> Vec<(usize, &str, bool)> {
+    line.ll_tokens()
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, token)| match token.get_token() {
+            LToken::Text(_, TextTag::SPACE) => None,
+            LToken::Text(text, tag) => Some((idx, text.as_str(), *tag == TextTag::WORD)),
+            LToken::Value => None,
+        })
+        .collect()
+}
+
+/// Text of a line, concatenating its token texts.
+pub(crate) fn line_text(line: &LLLine) -> String {
+    line.ll_tokens()
+        .iter()
+        .filter_map(|token| match token.get_token() {
+            LToken::Text(text, _) => Some(text.as_str()),
+            LToken::Value => None,
+        })
+        .collect()
+}
@@ -0,0 +1,352 @@
+//! Tracing sections across many versions of a contract.
+//!
+//! [`DocumentAligner`] and [`SemanticDiffEngine`] compare two versions.
+//! [`VersionChain`] runs them on each consecutive pair (v1→v2, v2→v3, ...)
+//! and follows every section through the chained alignments, so each one gets
+//! a timeline of where it was introduced, changed and removed.
+//!
+//! ```ignore
+//! let history = VersionChain::new().trace(&[draft_1, draft_2, draft_3]);
+//! for event in &history.section("SECTION:3.1").unwrap().timeline {
+//!     println!("v{}: {:?}", event.version + 1, event.change);
+//! }
+//! ```
+//!
+//! Each version must have been processed as for [`SemanticDiffEngine`]
+//! (`SectionHeaderResolver`, `ObligationPhraseResolver`, ...).
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::document_aligner::{AlignmentType, DocumentAligner, SectionRef};
+use crate::document_structure::{DocumentStructureExt, SectionNode};
+use crate::semantic_diff::{
+    ConditionChange, SemanticChange, SemanticChangeType, SemanticDiffEngine, SemanticDiffResult,
+};
+use crate::ContractDocument;
+
+/// How a section changed in a version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VersionChange {
+    /// First appears in this version
+    Introduced,
+    /// Content or obligations changed
+    Modified,
+    /// Identifier changed, content the same
+    Renumbered { from: String },
+    /// Same content in a different position in the hierarchy
+    Moved,
+    /// Split into several sections
+    Split,
+    /// Merged with other sections
+    Merged,
+    /// No longer present in this version
+    Removed,
+}
+
+/// One entry in a section's timeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionEvent {
+    /// Index of the version in the chain (0 = first)
+    pub version: usize,
+    /// What happened to the section
+    pub change: VersionChange,
+    /// The section's identifier in this version (its last one, if removed)
+    pub canonical_id: String,
+    /// The section's title in this version
+    pub title: Option<String>,
+    /// Obligation changes inside the section from the previous version
+    pub obligation_changes: Vec<SemanticChange>,
+}
+
+/// A section followed through every version of the chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedSection {
+    /// Identifier in the latest version containing the section
+    pub canonical_id: String,
+    /// Changes in version order; versions without changes are omitted
+    pub timeline: Vec<VersionEvent>,
+}
+
+impl TrackedSection {
+    /// The version the section first appears in.
+    pub fn introduced_in(&self) -> usize {
+        self.timeline.first().map_or(0, |event| event.version)
+    }
+
+    /// The version the section was removed in, if it was.
+    pub fn removed_in(&self) -> Option<usize> {
+        self.timeline
+            .iter()
+            .find(|event| event.change == VersionChange::Removed)
+            .map(|event| event.version)
+    }
+
+    fn record(&mut self, version: usize, change: VersionChange, section: &SectionRef) {
+        self.canonical_id = section.canonical_id.clone();
+        self.timeline.push(VersionEvent {
+            version,
+            change,
+            canonical_id: section.canonical_id.clone(),
+            title: section.title.clone(),
+            obligation_changes: Vec::new(),
+        });
+    }
+}
+
+/// The history of a chain of versions, from [`VersionChain::trace`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VersionHistory {
+    /// Number of versions traced
+    pub version_count: usize,
+    /// Every section of every version, in order of first appearance
+    pub sections: Vec<TrackedSection>,
+    /// Semantic diff of each consecutive pair: `diffs[i]` compares version
+    /// `i` with version `i + 1`
+    pub diffs: Vec<SemanticDiffResult>,
+}
+
+impl VersionHistory {
+    /// The section with `canonical_id` in any version, preferring the latest.
+    pub fn section(&self, canonical_id: &str) -> Option<&TrackedSection> {
+        self.sections
+            .iter()
+            .filter(|section| {
+                section.timeline.iter().any(|event| event.canonical_id == canonical_id)
+            })
+            .max_by_key(|section| {
+                section
+                    .timeline
+                    .iter()
+                    .rev()
+                    .find(|event| event.canonical_id == canonical_id)
+                    .map(|event| event.version)
+            })
+    }
+}
+
+/// Chains pairwise alignments into a multi-version history.
+#[derive(Default)]
+pub struct VersionChain {
+    aligner: DocumentAligner,
+    diff_engine: SemanticDiffEngine,
+}
+
+impl VersionChain {
+    /// Create a chain with the default aligner and diff engine.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Align consecutive versions with `aligner`.
+    pub fn with_aligner(mut self, aligner: DocumentAligner) -> Self {
+        self.aligner = aligner;
+        self
+    }
+
+    /// Diff consecutive versions with `diff_engine`.
+    pub fn with_diff_engine(mut self, diff_engine: SemanticDiffEngine) -> Self {
+        self.diff_engine = diff_engine;
+        self
+    }
+
+    /// Trace every section through `versions`, oldest first.
+    pub fn trace(&self, versions: &[ContractDocument]) -> VersionHistory {
+        let mut history = VersionHistory {
+            version_count: versions.len(),
+            ..VersionHistory::default()
+        };
+        let first = match versions.first() {
+            Some(first) => first,
+            None => return history,
+        };
+
+        // Canonical id in the current version -> index into `history.sections`
+        let mut live: HashMap<String, usize> = HashMap::new();
+        for node in first.structure().flatten() {
+            let section = SectionRef::from_node(node);
+            live.insert(section.canonical_id.clone(), history.sections.len());
+            history.sections.push(Self::introduce(0, &section));
+        }
+
+        for (version, pair) in versions.windows(2).enumerate().map(|(i, pair)| (i + 1, pair)) {
+            let (original, revised) = (&pair[0], &pair[1]);
+            let revised_sections = revised.structure().flatten();
+            let alignment = self.aligner.align(
+                original.structure(),
+                revised.structure(),
+                original,
+                revised,
+            );
+            let diff = self.diff_engine.compute_diff(&alignment, original, revised);
+
+            let mut next_live: HashMap<String, usize> = HashMap::new();
+            for pair in &alignment.alignments {
+                let tracked: Vec<usize> = pair
+                    .original
+                    .iter()
+                    .filter_map(|section| live.get(&section.canonical_id))
+                    .copied()
+                    .collect();
+                match (pair.alignment_type, pair.revised.first()) {
+                    (AlignmentType::Deleted, _) => {
+                        for section in &pair.original {
+                            if let Some(&idx) = live.get(&section.canonical_id) {
+                                let removed = VersionChange::Removed;
+                                history.sections[idx].record(version, removed, section);
+                            }
+                        }
+                    }
+                    (AlignmentType::Inserted, _) => {
+                        for section in &pair.revised {
+                            next_live.insert(section.canonical_id.clone(), history.sections.len());
+                            history.sections.push(Self::introduce(version, section));
+                        }
+                    }
+                    (alignment_type, Some(revised_section)) => {
+                        let change = match alignment_type {
+                            AlignmentType::Renumbered => Some(VersionChange::Renumbered {
+                                from: pair.original[0].canonical_id.clone(),
+                            }),
+                            AlignmentType::Moved => Some(VersionChange::Moved),
+                            AlignmentType::Modified => Some(VersionChange::Modified),
+                            AlignmentType::Split => Some(VersionChange::Split),
+                            AlignmentType::Merged => Some(VersionChange::Merged),
+                            _ => None,
+                        };
+                        if let Some(change) = change {
+                            for &idx in &tracked {
+                                let section = &mut history.sections[idx];
+                                section.record(version, change.clone(), revised_section);
+                            }
+                        }
+                        // Split sections continue the original; merged ones the first
+                        if let Some(&idx) = tracked.first() {
+                            for section in &pair.revised {
+                                next_live.insert(section.canonical_id.clone(), idx);
+                            }
+                        }
+                    }
+                    (_, None) => {}
+                }
+            }
+
+            // Obligation changes mark their enclosing section as modified
+            for change in &diff.changes {
+                let node = match obligation_line(change)
+                    .and_then(|line| innermost_section(&revised_sections, line))
+                {
+                    Some(node) => node,
+                    None => continue,
+                };
+                let section = match next_live.get(&node.header.identifier.canonical()) {
+                    Some(&idx) => &mut history.sections[idx],
+                    None => continue,
+                };
+                let last_version = section.timeline.last().map(|event| event.version);
+                if last_version != Some(version) {
+                    section.record(version, VersionChange::Modified, &SectionRef::from_node(node));
+                }
+                if let Some(event) = section.timeline.last_mut() {
+                    event.obligation_changes.push(change.clone());
+                }
+            }
+
+            live = next_live;
+            history.diffs.push(diff);
+        }
+
+        history
+    }
+
+    fn introduce(version: usize, section: &SectionRef) -> TrackedSection {
+        let mut tracked = TrackedSection {
+            canonical_id: section.canonical_id.clone(),
+            timeline: Vec::new(),
+        };
+        tracked.record(version, VersionChange::Introduced, section);
+        tracked
+    }
+}
+
+/// Line of the revised obligation an obligation-level change is about.
+fn obligation_line(change: &SemanticChange) -> Option<usize> {
+    let span = match &change.change_type {
+        SemanticChangeType::ObligationModal(modal) => modal.after_span,
+        SemanticChangeType::ObligationCondition(
+            ConditionChange::Added { after_span, .. }
+            | ConditionChange::Removed { after_span, .. }
+            | ConditionChange::Modified { after_span, .. },
+        ) => *after_span,
+        _ => return None,
+    };
+    Some(span.start.line)
+}
+
+/// The deepest section containing `line`.
+fn innermost_section<'a>(sections: &[&'a SectionNode], line: usize) -> Option<&'a SectionNode> {
+    sections
+        .iter()
+        .filter(|s| s.start_line <= line && line < s.end_line.unwrap_or(usize::MAX))
+        .max_by_key(|s| s.start_line)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver,
+        SectionHeaderResolver, TermReferenceResolver,
+    };
+    use layered_part_of_speech::POSTagResolver;
+
+    fn process(text: &str) -> ContractDocument {
+        ContractDocument::from_text(text)
+            .run_resolver(&SectionHeaderResolver::new())
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::new())
+            .run_resolver(&DefinedTermResolver::new())
+            .run_resolver(&TermReferenceResolver::new())
+            .run_resolver(&ObligationPhraseResolver::new())
+    }
+
+    #[test]
+    fn test_sections_traced_across_three_versions() {
+        let v1 = process(
+            "Section 1.1 Payment\nThe Company shall pay the Fees within thirty days.\n\
+             Section 1.2 Delivery\nThe Supplier shall deliver the goods to the warehouse.",
+        );
+        let v2 = process(
+            "Section 1.1 Payment\nThe Company may pay the Fees within thirty days.\n\
+             Section 1.2 Delivery\nThe Supplier shall deliver the goods to the warehouse.\n\
+             Section 1.3 Audit\nThe Auditor shall review the books and records annually.",
+        );
+        let v3 = process(
+            "Section 1.1 Payment\nThe Company may pay the Fees within thirty days.\n\
+             Section 1.3 Audit\nThe Auditor shall review the books and records annually.",
+        );
+
+        let history = VersionChain::new().trace(&[v1, v2, v3]);
+        assert_eq!(history.version_count, 3);
+        assert_eq!(history.diffs.len(), 2);
+
+        let payment = history.section("SECTION:1.1").unwrap();
+        let changes: Vec<_> =
+            payment.timeline.iter().map(|e| (e.version, e.change.clone())).collect();
+        assert_eq!(
+            changes,
+            vec![(0, VersionChange::Introduced), (1, VersionChange::Modified)]
+        );
+        assert!(!payment.timeline[1].obligation_changes.is_empty());
+
+        let delivery = history.section("SECTION:1.2").unwrap();
+        assert_eq!(delivery.introduced_in(), 0);
+        assert_eq!(delivery.removed_in(), Some(2));
+
+        let audit = history.section("SECTION:1.3").unwrap();
+        assert_eq!(audit.introduced_in(), 1);
+        assert_eq!(audit.removed_in(), None);
+    }
+}
@@ -0,0 +1,444 @@
+//! Obligation workflows: which obligations must happen before which.
+//!
+//! [`ObligationWorkflowExt::obligation_workflow`] turns a contract into a DAG
+//! of its obligations. Each node carries the obligation, its conditions and its
+//! deadline; an edge runs from an upstream obligation to one whose trigger
+//! ("after delivery of the Goods", "upon payment", "if the Vendor delivers")
+//! names the upstream action. Circular dependencies are reported as
+//! [`WorkflowError::Cycle`] instead of being silently dropped.
+//!
+//! Requires that `ObligationPhraseResolver` and `TemporalExpressionResolver`
+//! have been run on the document.
+
+use std::collections::VecDeque;
+
+use layered_nlp::x;
+
+use crate::deadline::{DeadlineTracker, ObligationDeadline, UndeterminedDeadline};
+use crate::obligation::{ConditionRef, ObligationPhrase};
+use crate::temporal::{TemporalExpression, TemporalType, TimeRelation};
+use crate::{ContractDocument, ContractKeyword, DocSpan, Scored};
+
+/// Words in an action that introduce the event it waits for.
+const TRIGGER_CUES: &[&str] = &["after", "upon", "following", "once"];
+
+/// Deadline attached to a workflow node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkflowDeadline {
+    /// The deadline resolved to a calendar date
+    Resolved(ObligationDeadline),
+    /// The obligation has timing that could not be resolved to a date
+    Undetermined(UndeterminedDeadline),
+}
+
+/// One obligation in the workflow, keyed by its span.
+#[derive(Debug, Clone)]
+pub struct WorkflowNode {
+    /// Span of the obligation phrase
+    pub span: DocSpan,
+    /// The obligation itself
+    pub obligation: Scored<ObligationPhrase>,
+    /// Deadline, if the obligation carries any timing
+    pub deadline: Option<WorkflowDeadline>,
+    /// Event descriptions the obligation waits for, used to find upstream obligations
+    pub triggers: Vec<String>,
+}
+
+impl WorkflowNode {
+    /// Conditions that qualify this obligation.
+    pub fn conditions(&self) -> &[ConditionRef] {
+        &self.obligation.value.conditions
+    }
+}
+
+/// A dependency between two obligations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkflowEdge {
+    /// The obligation that must be performed first
+    pub upstream: DocSpan,
+    /// The obligation that waits for it
+    pub downstream: DocSpan,
+    /// The trigger text of the downstream obligation that named the upstream action
+    pub trigger: String,
+}
+
+/// Problems that keep a workflow from being executed as-is.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkflowError {
+    /// Obligations that each (transitively) wait for the other, in document order
+    Cycle { spans: Vec<DocSpan> },
+}
+
+/// Obligations of a contract arranged as a dependency graph.
+#[derive(Debug, Clone, Default)]
+pub struct Workflow {
+    /// Nodes in document order
+    pub nodes: Vec<WorkflowNode>,
+    /// Dependencies between nodes
+    pub edges: Vec<WorkflowEdge>,
+    /// Cycles found among the dependencies
+    pub errors: Vec<WorkflowError>,
+}
+
+impl Workflow {
+    /// Look up the node for an obligation span.
+    pub fn node(&self, span: &DocSpan) -> Option<&WorkflowNode> {
+        self.nodes.iter().find(|node| node.span == *span)
+    }
+
+    /// Obligations that must be performed before the one at `span`.
+    pub fn upstream(&self, span: &DocSpan) -> Vec<&WorkflowNode> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.downstream == *span)
+            .filter_map(|edge| self.node(&edge.upstream))
+            .collect()
+    }
+
+    /// Obligations that wait for the one at `span`.
+    pub fn downstream(&self, span: &DocSpan) -> Vec<&WorkflowNode> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.upstream == *span)
+            .filter_map(|edge| self.node(&edge.downstream))
+            .collect()
+    }
+
+    /// Obligations that do not wait for any other obligation.
+    pub fn roots(&self) -> Vec<&WorkflowNode> {
+        self.nodes
+            .iter()
+            .filter(|node| !self.edges.iter().any(|edge| edge.downstream == node.span))
+            .collect()
+    }
+
+    /// Returns true if the dependencies contain no cycles.
+    pub fn is_acyclic(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Node spans ordered so every obligation comes after its upstream ones.
+    ///
+    /// Ties keep document order. Returns `None` if the workflow has a cycle.
+    pub fn topological_order(&self) -> Option<Vec<DocSpan>> {
+        let edges = self.edge_indices();
+        let mut in_degree = vec![0usize; self.nodes.len()];
+        for &(_, to) in &edges {
+            in_degree[to] += 1;
+        }
+
+        let mut ready: VecDeque<usize> =
+            (0..self.nodes.len()).filter(|&idx| in_degree[idx] == 0).collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(idx) = ready.pop_front() {
+            order.push(self.nodes[idx].span);
+            for &(from, to) in &edges {
+                if from == idx {
+                    in_degree[to] -= 1;
+                    if in_degree[to] == 0 {
+                        ready.push_back(to);
+                    }
+                }
+            }
+        }
+
+        (order.len() == self.nodes.len()).then_some(order)
+    }
+
+    fn edge_indices(&self) -> Vec<(usize, usize)> {
+        let index_of = |span: &DocSpan| self.nodes.iter().position(|node| node.span == *span);
+        self.edges
+            .iter()
+            .filter_map(|edge| Some((index_of(&edge.upstream)?, index_of(&edge.downstream)?)))
+            .collect()
+    }
+
+    /// Strongly connected components with more than one node, via Tarjan's algorithm.
+    fn cycles(&self) -> Vec<WorkflowError> {
+        struct Tarjan<'a> {
+            adjacency: Vec<Vec<usize>>,
+            nodes: &'a [WorkflowNode],
+            index: Vec<Option<usize>>,
+            low: Vec<usize>,
+            on_stack: Vec<bool>,
+            stack: Vec<usize>,
+            next_index: usize,
+            cycles: Vec<WorkflowError>,
+        }
+
+        impl Tarjan<'_> {
+            fn visit(&mut self, v: usize) {
+                self.index[v] = Some(self.next_index);
+                self.low[v] = self.next_index;
+                self.next_index += 1;
+                self.stack.push(v);
+                self.on_stack[v] = true;
+
+                for w in self.adjacency[v].clone() {
+                    match self.index[w] {
+                        None => {
+                            self.visit(w);
+                            self.low[v] = self.low[v].min(self.low[w]);
+                        }
+                        Some(w_index) if self.on_stack[w] => {
+                            self.low[v] = self.low[v].min(w_index);
+                        }
+                        Some(_) => {}
+                    }
+                }
+
+                if Some(self.low[v]) == self.index[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = self.stack.pop() {
+                        self.on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    if component.len() > 1 {
+                        component.sort_unstable();
+                        let spans = component.iter().map(|&idx| self.nodes[idx].span).collect();
+                        self.cycles.push(WorkflowError::Cycle { spans });
+                    }
+                }
+            }
+        }
+
+        let mut adjacency = vec![Vec::new(); self.nodes.len()];
+        for (from, to) in self.edge_indices() {
+            adjacency[from].push(to);
+        }
+        let count = self.nodes.len();
+        let mut tarjan = Tarjan {
+            adjacency,
+            nodes: &self.nodes,
+            index: vec![None; count],
+            low: vec![0; count],
+            on_stack: vec![false; count],
+            stack: Vec::new(),
+            next_index: 0,
+            cycles: Vec::new(),
+        };
+        for v in 0..count {
+            if tarjan.index[v].is_none() {
+                tarjan.visit(v);
+            }
+        }
+        tarjan.cycles
+    }
+}
+
+/// Builds the obligation workflow of a contract document.
+pub trait ObligationWorkflowExt {
+    /// Assemble obligations, their conditions, dependencies and deadlines into a DAG.
+    fn obligation_workflow(&self) -> Workflow;
+}
+
+impl ObligationWorkflowExt for ContractDocument {
+    fn obligation_workflow(&self) -> Workflow {
+        let tracker = DeadlineTracker::from_document(self);
+        let mut workflow = Workflow::default();
+
+        for (line_idx, line) in self.lines_enumerated() {
+            let mut obligations: Vec<((usize, usize), Scored<ObligationPhrase>)> = line
+                .find(&x::attr::<Scored<ObligationPhrase>>())
+                .into_iter()
+                .map(|found| (found.token_range(), (*found.attr()).clone()))
+                .collect();
+            obligations.sort_by_key(|((start, _), _)| *start);
+
+            let relative_times: Vec<(usize, String)> = line
+                .find(&x::attr::<TemporalExpression>())
+                .into_iter()
+                .filter_map(|found| match &found.attr().temporal_type {
+                    TemporalType::RelativeTime {
+                        trigger,
+                        relation: TimeRelation::Upon | TimeRelation::Following,
+                    } => Some((found.token_range().0, trigger.clone())),
+                    _ => None,
+                })
+                .collect();
+
+            for (idx, ((start, end), obligation)) in obligations.iter().enumerate() {
+                // Leading "Upon X, ..." belongs to the obligation that follows it
+                let window_start = idx.checked_sub(1).map_or(0, |prev| obligations[prev].0 .1 + 1);
+                let window_end = obligations
+                    .get(idx + 1)
+                    .map_or(usize::MAX, |((next_start, _), _)| *next_start);
+
+                let mut triggers = action_triggers(&obligation.value.action);
+                triggers.extend(
+                    obligation
+                        .value
+                        .conditions
+                        .iter()
+                        .filter(|condition| condition.condition_type != ContractKeyword::Unless)
+                        .map(|condition| condition.text_preview.clone()),
+                );
+                for (t_start, trigger) in &relative_times {
+                    let in_window = *t_start >= window_start && *t_start < window_end;
+                    let already_cued = triggers.iter().any(|t| t.contains(trigger.as_str()));
+                    if in_window && !already_cued {
+                        triggers.push(trigger.clone());
+                    }
+                }
+
+                let span = DocSpan::single_line(line_idx, *start, *end);
+                let deadline = tracker
+                    .deadlines()
+                    .iter()
+                    .find(|deadline| deadline.span == span)
+                    .cloned()
+                    .map(WorkflowDeadline::Resolved)
+                    .or_else(|| {
+                        tracker
+                            .undetermined_deadlines()
+                            .iter()
+                            .find(|deadline| deadline.span == span)
+                            .cloned()
+                            .map(WorkflowDeadline::Undetermined)
+                    });
+
+                workflow.nodes.push(WorkflowNode {
+                    span,
+                    obligation: obligation.clone(),
+                    deadline,
+                    triggers,
+                });
+            }
+        }
+
+        for downstream in &workflow.nodes {
+            for upstream in &workflow.nodes {
+                if upstream.span == downstream.span {
+                    continue;
+                }
+                let named = downstream
+                    .triggers
+                    .iter()
+                    .find(|trigger| names_action(trigger, &upstream.obligation.value.action));
+                if let Some(trigger) = named {
+                    workflow.edges.push(WorkflowEdge {
+                        upstream: upstream.span,
+                        downstream: downstream.span,
+                        trigger: trigger.clone(),
+                    });
+                }
+            }
+        }
+
+        workflow.errors = workflow.cycles();
+        workflow
+    }
+}
+
+/// Text following each trigger cue in an action ("after delivery of the Goods").
+fn action_triggers(action: &str) -> Vec<String> {
+    let words: Vec<&str> = action.split_whitespace().collect();
+    words
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| TRIGGER_CUES.contains(&word.to_lowercase().as_str()))
+        .filter(|(idx, _)| idx + 1 < words.len())
+        .map(|(idx, _)| words[idx + 1..].join(" "))
+        .collect()
+}
+
+/// Returns true if `trigger` mentions the head verb of `action`, in any form
+/// ("delivery" for "deliver", "payment" for "pay").
+fn names_action(trigger: &str, action: &str) -> bool {
+    let verb = match action.split_whitespace().next() {
+        Some(verb) => verb.to_lowercase(),
+        None => return false,
+    };
+    let stem = match verb.strip_suffix('e') {
+        Some(stem) if verb.len() > 4 => stem,
+        _ => verb.as_str(),
+    };
+    if stem.len() < 3 {
+        return false;
+    }
+    trigger
+        .split(|c: char| !c.is_alphabetic())
+        .any(|word| word.to_lowercase().starts_with(stem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ContractKeywordResolver, DefinedTermResolver, ObligationPhraseResolver,
+        ProhibitionResolver, PronounResolver, TemporalExpressionResolver, TermReferenceResolver,
+        UndeterminedReason,
+    };
+    use layered_part_of_speech::POSTagResolver;
+
+    fn workflow(text: &str) -> Workflow {
+        ContractDocument::from_text(text)
+            .run_resolver(&POSTagResolver::default())
+            .run_resolver(&ContractKeywordResolver::default())
+            .run_resolver(&ProhibitionResolver::default())
+            .run_resolver(&DefinedTermResolver::default())
+            .run_resolver(&TermReferenceResolver::default())
+            .run_resolver(&PronounResolver::default())
+            .run_resolver(&TemporalExpressionResolver::default())
+            .run_resolver(&ObligationPhraseResolver::default())
+            .obligation_workflow()
+    }
+
+    fn actions(nodes: &[&WorkflowNode]) -> Vec<String> {
+        nodes.iter().map(|node| node.obligation.value.action.clone()).collect()
+    }
+
+    #[test]
+    fn test_workflow_chains_obligations() {
+        let workflow = workflow(
+            r#"ABC Corp (the "Vendor") shall deliver the Goods.
+XYZ Inc (the "Company") shall pay the fees within thirty (30) days after delivery of the Goods.
+The Vendor shall issue a receipt upon payment of the fees."#,
+        );
+        assert_eq!(workflow.nodes.len(), 3);
+        assert!(workflow.is_acyclic());
+
+        let [deliver, pay, receipt] = [0, 1, 2].map(|idx| workflow.nodes[idx].span);
+        assert_eq!(actions(&workflow.roots()), vec!["deliver the Goods"]);
+        assert_eq!(actions(&workflow.upstream(&pay)), vec!["deliver the Goods"]);
+        assert_eq!(workflow.downstream(&pay).len(), 1);
+        assert_eq!(workflow.upstream(&receipt)[0].span, pay);
+        assert_eq!(workflow.topological_order(), Some(vec![deliver, pay, receipt]));
+
+        // Relative deadline without an Effective Date stays undetermined
+        assert!(matches!(
+            &workflow.node(&pay).unwrap().deadline,
+            Some(WorkflowDeadline::Undetermined(d)) if d.reason == UndeterminedReason::Unanchored
+        ));
+        assert!(workflow.node(&deliver).unwrap().deadline.is_none());
+    }
+
+    #[test]
+    fn test_workflow_spans_are_token_indices() {
+        let workflow = workflow(
+            "Upon payment of the fees, ABC Corp (the \"Vendor\") shall deliver the Goods.\n\
+             The Company shall pay the fees.",
+        );
+        // "shall" is token 23 of the first line, after the leading trigger
+        assert_eq!(workflow.nodes[0].span, DocSpan::single_line(0, 23, 23));
+        assert_eq!(workflow.nodes[0].triggers, vec!["payment of the fees"]);
+        assert_eq!(actions(&workflow.roots()), vec!["pay the fees"]);
+    }
+
+    #[test]
+    fn test_workflow_reports_cycles() {
+        let workflow = workflow(
+            "The Vendor shall deliver the Goods after payment of the fees.\n\
+             The Company shall pay the fees after delivery of the Goods.\n\
+             The Company shall inspect the Goods.",
+        );
+        assert_eq!(workflow.nodes.len(), 3);
+        assert!(!workflow.is_acyclic());
+        assert_eq!(workflow.topological_order(), None);
+        let spans = vec![workflow.nodes[0].span, workflow.nodes[1].span];
+        assert_eq!(workflow.errors, vec![WorkflowError::Cycle { spans }]);
+    }
+}
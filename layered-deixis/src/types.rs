@@ -62,7 +62,7 @@ impl DeicticReference {
 }
 
 /// The five major categories of deixis in linguistics.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum DeicticCategory {
     /// Person deixis - reference to participants (I, you, we, they, etc.)
     Person,
@@ -79,7 +79,7 @@ pub enum DeicticCategory {
 }
 
 /// Subcategories provide finer-grained classification within each deictic category.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum DeicticSubcategory {
     // === Person subcategories ===
     /// First person singular: I, me, my, mine, myself
@@ -185,6 +185,8 @@ pub enum DeicticSource {
     TemporalResolver,
     /// Mapped from a SectionReference/RelativeReference resolver
     SectionReferenceResolver,
+    /// From an anaphoric "such"/"said" noun phrase resolver
+    AnaphoricReferenceResolver,
     /// Mapped from POS tag analysis
     POSTag,
     /// Derived/composed from multiple sources
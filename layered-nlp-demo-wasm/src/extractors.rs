@@ -344,6 +344,9 @@ fn format_deictic_source(source: &layered_deixis::DeicticSource) -> serde_json::
         DeicticSource::SectionReferenceResolver => {
             serde_json::json!({ "type": "SectionReferenceResolver" })
         }
+        DeicticSource::AnaphoricReferenceResolver => {
+            serde_json::json!({ "type": "AnaphoricReferenceResolver" })
+        }
         DeicticSource::POSTag => serde_json::json!({ "type": "POSTag" }),
         DeicticSource::Derived => serde_json::json!({ "type": "Derived" }),
     }
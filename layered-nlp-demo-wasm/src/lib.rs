@@ -124,6 +124,8 @@ fn get_span_links_internal(text: &str) -> SpanLinkResult {
                 ClauseRole::ListContainer => "ListContainer",
                 ClauseRole::CrossReference => "CrossRef",
                 ClauseRole::Relative => "Relative",
+                ClauseRole::IncorporatesFrom => "IncorporatesFrom",
+                ClauseRole::GatedPermission => "GatedPermission",
                 ClauseRole::Self_ => "Self",
             };
 
@@ -4,6 +4,9 @@
 //! this module provides tools to aggregate them while preserving
 //! information about ambiguity and confidence.
 
+use std::fmt;
+use std::sync::Arc;
+
 use crate::Scored;
 
 /// Flag indicating the nature of ambiguity in an aggregated result.
@@ -26,9 +29,10 @@ pub struct AmbiguityConfig {
     pub min_score: f64,
     /// Threshold below which best candidate is flagged as low confidence
     pub low_confidence: f64,
-    /// Margin within which alternatives are considered competing
-    /// (alt >= best - margin triggers CompetingAlternatives)
-    pub ambiguity_margin: f64,
+    /// Gap between the two best scores at or below which the result is
+    /// flagged as CompetingAlternatives
+    #[serde(alias = "ambiguity_margin")]
+    pub margin_threshold: f64,
 }
 
 impl Default for AmbiguityConfig {
@@ -37,7 +41,47 @@ impl Default for AmbiguityConfig {
             n_best: 4,
             min_score: 0.25,
             low_confidence: 0.6,
-            ambiguity_margin: 0.1,
+            margin_threshold: 0.1,
+        }
+    }
+}
+
+/// How [`Ambiguous::merge`] combines the confidences of candidates proposing
+/// the same value.
+#[derive(Clone, Default)]
+pub enum MergePolicy {
+    /// Keep the highest confidence; agreement adds nothing
+    Max,
+    /// Treat each confidence as independent evidence: `1 - Π(1 - c)`.
+    /// Two resolvers agreeing at 0.7 yield 0.91.
+    #[default]
+    NoisyOr,
+    /// Fold the confidences pairwise with a custom function
+    Custom(Arc<dyn Fn(f64, f64) -> f64 + Send + Sync>),
+}
+
+impl MergePolicy {
+    /// Fold a custom pairwise aggregation.
+    pub fn custom(aggregate: impl Fn(f64, f64) -> f64 + Send + Sync + 'static) -> Self {
+        MergePolicy::Custom(Arc::new(aggregate))
+    }
+
+    /// Combine two confidences for the same value.
+    pub fn combine(&self, a: f64, b: f64) -> f64 {
+        match self {
+            MergePolicy::Max => a.max(b),
+            MergePolicy::NoisyOr => 1.0 - (1.0 - a) * (1.0 - b),
+            MergePolicy::Custom(aggregate) => aggregate(a, b),
+        }
+    }
+}
+
+impl fmt::Debug for MergePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergePolicy::Max => f.write_str("Max"),
+            MergePolicy::NoisyOr => f.write_str("NoisyOr"),
+            MergePolicy::Custom(_) => f.write_str("Custom(..)"),
         }
     }
 }
@@ -98,6 +142,38 @@ impl<T> Ambiguous<T> {
         Some(Self { best, alternatives, flag })
     }
 
+    /// Merge the candidates of two results, e.g. from two resolvers.
+    ///
+    /// Candidates proposing equal values are combined into one whose
+    /// confidence is aggregated by `policy` and whose source is
+    /// [`Derived`](crate::ScoreSource::Derived); the merged candidates are then
+    /// ranked, truncated and flagged as in [`from_candidates`](Self::from_candidates).
+    ///
+    /// Returns `None` only if no candidate survives the min_score filter,
+    /// which a custom policy lowering scores can cause.
+    pub fn merge(self, other: Self, policy: &MergePolicy, cfg: &AmbiguityConfig) -> Option<Self>
+    where
+        T: PartialEq,
+    {
+        let mut merged: Vec<Scored<T>> = Vec::new();
+        for candidate in self.into_candidates().chain(other.into_candidates()) {
+            match merged.iter_mut().find(|existing| existing.value == candidate.value) {
+                Some(existing) => {
+                    existing.confidence =
+                        policy.combine(existing.confidence, candidate.confidence).clamp(0.0, 1.0);
+                    existing.source = crate::ScoreSource::Derived;
+                }
+                None => merged.push(candidate),
+            }
+        }
+        Self::from_candidates(merged, cfg)
+    }
+
+    /// All candidates, best first.
+    fn into_candidates(self) -> impl Iterator<Item = Scored<T>> {
+        std::iter::once(self.best).chain(self.alternatives)
+    }
+
     /// Compute the ambiguity flag based on the best candidate, alternatives, and config.
     fn compute_flag(best: &Scored<T>, alts: &[Scored<T>], cfg: &AmbiguityConfig) -> AmbiguityFlag {
         // Low confidence if best is below threshold
@@ -105,12 +181,8 @@ impl<T> Ambiguous<T> {
             return AmbiguityFlag::LowConfidence;
         }
 
-        // Check for competing alternatives within margin
-        let has_competing = alts.iter().any(|alt| {
-            alt.confidence >= best.confidence - cfg.ambiguity_margin
-        });
-
-        if has_competing {
+        // Check for a runner-up within the margin
+        if margin_within(best, alts, cfg.margin_threshold) {
             AmbiguityFlag::CompetingAlternatives
         } else {
             AmbiguityFlag::None
@@ -136,6 +208,48 @@ impl<T> Ambiguous<T> {
     pub fn is_ambiguous(&self) -> bool {
         self.flag != AmbiguityFlag::None
     }
+
+    /// Score difference between the best and second-best candidates.
+    ///
+    /// Returns `None` when there is no alternative.
+    pub fn top_margin(&self) -> Option<f64> {
+        top_margin(&self.best, &self.alternatives)
+    }
+
+    /// Check if the runner-up scores within `threshold` of the best.
+    ///
+    /// Separates close calls (two antecedents at 0.51 and 0.49) from clear
+    /// wins regardless of how confident the best candidate is. Always false
+    /// without an alternative.
+    pub fn is_ambiguous_by_margin(&self, threshold: f64) -> bool {
+        margin_within(&self.best, &self.alternatives, threshold)
+    }
+
+    /// Iterate all candidates (best first) as `(value, score, margin_to_next)`.
+    ///
+    /// The margin is the score gap to the next-ranked candidate; for the last
+    /// candidate it is the gap to a score of zero.
+    pub fn alternatives_with_margins(&self) -> Vec<(&T, f64, f64)> {
+        let ranked: Vec<&Scored<T>> =
+            std::iter::once(&self.best).chain(self.alternatives.iter()).collect();
+
+        ranked
+            .iter()
+            .enumerate()
+            .map(|(idx, candidate)| {
+                let next_score = ranked.get(idx + 1).map_or(0.0, |next| next.confidence);
+                (&candidate.value, candidate.confidence, candidate.confidence - next_score)
+            })
+            .collect()
+    }
+}
+
+fn top_margin<T>(best: &Scored<T>, alts: &[Scored<T>]) -> Option<f64> {
+    alts.first().map(|runner_up| best.confidence - runner_up.confidence)
+}
+
+fn margin_within<T>(best: &Scored<T>, alts: &[Scored<T>], threshold: f64) -> bool {
+    top_margin(best, alts).is_some_and(|margin| margin <= threshold)
 }
 
 #[cfg(test)]
@@ -176,6 +290,27 @@ mod tests {
         assert_eq!(result.flag, AmbiguityFlag::None);
     }
 
+    #[test]
+    fn test_alternatives_with_margins() {
+        let candidates = vec![
+            make_candidate("b", 0.82),
+            make_candidate("a", 0.85),
+            make_candidate("c", 0.5),
+        ];
+        let result = Ambiguous::from_candidates(candidates, &AmbiguityConfig::default()).unwrap();
+        let margins = result.alternatives_with_margins();
+
+        assert_eq!(margins.len(), 3);
+        assert_eq!(margins[0].0, "a");
+        assert!((margins[0].2 - 0.03).abs() < 1e-9);
+        assert_eq!(margins[1].0, "b");
+        assert!((margins[1].2 - 0.32).abs() < 1e-9);
+        // Last candidate's margin is measured against zero
+        assert_eq!(margins[2].0, "c");
+        assert_eq!(margins[2].1, 0.5);
+        assert_eq!(margins[2].2, 0.5);
+    }
+
     #[test]
     fn test_best_below_low_confidence() {
         let candidates = vec![
@@ -212,6 +347,42 @@ mod tests {
         assert_eq!(result.flag, AmbiguityFlag::CompetingAlternatives);
     }
 
+    #[test]
+    fn test_top_margin() {
+        let single =
+            Ambiguous::from_candidates(vec![make_candidate("a", 0.9)], &AmbiguityConfig::default())
+                .unwrap();
+        assert_eq!(single.top_margin(), None);
+        assert!(!single.is_ambiguous_by_margin(1.0));
+
+        let close = Ambiguous::from_candidates(
+            vec![make_candidate("a", 0.51), make_candidate("b", 0.49), make_candidate("c", 0.3)],
+            &AmbiguityConfig { min_score: 0.0, low_confidence: 0.0, ..Default::default() },
+        )
+        .unwrap();
+        assert!((close.top_margin().unwrap() - 0.02).abs() < 1e-9);
+        assert!(close.is_ambiguous_by_margin(0.05));
+        assert!(!close.is_ambiguous_by_margin(0.01));
+        assert_eq!(close.flag, AmbiguityFlag::CompetingAlternatives);
+    }
+
+    #[test]
+    fn test_margin_threshold_drives_flag() {
+        let candidates = || vec![make_candidate("a", 0.9), make_candidate("b", 0.75)];
+        let cfg = AmbiguityConfig { margin_threshold: 0.2, ..Default::default() };
+        let wide = Ambiguous::from_candidates(candidates(), &cfg).unwrap();
+        assert_eq!(wide.flag, AmbiguityFlag::CompetingAlternatives);
+
+        let narrow = Ambiguous::from_candidates(candidates(), &AmbiguityConfig::default()).unwrap();
+        assert_eq!(narrow.flag, AmbiguityFlag::None);
+
+        let legacy: AmbiguityConfig = ron::from_str(
+            "(n_best: 4, min_score: 0.25, low_confidence: 0.6, ambiguity_margin: 0.2)",
+        )
+        .unwrap();
+        assert_eq!(legacy.margin_threshold, 0.2);
+    }
+
     #[test]
     fn test_n_best_truncation() {
         let candidates = vec![
@@ -248,6 +419,44 @@ mod tests {
         assert_eq!(result.alternatives[1].value, "c");
     }
 
+    #[test]
+    fn test_merge_policies() {
+        let cfg = AmbiguityConfig::default();
+        let first = || {
+            Ambiguous::from_candidates(
+                vec![make_candidate("a", 0.7), make_candidate("b", 0.75)],
+                &cfg,
+            )
+            .unwrap()
+        };
+        let second = || {
+            Ambiguous::from_candidates(
+                vec![make_candidate("a", 0.7), make_candidate("c", 0.4)],
+                &cfg,
+            )
+            .unwrap()
+        };
+
+        // Corroboration lifts "a" above the single-source "b"
+        let noisy_or = first().merge(second(), &MergePolicy::default(), &cfg).unwrap();
+        assert_eq!(noisy_or.best.value, "a");
+        assert!((noisy_or.best.confidence - 0.91).abs() < 1e-9);
+        assert_eq!(noisy_or.best.source, crate::ScoreSource::Derived);
+        assert_eq!(noisy_or.candidate_count(), 3);
+        assert_eq!(noisy_or.flag, AmbiguityFlag::None);
+
+        let max = first().merge(second(), &MergePolicy::Max, &cfg).unwrap();
+        assert_eq!(max.best.value, "b");
+        assert_eq!(max.alternatives[0].value, "a");
+        assert_eq!(max.alternatives[0].confidence, 0.7);
+        assert_eq!(max.flag, AmbiguityFlag::CompetingAlternatives);
+
+        let mean = MergePolicy::custom(|a, b| (a + b) / 2.0);
+        let merged = first().merge(second(), &mean, &cfg).unwrap();
+        assert_eq!(merged.best.value, "b");
+        assert!((merged.alternatives[0].confidence - 0.7).abs() < 1e-9);
+    }
+
     #[test]
     fn test_helper_methods() {
         let candidates = vec![
@@ -0,0 +1,188 @@
+//! Text-anchored spans that survive edits to the document.
+//!
+//! A [`DocSpan`] addresses tokens by index, so it goes stale as soon as the
+//! text before it is edited. An [`AnchoredSpan`] records the span's tokens and
+//! a few tokens of context on either side instead; [`reanchor`] finds them
+//! again in the edited document. An editor can keep review decisions attached
+//! to their text across edits this way:
+//!
+//! ```
+//! use layered_nlp_document::{reanchor, AnchoredSpan, DocSpan, LayeredDocument};
+//!
+//! let doc = LayeredDocument::from_text("The Tenant shall pay rent monthly.");
+//! let anchor = AnchoredSpan::capture(&doc, &DocSpan::single_line(0, 6, 10));
+//! assert_eq!(anchor.text, vec!["pay", "rent", "monthly"]);
+//!
+//! let edited = LayeredDocument::from_text("The Tenant shall promptly pay rent monthly.");
+//! assert_eq!(reanchor(&anchor, &edited), Some(DocSpan::single_line(0, 8, 12)));
+//! ```
+
+use layered_nlp::{LToken, TextTag};
+use serde::{Deserialize, Serialize};
+
+use crate::{DocPosition, DocSpan, LayeredDocument};
+
+/// Number of context tokens captured on each side by default.
+const DEFAULT_CONTEXT: usize = 4;
+
+/// Extra tokens searched beyond the captured context, so that insertions
+/// next to the span do not push the context out of reach.
+const CONTEXT_SLACK: usize = 3;
+
+/// A span identified by its text and surrounding context rather than by
+/// token indices.
+///
+/// All fields hold the texts of non-whitespace tokens, so whitespace edits
+/// never affect an anchor. Context runs across line boundaries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnchoredSpan {
+    /// Tokens directly before the span, in document order
+    pub context_before: Vec<String>,
+    /// The span's own tokens
+    pub text: Vec<String>,
+    /// Tokens directly after the span
+    pub context_after: Vec<String>,
+}
+
+impl AnchoredSpan {
+    /// Anchor `span` of `doc` with the default amount of context.
+    pub fn capture(doc: &LayeredDocument, span: &DocSpan) -> Self {
+        Self::capture_with_context(doc, span, DEFAULT_CONTEXT)
+    }
+
+    /// Anchor `span` of `doc` with up to `context` tokens on each side.
+    pub fn capture_with_context(doc: &LayeredDocument, span: &DocSpan, context: usize) -> Self {
+        let tokens = content_tokens(doc);
+        let key = |position: &DocPosition| (position.line, position.token);
+        let start = tokens.partition_point(|(position, _)| key(position) < key(&span.start));
+        let end = tokens.partition_point(|(position, _)| key(position) <= key(&span.end));
+        let texts = |range: std::ops::Range<usize>| -> Vec<String> {
+            tokens[range].iter().map(|(_, text)| text.to_string()).collect()
+        };
+        Self {
+            context_before: texts(start.saturating_sub(context)..start),
+            text: texts(start..end.max(start)),
+            context_after: texts(end.max(start)..(end + context).min(tokens.len())),
+        }
+    }
+}
+
+/// Locate `anchor` in `doc`, an edited version of the document it was
+/// captured from.
+///
+/// The anchored text must occur unchanged. When it occurs once, that
+/// occurrence is returned whatever its context. When it occurs several times,
+/// the occurrence whose surroundings best match the captured context wins;
+/// context tokens are matched in order but may have tokens inserted between
+/// or removed from them. Returns `None` if the text is gone, or if two
+/// occurrences match the context equally well.
+pub fn reanchor(anchor: &AnchoredSpan, doc: &LayeredDocument) -> Option<DocSpan> {
+    if anchor.text.is_empty() {
+        return None;
+    }
+    let tokens = content_tokens(doc);
+    let len = anchor.text.len();
+    let occurrences: Vec<usize> = (0..(tokens.len() + 1).saturating_sub(len))
+        .filter(|&start| {
+            let candidate = tokens[start..start + len].iter().map(|(_, text)| *text);
+            candidate.eq(anchor.text.iter().map(String::as_str))
+        })
+        .collect();
+
+    let best = match occurrences.as_slice() {
+        [] => return None,
+        [only] => *only,
+        _ => {
+            let mut scored: Vec<(usize, usize)> = occurrences
+                .iter()
+                .map(|&start| (context_score(anchor, &tokens, start), start))
+                .collect();
+            scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+            if scored[0].0 == scored[1].0 {
+                return None;
+            }
+            scored[0].1
+        }
+    };
+    Some(DocSpan::new(tokens[best].0, tokens[best + len - 1].0))
+}
+
+/// Context tokens of `anchor` found around the occurrence at `start`.
+fn context_score(anchor: &AnchoredSpan, tokens: &[(DocPosition, &str)], start: usize) -> usize {
+    let texts = |range: std::ops::Range<usize>| -> Vec<&str> {
+        tokens[range].iter().map(|(_, text)| *text).collect()
+    };
+    let before_window = anchor.context_before.len() + CONTEXT_SLACK;
+    let end = start + anchor.text.len();
+    let after_window = anchor.context_after.len() + CONTEXT_SLACK;
+    common_subsequence(&anchor.context_before, &texts(start.saturating_sub(before_window)..start))
+        + common_subsequence(
+            &anchor.context_after,
+            &texts(end..(end + after_window).min(tokens.len())),
+        )
+}
+
+/// Length of the longest common subsequence of two token sequences.
+fn common_subsequence(a: &[String], b: &[&str]) -> usize {
+    let mut row = vec![0; b.len() + 1];
+    for x in a {
+        let mut diagonal = 0;
+        for (j, y) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if x == y { diagonal + 1 } else { above.max(row[j]) };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Every non-whitespace token of the document with its position.
+fn content_tokens(doc: &LayeredDocument) -> Vec<(DocPosition, &str)> {
+    doc.lines_enumerated()
+        .flat_map(|(line_index, line)| {
+            line.ll_tokens().iter().enumerate().filter_map(move |(idx, token)| {
+                match token.get_token() {
+                    LToken::Text(_, TextTag::SPACE) | LToken::Value => None,
+                    LToken::Text(text, _) => {
+                        Some((DocPosition::new(line_index, idx), text.as_str()))
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str = "The Tenant shall pay rent.\nThe Landlord shall pay taxes.";
+
+    #[test]
+    fn test_reanchor_after_edits_near_the_span() {
+        let doc = LayeredDocument::from_text(ORIGINAL);
+        // "pay" of the Landlord's obligation
+        let anchor = AnchoredSpan::capture(&doc, &DocSpan::single_line(1, 6, 6));
+        assert_eq!(anchor.context_before, vec![".", "The", "Landlord", "shall"]);
+
+        let edited = LayeredDocument::from_text(
+            "Each month, the Tenant shall pay rent.\nThe Landlord shall promptly pay all taxes.",
+        );
+        assert_eq!(reanchor(&anchor, &edited), Some(DocSpan::single_line(1, 8, 8)));
+    }
+
+    #[test]
+    fn test_reanchor_fails_when_text_changed_or_ambiguous() {
+        let doc = LayeredDocument::from_text(ORIGINAL);
+        let anchor = AnchoredSpan::capture(&doc, &DocSpan::single_line(0, 6, 8));
+        assert_eq!(anchor.text, vec!["pay", "rent"]);
+
+        let edited = LayeredDocument::from_text("The Tenant shall pay the rent.");
+        assert_eq!(reanchor(&anchor, &edited), None);
+
+        // Both "shall" occurrences match the context equally badly
+        let shall = AnchoredSpan::capture(&doc, &DocSpan::single_line(0, 4, 4));
+        let edited = LayeredDocument::from_text("Buyer shall act.\nSeller shall act.");
+        assert_eq!(reanchor(&shall, &edited), None);
+    }
+}
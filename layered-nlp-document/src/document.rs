@@ -4,9 +4,15 @@
 //! This module provides `LayeredDocument` which wraps multiple lines
 //! and enables cross-line operations like section structure detection.
 
-use layered_nlp::{LLLine, Resolver};
+use crate::language::{detect_line_language, Lang};
+use crate::instrument::StageSpan;
+use crate::position_index::PositionIndex;
+use crate::query::DocQuery;
+use crate::Scored;
+use layered_nlp::{LLLine, LLLineFind, LToken, Resolver, TextTag};
 use std::any::{Any, TypeId};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
 
 /// Position within a multi-line document.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
@@ -34,7 +40,7 @@ impl DocPosition {
 }
 
 /// A span within a document that can cross line boundaries.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct DocSpan {
     pub start: DocPosition,
     pub end: DocPosition,
@@ -53,6 +59,15 @@ impl DocSpan {
         }
     }
 
+    /// The span of a [`LLLine::find`] match on line `line`.
+    ///
+    /// Uses the match's token indices; [`LLLineFind::range`] gives byte
+    /// positions, which are not token indices.
+    pub fn from_find<T>(line: usize, found: &LLLineFind<'_, T>) -> Self {
+        let (start, end) = found.token_range();
+        Self::single_line(line, start, end)
+    }
+
     /// Returns true if this span is within a single line.
     pub fn is_single_line(&self) -> bool {
         self.start.line == self.end.line
@@ -74,7 +89,7 @@ impl DocSpan {
     }
 
     /// Check if this span contains the given position.
-    pub fn contains(&self, pos: &DocPosition) -> bool {
+    pub fn contains_position(&self, pos: &DocPosition) -> bool {
         // A position is contained if:
         // 1. It's on a line between start.line and end.line (inclusive)
         // 2. If on start line, token >= start.token
@@ -91,8 +106,20 @@ impl DocSpan {
         true
     }
 
-    /// Check if this span overlaps with another span.
-    pub fn overlaps(&self, other: &DocSpan) -> bool {
+    /// Check if `other` lies entirely within this span, boundaries included.
+    ///
+    /// Positions compare line first, so a span ending at token 2 of a later
+    /// line contains one ending at token 40 of an earlier line.
+    pub fn contains(&self, other: &DocSpan) -> bool {
+        self.contains_position(&other.start) && self.contains_position(&other.end)
+    }
+
+    /// Check if this span shares at least one position with another span.
+    ///
+    /// Spans are inclusive of both ends, so spans meeting at a token
+    /// intersect while a span ending at token 4 and one starting at token 5
+    /// do not.
+    pub fn intersects(&self, other: &DocSpan) -> bool {
         // Two spans overlap if neither ends before the other starts
         // No overlap if: self ends before other starts OR other ends before self starts
         let self_ends_before = self.end.line < other.start.line
@@ -102,6 +129,142 @@ impl DocSpan {
 
         !self_ends_before && !other_ends_before
     }
+
+    /// Check if this span overlaps with another span.
+    #[deprecated = "Use DocSpan::intersects"]
+    pub fn overlaps(&self, other: &DocSpan) -> bool {
+        self.intersects(other)
+    }
+
+    /// Convert to character offsets into [`LayeredDocument::original_text`].
+    ///
+    /// The span covers its start and end tokens completely. Returns `None`
+    /// if either position does not name a token in `doc`.
+    pub fn to_char_span(&self, doc: &LayeredDocument) -> Option<CharSpan> {
+        let start = doc.token_char_range(self.start)?.0;
+        let end = doc.token_char_range(self.end)?.1;
+        Some(CharSpan::new(start, end.max(start)))
+    }
+
+    /// Convert character offsets into [`LayeredDocument::original_text`] to a span.
+    ///
+    /// Offsets are snapped outward to whole tokens, so the result covers
+    /// every token the range touches. Returns `None` when the range is empty
+    /// or touches no token (for example, only blank lines).
+    pub fn from_char_span(doc: &LayeredDocument, span: CharSpan) -> Option<DocSpan> {
+        if span.is_empty() {
+            return None;
+        }
+        let start = doc.position_at_char(span.start, Snap::Forward)?;
+        let end = doc.position_at_char(span.end - 1, Snap::Backward)?;
+        if (end.line, end.token) < (start.line, start.token) {
+            return None;
+        }
+        Some(DocSpan::new(start, end))
+    }
+}
+
+/// A range of character offsets into a document's original text.
+///
+/// Offsets count Unicode scalar values (Rust `char`s), not bytes; `end` is
+/// exclusive. Convert with [`DocSpan::to_char_span`] and
+/// [`DocSpan::from_char_span`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct CharSpan {
+    /// First character (inclusive)
+    pub start: usize,
+    /// End character (exclusive)
+    pub end: usize,
+}
+
+impl CharSpan {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Number of characters covered.
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Returns true if the span covers no characters.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The covered slice of `text`, or `None` if the span is out of bounds.
+    pub fn slice<'t>(&self, text: &'t str) -> Option<&'t str> {
+        let mut boundaries = text.char_indices().map(|(byte, _)| byte).chain([text.len()]);
+        let start = boundaries.nth(self.start)?;
+        let end = if self.end > self.start {
+            boundaries.nth(self.end - self.start - 1)?
+        } else {
+            start
+        };
+        text.get(start..end)
+    }
+}
+
+/// Direction to look for a token when a character falls between tokens.
+#[derive(Clone, Copy)]
+enum Snap {
+    Forward,
+    Backward,
+}
+
+/// Terminator ending a line of the original text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum LineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+impl LineEnding {
+    /// The terminator's text.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+
+    /// The terminator ending `source_line`, if any.
+    fn of(source_line: &str) -> Option<Self> {
+        if source_line.ends_with("\r\n") {
+            Some(LineEnding::CrLf)
+        } else if source_line.ends_with('\n') {
+            Some(LineEnding::Lf)
+        } else {
+            None
+        }
+    }
+}
+
+/// Where each document line starts in the original text.
+struct LineOffsets {
+    /// (byte offset, char offset) of each line's first character
+    starts: Vec<(usize, usize)>,
+}
+
+impl LineOffsets {
+    fn new(doc: &LayeredDocument) -> Self {
+        let mut source_starts = Vec::new();
+        let mut byte = 0;
+        let mut chars = 0;
+        for source_line in doc.original_text.split_inclusive('\n') {
+            source_starts.push((byte, chars));
+            byte += source_line.len();
+            chars += source_line.chars().count();
+        }
+        let starts = doc
+            .line_to_source
+            .iter()
+            .map(|source| source_starts.get(source - 1).copied().unwrap_or((byte, chars)))
+            .collect();
+        Self { starts }
+    }
 }
 
 /// Document-level attribute storage using TypeId-indexed vectors.
@@ -153,6 +316,54 @@ impl DocAttrStore {
     }
 }
 
+/// Append-only, type-keyed store for values derived from a document's lines.
+///
+/// Each node holds one value; lookups walk the chain and inserts fill the first
+/// free slot, so references handed out stay valid for the store's lifetime.
+#[derive(Default)]
+struct DocMemo {
+    value: OnceLock<Box<dyn Any + Send + Sync>>,
+    next: OnceLock<Box<DocMemo>>,
+}
+
+impl DocMemo {
+    fn get<T: 'static>(&self) -> Option<&T> {
+        let mut node = Some(self);
+        while let Some(current) = node {
+            if let Some(value) = current.value.get()?.downcast_ref::<T>() {
+                return Some(value);
+            }
+            node = current.next.get().map(|next| next.as_ref());
+        }
+        None
+    }
+
+    fn get_or_insert_with<T: 'static + Send + Sync>(&self, build: impl FnOnce() -> T) -> &T {
+        if let Some(value) = self.get::<T>() {
+            return value;
+        }
+
+        // Build outside the cells so `build` may itself use the memo.
+        let mut pending: Box<dyn Any + Send + Sync> = Box::new(build());
+        let mut node = self;
+        loop {
+            match node.value.set(pending) {
+                Ok(()) => break,
+                Err(rejected) => pending = rejected,
+            }
+            if let Some(value) = node.value.get().and_then(|v| v.downcast_ref::<T>()) {
+                // Another thread stored the same type first
+                return value;
+            }
+            node = node.next.get_or_init(Default::default);
+        }
+        node.value
+            .get()
+            .and_then(|value| value.downcast_ref::<T>())
+            .expect("memo slot holds the value just stored")
+    }
+}
+
 /// Trait for document-level resolvers that analyze entire documents.
 /// Unlike line-level `Resolver` which processes individual lines,
 /// DocumentResolver operates across the full document structure.
@@ -177,6 +388,10 @@ pub struct LayeredDocument {
     original_text: String,
     /// Document-level attributes indexed by type
     doc_attrs: DocAttrStore,
+    /// When set, `run_resolver` skips lines detected as a different language
+    language_filter: Option<Lang>,
+    /// Values derived from the lines, reset whenever the lines change
+    memo: DocMemo,
 }
 
 impl std::fmt::Debug for LayeredDocument {
@@ -211,7 +426,98 @@ impl LayeredDocument {
             line_to_source,
             original_text: text.to_string(),
             doc_attrs: DocAttrStore::new(),
+            language_filter: None,
+            memo: DocMemo::default(),
+        }
+    }
+
+    /// Only run line resolvers on lines in the given language.
+    ///
+    /// Lines detected as another language are left unannotated; lines whose
+    /// language cannot be determined (headings, numbers) are still processed.
+    /// Line indices are unaffected, so `DocPosition`s remain stable.
+    pub fn with_language_filter(mut self, lang: Lang) -> Self {
+        self.language_filter = Some(lang);
+        self
+    }
+
+    /// Leave lines matching `predicate` out of all analysis.
+    ///
+    /// Meant for metadata such as page markers or reviewer comments: the
+    /// predicate sees each line's source text, and matching lines are emptied
+    /// of tokens. Line resolvers then have nothing to annotate on them, and
+    /// document resolvers find no text there to use as context. The lines keep
+    /// their indices, so `DocPosition`s of the remaining lines are unaffected.
+    /// Call this before running resolvers; earlier annotations on ignored
+    /// lines are discarded.
+    pub fn with_ignored_lines(mut self, predicate: impl Fn(&str) -> bool) -> Self {
+        let source_lines: Vec<&str> = self.original_text.lines().collect();
+        for (line, &source) in self.lines.iter_mut().zip(&self.line_to_source) {
+            if predicate(source_lines[source - 1]) {
+                *line = layered_nlp::create_line_from_input_tokens(Vec::new(), |s| s.len());
+            }
+        }
+        self.memo = DocMemo::default();
+        self
+    }
+
+    /// Whether the line at `index` was left out by [`Self::with_ignored_lines`].
+    pub fn is_ignored_line(&self, index: usize) -> bool {
+        self.lines.get(index).is_some_and(|line| line.ll_tokens().is_empty())
+    }
+
+    /// Get the language filter, if one is set.
+    pub fn language_filter(&self) -> Option<Lang> {
+        self.language_filter
+    }
+
+    /// Detect the language of a specific line.
+    pub fn line_language(&self, index: usize) -> Option<Lang> {
+        self.lines.get(index).map(detect_line_language)
+    }
+
+    /// Create a document from raw text, reporting lines that fail to tokenize.
+    ///
+    /// Like [`LayeredDocument::from_text`], but a line whose tokenization panics
+    /// is left out of the document and reported as
+    /// [`ProcessError::TokenizationFailed`] instead of aborting. The error
+    /// names the line's 1-based line number in `text`, since the line has no
+    /// index in the document.
+    pub fn try_from_text(text: &str) -> ProcessResult<Self> {
+        let mut lines = Vec::new();
+        let mut line_to_source = Vec::new();
+        let mut errors = Vec::new();
+
+        for (source_idx, line_text) in text.lines().enumerate() {
+            if line_text.trim().is_empty() {
+                continue;
+            }
+            let tokenized = std::panic::catch_unwind(|| {
+                layered_nlp::create_line_from_string(line_text)
+            });
+            match tokenized {
+                Ok(line) => {
+                    lines.push(line);
+                    line_to_source.push(source_idx + 1);
+                }
+                Err(payload) => errors.push(ProcessError::TokenizationFailed {
+                    source_line: source_idx + 1,
+                    message: panic_message(payload.as_ref()),
+                }),
+            }
         }
+
+        ProcessResult::with_errors(
+            Self {
+                lines,
+                line_to_source,
+                original_text: text.to_string(),
+                doc_attrs: DocAttrStore::new(),
+                language_filter: None,
+                memo: DocMemo::default(),
+            },
+            errors,
+        )
     }
 
     /// Get the number of lines in the document.
@@ -224,8 +530,23 @@ impl LayeredDocument {
         self.lines.get(index)
     }
 
+    /// The span of tokens `start..=end` on line `line`.
+    ///
+    /// `end` is clamped to the line's last token, so a range running past the
+    /// end of the line (an exclusive end, say) still names real tokens.
+    pub fn span_from_line_range(&self, line: usize, start: usize, end: usize) -> DocSpan {
+        let end = match self.lines.get(line) {
+            Some(ll_line) => end.min(ll_line.ll_tokens().len().saturating_sub(1)),
+            None => end,
+        };
+        DocSpan::single_line(line, start, end.max(start))
+    }
+
     /// Get a mutable reference to a specific line.
+    ///
+    /// Clears memoized values, since the line may be changed.
     pub fn get_line_mut(&mut self, index: usize) -> Option<&mut LLLine> {
+        self.memo = DocMemo::default();
         self.lines.get_mut(index)
     }
 
@@ -237,17 +558,98 @@ impl LayeredDocument {
     /// Run a resolver on all lines in the document.
     ///
     /// This is the bridge between per-line `Resolver` trait and document-level processing.
+    /// If a language filter is set, lines detected as another language are skipped.
     pub fn run_resolver<R: Resolver>(self, resolver: &R) -> Self {
+        self.run_resolver_with_progress(resolver, |_| {})
+    }
+
+    /// Run a resolver on all lines, calling `on_line` with each line's index
+    /// once it has been processed (or skipped by the language filter).
+    pub fn run_resolver_with_progress<R: Resolver>(
+        self,
+        resolver: &R,
+        mut on_line: impl FnMut(usize),
+    ) -> Self {
+        let stage = StageSpan::start("resolver", std::any::type_name::<R>());
+        let language_filter = self.language_filter;
         let lines = self
             .lines
             .into_iter()
-            .map(|line| line.run(resolver))
-            .collect();
+            .enumerate()
+            .map(|(line_idx, line)| {
+                let line = if Self::passes_language_filter(language_filter, &line) {
+                    line.run(resolver)
+                } else {
+                    line
+                };
+                on_line(line_idx);
+                line
+            })
+            .collect::<Vec<LLLine>>();
+        stage.finish(|| {
+            lines
+                .iter()
+                .flat_map(|line| line.query::<R::Attr>())
+                .map(|(_, _, values)| values.len())
+                .sum()
+        });
         Self {
             lines,
             line_to_source: self.line_to_source,
             original_text: self.original_text,
             doc_attrs: self.doc_attrs,
+            language_filter,
+            memo: DocMemo::default(),
+        }
+    }
+
+    /// Run a resolver on all lines, converting resolver panics into errors.
+    ///
+    /// A line whose resolver panics keeps the annotations it had before this
+    /// run, and a [`ProcessError::ResolverPanic`] naming the resolver and line
+    /// is recorded. The remaining lines are processed normally.
+    pub fn try_run_resolver<R: Resolver>(self, resolver: &R) -> ProcessResult<Self> {
+        let language_filter = self.language_filter;
+        let mut errors = Vec::new();
+        let lines = self
+            .lines
+            .into_iter()
+            .enumerate()
+            .map(|(line_idx, line)| {
+                if !Self::passes_language_filter(language_filter, &line) {
+                    return line;
+                }
+                let (line, outcome) = line.try_run(resolver);
+                if let Err(message) = outcome {
+                    errors.push(ProcessError::ResolverPanic {
+                        resolver: std::any::type_name::<R>(),
+                        line: line_idx,
+                        message,
+                    });
+                }
+                line
+            })
+            .collect();
+        ProcessResult::with_errors(
+            Self {
+                lines,
+                line_to_source: self.line_to_source,
+                original_text: self.original_text,
+                doc_attrs: self.doc_attrs,
+                language_filter,
+                memo: DocMemo::default(),
+            },
+            errors,
+        )
+    }
+
+    fn passes_language_filter(filter: Option<Lang>, line: &LLLine) -> bool {
+        match filter {
+            Some(target) => match detect_line_language(line) {
+                Lang::Unknown => true,
+                lang => lang == target,
+            },
+            None => true,
         }
     }
 
@@ -256,6 +658,36 @@ impl LayeredDocument {
         &self.original_text
     }
 
+    /// Reproduce the source text byte-for-byte.
+    ///
+    /// Empty lines dropped from analysis and each line's terminator are
+    /// kept, so CRLF input comes back as CRLF.
+    pub fn to_text(&self) -> String {
+        self.original_text.clone()
+    }
+
+    /// The terminator ending the line at `internal_index`.
+    ///
+    /// `None` if there is no such line, or for a final line without a
+    /// trailing newline.
+    pub fn line_ending(&self, internal_index: usize) -> Option<LineEnding> {
+        let source = self.source_line_number(internal_index)?;
+        self.original_text.split_inclusive('\n').nth(source - 1).and_then(LineEnding::of)
+    }
+
+    /// Terminators of every source line, including empty ones, in order.
+    ///
+    /// The last entry is `None` when the text does not end with a newline.
+    /// An empty document has no lines.
+    pub fn source_line_endings(&self) -> Vec<Option<LineEnding>> {
+        self.original_text.split_inclusive('\n').map(LineEnding::of).collect()
+    }
+
+    /// Whether the text ends with a line terminator.
+    pub fn ends_with_newline(&self) -> bool {
+        self.original_text.ends_with('\n')
+    }
+
     /// Take ownership of the lines (consuming the document).
     pub fn into_lines(self) -> Vec<LLLine> {
         self.lines
@@ -289,6 +721,21 @@ impl LayeredDocument {
         self.doc_attrs.query()
     }
 
+    /// Start a fluent query over line-level attributes.
+    ///
+    /// See [`DocQuery`] for the available filters.
+    pub fn query(&self) -> DocQuery<'_> {
+        DocQuery::new(self)
+    }
+
+    /// Index the spans of all line-level annotations for position lookups.
+    ///
+    /// Built on first use and kept until the lines change; see
+    /// [`PositionIndex`].
+    pub fn annotation_map(&self) -> &PositionIndex {
+        self.memoized(PositionIndex::build)
+    }
+
     /// Add multiple document-level attributes.
     pub fn add_doc_attrs<T: 'static + Send + Sync>(&mut self, attrs: impl IntoIterator<Item = T>) {
         for attr in attrs {
@@ -296,15 +743,282 @@ impl LayeredDocument {
         }
     }
 
+    /// Get a value derived from the document, building it on first use.
+    ///
+    /// One value is kept per type `T`, so every caller sees the same instance.
+    /// The memo is cleared whenever the lines change (`run_resolver`,
+    /// `get_line_mut`, `resolve_overlaps`); document-level attributes added
+    /// afterwards do not invalidate it, so `build` should only read the lines.
+    pub fn memoized<T: 'static + Send + Sync>(&self, build: impl FnOnce(&Self) -> T) -> &T {
+        self.memo.get_or_insert_with(|| build(self))
+    }
+
     /// Run a document-level resolver and store its results.
     /// Returns self for chaining.
     pub fn run_document_resolver<R: DocumentResolver>(mut self, resolver: &R) -> Self {
+        let stage = StageSpan::start("document_resolver", std::any::type_name::<R>());
         let attrs = resolver.resolve(&self);
+        let count = attrs.len();
+        stage.finish(|| count);
         for attr in attrs {
             self.doc_attrs.add(attr);
         }
         self
     }
+
+    /// Resolve overlapping `Scored<T>` annotations on each line according to `policy`.
+    ///
+    /// Two annotations overlap when their token ranges intersect but differ;
+    /// several values on the identical range are never treated as overlapping.
+    /// Only `Scored<T>` attributes are affected, and annotations that overlap
+    /// nothing are left untouched.
+    pub fn resolve_overlaps<T: 'static>(mut self, policy: OverlapPolicy) -> Self {
+        if policy == OverlapPolicy::KeepBoth {
+            return self;
+        }
+        self.memo = DocMemo::default();
+
+        for line in &mut self.lines {
+            // (range, index within range, confidence)
+            let candidates: Vec<((usize, usize), usize, f64)> = line
+                .query::<Scored<T>>()
+                .into_iter()
+                .flat_map(|(range, _, values)| {
+                    values
+                        .into_iter()
+                        .enumerate()
+                        .map(move |(idx, value)| (range, idx, value.confidence))
+                })
+                .collect();
+
+            let mut order: Vec<usize> = (0..candidates.len()).collect();
+            order.sort_by(|&a, &b| {
+                let (range_a, _, conf_a) = candidates[a];
+                let (range_b, _, conf_b) = candidates[b];
+                let by_confidence =
+                    conf_b.partial_cmp(&conf_a).unwrap_or(std::cmp::Ordering::Equal);
+                let by_length = (range_b.1 - range_b.0).cmp(&(range_a.1 - range_a.0));
+                let primary = match policy {
+                    OverlapPolicy::KeepLongest => by_length.then(by_confidence),
+                    _ => by_confidence.then(by_length),
+                };
+                primary.then(range_a.0.cmp(&range_b.0))
+            });
+
+            let mut kept_ranges: Vec<(usize, usize)> = Vec::new();
+            let mut keep: HashSet<((usize, usize), usize)> = HashSet::new();
+            for idx in order {
+                let (range, value_idx, _) = candidates[idx];
+                let overlaps = kept_ranges
+                    .iter()
+                    .any(|kept| *kept != range && kept.0 <= range.1 && range.0 <= kept.1);
+                if !overlaps {
+                    keep.insert((range, value_idx));
+                    kept_ranges.push(range);
+                }
+            }
+
+            let mut seen: HashMap<(usize, usize), usize> = HashMap::new();
+            line.retain_attrs::<Scored<T>>(|range, _| {
+                let counter = seen.entry(range).or_insert(0);
+                let value_idx = *counter;
+                *counter += 1;
+                keep.contains(&(range, value_idx))
+            });
+        }
+
+        self
+    }
+
+    /// Character offsets of the token at `pos`, as (start, exclusive end).
+    fn token_char_range(&self, pos: DocPosition) -> Option<(usize, usize)> {
+        let token = self.lines.get(pos.line)?.ll_tokens().get(pos.token)?;
+        let (line_byte, line_char) = self.memoized(LineOffsets::new).starts[pos.line];
+        let text = &self.original_text[line_byte..];
+        let chars_until = |byte: usize| text.get(..byte).map(|prefix| prefix.chars().count());
+        let start = line_char + chars_until(token.pos_starts_at())?;
+        let end = line_char + chars_until(token.pos_ends_at())?;
+        Some((start, end))
+    }
+
+    /// The token covering the character at `offset`, or the nearest token in
+    /// the `snap` direction when the character lies outside every token.
+    fn position_at_char(&self, offset: usize, snap: Snap) -> Option<DocPosition> {
+        let starts = &self.memoized(LineOffsets::new).starts;
+        let line = match starts.partition_point(|&(_, chars)| chars <= offset).checked_sub(1) {
+            Some(line) => line,
+            // Before the first line (leading blank lines)
+            None => {
+                return match snap {
+                    Snap::Forward if !self.lines.is_empty() => Some(DocPosition::new(0, 0)),
+                    _ => None,
+                };
+            }
+        };
+        let tokens = self.lines[line].ll_tokens();
+        let (line_byte, line_char) = starts[line];
+        let text = &self.original_text[line_byte..];
+        let byte = text
+            .char_indices()
+            .nth(offset - line_char)
+            .map_or(text.len(), |(byte, _)| byte);
+
+        let next = tokens.iter().position(|token| byte < token.pos_ends_at());
+        let token = match (snap, next) {
+            (_, Some(idx)) if tokens[idx].pos_starts_at() <= byte => idx,
+            (Snap::Forward, Some(idx)) => idx,
+            (Snap::Forward, None) => {
+                let next_line = line + 1;
+                return (next_line < self.lines.len()).then(|| DocPosition::new(next_line, 0));
+            }
+            (Snap::Backward, Some(0)) => {
+                let previous = line.checked_sub(1)?;
+                let last = self.lines[previous].ll_tokens().len().checked_sub(1)?;
+                return Some(DocPosition::new(previous, last));
+            }
+            (Snap::Backward, Some(idx)) => idx - 1,
+            (Snap::Backward, None) => tokens.len().checked_sub(1)?,
+        };
+        Some(DocPosition::new(line, token))
+    }
+
+    /// Find every occurrence of `needle` and return it as a `DocSpan`.
+    ///
+    /// The needle is tokenized the same way as document lines and matched
+    /// token-by-token, so matches always start and end on token boundaries
+    /// and the returned spans share the annotation coordinate system. Any
+    /// whitespace in the needle matches any whitespace token. Matches never
+    /// cross line boundaries.
+    pub fn find_text(&self, needle: &str, case_insensitive: bool) -> Vec<DocSpan> {
+        let needle = needle.trim();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        let needle_line = layered_nlp::create_line_from_string(needle);
+        let needle_tokens: Vec<&LToken> = needle_line
+            .ll_tokens()
+            .iter()
+            .map(|token| token.get_token())
+            .collect();
+
+        let mut spans = Vec::new();
+        for (line_idx, line) in self.lines_enumerated() {
+            let tokens = line.ll_tokens();
+            if tokens.len() < needle_tokens.len() {
+                continue;
+            }
+            for start in 0..=(tokens.len() - needle_tokens.len()) {
+                let matched = needle_tokens
+                    .iter()
+                    .zip(&tokens[start..])
+                    .all(|(n, t)| tokens_match(n, t.get_token(), case_insensitive));
+                if matched {
+                    let end = start + needle_tokens.len() - 1;
+                    spans.push(DocSpan::single_line(
+                        line_idx,
+                        tokens[start].token_idx(),
+                        tokens[end].token_idx(),
+                    ));
+                }
+            }
+        }
+        spans
+    }
+
+    /// The source text an annotation covers, exactly as written.
+    ///
+    /// `span` is where `scored` was found, e.g. from [`DocSpan::from_find`].
+    /// Use [`evidence`](Self::evidence) to also get the text of the spans it
+    /// is associated with.
+    pub fn evidence_text<T: 'static>(&self, scored: &Scored<T>, span: &DocSpan) -> String {
+        self.evidence(scored, span).text
+    }
+
+    /// The source text an annotation covers, with the text of each span its
+    /// provenance associations point to ("obligor", "keyword", ...).
+    ///
+    /// Associations are looked up on the line at `span` and matched to
+    /// `scored` by confidence and source; multi-line spans have none.
+    pub fn evidence<T: 'static>(&self, scored: &Scored<T>, span: &DocSpan) -> Evidence {
+        let text = span
+            .to_char_span(self)
+            .and_then(|chars| chars.slice(&self.original_text))
+            .unwrap_or_default()
+            .to_string();
+
+        let mut associations = Vec::new();
+        if let (true, Some(line)) = (span.is_single_line(), self.lines.get(span.start.line)) {
+            let range = (span.start.token, span.end.token);
+            let annotated = line
+                .query_with_associations::<Scored<T>>()
+                .into_iter()
+                .filter(|(found_range, _, _)| *found_range == range)
+                .flat_map(|(_, _, values)| values)
+                .find(|(value, _)| {
+                    std::ptr::eq(*value, scored)
+                        || (value.confidence == scored.confidence && value.source == scored.source)
+                });
+            if let Some((_, spans)) = annotated {
+                for associated in spans {
+                    let target = DocSpan::single_line(
+                        span.start.line,
+                        associated.span.start_idx,
+                        associated.span.end_idx,
+                    );
+                    let text = target
+                        .to_char_span(self)
+                        .and_then(|chars| chars.slice(&self.original_text))
+                        .unwrap_or_default();
+                    associations.push((associated.label(), text.to_string()));
+                }
+            }
+        }
+
+        Evidence { text, associations }
+    }
+}
+
+/// The source text behind an annotation, from [`LayeredDocument::evidence`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evidence {
+    /// The annotated text as written in the source
+    pub text: String,
+    /// (association label, associated text) for each provenance association
+    pub associations: Vec<(&'static str, String)>,
+}
+
+/// Extract a readable message from a caught panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+/// Compare a needle token against a document token for `find_text`.
+fn tokens_match(needle: &LToken, token: &LToken, case_insensitive: bool) -> bool {
+    match (needle, token) {
+        (LToken::Text(_, TextTag::SPACE), LToken::Text(_, TextTag::SPACE)) => true,
+        (LToken::Text(a, _), LToken::Text(b, _)) if case_insensitive => {
+            a.to_lowercase() == b.to_lowercase()
+        }
+        (LToken::Text(a, _), LToken::Text(b, _)) => a == b,
+        _ => false,
+    }
+}
+
+/// Policy for [`LayeredDocument::resolve_overlaps`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Keep the annotation with the highest confidence (longest span breaks ties)
+    KeepHighestConfidence,
+    /// Keep the annotation covering the most tokens (confidence breaks ties)
+    KeepLongest,
+    /// Keep every annotation
+    KeepBoth,
 }
 
 /// Error types for document processing.
@@ -327,10 +1041,36 @@ pub enum ProcessError {
         reference: String,
         location: DocSpan,
     },
+    /// A line of source text could not be tokenized and was left out of the
+    /// document
+    TokenizationFailed {
+        /// 1-based line number in the source text
+        source_line: usize,
+        message: String,
+    },
+    /// A line-level resolver panicked while processing a line
+    ResolverPanic {
+        resolver: &'static str,
+        line: usize,
+        message: String,
+    },
     /// Generic processing error
     Other(String),
 }
 
+impl ProcessError {
+    /// The document line this error refers to, if it is located.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ProcessError::MalformedSectionHeader { line, .. }
+            | ProcessError::InconsistentNumbering { line, .. }
+            | ProcessError::ResolverPanic { line, .. } => Some(*line),
+            ProcessError::DanglingReference { location, .. } => Some(location.start.line),
+            ProcessError::TokenizationFailed { .. } | ProcessError::Other(_) => None,
+        }
+    }
+}
+
 impl std::fmt::Display for ProcessError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -351,6 +1091,12 @@ impl std::fmt::Display for ProcessError {
                     reference, location.start.line
                 )
             }
+            ProcessError::TokenizationFailed { source_line, message } => {
+                write!(f, "Source line {}: tokenization failed: {}", source_line, message)
+            }
+            ProcessError::ResolverPanic { resolver, line, message } => {
+                write!(f, "Line {}: resolver {} panicked: {}", line, resolver, message)
+            }
             ProcessError::Other(msg) => write!(f, "{}", msg),
         }
     }
@@ -358,12 +1104,100 @@ impl std::fmt::Display for ProcessError {
 
 impl std::error::Error for ProcessError {}
 
+/// How much a [`DocumentWarning`] matters to a reviewer, least first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WarningSeverity {
+    /// Worth a look, often intended ("company" where "Company" is defined)
+    Info,
+    /// Likely a drafting problem (an ambiguous section reference)
+    Warning,
+    /// A processing error surfaced as a warning (a dangling reference)
+    Error,
+}
+
+impl std::fmt::Display for WarningSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WarningSeverity::Info => write!(f, "info"),
+            WarningSeverity::Warning => write!(f, "warning"),
+            WarningSeverity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A soft problem found while processing a document: processing continued,
+/// but a reviewer should look at it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentWarning {
+    /// How much the problem matters
+    pub severity: WarningSeverity,
+    /// Where the problem is, when it can be located
+    pub span: Option<DocSpan>,
+    /// What is wrong, for a reviewer
+    pub message: String,
+}
+
+impl DocumentWarning {
+    /// A warning of `severity` without a location.
+    pub fn new(severity: WarningSeverity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            span: None,
+            message: message.into(),
+        }
+    }
+
+    /// Locate the warning at `span`.
+    pub fn at(mut self, span: DocSpan) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Surface a processing error as a warning of [`WarningSeverity::Error`].
+    ///
+    /// Errors with a position keep it; errors naming only a line are located
+    /// at the line's first token.
+    pub fn from_error(error: &ProcessError) -> Self {
+        let span = match error {
+            ProcessError::DanglingReference { location, .. } => Some(*location),
+            _ => error.line().map(|line| DocSpan::single_line(line, 0, 0)),
+        };
+        Self {
+            severity: WarningSeverity::Error,
+            span,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// A plain message is a [`WarningSeverity::Warning`] without a location.
+impl From<String> for DocumentWarning {
+    fn from(message: String) -> Self {
+        Self::new(WarningSeverity::Warning, message)
+    }
+}
+
+impl From<&str> for DocumentWarning {
+    fn from(message: &str) -> Self {
+        Self::new(WarningSeverity::Warning, message)
+    }
+}
+
+impl std::fmt::Display for DocumentWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.span {
+            Some(span) => write!(f, "{}: Line {}: {}", self.severity, span.start.line, self.message),
+            None => write!(f, "{}: {}", self.severity, self.message),
+        }
+    }
+}
+
 /// Result wrapper that collects errors without halting processing.
 #[derive(Debug)]
 pub struct ProcessResult<T> {
     pub value: T,
     pub errors: Vec<ProcessError>,
-    pub warnings: Vec<String>,
+    pub warnings: Vec<DocumentWarning>,
 }
 
 impl<T> ProcessResult<T> {
@@ -387,19 +1221,71 @@ impl<T> ProcessResult<T> {
         !self.errors.is_empty()
     }
 
-    pub fn add_warning(&mut self, warning: impl Into<String>) {
+    pub fn add_warning(&mut self, warning: impl Into<DocumentWarning>) {
         self.warnings.push(warning.into());
     }
 
     pub fn add_error(&mut self, error: ProcessError) {
         self.errors.push(error);
     }
+
+    /// Take over another pass's errors and warnings, returning its value.
+    ///
+    /// Lets one result accumulate the problems of every pass run over a
+    /// document.
+    pub fn merge<U>(&mut self, other: ProcessResult<U>) -> U {
+        self.errors.extend(other.errors);
+        self.warnings.extend(other.warnings);
+        other.value
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_char_span_round_trip_unicode() {
+        let text = "Café « déjà » 🎉 paid €5.\n\r\n  Naïve 👩‍👩‍👧 résumé\r\nend";
+        let doc = LayeredDocument::from_text(text);
+        assert_eq!(doc.line_count(), 3);
+
+        for (line_idx, line) in doc.lines_enumerated() {
+            for token in line.ll_tokens() {
+                let span = DocSpan::single_line(line_idx, token.token_idx(), token.token_idx());
+                let chars = span.to_char_span(&doc).unwrap();
+                if let LToken::Text(token_text, _) = token.get_token() {
+                    assert_eq!(chars.slice(text), Some(token_text.as_str()));
+                }
+                assert_eq!(DocSpan::from_char_span(&doc, chars), Some(span));
+            }
+        }
+
+        let whole = DocSpan::new(DocPosition::new(0, 0), DocPosition::new(2, 0));
+        let chars = whole.to_char_span(&doc).unwrap();
+        assert_eq!(chars, CharSpan::new(0, text.chars().count()));
+        assert_eq!(DocSpan::from_char_span(&doc, chars), Some(whole));
+    }
+
+    #[test]
+    fn test_char_span_snaps_to_tokens() {
+        let text = "\nÉté 🎉 ends\n\nnext";
+        let doc = LayeredDocument::from_text(text);
+
+        // "té 🎉 e" starts and ends mid-word: snapped out to whole tokens
+        let span = DocSpan::from_char_span(&doc, CharSpan::new(2, 9)).unwrap();
+        assert_eq!(span, DocSpan::single_line(0, 0, 4));
+        assert_eq!(span.to_char_span(&doc).unwrap().slice(text), Some("Été 🎉 ends"));
+
+        // Leading and blank lines snap to the nearest tokens inside the range
+        let across = DocSpan::from_char_span(&doc, CharSpan::new(0, 17)).unwrap();
+        assert_eq!(across, DocSpan::new(DocPosition::new(0, 0), DocPosition::new(1, 0)));
+        assert_eq!(DocSpan::from_char_span(&doc, CharSpan::new(0, 1)), None);
+        assert_eq!(DocSpan::from_char_span(&doc, CharSpan::new(11, 13)), None);
+        assert_eq!(DocSpan::from_char_span(&doc, CharSpan::new(3, 3)), None);
+        assert_eq!(DocSpan::single_line(0, 9, 9).to_char_span(&doc), None);
+    }
+
     #[test]
     fn test_doc_span_single_line() {
         let span = DocSpan::single_line(5, 0, 10);
@@ -419,6 +1305,22 @@ mod tests {
         assert_eq!(span.line_count(), 3);
     }
 
+    #[test]
+    fn test_doc_span_from_line_coordinates() {
+        use layered_nlp::{x, TextTag};
+
+        let doc = LayeredDocument::from_text("ARTICLE 1\nThe Supplier shall deliver.");
+        let line = doc.get_line(1).unwrap();
+        let words = line.find(&x::attr_eq(&TextTag::WORD));
+        // "shall" starts at byte 13 but is the fifth token
+        assert_eq!(words[2].range(), (13, 18));
+        assert_eq!(DocSpan::from_find(1, &words[2]), DocSpan::single_line(1, 4, 4));
+
+        assert_eq!(doc.span_from_line_range(1, 2, 4), DocSpan::single_line(1, 2, 4));
+        // Clamped to the last token, "."
+        assert_eq!(doc.span_from_line_range(1, 6, 99), DocSpan::single_line(1, 6, 7));
+    }
+
     #[test]
     fn test_document_from_text() {
         let text = "Section 1. Introduction\nThis is the intro.\n\nSection 2. Terms";
@@ -444,6 +1346,51 @@ mod tests {
         assert_eq!(doc.source_line_number(4), None);    // Out of bounds
     }
 
+    #[test]
+    fn test_line_endings_round_trip() {
+        let text = "First line.\r\n\r\nSecond line.\nLast line.";
+        let doc = LayeredDocument::from_text(text);
+
+        assert_eq!(doc.to_text(), text);
+        assert_eq!(doc.line_count(), 3);
+        assert_eq!(doc.line_ending(0), Some(LineEnding::CrLf));
+        assert_eq!(doc.line_ending(1), Some(LineEnding::Lf));
+        assert_eq!(doc.line_ending(2), None);
+        assert_eq!(doc.line_ending(3), None);
+        assert!(!doc.ends_with_newline());
+        assert_eq!(
+            doc.source_line_endings(),
+            vec![Some(LineEnding::CrLf), Some(LineEnding::CrLf), Some(LineEnding::Lf), None]
+        );
+
+        let rebuilt: String = doc
+            .original_text()
+            .lines()
+            .zip(doc.source_line_endings())
+            .map(|(line, ending)| format!("{}{}", line, ending.map_or("", LineEnding::as_str)))
+            .collect();
+        assert_eq!(rebuilt, text);
+
+        // Lines are tokenized without their carriage return
+        let last_token = doc.lines()[0].ll_tokens().last().unwrap();
+        assert!(matches!(last_token.get_token(), layered_nlp::LToken::Text(t, _) if t == "."));
+    }
+
+    #[test]
+    fn test_line_endings_trailing_newline_and_empty() {
+        let doc = LayeredDocument::from_text("Only line.\r\n");
+        assert_eq!(doc.to_text(), "Only line.\r\n");
+        assert!(doc.ends_with_newline());
+        assert_eq!(doc.source_line_endings(), vec![Some(LineEnding::CrLf)]);
+
+        let empty = LayeredDocument::from_text("");
+        assert_eq!(empty.to_text(), "");
+        assert_eq!(empty.line_count(), 0);
+        assert!(!empty.ends_with_newline());
+        assert!(empty.source_line_endings().is_empty());
+        assert_eq!(empty.line_ending(0), None);
+    }
+
     #[test]
     fn test_process_result() {
         let mut result = ProcessResult::ok(42);
@@ -455,26 +1402,91 @@ mod tests {
     }
 
     #[test]
-    fn test_docspan_contains() {
+    fn test_process_result_merges_warnings() {
+        let mut result = ProcessResult::ok(());
+        result.add_warning("no section headers");
+
+        let mut pass = ProcessResult::ok(7);
+        let reference = DocSpan::single_line(2, 1, 3);
+        pass.add_warning(DocumentWarning::new(WarningSeverity::Info, "unusual casing").at(reference));
+        pass.add_error(ProcessError::DanglingReference {
+            reference: "Section 9".into(),
+            location: reference,
+        });
+        assert_eq!(result.merge(pass), 7);
+
+        assert_eq!(result.warnings.len(), 2);
+        assert_eq!(result.warnings[0].severity, WarningSeverity::Warning);
+        assert_eq!(result.warnings[0].span, None);
+        assert_eq!(result.warnings[1].to_string(), "info: Line 2: unusual casing");
+
+        let dangling = DocumentWarning::from_error(&result.errors[0]);
+        assert_eq!(dangling.severity, WarningSeverity::Error);
+        assert_eq!(dangling.span, Some(reference));
+        assert!(WarningSeverity::Error > WarningSeverity::Info);
+    }
+
+    #[test]
+    fn test_docspan_contains_position() {
         let span = DocSpan::new(
             DocPosition { line: 1, token: 5 },
             DocPosition { line: 3, token: 10 },
         );
 
         // Inside
-        assert!(span.contains(&DocPosition { line: 2, token: 0 }));
-        assert!(span.contains(&DocPosition { line: 1, token: 5 })); // start boundary
-        assert!(span.contains(&DocPosition { line: 3, token: 10 })); // end boundary
+        assert!(span.contains_position(&DocPosition { line: 2, token: 0 }));
+        assert!(span.contains_position(&DocPosition { line: 1, token: 5 })); // start boundary
+        assert!(span.contains_position(&DocPosition { line: 3, token: 10 })); // end boundary
 
         // Outside
-        assert!(!span.contains(&DocPosition { line: 0, token: 5 })); // before
-        assert!(!span.contains(&DocPosition { line: 4, token: 0 })); // after
-        assert!(!span.contains(&DocPosition { line: 1, token: 4 })); // same line, before start token
-        assert!(!span.contains(&DocPosition { line: 3, token: 11 })); // same line, after end token
+        assert!(!span.contains_position(&DocPosition { line: 0, token: 5 })); // before
+        assert!(!span.contains_position(&DocPosition { line: 4, token: 0 })); // after
+        assert!(!span.contains_position(&DocPosition { line: 1, token: 4 })); // same line, before start token
+        assert!(!span.contains_position(&DocPosition { line: 3, token: 11 })); // same line, after end token
     }
 
     #[test]
-    fn test_docspan_overlaps() {
+    fn test_docspan_contains_span() {
+        let outer = DocSpan::new(DocPosition::new(1, 5), DocPosition::new(3, 2));
+
+        // Fully nested, including a multi-line span ending on a later line
+        // with a smaller token index than it starts with
+        assert!(outer.contains(&DocSpan::single_line(2, 0, 40)));
+        assert!(outer.contains(&DocSpan::new(DocPosition::new(1, 30), DocPosition::new(3, 1))));
+        assert!(outer.contains(&outer)); // boundaries included
+
+        // Sticking out at either end
+        assert!(!outer.contains(&DocSpan::single_line(1, 4, 6)));
+        assert!(!outer.contains(&DocSpan::single_line(3, 0, 3)));
+        // The inner span contains nothing larger than itself
+        assert!(!DocSpan::single_line(2, 0, 40).contains(&outer));
+    }
+
+    #[test]
+    fn test_docspan_intersects_adjacent_and_multi_line() {
+        // Adjacent on one line: 0-4 and 5-9 share no token
+        let left = DocSpan::single_line(0, 0, 4);
+        let right = DocSpan::single_line(0, 5, 9);
+        assert!(!left.intersects(&right));
+        assert!(!right.intersects(&left));
+        assert!(left.intersects(&DocSpan::single_line(0, 4, 9))); // touching at token 4
+
+        // Adjacent across a line break
+        let first = DocSpan::new(DocPosition::new(1, 3), DocPosition::new(2, 7));
+        let second = DocSpan::new(DocPosition::new(2, 8), DocPosition::new(4, 0));
+        assert!(!first.intersects(&second));
+
+        // A multi-line span intersects a span on any of its lines
+        assert!(first.intersects(&DocSpan::single_line(2, 0, 0)));
+        assert!(first.intersects(&DocSpan::single_line(1, 50, 60)));
+        assert!(!first.intersects(&DocSpan::single_line(1, 0, 2)));
+
+        // Nested spans intersect
+        assert!(first.intersects(&DocSpan::single_line(1, 10, 20)));
+    }
+
+    #[test]
+    fn test_docspan_intersects() {
         let span1 = DocSpan::new(
             DocPosition { line: 1, token: 0 },
             DocPosition { line: 1, token: 10 },
@@ -488,10 +1500,44 @@ mod tests {
             DocPosition { line: 1, token: 20 },
         );
 
-        assert!(span1.overlaps(&span2)); // overlap
-        assert!(span2.overlaps(&span1)); // symmetric
-        assert!(!span1.overlaps(&span3)); // no overlap (adjacent but not overlapping)
-        assert!(span2.overlaps(&span3)); // overlap
+        assert!(span1.intersects(&span2)); // overlap
+        assert!(span2.intersects(&span1)); // symmetric
+        assert!(!span1.intersects(&span3)); // no overlap (adjacent but not overlapping)
+        assert!(span2.intersects(&span3)); // overlap
+    }
+
+    #[test]
+    fn test_find_text_multi_token() {
+        let doc = LayeredDocument::from_text(
+            "Payment is due within 30 days.\nNotice must be given 30  days prior.",
+        );
+        let spans = doc.find_text("30 days", false);
+
+        // "30", " ", "days" -> tokens 8..=10 on the first line
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0], DocSpan::single_line(0, 8, 10));
+        assert_eq!(spans[1].start.line, 1);
+        assert!(spans[1].is_single_line());
+    }
+
+    #[test]
+    fn test_find_text_case_sensitivity() {
+        let doc = LayeredDocument::from_text("Force Majeure applies.\nNo force majeure here.");
+
+        assert_eq!(doc.find_text("force majeure", false).len(), 1);
+        assert_eq!(doc.find_text("force majeure", true).len(), 2);
+    }
+
+    #[test]
+    fn test_find_text_respects_token_and_line_boundaries() {
+        let doc = LayeredDocument::from_text("within 30\ndays of notice, the days");
+
+        // Needle spanning the line break never matches
+        assert!(doc.find_text("30 days", false).is_empty());
+        // Partial tokens never match
+        assert!(doc.find_text("day", false).is_empty());
+        assert_eq!(doc.find_text("days", false).len(), 2);
+        assert!(doc.find_text("   ", false).is_empty());
     }
 }
 
@@ -619,3 +1665,276 @@ mod document_resolver_tests {
         assert_eq!(line_counts[0].count, 3);
     }
 }
+
+#[cfg(test)]
+mod language_filter_tests {
+    use super::*;
+    use layered_nlp::{x, LLCursorAssignment, LLSelection};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Seen;
+
+    struct SeenResolver;
+
+    impl Resolver for SeenResolver {
+        type Attr = Seen;
+
+        fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+            selection
+                .find_by(&x::token_text())
+                .into_iter()
+                .take(1)
+                .map(|(sel, _)| sel.finish_with_attr(Seen))
+                .collect()
+        }
+    }
+
+    fn seen_lines(doc: &LayeredDocument) -> Vec<bool> {
+        doc.lines()
+            .iter()
+            .map(|line| !line.find(&x::attr::<Seen>()).is_empty())
+            .collect()
+    }
+
+    const BILINGUAL: &str = "ARTICLE 1\n\
+        The Supplier shall deliver the goods.\n\
+        Le Fournisseur doit livrer les marchandises.";
+
+    #[test]
+    fn test_language_filter_skips_other_languages() {
+        let doc = LayeredDocument::from_text(BILINGUAL)
+            .with_language_filter(Lang::English)
+            .run_resolver(&SeenResolver);
+
+        assert_eq!(doc.language_filter(), Some(Lang::English));
+        assert_eq!(doc.line_language(2), Some(Lang::French));
+        // Heading (unknown language) and English line are annotated, French line is not
+        assert_eq!(seen_lines(&doc), vec![true, true, false]);
+        // Line indices are unchanged
+        assert_eq!(doc.line_count(), 3);
+    }
+
+    #[test]
+    fn test_no_language_filter_runs_everywhere() {
+        let doc = LayeredDocument::from_text(BILINGUAL).run_resolver(&SeenResolver);
+        assert_eq!(seen_lines(&doc), vec![true, true, true]);
+    }
+
+    #[test]
+    fn test_ignored_lines_are_skipped_but_keep_indices() {
+        let doc = LayeredDocument::from_text(
+            "# reviewer: check this clause\n\
+             The Supplier shall deliver the goods.\n\
+             [page 2]\n\
+             The Buyer shall pay.",
+        )
+        .with_ignored_lines(|line| line.starts_with('#') || line.starts_with("[page"))
+        .run_resolver(&SeenResolver);
+
+        assert_eq!(seen_lines(&doc), vec![false, true, false, true]);
+        assert_eq!(doc.line_count(), 4);
+        assert!(doc.is_ignored_line(0) && !doc.is_ignored_line(1));
+        assert_eq!(doc.source_line_number(3), Some(4));
+    }
+}
+
+#[cfg(test)]
+mod resolver_panic_tests {
+    use super::*;
+    use layered_nlp::{x, LLCursorAssignment, LLSelection};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Marked;
+
+    /// Marks the first token of each line, panicking on lines containing "boom".
+    struct FragileResolver;
+
+    impl Resolver for FragileResolver {
+        type Attr = Marked;
+
+        fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+            let tokens = selection.find_by(&x::token_text());
+            if tokens.iter().any(|(_, text)| *text == "boom") {
+                panic!("cannot handle boom");
+            }
+            tokens
+                .into_iter()
+                .take(1)
+                .map(|(sel, _)| sel.finish_with_attr(Marked))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_try_run_resolver_locates_panics() {
+        let result = LayeredDocument::from_text("first line\nthis goes boom\nthird line")
+            .try_run_resolver(&FragileResolver);
+
+        assert_eq!(result.errors.len(), 1);
+        match &result.errors[0] {
+            ProcessError::ResolverPanic { resolver, line, message } => {
+                assert!(resolver.ends_with("FragileResolver"));
+                assert_eq!(*line, 1);
+                assert_eq!(message, "cannot handle boom");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert_eq!(result.errors[0].line(), Some(1));
+
+        // Other lines were still processed, and the failing line is intact
+        let marked: Vec<bool> = result
+            .value
+            .lines()
+            .iter()
+            .map(|line| !line.find(&x::attr::<Marked>()).is_empty())
+            .collect();
+        assert_eq!(marked, vec![true, false, true]);
+        assert_eq!(result.value.line_count(), 3);
+    }
+
+    #[test]
+    fn test_try_from_text_without_failures() {
+        let result = LayeredDocument::try_from_text("Line 1\n\nLine 3");
+        assert!(!result.has_errors());
+        assert_eq!(result.value.line_count(), 2);
+        assert_eq!(result.value.source_line_number(1), Some(3));
+    }
+
+    #[test]
+    fn test_located_error_display() {
+        let err = ProcessError::InconsistentNumbering {
+            expected: "2".to_string(),
+            found: "3".to_string(),
+            line: 4,
+        };
+        assert_eq!(err.to_string(), "Line 4: expected section '2', found '3'");
+        assert_eq!(err.line(), Some(4));
+        assert_eq!(ProcessError::Other("x".into()).line(), None);
+
+        // A line that failed to tokenize has no document line, only a source line
+        let err = ProcessError::TokenizationFailed {
+            source_line: 5,
+            message: "bad input".to_string(),
+        };
+        assert_eq!(err.to_string(), "Source line 5: tokenization failed: bad input");
+        assert_eq!(err.line(), None);
+        assert_eq!(DocumentWarning::from_error(&err).span, None);
+    }
+}
+
+#[cfg(test)]
+mod overlap_tests {
+    use super::*;
+    use layered_nlp::{x, LLCursorAssignment, LLSelection};
+
+    /// Marks every run of tokens from `first` through `last` with a fixed confidence.
+    struct SpanMarker {
+        first: &'static str,
+        last: &'static str,
+        confidence: f64,
+    }
+
+    impl Resolver for SpanMarker {
+        type Attr = Scored<String>;
+
+        fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+            let mut assignments = Vec::new();
+            for (start, text) in selection.find_by(&x::token_text()) {
+                if text != self.first {
+                    continue;
+                }
+                let mut current = start;
+                let mut current_text = text.to_string();
+                while current_text != self.last {
+                    let next = current
+                        .match_first_forwards(&x::token_text())
+                        .map(|(next, next_text)| (next, next_text.to_string()));
+                    match next {
+                        Some((next, next_text)) => {
+                            current = next;
+                            current_text = next_text;
+                        }
+                        None => break,
+                    }
+                }
+                if current_text == self.last {
+                    let label = format!("{}..{}", self.first, self.last);
+                    let scored = Scored::rule_based(label, self.confidence, "test");
+                    assignments.push(current.finish_with_attr(scored));
+                }
+            }
+            assignments
+        }
+    }
+
+    fn labels(doc: &LayeredDocument) -> Vec<String> {
+        let mut labels: Vec<String> = doc
+            .lines()
+            .iter()
+            .flat_map(|line| {
+                line.find(&x::attr::<Scored<String>>())
+                    .into_iter()
+                    .map(|found| found.attr().value.clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        labels.sort();
+        labels
+    }
+
+    fn annotated() -> LayeredDocument {
+        LayeredDocument::from_text("Pay the Company now.\nThe Vendor agrees.")
+            .run_resolver(&SpanMarker { first: "Company", last: "Company", confidence: 0.9 })
+            .run_resolver(&SpanMarker { first: "the", last: "Company", confidence: 0.7 })
+            .run_resolver(&SpanMarker { first: "Vendor", last: "Vendor", confidence: 0.5 })
+    }
+
+    #[test]
+    fn test_keep_highest_confidence() {
+        let doc = annotated().resolve_overlaps::<String>(OverlapPolicy::KeepHighestConfidence);
+        assert_eq!(labels(&doc), vec!["Company..Company", "Vendor..Vendor"]);
+    }
+
+    #[test]
+    fn test_keep_longest() {
+        let doc = annotated().resolve_overlaps::<String>(OverlapPolicy::KeepLongest);
+        assert_eq!(labels(&doc), vec!["Vendor..Vendor", "the..Company"]);
+    }
+
+    #[test]
+    fn test_keep_both_and_other_types_untouched() {
+        let doc = annotated().resolve_overlaps::<String>(OverlapPolicy::KeepBoth);
+        assert_eq!(labels(&doc).len(), 3);
+
+        // Policies only touch Scored<T> for the requested T
+        let doc = annotated().resolve_overlaps::<u32>(OverlapPolicy::KeepLongest);
+        assert_eq!(labels(&doc).len(), 3);
+    }
+
+    #[test]
+    fn test_memoized_builds_once_per_type() {
+        use std::cell::Cell;
+
+        let builds = Cell::new(0);
+        let line_count = |doc: &LayeredDocument| {
+            builds.set(builds.get() + 1);
+            doc.line_count()
+        };
+
+        let doc = annotated();
+        let first: *const usize = doc.memoized(line_count);
+        let second: *const usize = doc.memoized(line_count);
+        assert_eq!(first, second);
+        assert_eq!(builds.get(), 1);
+
+        // Distinct types get their own slots
+        assert_eq!(doc.memoized(|doc| doc.original_text().len() as u32), &39);
+        assert_eq!(*doc.memoized(line_count), 2);
+        assert_eq!(builds.get(), 1);
+
+        // Running a resolver invalidates the memo
+        let doc = doc.run_resolver(&SpanMarker { first: "Pay", last: "Pay", confidence: 1.0 });
+        doc.memoized(line_count);
+        assert_eq!(builds.get(), 2);
+    }
+}
@@ -0,0 +1,71 @@
+//! Optional `tracing` instrumentation of pipeline stages.
+//!
+//! With the `tracing` feature, each [`StageSpan`] enters an info-level span
+//! named `stage` carrying the stage's `kind` and `label`, and on
+//! [`finish`](StageSpan::finish) emits an event with the number of annotations
+//! the stage produced and its duration in microseconds. Without the feature a
+//! `StageSpan` is empty and the calls compile to nothing.
+//!
+//! Stages nest: the resolver spans of a pipeline run appear inside its
+//! `pipeline` span.
+
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+/// One pipeline stage being traced: a resolver run or a document-level pass.
+#[must_use = "a stage reports nothing until it is finished"]
+pub struct StageSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "tracing")]
+    started: Instant,
+}
+
+impl StageSpan {
+    /// Enter a stage. `kind` groups stages ("resolver", "document_resolver",
+    /// "analysis"); `label` tells stages of a kind apart, e.g. the resolver's
+    /// type name.
+    #[inline]
+    pub fn start(kind: &'static str, label: &str) -> Self {
+        #[cfg(not(feature = "tracing"))]
+        let _ = (kind, label);
+        StageSpan {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!("stage", kind, label).entered(),
+            #[cfg(feature = "tracing")]
+            started: Instant::now(),
+        }
+    }
+
+    /// Leave the stage, reporting the annotations it produced.
+    ///
+    /// `annotations` is only called when the `tracing` feature is on, so it
+    /// may count by walking the document.
+    #[inline]
+    pub fn finish(self, annotations: impl FnOnce() -> usize) {
+        #[cfg(feature = "tracing")]
+        {
+            let annotations = annotations();
+            let elapsed_us = self.started.elapsed().as_micros() as u64;
+            tracing::info!(annotations, elapsed_us, "stage finished");
+            drop(self.span);
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = annotations;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotations_are_counted_only_when_tracing() {
+        let mut counted = false;
+        StageSpan::start("test", "counting").finish(|| {
+            counted = true;
+            0
+        });
+        assert_eq!(counted, cfg!(feature = "tracing"));
+    }
+}
@@ -0,0 +1,113 @@
+//! Lightweight per-line language detection.
+//!
+//! Bilingual contracts often interleave an English line with its translation.
+//! This module provides a stopword-ratio heuristic that is good enough to tell
+//! those lines apart so resolvers can skip the translated text.
+
+use layered_nlp::{LLLine, LToken, TextTag};
+
+/// Languages recognized by the stopword heuristic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Lang {
+    English,
+    French,
+    Spanish,
+    German,
+    /// Too few stopwords to decide (headings, numbers, very short lines)
+    Unknown,
+}
+
+const ENGLISH_STOPWORDS: &[&str] = &[
+    "the", "and", "of", "to", "shall", "is", "be", "in", "for", "with", "by", "any", "or",
+    "this", "that", "which", "not", "all", "such", "will", "may", "must", "its", "their",
+];
+
+const FRENCH_STOPWORDS: &[&str] = &[
+    "le", "la", "les", "des", "du", "et", "est", "une", "pour", "dans", "par", "sur", "qui",
+    "pas", "sera", "doit", "aux", "au", "ce", "cette", "ou", "leur", "ses",
+];
+
+const SPANISH_STOPWORDS: &[&str] = &[
+    "el", "los", "las", "del", "y", "una", "para", "con", "será", "debe", "está", "sus", "o",
+    "lo", "al", "como", "entre",
+];
+
+const GERMAN_STOPWORDS: &[&str] = &[
+    "der", "die", "das", "und", "ist", "nicht", "mit", "für", "von", "zu", "den", "dem",
+    "ein", "eine", "wird", "sich", "oder", "muss", "auf",
+];
+
+/// Minimum share of words that must be stopwords of the winning language.
+const MIN_STOPWORD_RATIO: f64 = 0.15;
+
+/// Detect the language of a line by counting language-specific stopwords.
+///
+/// Returns [`Lang::Unknown`] when no language reaches the minimum stopword
+/// ratio or when two languages tie.
+pub fn detect_line_language(line: &LLLine) -> Lang {
+    let words: Vec<String> = line
+        .ll_tokens()
+        .iter()
+        .filter_map(|token| match token.get_token() {
+            LToken::Text(text, TextTag::WORD) => Some(text.to_lowercase()),
+            _ => None,
+        })
+        .collect();
+
+    if words.is_empty() {
+        return Lang::Unknown;
+    }
+
+    let candidates = [
+        (Lang::English, ENGLISH_STOPWORDS),
+        (Lang::French, FRENCH_STOPWORDS),
+        (Lang::Spanish, SPANISH_STOPWORDS),
+        (Lang::German, GERMAN_STOPWORDS),
+    ];
+
+    let mut counts: Vec<(Lang, usize)> = candidates
+        .iter()
+        .map(|(lang, stopwords)| {
+            let hits = words.iter().filter(|w| stopwords.contains(&w.as_str())).count();
+            (*lang, hits)
+        })
+        .collect();
+    counts.sort_by_key(|&(_, hits)| std::cmp::Reverse(hits));
+
+    let (best_lang, best_hits) = counts[0];
+    let runner_up_hits = counts[1].1;
+    let ratio = best_hits as f64 / words.len() as f64;
+
+    if best_hits == 0 || best_hits == runner_up_hits || ratio < MIN_STOPWORD_RATIO {
+        Lang::Unknown
+    } else {
+        best_lang
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(text: &str) -> Lang {
+        detect_line_language(&layered_nlp::create_line_from_string(text))
+    }
+
+    #[test]
+    fn test_detect_english_and_french() {
+        assert_eq!(detect("The Supplier shall deliver the goods within thirty days."), Lang::English);
+        assert_eq!(detect("Le Fournisseur doit livrer les marchandises dans les trente jours."), Lang::French);
+    }
+
+    #[test]
+    fn test_detect_spanish_and_german() {
+        assert_eq!(detect("El Proveedor debe entregar los bienes para el Comprador."), Lang::Spanish);
+        assert_eq!(detect("Der Lieferant muss die Waren an den Käufer liefern."), Lang::German);
+    }
+
+    #[test]
+    fn test_headings_are_unknown() {
+        assert_eq!(detect("ARTICLE 12"), Lang::Unknown);
+        assert_eq!(detect("12.3"), Lang::Unknown);
+    }
+}
@@ -7,8 +7,13 @@
 //!
 //! - [`LayeredDocument`] - Multi-line document abstraction
 //! - [`DocPosition`] / [`DocSpan`] - Position within documents
+//! - [`CharSpan`] - Character offsets into the original text
 //! - [`Scored<T>`] - Values with confidence scores
 //! - [`Ambiguous<T>`] - N-best alternatives with ambiguity detection
+//! - [`DocQuery`] - Fluent filtering of line-level annotations
+//! - [`AnchoredSpan`] - Spans re-located by their text after edits
+//! - [`PositionIndex`] - Annotations at a position, for editor lookups
+//! - [`StageSpan`] - `tracing` spans per resolver run (`tracing` feature)
 //!
 //! ## Example
 //!
@@ -22,28 +27,69 @@
 //! assert!(scored.needs_verification());
 //! ```
 
+mod anchor;
 mod document;
+mod language;
 mod scored;
 mod ambiguity;
 mod reviewable;
 mod span_link;
 mod scope_operator;
 mod scope_index;
+mod link_index;
+mod scope_resolver;
+mod query;
+mod position_index;
+mod instrument;
 
 // Document types
 pub use document::{
+    CharSpan,
     DocPosition,
     DocSpan,
     DocumentResolver,
+    DocumentWarning,
+    Evidence,
     LayeredDocument,
+    LineEnding,
+    OverlapPolicy,
     ProcessError,
     ProcessResult,
+    WarningSeverity,
 };
 
+// Text anchoring
+pub use anchor::{reanchor, AnchoredSpan};
+
+// Annotation queries
+pub use query::{
+    Annotation,
+    DocQuery,
+    HasConfidence,
+    TypedQuery,
+};
+
+// Language detection
+pub use position_index::{
+    AnnotationRef,
+    PositionIndex,
+};
+
+pub use language::{
+    detect_line_language,
+    Lang,
+};
+
+// Optional tracing of pipeline stages
+pub use instrument::StageSpan;
+
 // Scoring infrastructure
 pub use scored::{
+    ConfidenceAdjustment,
     Scored,
     ScoreSource,
+    ScoreSourceKind,
+    ScoreSourcePolicy,
 };
 
 // Ambiguity infrastructure
@@ -51,6 +97,7 @@ pub use ambiguity::{
     AmbiguityFlag,
     AmbiguityConfig,
     Ambiguous,
+    MergePolicy,
 };
 
 // Reviewable infrastructure
@@ -84,3 +131,14 @@ pub use scope_operator::{
 
 // Scope index
 pub use scope_index::ScopeIndex;
+
+// Link index
+pub use link_index::{LinkIndex, LinkTraversal};
+
+// Scope composition
+pub use scope_resolver::{
+    PolarityEffect,
+    PolarityOp,
+    ResolvedPolarity,
+    ScopeResolver,
+};
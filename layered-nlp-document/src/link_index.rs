@@ -0,0 +1,224 @@
+//! Index and fluent traversal over span-to-span links.
+
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use crate::{DocSpan, DocSpanLink};
+
+/// Index of [`DocSpanLink`]s by anchor and target span.
+///
+/// Links are stored as `(anchor, link)` pairs, the anchor being the span the
+/// link was attached to. [`from`](Self::from) starts a [`LinkTraversal`] for
+/// chaining hops:
+///
+/// ```
+/// use layered_nlp_document::{DocSpan, DocSpanLink, LinkIndex};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Role {
+///     Coreference,
+///     Obligor,
+/// }
+///
+/// let pronoun = DocSpan::single_line(1, 0, 0);
+/// let company = DocSpan::single_line(0, 0, 1);
+/// let obligation = DocSpan::single_line(2, 2, 4);
+///
+/// let mut index = LinkIndex::new();
+/// index.insert(pronoun, DocSpanLink::new(Role::Coreference, company));
+/// index.insert(obligation, DocSpanLink::new(Role::Obligor, company));
+///
+/// // What does the entity "it" refers to owe?
+/// let owed = index
+///     .from(pronoun)
+///     .follow(Role::Coreference)
+///     .follow_incoming(Role::Obligor)
+///     .collect();
+/// assert_eq!(owed, vec![obligation]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LinkIndex<R> {
+    links: Vec<(DocSpan, DocSpanLink<R>)>,
+}
+
+impl<R> Default for LinkIndex<R> {
+    fn default() -> Self {
+        Self { links: Vec::new() }
+    }
+}
+
+impl<R> FromIterator<(DocSpan, DocSpanLink<R>)> for LinkIndex<R> {
+    fn from_iter<I: IntoIterator<Item = (DocSpan, DocSpanLink<R>)>>(iter: I) -> Self {
+        Self {
+            links: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<R: PartialEq> LinkIndex<R> {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a link anchored at `anchor`.
+    pub fn insert(&mut self, anchor: DocSpan, link: DocSpanLink<R>) {
+        self.links.push((anchor, link));
+    }
+
+    /// Links anchored at `span`.
+    pub fn outgoing<'a>(
+        &'a self,
+        span: &'a DocSpan,
+    ) -> impl Iterator<Item = &'a DocSpanLink<R>> + 'a {
+        self.links
+            .iter()
+            .filter(move |(anchor, _)| anchor == span)
+            .map(|(_, link)| link)
+    }
+
+    /// Links pointing at `span`, as `(anchor, link)`.
+    pub fn incoming<'a>(
+        &'a self,
+        span: &'a DocSpan,
+    ) -> impl Iterator<Item = (&'a DocSpan, &'a DocSpanLink<R>)> + 'a {
+        self.links
+            .iter()
+            .filter(move |(_, link)| link.target == *span)
+            .map(|(anchor, link)| (anchor, link))
+    }
+
+    /// Start a traversal at `span`.
+    pub fn from(&self, span: DocSpan) -> LinkTraversal<'_, R> {
+        LinkTraversal {
+            index: self,
+            frontier: vec![span],
+            visited: std::iter::once(span).collect(),
+        }
+    }
+
+    /// Total number of links in this index.
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    /// Check if this index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+}
+
+/// A set of spans reached by following links, built by chaining hops.
+///
+/// Each hop replaces the current spans with the spans one link away. A span
+/// reached by an earlier hop is never entered again, so cyclic link graphs
+/// terminate: a traversal that can only go back where it came from ends
+/// empty.
+#[derive(Debug, Clone)]
+pub struct LinkTraversal<'a, R> {
+    index: &'a LinkIndex<R>,
+    frontier: Vec<DocSpan>,
+    visited: HashSet<DocSpan>,
+}
+
+impl<'a, R: PartialEq> LinkTraversal<'a, R> {
+    /// Follow links with `role` from the current spans to their targets.
+    pub fn follow(self, role: R) -> Self {
+        let index = self.index;
+        self.hop(|span| {
+            index
+                .outgoing(span)
+                .filter(|link| link.role == role)
+                .map(|link| link.target)
+                .collect()
+        })
+    }
+
+    /// Follow links with `role` backwards, from the current spans to the
+    /// anchors of links pointing at them.
+    pub fn follow_incoming(self, role: R) -> Self {
+        let index = self.index;
+        self.hop(|span| {
+            index
+                .incoming(span)
+                .filter(|(_, link)| link.role == role)
+                .map(|(anchor, _)| *anchor)
+                .collect()
+        })
+    }
+
+    fn hop(mut self, next: impl Fn(&DocSpan) -> Vec<DocSpan>) -> Self {
+        let mut frontier = Vec::new();
+        for span in &self.frontier {
+            for reached in next(span) {
+                if self.visited.insert(reached) {
+                    frontier.push(reached);
+                }
+            }
+        }
+        self.frontier = frontier;
+        self
+    }
+
+    /// The spans reached by the last hop, in link order.
+    pub fn collect(self) -> Vec<DocSpan> {
+        self.frontier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClauseRole;
+
+    fn span(line: usize) -> DocSpan {
+        DocSpan::single_line(line, 0, 1)
+    }
+
+    #[test]
+    fn test_follow_chains_hops() {
+        let index: LinkIndex<ClauseRole> = vec![
+            (span(0), DocSpanLink::new(ClauseRole::Child, span(1))),
+            (span(0), DocSpanLink::new(ClauseRole::Child, span(2))),
+            (span(1), DocSpanLink::new(ClauseRole::CrossReference, span(5))),
+            (span(2), DocSpanLink::new(ClauseRole::CrossReference, span(5))),
+            (span(2), DocSpanLink::new(ClauseRole::Conjunct, span(3))),
+        ]
+        .into_iter()
+        .collect();
+
+        let children = index.from(span(0)).follow(ClauseRole::Child).collect();
+        assert_eq!(children, vec![span(1), span(2)]);
+
+        // Both children reach span 5; it is reported once
+        let referenced = index
+            .from(span(0))
+            .follow(ClauseRole::Child)
+            .follow(ClauseRole::CrossReference)
+            .collect();
+        assert_eq!(referenced, vec![span(5)]);
+
+        let referrers = index.from(span(5)).follow_incoming(ClauseRole::CrossReference).collect();
+        assert_eq!(referrers, vec![span(1), span(2)]);
+        assert!(index.from(span(3)).follow(ClauseRole::Child).collect().is_empty());
+    }
+
+    #[test]
+    fn test_cycles_terminate() {
+        let mut index = LinkIndex::new();
+        index.insert(span(0), DocSpanLink::new(ClauseRole::Conjunct, span(1)));
+        index.insert(span(1), DocSpanLink::new(ClauseRole::Conjunct, span(0)));
+        assert_eq!(index.len(), 2);
+
+        let once = index.from(span(0)).follow(ClauseRole::Conjunct);
+        assert_eq!(once.clone().collect(), vec![span(1)]);
+        // Back to the start is a visited span, so the cycle ends
+        assert!(once.follow(ClauseRole::Conjunct).collect().is_empty());
+
+        let mut traversal = index.from(span(0));
+        for _ in 0..10 {
+            traversal = traversal.follow(ClauseRole::Conjunct);
+        }
+        assert!(traversal.collect().is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+//! Position lookup over every line-level annotation of a document.
+//!
+//! [`LayeredDocument::annotation_map`] answers "what is annotated at this
+//! cursor?" without scanning each line's attributes. The [`PositionIndex`]
+//! sorts annotation spans by start and keeps, for each subtree of the implicit
+//! balanced tree over that order, the furthest end it reaches; overlap queries
+//! skip any subtree ending before the query, for O(log n + k) lookups.
+//!
+//! ```
+//! use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver};
+//! use layered_nlp_document::{DocPosition, LayeredDocument};
+//!
+//! #[derive(Debug)]
+//! struct Word;
+//! struct Words;
+//! impl Resolver for Words {
+//!     type Attr = Word;
+//!     fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+//!         selection
+//!             .find_by(&x::attr_eq(&layered_nlp::TextTag::WORD))
+//!             .into_iter()
+//!             .map(|(sel, _)| sel.finish_with_attr(Word))
+//!             .collect()
+//!     }
+//! }
+//!
+//! let doc = LayeredDocument::from_text("hello world").run_resolver(&Words);
+//! let at = doc.annotation_map().at(DocPosition::new(0, 2));
+//! assert_eq!(at.len(), 1);
+//! assert!(at[0].is::<Word>());
+//! ```
+
+use std::any::TypeId;
+
+use layered_nlp::TextTag;
+
+use crate::{DocPosition, DocSpan, LayeredDocument};
+
+/// A line-level annotation located by [`PositionIndex`], whatever its type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnnotationRef {
+    /// Where the annotation is attached
+    pub span: DocSpan,
+    /// Type of the annotation value
+    pub type_id: TypeId,
+    /// `std::any::type_name` of the annotation value
+    pub type_name: &'static str,
+    /// Index among the values of this type on the same span
+    pub index: usize,
+}
+
+impl AnnotationRef {
+    /// Whether the annotation value is a `T`.
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// The annotation value, if it is a `T`.
+    pub fn get<'d, T: 'static>(&self, doc: &'d LayeredDocument) -> Option<&'d T> {
+        if !self.is::<T>() {
+            return None;
+        }
+        let range = self.span.to_lrange()?;
+        doc.get_line(self.span.start.line)?
+            .query::<T>()
+            .into_iter()
+            .find(|(found, _, _)| *found == range)
+            .and_then(|(_, _, values)| values.get(self.index).copied())
+    }
+}
+
+/// `(line, token)`, ordered like positions in the document.
+type Key = (usize, usize);
+
+fn key(position: &DocPosition) -> Key {
+    (position.line, position.token)
+}
+
+/// Interval index over the spans of all line-level annotations.
+///
+/// Covers every attribute type uniformly except the `TextTag`s and `char`s
+/// assigned to each token at tokenization, which would otherwise make up most
+/// entries. Build it with [`LayeredDocument::annotation_map`].
+#[derive(Debug, Clone, Default)]
+pub struct PositionIndex {
+    /// Annotations sorted by span start
+    entries: Vec<AnnotationRef>,
+    /// Furthest end within the subtree rooted at each index, where the root of
+    /// `lo..hi` is its midpoint
+    max_end: Vec<Key>,
+}
+
+impl PositionIndex {
+    /// Index every line-level annotation of `doc`.
+    pub fn build(doc: &LayeredDocument) -> Self {
+        let tokenizer_types = [TypeId::of::<TextTag>(), TypeId::of::<char>()];
+        let mut entries: Vec<AnnotationRef> = doc
+            .lines_enumerated()
+            .flat_map(|(line_idx, line)| {
+                line.attr_types()
+                    .into_iter()
+                    .filter(|(_, type_id, _, _)| !tokenizer_types.contains(type_id))
+                    .flat_map(move |((start, end), type_id, type_name, count)| {
+                        (0..count).map(move |index| AnnotationRef {
+                            span: DocSpan::single_line(line_idx, start, end),
+                            type_id,
+                            type_name,
+                            index,
+                        })
+                    })
+            })
+            .collect();
+        entries.sort_by_key(|entry| key(&entry.span.start));
+
+        let mut max_end = vec![(0, 0); entries.len()];
+        Self::fill_max_end(&entries, &mut max_end, 0, entries.len());
+        Self { entries, max_end }
+    }
+
+    fn fill_max_end(entries: &[AnnotationRef], max_end: &mut [Key], lo: usize, hi: usize) -> Option<Key> {
+        if lo >= hi {
+            return None;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let left = Self::fill_max_end(entries, max_end, lo, mid);
+        let right = Self::fill_max_end(entries, max_end, mid + 1, hi);
+        let furthest = key(&entries[mid].span.end).max(left.max(right).unwrap_or_default());
+        max_end[mid] = furthest;
+        Some(furthest)
+    }
+
+    /// Annotations whose span contains `position`, in document order.
+    pub fn at(&self, position: DocPosition) -> Vec<AnnotationRef> {
+        self.overlapping(DocSpan::new(position, position))
+    }
+
+    /// Annotations whose span overlaps `span`, in document order.
+    pub fn overlapping(&self, span: DocSpan) -> Vec<AnnotationRef> {
+        let mut found = Vec::new();
+        self.collect(&span, 0, self.entries.len(), &mut found);
+        found
+    }
+
+    fn collect(&self, span: &DocSpan, lo: usize, hi: usize, found: &mut Vec<AnnotationRef>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        if self.max_end[mid] < key(&span.start) {
+            return;
+        }
+        self.collect(span, lo, mid, found);
+        // Entries from `mid` on start at or after it
+        if key(&self.entries[mid].span.start) > key(&span.end) {
+            return;
+        }
+        if self.entries[mid].span.intersects(span) {
+            found.push(self.entries[mid]);
+        }
+        self.collect(span, mid + 1, hi, found);
+    }
+
+    /// Number of indexed annotations.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no annotations are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver};
+
+    #[derive(Debug, PartialEq)]
+    struct Word(String);
+
+    #[derive(Debug, PartialEq)]
+    struct Pair;
+
+    struct Words;
+
+    impl Resolver for Words {
+        type Attr = Word;
+
+        fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+            selection
+                .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+                .into_iter()
+                .map(|(sel, (_, text))| sel.finish_with_attr(Word(text.to_string())))
+                .collect()
+        }
+    }
+
+    /// Each word together with the next word.
+    struct Pairs;
+
+    impl Resolver for Pairs {
+        type Attr = Pair;
+
+        fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+            selection
+                .find_by(&x::attr::<Word>())
+                .into_iter()
+                .filter_map(|(sel, _)| {
+                    let (ws, _) = sel.match_first_forwards(&x::whitespace())?;
+                    let (pair, _) = ws.match_first_forwards(&x::attr::<Word>())?;
+                    Some(pair.finish_with_attr(Pair))
+                })
+                .collect()
+        }
+    }
+
+    fn doc() -> LayeredDocument {
+        LayeredDocument::from_text("one two three\nfour five")
+            .run_resolver(&Words)
+            .run_resolver(&Pairs)
+    }
+
+    #[test]
+    fn test_at_position_finds_every_type() {
+        let doc = doc();
+        let map = doc.annotation_map();
+        // 5 words and 3 pairs; tokenizer tags are left out
+        assert_eq!(map.len(), 8);
+
+        // "two" is token 2 of line 0, inside both pairs of that line
+        let at = map.at(DocPosition::new(0, 2));
+        let words: Vec<&Word> = at.iter().filter_map(|a| a.get::<Word>(&doc)).collect();
+        assert_eq!(words, vec![&Word("two".to_string())]);
+        assert_eq!(at.iter().filter(|a| a.is::<Pair>()).count(), 2);
+
+        // Whitespace between words is covered only by the pair
+        let at = map.at(DocPosition::new(1, 1));
+        assert_eq!(at.len(), 1);
+        assert!(at[0].is::<Pair>());
+        assert!(at[0].get::<Word>(&doc).is_none());
+    }
+
+    #[test]
+    fn test_overlapping_matches_linear_scan() {
+        let doc = doc();
+        let map = doc.annotation_map();
+        let all = map.overlapping(DocSpan::new(DocPosition::new(0, 0), DocPosition::new(9, 0)));
+        assert_eq!(all.len(), map.len());
+
+        for line in 0..2 {
+            for start in 0..5 {
+                for end in start..5 {
+                    let span = DocSpan::single_line(line, start, end);
+                    let expected: Vec<AnnotationRef> =
+                        all.iter().filter(|a| a.span.intersects(&span)).copied().collect();
+                    assert_eq!(map.overlapping(span), expected, "{:?}", span);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_map_is_rebuilt_after_lines_change() {
+        let doc = LayeredDocument::from_text("one two");
+        assert!(doc.annotation_map().is_empty());
+        let doc = doc.run_resolver(&Words);
+        assert_eq!(doc.annotation_map().len(), 2);
+    }
+}
@@ -0,0 +1,310 @@
+//! Fluent queries over line-level annotations.
+//!
+//! [`LayeredDocument::query`] starts a [`DocQuery`]; [`DocQuery::of_type`]
+//! picks the attribute type and returns a [`TypedQuery`] that accumulates
+//! filters, an optional ordering and a limit. Nothing is evaluated until the
+//! query is iterated or collected, and without an ordering no intermediate
+//! `Vec` of matches is built.
+//!
+//! ```
+//! use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver};
+//! use layered_nlp_document::{LayeredDocument, Scored};
+//!
+//! struct Numbers;
+//! impl Resolver for Numbers {
+//!     type Attr = Scored<u32>;
+//!     fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+//!         selection
+//!             .find_by(&x::token_text())
+//!             .into_iter()
+//!             .filter_map(|(sel, text)| {
+//!                 let n: u32 = text.parse().ok()?;
+//!                 Some(sel.finish_with_attr(Scored::rule_based(n, n as f64 / 10.0, "n")))
+//!             })
+//!             .collect()
+//!     }
+//! }
+//!
+//! let doc = LayeredDocument::from_text("1 9\n5 7").run_resolver(&Numbers);
+//! let found: Vec<u32> = doc
+//!     .query()
+//!     .of_type::<Scored<u32>>()
+//!     .above_confidence(0.6)
+//!     .order_by_confidence()
+//!     .limit(2)
+//!     .collect()
+//!     .into_iter()
+//!     .map(|a| a.value.value)
+//!     .collect();
+//! assert_eq!(found, vec![9, 7]);
+//! ```
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use crate::{DocSpan, LayeredDocument, Scored};
+
+/// A line-level attribute together with its document span.
+#[derive(Debug)]
+pub struct Annotation<'a, T> {
+    /// Where the attribute is attached
+    pub span: DocSpan,
+    /// The attribute value
+    pub value: &'a T,
+}
+
+impl<T> Clone for Annotation<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Annotation<'_, T> {}
+
+/// Attribute types carrying a confidence score.
+pub trait HasConfidence {
+    /// Confidence score (0.0-1.0)
+    fn confidence(&self) -> f64;
+}
+
+impl<T> HasConfidence for Scored<T> {
+    fn confidence(&self) -> f64 {
+        self.confidence
+    }
+}
+
+/// Entry point of a query; see [`LayeredDocument::query`].
+#[derive(Debug, Clone, Copy)]
+pub struct DocQuery<'a> {
+    doc: &'a LayeredDocument,
+}
+
+impl<'a> DocQuery<'a> {
+    pub(crate) fn new(doc: &'a LayeredDocument) -> Self {
+        Self { doc }
+    }
+
+    /// Query line-level attributes of type `T`.
+    pub fn of_type<T: 'static>(self) -> TypedQuery<'a, T> {
+        TypedQuery {
+            doc: self.doc,
+            lines: 0..self.doc.line_count(),
+            filters: Vec::new(),
+            order: None,
+            limit: None,
+        }
+    }
+}
+
+type Filter<'a, T> = Box<dyn Fn(&Annotation<'a, T>) -> bool + 'a>;
+type Order<'a, T> = Box<dyn Fn(&Annotation<'a, T>, &Annotation<'a, T>) -> Ordering + 'a>;
+
+/// A query over attributes of type `T`, built up fluently.
+///
+/// Matches are produced in document order unless [`order_by`](Self::order_by)
+/// or [`order_by_confidence`](Self::order_by_confidence) is set.
+pub struct TypedQuery<'a, T> {
+    doc: &'a LayeredDocument,
+    lines: Range<usize>,
+    filters: Vec<Filter<'a, T>>,
+    order: Option<Order<'a, T>>,
+    limit: Option<usize>,
+}
+
+impl<'a, T: 'static> TypedQuery<'a, T> {
+    /// The document being queried.
+    pub fn document(&self) -> &'a LayeredDocument {
+        self.doc
+    }
+
+    /// Keep annotations starting inside `span`.
+    ///
+    /// Lines outside the span are not visited at all.
+    pub fn within(mut self, span: DocSpan) -> Self {
+        self.lines = self.lines.start.max(span.start.line)..self.lines.end.min(span.end.line + 1);
+        self.filter(move |annotation| span.contains_position(&annotation.span.start))
+    }
+
+    /// Keep annotations on the given lines.
+    pub fn on_lines(mut self, lines: Range<usize>) -> Self {
+        self.lines = self.lines.start.max(lines.start)..self.lines.end.min(lines.end);
+        self
+    }
+
+    /// Keep annotations whose value satisfies `predicate`.
+    pub fn matching(self, predicate: impl Fn(&T) -> bool + 'a) -> Self {
+        self.filter(move |annotation| predicate(annotation.value))
+    }
+
+    /// Keep annotations satisfying `predicate`.
+    pub fn filter(mut self, predicate: impl Fn(&Annotation<'a, T>) -> bool + 'a) -> Self {
+        self.filters.push(Box::new(predicate));
+        self
+    }
+
+    /// Order matches with `compare`; ties keep document order.
+    pub fn order_by(
+        mut self,
+        compare: impl Fn(&Annotation<'a, T>, &Annotation<'a, T>) -> Ordering + 'a,
+    ) -> Self {
+        self.order = Some(Box::new(compare));
+        self
+    }
+
+    /// Return at most `count` matches (after ordering).
+    pub fn limit(mut self, count: usize) -> Self {
+        self.limit = Some(count);
+        self
+    }
+
+    /// Number of matches, ignoring any limit.
+    pub fn count(self) -> usize {
+        let TypedQuery { doc, lines, filters, .. } = self;
+        Self::matches(doc, lines, filters).count()
+    }
+
+    /// The first match in query order.
+    pub fn first(self) -> Option<Annotation<'a, T>> {
+        self.into_iter().next()
+    }
+
+    /// Evaluate the query.
+    pub fn collect(self) -> Vec<Annotation<'a, T>> {
+        self.into_iter().collect()
+    }
+
+    /// Filtered matches in document order.
+    fn matches(
+        doc: &'a LayeredDocument,
+        lines: Range<usize>,
+        filters: Vec<Filter<'a, T>>,
+    ) -> impl Iterator<Item = Annotation<'a, T>> + 'a {
+        let start = lines.start.min(doc.line_count());
+        let end = lines.end.clamp(start, doc.line_count());
+        doc.lines()[start..end]
+            .iter()
+            .enumerate()
+            .flat_map(move |(offset, line)| {
+                let line_idx = start + offset;
+                let mut found = line.query::<T>();
+                found.sort_by_key(|(range, _, _)| *range);
+                found.into_iter().flat_map(move |((first, last), _, values)| {
+                    values.into_iter().map(move |value| Annotation {
+                        span: DocSpan::single_line(line_idx, first, last),
+                        value,
+                    })
+                })
+            })
+            .filter(move |annotation| filters.iter().all(|keep| keep(annotation)))
+    }
+}
+
+impl<'a, T: HasConfidence + 'static> TypedQuery<'a, T> {
+    /// Keep annotations with confidence of at least `min`.
+    pub fn above_confidence(self, min: f64) -> Self {
+        self.filter(move |annotation| annotation.value.confidence() >= min)
+    }
+
+    /// Order matches by descending confidence.
+    pub fn order_by_confidence(self) -> Self {
+        self.order_by(|a, b| b.value.confidence().total_cmp(&a.value.confidence()))
+    }
+}
+
+impl<'a, T: 'static> IntoIterator for TypedQuery<'a, T> {
+    type Item = Annotation<'a, T>;
+    type IntoIter = Box<dyn Iterator<Item = Annotation<'a, T>> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let TypedQuery { doc, lines, filters, order, limit } = self;
+        let matches = Self::matches(doc, lines, filters);
+        let limit = limit.unwrap_or(usize::MAX);
+        match order {
+            None => Box::new(matches.take(limit)),
+            Some(compare) => {
+                let mut sorted: Vec<_> = matches.collect();
+                sorted.sort_by(|a, b| compare(a, b));
+                sorted.truncate(limit);
+                Box::new(sorted.into_iter())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DocPosition;
+    use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver};
+
+    /// Tags each number token with a confidence of n / 10.
+    struct NumberResolver;
+
+    impl Resolver for NumberResolver {
+        type Attr = Scored<u32>;
+
+        fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+            selection
+                .find_by(&x::token_text())
+                .into_iter()
+                .filter_map(|(sel, text)| {
+                    let n: u32 = text.parse().ok()?;
+                    Some(sel.finish_with_attr(Scored::rule_based(n, n as f64 / 10.0, "number")))
+                })
+                .collect()
+        }
+    }
+
+    fn doc() -> LayeredDocument {
+        LayeredDocument::from_text("3 8\n5 1\n9 6").run_resolver(&NumberResolver)
+    }
+
+    fn values(found: Vec<Annotation<'_, Scored<u32>>>) -> Vec<u32> {
+        found.into_iter().map(|a| a.value.value).collect()
+    }
+
+    #[test]
+    fn test_query_filters_in_document_order() {
+        let doc = doc();
+        let query = doc.query().of_type::<Scored<u32>>();
+        assert_eq!(values(query.collect()), vec![3, 8, 5, 1, 9, 6]);
+
+        let found = doc
+            .query()
+            .of_type::<Scored<u32>>()
+            .above_confidence(0.5)
+            .matching(|n| n.value % 2 == 0)
+            .collect();
+        assert_eq!(values(found), vec![8, 6]);
+
+        let first = doc.query().of_type::<Scored<u32>>().on_lines(1..3).first().unwrap();
+        assert_eq!(first.value.value, 5);
+        assert_eq!(first.span, DocSpan::single_line(1, 0, 0));
+    }
+
+    #[test]
+    fn test_query_within_ordering_and_limit() {
+        let doc = doc();
+        let span = DocSpan::new(DocPosition::new(0, 2), DocPosition::new(1, 0));
+        let found = doc.query().of_type::<Scored<u32>>().within(span).collect();
+        assert_eq!(values(found), vec![8, 5]);
+
+        let query = doc.query().of_type::<Scored<u32>>().order_by_confidence().limit(3);
+        assert_eq!(values(query.collect()), vec![9, 8, 6]);
+
+        let query = doc.query().of_type::<Scored<u32>>().limit(2);
+        assert_eq!(values(query.collect()), vec![3, 8]);
+
+        assert_eq!(doc.query().of_type::<Scored<u32>>().above_confidence(0.6).count(), 3);
+        assert_eq!(doc.query().of_type::<String>().count(), 0);
+    }
+
+    #[test]
+    fn test_query_out_of_range_lines_match_nothing() {
+        let doc = doc();
+        assert_eq!(doc.query().of_type::<Scored<u32>>().on_lines(5..8).count(), 0);
+
+        let span = DocSpan::single_line(4, 0, 2);
+        assert!(doc.query().of_type::<Scored<u32>>().within(span).collect().is_empty());
+    }
+}
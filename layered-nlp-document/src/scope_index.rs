@@ -20,14 +20,14 @@ impl<'a, O> ScopeIndex<'a, O> {
     /// All operators whose primary domain contains the given position.
     pub fn covering_position<'b>(&'b self, pos: &'b DocPosition) -> impl Iterator<Item = &'a ScopeOperator<O>> + 'b {
         self.scopes.iter().filter(move |op| {
-            op.domain.primary().map_or(false, |span| span.contains(pos))
+            op.domain.primary().map_or(false, |span| span.contains_position(pos))
         })
     }
 
     /// All operators whose primary domain overlaps the given span.
     pub fn covering_span<'b>(&'b self, span: &'b DocSpan) -> impl Iterator<Item = &'a ScopeOperator<O>> + 'b {
         self.scopes.iter().filter(move |op| {
-            op.domain.primary().map_or(false, |s| s.overlaps(span))
+            op.domain.primary().map_or(false, |s| s.intersects(span))
         })
     }
 
@@ -40,6 +40,27 @@ impl<'a, O> ScopeIndex<'a, O> {
         self.covering_span(span).filter(move |op| op.dimension == dim)
     }
 
+    /// All operators whose extent intersects the given span, ordered by start.
+    ///
+    /// An operator's extent runs from its trigger through the end of its
+    /// primary domain, so "not" in "shall not deliver" covers both the marker
+    /// and "deliver". Extents may cross lines; a domain that stops at the end
+    /// of a clause is covered up to that boundary, and an operator without a
+    /// domain covers only its trigger. Ties on start position keep the
+    /// shorter extent first.
+    pub fn query_overlapping(&self, span: &DocSpan) -> Vec<&'a ScopeOperator<O>> {
+        let mut found: Vec<(DocSpan, &'a ScopeOperator<O>)> = self
+            .scopes
+            .iter()
+            .map(|op| (extent(op), op))
+            .filter(|(extent, _)| extent.intersects(span))
+            .collect();
+        found.sort_by_key(|(extent, _)| {
+            (extent.start.line, extent.start.token, extent.end.line, extent.end.token)
+        });
+        found.into_iter().map(|(_, op)| op).collect()
+    }
+
     /// Total number of scope operators in this index.
     pub fn len(&self) -> usize {
         self.scopes.len()
@@ -51,6 +72,17 @@ impl<'a, O> ScopeIndex<'a, O> {
     }
 }
 
+/// The span from an operator's trigger through the end of its primary domain.
+fn extent<O>(op: &ScopeOperator<O>) -> DocSpan {
+    let key = |pos: &DocPosition| (pos.line, pos.token);
+    let Some(domain) = op.domain.primary() else {
+        return op.trigger;
+    };
+    let start = if key(&domain.start) < key(&op.trigger.start) { domain.start } else { op.trigger.start };
+    let end = if key(&domain.end) > key(&op.trigger.end) { domain.end } else { op.trigger.end };
+    DocSpan::new(start, end)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,6 +168,58 @@ mod tests {
         assert_eq!(index.covering_position(&DocPosition { line: 0, token: 12 }).count(), 0);
     }
 
+    #[test]
+    fn test_query_overlapping_orders_by_start() {
+        let scopes = vec![
+            make_negation_op(15, 16, 17, 25),
+            make_negation_op(0, 1, 2, 20),
+            make_negation_op(0, 1, 2, 5),
+        ];
+        let index = ScopeIndex::new(&scopes);
+
+        let found = index.query_overlapping(&DocSpan::single_line(0, 4, 18));
+        let ranges: Vec<(usize, usize)> = found
+            .iter()
+            .map(|op| (op.trigger.start.token, op.domain.primary().unwrap().end.token))
+            .collect();
+        assert_eq!(ranges, vec![(0, 5), (0, 20), (15, 25)]);
+
+        // The trigger itself is part of the extent
+        assert_eq!(index.query_overlapping(&DocSpan::single_line(0, 0, 0)).len(), 2);
+        assert!(index.query_overlapping(&DocSpan::single_line(0, 26, 30)).is_empty());
+    }
+
+    #[test]
+    fn test_query_overlapping_across_lines() {
+        let scopes = vec![
+            ScopeOperator::new(
+                ScopeDimension::Negation,
+                DocSpan::single_line(1, 8, 8),
+                ScopeDomain::from_single(DocSpan::new(
+                    DocPosition::new(1, 10),
+                    DocPosition::new(2, 3),
+                )),
+                NegationOp { marker: "not".to_string(), kind: NegationKind::Simple },
+            ),
+            ScopeOperator::new(
+                ScopeDimension::Negation,
+                DocSpan::single_line(2, 6, 6),
+                ScopeDomain::from_candidates(vec![]),
+                NegationOp { marker: "never".to_string(), kind: NegationKind::Temporal },
+            ),
+        ];
+        let index = ScopeIndex::new(&scopes);
+
+        let continuation = DocSpan::single_line(2, 0, 1);
+        let found = index.query_overlapping(&continuation);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].payload.marker, "not");
+
+        // Without a domain only the trigger is covered
+        assert!(index.query_overlapping(&DocSpan::single_line(2, 7, 9)).is_empty());
+        assert_eq!(index.query_overlapping(&DocSpan::single_line(2, 0, 6)).len(), 2);
+    }
+
     #[test]
     fn test_dimension_filtering() {
         // Need to use a common payload type for mixed dimensions
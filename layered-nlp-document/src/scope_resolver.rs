@@ -0,0 +1,205 @@
+//! Composition of nested scope operators into a net polarity.
+//!
+//! "No Party shall fail to deliver" stacks a negative quantifier ("no") over a
+//! negation ("fail to"); the two cancel and the enclosed proposition is positive.
+//! [`ScopeResolver`] finds the operators whose domains enclose a proposition,
+//! orders them by nesting and folds their effects together.
+
+use crate::{DocSpan, NegationOp, QuantifierKind, QuantifierOp, ScopeOperator};
+
+/// How an operator payload affects the polarity of the proposition it scopes over.
+pub trait PolarityEffect {
+    /// Returns true if this operator inverts the polarity of its domain.
+    fn flips_polarity(&self) -> bool;
+}
+
+impl PolarityEffect for NegationOp {
+    fn flips_polarity(&self) -> bool {
+        true
+    }
+}
+
+impl PolarityEffect for QuantifierOp {
+    fn flips_polarity(&self) -> bool {
+        self.kind == QuantifierKind::Negative
+    }
+}
+
+/// Payload for mixing negations and quantifiers in a single operator slice.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PolarityOp {
+    Negation(NegationOp),
+    Quantifier(QuantifierOp),
+}
+
+impl PolarityEffect for PolarityOp {
+    fn flips_polarity(&self) -> bool {
+        match self {
+            PolarityOp::Negation(op) => op.flips_polarity(),
+            PolarityOp::Quantifier(op) => op.flips_polarity(),
+        }
+    }
+}
+
+impl From<NegationOp> for PolarityOp {
+    fn from(op: NegationOp) -> Self {
+        PolarityOp::Negation(op)
+    }
+}
+
+impl From<QuantifierOp> for PolarityOp {
+    fn from(op: QuantifierOp) -> Self {
+        PolarityOp::Quantifier(op)
+    }
+}
+
+/// Net polarity of a proposition after composing every operator that scopes over it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ResolvedPolarity {
+    /// True if an odd number of polarity-flipping operators enclose the proposition
+    pub negated: bool,
+    /// Number of operators that flipped polarity
+    pub flip_count: usize,
+    /// Indices into the resolved slice of the enclosing operators, outermost first
+    pub chain: Vec<usize>,
+    /// Product of the primary-domain confidences along the chain
+    pub confidence: f64,
+}
+
+impl ResolvedPolarity {
+    /// Returns true if the proposition holds positively.
+    pub fn is_positive(&self) -> bool {
+        !self.negated
+    }
+}
+
+/// Resolves the combined effect of nested scope operators.
+pub struct ScopeResolver;
+
+impl ScopeResolver {
+    /// Compose all operators whose primary domain encloses `proposition_span`.
+    ///
+    /// Operators are applied outermost first: an earlier trigger scopes over a
+    /// later one, and for equal triggers the wider domain is outermost. Operators
+    /// whose domain only partially overlaps the proposition are ignored.
+    pub fn resolve<O: PolarityEffect>(
+        ops: &[ScopeOperator<O>],
+        proposition_span: DocSpan,
+    ) -> ResolvedPolarity {
+        let mut chain: Vec<(usize, DocSpan, f64)> = ops
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, op)| {
+                let best = op.domain.candidates.first()?;
+                if best.value.contains(&proposition_span) {
+                    Some((idx, best.value, best.confidence))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        chain.sort_by(|(a_idx, a_domain, _), (b_idx, b_domain, _)| {
+            let a_trigger = ops[*a_idx].trigger.start;
+            let b_trigger = ops[*b_idx].trigger.start;
+            (a_trigger.line, a_trigger.token)
+                .cmp(&(b_trigger.line, b_trigger.token))
+                .then_with(|| {
+                    (b_domain.end.line, b_domain.end.token)
+                        .cmp(&(a_domain.end.line, a_domain.end.token))
+                })
+        });
+
+        let flip_count = chain
+            .iter()
+            .filter(|(idx, _, _)| ops[*idx].payload.flips_polarity())
+            .count();
+        let confidence = chain.iter().map(|(_, _, conf)| conf).product();
+
+        ResolvedPolarity {
+            negated: flip_count % 2 == 1,
+            flip_count,
+            chain: chain.into_iter().map(|(idx, _, _)| idx).collect(),
+            confidence,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NegationKind, ScopeDimension, ScopeDomain, Scored};
+
+    fn negation(trigger: usize, domain_start: usize, domain_end: usize) -> ScopeOperator<PolarityOp> {
+        ScopeOperator::new(
+            ScopeDimension::Negation,
+            DocSpan::single_line(0, trigger, trigger),
+            ScopeDomain::from_single(DocSpan::single_line(0, domain_start, domain_end)),
+            NegationOp { marker: "fail".to_string(), kind: NegationKind::Simple }.into(),
+        )
+    }
+
+    fn quantifier(kind: QuantifierKind, domain_end: usize) -> ScopeOperator<PolarityOp> {
+        ScopeOperator::new(
+            ScopeDimension::Quantifier,
+            DocSpan::single_line(0, 0, 0),
+            ScopeDomain::from_single(DocSpan::single_line(0, 2, domain_end)),
+            QuantifierOp { marker: "no".to_string(), kind }.into(),
+        )
+    }
+
+    #[test]
+    fn test_no_operators_is_positive() {
+        let ops: Vec<ScopeOperator<PolarityOp>> = Vec::new();
+        let resolved = ScopeResolver::resolve(&ops, DocSpan::single_line(0, 4, 6));
+        assert!(resolved.is_positive());
+        assert!(resolved.chain.is_empty());
+        assert_eq!(resolved.confidence, 1.0);
+    }
+
+    #[test]
+    fn test_negative_quantifier_cancels_negation() {
+        // "No Party shall fail to deliver" - tokens: No(0) Party(2) shall(4) fail(6) to(8) deliver(10)
+        let ops = vec![negation(6, 8, 10), quantifier(QuantifierKind::Negative, 10)];
+        let resolved = ScopeResolver::resolve(&ops, DocSpan::single_line(0, 10, 10));
+
+        assert!(resolved.is_positive());
+        assert_eq!(resolved.flip_count, 2);
+        // Quantifier is outermost even though it was listed second
+        assert_eq!(resolved.chain, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_universal_quantifier_keeps_negation() {
+        let ops = vec![quantifier(QuantifierKind::Universal, 10), negation(6, 8, 10)];
+        let resolved = ScopeResolver::resolve(&ops, DocSpan::single_line(0, 10, 10));
+
+        assert!(resolved.negated);
+        assert_eq!(resolved.flip_count, 1);
+    }
+
+    #[test]
+    fn test_operators_outside_proposition_are_ignored() {
+        // Negation domain ends before the proposition starts
+        let ops = vec![negation(0, 2, 4)];
+        let resolved = ScopeResolver::resolve(&ops, DocSpan::single_line(0, 6, 8));
+        assert!(resolved.is_positive());
+        assert!(resolved.chain.is_empty());
+    }
+
+    #[test]
+    fn test_confidence_uses_primary_domain_scores() {
+        let op = ScopeOperator::new(
+            ScopeDimension::Negation,
+            DocSpan::single_line(0, 0, 0),
+            ScopeDomain::from_candidates(vec![
+                Scored::rule_based(DocSpan::single_line(0, 2, 10), 0.8, "clause_end"),
+                Scored::rule_based(DocSpan::single_line(0, 2, 4), 0.6, "phrase_end"),
+            ]),
+            NegationOp { marker: "not".to_string(), kind: NegationKind::Simple },
+        );
+        let resolved = ScopeResolver::resolve(&[op], DocSpan::single_line(0, 6, 8));
+        assert!(resolved.negated);
+        assert!((resolved.confidence - 0.8).abs() < 1e-9);
+    }
+}
@@ -9,6 +9,9 @@
 //! - LLM passes
 //! - Human verification
 //! - Derived from other scores
+//!
+//! A [`ScoreSourcePolicy`] encodes how much each kind of source is trusted, so
+//! deployments can cap or scale confidences centrally.
 
 use std::fmt;
 
@@ -65,6 +68,162 @@ pub enum ScoreSource {
     Derived,
 }
 
+impl ScoreSource {
+    /// The variant of this source, without its identifying details.
+    pub fn kind(&self) -> ScoreSourceKind {
+        match self {
+            ScoreSource::RuleBased { .. } => ScoreSourceKind::RuleBased,
+            ScoreSource::LLMPass { .. } => ScoreSourceKind::LLMPass,
+            ScoreSource::HumanVerified { .. } => ScoreSourceKind::HumanVerified,
+            ScoreSource::Derived => ScoreSourceKind::Derived,
+        }
+    }
+}
+
+/// The variant of a [`ScoreSource`], used to key a [`ScoreSourcePolicy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum ScoreSourceKind {
+    RuleBased,
+    LLMPass,
+    HumanVerified,
+    Derived,
+}
+
+/// Scale-then-clamp adjustment applied to a confidence.
+///
+/// The adjusted confidence is `(confidence * scale).clamp(floor, ceiling)`.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ConfidenceAdjustment {
+    /// Multiplier applied first
+    scale: f64,
+    /// Lowest confidence allowed after scaling
+    floor: f64,
+    /// Highest confidence allowed after scaling
+    ceiling: f64,
+}
+
+impl Default for ConfidenceAdjustment {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl ConfidenceAdjustment {
+    /// An adjustment that leaves confidences unchanged.
+    pub fn identity() -> Self {
+        Self { scale: 1.0, floor: 0.0, ceiling: 1.0 }
+    }
+
+    /// Multiply confidences by `scale` (negative values are treated as 0).
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale.max(0.0);
+        self
+    }
+
+    /// Never report less than `floor`.
+    pub fn floor(mut self, floor: f64) -> Self {
+        self.floor = floor.clamp(0.0, 1.0);
+        self.ceiling = self.ceiling.max(self.floor);
+        self
+    }
+
+    /// Never report more than `ceiling`.
+    pub fn ceiling(mut self, ceiling: f64) -> Self {
+        self.ceiling = ceiling.clamp(0.0, 1.0);
+        self.floor = self.floor.min(self.ceiling);
+        self
+    }
+
+    /// Adjust a confidence.
+    pub fn apply(&self, confidence: f64) -> f64 {
+        // Deserialized bounds may be inverted; `clamp` panics on those
+        let low = self.floor.min(self.ceiling);
+        let high = self.floor.max(self.ceiling);
+        (confidence * self.scale).clamp(low, high)
+    }
+
+    /// The adjustment equivalent to applying `self` and then `next`.
+    pub fn then(&self, next: &ConfidenceAdjustment) -> Self {
+        Self {
+            scale: self.scale * next.scale,
+            // Scaling distributes over the first clamp, so its bounds pass through `next`
+            floor: next.apply(self.floor),
+            ceiling: next.apply(self.ceiling),
+        }
+    }
+}
+
+/// Per-source trust model applied to confidences before they reach review.
+///
+/// The default policy is the identity. Policies compose with [`then`](Self::then),
+/// so a deployment-wide policy can be layered with a stricter local one.
+///
+/// # Example
+/// ```
+/// use layered_nlp_document::{ConfidenceAdjustment, ScoreSourceKind, ScoreSourcePolicy, Scored};
+///
+/// // Never trust a rule above 0.95 without a human check
+/// let policy = ScoreSourcePolicy::default()
+///     .with(ScoreSourceKind::RuleBased, ConfidenceAdjustment::identity().ceiling(0.95));
+///
+/// let scored = Scored::rule_based("Company", 1.0, "defined_term").apply_policy(&policy);
+/// assert_eq!(scored.confidence, 0.95);
+/// assert!(scored.needs_verification());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ScoreSourcePolicy {
+    rule_based: ConfidenceAdjustment,
+    llm_pass: ConfidenceAdjustment,
+    human_verified: ConfidenceAdjustment,
+    derived: ConfidenceAdjustment,
+}
+
+impl ScoreSourcePolicy {
+    /// Set the adjustment for one kind of source.
+    pub fn with(mut self, kind: ScoreSourceKind, adjustment: ConfidenceAdjustment) -> Self {
+        *self.slot_mut(kind) = adjustment;
+        self
+    }
+
+    /// The adjustment used for one kind of source.
+    pub fn adjustment(&self, kind: ScoreSourceKind) -> &ConfidenceAdjustment {
+        match kind {
+            ScoreSourceKind::RuleBased => &self.rule_based,
+            ScoreSourceKind::LLMPass => &self.llm_pass,
+            ScoreSourceKind::HumanVerified => &self.human_verified,
+            ScoreSourceKind::Derived => &self.derived,
+        }
+    }
+
+    /// Adjust a confidence produced by `source`.
+    pub fn apply(&self, confidence: f64, source: &ScoreSource) -> f64 {
+        self.adjustment(source.kind()).apply(confidence)
+    }
+
+    /// The policy equivalent to applying `self` and then `next`.
+    pub fn then(&self, next: &ScoreSourcePolicy) -> Self {
+        let mut composed = self.clone();
+        for kind in [
+            ScoreSourceKind::RuleBased,
+            ScoreSourceKind::LLMPass,
+            ScoreSourceKind::HumanVerified,
+            ScoreSourceKind::Derived,
+        ] {
+            *composed.slot_mut(kind) = self.adjustment(kind).then(next.adjustment(kind));
+        }
+        composed
+    }
+
+    fn slot_mut(&mut self, kind: ScoreSourceKind) -> &mut ConfidenceAdjustment {
+        match kind {
+            ScoreSourceKind::RuleBased => &mut self.rule_based,
+            ScoreSourceKind::LLMPass => &mut self.llm_pass,
+            ScoreSourceKind::HumanVerified => &mut self.human_verified,
+            ScoreSourceKind::Derived => &mut self.derived,
+        }
+    }
+}
+
 impl<T> Scored<T> {
     /// Create a new scored value with explicit confidence and source.
     pub fn new(value: T, confidence: f64, source: ScoreSource) -> Self {
@@ -152,6 +311,18 @@ impl<T> Scored<T> {
             source: self.source.clone(),
         }
     }
+
+    /// Copy of this value with its confidence adjusted by `policy` for its source.
+    pub fn apply_policy(&self, policy: &ScoreSourcePolicy) -> Self
+    where
+        T: Clone,
+    {
+        Scored {
+            value: self.value.clone(),
+            confidence: policy.apply(self.confidence, &self.source),
+            source: self.source.clone(),
+        }
+    }
 }
 
 impl<T: fmt::Debug> fmt::Debug for Scored<T> {
@@ -220,4 +391,49 @@ mod tests {
         let debug = format!("{:?}", scored);
         assert_eq!(debug, r#"Scored("Contractor", conf: 0.85)"#);
     }
+
+    #[test]
+    fn test_default_policy_is_identity() {
+        let scored = Scored::llm_pass("Vendor", 0.73, "model", "pass-1");
+        assert_eq!(scored.apply_policy(&ScoreSourcePolicy::default()), scored);
+        assert!(Scored::verified("x").apply_policy(&ScoreSourcePolicy::default()).is_verified());
+    }
+
+    #[test]
+    fn test_policy_adjusts_by_source_kind() {
+        let policy = ScoreSourcePolicy::default()
+            .with(ScoreSourceKind::RuleBased, ConfidenceAdjustment::identity().ceiling(0.95))
+            .with(ScoreSourceKind::LLMPass, ConfidenceAdjustment::identity().scale(0.5).floor(0.2));
+
+        assert_eq!(Scored::rule_based("a", 1.0, "rule").apply_policy(&policy).confidence, 0.95);
+        assert_eq!(Scored::rule_based("a", 0.6, "rule").apply_policy(&policy).confidence, 0.6);
+        assert_eq!(Scored::llm_pass("a", 0.8, "m", "p").apply_policy(&policy).confidence, 0.4);
+        assert_eq!(Scored::llm_pass("a", 0.2, "m", "p").apply_policy(&policy).confidence, 0.2);
+        assert!(Scored::verified("a").apply_policy(&policy).is_verified());
+    }
+
+    #[test]
+    fn test_policies_compose() {
+        let rule = ConfidenceAdjustment::identity().scale(0.9).floor(0.3);
+        let global = ScoreSourcePolicy::default().with(ScoreSourceKind::RuleBased, rule);
+        let local = ScoreSourcePolicy::default()
+            .with(ScoreSourceKind::RuleBased, ConfidenceAdjustment::identity().ceiling(0.8));
+        let composed = global.then(&local);
+
+        for confidence in [0.0, 0.1, 0.5, 0.85, 1.0] {
+            let scored = Scored::rule_based("a", confidence, "rule");
+            let stepwise = scored.apply_policy(&global).apply_policy(&local);
+            let combined = scored.apply_policy(&composed);
+            assert!((stepwise.confidence - combined.confidence).abs() < 1e-12);
+        }
+        assert_eq!(global.then(&ScoreSourcePolicy::default()), global);
+    }
+
+    #[test]
+    fn test_inverted_deserialized_bounds_do_not_panic() {
+        let adjustment: ConfidenceAdjustment =
+            ron::from_str("(scale: 1.0, floor: 0.9, ceiling: 0.2)").unwrap();
+        assert_eq!(adjustment.apply(1.0), 0.9);
+        assert_eq!(adjustment.apply(0.0), 0.2);
+    }
 }
@@ -58,6 +58,14 @@ pub enum ClauseRole {
     /// E.g., in "the tenant who fails to pay", the relative clause
     /// "who fails to pay" has role Relative pointing to head noun "tenant"
     Relative,
+    /// Section whose terms this clause incorporates by reference
+    /// E.g., "the provisions of Section 5 shall apply mutatis mutandis"
+    /// points to Section 5 with role IncorporatesFrom
+    IncorporatesFrom,
+    /// Permission whose exercise this clause's obligation is a precondition of
+    /// E.g., in "may terminate only upon 30 days notice", the implied duty to
+    /// give notice points to the termination permission with role GatedPermission
+    GatedPermission,
 }
 
 /// M6: PP/Relative clause attachment relations
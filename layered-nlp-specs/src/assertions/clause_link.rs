@@ -138,6 +138,8 @@ fn parse_clause_role(input: &str) -> Option<ClauseRole> {
         "crossreference" | "cross_reference" => Some(ClauseRole::CrossReference),
         "self" | "self_" => Some(ClauseRole::Self_),
         "relative" => Some(ClauseRole::Relative),
+        "incorporatesfrom" | "incorporates_from" => Some(ClauseRole::IncorporatesFrom),
+        "gatedpermission" | "gated_permission" => Some(ClauseRole::GatedPermission),
         _ => None,
     }
 }
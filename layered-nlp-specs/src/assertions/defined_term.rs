@@ -75,6 +75,7 @@ impl SpanAssertion for DefinedTerm {
                         let expected_type = match expected.as_str() {
                             "QuotedMeans" => Some(DefinitionType::QuotedMeans),
                             "Parenthetical" => Some(DefinitionType::Parenthetical),
+                            "Alias" => Some(DefinitionType::Alias),
                             "Hereinafter" => Some(DefinitionType::Hereinafter),
                             _ => None,
                         };
@@ -119,10 +120,7 @@ mod tests {
     use super::*;
 
     fn make_defined_term(term_name: &str, definition_type: DefinitionType) -> DefinedTerm {
-        DefinedTerm {
-            term_name: term_name.to_string(),
-            definition_type,
-        }
+        DefinedTerm::new(term_name.to_string(), definition_type)
     }
 
     #[test]
@@ -6,6 +6,7 @@ mod defined_term;
 mod term_reference;
 mod clause;
 mod clause_link;
+mod section;
 
 pub use obligation::ObligationAssertion;
 pub use pronoun::PronounAssertion;
@@ -13,3 +14,4 @@ pub use defined_term::DefinedTermAssertion;
 pub use term_reference::TermReferenceAssertion;
 pub use clause::ClauseAssertion;
 pub use clause_link::{ClauseLinkAssertion, ClauseLinkMatch};
+pub use section::{render_structure, SectionAssertion};
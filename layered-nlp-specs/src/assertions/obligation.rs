@@ -4,7 +4,7 @@ use crate::assertion::{
     AssertionMismatch, AssertionSpec, FieldMismatch, FieldValue, ParseError, SpanAssertion,
     TypeFieldCheck,
 };
-use layered_contracts::{ObligationPhrase, ObligationType, ObligorReference};
+use layered_contracts::{ObligationPhrase, ObligationType, ObligorReference, UNKNOWN_OBLIGOR};
 
 /// Parsed assertion for ObligationPhrase.
 #[derive(Debug, Clone)]
@@ -97,6 +97,7 @@ impl SpanAssertion for ObligationPhrase {
                             ObligorReference::TermRef { term_name, .. } => term_name.clone(),
                             ObligorReference::PronounRef { resolved_to, .. } => resolved_to.clone(),
                             ObligorReference::NounPhrase { text } => text.clone(),
+                            ObligorReference::Unknown => UNKNOWN_OBLIGOR.to_string(),
                         };
 
                         // Check if the entity ID appears in the obligor text
@@ -149,6 +150,7 @@ impl SpanAssertion for ObligationPhrase {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use layered_contracts::ObligationNature;
 
     fn make_obligation(
         obligation_type: ObligationType,
@@ -160,6 +162,12 @@ mod tests {
             obligation_type,
             action: action.to_string(),
             conditions: Vec::new(),
+            confidence_breakdown: Default::default(),
+            qualifiers: Vec::new(),
+            object: None,
+            performer: None,
+            obligation_nature: ObligationNature::Performance,
+            discretion: None,
         }
     }
 
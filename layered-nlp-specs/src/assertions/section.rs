@@ -0,0 +1,260 @@
+//! Assertions for document structure (`requires:` lines).
+
+use crate::assertion::{
+    AssertionMismatch, AssertionSpec, FieldMismatch, FieldValue, ParseError, SpanAssertion,
+    TypeFieldCheck,
+};
+use layered_contracts::{DocumentStructure, SectionIdentifier, SectionNode};
+
+/// Parsed assertion for a section of the document structure.
+///
+/// `id` names the section ("Section 1.1", "SECTION:1.1", "Article I") and is
+/// required; `kind`, `parent`, `depth` and `title` are optional. `depth`
+/// counts nesting in the structure tree (1 = top level); `parent=none`
+/// requires a top-level section.
+#[derive(Debug, Clone)]
+pub struct SectionAssertion {
+    pub checks: Vec<TypeFieldCheck>,
+}
+
+impl AssertionSpec for SectionAssertion {
+    fn describe(&self) -> String {
+        let fields: Vec<_> = self
+            .checks
+            .iter()
+            .map(|c| format!("{}={:?}", c.field, c.value))
+            .collect();
+        format!("Section({})", fields.join(", "))
+    }
+
+    fn constrained_fields(&self) -> Vec<&'static str> {
+        self.checks
+            .iter()
+            .map(|c| match c.field.as_str() {
+                "id" => "id",
+                "kind" => "kind",
+                "parent" => "parent",
+                "depth" => "depth",
+                "title" => "title",
+                _ => "unknown",
+            })
+            .collect()
+    }
+}
+
+impl SpanAssertion for DocumentStructure {
+    type Assertion = SectionAssertion;
+
+    fn parse_assertion(input: &str) -> Result<Self::Assertion, ParseError> {
+        let mut checks = Vec::new();
+        for part in input.split(',') {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            checks.push(TypeFieldCheck::parse(part)?);
+        }
+
+        if !checks.iter().any(|c| c.field == "id") {
+            return Err(ParseError::new("Section assertion requires an `id` field")
+                .with_valid_fields(vec!["id", "kind", "parent", "depth", "title"]));
+        }
+        Ok(SectionAssertion { checks })
+    }
+
+    fn check(&self, assertion: &Self::Assertion) -> Result<(), AssertionMismatch> {
+        let id = assertion
+            .checks
+            .iter()
+            .find(|c| c.field == "id")
+            .map(|c| field_text(&c.value))
+            .unwrap_or_default();
+        let mut mismatch = AssertionMismatch::new(&id, assertion.describe());
+
+        let Some(path) = find_path(&self.sections, &id) else {
+            mismatch
+                .fields
+                .push(FieldMismatch::hard("id", id, "no such section".to_string()));
+            return Err(mismatch);
+        };
+        let node = path[path.len() - 1];
+        let parent = path.len().checked_sub(2).map(|idx| path[idx]);
+
+        for check in &assertion.checks {
+            let expected = field_text(&check.value);
+            match check.field.as_str() {
+                "id" => {}
+                "kind" => {
+                    let actual = section_kind(node);
+                    if !actual.eq_ignore_ascii_case(&expected) {
+                        mismatch.fields.push(FieldMismatch::hard("kind", expected, actual));
+                    }
+                }
+                "parent" => {
+                    let matches = match parent {
+                        Some(parent) => matches_label(parent, &expected),
+                        None => expected.eq_ignore_ascii_case("none"),
+                    };
+                    if !matches {
+                        let actual = parent.map_or("none".to_string(), section_label);
+                        mismatch.fields.push(FieldMismatch::hard("parent", expected, actual));
+                    }
+                }
+                "depth" => {
+                    if expected.parse::<usize>().ok() != Some(path.len()) {
+                        mismatch.fields.push(FieldMismatch::hard(
+                            "depth",
+                            expected,
+                            path.len().to_string(),
+                        ));
+                    }
+                }
+                "title" => {
+                    let actual = node.header.title.clone().unwrap_or_default();
+                    if actual != expected {
+                        mismatch.fields.push(FieldMismatch::hard("title", expected, actual));
+                    }
+                }
+                _ => {
+                    mismatch.fields.push(FieldMismatch::info(
+                        "unknown",
+                        format!("valid field for {}", Self::span_type_name()),
+                        check.field.clone(),
+                    ));
+                }
+            }
+        }
+
+        if mismatch.fields.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatch)
+        }
+    }
+
+    fn span_type_name() -> &'static str {
+        "Section"
+    }
+}
+
+/// Render a structure as an indented outline, for failure messages.
+///
+/// ```text
+/// ARTICLE I [Article]
+///   Section 1.1 [Section] "Payment"
+/// ```
+pub fn render_structure(structure: &DocumentStructure) -> String {
+    fn render(node: &SectionNode, indent: usize, out: &mut String) {
+        out.push_str(&"  ".repeat(indent));
+        out.push_str(&format!("{} [{}]", section_label(node), section_kind(node)));
+        if let Some(title) = &node.header.title {
+            out.push_str(&format!(" {:?}", title));
+        }
+        out.push('\n');
+        for child in &node.children {
+            render(child, indent + 1, out);
+        }
+    }
+
+    if structure.sections.is_empty() {
+        return "(no sections)\n".to_string();
+    }
+    let mut out = String::new();
+    for section in &structure.sections {
+        render(section, 0, &mut out);
+    }
+    out
+}
+
+fn field_text(value: &FieldValue) -> String {
+    match value {
+        FieldValue::String(s) | FieldValue::EntityRef(s) => s.clone(),
+        FieldValue::Number(n) => n.to_string(),
+    }
+}
+
+fn section_label(node: &SectionNode) -> String {
+    node.header.raw_text.trim().to_string()
+}
+
+fn section_kind(node: &SectionNode) -> String {
+    match &node.header.identifier {
+        SectionIdentifier::Named { kind, .. } => format!("{:?}", kind),
+        _ => "Unnamed".to_string(),
+    }
+}
+
+/// Whether `label` names the section: its raw text ("Section 3"), its
+/// canonical identifier ("SECTION:3"), or the identifier after the keyword
+/// ("3").
+fn matches_label(node: &SectionNode, label: &str) -> bool {
+    let label = label.trim().to_uppercase();
+    let canonical = node.header.identifier.canonical().to_uppercase();
+    section_label(node).to_uppercase() == label
+        || canonical == label
+        || canonical.split_once(':').is_some_and(|(_, sub)| sub == label)
+}
+
+/// Path from the root to the first section (depth-first) matching `label`.
+fn find_path<'a>(sections: &'a [SectionNode], label: &str) -> Option<Vec<&'a SectionNode>> {
+    for section in sections {
+        if matches_label(section, label) {
+            return Some(vec![section]);
+        }
+        if let Some(mut path) = find_path(&section.children, label) {
+            path.insert(0, section);
+            return Some(path);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use layered_contracts::{ContractDocument, DocumentStructureBuilder, SectionHeaderResolver};
+
+    fn structure(text: &str) -> DocumentStructure {
+        let doc = ContractDocument::from_text(text).run_resolver(&SectionHeaderResolver::new());
+        DocumentStructureBuilder::build(&doc).value
+    }
+
+    fn check(structure: &DocumentStructure, body: &str) -> Result<(), AssertionMismatch> {
+        structure.check(&DocumentStructure::parse_assertion(body).unwrap())
+    }
+
+    #[test]
+    fn test_parse_requires_id() {
+        assert!(DocumentStructure::parse_assertion("kind=Section").is_err());
+        let assertion = DocumentStructure::parse_assertion("id=3, depth=2").unwrap();
+        assert_eq!(assertion.checks.len(), 2);
+    }
+
+    #[test]
+    fn test_check_nested_section() {
+        let structure = structure(
+            "ARTICLE I\nSection 1.1 Payment\nFees are due.\nARTICLE II\nSection 2.1\nTerm.",
+        );
+
+        let nested = "id=Section 1.1, kind=Section, parent=Article I, depth=2";
+        assert!(check(&structure, nested).is_ok());
+        assert!(check(&structure, "id=ARTICLE:R1, parent=none, depth=1").is_ok());
+        assert!(check(&structure, "id=SECTION:1.1, title=Payment").is_ok());
+
+        let mismatch =
+            check(&structure, "id=Section 2.1, parent=ARTICLE I, kind=Article").unwrap_err();
+        let fields: Vec<_> = mismatch.fields.iter().map(|f| f.field).collect();
+        assert_eq!(fields, vec!["parent", "kind"]);
+        assert_eq!(mismatch.fields[0].actual, "ARTICLE II");
+
+        let missing = check(&structure, "id=Section 9").unwrap_err();
+        assert_eq!(missing.fields[0].actual, "no such section");
+    }
+
+    #[test]
+    fn test_render_structure() {
+        let rendered = render_structure(&structure("ARTICLE I\nSection 1.1 Payment\nFees."));
+        assert_eq!(rendered, "ARTICLE I [Article]\n  Section 1.1 [Section] \"Payment\"\n");
+        assert_eq!(render_structure(&DocumentStructure::empty()), "(no sections)\n");
+    }
+}
@@ -100,6 +100,7 @@ impl SpanAssertion for TermReference {
                         let expected_type = match expected.as_str() {
                             "QuotedMeans" => Some(DefinitionType::QuotedMeans),
                             "Parenthetical" => Some(DefinitionType::Parenthetical),
+                            "Alias" => Some(DefinitionType::Alias),
                             "Hereinafter" => Some(DefinitionType::Hereinafter),
                             _ => None,
                         };
@@ -147,6 +148,8 @@ mod tests {
         TermReference {
             term_name: term_name.to_string(),
             definition_type,
+            article: None,
+            qualifier: None,
         }
     }
 
@@ -70,6 +70,8 @@ impl<'a> DocumentContext<'a> {
             RefTarget::Span(id) => self.resolve_span_any(*id).map(|(_, span)| span.text.clone()),
             RefTarget::Entity(id) => self.resolve_entity(id).map(|e| e.text.clone()),
             RefTarget::TextRef { text, .. } => Some(text.clone()),
+            // Structural assertions name no span
+            RefTarget::Structure => None,
         }
     }
 
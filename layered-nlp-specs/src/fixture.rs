@@ -86,6 +86,8 @@ pub enum RefTarget {
         /// 0-indexed occurrence (default 0)
         occurrence: usize,
     },
+    /// The document's section structure rather than a span: `requires:`
+    Structure,
 }
 
 /// Parsed assertion body containing field assertions.
@@ -197,6 +199,7 @@ impl std::fmt::Display for RefTarget {
                     write!(f, "[\"{}\"@{}]", text, occurrence)
                 }
             }
+            RefTarget::Structure => write!(f, "requires"),
         }
     }
 }
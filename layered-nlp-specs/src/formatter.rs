@@ -1,8 +1,8 @@
 //! Rich error formatting for assertion failures.
 
-use crate::assertion::MismatchSeverity;
-use crate::fixture::Assertion;
-use crate::matcher::AssertionOutcome;
+use crate::assertion::{FieldMismatch, MismatchSeverity};
+use crate::fixture::{Assertion, RefTarget};
+use crate::matcher::{AssertionOutcome, NearMissKind};
 use std::fmt::Write;
 
 /// Result of a single assertion check (for formatting purposes).
@@ -43,30 +43,36 @@ pub fn format_failure(
     // Main failure message
     match outcome {
         AssertionOutcome::Failed(mismatch) => {
-            writeln!(
-                output,
-                "  assertion failed for {} span: \"{}\"",
-                assertion.span_type, span_text
-            )
-            .unwrap();
-
-            // Field-level details
-            for field in &mismatch.fields {
-                let marker = match field.severity {
-                    MismatchSeverity::Hard => "\u{2717}", // cross mark
-                    MismatchSeverity::Soft => "~",
-                    MismatchSeverity::Info => "\u{2139}", // info
-                };
+            if assertion.target == RefTarget::Structure {
+                writeln!(output, "  structure assertion failed for {}", assertion.span_type)
+                    .unwrap();
+            } else {
                 writeln!(
                     output,
-                    "    {} {}: expected `{}`, found `{}`",
-                    marker, field.field, field.expected, field.actual
+                    "  assertion failed for {} span: \"{}\"",
+                    assertion.span_type, span_text
                 )
                 .unwrap();
             }
+
+            write_field_mismatches(&mut output, &mismatch.fields, "    ");
         }
-        AssertionOutcome::NotFound { reason } => {
+        AssertionOutcome::NotFound { reason, near_misses } => {
             writeln!(output, "  span not found: {}", reason).unwrap();
+            if !near_misses.is_empty() {
+                writeln!(output, "  closest matches:").unwrap();
+            }
+            for near_miss in near_misses {
+                writeln!(
+                    output,
+                    "    - {} \"{}\" ({})",
+                    near_miss.span_type,
+                    near_miss.span_text,
+                    near_miss.kind.describe()
+                )
+                .unwrap();
+                write_field_mismatches(&mut output, &near_miss.fields, "        ");
+            }
         }
         AssertionOutcome::TypeMismatch { expected, actual } => {
             writeln!(
@@ -82,6 +88,15 @@ pub fn format_failure(
         }
     }
 
+    // For structural assertions the span text is the rendered structure tree
+    if assertion.target == RefTarget::Structure && !span_text.is_empty() {
+        writeln!(output).unwrap();
+        writeln!(output, "  actual structure:").unwrap();
+        for line in span_text.lines() {
+            writeln!(output, "    {}", line).unwrap();
+        }
+    }
+
     // Assertion source
     writeln!(output).unwrap();
     writeln!(
@@ -124,6 +139,22 @@ pub fn format_summary(
     output
 }
 
+fn write_field_mismatches(output: &mut String, fields: &[FieldMismatch], indent: &str) {
+    for field in fields {
+        let marker = match field.severity {
+            MismatchSeverity::Hard => "\u{2717}", // cross mark
+            MismatchSeverity::Soft => "~",
+            MismatchSeverity::Info => "\u{2139}", // info
+        };
+        writeln!(
+            output,
+            "{}{} {}: expected `{}`, found `{}`",
+            indent, marker, field.field, field.expected, field.actual
+        )
+        .unwrap();
+    }
+}
+
 fn format_assertion_body(assertion: &Assertion) -> String {
     assertion
         .body
@@ -163,7 +194,22 @@ fn generate_hint(assertion: &Assertion, span_text: &str, outcome: &AssertionOutc
         }
     }
 
-    if let AssertionOutcome::NotFound { .. } = outcome {
+    if let AssertionOutcome::NotFound { near_misses, .. } = outcome {
+        match near_misses.first().map(|near_miss| near_miss.kind) {
+            Some(NearMissKind::WrongType) => {
+                return Some(format!(
+                    "A {} was detected here instead - check the assertion's span type",
+                    near_misses[0].span_type
+                ));
+            }
+            Some(NearMissKind::WrongSpan) => {
+                return Some(
+                    "The span boundaries differ - adjust the «» marker to the detected text"
+                        .to_string(),
+                );
+            }
+            Some(NearMissKind::WrongParagraph) | None => {}
+        }
         return Some(format!(
             "No {} was detected at this position - check the resolver is enabled",
             assertion.span_type
@@ -177,7 +223,8 @@ fn generate_hint(assertion: &Assertion, span_text: &str, outcome: &AssertionOutc
 mod tests {
     use super::*;
     use crate::assertion::{AssertionMismatch, FieldMismatch};
-    use crate::fixture::{AssertionBody, RefTarget};
+    use crate::fixture::AssertionBody;
+    use crate::matcher::NearMiss;
 
     fn make_assertion() -> Assertion {
         Assertion {
@@ -193,6 +240,7 @@ mod tests {
         let assertion = make_assertion();
         let outcome = AssertionOutcome::NotFound {
             reason: "No ObligationPhrase found".to_string(),
+            near_misses: Vec::new(),
         };
 
         let output = format_failure(
@@ -227,6 +275,7 @@ mod tests {
         let assertion = make_assertion();
         let outcome = AssertionOutcome::NotFound {
             reason: "No ObligationPhrase found".to_string(),
+            near_misses: Vec::new(),
         };
 
         let output = format_failure(
@@ -240,6 +289,56 @@ mod tests {
         assert!(output.contains("^^^"));
     }
 
+    #[test]
+    fn test_format_structure_failure_shows_tree() {
+        let assertion = Assertion {
+            target: RefTarget::Structure,
+            span_type: "Section".to_string(),
+            ..make_assertion()
+        };
+        let mismatch = AssertionMismatch {
+            span_text: "Section 3".to_string(),
+            assertion_source: "Section(id=Section 3)".to_string(),
+            fields: vec![FieldMismatch::hard("parent", "Article I", "none")],
+        };
+        let tree = "ARTICLE I [Article]\nSection 3 [Section]\n";
+        let outcome = AssertionOutcome::Failed(mismatch);
+
+        let output = format_failure("test.nlp", &assertion, tree, &outcome, None);
+        assert!(output.contains("structure assertion failed for Section"));
+        assert!(output.contains("actual structure:\n    ARTICLE I [Article]\n    Section 3"));
+    }
+
+    #[test]
+    fn test_format_not_found_lists_near_misses() {
+        let assertion = make_assertion();
+        let outcome = AssertionOutcome::NotFound {
+            reason: "No ObligationPhrase found for 'shall pay'".to_string(),
+            near_misses: vec![
+                NearMiss {
+                    span_type: "DefinedTerm",
+                    span_text: "shall pay".to_string(),
+                    kind: NearMissKind::WrongType,
+                    fields: Vec::new(),
+                },
+                NearMiss {
+                    span_type: "ObligationPhrase",
+                    span_text: "shall pay rent".to_string(),
+                    kind: NearMissKind::WrongSpan,
+                    fields: vec![FieldMismatch::hard("modal", "may", "Duty")],
+                },
+            ],
+        };
+
+        let output = format_failure("test.nlp", &assertion, "", &outcome, None);
+        assert!(output.contains(
+            "  closest matches:\n    - DefinedTerm \"shall pay\" (wrong type)\n    \
+             - ObligationPhrase \"shall pay rent\" (different span)\n        \
+             \u{2717} modal: expected `may`, found `Duty`\n"
+        ));
+        assert!(output.contains("hint: A DefinedTerm was detected here instead"));
+    }
+
     #[test]
     fn test_format_summary_pass() {
         let output = format_summary("contract.nlp", 10, 0, 0, 0);
@@ -45,16 +45,16 @@ pub use assertion::{
 };
 pub use assertions::{
     ClauseAssertion, ClauseLinkAssertion, ClauseLinkMatch, DefinedTermAssertion, ObligationAssertion,
-    PronounAssertion, TermReferenceAssertion,
+    PronounAssertion, SectionAssertion, TermReferenceAssertion, render_structure,
 };
 pub use errors::{SpecError, SpecResult};
 pub use fixture::{
     Assertion, AssertionBody, CompareOp, FieldCheck, NlpFixture, RefTarget, SpanMarker,
 };
 pub use matcher::{
-    MatchResult, AssertionResult, AssertionOutcome,
+    MatchResult, AssertionResult, AssertionOutcome, NearMiss, NearMissKind,
     check_clause, check_clause_link, check_obligation, check_pronoun, check_defined_term,
-    check_term_reference,
+    check_section, check_term_reference, find_near_misses,
     is_supported_type, valid_fields_for_type,
 };
 pub use parser::{parse_fixture, parse_spans};
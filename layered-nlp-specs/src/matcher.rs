@@ -1,10 +1,13 @@
 //! Assertion registry and span matching.
 
-use crate::assertion::{AssertionMismatch, SpanAssertion};
+use crate::assertion::{AssertionMismatch, FieldMismatch, SpanAssertion};
 use crate::fixture::{Assertion, AssertionBody};
+use crate::runner::PipelineResult;
 use layered_clauses::Clause;
 use crate::assertions::ClauseLinkMatch;
-use layered_contracts::{DefinedTerm, ObligationPhrase, PronounReference, TermReference};
+use layered_contracts::{
+    DefinedTerm, DocumentStructure, ObligationPhrase, PronounReference, TermReference,
+};
 
 /// Result of matching assertions against resolver output.
 #[derive(Debug, Clone)]
@@ -26,10 +29,44 @@ pub struct AssertionResult {
 pub enum AssertionOutcome {
     Passed,
     Failed(AssertionMismatch),
-    NotFound { reason: String },
+    NotFound { reason: String, near_misses: Vec<NearMiss> },
     TypeMismatch { expected: String, actual: String },
 }
 
+/// Something the pipeline found close to a span whose assertion matched nothing.
+#[derive(Debug, Clone)]
+pub struct NearMiss {
+    /// Span type of the annotation found (e.g., "DefinedTerm").
+    pub span_type: &'static str,
+    /// Text of the annotation found.
+    pub span_text: String,
+    /// How it differs from the asserted span.
+    pub kind: NearMissKind,
+    /// Fields of the assertion it would fail, when it has the asserted type.
+    pub fields: Vec<FieldMismatch>,
+}
+
+/// How a [`NearMiss`] differs from the asserted span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NearMissKind {
+    /// Same text, but a different span type.
+    WrongType,
+    /// Asserted span type, over text overlapping the asserted span.
+    WrongSpan,
+    /// Asserted span type and text, but in another paragraph.
+    WrongParagraph,
+}
+
+impl NearMissKind {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            NearMissKind::WrongType => "wrong type",
+            NearMissKind::WrongSpan => "different span",
+            NearMissKind::WrongParagraph => "other paragraph",
+        }
+    }
+}
+
 /// Check if a span type name is supported.
 pub fn is_supported_type(type_name: &str) -> bool {
     matches!(
@@ -39,7 +76,8 @@ pub fn is_supported_type(type_name: &str) -> bool {
         "DefinedTerm" |
         "TermReference" |
         "Clause" |
-        "ClauseLink"
+        "ClauseLink" |
+        "Section"
     )
 }
 
@@ -52,6 +90,7 @@ pub fn valid_fields_for_type(type_name: &str) -> Vec<&'static str> {
         "TermReference" => vec!["term_name", "definition_type", "target"],
         "Clause" => vec!["type", "category"],
         "ClauseLink" => vec!["role", "target"],
+        "Section" => vec!["id", "kind", "parent", "depth", "title"],
         _ => vec![],
     }
 }
@@ -117,6 +156,127 @@ pub fn check_clause_link(
     clause_link.check(&assertion)
 }
 
+/// Parse and check a structural assertion against the document's sections.
+pub fn check_section(
+    structure: &DocumentStructure,
+    body: &str,
+) -> Result<(), AssertionMismatch> {
+    let assertion = DocumentStructure::parse_assertion(body)
+        .map_err(|e| AssertionMismatch::new(body, format!("parse error: {}", e)))?;
+    structure.check(&assertion)
+}
+
+/// The canonical span type for a type name or its alias.
+fn canonical_type(type_name: &str) -> Option<&'static str> {
+    match type_name {
+        "Obligation" | "ObligationPhrase" => Some("ObligationPhrase"),
+        "PronounReference" | "PronounRef" => Some("PronounReference"),
+        "DefinedTerm" => Some("DefinedTerm"),
+        "TermReference" => Some("TermReference"),
+        "Clause" => Some("Clause"),
+        "ClauseLink" => Some("ClauseLink"),
+        _ => None,
+    }
+}
+
+/// The span an assertion targeted, for near-miss searches.
+struct NearMissTarget<'a> {
+    span_type: &'static str,
+    paragraph: Option<usize>,
+    span_text: &'a str,
+}
+
+impl NearMissTarget<'_> {
+    /// Classify one detected span against the target, checking the assertion
+    /// body against it when it has the asserted type.
+    fn classify<T>(
+        &self,
+        span_type: &'static str,
+        (paragraph, text, value): &(usize, String, T),
+        check: impl Fn(&T) -> Result<(), AssertionMismatch>,
+    ) -> Option<NearMiss> {
+        let same_paragraph = self.paragraph.is_none_or(|p| p == *paragraph);
+        let same_text = text == self.span_text;
+        let overlaps = {
+            let (found, wanted) = (text.to_lowercase(), self.span_text.to_lowercase());
+            !found.is_empty() && (found.contains(&wanted) || wanted.contains(&found))
+        };
+
+        let kind = if span_type != self.span_type {
+            (same_paragraph && same_text).then_some(NearMissKind::WrongType)?
+        } else if same_paragraph && !same_text && overlaps {
+            NearMissKind::WrongSpan
+        } else if !same_paragraph && same_text {
+            NearMissKind::WrongParagraph
+        } else {
+            return None;
+        };
+
+        let fields = if kind == NearMissKind::WrongType {
+            Vec::new()
+        } else {
+            check(value).err().map(|m| m.fields).unwrap_or_default()
+        };
+        Some(NearMiss {
+            span_type,
+            span_text: text.clone(),
+            kind,
+            fields,
+        })
+    }
+}
+
+/// Search the pipeline output for spans close to one an assertion did not find.
+///
+/// Reports spans of another type over the same text, spans of the asserted
+/// type over overlapping text (an off-by-one boundary), and spans of the
+/// asserted type and text in another paragraph. Spans of the asserted type
+/// carry the field mismatches the assertion body would report against them.
+pub fn find_near_misses(
+    result: &PipelineResult,
+    span_type: &str,
+    paragraph: Option<usize>,
+    span_text: &str,
+    body: &str,
+) -> Vec<NearMiss> {
+    let Some(span_type) = canonical_type(span_type) else {
+        return Vec::new();
+    };
+    let target = NearMissTarget {
+        span_type,
+        paragraph,
+        span_text,
+    };
+
+    let obligations = result.obligations.iter().filter_map(|span| {
+        target.classify("ObligationPhrase", span, |o| check_obligation(o, body))
+    });
+    let pronouns = result.pronouns.iter().filter_map(|span| {
+        target.classify("PronounReference", span, |p| check_pronoun(p, body))
+    });
+    let defined_terms = result.defined_terms.iter().filter_map(|span| {
+        target.classify("DefinedTerm", span, |t| check_defined_term(&t.value, body))
+    });
+    let term_references = result.term_references.iter().filter_map(|span| {
+        target.classify("TermReference", span, |t| check_term_reference(&t.value, body))
+    });
+    let clauses = result
+        .clauses
+        .iter()
+        .filter_map(|span| target.classify("Clause", span, |c| check_clause(c, body)));
+    let clause_links = result.clause_links.iter().filter_map(|span| {
+        target.classify("ClauseLink", span, |l| check_clause_link(l, body))
+    });
+
+    obligations
+        .chain(pronouns)
+        .chain(defined_terms)
+        .chain(term_references)
+        .chain(clauses)
+        .chain(clause_links)
+        .collect()
+}
+
 /// Format an assertion body as a string for error messages.
 pub fn format_body(body: &AssertionBody) -> String {
     body.field_checks
@@ -150,11 +310,11 @@ impl MatchResult {
         });
     }
 
-    pub fn add_not_found(&mut self, assertion: Assertion, reason: String) {
+    pub fn add_not_found(&mut self, assertion: Assertion, reason: String, near_misses: Vec<NearMiss>) {
         self.failed.push(AssertionResult {
             assertion,
             span_text: String::new(),
-            outcome: AssertionOutcome::NotFound { reason },
+            outcome: AssertionOutcome::NotFound { reason, near_misses },
         });
     }
 
@@ -185,7 +345,8 @@ impl Default for MatchResult {
 mod tests {
     use super::*;
     use layered_contracts::{
-        AntecedentCandidate, DefinitionType, ObligationType, ObligorReference, PronounType,
+        AntecedentCandidate, DefinitionType, ObligationNature, ObligationType,
+        ObligorReference, PronounType,
     };
 
     #[test]
@@ -195,6 +356,12 @@ mod tests {
             obligation_type: ObligationType::Duty,
             action: "pay rent".to_string(),
             conditions: vec![],
+            confidence_breakdown: Default::default(),
+            qualifiers: Vec::new(),
+            object: None,
+            performer: None,
+            obligation_nature: ObligationNature::Performance,
+            discretion: None,
         };
 
         assert!(check_obligation(&obligation, "modal=shall").is_ok());
@@ -207,6 +374,12 @@ mod tests {
             obligation_type: ObligationType::Permission,
             action: "pay rent".to_string(),
             conditions: vec![],
+            confidence_breakdown: Default::default(),
+            qualifiers: Vec::new(),
+            object: None,
+            performer: None,
+            obligation_nature: ObligationNature::Performance,
+            discretion: None,
         };
 
         let result = check_obligation(&obligation, "modal=shall");
@@ -217,10 +390,7 @@ mod tests {
 
     #[test]
     fn test_check_defined_term() {
-        let term = DefinedTerm {
-            term_name: "Tenant".to_string(),
-            definition_type: DefinitionType::QuotedMeans,
-        };
+        let term = DefinedTerm::new("Tenant".to_string(), DefinitionType::QuotedMeans);
 
         assert!(check_defined_term(&term, "term_name=Tenant").is_ok());
         assert!(check_defined_term(&term, "definition_type=QuotedMeans").is_ok());
@@ -247,6 +417,8 @@ mod tests {
         let term_ref = TermReference {
             term_name: "Agreement".to_string(),
             definition_type: DefinitionType::QuotedMeans,
+            article: None,
+            qualifier: None,
         };
 
         assert!(check_term_reference(&term_ref, "term_name=Agreement").is_ok());
@@ -265,6 +437,7 @@ mod tests {
                 source_line: 1,
             },
             "span not found".to_string(),
+            Vec::new(),
         );
 
         assert!(!result.all_passed());
@@ -280,6 +453,7 @@ mod tests {
         assert!(is_supported_type("TermReference"));
         assert!(is_supported_type("Clause"));
         assert!(is_supported_type("ClauseLink"));
+        assert!(is_supported_type("Section"));
         assert!(!is_supported_type("Unknown"));
     }
 }
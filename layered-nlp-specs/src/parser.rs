@@ -182,12 +182,15 @@ pub fn parse_fixture(input: &str) -> SpecResult<NlpFixture> {
     })
 }
 
-/// Parse a single assertion line: [n]: Type(body) or §Entity: Type(body)
+/// Parse a single assertion line: [n]: Type(body), §Entity: Type(body), or
+/// requires: Type(body) for the document's structure
 fn parse_assertion(input: &str, source_line: usize) -> SpecResult<Assertion> {
     let input = input.trim();
 
     // Check for entity reference §ID
-    let target = if input.starts_with('§') {
+    let target = if let Some(rest) = input.strip_prefix("requires:") {
+        (RefTarget::Structure, rest)
+    } else if input.starts_with('§') {
         // Parse §EntityId: ...
         // Note: § is multi-byte UTF-8, so we need to skip its full byte length
         let after_section = &input['§'.len_utf8()..];
@@ -219,7 +222,10 @@ fn parse_assertion(input: &str, source_line: usize) -> SpecResult<Assertion> {
     } else {
         return Err(SpecError::Parse {
             line: source_line,
-            message: format!("Assertion must start with '[' or '§': {}", input),
+            message: format!(
+                "Assertion must start with '[', '§' or 'requires:': {}",
+                input
+            ),
         });
     };
 
@@ -388,6 +394,16 @@ mod tests {
         assert_eq!(assertion.span_type, "Party");
     }
 
+    #[test]
+    fn test_parse_structure_assertion() {
+        let assertion =
+            parse_assertion("requires: Section(id=Section 3, parent=Article I)", 1).unwrap();
+        assert_eq!(assertion.target, RefTarget::Structure);
+        assert_eq!(assertion.span_type, "Section");
+        assert_eq!(assertion.body.field_checks[0].expected, "Section 3");
+        assert_eq!(assertion.body.field_checks[1].field, "parent");
+    }
+
     #[test]
     fn test_parse_text_ref_assertion() {
         let assertion = parse_assertion("[\"shall pay\"]: Obligation()", 1).unwrap();
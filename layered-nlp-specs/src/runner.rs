@@ -5,14 +5,16 @@ use crate::context::DocumentContext;
 use crate::fixture::NlpFixture;
 use crate::matcher::{
     check_clause, check_clause_link, check_defined_term, check_obligation, check_pronoun,
-    check_term_reference, format_body, AssertionOutcome, MatchResult,
+    check_section, check_term_reference, find_near_misses, format_body, AssertionOutcome,
+    MatchResult,
 };
-use crate::assertions::ClauseLinkMatch;
+use crate::assertions::{render_structure, ClauseLinkMatch};
 use layered_clauses::{Clause, ClauseKeywordResolver, ClauseLinkResolver, ClauseResolver, SentenceBoundaryResolver};
 use layered_contracts::{
-    ContractKeywordResolver, DefinedTerm, DefinedTermResolver, ObligationPhrase,
-    ObligationPhraseResolver, ProhibitionResolver, PronounReference, PronounResolver, Scored,
-    SectionReferenceResolver, TermReference, TermReferenceResolver,
+    ContractKeywordResolver, DefinedTerm, DefinedTermResolver, DocumentStructure,
+    DocumentStructureBuilder, ObligationPhrase, ObligationPhraseResolver, ProhibitionResolver,
+    PronounReference, PronounResolver, Scored, SectionHeaderResolver, SectionReferenceResolver,
+    TermReference, TermReferenceResolver,
 };
 use layered_nlp::{create_line_from_string, x};
 use layered_part_of_speech::POSTagResolver;
@@ -33,6 +35,8 @@ pub struct PipelineResult {
     pub clauses: Vec<(usize, String, Clause)>,
     /// Collected clause links.
     pub clause_links: Vec<(usize, String, ClauseLinkMatch)>,
+    /// Section structure of the whole fixture, for `requires:` assertions.
+    pub structure: Option<DocumentStructure>,
 }
 
 impl PipelineResult {
@@ -170,6 +174,7 @@ pub fn run_fixture(fixture: &NlpFixture, _config: &PipelineConfig) -> PipelineRe
         .run_resolver(&ProhibitionResolver::default())
         .run_resolver(&ObligationPhraseResolver::default())
         .run_resolver(&SentenceBoundaryResolver::new())
+        .run_resolver(&SectionHeaderResolver::new())
         .run_resolver(&SectionReferenceResolver::new())
         .run_resolver(&ClauseKeywordResolver::new(
             &["if", "when"],
@@ -181,6 +186,8 @@ pub fn run_fixture(fixture: &NlpFixture, _config: &PipelineConfig) -> PipelineRe
         ))
         .run_resolver(&ClauseResolver::default());
 
+    result.structure = Some(DocumentStructureBuilder::build(&doc).value);
+
     let (_doc_with_markers, links) = ClauseLinkResolver::resolve_with_list_markers(doc);
 
     for link in links {
@@ -233,6 +240,11 @@ pub fn check_fixture_assertions(fixture: &NlpFixture, result: &PipelineResult) -
     let mut match_result = MatchResult::new();
 
     for assertion in &fixture.assertions {
+        if assertion.target == crate::fixture::RefTarget::Structure {
+            check_structure_assertion(assertion, result, &mut match_result);
+            continue;
+        }
+
         let match_paragraph_idx = match &assertion.target {
             crate::fixture::RefTarget::Span(id) => {
                 fixture.span_by_numeric_id(*id).map(|(para, _)| para.index)
@@ -247,6 +259,7 @@ pub fn check_fixture_assertions(fixture: &NlpFixture, result: &PipelineResult) -
                 match_result.add_not_found(
                     assertion.clone(),
                     format!("Failed to resolve target '{:?}'", assertion.target),
+                    Vec::new(),
                 );
                 continue;
             }
@@ -279,6 +292,7 @@ pub fn check_fixture_assertions(fixture: &NlpFixture, result: &PipelineResult) -
                 } else {
                     AssertionOutcome::NotFound {
                         reason: format!("No ObligationPhrase found for '{}'", span_text),
+                        near_misses: Vec::new(),
                     }
                 }
             }
@@ -295,6 +309,7 @@ pub fn check_fixture_assertions(fixture: &NlpFixture, result: &PipelineResult) -
                 } else {
                     AssertionOutcome::NotFound {
                         reason: format!("No PronounReference found for '{}'", span_text),
+                        near_misses: Vec::new(),
                     }
                 }
             }
@@ -311,6 +326,7 @@ pub fn check_fixture_assertions(fixture: &NlpFixture, result: &PipelineResult) -
                 } else {
                     AssertionOutcome::NotFound {
                         reason: format!("No DefinedTerm found for '{}'", span_text),
+                        near_misses: Vec::new(),
                     }
                 }
             }
@@ -327,6 +343,7 @@ pub fn check_fixture_assertions(fixture: &NlpFixture, result: &PipelineResult) -
                 } else {
                     AssertionOutcome::NotFound {
                         reason: format!("No TermReference found for '{}'", span_text),
+                        near_misses: Vec::new(),
                     }
                 }
             }
@@ -343,6 +360,7 @@ pub fn check_fixture_assertions(fixture: &NlpFixture, result: &PipelineResult) -
                 } else {
                     AssertionOutcome::NotFound {
                         reason: format!("No Clause found for '{}'", span_text),
+                        near_misses: Vec::new(),
                     }
                 }
             }
@@ -356,6 +374,7 @@ pub fn check_fixture_assertions(fixture: &NlpFixture, result: &PipelineResult) -
                 if candidates.is_empty() {
                     AssertionOutcome::NotFound {
                         reason: format!("No ClauseLink found for '{}'", span_text),
+                        near_misses: Vec::new(),
                     }
                 } else {
                     let mut first_mismatch = None;
@@ -397,8 +416,15 @@ pub fn check_fixture_assertions(fixture: &NlpFixture, result: &PipelineResult) -
             AssertionOutcome::Failed(mismatch) => {
                 match_result.add_failed(assertion.clone(), span_text, mismatch);
             }
-            AssertionOutcome::NotFound { reason } => {
-                match_result.add_not_found(assertion.clone(), reason);
+            AssertionOutcome::NotFound { reason, .. } => {
+                let near_misses = find_near_misses(
+                    result,
+                    &assertion.span_type,
+                    match_paragraph_idx,
+                    &span_text,
+                    &body_str,
+                );
+                match_result.add_not_found(assertion.clone(), reason, near_misses);
             }
             AssertionOutcome::TypeMismatch { expected, actual } => {
                 match_result.add_type_mismatch(assertion.clone(), span_text, expected, actual);
@@ -409,13 +435,49 @@ pub fn check_fixture_assertions(fixture: &NlpFixture, result: &PipelineResult) -
     match_result
 }
 
+/// Check a `requires:` assertion against the fixture's section structure.
+///
+/// The rendered structure tree stands in for the span text, so failures show
+/// what `DocumentStructureBuilder` actually produced.
+fn check_structure_assertion(
+    assertion: &crate::fixture::Assertion,
+    result: &PipelineResult,
+    match_result: &mut MatchResult,
+) {
+    let Some(structure) = &result.structure else {
+        match_result.add_not_found(
+            assertion.clone(),
+            "No document structure was built for this fixture".to_string(),
+            Vec::new(),
+        );
+        return;
+    };
+    let tree = render_structure(structure);
+
+    if assertion.span_type != "Section" {
+        match_result.add_type_mismatch(
+            assertion.clone(),
+            tree,
+            "Section".to_string(),
+            assertion.span_type.clone(),
+        );
+        return;
+    }
+
+    match check_section(structure, &format_body(&assertion.body)) {
+        Ok(()) => match_result.add_passed(assertion.clone(), tree),
+        Err(mismatch) => match_result.add_failed(assertion.clone(), tree, mismatch),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::loader::load_all_fixtures;
+    use crate::matcher::NearMissKind;
     use crate::parse_fixture;
     use layered_clauses::Clause;
-    use layered_contracts::{DefinitionType, ObligationType, ObligorReference};
+    use layered_contracts::{DefinitionType, ObligationNature, ObligationType, ObligorReference};
     use std::path::Path;
 
     #[test]
@@ -539,6 +601,12 @@ If payment is late, then the Company shall charge a fee.
                 obligation_type: ObligationType::Duty,
                 action: "pay".to_string(),
                 conditions: vec![],
+                confidence_breakdown: Default::default(),
+                qualifiers: Vec::new(),
+                object: None,
+                performer: None,
+                obligation_nature: ObligationNature::Performance,
+                discretion: None,
             },
         ));
 
@@ -569,6 +637,12 @@ If payment is late, then the Company shall charge a fee.
                 obligation_type: ObligationType::Permission,
                 action: "enter".to_string(),
                 conditions: vec![],
+                confidence_breakdown: Default::default(),
+                qualifiers: Vec::new(),
+                object: None,
+                performer: None,
+                obligation_nature: ObligationNature::Performance,
+                discretion: None,
             },
         ));
 
@@ -577,6 +651,64 @@ If payment is late, then the Company shall charge a fee.
         assert_eq!(match_result.failed.len(), 1);
     }
 
+    #[test]
+    fn test_check_fixture_reports_near_misses() {
+        let fixture = parse_fixture(
+            r#"
+# Test
+«1:The Tenant» shall pay «2:rent».
+> [1]: DefinedTerm(term_name=Tenant)
+> [2]: Obligation(modal=shall)
+"#,
+        )
+        .unwrap();
+
+        let mut result = PipelineResult::new();
+        result.defined_terms.push((
+            0,
+            "Tenant".to_string(),
+            Scored::rule_based(
+                DefinedTerm::new("Tenant".to_string(), DefinitionType::QuotedMeans),
+                0.9,
+                "test",
+            ),
+        ));
+        result.term_references.push((
+            0,
+            "rent".to_string(),
+            Scored::rule_based(
+                TermReference {
+                    term_name: "Rent".to_string(),
+                    definition_type: DefinitionType::QuotedMeans,
+                    article: None,
+                    qualifier: None,
+                },
+                0.9,
+                "test",
+            ),
+        ));
+
+        let match_result = check_fixture_assertions(&fixture, &result);
+        let near_misses: Vec<_> = match_result
+            .failed
+            .iter()
+            .map(|failure| match &failure.outcome {
+                AssertionOutcome::NotFound { near_misses, .. } => near_misses
+                    .iter()
+                    .map(|n| (n.span_type, n.span_text.as_str(), n.kind))
+                    .collect::<Vec<_>>(),
+                other => panic!("expected NotFound, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            near_misses,
+            vec![
+                vec![("DefinedTerm", "Tenant", NearMissKind::WrongSpan)],
+                vec![("TermReference", "rent", NearMissKind::WrongType)],
+            ]
+        );
+    }
+
     /// Integration test: load fixtures, run pipeline, check assertions.
     ///
     /// This validates the full pipeline works end-to-end:
@@ -639,7 +771,7 @@ If payment is late, then the Company shall charge a fee.
                                 f.span_text, mismatch
                             );
                         }
-                        AssertionOutcome::NotFound { reason } => {
+                        AssertionOutcome::NotFound { reason, .. } => {
                             eprintln!(
                                 "       - NotFound: {:?} | {}",
                                 f.assertion.target, reason
@@ -27,7 +27,8 @@ pub fn create_line_from_string<T: AsRef<str>>(input_string: T) -> LLLine {
 
 pub use ll_line::{
     x, AssociatedSpan, Association, FinishWith, LLAssignmentBuilder, LLCursorAssignment, LLLine,
-    LLLineDisplay, LLSelection, LToken, Resolver, SpanRef, TextTag,
+    LLLineDisplay, LLLineFind, LLSelection, LToken, Resolver, ResolverTrace, SpanRef, TextTag,
+    TraceEvent, TraceOutcome,
 };
 pub use resolvers::TextMatchAssignResolver;
 pub use type_bucket::AnyAttribute;
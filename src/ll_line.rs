@@ -2,18 +2,21 @@ mod association;
 mod display;
 mod finish_with;
 mod ll_selection;
+mod trace;
 pub mod x;
 
 pub use association::{AssociatedSpan, Association, SpanRef};
 
 pub use finish_with::FinishWith;
 pub use ll_selection::{LLAssignmentBuilder, LLSelection};
+pub use trace::{ResolverTrace, TraceEvent, TraceOutcome};
 
 use crate::type_bucket::{self, AnyAttribute};
 use crate::type_id_to_many::TypeIdToMany;
 pub use display::LLLineDisplay;
 use std::fmt::{self, Write};
 use std::iter::FromIterator;
+use std::any::TypeId;
 use std::{collections::HashMap, rc::Rc};
 use x::{XForwards, XMatch};
 
@@ -96,6 +99,8 @@ struct LLLineAttrs {
 pub struct LLLineFind<'l, Found> {
     start_pos_at: usize,
     end_pos_at: usize,
+    start_idx: usize,
+    end_idx: usize,
     found: Found,
     _phantom: std::marker::PhantomData<&'l ()>,
 }
@@ -111,9 +116,15 @@ impl<'l, Found: fmt::Debug> fmt::Debug for LLLineFind<'l, Found> {
 }
 
 impl<'l, Found> LLLineFind<'l, Found> {
+    /// Byte positions of the match in the line's text (start inclusive, end
+    /// exclusive).
     pub fn range(&self) -> PositionRange {
         (self.start_pos_at, self.end_pos_at)
     }
+    /// Indices of the first and last tokens of the match (both inclusive).
+    pub fn token_range(&self) -> LRange {
+        (self.start_idx, self.end_idx)
+    }
     pub fn attr(&self) -> &Found {
         &self.found
     }
@@ -160,9 +171,31 @@ impl LLLine {
         LLLine { ll_tokens, attrs }
     }
 
-    pub fn run<R>(mut self, recognizer: &R) -> Self
+    pub fn run<R>(self, recognizer: &R) -> Self
+    where
+        R: Resolver,
+    {
+        self.run_with(|selection| recognizer.go(selection))
+    }
+
+    /// Like [`LLLine::run`], but records the resolver's decisions in `trace`.
+    ///
+    /// See [`Resolver::go_traced`]. Events are attributed to the resolver's
+    /// type name, so one trace can follow several resolvers in turn.
+    pub fn run_traced<R>(self, recognizer: &R, trace: &mut ResolverTrace) -> Self
     where
         R: Resolver,
+    {
+        trace.set_resolver(std::any::type_name::<R>());
+        self.run_with(|selection| recognizer.go_traced(selection, trace))
+    }
+
+    fn run_with<Attr>(
+        mut self,
+        go: impl FnOnce(LLSelection) -> Vec<LLCursorAssignment<Attr>>,
+    ) -> Self
+    where
+        Attr: std::fmt::Debug + 'static + Send + Sync,
     {
         // Empty line can't recognize anything since they can't create `LLSelection`
         if self.ll_tokens.is_empty() {
@@ -171,7 +204,7 @@ impl LLLine {
 
         let ll_line = Rc::new(self);
 
-        let assignments = recognizer.go(LLSelection {
+        let assignments = go(LLSelection {
             ll_line: ll_line.clone(),
             start_idx: 0,
             end_idx: ll_line.ll_tokens().len() - 1,
@@ -195,6 +228,61 @@ impl LLLine {
 
         self
     }
+
+    /// Like [`LLLine::run`], but catches a panic raised by the resolver.
+    ///
+    /// Resolver output is only stored after `go` returns, so on panic the line
+    /// is handed back unchanged along with the panic message.
+    pub fn try_run<R>(self, recognizer: &R) -> (Self, Result<(), String>)
+    where
+        R: Resolver,
+    {
+        if self.ll_tokens.is_empty() {
+            return (self, Ok(()));
+        }
+
+        let ll_line = Rc::new(self);
+        let selection = LLSelection {
+            ll_line: ll_line.clone(),
+            start_idx: 0,
+            end_idx: ll_line.ll_tokens().len() - 1,
+        };
+
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            recognizer.go(selection)
+        }));
+
+        let mut line = Rc::try_unwrap(ll_line)
+            .map_err(drop)
+            .expect("there is no other Rc currently");
+
+        match outcome {
+            Ok(assignments) => {
+                for LLCursorAssignment {
+                    start_idx,
+                    end_idx,
+                    value,
+                    associations,
+                } in assignments
+                {
+                    line.attrs
+                        .insert_with_associations((start_idx, end_idx), value, associations);
+                }
+                (line, Ok(()))
+            }
+            Err(payload) => {
+                let message = if let Some(msg) = payload.downcast_ref::<&str>() {
+                    msg.to_string()
+                } else if let Some(msg) = payload.downcast_ref::<String>() {
+                    msg.clone()
+                } else {
+                    "resolver panicked".to_string()
+                };
+                (line, Err(message))
+            }
+        }
+    }
+
     pub(crate) fn add_any_attrs(
         &mut self,
         start_idx: usize,
@@ -223,6 +311,71 @@ impl LLLine {
         }
     }
 
+    /// Remove attributes of type `T` for which `keep` returns false.
+    ///
+    /// `keep` receives the token range of each attribute. Ranges left without
+    /// any `T` value are unregistered, so matchers no longer see them.
+    pub fn retain_attrs<T: 'static>(&mut self, mut keep: impl FnMut(LRange, &T) -> bool) {
+        let ranges = self.attrs.ranges.get::<T>().to_vec();
+        for range in ranges {
+            let remaining = match self.attrs.values.get_mut(&range) {
+                Some(bucket) => bucket.retain::<T>(|value| keep(range, value)),
+                None => 0,
+            };
+            if remaining == 0 {
+                self.attrs.ranges.remove::<T>(&range);
+                self.attrs.starts_at[range.0].remove::<T>(&range);
+                self.attrs.ends_at[range.1].remove::<T>(&range);
+            }
+        }
+    }
+
+    /// A stable hash of every attribute on the line.
+    ///
+    /// Covers each attribute's token range, type, `Debug` output and
+    /// associations, including the `TextTag`s assigned at tokenization, so two
+    /// lines hash equal when they carry the same annotations. Ranges are
+    /// visited in token order and values by type name, then insertion order,
+    /// and the hash function is fixed, so the result is the same across runs
+    /// and independent of where values live in memory.
+    pub fn content_hash(&self) -> u64 {
+        let mut ranges: Vec<&LRange> = self.attrs.values.keys().collect();
+        ranges.sort();
+
+        let mut hasher = StableHasher::default();
+        for range in ranges {
+            for (type_name, value, associations) in self.attrs.values[range].debug_all() {
+                hasher.write_u64(range.0 as u64);
+                hasher.write_u64(range.1 as u64);
+                hasher.write_str(type_name);
+                hasher.write_str(&value);
+                for association in associations {
+                    hasher.write_str(&format!("{:?}", association));
+                }
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Every attribute type on the line, whatever its type.
+    ///
+    /// Returns (range, type id, type name, number of values) tuples ordered by
+    /// range, then type name. Includes the `TextTag`s and `char`s assigned at
+    /// tokenization.
+    pub fn attr_types(&self) -> Vec<(LRange, TypeId, &'static str, usize)> {
+        let mut ranges: Vec<&LRange> = self.attrs.values.keys().collect();
+        ranges.sort();
+        ranges
+            .into_iter()
+            .flat_map(|range| {
+                self.attrs.values[range]
+                    .types()
+                    .into_iter()
+                    .map(move |(type_id, type_name, count)| (*range, type_id, type_name, count))
+            })
+            .collect()
+    }
+
     /// Get a reference to the ll line's ll tokens.
     pub fn ll_tokens(&self) -> &[LLToken] {
         &self.ll_tokens
@@ -241,6 +394,8 @@ impl LLLine {
                     .map(move |(out, next_idx)| LLLineFind {
                         start_pos_at: self.pos_start_at(i),
                         end_pos_at: self.pos_end_at(next_idx.0),
+                        start_idx: i,
+                        end_idx: next_idx.0,
                         found: out,
                         _phantom: std::marker::PhantomData,
                     })
@@ -329,6 +484,39 @@ impl LLLine {
     }
 }
 
+/// 64-bit FNV-1a, used by [`LLLine::content_hash`] because `std`'s default
+/// hasher is not guaranteed to stay the same between Rust releases.
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Length-prefixed, so adjacent strings cannot run together.
+    fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write(value.as_bytes());
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 impl LLLineAttrs {
     fn insert<T: 'static + std::fmt::Debug + Send + Sync>(&mut self, range: LRange, value: T) {
         self.insert_with_associations(range, value, Vec::new());
@@ -383,4 +571,23 @@ pub trait Resolver {
     type Attr: std::fmt::Debug + 'static + Send + Sync;
     /// How to perform the assignments.
     fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>>;
+
+    /// Like [`Resolver::go`], recording decisions in `trace`.
+    ///
+    /// The default runs `go` and records every assignment as accepted.
+    /// Resolvers with interesting rejection paths override this with their
+    /// real logic and implement `go` as
+    /// `self.go_traced(selection, &mut ResolverTrace::disabled())`.
+    fn go_traced(
+        &self,
+        selection: LLSelection,
+        trace: &mut ResolverTrace,
+    ) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let assignments = self.go(selection);
+        for assignment in &assignments {
+            let span = SpanRef::new(assignment.start_idx, assignment.end_idx);
+            trace.record(span, TraceOutcome::Accepted, "assigned");
+        }
+        assignments
+    }
 }
@@ -0,0 +1,184 @@
+//! Structured decision logs for resolvers.
+//!
+//! A [`ResolverTrace`] collects [`TraceEvent`]s emitted by
+//! [`Resolver::go_traced`](crate::Resolver::go_traced): each attempted match
+//! and whether it was accepted or rejected, with a reason. Run a resolver with
+//! [`LLLine::run_traced`](crate::LLLine::run_traced) to fill a trace.
+
+use std::fmt;
+
+use super::{LLSelection, SpanRef};
+
+/// Whether an attempted match produced an assignment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceOutcome {
+    /// The match produced an assignment
+    Accepted,
+    /// The match was considered and discarded
+    Rejected,
+}
+
+/// A single decision recorded by a resolver.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEvent {
+    /// Type name of the resolver that recorded the event
+    pub resolver: &'static str,
+    /// Token range the decision was about
+    pub span: SpanRef,
+    /// What the resolver decided
+    pub outcome: TraceOutcome,
+    /// Why, in a few words ("obligor resolution failed")
+    pub reason: String,
+}
+
+/// An inspectable log of resolver decisions.
+///
+/// A disabled trace ([`ResolverTrace::disabled`]) records nothing, so
+/// resolvers can emit events unconditionally. Guard expensive reasons
+/// (e.g. `format!`) with [`ResolverTrace::is_enabled`].
+///
+/// # Example
+///
+/// ```
+/// use layered_nlp::{create_line_from_string, x, LLCursorAssignment, LLSelection};
+/// use layered_nlp::{Resolver, ResolverTrace, TraceOutcome};
+///
+/// struct Yes;
+///
+/// impl Resolver for Yes {
+///     type Attr = ();
+///
+///     fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<()>> {
+///         self.go_traced(selection, &mut ResolverTrace::disabled())
+///     }
+///
+///     fn go_traced(
+///         &self,
+///         selection: LLSelection,
+///         trace: &mut ResolverTrace,
+///     ) -> Vec<LLCursorAssignment<()>> {
+///         let mut found = Vec::new();
+///         for (sel, text) in selection.find_by(&x::token_text()) {
+///             if text == "yes" {
+///                 trace.accept(&sel, "keyword matched");
+///                 found.push(sel.finish_with_attr(()));
+///             } else {
+///                 trace.reject(&sel, "not a keyword");
+///             }
+///         }
+///         found
+///     }
+/// }
+///
+/// let mut trace = ResolverTrace::new();
+/// create_line_from_string("yes no").run_traced(&Yes, &mut trace);
+/// assert_eq!(trace.accepted().count(), 1);
+/// assert_eq!(trace.events()[1].outcome, TraceOutcome::Rejected);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResolverTrace {
+    enabled: bool,
+    resolver: &'static str,
+    events: Vec<TraceEvent>,
+}
+
+impl Default for ResolverTrace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResolverTrace {
+    /// Create a trace that records events.
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            resolver: "",
+            events: Vec::new(),
+        }
+    }
+
+    /// Create a trace that discards every event.
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::new()
+        }
+    }
+
+    /// Whether events are being recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Attribute subsequent events to `resolver` (a type name).
+    ///
+    /// [`LLLine::run_traced`](crate::LLLine::run_traced) calls this before
+    /// running each resolver.
+    pub fn set_resolver(&mut self, resolver: &'static str) {
+        self.resolver = resolver;
+    }
+
+    /// Record an accepted match over the selection.
+    pub fn accept(&mut self, selection: &LLSelection, reason: impl Into<String>) {
+        self.record(selection.span_ref(), TraceOutcome::Accepted, reason);
+    }
+
+    /// Record a rejected match over the selection.
+    pub fn reject(&mut self, selection: &LLSelection, reason: impl Into<String>) {
+        self.record(selection.span_ref(), TraceOutcome::Rejected, reason);
+    }
+
+    /// Record a decision over a token range.
+    pub fn record(&mut self, span: SpanRef, outcome: TraceOutcome, reason: impl Into<String>) {
+        if self.enabled {
+            self.events.push(TraceEvent {
+                resolver: self.resolver,
+                span,
+                outcome,
+                reason: reason.into(),
+            });
+        }
+    }
+
+    /// All recorded events, in the order they were emitted.
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    /// Events for matches that produced an assignment.
+    pub fn accepted(&self) -> impl Iterator<Item = &TraceEvent> {
+        self.events.iter().filter(|e| e.outcome == TraceOutcome::Accepted)
+    }
+
+    /// Events for matches that were discarded.
+    pub fn rejected(&self) -> impl Iterator<Item = &TraceEvent> {
+        self.events.iter().filter(|e| e.outcome == TraceOutcome::Rejected)
+    }
+
+    /// Events whose span overlaps the token range `start..=end`.
+    pub fn at(&self, start: usize, end: usize) -> impl Iterator<Item = &TraceEvent> {
+        self.events
+            .iter()
+            .filter(move |e| e.span.start_idx <= end && start <= e.span.end_idx)
+    }
+}
+
+impl fmt::Display for ResolverTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for event in &self.events {
+            // "layered_contracts::obligation::ObligationPhraseResolver" -> last segment
+            let resolver = event.resolver.rsplit("::").next().unwrap_or(event.resolver);
+            let outcome = match event.outcome {
+                TraceOutcome::Accepted => "accept",
+                TraceOutcome::Rejected => "reject",
+            };
+            writeln!(
+                f,
+                "{} {}..={} {}: {}",
+                resolver, event.span.start_idx, event.span.end_idx, outcome, event.reason
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -42,3 +42,27 @@ where
         self.0(start)
     }
 }
+
+#[test]
+fn content_hash_tracks_annotations() {
+    use crate::ll_line::x;
+
+    let mark = |word: &'static str| {
+        move |sel: LLSelection| {
+            sel.find_by(&x::token_text())
+                .into_iter()
+                .filter(|(_, text)| *text == word)
+                .map(|(sel, _)| sel.finish_with_attr(String::from("noun")))
+                .collect()
+        }
+    };
+
+    let plain = test_line("the cat sat");
+    let cat = test_line("the cat sat").run(&TestResolver(mark("cat")));
+    let cat_again = test_line("the cat sat").run(&TestResolver(mark("cat")));
+    let sat = test_line("the cat sat").run(&TestResolver(mark("sat")));
+
+    assert_eq!(cat.content_hash(), cat_again.content_hash());
+    assert_ne!(cat.content_hash(), plain.content_hash());
+    assert_ne!(cat.content_hash(), sat.content_hash());
+}
@@ -150,6 +150,9 @@ trait Bucket {
     where
         Self: 'static;
     fn insert_any(&mut self, val: Box<dyn Any>);
+    fn type_name(&self) -> &'static str;
+    fn len(&self) -> usize;
+    fn debug_values(&self) -> Vec<String>;
     fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.pad("Bucket")
     }
@@ -174,6 +177,18 @@ impl<T: 'static + Debug> Bucket for Vec<T> {
         self.push(*val.downcast().expect("type doesn't match"));
     }
 
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn debug_values(&self) -> Vec<String> {
+        self.iter().map(|item| format!("{:?}", item)).collect()
+    }
+
     fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Debug::fmt(self, f)
     }
@@ -256,6 +271,30 @@ impl TypeBucket {
             .unwrap_or_else(|| &[])
     }
 
+    /// Retain only the values of type `T` for which `keep` returns true.
+    ///
+    /// Associations are removed together with their values. Returns the
+    /// number of `T` values left in the bucket.
+    pub fn retain<T: 'static>(&mut self, mut keep: impl FnMut(&T) -> bool) -> usize {
+        let type_id = TypeId::of::<T>();
+        let values = match self.map.get_mut(&type_id) {
+            Some(bucket) => bucket.as_any_mut().downcast_mut::<Vec<T>>().unwrap(),
+            None => return 0,
+        };
+
+        let kept: Vec<bool> = values.iter().map(&mut keep).collect();
+        let mut flags = kept.iter();
+        values.retain(|_| *flags.next().unwrap());
+        let remaining = values.len();
+
+        if let Some(associations) = self.associations.get_mut(&type_id) {
+            let mut flags = kept.iter();
+            associations.retain(|_| *flags.next().unwrap());
+        }
+
+        remaining
+    }
+
     /// Get values paired with their associations for graph traversal.
     ///
     /// Returns a vector of (value_ref, associations_slice) tuples, allowing
@@ -326,6 +365,45 @@ impl TypeBucket {
             .collect()
     }
 
+    /// Get debug strings of every value, whatever its type.
+    ///
+    /// Returns (type name, debug string, associations) tuples ordered by type
+    /// name, then by insertion order, so the result does not depend on how
+    /// the underlying map happens to iterate.
+    pub fn debug_all(&self) -> Vec<(&'static str, String, &[AssociatedSpan])> {
+        let mut buckets: Vec<_> = self.map.iter().collect();
+        buckets.sort_by_key(|(_, bucket)| bucket.type_name());
+
+        buckets
+            .into_iter()
+            .flat_map(|(type_id, bucket)| {
+                let associations = self
+                    .associations
+                    .get(type_id)
+                    .map(|a| a.as_slice())
+                    .unwrap_or(&[]);
+                bucket
+                    .debug_values()
+                    .into_iter()
+                    .zip(associations.iter())
+                    .map(move |(value, assocs)| (bucket.type_name(), value, assocs.as_slice()))
+            })
+            .collect()
+    }
+
+    /// Every type with values in the bucket, as (type id, type name, number
+    /// of values), ordered by type name.
+    pub fn types(&self) -> Vec<(TypeId, &'static str, usize)> {
+        let mut types: Vec<_> = self
+            .map
+            .iter()
+            .map(|(type_id, bucket)| (*type_id, bucket.type_name(), bucket.len()))
+            .filter(|(_, _, len)| *len > 0)
+            .collect();
+        types.sort_by_key(|(_, type_name, _)| *type_name);
+        types
+    }
+
     // /// Get a mutable reference to a value previously inserted on this `TypeBucket`.
     // pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
     //     self.map
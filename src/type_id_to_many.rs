@@ -26,6 +26,14 @@ impl<Value> TypeIdToMany<Value> {
             entry.push(value_to_add);
         }
     }
+    pub fn remove<Type: 'static>(&mut self, value_to_remove: &Value)
+    where
+        Value: PartialEq,
+    {
+        if let Some(entry) = self.map.get_mut(&TypeId::of::<Type>()) {
+            entry.retain(|value| value != value_to_remove);
+        }
+    }
     pub fn get<Type: 'static>(&self) -> &[Value] {
         self.map
             .get(&TypeId::of::<Type>())